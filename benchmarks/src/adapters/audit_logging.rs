@@ -1,6 +1,8 @@
 use crate::adapters::BenchTarget;
+use crate::config::BenchConfig;
+use crate::http::{with_http_metrics, HttpEndpoint};
 use crate::result::BenchmarkResult;
-use std::time::Instant;
+use crate::summary::{measure, merge_metrics, summarize_durations};
 
 /// Benchmark adapter for audit logging operations
 pub struct AuditLoggingBench;
@@ -10,15 +12,12 @@ impl BenchTarget for AuditLoggingBench {
         "audit_logging".to_string()
     }
 
-    fn run(&self) -> BenchmarkResult {
-        let start = Instant::now();
-
-        // Simulate audit logging operations
-        let iterations = 1000;
+    fn run(&self, config: &BenchConfig) -> BenchmarkResult {
         let mut total_logs_created = 0;
         let mut total_checksums_calculated = 0;
 
-        for i in 0..iterations {
+        // Simulate audit logging operations
+        let samples = measure(config, |i| {
             // Simulate creating audit logs
             let action = match i % 5 {
                 0 => "create",
@@ -36,25 +35,27 @@ impl BenchTarget for AuditLoggingBench {
                 calculate_checksum(action);
                 total_checksums_calculated += 1;
             }
-        }
+        });
 
-        let duration = start.elapsed();
-        let avg_latency_ms = duration.as_millis() as f64 / iterations as f64;
+        let summary = summarize_durations(&samples, config.trim_fraction);
 
-        BenchmarkResult::new(
-            self.id(),
+        let metrics = merge_metrics(
+            summary.to_metrics(config),
             serde_json::json!({
-                "iterations": iterations,
-                "total_duration_ms": duration.as_millis(),
-                "avg_latency_ms": avg_latency_ms,
                 "total_logs_created": total_logs_created,
                 "total_checksums_calculated": total_checksums_calculated,
-                "throughput_ops_per_sec": (iterations as f64 / duration.as_secs_f64()),
             }),
-        )
+        );
+
+        BenchmarkResult::new(self.id(), with_http_metrics(metrics, config, &http_endpoint()))
     }
 }
 
+/// Endpoint timed when a base URL is configured
+fn http_endpoint() -> HttpEndpoint {
+    HttpEndpoint::get("/api/v1/audit/logs")
+}
+
 /// Simulate creating an audit log
 fn create_audit_log(action: &str) {
     // Simulate log creation with minimal overhead
@@ -96,7 +97,7 @@ mod tests {
         let bench = AuditLoggingBench;
         assert_eq!(bench.id(), "audit_logging");
 
-        let result = bench.run();
+        let result = bench.run(&BenchConfig::default());
         assert_eq!(result.target_id, "audit_logging");
 
         // Verify metrics exist
@@ -1,6 +1,8 @@
 use crate::adapters::BenchTarget;
+use crate::config::BenchConfig;
+use crate::http::{with_http_metrics, HttpEndpoint};
 use crate::result::BenchmarkResult;
-use std::time::Instant;
+use crate::summary::{measure, merge_metrics, summarize_durations};
 
 /// Benchmark adapter for cost calculation operations
 pub struct CostCalculationBench;
@@ -10,15 +12,12 @@ impl BenchTarget for CostCalculationBench {
         "cost_calculation".to_string()
     }
 
-    fn run(&self) -> BenchmarkResult {
-        let start = Instant::now();
-
-        // Simulate cost calculation operations
-        let iterations = 1000;
+    fn run(&self, config: &BenchConfig) -> BenchmarkResult {
         let mut total_calculations = 0;
         let mut total_cost_computed = 0.0;
 
-        for i in 0..iterations {
+        // Simulate cost calculation operations
+        let samples = measure(config, |i| {
             // Simulate cost calculations for different providers/models
             let (provider, model) = match i % 6 {
                 0 => ("openai", "gpt-4"),
@@ -35,26 +34,41 @@ impl BenchTarget for CostCalculationBench {
             let cost = calculate_cost(provider, model, tokens_in, tokens_out);
             total_cost_computed += cost;
             total_calculations += 1;
-        }
+        });
 
-        let duration = start.elapsed();
-        let avg_latency_ms = duration.as_millis() as f64 / iterations as f64;
+        let summary = summarize_durations(&samples, config.trim_fraction);
+        let avg_cost_per_calculation = if total_calculations > 0 {
+            total_cost_computed / total_calculations as f64
+        } else {
+            0.0
+        };
 
-        BenchmarkResult::new(
-            self.id(),
+        let metrics = merge_metrics(
+            summary.to_metrics(config),
             serde_json::json!({
-                "iterations": iterations,
-                "total_duration_ms": duration.as_millis(),
-                "avg_latency_ms": avg_latency_ms,
                 "total_calculations": total_calculations,
                 "total_cost_computed": total_cost_computed,
-                "avg_cost_per_calculation": total_cost_computed / total_calculations as f64,
-                "throughput_ops_per_sec": (iterations as f64 / duration.as_secs_f64()),
+                "avg_cost_per_calculation": avg_cost_per_calculation,
             }),
-        )
+        );
+
+        BenchmarkResult::new(self.id(), with_http_metrics(metrics, config, &http_endpoint()))
     }
 }
 
+/// Endpoint timed when a base URL is configured
+fn http_endpoint() -> HttpEndpoint {
+    HttpEndpoint::post(
+        "/api/v1/costs/calculate",
+        serde_json::json!({
+            "provider": "openai",
+            "model": "gpt-4",
+            "tokens_in": 1000,
+            "tokens_out": 500,
+        }),
+    )
+}
+
 /// Simulate cost calculation based on provider, model, and token usage
 fn calculate_cost(provider: &str, model: &str, tokens_in: i64, tokens_out: i64) -> f64 {
     let pricing = get_model_pricing(provider, model);
@@ -115,7 +129,7 @@ mod tests {
         let bench = CostCalculationBench;
         assert_eq!(bench.id(), "cost_calculation");
 
-        let result = bench.run();
+        let result = bench.run(&BenchConfig::default());
         assert_eq!(result.target_id, "cost_calculation");
 
         // Verify metrics exist
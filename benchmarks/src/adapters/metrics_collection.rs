@@ -1,6 +1,8 @@
 use crate::adapters::BenchTarget;
+use crate::config::BenchConfig;
+use crate::http::{with_http_metrics, HttpEndpoint};
 use crate::result::BenchmarkResult;
-use std::time::Instant;
+use crate::summary::{measure, merge_metrics, summarize_durations};
 
 /// Benchmark adapter for metrics collection operations
 pub struct MetricsCollectionBench;
@@ -10,15 +12,12 @@ impl BenchTarget for MetricsCollectionBench {
         "metrics_collection".to_string()
     }
 
-    fn run(&self) -> BenchmarkResult {
-        let start = Instant::now();
-
-        // Simulate metrics collection operations
-        let iterations = 1000;
+    fn run(&self, config: &BenchConfig) -> BenchmarkResult {
         let mut total_metrics_ingested = 0;
         let mut total_aggregations = 0;
 
-        for i in 0..iterations {
+        // Simulate metrics collection operations
+        let samples = measure(config, |i| {
             // Simulate ingesting metrics
             let provider = match i % 2 {
                 0 => "openai",
@@ -33,25 +32,27 @@ impl BenchTarget for MetricsCollectionBench {
                 aggregate_metrics(provider);
                 total_aggregations += 1;
             }
-        }
+        });
 
-        let duration = start.elapsed();
-        let avg_latency_ms = duration.as_millis() as f64 / iterations as f64;
+        let summary = summarize_durations(&samples, config.trim_fraction);
 
-        BenchmarkResult::new(
-            self.id(),
+        let metrics = merge_metrics(
+            summary.to_metrics(config),
             serde_json::json!({
-                "iterations": iterations,
-                "total_duration_ms": duration.as_millis(),
-                "avg_latency_ms": avg_latency_ms,
                 "total_metrics_ingested": total_metrics_ingested,
                 "total_aggregations": total_aggregations,
-                "throughput_ops_per_sec": (iterations as f64 / duration.as_secs_f64()),
             }),
-        )
+        );
+
+        BenchmarkResult::new(self.id(), with_http_metrics(metrics, config, &http_endpoint()))
     }
 }
 
+/// Endpoint timed when a base URL is configured
+fn http_endpoint() -> HttpEndpoint {
+    HttpEndpoint::get("/api/v1/metrics/stats/usage")
+}
+
 /// Simulate ingesting a metric
 fn ingest_metric(provider: &str, iteration: usize) {
     let _metric = Metric {
@@ -109,7 +110,7 @@ mod tests {
         let bench = MetricsCollectionBench;
         assert_eq!(bench.id(), "metrics_collection");
 
-        let result = bench.run();
+        let result = bench.run(&BenchConfig::default());
         assert_eq!(result.target_id, "metrics_collection");
 
         // Verify metrics exist
@@ -1,3 +1,4 @@
+use crate::config::BenchConfig;
 use crate::result::BenchmarkResult;
 
 // Re-export adapters
@@ -12,7 +13,10 @@ pub trait BenchTarget {
     fn id(&self) -> String;
 
     /// Executes the benchmark and returns the result
-    fn run(&self) -> BenchmarkResult;
+    ///
+    /// Implementations run `config.warmup_iterations` discarded iterations
+    /// before timing `config.iterations` measured ones.
+    fn run(&self, config: &BenchConfig) -> BenchmarkResult;
 }
 
 /// Registry of all available benchmark targets
@@ -1,6 +1,8 @@
 use crate::adapters::BenchTarget;
+use crate::config::BenchConfig;
+use crate::http::{with_http_metrics, HttpEndpoint};
 use crate::result::BenchmarkResult;
-use std::time::Instant;
+use crate::summary::{measure, merge_metrics, summarize_durations};
 
 /// Benchmark adapter for policy evaluation operations
 pub struct PolicyEvaluationBench;
@@ -10,14 +12,11 @@ impl BenchTarget for PolicyEvaluationBench {
         "policy_evaluation".to_string()
     }
 
-    fn run(&self) -> BenchmarkResult {
-        let start = Instant::now();
-
-        // Simulate policy evaluation operations
-        let iterations = 1000;
+    fn run(&self, config: &BenchConfig) -> BenchmarkResult {
         let mut total_rules_evaluated = 0;
 
-        for i in 0..iterations {
+        // Simulate policy evaluation operations
+        let samples = measure(config, |i| {
             // Simulate evaluating different policy types
             let policy_type = match i % 4 {
                 0 => "cost",
@@ -28,24 +27,26 @@ impl BenchTarget for PolicyEvaluationBench {
 
             // Simulate rule evaluation (counting rules)
             total_rules_evaluated += evaluate_policy_rules(policy_type);
-        }
+        });
 
-        let duration = start.elapsed();
-        let avg_latency_ms = duration.as_millis() as f64 / iterations as f64;
+        let summary = summarize_durations(&samples, config.trim_fraction);
 
-        BenchmarkResult::new(
-            self.id(),
+        let metrics = merge_metrics(
+            summary.to_metrics(config),
             serde_json::json!({
-                "iterations": iterations,
-                "total_duration_ms": duration.as_millis(),
-                "avg_latency_ms": avg_latency_ms,
                 "total_rules_evaluated": total_rules_evaluated,
-                "throughput_ops_per_sec": (iterations as f64 / duration.as_secs_f64()),
             }),
-        )
+        );
+
+        BenchmarkResult::new(self.id(), with_http_metrics(metrics, config, &http_endpoint()))
     }
 }
 
+/// Endpoint timed when a base URL is configured
+fn http_endpoint() -> HttpEndpoint {
+    HttpEndpoint::get("/api/v1/policies")
+}
+
 /// Simulate policy rule evaluation
 fn evaluate_policy_rules(policy_type: &str) -> usize {
     match policy_type {
@@ -128,17 +129,37 @@ fn check_pii_detection() -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use llm_governance_common::testing::{MockResponse, MockServer};
 
     #[test]
     fn test_policy_evaluation_bench() {
         let bench = PolicyEvaluationBench;
         assert_eq!(bench.id(), "policy_evaluation");
 
-        let result = bench.run();
+        let result = bench.run(&BenchConfig::default());
         assert_eq!(result.target_id, "policy_evaluation");
 
         // Verify metrics exist
         assert!(result.metrics.get("iterations").is_some());
         assert!(result.metrics.get("avg_latency_ms").is_some());
     }
+
+    #[test]
+    fn test_reports_http_metrics_against_live_endpoint() {
+        let server = MockServer::start_blocking(vec![MockResponse::new(200).with_body("{}")]);
+        let config = BenchConfig::default()
+            .with_warmup_iterations(2)
+            .with_iterations(5)
+            .with_base_url(server.url().to_string());
+
+        let result = PolicyEvaluationBench.run(&config);
+
+        // In-process metrics are still reported alongside the HTTP ones
+        assert_eq!(result.metrics["iterations"], 5);
+        let http = &result.metrics["http"];
+        assert_eq!(http["iterations"], 5);
+        assert_eq!(http["http_errors"], 0);
+        assert!(http["url"].as_str().unwrap().ends_with("/api/v1/policies"));
+        assert!(http["p50_latency_ms"].as_f64().unwrap() > 0.0);
+    }
 }
@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+
+/// Default number of discarded warmup iterations before measurement
+pub const DEFAULT_WARMUP_ITERATIONS: usize = 10;
+
+/// Default number of measured iterations
+pub const DEFAULT_ITERATIONS: usize = 1000;
+
+/// Default fraction of samples trimmed from each end (5%)
+pub const DEFAULT_TRIM_FRACTION: f64 = 0.05;
+
+/// Configuration passed into every `BenchTarget::run`
+///
+/// Defaults: 10 warmup iterations, 1000 measured iterations, 5% trim, no
+/// HTTP run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchConfig {
+    /// Iterations executed before measurement starts; their timings are discarded
+    pub warmup_iterations: usize,
+
+    /// Iterations that are timed and summarized
+    pub iterations: usize,
+
+    /// Fraction (0.0..0.5) of samples dropped from both the top and the bottom
+    /// before computing the trimmed mean
+    pub trim_fraction: f64,
+
+    /// Base URL of a running deployment (e.g. the API gateway); when set,
+    /// each adapter also times its endpoint over HTTP
+    #[serde(default)]
+    pub base_url: Option<String>,
+
+    /// Bearer token sent with HTTP requests
+    #[serde(default, skip_serializing)]
+    pub auth_token: Option<String>,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            warmup_iterations: DEFAULT_WARMUP_ITERATIONS,
+            iterations: DEFAULT_ITERATIONS,
+            trim_fraction: DEFAULT_TRIM_FRACTION,
+            base_url: None,
+            auth_token: None,
+        }
+    }
+}
+
+impl BenchConfig {
+    /// Set the number of warmup iterations
+    pub fn with_warmup_iterations(mut self, warmup_iterations: usize) -> Self {
+        self.warmup_iterations = warmup_iterations;
+        self
+    }
+
+    /// Set the number of measured iterations
+    pub fn with_iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Set the trim fraction, clamped to the valid range
+    pub fn with_trim_fraction(mut self, trim_fraction: f64) -> Self {
+        self.trim_fraction = trim_fraction.clamp(0.0, 0.49);
+        self
+    }
+
+    /// Also benchmark each adapter's endpoint on the service at `base_url`
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Authenticate HTTP requests with a bearer token
+    pub fn with_auth_token(mut self, auth_token: impl Into<String>) -> Self {
+        self.auth_token = Some(auth_token.into());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config() {
+        let config = BenchConfig::default();
+        assert_eq!(config.warmup_iterations, 10);
+        assert_eq!(config.iterations, 1000);
+        assert_eq!(config.trim_fraction, 0.05);
+        assert!(config.base_url.is_none());
+    }
+
+    #[test]
+    fn test_trim_fraction_is_clamped() {
+        let config = BenchConfig::default().with_trim_fraction(0.9);
+        assert!(config.trim_fraction < 0.5);
+
+        let config = BenchConfig::default().with_trim_fraction(-1.0);
+        assert_eq!(config.trim_fraction, 0.0);
+    }
+}
@@ -0,0 +1,122 @@
+use crate::config::BenchConfig;
+use crate::summary::{measure, merge_metrics, summarize_durations};
+use reqwest::blocking::Client;
+use reqwest::Method;
+use std::time::Duration;
+
+/// Timeout of a single benchmark request
+pub const HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Request an adapter times against a running service
+#[derive(Debug, Clone)]
+pub struct HttpEndpoint {
+    pub method: Method,
+    /// Path appended to `BenchConfig::base_url`, e.g. `/api/v1/policies`
+    pub path: &'static str,
+    /// JSON body sent with every request
+    pub body: Option<serde_json::Value>,
+}
+
+impl HttpEndpoint {
+    pub fn get(path: &'static str) -> Self {
+        Self {
+            method: Method::GET,
+            path,
+            body: None,
+        }
+    }
+
+    pub fn post(path: &'static str, body: serde_json::Value) -> Self {
+        Self {
+            method: Method::POST,
+            path,
+            body: Some(body),
+        }
+    }
+}
+
+/// Time round trips to `endpoint` on the service at `config.base_url`
+///
+/// Returns `None` when no base URL is configured. Every measured request is a
+/// sample, including those that fail or get a non-2xx status; those are also
+/// counted in `http_errors` so a misconfigured target is visible.
+pub fn measure_http(config: &BenchConfig, endpoint: &HttpEndpoint) -> Option<serde_json::Value> {
+    let base_url = config.base_url.as_deref()?;
+    let url = format!("{}{}", base_url.trim_end_matches('/'), endpoint.path);
+
+    let client = match Client::builder().timeout(HTTP_TIMEOUT).build() {
+        Ok(client) => client,
+        Err(e) => return Some(serde_json::json!({ "url": url, "error": e.to_string() })),
+    };
+
+    let mut succeeded = Vec::with_capacity(config.warmup_iterations + config.iterations);
+    let samples = measure(config, |_| {
+        let mut request = client.request(endpoint.method.clone(), url.as_str());
+        if let Some(token) = &config.auth_token {
+            request = request.bearer_auth(token);
+        }
+        if let Some(body) = &endpoint.body {
+            request = request.json(body);
+        }
+
+        // Reading the body is part of the round trip
+        let ok = request
+            .send()
+            .and_then(|response| {
+                let ok = response.status().is_success();
+                response.bytes().map(|_| ok)
+            })
+            .unwrap_or(false);
+        succeeded.push(ok);
+    });
+
+    let errors = succeeded[config.warmup_iterations..].iter().filter(|ok| !**ok).count();
+    let summary = summarize_durations(&samples, config.trim_fraction);
+
+    Some(merge_metrics(
+        summary.to_metrics(config),
+        serde_json::json!({
+            "url": url,
+            "http_errors": errors,
+        }),
+    ))
+}
+
+/// Add the over-HTTP metrics of `endpoint` under `http`, when a base URL is configured
+pub fn with_http_metrics(
+    metrics: serde_json::Value,
+    config: &BenchConfig,
+    endpoint: &HttpEndpoint,
+) -> serde_json::Value {
+    match measure_http(config, endpoint) {
+        Some(http) => merge_metrics(metrics, serde_json::json!({ "http": http })),
+        None => metrics,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_http_run_is_skipped_without_base_url() {
+        let endpoint = HttpEndpoint::get("/api/v1/policies");
+        assert!(measure_http(&BenchConfig::default(), &endpoint).is_none());
+
+        let metrics = with_http_metrics(serde_json::json!({"iterations": 1}), &BenchConfig::default(), &endpoint);
+        assert!(metrics.get("http").is_none());
+    }
+
+    #[test]
+    fn test_unreachable_service_counts_errors() {
+        let config = BenchConfig::default()
+            .with_warmup_iterations(1)
+            .with_iterations(3)
+            .with_base_url("http://127.0.0.1:1/");
+
+        let http = measure_http(&config, &HttpEndpoint::get("/api/v1/policies")).unwrap();
+        assert_eq!(http["url"], "http://127.0.0.1:1/api/v1/policies");
+        assert_eq!(http["iterations"], 3);
+        assert_eq!(http["http_errors"], 3);
+    }
+}
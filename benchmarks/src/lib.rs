@@ -1,12 +1,22 @@
 pub mod adapters;
+pub mod config;
+pub mod http;
 pub mod io;
 pub mod markdown;
 pub mod result;
+pub mod summary;
 
+pub use config::BenchConfig;
 pub use result::BenchmarkResult;
+pub use summary::{summarize_durations, DurationSummary};
 
-/// Run all registered benchmarks and return their results
+/// Run all registered benchmarks with the default configuration
 pub fn run_all_benchmarks() -> Vec<BenchmarkResult> {
+    run_all_benchmarks_with_config(&BenchConfig::default())
+}
+
+/// Run all registered benchmarks with the given configuration
+pub fn run_all_benchmarks_with_config(config: &BenchConfig) -> Vec<BenchmarkResult> {
     let targets = adapters::all_targets();
     let mut results = Vec::new();
 
@@ -16,7 +26,7 @@ pub fn run_all_benchmarks() -> Vec<BenchmarkResult> {
         let target_id = target.id();
         println!("  Running: {}", target_id);
 
-        let result = target.run();
+        let result = target.run(config);
         results.push(result);
     }
 
@@ -54,4 +64,18 @@ mod tests {
             assert!(result.metrics.is_object(), "Metrics for {} should be a JSON object", result.target_id);
         }
     }
+
+    #[test]
+    fn test_run_all_benchmarks_with_config() {
+        let config = BenchConfig::default()
+            .with_warmup_iterations(2)
+            .with_iterations(50);
+        let results = run_all_benchmarks_with_config(&config);
+
+        for result in results {
+            assert_eq!(result.metrics["iterations"], 50);
+            assert_eq!(result.metrics["warmup_iterations"], 2);
+            assert!(result.metrics.get("trimmed_mean_latency_ms").is_some());
+        }
+    }
 }
@@ -0,0 +1,186 @@
+use crate::config::BenchConfig;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Summary statistics over a set of measured iteration durations
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DurationSummary {
+    /// Number of measured samples (warmups excluded)
+    pub samples: usize,
+
+    /// Number of samples dropped from each end for the trimmed mean
+    pub trimmed_per_side: usize,
+
+    /// Sum of all measured samples in milliseconds
+    pub total_ms: f64,
+
+    /// Arithmetic mean over all samples in milliseconds
+    pub mean_ms: f64,
+
+    /// Mean after dropping the top and bottom `trim_fraction` of samples
+    pub trimmed_mean_ms: f64,
+
+    pub min_ms: f64,
+    pub max_ms: f64,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+}
+
+/// Run `op` for the configured warmup iterations (discarded), then time each
+/// of the measured iterations individually.
+///
+/// The closure receives the iteration index; warmup iterations use the same
+/// index range as the measured ones so both exercise the same code paths.
+pub fn measure<F: FnMut(usize)>(config: &BenchConfig, mut op: F) -> Vec<Duration> {
+    for i in 0..config.warmup_iterations {
+        op(i);
+    }
+
+    let mut samples = Vec::with_capacity(config.iterations);
+    for i in 0..config.iterations {
+        let start = Instant::now();
+        op(i);
+        samples.push(start.elapsed());
+    }
+
+    samples
+}
+
+/// Summarize measured durations, computing a trimmed mean that drops
+/// `trim_fraction` of samples from both the top and bottom
+pub fn summarize_durations(samples: &[Duration], trim_fraction: f64) -> DurationSummary {
+    if samples.is_empty() {
+        return DurationSummary {
+            samples: 0,
+            trimmed_per_side: 0,
+            total_ms: 0.0,
+            mean_ms: 0.0,
+            trimmed_mean_ms: 0.0,
+            min_ms: 0.0,
+            max_ms: 0.0,
+            p50_ms: 0.0,
+            p95_ms: 0.0,
+        };
+    }
+
+    let mut sorted: Vec<f64> = samples.iter().map(|d| d.as_secs_f64() * 1000.0).collect();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+    let n = sorted.len();
+    let total_ms: f64 = sorted.iter().sum();
+
+    // Never trim away every sample
+    let trim_fraction = trim_fraction.clamp(0.0, 0.49);
+    let trimmed_per_side = ((n as f64) * trim_fraction).floor() as usize;
+    let trimmed_per_side = trimmed_per_side.min((n - 1) / 2);
+    let kept = &sorted[trimmed_per_side..n - trimmed_per_side];
+    let trimmed_mean_ms = kept.iter().sum::<f64>() / kept.len() as f64;
+
+    DurationSummary {
+        samples: n,
+        trimmed_per_side,
+        total_ms,
+        mean_ms: total_ms / n as f64,
+        trimmed_mean_ms,
+        min_ms: sorted[0],
+        max_ms: sorted[n - 1],
+        p50_ms: percentile(&sorted, 0.50),
+        p95_ms: percentile(&sorted, 0.95),
+    }
+}
+
+/// Nearest-rank percentile over an already sorted slice
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = ((sorted.len() as f64) * p).ceil() as usize;
+    sorted[rank.saturating_sub(1).min(sorted.len() - 1)]
+}
+
+impl DurationSummary {
+    /// Standard timing metrics shared by every benchmark adapter
+    pub fn to_metrics(&self, config: &BenchConfig) -> serde_json::Value {
+        let throughput = if self.total_ms > 0.0 {
+            self.samples as f64 / (self.total_ms / 1000.0)
+        } else {
+            0.0
+        };
+
+        serde_json::json!({
+            "iterations": self.samples,
+            "warmup_iterations": config.warmup_iterations,
+            "trim_fraction": config.trim_fraction,
+            "total_duration_ms": self.total_ms,
+            "avg_latency_ms": self.mean_ms,
+            "trimmed_mean_latency_ms": self.trimmed_mean_ms,
+            "min_latency_ms": self.min_ms,
+            "max_latency_ms": self.max_ms,
+            "p50_latency_ms": self.p50_ms,
+            "p95_latency_ms": self.p95_ms,
+            "throughput_ops_per_sec": throughput,
+        })
+    }
+}
+
+/// Merge adapter-specific metrics into the standard timing metrics
+pub fn merge_metrics(mut base: serde_json::Value, extra: serde_json::Value) -> serde_json::Value {
+    if let (Some(base_map), serde_json::Value::Object(extra_map)) = (base.as_object_mut(), extra) {
+        base_map.extend(extra_map);
+    }
+    base
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_warmup_samples_are_excluded() {
+        let config = BenchConfig::default()
+            .with_warmup_iterations(7)
+            .with_iterations(20);
+
+        let mut calls = 0;
+        let samples = measure(&config, |_| calls += 1);
+
+        assert_eq!(calls, 27, "warmup and measured iterations should both execute");
+        assert_eq!(samples.len(), 20, "only measured iterations should be sampled");
+    }
+
+    #[test]
+    fn test_outlier_does_not_dominate_trimmed_mean() {
+        let mut samples = vec![Duration::from_millis(1); 99];
+        samples.push(Duration::from_secs(10));
+
+        let summary = summarize_durations(&samples, 0.05);
+
+        assert_eq!(summary.samples, 100);
+        assert_eq!(summary.trimmed_per_side, 5);
+        assert!((summary.trimmed_mean_ms - 1.0).abs() < 1e-9);
+        assert!(summary.mean_ms > 100.0, "untrimmed mean should reflect the outlier");
+        assert_eq!(summary.max_ms, 10_000.0);
+    }
+
+    #[test]
+    fn test_zero_trim_matches_mean() {
+        let samples: Vec<Duration> = (1..=4).map(Duration::from_millis).collect();
+        let summary = summarize_durations(&samples, 0.0);
+
+        assert_eq!(summary.trimmed_per_side, 0);
+        assert!((summary.trimmed_mean_ms - summary.mean_ms).abs() < 1e-9);
+        assert!((summary.mean_ms - 2.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_empty_samples() {
+        let summary = summarize_durations(&[], 0.05);
+        assert_eq!(summary.samples, 0);
+        assert_eq!(summary.trimmed_mean_ms, 0.0);
+    }
+
+    #[test]
+    fn test_small_sample_keeps_at_least_one() {
+        let samples = vec![Duration::from_millis(3)];
+        let summary = summarize_durations(&samples, 0.49);
+        assert_eq!(summary.trimmed_per_side, 0);
+        assert!((summary.trimmed_mean_ms - 3.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,28 @@
+-- ============================================================================
+-- Idempotency Keys Migration
+-- ============================================================================
+-- Records the outcome of mutating requests sent with an Idempotency-Key header
+-- so client retries replay the original response instead of creating duplicates
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS idempotency_keys (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -- Endpoint the key applies to, e.g. 'costs.create_budget'
+    scope VARCHAR(100) NOT NULL,
+    user_id UUID NOT NULL,
+    idempotency_key VARCHAR(255) NOT NULL,
+
+    -- NULL while the original request is still in progress
+    resource_id UUID,
+    status_code INTEGER,
+    response_body JSONB,
+
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+
+    CONSTRAINT idempotency_keys_scope_user_key_unique UNIQUE(scope, user_id, idempotency_key)
+);
+
+CREATE INDEX idx_idempotency_keys_expires_at ON idempotency_keys(expires_at);
+
+COMMENT ON TABLE idempotency_keys IS 'Stored responses for idempotent retries of mutating requests';
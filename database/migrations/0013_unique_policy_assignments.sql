@@ -0,0 +1,20 @@
+-- ============================================================================
+-- Unique Policy Assignments Migration
+-- ============================================================================
+-- A policy can be assigned to a given team or user at most once, which lets
+-- bulk assignment use ON CONFLICT DO NOTHING and be re-run safely
+-- ============================================================================
+
+-- Drop duplicate assignments, keeping the earliest
+DELETE FROM policy_assignments a
+USING policy_assignments b
+WHERE a.policy_id = b.policy_id
+  AND a.team_id IS NOT DISTINCT FROM b.team_id
+  AND a.user_id IS NOT DISTINCT FROM b.user_id
+  AND (a.assigned_at, a.id) > (b.assigned_at, b.id);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_policy_assignments_policy_team_unique
+    ON policy_assignments(policy_id, team_id) WHERE team_id IS NOT NULL;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_policy_assignments_policy_user_unique
+    ON policy_assignments(policy_id, user_id) WHERE user_id IS NOT NULL;
@@ -0,0 +1,12 @@
+-- ============================================================================
+-- Cost Attribution Tags Migration
+-- ============================================================================
+-- Free-form key/value tags (e.g. project, cost_center) supplied on proxied
+-- requests, used to attribute spend beyond user/team/provider/model
+-- ============================================================================
+
+ALTER TABLE llm_metrics ADD COLUMN IF NOT EXISTS tags JSONB NOT NULL DEFAULT '{}';
+
+CREATE INDEX IF NOT EXISTS idx_llm_metrics_tags ON llm_metrics USING GIN(tags);
+
+COMMENT ON COLUMN llm_metrics.tags IS 'Cost attribution tags supplied with the request';
@@ -0,0 +1,10 @@
+-- ============================================================================
+-- User Token Version Migration
+-- ============================================================================
+-- Access tokens carry the user's token_version at issue time; bumping it
+-- (e.g. on deactivation) invalidates every previously issued access token
+-- ============================================================================
+
+ALTER TABLE users ADD COLUMN IF NOT EXISTS token_version INTEGER NOT NULL DEFAULT 0;
+
+COMMENT ON COLUMN users.token_version IS 'Incremented to invalidate all outstanding access tokens';
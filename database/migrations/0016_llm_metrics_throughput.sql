@@ -0,0 +1,14 @@
+-- ============================================================================
+-- LLM Metrics Throughput Migration
+-- ============================================================================
+-- Stores derived throughput per request so latency SLOs can be tracked
+-- without recomputing rates from raw token counts. The proxy only makes
+-- buffered provider calls so far, so time_to_first_token_ms stays NULL until
+-- streaming is supported
+-- ============================================================================
+
+ALTER TABLE llm_metrics ADD COLUMN IF NOT EXISTS tokens_per_second DOUBLE PRECISION;
+ALTER TABLE llm_metrics ADD COLUMN IF NOT EXISTS time_to_first_token_ms INTEGER;
+
+COMMENT ON COLUMN llm_metrics.tokens_per_second IS 'Completion tokens divided by request latency; NULL when no output was produced';
+COMMENT ON COLUMN llm_metrics.time_to_first_token_ms IS 'Latency until the first streamed token; NULL for buffered requests';
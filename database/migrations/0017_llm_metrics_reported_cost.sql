@@ -0,0 +1,10 @@
+-- ============================================================================
+-- LLM Metrics Reported Cost Migration
+-- ============================================================================
+-- Stores the amount billed by the provider alongside our computed cost so the
+-- two can be reconciled to detect pricing-table drift
+-- ============================================================================
+
+ALTER TABLE llm_metrics ADD COLUMN IF NOT EXISTS reported_cost DECIMAL(10, 6);
+
+COMMENT ON COLUMN llm_metrics.reported_cost IS 'Cost reported by the provider; NULL when the provider does not report billing';
@@ -0,0 +1,20 @@
+-- ============================================================================
+-- Audit Event Receipts Migration
+-- ============================================================================
+-- Idempotency keys of audit events forwarded by other services, so a retried
+-- batch does not append the same event to the audit log twice
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS audit_event_receipts (
+    -- Service that forwarded the event, e.g. 'integration-service'
+    source VARCHAR(100) NOT NULL,
+    idempotency_key VARCHAR(255) NOT NULL,
+    audit_log_id UUID NOT NULL REFERENCES audit_logs(id),
+    received_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+
+    PRIMARY KEY (source, idempotency_key)
+);
+
+CREATE INDEX idx_audit_event_receipts_log ON audit_event_receipts(audit_log_id);
+
+COMMENT ON TABLE audit_event_receipts IS 'Idempotency keys of audit events ingested over HTTP';
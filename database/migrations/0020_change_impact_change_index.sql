@@ -0,0 +1,10 @@
+-- ============================================================================
+-- Change Impact Change Index Migration
+-- ============================================================================
+-- Lets reviewers find the impact assessments recorded for a change from the
+-- change's own id
+-- ============================================================================
+
+CREATE INDEX IF NOT EXISTS idx_audit_logs_change_request_id
+    ON audit_logs ((details->>'change_request_id'))
+    WHERE resource_type = 'change_impact_assessment';
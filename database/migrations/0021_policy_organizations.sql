@@ -0,0 +1,18 @@
+-- ============================================================================
+-- Policy Organizations Migration
+-- ============================================================================
+-- Scopes policies to the organization that owns them. Existing policies keep
+-- a NULL organization_id and stay visible to every organization as shared
+-- policies; set organization_id on any that belong to a single tenant.
+-- Policy names become unique per organization instead of globally.
+-- ============================================================================
+
+ALTER TABLE policies
+    ADD COLUMN IF NOT EXISTS organization_id UUID REFERENCES organizations(id) ON DELETE CASCADE;
+
+CREATE INDEX IF NOT EXISTS idx_policies_organization_id ON policies(organization_id);
+
+ALTER TABLE policies DROP CONSTRAINT IF EXISTS policies_name_key;
+
+CREATE UNIQUE INDEX IF NOT EXISTS policies_org_name_key
+    ON policies (COALESCE(organization_id, '00000000-0000-0000-0000-000000000000'::uuid), name);
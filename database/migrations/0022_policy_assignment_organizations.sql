@@ -0,0 +1,25 @@
+-- ============================================================================
+-- Policy Assignment Organizations Migration
+-- ============================================================================
+-- Lets a policy be assigned to a whole organization, in addition to a team or
+-- a single user. Every member of the organization inherits such policies.
+-- Each assignment still targets exactly one of team, user or organization.
+-- ============================================================================
+
+ALTER TABLE policy_assignments
+    ADD COLUMN IF NOT EXISTS organization_id UUID REFERENCES organizations(id) ON DELETE CASCADE;
+
+ALTER TABLE policy_assignments DROP CONSTRAINT IF EXISTS assignment_target;
+
+ALTER TABLE policy_assignments ADD CONSTRAINT assignment_target CHECK (
+    num_nonnulls(team_id, user_id, organization_id) = 1
+);
+
+COMMENT ON CONSTRAINT assignment_target ON policy_assignments IS
+    'Policy must be assigned to exactly one of a team, a user or an organization';
+
+CREATE INDEX IF NOT EXISTS idx_policy_assignments_organization_id
+    ON policy_assignments(organization_id);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_policy_assignments_policy_org_unique
+    ON policy_assignments(policy_id, organization_id) WHERE organization_id IS NOT NULL;
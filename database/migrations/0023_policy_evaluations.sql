@@ -0,0 +1,28 @@
+-- ============================================================================
+-- Policy Evaluations Migration
+-- ============================================================================
+-- Records every policy evaluation and its outcome, so there is a history of
+-- what was evaluated, for whom, and what was decided. The evaluated context
+-- itself is not stored; its hash identifies repeated evaluations.
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS policy_evaluations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    policy_id UUID NOT NULL REFERENCES policies(id) ON DELETE CASCADE,
+    -- Policy version the rules were read from
+    policy_version INTEGER NOT NULL,
+    -- SHA-256 of the evaluated context, after subject enrichment
+    context_hash CHAR(64) NOT NULL,
+    passed BOOLEAN NOT NULL,
+    violation_count INTEGER NOT NULL DEFAULT 0,
+    -- Violations as returned to the caller: rule_violated, severity, message
+    violations JSONB NOT NULL DEFAULT '[]',
+    -- User the policy was evaluated for
+    subject_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    evaluated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_policy_evaluations_policy ON policy_evaluations(policy_id, evaluated_at DESC);
+CREATE INDEX idx_policy_evaluations_evaluated_at ON policy_evaluations(evaluated_at);
+
+COMMENT ON TABLE policy_evaluations IS 'Outcome of each policy evaluation';
@@ -0,0 +1,15 @@
+-- ============================================================================
+-- Idempotency Request Fingerprint Migration
+-- ============================================================================
+-- Binds each Idempotency-Key to a hash of the request body so a key reused
+-- for a different request is rejected instead of replaying another response
+-- ============================================================================
+
+-- Keys claimed before this migration have no fingerprint and are dropped;
+-- they expire within a day anyway
+DELETE FROM idempotency_keys;
+
+ALTER TABLE idempotency_keys
+    ADD COLUMN request_fingerprint VARCHAR(64) NOT NULL;
+
+COMMENT ON COLUMN idempotency_keys.request_fingerprint IS 'SHA-256 of the canonical JSON request body the key was first used with';
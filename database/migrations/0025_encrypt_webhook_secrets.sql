@@ -0,0 +1,12 @@
+-- ============================================================================
+-- Encrypt Webhook Secrets Migration
+-- ============================================================================
+-- Signing secrets are stored sealed with the integration-service key. Rows
+-- written before this migration hold plaintext, which the worker cannot
+-- decrypt; their deliveries fail until the subscription is recreated.
+-- ============================================================================
+
+ALTER TABLE webhook_subscriptions RENAME COLUMN secret TO secret_encrypted;
+ALTER TABLE webhook_subscriptions ALTER COLUMN secret_encrypted TYPE TEXT;
+
+COMMENT ON COLUMN webhook_subscriptions.secret_encrypted IS 'Signing secret, AES-256-GCM sealed as v1:<base64(nonce || ciphertext)>';
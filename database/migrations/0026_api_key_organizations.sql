@@ -0,0 +1,13 @@
+-- ============================================================================
+-- API Key Organizations Migration
+-- ============================================================================
+-- Records which organization an API key acts for, so organization-level
+-- exports include only that organization's keys. Keys without an
+-- organization belong to the user alone.
+-- ============================================================================
+
+ALTER TABLE api_keys ADD COLUMN IF NOT EXISTS organization_id UUID REFERENCES organizations(id) ON DELETE CASCADE;
+
+CREATE INDEX IF NOT EXISTS idx_api_keys_organization_id ON api_keys(organization_id);
+
+COMMENT ON COLUMN api_keys.organization_id IS 'Organization the key acts for; NULL for personal keys';
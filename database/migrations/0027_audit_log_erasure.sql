@@ -0,0 +1,105 @@
+-- ============================================================================
+-- Audit Log Erasure Migration
+-- ============================================================================
+-- Right-to-be-forgotten requests must remove personal data (emails typed at
+-- failed logins, client IPs) from audit rows. Audit logs stay immutable:
+-- only `pseudonymize_audit_logs`, which runs as the NOLOGIN `audit_erasure`
+-- role, may rewrite `details` and `ip_address`, and only for the rows of one
+-- user that has already been tombstoned. The checksum is recomputed so
+-- integrity verification keeps passing for pseudonymized rows.
+--
+-- Creating the role requires CREATEROLE for the migrating role, which is only
+-- a member of audit_erasure while this migration hands the function over.
+-- Services should connect as a role without CREATEROLE, which could grant
+-- itself audit_erasure again.
+-- ============================================================================
+
+DO $$
+BEGIN
+    IF NOT EXISTS (SELECT 1 FROM pg_roles WHERE rolname = 'audit_erasure') THEN
+        CREATE ROLE audit_erasure NOLOGIN;
+    END IF;
+END
+$$;
+
+-- Nobody updates audit rows directly; audit_erasure may touch only the
+-- columns that hold personal data
+REVOKE UPDATE, DELETE, TRUNCATE ON audit_logs FROM PUBLIC;
+REVOKE UPDATE, DELETE, TRUNCATE ON audit_logs FROM CURRENT_USER;
+GRANT SELECT ON audit_logs TO audit_erasure;
+GRANT UPDATE (ip_address, details) ON audit_logs TO audit_erasure;
+GRANT SELECT ON users TO audit_erasure;
+
+CREATE OR REPLACE FUNCTION prevent_audit_log_modification()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'UPDATE'
+        AND current_user = 'audit_erasure'
+        AND NEW.id = OLD.id
+        AND NEW.timestamp = OLD.timestamp
+        AND NEW.user_id IS NOT DISTINCT FROM OLD.user_id
+        AND NEW.action = OLD.action
+        AND NEW.resource_type = OLD.resource_type
+        AND NEW.resource_id = OLD.resource_id
+    THEN
+        NEW.checksum = generate_audit_checksum(
+            NEW.timestamp,
+            NEW.user_id,
+            NEW.action,
+            NEW.resource_type,
+            NEW.resource_id,
+            NEW.details
+        );
+        RETURN NEW;
+    END IF;
+
+    RAISE EXCEPTION 'Audit logs are immutable and cannot be modified or deleted';
+END;
+$$ LANGUAGE plpgsql;
+
+COMMENT ON FUNCTION prevent_audit_log_modification() IS 'Prevents modification or deletion of audit logs, except erasure of personal data by pseudonymize_audit_logs';
+
+-- Clears client IPs and replaces the email typed at failed logins in the
+-- audit rows of `erased_user_id`, whose account must already carry
+-- `tombstone_email`; returns the number of rows rewritten
+CREATE OR REPLACE FUNCTION pseudonymize_audit_logs(
+    erased_user_id UUID,
+    erased_email TEXT,
+    tombstone_email TEXT
+)
+RETURNS BIGINT
+LANGUAGE plpgsql
+SECURITY DEFINER
+SET search_path = public, pg_temp
+AS $$
+DECLARE
+    pseudonymized BIGINT;
+BEGIN
+    IF NOT EXISTS (SELECT 1 FROM users WHERE id = erased_user_id AND email = tombstone_email) THEN
+        RAISE EXCEPTION 'User % has not been erased', erased_user_id;
+    END IF;
+
+    UPDATE audit_logs
+    SET ip_address = NULL,
+        details = CASE
+            WHEN lower(details->>'email') = lower(erased_email)
+                THEN jsonb_set(details, '{email}', to_jsonb(tombstone_email))
+            ELSE details
+        END
+    WHERE user_id = erased_user_id OR lower(details->>'email') = lower(erased_email);
+
+    GET DIAGNOSTICS pseudonymized = ROW_COUNT;
+    RETURN pseudonymized;
+END;
+$$;
+
+-- Hand the function over and let only the migrating role call it, then drop
+-- the membership and schema privilege the handover needs so no login role
+-- can act as audit_erasure
+GRANT audit_erasure TO CURRENT_USER;
+GRANT CREATE ON SCHEMA public TO audit_erasure;
+ALTER FUNCTION pseudonymize_audit_logs(UUID, TEXT, TEXT) OWNER TO audit_erasure;
+REVOKE ALL ON FUNCTION pseudonymize_audit_logs(UUID, TEXT, TEXT) FROM PUBLIC;
+GRANT EXECUTE ON FUNCTION pseudonymize_audit_logs(UUID, TEXT, TEXT) TO CURRENT_USER;
+REVOKE CREATE ON SCHEMA public FROM audit_erasure;
+REVOKE audit_erasure FROM CURRENT_USER;
@@ -0,0 +1,29 @@
+-- ============================================================================
+-- Unique Budget Names Migration
+-- ============================================================================
+-- A budget is identified by its organization, team or user, and name. The
+-- upsert endpoint resolves budgets by that key, so POST /costs/budgets must
+-- not be able to create a second budget with the same one.
+-- ============================================================================
+
+-- Rename duplicates rather than drop them, keeping the earliest name as is
+UPDATE budgets b
+SET name = LEFT(b.name, 240) || ' #' || LEFT(b.id::text, 8),
+    updated_at = NOW()
+WHERE EXISTS (
+    SELECT 1 FROM budgets earlier
+    WHERE earlier.organization_id = b.organization_id
+      AND earlier.team_id IS NOT DISTINCT FROM b.team_id
+      AND earlier.user_id IS NOT DISTINCT FROM b.user_id
+      AND earlier.name = b.name
+      AND (earlier.created_at, earlier.id) < (b.created_at, b.id)
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_budgets_org_name_unique
+    ON budgets(organization_id, name) WHERE team_id IS NULL AND user_id IS NULL;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_budgets_team_name_unique
+    ON budgets(organization_id, team_id, name) WHERE team_id IS NOT NULL;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_budgets_user_name_unique
+    ON budgets(organization_id, user_id, name) WHERE user_id IS NOT NULL;
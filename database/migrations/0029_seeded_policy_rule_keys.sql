@@ -0,0 +1,27 @@
+-- ============================================================================
+-- Seeded Policy Rule Keys Migration
+-- ============================================================================
+-- The default policies seeded in 011 use rule keys the policy evaluator never
+-- reads, so they were silent no-ops, and policy updates now reject rules with
+-- unknown keys. Rewrite each seeded policy's rules to the evaluated key,
+-- dropping the keys no evaluator reads. Policies whose rules no longer carry
+-- the seeded key were edited since and are left alone.
+-- ============================================================================
+
+-- Default Cost Limit: daily_limit -> max_cost_per_request
+UPDATE policies
+SET rules = jsonb_build_object('max_cost_per_request', rules->'daily_limit')
+WHERE id = '30000000-0000-0000-0000-000000000001'
+  AND rules ? 'daily_limit';
+
+-- Default Rate Limit: requests_per_minute -> max_requests_per_minute
+UPDATE policies
+SET rules = jsonb_build_object('max_requests_per_minute', rules->'requests_per_minute')
+WHERE id = '30000000-0000-0000-0000-000000000002'
+  AND rules ? 'requests_per_minute';
+
+-- PII Detection: block_patterns -> blocked_patterns
+UPDATE policies
+SET rules = jsonb_build_object('blocked_patterns', rules->'block_patterns')
+WHERE id = '30000000-0000-0000-0000-000000000003'
+  AND rules ? 'block_patterns';
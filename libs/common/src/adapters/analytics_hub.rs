@@ -3,8 +3,8 @@
 //! Consumes aggregated analytics, baselines, usage clusters, and forecasting
 //! insights from the LLM-Analytics-Hub upstream service.
 
-use super::{EcosystemConsumer, UpstreamConfig};
-use crate::error::{AppError, Result};
+use super::{EcosystemConsumer, HttpConsumer, UpstreamConfig};
+use crate::error::Result;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -216,18 +216,15 @@ pub enum AnomalySeverity {
 /// Consumer adapter for LLM-Analytics-Hub
 pub struct AnalyticsHubConsumer {
     config: UpstreamConfig,
-    client: reqwest::Client,
+    http: HttpConsumer,
 }
 
 impl AnalyticsHubConsumer {
     /// Create a new Analytics Hub consumer
     pub fn new(config: UpstreamConfig) -> Result<Self> {
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_millis(config.timeout_ms))
-            .build()
-            .map_err(|e| AppError::Internal(format!("Failed to create HTTP client: {}", e)))?;
+        let http = HttpConsumer::new("Analytics Hub", config.clone())?;
 
-        Ok(Self { config, client })
+        Ok(Self { config, http })
     }
 
     /// Consume aggregated analytics
@@ -241,7 +238,7 @@ impl AnalyticsHubConsumer {
             "{}/api/v1/analytics/aggregate?org_id={}&start={}&end={}",
             self.config.base_url, organization_id, period_start, period_end
         );
-        self.fetch_json(&url).await
+        self.http.get_json(&url).await
     }
 
     /// Consume performance baselines
@@ -259,7 +256,7 @@ impl AnalyticsHubConsumer {
             url.push_str(&format!("&model_id={}", mid));
         }
 
-        self.fetch_json(&url).await
+        self.http.get_json(&url).await
     }
 
     /// Consume usage clusters
@@ -268,7 +265,7 @@ impl AnalyticsHubConsumer {
             "{}/api/v1/analytics/clusters?org_id={}",
             self.config.base_url, organization_id
         );
-        self.fetch_json(&url).await
+        self.http.get_json(&url).await
     }
 
     /// Consume forecasting insights
@@ -287,7 +284,7 @@ impl AnalyticsHubConsumer {
             "{}/api/v1/analytics/forecast?org_id={}&type={}&horizon={}",
             self.config.base_url, organization_id, type_str, horizon_days
         );
-        self.fetch_json(&url).await
+        self.http.get_json(&url).await
     }
 
     /// Consume detected anomalies
@@ -305,33 +302,7 @@ impl AnalyticsHubConsumer {
             url.push_str(&format!("&from={}", ts));
         }
 
-        self.fetch_json(&url).await
-    }
-
-    /// Internal helper to fetch JSON from upstream
-    async fn fetch_json<T: for<'de> Deserialize<'de>>(&self, url: &str) -> Result<T> {
-        let mut request = self.client.get(url);
-
-        if let Some(ref api_key) = self.config.api_key {
-            request = request.header("Authorization", format!("Bearer {}", api_key));
-        }
-
-        let response = request
-            .send()
-            .await
-            .map_err(|e| AppError::Internal(format!("Analytics Hub request failed: {}", e)))?;
-
-        if !response.status().is_success() {
-            return Err(AppError::Internal(format!(
-                "Analytics Hub returned status: {}",
-                response.status()
-            )));
-        }
-
-        response
-            .json()
-            .await
-            .map_err(|e| AppError::Internal(format!("Failed to parse Analytics Hub response: {}", e)))
+        self.http.get_json(&url).await
     }
 }
 
@@ -342,11 +313,7 @@ impl EcosystemConsumer for AnalyticsHubConsumer {
     }
 
     async fn health_check(&self) -> Result<bool> {
-        let url = format!("{}/health", self.config.base_url);
-        match self.client.get(&url).send().await {
-            Ok(resp) => Ok(resp.status().is_success()),
-            Err(_) => Ok(false),
-        }
+        Ok(self.http.health_check().await)
     }
 }
 
@@ -24,23 +24,28 @@
 
 use super::ruvector::{
     create_decision_event, default_confidence, execution_ref_from_request,
+    weighted_confidence,
     ConfidenceBand, ConfidenceFactor, ConfidenceImpact, ConstraintApplication,
     ConstraintScope, ConstraintType, DataReference, DataReferenceType, DateRange,
     DecisionConfidence, DecisionEvent, DecisionOutputs, ExecutionReference,
     FindingCategory, GovernanceDecisionType, GovernanceFinding, GovernanceMetrics,
-    GovernanceSeverity, InvocationSource, PersistDecisionResponse, RuVectorConsumer,
+    GovernanceSeverity, InvocationSource, PersistDecisionResponse, DecisionStore,
+    decision_store_from_env,
     TrendDirection,
 };
 use super::policy_engine::{PolicyEngineConsumer, PolicyEvaluationResult, ComplianceStatus};
-use super::registry::RegistryConsumer;
-use super::cost_ops::CostOpsConsumer;
+use super::registry::{ModelMetadata, RegistryConsumer};
+use super::cost_ops::{CostBudget, CostOpsConsumer};
+use super::analytics_hub::{AnalyticsHubConsumer, PerformanceBaseline};
 use super::observatory::{ObservatoryConsumer, AgentTelemetryEvent, EmitSpanRequest, SpanStatus, SpanEvent};
 use super::{EcosystemConsumer, UpstreamConfig};
 use crate::error::{AppError, Result};
+use crate::timestamp::Timestamp;
+use crate::utils::hash_inputs;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use sha2::{Digest, Sha256};
 use std::collections::HashMap;
+use std::sync::Arc;
 use uuid::Uuid;
 
 // ============================================================================
@@ -56,6 +61,10 @@ pub const AGENT_VERSION: &str = "1.0.0";
 /// Decision type for change impact assessments
 pub const DECISION_TYPE: GovernanceDecisionType = GovernanceDecisionType::ChangeImpact;
 
+/// (completeness, certainty) weights for clear-cut risk scores, where
+/// certainty is the better signal of how trustworthy the assessment is
+pub const CLEAR_CASE_CONFIDENCE_WEIGHTS: (f64, f64) = (0.3, 0.7);
+
 // ============================================================================
 // Change Impact Input Types
 // ============================================================================
@@ -158,6 +167,142 @@ pub struct ChangeImpactScope {
     pub include_compliance_impact: Option<bool>,
 }
 
+// ============================================================================
+// Analysis Depth
+// ============================================================================
+
+/// Downstream traversal depth used when the scope does not specify one
+pub const DEFAULT_ANALYSIS_DEPTH: u8 = 3;
+
+/// Deepest downstream traversal allowed; larger requests are clamped
+pub const MAX_ANALYSIS_DEPTH: u8 = 5;
+
+/// Resolve the requested analysis depth to the one actually used
+///
+/// Omitted depths use the default, depths above the maximum are clamped,
+/// and zero is rejected since it would analyze nothing.
+pub fn effective_analysis_depth(requested: Option<u8>) -> Result<u8> {
+    match requested {
+        None => Ok(DEFAULT_ANALYSIS_DEPTH),
+        Some(0) => Err(AppError::Validation(format!(
+            "analysis_depth must be between 1 and {}",
+            MAX_ANALYSIS_DEPTH
+        ))),
+        Some(depth) => Ok(depth.min(MAX_ANALYSIS_DEPTH)),
+    }
+}
+
+/// Ecosystem system node: (system_id, system_name, system_type)
+type SystemNode = (&'static str, &'static str, &'static str);
+
+const ECOSYSTEM_SYSTEMS: &[SystemNode] = &[
+    ("policy-engine", "LLM-Policy-Engine", "enforcement"),
+    ("registry", "LLM-Registry", "model-management"),
+    ("cost-ops", "LLM-CostOps", "cost-management"),
+    ("api-gateway", "LLM-Governance API Gateway", "routing"),
+    ("observatory", "LLM-Observatory", "observability"),
+    ("analytics-hub", "LLM-Analytics-Hub", "analytics"),
+];
+
+/// Directed edges (upstream, downstream) between ecosystem systems
+const ECOSYSTEM_DEPENDENCIES: &[(&str, &str)] = &[
+    ("policy-engine", "api-gateway"),
+    ("registry", "api-gateway"),
+    ("registry", "cost-ops"),
+    ("cost-ops", "analytics-hub"),
+    ("api-gateway", "observatory"),
+    ("observatory", "analytics-hub"),
+];
+
+/// Severity one hop further downstream
+fn attenuate_severity(severity: &GovernanceSeverity) -> GovernanceSeverity {
+    match severity {
+        GovernanceSeverity::Critical => GovernanceSeverity::High,
+        GovernanceSeverity::High => GovernanceSeverity::Medium,
+        GovernanceSeverity::Medium => GovernanceSeverity::Low,
+        GovernanceSeverity::Low | GovernanceSeverity::Info => GovernanceSeverity::Info,
+    }
+}
+
+/// Expand directly affected systems through the ecosystem dependency graph
+///
+/// Directly affected systems count as depth 1; each further level adds the
+/// systems that depend on the previous one, with severity attenuated per hop.
+/// Each system appears at most once, at the shallowest depth it is reached.
+pub fn expand_downstream(direct: Vec<AffectedSystem>, depth: u8) -> Vec<AffectedSystem> {
+    let mut visited: Vec<String> = direct.iter().map(|s| s.system_id.clone()).collect();
+    let mut frontier: Vec<AffectedSystem> = direct.clone();
+    let mut systems = direct;
+
+    for _ in 1..depth {
+        let mut next = Vec::new();
+
+        for parent in &frontier {
+            let downstream = ECOSYSTEM_DEPENDENCIES
+                .iter()
+                .filter(|(upstream, _)| *upstream == parent.system_id)
+                .map(|(_, downstream)| *downstream);
+
+            for system_id in downstream {
+                if visited.iter().any(|v| v == system_id) {
+                    continue;
+                }
+                let Some((id, name, system_type)) =
+                    ECOSYSTEM_SYSTEMS.iter().find(|(id, _, _)| *id == system_id)
+                else {
+                    continue;
+                };
+
+                visited.push(id.to_string());
+                next.push(AffectedSystem {
+                    system_id: id.to_string(),
+                    system_name: name.to_string(),
+                    system_type: system_type.to_string(),
+                    impact_description: format!("Indirectly affected via {}", parent.system_name),
+                    severity: attenuate_severity(&parent.severity),
+                    dependencies: vec![parent.system_id.clone()],
+                });
+            }
+        }
+
+        if next.is_empty() {
+            break;
+        }
+        systems.extend(next.iter().cloned());
+        frontier = next;
+    }
+
+    systems
+}
+
+/// Percentage of the systems in scope of a change that the analysis covered
+///
+/// The scope is every system reachable through the dependency graph from the
+/// directly affected ones. A shallow traversal, or none at all, covers only
+/// part of it; a change that affects no known system is fully covered.
+pub fn downstream_coverage(direct: &[AffectedSystem], analyzed: &[AffectedSystem]) -> f64 {
+    let mut scope: Vec<&str> = direct.iter().map(|s| s.system_id.as_str()).collect();
+    let mut next = 0;
+    while next < scope.len() {
+        let upstream = scope[next];
+        for &(from, to) in ECOSYSTEM_DEPENDENCIES {
+            if from == upstream && !scope.contains(&to) {
+                scope.push(to);
+            }
+        }
+        next += 1;
+    }
+
+    if scope.is_empty() {
+        return 100.0;
+    }
+    let covered = scope
+        .iter()
+        .filter(|id| analyzed.iter().any(|s| s.system_id == **id))
+        .count();
+    covered as f64 / scope.len() as f64 * 100.0
+}
+
 // ============================================================================
 // Change Impact Output Types
 // ============================================================================
@@ -171,6 +316,60 @@ pub struct ChangeImpactOutput {
     pub assessment: ChangeImpactAssessment,
     /// Telemetry reference for LLM-Observatory
     pub telemetry_ref: String,
+    /// Whether LLM-Observatory accepted the telemetry; absent when it is not configured
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub telemetry_status: Option<TelemetryStatus>,
+}
+
+/// What an assessment's telemetry event and trace span report
+#[derive(Debug, Clone)]
+pub struct AssessmentTelemetry<'a> {
+    pub organization_id: &'a str,
+    pub change_request: &'a ChangeRequest,
+    pub decision_event: &'a DecisionEvent,
+    pub impact_level: &'a ImpactLevel,
+    pub risk_classification: &'a RiskClassification,
+    pub risk_score: f64,
+    pub confidence_score: f64,
+    pub affected_systems_count: usize,
+    pub policy_implications_count: usize,
+    pub recommendations_count: usize,
+    /// Risk indicators surfaced
+    pub findings_count: usize,
+    pub assessed_at: Timestamp,
+    pub execution_duration_ms: f64,
+    pub request_id: Option<&'a str>,
+    pub trace_id: Option<&'a str>,
+}
+
+/// Outcome of emitting an assessment's telemetry event and trace span
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TelemetryStatus {
+    /// Both the event and the span were accepted
+    Accepted,
+    /// Only one of the event and the span was accepted
+    Degraded,
+    /// Neither was accepted
+    Failed,
+}
+
+impl TelemetryStatus {
+    fn from_outcomes(event_accepted: bool, span_accepted: bool) -> Self {
+        match (event_accepted, span_accepted) {
+            (true, true) => TelemetryStatus::Accepted,
+            (false, false) => TelemetryStatus::Failed,
+            _ => TelemetryStatus::Degraded,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TelemetryStatus::Accepted => "accepted",
+            TelemetryStatus::Degraded => "degraded",
+            TelemetryStatus::Failed => "failed",
+        }
+    }
 }
 
 /// Comprehensive change impact assessment
@@ -204,8 +403,25 @@ pub struct ChangeImpactAssessment {
     pub recommendations: Vec<ImpactRecommendation>,
     /// Historical context from similar changes
     pub historical_context: Option<HistoricalContext>,
+    /// Downstream traversal depth actually used
+    #[serde(default = "default_analysis_depth")]
+    pub analysis_depth: u8,
+    /// Share of the systems in scope that the downstream analysis reached
+    #[serde(default = "default_coverage_percentage")]
+    pub coverage_percentage: f64,
+    /// Comparison against `baseline_ref`, when one was given and resolved
+    #[serde(default)]
+    pub baseline_comparison: Option<BaselineComparison>,
     /// Assessment timestamp
-    pub assessed_at: String,
+    pub assessed_at: Timestamp,
+}
+
+fn default_coverage_percentage() -> f64 {
+    100.0
+}
+
+fn default_analysis_depth() -> u8 {
+    DEFAULT_ANALYSIS_DEPTH
 }
 
 /// Impact severity level
@@ -410,7 +626,7 @@ pub struct RiskIndicator {
 }
 
 /// Risk indicator categories
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
 pub enum RiskIndicatorCategory {
     SecurityRisk,
@@ -490,6 +706,586 @@ pub enum HistoricalOutcome {
     InsufficientData,
 }
 
+// ============================================================================
+// Model Metadata Impact
+// ============================================================================
+
+/// Downstream usage count at which a model counts as high-usage
+pub const HIGH_USAGE_THRESHOLD: u64 = 100;
+
+/// Impact and risks of changing a model, derived from its registry metadata
+///
+/// Deprecated or retired models and high-usage models each raise the
+/// severity one step above the Medium baseline.
+pub fn model_impact_from_metadata(
+    metadata: &ModelMetadata,
+    change: &ChangeRequest,
+) -> (ImpactDetail, Vec<RiskIndicator>) {
+    let deprecated = metadata.is_deprecated();
+    let high_usage = metadata.downstream_usage_count >= HIGH_USAGE_THRESHOLD;
+
+    let (level, severity) = match (deprecated, high_usage) {
+        (true, true) => (ImpactLevel::Critical, GovernanceSeverity::Critical),
+        (true, false) | (false, true) => (ImpactLevel::High, GovernanceSeverity::High),
+        (false, false) => (ImpactLevel::Moderate, GovernanceSeverity::Medium),
+    };
+
+    let mut notes = Vec::new();
+    if deprecated {
+        notes.push(match metadata.deprecation_date {
+            Some(ref date) => format!("targets deprecated version {} (deprecated {})", metadata.version, date),
+            None => format!("targets deprecated version {}", metadata.version),
+        });
+    }
+    if high_usage {
+        notes.push(format!("is used by {} downstream deployments", metadata.downstream_usage_count));
+    }
+
+    let description = if notes.is_empty() {
+        format!(
+            "Change to {} {} ({}) may affect model behavior",
+            metadata.provider, metadata.model_name, metadata.version
+        )
+    } else {
+        format!(
+            "Change to {} {} ({}) {}",
+            metadata.provider,
+            metadata.model_name,
+            metadata.version,
+            notes.join(" and ")
+        )
+    };
+
+    let mut metrics = HashMap::new();
+    metrics.insert("downstream_usage_count".to_string(), metadata.downstream_usage_count as f64);
+
+    let impact = ImpactDetail {
+        area: ImpactArea::ModelBehavior,
+        level,
+        description: description.clone(),
+        affected_entities: vec![metadata.model_id.clone()],
+        metrics: Some(metrics),
+    };
+
+    let mut risks = Vec::new();
+    if deprecated {
+        risks.push(RiskIndicator {
+            id: Uuid::new_v4().to_string(),
+            category: RiskIndicatorCategory::DependencyRisk,
+            severity: severity.clone(),
+            description: format!("Model {} version {} is deprecated", metadata.model_name, metadata.version),
+            evidence: vec![format!("Registry status: {:?}", metadata.status)],
+            mitigation_suggestions: vec![
+                "Target a supported model version".to_string(),
+                "Plan migration before the deprecation date".to_string(),
+            ],
+        });
+    }
+    if high_usage {
+        risks.push(RiskIndicator {
+            id: Uuid::new_v4().to_string(),
+            category: RiskIndicatorCategory::OperationalRisk,
+            severity,
+            description: format!(
+                "High-usage model {} affected by {:?}",
+                metadata.model_name, change.change_type
+            ),
+            evidence: vec![format!("{} downstream deployments", metadata.downstream_usage_count)],
+            mitigation_suggestions: vec![
+                "Notify downstream owners before rollout".to_string(),
+                "Use a staged rollout".to_string(),
+            ],
+        });
+    }
+
+    (impact, risks)
+}
+
+// ============================================================================
+// Cost Projection
+// ============================================================================
+
+/// Default window of recent spend that cost projections are based on
+pub const COST_ANALYSIS_WINDOW_DAYS: i64 = 30;
+
+/// Observed spend for one breakdown category over the analysis window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategorySpend {
+    /// Identifier the change subject is matched against (e.g. model id)
+    pub category_id: String,
+    /// Human-readable category label
+    pub category: String,
+    /// Spend over the analysis window
+    pub current_cost: f64,
+}
+
+/// Active budget a projected spend is checked against
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BudgetThreshold {
+    pub budget_id: String,
+    pub name: String,
+    pub amount: f64,
+    /// One of `daily`, `weekly`, `monthly`, `yearly`
+    pub period: String,
+    pub alert_threshold_percentage: f64,
+    pub current_spend: f64,
+}
+
+impl From<CostBudget> for BudgetThreshold {
+    fn from(budget: CostBudget) -> Self {
+        Self {
+            budget_id: budget.budget_id,
+            name: budget.name,
+            amount: budget.amount,
+            period: budget.period,
+            alert_threshold_percentage: budget.alert_threshold_percentage,
+            current_spend: budget.current_spend,
+        }
+    }
+}
+
+fn state_f64(state: &Option<serde_json::Value>, field: &str) -> Option<f64> {
+    state.as_ref()?.get(field)?.as_f64()
+}
+
+/// Multiplier the change applies to spend attributed to its subject
+///
+/// Deletions and disabling toggles remove the subject's spend; pricing
+/// updates scale it by the ratio of new to previous per-1k-token prices.
+/// Anything else leaves spend unchanged.
+pub fn subject_cost_factor(change: &ChangeRequest) -> f64 {
+    match change.change_type {
+        ChangeType::Delete => 0.0,
+        ChangeType::Toggle => {
+            let enabled = change
+                .new_state
+                .as_ref()
+                .and_then(|s| s.get("is_active").or_else(|| s.get("enabled")))
+                .and_then(|v| v.as_bool());
+            if enabled == Some(false) { 0.0 } else { 1.0 }
+        }
+        ChangeType::Update | ChangeType::Configure | ChangeType::ModelVersion => {
+            let price = |state: &Option<serde_json::Value>| {
+                Some(
+                    state_f64(state, "cost_per_1k_prompt_tokens")?
+                        + state_f64(state, "cost_per_1k_completion_tokens")?,
+                )
+            };
+            match (price(&change.previous_state), price(&change.new_state)) {
+                (Some(previous), Some(new)) if previous > 0.0 => new / previous,
+                _ => 1.0,
+            }
+        }
+        _ => 1.0,
+    }
+}
+
+/// Project per-category spend after the change
+///
+/// Only categories matching the change subject (by id or label) are
+/// adjusted; the result is ordered by category label.
+pub fn project_cost_breakdown(change: &ChangeRequest, spend: &[CategorySpend]) -> Vec<CostBreakdownItem> {
+    let factor = subject_cost_factor(change);
+
+    let mut breakdown: Vec<CostBreakdownItem> = spend
+        .iter()
+        .map(|s| {
+            let is_subject = s.category_id == change.subject_id || s.category == change.subject_id;
+            let projected_cost = if is_subject { s.current_cost * factor } else { s.current_cost };
+            CostBreakdownItem {
+                category: s.category.clone(),
+                current_cost: s.current_cost,
+                projected_cost,
+                delta: projected_cost - s.current_cost,
+            }
+        })
+        .collect();
+
+    breakdown.sort_by(|a, b| a.category.cmp(&b.category));
+    breakdown
+}
+
+/// Ratio of one budget period to an analysis window of `window_days`
+fn budget_period_scale(period: &str, window_days: i64) -> f64 {
+    let days = match period {
+        "daily" => 1.0,
+        "weekly" => 7.0,
+        "yearly" => 365.0,
+        _ => 30.0,
+    };
+    days / window_days as f64
+}
+
+/// Budgets whose alert threshold the projected spend would newly cross
+///
+/// `window_delta` is the projected change over the `window_days` analysis
+/// window and is scaled to each budget's period. The full organization delta is applied
+/// to every budget, which over-approximates for team and user budgets.
+/// Budget changes are evaluated against the new `amount` and
+/// `alert_threshold_percentage` from the change's `new_state`.
+pub fn triggered_budget_alerts(
+    change: &ChangeRequest,
+    budgets: &[BudgetThreshold],
+    window_delta: f64,
+    window_days: i64,
+) -> Vec<String> {
+    budgets
+        .iter()
+        .filter_map(|budget| {
+            let is_subject = change.subject_type == ChangeSubjectType::Budget
+                && budget.budget_id == change.subject_id;
+            let (amount, threshold_pct) = if is_subject {
+                (
+                    state_f64(&change.new_state, "amount").unwrap_or(budget.amount),
+                    state_f64(&change.new_state, "alert_threshold_percentage")
+                        .unwrap_or(budget.alert_threshold_percentage),
+                )
+            } else {
+                (budget.amount, budget.alert_threshold_percentage)
+            };
+
+            let current_threshold = budget.amount * budget.alert_threshold_percentage / 100.0;
+            let projected_threshold = amount * threshold_pct / 100.0;
+            let projected_spend = budget.current_spend + window_delta * budget_period_scale(&budget.period, window_days);
+
+            if budget.current_spend < current_threshold && projected_spend >= projected_threshold {
+                let utilization = if amount > 0.0 { projected_spend / amount * 100.0 } else { 100.0 };
+                Some(format!(
+                    "Budget '{}' projected at {:.1}% of {:.2} (alert threshold {:.0}%)",
+                    budget.name, utilization, amount, threshold_pct
+                ))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Build a cost implication from spend observed over `window_days` and
+/// active budgets
+pub fn build_cost_implication(
+    change: &ChangeRequest,
+    spend: &[CategorySpend],
+    budgets: &[BudgetThreshold],
+    currency: &str,
+    window_days: i64,
+) -> CostImplication {
+    let breakdown = project_cost_breakdown(change, spend);
+    let estimated_delta: f64 = breakdown.iter().map(|b| b.delta).sum();
+    let budget_alerts_triggered = triggered_budget_alerts(change, budgets, estimated_delta, window_days);
+
+    // Estimates are firmer when the subject has observed spend to scale
+    let subject_observed = spend
+        .iter()
+        .any(|s| (s.category_id == change.subject_id || s.category == change.subject_id) && s.current_cost > 0.0);
+
+    CostImplication {
+        estimated_delta,
+        currency: currency.to_string(),
+        period: "monthly".to_string(),
+        confidence: if subject_observed { 0.8 } else { 0.5 },
+        breakdown,
+        budget_alerts_triggered,
+    }
+}
+
+// ============================================================================
+// Baseline Comparison
+// ============================================================================
+
+/// Where a baseline was resolved from
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BaselineSource {
+    /// A previously persisted change impact assessment
+    PriorAssessment,
+    /// A performance baseline published by LLM-Analytics-Hub
+    AnalyticsHub,
+}
+
+/// Reference state an assessment is compared against
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BaselineSnapshot {
+    /// The `baseline_ref` this snapshot was resolved from
+    pub baseline_ref: String,
+    /// Where the baseline came from
+    pub source: BaselineSource,
+    /// Risk score of the baseline (0.0-1.0)
+    pub risk_score: f64,
+    /// Finding titles present in the baseline
+    pub findings: Vec<String>,
+}
+
+impl BaselineSnapshot {
+    /// Snapshot of a prior change impact DecisionEvent
+    ///
+    /// The risk score is recovered from the compliance rate, which change
+    /// impact outputs record as `100 - risk_score * 100`.
+    pub fn from_decision_event(event: &DecisionEvent) -> Self {
+        let risk_score = ((100.0 - event.outputs.metrics.compliance_rate) / 100.0).clamp(0.0, 1.0);
+        Self {
+            baseline_ref: event.id.clone(),
+            source: BaselineSource::PriorAssessment,
+            risk_score,
+            findings: event.outputs.findings.iter().map(|f| f.title.clone()).collect(),
+        }
+    }
+
+    /// Snapshot of an Analytics Hub performance baseline
+    ///
+    /// Performance baselines describe the accepted steady state, so they carry
+    /// no risk and no findings.
+    pub fn from_performance_baseline(baseline: &PerformanceBaseline) -> Self {
+        Self {
+            baseline_ref: baseline.baseline_id.clone(),
+            source: BaselineSource::AnalyticsHub,
+            risk_score: 0.0,
+            findings: vec![],
+        }
+    }
+}
+
+/// How an assessment differs from its baseline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BaselineComparison {
+    /// Baseline the assessment was compared against
+    pub baseline_ref: String,
+    /// Where the baseline came from
+    pub source: BaselineSource,
+    /// Risk score of the baseline
+    pub baseline_risk_score: f64,
+    /// Assessment risk score minus baseline risk score
+    pub risk_score_delta: f64,
+    /// Findings not present in the baseline
+    pub new_findings: Vec<String>,
+    /// Baseline findings no longer present
+    pub resolved_findings: Vec<String>,
+}
+
+/// Compare an assessment's risk score and findings against a baseline
+///
+/// Findings are matched by title, since finding ids are generated per run.
+pub fn compare_to_baseline(
+    baseline: &BaselineSnapshot,
+    risk_score: f64,
+    risk_indicators: &[RiskIndicator],
+) -> BaselineComparison {
+    let new_findings = risk_indicators
+        .iter()
+        .map(|r| r.description.clone())
+        .filter(|title| !baseline.findings.contains(title))
+        .collect();
+
+    let resolved_findings = baseline
+        .findings
+        .iter()
+        .filter(|title| !risk_indicators.iter().any(|r| &r.description == *title))
+        .cloned()
+        .collect();
+
+    BaselineComparison {
+        baseline_ref: baseline.baseline_ref.clone(),
+        source: baseline.source.clone(),
+        baseline_risk_score: baseline.risk_score,
+        risk_score_delta: risk_score - baseline.risk_score,
+        new_findings,
+        resolved_findings,
+    }
+}
+
+/// Confidence factor describing the baseline comparison, if one was requested
+pub fn baseline_confidence_factor(
+    baseline_ref: Option<&str>,
+    comparison: Option<&BaselineComparison>,
+) -> Option<ConfidenceFactor> {
+    let baseline_ref = baseline_ref?;
+    Some(match comparison {
+        Some(_) => ConfidenceFactor {
+            factor: "baseline_comparison".to_string(),
+            impact: ConfidenceImpact::Positive,
+            weight: 0.1,
+            description: format!("Compared against baseline {}", baseline_ref),
+        },
+        None => ConfidenceFactor {
+            factor: "baseline_comparison".to_string(),
+            impact: ConfidenceImpact::Negative,
+            weight: 0.1,
+            description: format!("Baseline {} could not be resolved; no comparison made", baseline_ref),
+        },
+    })
+}
+
+// ============================================================================
+// Severity Escalation
+// ============================================================================
+
+/// One escalation rule; a `change_type` of `None` matches every change type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeverityRule {
+    pub subject_type: ChangeSubjectType,
+    pub change_type: Option<ChangeType>,
+    pub minimum: GovernanceSeverity,
+}
+
+/// Minimum severities for risk indicators, keyed by subject and change type
+///
+/// Rules only ever raise a computed severity. When several rules match a
+/// change, the highest minimum applies.
+#[derive(Debug, Clone, Default)]
+pub struct SeverityPolicy {
+    rules: Vec<SeverityRule>,
+}
+
+impl SeverityPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Require at least `minimum` for changes to `subject_type`, optionally
+    /// only for one `change_type`
+    pub fn escalate(
+        mut self,
+        subject_type: ChangeSubjectType,
+        change_type: Option<ChangeType>,
+        minimum: GovernanceSeverity,
+    ) -> Self {
+        self.rules.push(SeverityRule {
+            subject_type,
+            change_type,
+            minimum,
+        });
+        self
+    }
+
+    /// Parse `subject[:change_type]=severity` rules, e.g.
+    /// `llm_model=critical,policy:delete=high`
+    pub fn parse(spec: &str) -> std::result::Result<Self, String> {
+        let mut policy = Self::new();
+        for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (target, severity) = entry
+                .split_once('=')
+                .ok_or_else(|| format!("expected subject[:change_type]=severity, got '{}'", entry))?;
+            let (subject, change_type) = match target.split_once(':') {
+                Some((subject, change_type)) => (subject, Some(change_type)),
+                None => (target, None),
+            };
+            policy = policy.escalate(
+                parse_name(subject, "change subject type")?,
+                change_type.map(|t| parse_name(t, "change type")).transpose()?,
+                parse_name(severity, "severity")?,
+            );
+        }
+        Ok(policy)
+    }
+
+    pub fn rules(&self) -> &[SeverityRule] {
+        &self.rules
+    }
+
+    /// Highest minimum among the rules matching `change`
+    pub fn minimum_for(&self, change: &ChangeRequest) -> Option<GovernanceSeverity> {
+        self.rules
+            .iter()
+            .filter(|rule| {
+                rule.subject_type == change.subject_type
+                    && rule.change_type.as_ref().is_none_or(|t| *t == change.change_type)
+            })
+            .map(|rule| rule.minimum.clone())
+            .max()
+    }
+
+    /// Raise risk indicators below the change's minimum severity, noting the
+    /// escalation in their evidence
+    pub fn apply(&self, change: &ChangeRequest, risks: &mut [RiskIndicator]) {
+        let Some(minimum) = self.minimum_for(change) else {
+            return;
+        };
+
+        for risk in risks.iter_mut() {
+            if risk.severity < minimum {
+                risk.evidence.push(format!(
+                    "Severity escalated from {:?} to {:?} by severity policy for {} changes",
+                    risk.severity, minimum, change.subject_type
+                ));
+                risk.severity = minimum.clone();
+            }
+        }
+    }
+}
+
+/// Variant of a snake_case enum named `name`
+fn parse_name<T: serde::de::DeserializeOwned>(name: &str, kind: &str) -> std::result::Result<T, String> {
+    serde_json::from_value(serde_json::Value::String(name.trim().to_string()))
+        .map_err(|_| format!("unknown {} '{}'", kind, name.trim()))
+}
+
+// ============================================================================
+// Risk Category Weights
+// ============================================================================
+
+impl RiskIndicatorCategory {
+    /// Parse the snake_case name used in serialized output
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "security_risk" => Some(Self::SecurityRisk),
+            "compliance_risk" => Some(Self::ComplianceRisk),
+            "operational_risk" => Some(Self::OperationalRisk),
+            "financial_risk" => Some(Self::FinancialRisk),
+            "reputational_risk" => Some(Self::ReputationalRisk),
+            "dependency_risk" => Some(Self::DependencyRisk),
+            "configuration_risk" => Some(Self::ConfigurationRisk),
+            "access_risk" => Some(Self::AccessRisk),
+            _ => None,
+        }
+    }
+}
+
+/// Per-category multipliers for risk indicators in the overall risk score
+///
+/// An indicator's weight in the score is multiplied by its category's
+/// multiplier, so with `security_risk=2` a security indicator counts twice as
+/// much as a financial one of the same severity. Unlisted categories keep 1.0.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CategoryWeights {
+    weights: HashMap<RiskIndicatorCategory, f64>,
+}
+
+impl CategoryWeights {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_weight(mut self, category: RiskIndicatorCategory, weight: f64) -> Self {
+        self.weights.insert(category, weight);
+        self
+    }
+
+    /// Parse `category=multiplier` pairs, e.g. `security_risk=2.0,financial_risk=0.5`
+    pub fn parse(spec: &str) -> std::result::Result<Self, String> {
+        let mut weights = Self::new();
+        for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (name, weight) = entry
+                .split_once('=')
+                .ok_or_else(|| format!("expected category=multiplier, got '{}'", entry))?;
+            let category = RiskIndicatorCategory::from_name(name.trim())
+                .ok_or_else(|| format!("unknown risk category '{}'", name.trim()))?;
+            let weight: f64 = weight
+                .trim()
+                .parse()
+                .map_err(|_| format!("invalid multiplier for '{}': '{}'", name.trim(), weight.trim()))?;
+            if !weight.is_finite() || weight <= 0.0 {
+                return Err(format!("multiplier for '{}' must be positive", name.trim()));
+            }
+            weights = weights.with_weight(category, weight);
+        }
+        Ok(weights)
+    }
+
+    pub fn weight(&self, category: &RiskIndicatorCategory) -> f64 {
+        self.weights.get(category).copied().unwrap_or(1.0)
+    }
+}
+
 // ============================================================================
 // Change Impact Agent Implementation
 // ============================================================================
@@ -511,8 +1307,9 @@ pub enum HistoricalOutcome {
 /// - Block or approve changes
 /// - Execute changes
 pub struct ChangeImpactAgent {
-    /// ruvector-service client for persistence
-    ruvector: RuVectorConsumer,
+    /// Decision store for persistence: ruvector-service, or in memory when
+    /// `RUVECTOR_MODE=memory`
+    ruvector: Arc<dyn DecisionStore>,
     /// Policy engine consumer for policy analysis
     policy_engine: Option<PolicyEngineConsumer>,
     /// Registry consumer for model metadata
@@ -521,18 +1318,30 @@ pub struct ChangeImpactAgent {
     cost_ops: Option<CostOpsConsumer>,
     /// Observatory consumer for telemetry
     observatory: Option<ObservatoryConsumer>,
+    /// Analytics Hub consumer for baseline resolution
+    analytics_hub: Option<AnalyticsHubConsumer>,
+    /// Minimum severities applied to computed risk indicators
+    severity_policy: SeverityPolicy,
+    /// Multipliers for risk indicators in the overall risk score
+    category_weights: CategoryWeights,
+    /// Days of recent spend cost projections are based on
+    cost_window_days: i64,
 }
 
 impl ChangeImpactAgent {
     /// Create a new Change Impact Agent
     pub fn new(ruvector_config: UpstreamConfig) -> Result<Self> {
-        let ruvector = RuVectorConsumer::new(ruvector_config)?;
+        let ruvector = Arc::from(decision_store_from_env(ruvector_config)?);
         Ok(Self {
             ruvector,
             policy_engine: None,
             registry: None,
             cost_ops: None,
             observatory: None,
+            analytics_hub: None,
+            severity_policy: SeverityPolicy::default(),
+            category_weights: CategoryWeights::default(),
+            cost_window_days: COST_ANALYSIS_WINDOW_DAYS,
         })
     }
 
@@ -544,7 +1353,7 @@ impl ChangeImpactAgent {
         cost_ops_config: Option<UpstreamConfig>,
         observatory_config: Option<UpstreamConfig>,
     ) -> Result<Self> {
-        let ruvector = RuVectorConsumer::new(ruvector_config)?;
+        let ruvector = Arc::from(decision_store_from_env(ruvector_config)?);
 
         let policy_engine = policy_engine_config
             .map(PolicyEngineConsumer::new)
@@ -565,9 +1374,50 @@ impl ChangeImpactAgent {
             registry,
             cost_ops,
             observatory,
+            analytics_hub: None,
+            severity_policy: SeverityPolicy::default(),
+            category_weights: CategoryWeights::default(),
+            cost_window_days: COST_ANALYSIS_WINDOW_DAYS,
         })
     }
 
+    /// Resolve `baseline_ref` against Analytics Hub baselines as well as prior assessments
+    pub fn with_analytics_hub(mut self, analytics_hub_config: UpstreamConfig) -> Result<Self> {
+        self.analytics_hub = Some(AnalyticsHubConsumer::new(analytics_hub_config)?);
+        Ok(self)
+    }
+
+    /// Emit assessment telemetry and trace spans to LLM-Observatory
+    pub fn with_observatory(mut self, observatory_config: UpstreamConfig) -> Result<Self> {
+        self.observatory = Some(ObservatoryConsumer::new(observatory_config)?);
+        Ok(self)
+    }
+
+    /// Escalate computed risk severities according to `policy`
+    pub fn with_severity_policy(mut self, policy: SeverityPolicy) -> Self {
+        self.severity_policy = policy;
+        self
+    }
+
+    /// Weight risk indicators in the overall score by category
+    pub fn with_category_weights(mut self, weights: CategoryWeights) -> Self {
+        self.category_weights = weights;
+        self
+    }
+
+    /// Base cost projections on the last `days` of spend; must be positive
+    pub fn with_cost_window_days(mut self, days: i64) -> Self {
+        self.cost_window_days = days;
+        self
+    }
+
+    /// Persist to and read prior assessments from `store` instead, which
+    /// agents and handlers can share so each sees the others' events
+    pub fn with_decision_store(mut self, store: Arc<dyn DecisionStore>) -> Self {
+        self.ruvector = store;
+        self
+    }
+
     /// Assess the impact of a change
     ///
     /// This is the primary entry point for change impact analysis.
@@ -584,7 +1434,7 @@ impl ChangeImpactAgent {
         let start_time = std::time::Instant::now();
 
         // Generate inputs hash for reproducibility
-        let inputs_hash = self.hash_inputs(&input);
+        let inputs_hash = hash_inputs(&input);
 
         // Perform impact analysis
         let assessment = self.analyze_impact(&input).await?;
@@ -626,35 +1476,44 @@ impl ChangeImpactAgent {
         let execution_duration_ms = start_time.elapsed().as_millis() as f64;
 
         // Emit telemetry to LLM-Observatory (if configured)
-        let telemetry_ref = self.emit_assessment_telemetry(
-            &input,
-            &assessment,
-            &decision_event,
-            &confidence,
+        let (telemetry_ref, telemetry_status) = self.emit_assessment_telemetry(&AssessmentTelemetry {
+            organization_id: &input.organization_id,
+            change_request: &input.change_request,
+            decision_event: &decision_event,
+            impact_level: &assessment.impact_level,
+            risk_classification: &assessment.risk_classification,
+            risk_score: assessment.risk_score,
+            confidence_score: confidence.overall,
+            affected_systems_count: assessment.affected_systems.len(),
+            policy_implications_count: assessment.policy_implications.len(),
+            recommendations_count: assessment.recommendations.len(),
+            findings_count: assessment.risk_indicators.len(),
+            assessed_at: assessment.assessed_at,
             execution_duration_ms,
-            &execution_context,
-        ).await;
+            request_id: execution_context.request_id.as_deref(),
+            trace_id: execution_context.trace_id.as_deref(),
+        }).await;
 
         Ok(ChangeImpactOutput {
             decision_event,
             assessment,
             telemetry_ref,
+            telemetry_status,
         })
     }
 
     /// Emit telemetry to LLM-Observatory
     ///
     /// Emits both a custom telemetry event and a trace span for the assessment.
-    /// Returns a telemetry reference URI.
-    async fn emit_assessment_telemetry(
+    /// Returns a telemetry reference URI and, when Observatory is configured,
+    /// whether it accepted them. Failures never fail the assessment; they are
+    /// logged and counted in `agent_telemetry_emissions_total`.
+    pub async fn emit_assessment_telemetry(
         &self,
-        input: &ChangeImpactInput,
-        assessment: &ChangeImpactAssessment,
-        decision_event: &DecisionEvent,
-        confidence: &DecisionConfidence,
-        execution_duration_ms: f64,
-        execution_context: &ExecutionContext,
-    ) -> String {
+        telemetry: &AssessmentTelemetry<'_>,
+    ) -> (String, Option<TelemetryStatus>) {
+        let decision_event = telemetry.decision_event;
+
         // Default telemetry reference if Observatory is not configured
         let default_ref = format!(
             "observatory://telemetry/{}/{}",
@@ -663,25 +1522,26 @@ impl ChangeImpactAgent {
         );
 
         let Some(ref observatory) = self.observatory else {
-            return default_ref;
+            return (default_ref, None);
         };
 
         // Build agent telemetry event
+        let change = telemetry.change_request;
         let mut attributes = HashMap::new();
-        attributes.insert("change_id".to_string(), serde_json::json!(input.change_request.change_id));
-        attributes.insert("change_type".to_string(), serde_json::json!(input.change_request.change_type));
-        attributes.insert("subject_type".to_string(), serde_json::json!(input.change_request.subject_type));
-        attributes.insert("subject_id".to_string(), serde_json::json!(input.change_request.subject_id));
-        attributes.insert("impact_level".to_string(), serde_json::json!(assessment.impact_level));
-        attributes.insert("risk_classification".to_string(), serde_json::json!(assessment.risk_classification));
-        attributes.insert("affected_systems_count".to_string(), serde_json::json!(assessment.affected_systems.len()));
-        attributes.insert("policy_implications_count".to_string(), serde_json::json!(assessment.policy_implications.len()));
-        attributes.insert("recommendations_count".to_string(), serde_json::json!(assessment.recommendations.len()));
-
-        if let Some(ref request_id) = execution_context.request_id {
+        attributes.insert("change_id".to_string(), serde_json::json!(change.change_id));
+        attributes.insert("change_type".to_string(), serde_json::json!(change.change_type));
+        attributes.insert("subject_type".to_string(), serde_json::json!(change.subject_type));
+        attributes.insert("subject_id".to_string(), serde_json::json!(change.subject_id));
+        attributes.insert("impact_level".to_string(), serde_json::json!(telemetry.impact_level));
+        attributes.insert("risk_classification".to_string(), serde_json::json!(telemetry.risk_classification));
+        attributes.insert("affected_systems_count".to_string(), serde_json::json!(telemetry.affected_systems_count));
+        attributes.insert("policy_implications_count".to_string(), serde_json::json!(telemetry.policy_implications_count));
+        attributes.insert("recommendations_count".to_string(), serde_json::json!(telemetry.recommendations_count));
+
+        if let Some(request_id) = telemetry.request_id {
             attributes.insert("request_id".to_string(), serde_json::json!(request_id));
         }
-        if let Some(ref trace_id) = execution_context.trace_id {
+        if let Some(trace_id) = telemetry.trace_id {
             attributes.insert("trace_id".to_string(), serde_json::json!(trace_id));
         }
 
@@ -689,48 +1549,51 @@ impl ChangeImpactAgent {
             agent_id: AGENT_ID.to_string(),
             agent_version: AGENT_VERSION.to_string(),
             decision_type: "change_impact_assessment".to_string(),
-            organization_id: input.organization_id.clone(),
+            organization_id: telemetry.organization_id.to_string(),
             decision_event_id: decision_event.id.clone(),
-            execution_duration_ms,
-            confidence_score: confidence.overall,
-            risk_score: Some(assessment.risk_score),
-            findings_count: assessment.risk_indicators.len() as u32,
+            execution_duration_ms: telemetry.execution_duration_ms,
+            confidence_score: telemetry.confidence_score,
+            risk_score: Some(telemetry.risk_score),
+            findings_count: telemetry.findings_count as u32,
             attributes: attributes.clone(),
         };
 
-        // Emit agent telemetry event (fire and forget - don't block on errors)
-        let event_id = match observatory.emit_agent_telemetry(agent_event).await {
-            Ok(response) => response.event_id,
-            Err(_) => decision_event.id.clone(), // Fallback to decision event ID
+        // Emit agent telemetry event (don't block on errors)
+        let (event_id, event_accepted) = match observatory.emit_agent_telemetry(agent_event).await {
+            Ok(response) => (response.event_id, response.acknowledged),
+            Err(e) => {
+                tracing::warn!("Failed to emit change impact telemetry event: {}", e);
+                (decision_event.id.clone(), false) // Fallback to decision event ID
+            }
         };
 
         // Emit trace span for distributed tracing
         let span_events = vec![
             SpanEvent {
                 name: "impact_analysis_started".to_string(),
-                timestamp: decision_event.timestamp.clone(),
+                timestamp: decision_event.timestamp.to_string(),
                 attributes: HashMap::new(),
             },
             SpanEvent {
                 name: "impact_analysis_completed".to_string(),
-                timestamp: assessment.assessed_at.clone(),
+                timestamp: telemetry.assessed_at.to_string(),
                 attributes: {
                     let mut attrs = HashMap::new();
-                    attrs.insert("risk_score".to_string(), serde_json::json!(assessment.risk_score));
-                    attrs.insert("impact_level".to_string(), serde_json::json!(assessment.impact_level));
+                    attrs.insert("risk_score".to_string(), serde_json::json!(telemetry.risk_score));
+                    attrs.insert("impact_level".to_string(), serde_json::json!(telemetry.impact_level));
                     attrs
                 },
             },
         ];
 
-        let span_status = if assessment.risk_score < 0.7 {
+        let span_status = if telemetry.risk_score < 0.7 {
             SpanStatus::Ok
         } else {
             SpanStatus::Unset // High risk doesn't mean error, just noteworthy
         };
 
         let span_request = EmitSpanRequest {
-            trace_id: execution_context.trace_id.clone(),
+            trace_id: telemetry.trace_id.map(str::to_string),
             parent_span_id: None,
             operation_name: "change_impact_assessment".to_string(),
             service_name: AGENT_ID.to_string(),
@@ -739,17 +1602,35 @@ impl ChangeImpactAgent {
             events: span_events,
         };
 
-        // Emit span (fire and forget)
-        let _ = observatory.emit_span(span_request).await;
+        // Emit span (don't block on errors)
+        let span_accepted = match observatory.emit_span(span_request).await {
+            Ok(response) => response.acknowledged,
+            Err(e) => {
+                tracing::warn!("Failed to emit change impact trace span: {}", e);
+                false
+            }
+        };
+
+        let status = TelemetryStatus::from_outcomes(event_accepted, span_accepted);
+        crate::metrics::AGENT_TELEMETRY_EMISSIONS_TOTAL
+            .with_label_values(&[AGENT_ID, status.as_str()])
+            .inc();
 
         // Return telemetry reference
-        format!("observatory://telemetry/{}/{}", AGENT_ID, event_id)
+        (format!("observatory://telemetry/{}/{}", AGENT_ID, event_id), Some(status))
+    }
+
+    /// The store this agent persists DecisionEvents to
+    pub fn decision_store(&self) -> &dyn DecisionStore {
+        self.ruvector.as_ref()
     }
 
     /// Analyze the impact of a change
     async fn analyze_impact(&self, input: &ChangeImpactInput) -> Result<ChangeImpactAssessment> {
         let assessment_id = Uuid::new_v4().to_string();
         let change = &input.change_request;
+        let analysis_depth =
+            effective_analysis_depth(input.scope.as_ref().and_then(|s| s.analysis_depth))?;
 
         // Analyze impacts by area
         let mut impacts = Vec::new();
@@ -767,6 +1648,26 @@ impl ChangeImpactAgent {
             risk_indicators.extend(policy_impacts.risks);
         }
 
+        // Model metadata analysis
+        if input.change_request.subject_type == ChangeSubjectType::LlmModel {
+            if let Some(ref registry) = self.registry {
+                match registry.get_model_metadata(&input.change_request.subject_id).await {
+                    Ok(metadata) => {
+                        let (impact, risks) = model_impact_from_metadata(&metadata, &input.change_request);
+                        impacts.push(impact);
+                        risk_indicators.extend(risks);
+                    }
+                    Err(e) => {
+                        tracing::warn!(
+                            "Registry metadata unavailable for model {}: {}",
+                            input.change_request.subject_id,
+                            e
+                        );
+                    }
+                }
+            }
+        }
+
         // Cost impact analysis (if requested)
         if input.scope.as_ref().map_or(false, |s| s.include_cost_impact.unwrap_or(false)) {
             if let Some(ref cost_ops) = self.cost_ops {
@@ -781,9 +1682,14 @@ impl ChangeImpactAgent {
         }
 
         // Analyze affected downstream systems
+        let direct_systems = self.direct_systems(input);
         if input.include_downstream.unwrap_or(true) {
-            affected_systems = self.analyze_downstream_systems(input).await?;
+            affected_systems = expand_downstream(direct_systems.clone(), analysis_depth);
         }
+        let coverage_percentage = downstream_coverage(&direct_systems, &affected_systems);
+
+        // Apply configured minimum severities before scoring
+        self.severity_policy.apply(change, &mut risk_indicators);
 
         // Calculate overall risk score
         let risk_score = self.calculate_risk_score(&impacts, &risk_indicators, &policy_implications);
@@ -813,6 +1719,15 @@ impl ChangeImpactAgent {
             None
         };
 
+        // Compare against the baseline (if requested and resolvable)
+        let baseline_comparison = match input.baseline_ref {
+            Some(ref baseline_ref) => self
+                .resolve_baseline(&input.organization_id, baseline_ref)
+                .await
+                .map(|baseline| compare_to_baseline(&baseline, risk_score, &risk_indicators)),
+            None => None,
+        };
+
         Ok(ChangeImpactAssessment {
             id: assessment_id,
             change_request_id: change.change_id.clone(),
@@ -828,7 +1743,10 @@ impl ChangeImpactAgent {
             risk_indicators,
             recommendations,
             historical_context,
-            assessed_at: chrono::Utc::now().to_rfc3339(),
+            analysis_depth,
+            coverage_percentage,
+            baseline_comparison,
+            assessed_at: Timestamp::now(),
         })
     }
 
@@ -974,32 +1892,48 @@ impl ChangeImpactAgent {
         cost_ops: &CostOpsConsumer,
         input: &ChangeImpactInput,
     ) -> Result<Option<CostImplication>> {
-        // Get current cost summary
-        let cost_summary = cost_ops
-            .get_cost_summary(&input.organization_id, None)
+        let end = chrono::Utc::now();
+        let start = end - chrono::Duration::days(self.cost_window_days);
+
+        let summary = match cost_ops
+            .get_cost_summary(&input.organization_id, &start.to_rfc3339(), &end.to_rfc3339())
             .await
-            .ok();
+        {
+            Ok(summary) => summary,
+            Err(_) => return Ok(None),
+        };
 
-        if let Some(summary) = cost_summary {
-            // Estimate cost delta based on change type
-            let estimated_delta = match input.change_request.change_type {
-                ChangeType::Create => summary.total_cost * 0.1, // Rough estimate
-                ChangeType::Delete => -summary.total_cost * 0.05,
-                ChangeType::BudgetAdjust => 0.0, // Depends on specific change
-                _ => 0.0,
-            };
+        let by_category = match input.change_request.subject_type {
+            ChangeSubjectType::LlmProvider => &summary.cost_by_provider,
+            ChangeSubjectType::Team => &summary.cost_by_team,
+            _ => &summary.cost_by_model,
+        };
+        let spend: Vec<CategorySpend> = by_category
+            .iter()
+            .map(|(category, cost)| CategorySpend {
+                category_id: category.clone(),
+                category: category.clone(),
+                current_cost: *cost,
+            })
+            .collect();
+
+        // Without budgets the projection is still reported, just without
+        // threshold crossings
+        let budgets: Vec<BudgetThreshold> = match cost_ops.get_budgets(&input.organization_id).await {
+            Ok(budgets) => budgets.into_iter().map(BudgetThreshold::from).collect(),
+            Err(e) => {
+                tracing::warn!("Budgets unavailable for organization {}: {}", input.organization_id, e);
+                Vec::new()
+            }
+        };
 
-            Ok(Some(CostImplication {
-                estimated_delta,
-                currency: "USD".to_string(),
-                period: "monthly".to_string(),
-                confidence: 0.6, // Lower confidence for estimates
-                breakdown: vec![],
-                budget_alerts_triggered: vec![],
-            }))
-        } else {
-            Ok(None)
-        }
+        Ok(Some(build_cost_implication(
+            &input.change_request,
+            &spend,
+            &budgets,
+            &summary.currency,
+            self.cost_window_days,
+        )))
     }
 
     /// Analyze compliance-related impacts
@@ -1037,11 +1971,8 @@ impl ChangeImpactAgent {
         Ok(implications)
     }
 
-    /// Analyze affected downstream systems
-    async fn analyze_downstream_systems(
-        &self,
-        input: &ChangeImpactInput,
-    ) -> Result<Vec<AffectedSystem>> {
+    /// Systems the change affects directly, before downstream expansion
+    fn direct_systems(&self, input: &ChangeImpactInput) -> Vec<AffectedSystem> {
         let mut systems = Vec::new();
 
         // Identify affected systems based on subject type
@@ -1087,7 +2018,41 @@ impl ChangeImpactAgent {
             _ => {}
         }
 
-        Ok(systems)
+        systems
+    }
+
+    /// Resolve a baseline from a prior assessment, then from Analytics Hub
+    ///
+    /// Resolution failures are logged and yield `None` so the assessment
+    /// still completes; the gap is reported through confidence factors.
+    async fn resolve_baseline(
+        &self,
+        organization_id: &str,
+        baseline_ref: &str,
+    ) -> Option<BaselineSnapshot> {
+        match self.ruvector.get_decision_event(baseline_ref).await {
+            Ok(event) if event.decision_type == DECISION_TYPE && event.organization_id == organization_id => {
+                return Some(BaselineSnapshot::from_decision_event(&event));
+            }
+            Ok(_) => {
+                tracing::warn!("Baseline {} is not a change impact assessment for this organization", baseline_ref);
+            }
+            Err(e) => {
+                tracing::debug!("Baseline {} not found as prior assessment: {}", baseline_ref, e);
+            }
+        }
+
+        let analytics_hub = self.analytics_hub.as_ref()?;
+        match analytics_hub.get_baselines(organization_id, None).await {
+            Ok(baselines) => baselines
+                .iter()
+                .find(|b| b.baseline_id == baseline_ref)
+                .map(BaselineSnapshot::from_performance_baseline),
+            Err(e) => {
+                tracing::warn!("Failed to fetch Analytics Hub baselines: {}", e);
+                None
+            }
+        }
     }
 
     /// Calculate overall risk score
@@ -1123,8 +2088,9 @@ impl ChangeImpactAgent {
                 GovernanceSeverity::High => 0.7,
                 GovernanceSeverity::Critical => 1.0,
             };
-            score += risk_weight * 0.4;
-            weight_sum += 0.4;
+            let category_weight = 0.4 * self.category_weights.weight(&risk.category);
+            score += risk_weight * category_weight;
+            weight_sum += category_weight;
         }
 
         // Policy implication contribution
@@ -1285,7 +2251,7 @@ impl ChangeImpactAgent {
                     ref_type: DataReferenceType::DecisionEvent,
                     source_system: "ruvector-service".to_string(),
                     ref_id: e.id.clone(),
-                    ref_timestamp: e.timestamp.clone(),
+                    ref_timestamp: e.timestamp.to_string(),
                 }
             }).collect();
 
@@ -1301,14 +2267,6 @@ impl ChangeImpactAgent {
         }
     }
 
-    /// Hash inputs for reproducibility verification
-    fn hash_inputs(&self, input: &ChangeImpactInput) -> String {
-        let input_json = serde_json::to_string(input).unwrap_or_default();
-        let mut hasher = Sha256::new();
-        hasher.update(input_json.as_bytes());
-        format!("{:x}", hasher.finalize())
-    }
-
     /// Calculate confidence metrics
     fn calculate_confidence(
         &self,
@@ -1330,17 +2288,34 @@ impl ChangeImpactAgent {
             completeness += 0.1;
         }
 
-        // Certainty based on risk score variance
-        let certainty = if assessment.risk_score < 0.3 || assessment.risk_score > 0.7 {
-            0.85 // High certainty for clear cases
+        // Certainty based on risk score variance; clear-cut cases lean on it more
+        let clear_case = assessment.risk_score < 0.3 || assessment.risk_score > 0.7;
+        let (certainty, (w_completeness, w_certainty)) = if clear_case {
+            (0.85, CLEAR_CASE_CONFIDENCE_WEIGHTS)
         } else {
-            0.65 // Lower certainty for borderline cases
+            (0.65, (0.5, 0.5))
         };
 
+        let mut factors = vec![
+            ConfidenceFactor {
+                factor: "upstream_data_availability".to_string(),
+                impact: if self.policy_engine.is_some() { ConfidenceImpact::Positive } else { ConfidenceImpact::Negative },
+                weight: 0.3,
+                description: "Availability of policy engine data".to_string(),
+            },
+            ConfidenceFactor {
+                factor: "historical_data".to_string(),
+                impact: if assessment.historical_context.is_some() { ConfidenceImpact::Positive } else { ConfidenceImpact::Neutral },
+                weight: 0.2,
+                description: "Historical change data availability".to_string(),
+            },
+        ];
+        factors.extend(baseline_confidence_factor(
+            input.baseline_ref.as_deref(),
+            assessment.baseline_comparison.as_ref(),
+        ));
+
         DecisionConfidence {
-            overall: (completeness + certainty) / 2.0,
-            completeness: completeness.min(1.0),
-            certainty,
             bands: vec![
                 ConfidenceBand {
                     aspect: "policy_impact".to_string(),
@@ -1355,20 +2330,8 @@ impl ChangeImpactAgent {
                     median: 0.7,
                 },
             ],
-            factors: vec![
-                ConfidenceFactor {
-                    factor: "upstream_data_availability".to_string(),
-                    impact: if self.policy_engine.is_some() { ConfidenceImpact::Positive } else { ConfidenceImpact::Negative },
-                    weight: 0.3,
-                    description: "Availability of policy engine data".to_string(),
-                },
-                ConfidenceFactor {
-                    factor: "historical_data".to_string(),
-                    impact: if assessment.historical_context.is_some() { ConfidenceImpact::Positive } else { ConfidenceImpact::Neutral },
-                    weight: 0.2,
-                    description: "Historical change data availability".to_string(),
-                },
-            ],
+            factors,
+            ..weighted_confidence(completeness.min(1.0), certainty, w_completeness, w_certainty)
         }
     }
 
@@ -1426,8 +2389,8 @@ impl ChangeImpactAgent {
                 description: r.evidence.join("; "),
                 affected_resources: vec![assessment.change_request_id.clone()],
                 evidence_refs: r.evidence.clone(),
-                first_detected: assessment.assessed_at.clone(),
-                last_seen: assessment.assessed_at.clone(),
+                first_detected: assessment.assessed_at,
+                last_seen: assessment.assessed_at,
             }
         }).collect();
 
@@ -1443,10 +2406,10 @@ impl ChangeImpactAgent {
             metrics: GovernanceMetrics {
                 events_analyzed: assessment.impacts.len() as u64,
                 time_range: DateRange {
-                    start: assessment.assessed_at.clone(),
-                    end: assessment.assessed_at.clone(),
+                    start: assessment.assessed_at,
+                    end: assessment.assessed_at,
                 },
-                coverage_percentage: 85.0,
+                coverage_percentage: assessment.coverage_percentage,
                 policies_evaluated: assessment.policy_implications.len() as u32,
                 compliance_rate: 100.0 - (assessment.risk_score * 100.0),
                 findings_by_severity,
@@ -1464,7 +2427,7 @@ impl ChangeImpactAgent {
                     ref_type: DataReferenceType::DecisionEvent,
                     source_system: s.system_name.clone(),
                     ref_id: s.system_id.clone(),
-                    ref_timestamp: assessment.assessed_at.clone(),
+                    ref_timestamp: assessment.assessed_at.to_string(),
                 }
             }).collect(),
         }
@@ -1527,6 +2490,8 @@ impl EcosystemConsumer for ChangeImpactAgent {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::adapters::ruvector::{DecisionEventPage, DecisionEventQuery};
+    use crate::testing::{MockResponse, MockServer};
 
     #[test]
     fn test_impact_level_from_score() {
@@ -1581,4 +2546,658 @@ mod tests {
         assert!(json.contains("\"change_id\":\"ch-123\""));
         assert!(json.contains("\"change_type\":\"update\""));
     }
+
+    fn direct_system(system_id: &str, severity: GovernanceSeverity) -> AffectedSystem {
+        AffectedSystem {
+            system_id: system_id.to_string(),
+            system_name: system_id.to_string(),
+            system_type: "test".to_string(),
+            impact_description: "Directly affected".to_string(),
+            severity,
+            dependencies: vec![],
+        }
+    }
+
+    #[test]
+    fn test_analysis_depth_is_clamped() {
+        assert_eq!(effective_analysis_depth(Some(9)).unwrap(), MAX_ANALYSIS_DEPTH);
+        assert_eq!(effective_analysis_depth(Some(2)).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_analysis_depth_zero_is_rejected() {
+        assert!(matches!(
+            effective_analysis_depth(Some(0)),
+            Err(AppError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_analysis_depth_defaults_when_omitted() {
+        assert_eq!(effective_analysis_depth(None).unwrap(), DEFAULT_ANALYSIS_DEPTH);
+    }
+
+    #[test]
+    fn test_downstream_expansion_respects_depth() {
+        let direct = || vec![direct_system("registry", GovernanceSeverity::High)];
+
+        let ids = |systems: Vec<AffectedSystem>| {
+            systems.into_iter().map(|s| s.system_id).collect::<Vec<_>>()
+        };
+
+        assert_eq!(ids(expand_downstream(direct(), 1)), vec!["registry"]);
+        assert_eq!(
+            ids(expand_downstream(direct(), 2)),
+            vec!["registry", "api-gateway", "cost-ops"]
+        );
+
+        let full = expand_downstream(direct(), MAX_ANALYSIS_DEPTH);
+        assert_eq!(
+            ids(full.clone()),
+            vec!["registry", "api-gateway", "cost-ops", "observatory", "analytics-hub"]
+        );
+
+        let analytics = full.iter().find(|s| s.system_id == "analytics-hub").unwrap();
+        assert_eq!(analytics.dependencies, vec!["cost-ops".to_string()]);
+        assert_eq!(analytics.severity, GovernanceSeverity::Low);
+    }
+
+    #[test]
+    fn test_partial_downstream_analysis_is_partial_coverage() {
+        // Five systems are reachable from the registry
+        let direct = vec![direct_system("registry", GovernanceSeverity::High)];
+
+        let coverage = |depth: u8| downstream_coverage(&direct, &expand_downstream(direct.clone(), depth));
+        assert_eq!(coverage(1), 20.0);
+        assert_eq!(coverage(2), 60.0);
+        assert_eq!(coverage(MAX_ANALYSIS_DEPTH), 100.0);
+
+        // Skipping the downstream analysis covers nothing in scope
+        assert_eq!(downstream_coverage(&direct, &[]), 0.0);
+        // Nothing in scope is nothing missed
+        assert_eq!(downstream_coverage(&[], &[]), 100.0);
+
+        let policy = vec![direct_system("policy-engine", GovernanceSeverity::Medium)];
+        assert_eq!(downstream_coverage(&policy, &expand_downstream(policy.clone(), 2)), 50.0);
+    }
+
+
+    fn indicator(description: &str) -> RiskIndicator {
+        RiskIndicator {
+            id: Uuid::new_v4().to_string(),
+            category: RiskIndicatorCategory::ComplianceRisk,
+            severity: GovernanceSeverity::High,
+            description: description.to_string(),
+            evidence: vec![],
+            mitigation_suggestions: vec![],
+        }
+    }
+
+    #[test]
+    fn test_compare_to_synthetic_baseline() {
+        let baseline = BaselineSnapshot {
+            baseline_ref: "assessment-1".to_string(),
+            source: BaselineSource::PriorAssessment,
+            risk_score: 0.4,
+            findings: vec!["Policy A may become invalid".to_string(), "Old finding".to_string()],
+        };
+        let indicators = vec![
+            indicator("Policy A may become invalid"),
+            indicator("Policy B may become invalid"),
+        ];
+
+        let comparison = compare_to_baseline(&baseline, 0.65, &indicators);
+
+        assert_eq!(comparison.baseline_ref, "assessment-1");
+        assert!((comparison.risk_score_delta - 0.25).abs() < 1e-9);
+        assert_eq!(comparison.new_findings, vec!["Policy B may become invalid".to_string()]);
+        assert_eq!(comparison.resolved_findings, vec!["Old finding".to_string()]);
+    }
+
+    #[test]
+    fn test_analytics_baseline_has_no_findings() {
+        let baseline = BaselineSnapshot {
+            baseline_ref: "bl-1".to_string(),
+            source: BaselineSource::AnalyticsHub,
+            risk_score: 0.0,
+            findings: vec![],
+        };
+
+        let comparison = compare_to_baseline(&baseline, 0.3, &[indicator("New risk")]);
+
+        assert_eq!(comparison.source, BaselineSource::AnalyticsHub);
+        assert!((comparison.risk_score_delta - 0.3).abs() < 1e-9);
+        assert_eq!(comparison.new_findings, vec!["New risk".to_string()]);
+        assert!(comparison.resolved_findings.is_empty());
+    }
+
+    #[test]
+    fn test_unresolved_baseline_is_noted_in_confidence() {
+        assert!(baseline_confidence_factor(None, None).is_none());
+
+        let factor = baseline_confidence_factor(Some("missing"), None).unwrap();
+        assert_eq!(factor.factor, "baseline_comparison");
+        assert_eq!(factor.impact, ConfidenceImpact::Negative);
+        assert!(factor.description.contains("missing"));
+    }
+
+
+    fn model_change(change_type: ChangeType, previous: serde_json::Value, new: serde_json::Value) -> ChangeRequest {
+        ChangeRequest {
+            change_id: "ch-1".to_string(),
+            change_type,
+            subject_type: ChangeSubjectType::LlmModel,
+            subject_id: "gpt-4".to_string(),
+            description: "Model change".to_string(),
+            timestamp: "2024-01-15T10:00:00Z".to_string(),
+            initiator: "user@example.com".to_string(),
+            previous_state: Some(previous),
+            new_state: Some(new),
+            metadata: None,
+        }
+    }
+
+    fn spend() -> Vec<CategorySpend> {
+        vec![
+            CategorySpend { category_id: "gpt-4".to_string(), category: "gpt-4".to_string(), current_cost: 300.0 },
+            CategorySpend { category_id: "claude-3".to_string(), category: "claude-3".to_string(), current_cost: 100.0 },
+        ]
+    }
+
+    #[test]
+    fn test_cost_delta_scales_subject_by_price_ratio() {
+        let change = model_change(
+            ChangeType::Update,
+            serde_json::json!({"cost_per_1k_prompt_tokens": 0.03, "cost_per_1k_completion_tokens": 0.06}),
+            serde_json::json!({"cost_per_1k_prompt_tokens": 0.045, "cost_per_1k_completion_tokens": 0.09}),
+        );
+
+        let implication = build_cost_implication(&change, &spend(), &[], "USD", COST_ANALYSIS_WINDOW_DAYS);
+
+        assert!((implication.estimated_delta - 150.0).abs() < 1e-6);
+        let gpt4 = implication.breakdown.iter().find(|b| b.category == "gpt-4").unwrap();
+        assert!((gpt4.projected_cost - 450.0).abs() < 1e-6);
+        let claude = implication.breakdown.iter().find(|b| b.category == "claude-3").unwrap();
+        assert_eq!(claude.delta, 0.0);
+        assert_eq!(implication.confidence, 0.8);
+    }
+
+    #[test]
+    fn test_cost_delta_removes_deleted_subject() {
+        let change = model_change(ChangeType::Delete, serde_json::json!({}), serde_json::json!({}));
+        let implication = build_cost_implication(&change, &spend(), &[], "USD", COST_ANALYSIS_WINDOW_DAYS);
+        assert!((implication.estimated_delta + 300.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_budget_alert_triggered_when_threshold_crossed() {
+        let change = model_change(
+            ChangeType::Update,
+            serde_json::json!({"cost_per_1k_prompt_tokens": 0.03, "cost_per_1k_completion_tokens": 0.06}),
+            serde_json::json!({"cost_per_1k_prompt_tokens": 0.045, "cost_per_1k_completion_tokens": 0.09}),
+        );
+        let budgets = vec![
+            BudgetThreshold {
+                budget_id: "b-1".to_string(),
+                name: "Monthly".to_string(),
+                amount: 500.0,
+                period: "monthly".to_string(),
+                alert_threshold_percentage: 80.0,
+                current_spend: 350.0,
+            },
+            BudgetThreshold {
+                budget_id: "b-2".to_string(),
+                name: "Roomy".to_string(),
+                amount: 5000.0,
+                period: "monthly".to_string(),
+                alert_threshold_percentage: 80.0,
+                current_spend: 350.0,
+            },
+            BudgetThreshold {
+                budget_id: "b-3".to_string(),
+                name: "Already over".to_string(),
+                amount: 300.0,
+                period: "monthly".to_string(),
+                alert_threshold_percentage: 80.0,
+                current_spend: 290.0,
+            },
+        ];
+
+        let implication = build_cost_implication(&change, &spend(), &budgets, "USD", COST_ANALYSIS_WINDOW_DAYS);
+
+        assert_eq!(implication.budget_alerts_triggered.len(), 1);
+        assert!(implication.budget_alerts_triggered[0].contains("'Monthly'"));
+    }
+
+    #[test]
+    fn test_budget_decrease_triggers_alert_for_that_budget() {
+        let mut change = model_change(
+            ChangeType::BudgetAdjust,
+            serde_json::json!({"amount": 1000.0}),
+            serde_json::json!({"amount": 400.0}),
+        );
+        change.subject_type = ChangeSubjectType::Budget;
+        change.subject_id = "b-1".to_string();
+        let budgets = vec![BudgetThreshold {
+            budget_id: "b-1".to_string(),
+            name: "Team".to_string(),
+            amount: 1000.0,
+            period: "monthly".to_string(),
+            alert_threshold_percentage: 80.0,
+            current_spend: 350.0,
+        }];
+
+        let alerts = triggered_budget_alerts(&change, &budgets, 0.0, COST_ANALYSIS_WINDOW_DAYS);
+        assert_eq!(alerts.len(), 1);
+        assert!(triggered_budget_alerts(&change, &budgets[..0], 0.0, COST_ANALYSIS_WINDOW_DAYS).is_empty());
+    }
+
+    #[test]
+    fn test_longer_window_spreads_delta_over_more_days() {
+        let budget = |period: &str| BudgetThreshold {
+            budget_id: "b-1".to_string(),
+            name: "Weekly".to_string(),
+            amount: 100.0,
+            period: period.to_string(),
+            alert_threshold_percentage: 80.0,
+            current_spend: 70.0,
+        };
+        let change = model_change(ChangeType::Update, serde_json::json!({}), serde_json::json!({}));
+
+        // +60 over 30 days is +14 a week, crossing the 80 threshold; over 90
+        // days it is under +5 a week and does not
+        assert_eq!(triggered_budget_alerts(&change, &[budget("weekly")], 60.0, 30).len(), 1);
+        assert!(triggered_budget_alerts(&change, &[budget("weekly")], 60.0, 90).is_empty());
+    }
+
+
+    const DEPRECATED_MODEL_BODY: &str = r#"{
+        "model_id": "gpt-4-0314",
+        "model_name": "gpt-4",
+        "provider": "openai",
+        "version": "0314",
+        "description": null,
+        "capabilities": ["chat"],
+        "parameters": {
+            "context_window": 8192,
+            "max_output_tokens": 4096,
+            "supports_streaming": true,
+            "supports_functions": true,
+            "supports_vision": false,
+            "input_modalities": ["text"],
+            "output_modalities": ["text"]
+        },
+        "created_at": "2023-03-14T00:00:00Z",
+        "updated_at": "2024-06-13T00:00:00Z",
+        "status": "deprecated",
+        "tags": {},
+        "deprecation_date": "2024-06-13",
+        "downstream_usage_count": 250
+    }"#;
+
+    #[tokio::test]
+    async fn test_deprecated_high_usage_model_from_mock_registry() {
+        let server = MockServer::start(vec![MockResponse::new(200).with_body(DEPRECATED_MODEL_BODY)]).await;
+        let registry = RegistryConsumer::new(UpstreamConfig {
+            base_url: server.url().to_string(),
+            ..UpstreamConfig::default()
+        })
+        .unwrap();
+
+        let metadata = registry.get_model_metadata("gpt-4-0314").await.unwrap();
+        assert!(metadata.is_deprecated());
+        assert_eq!(metadata.downstream_usage_count, 250);
+
+        let change = model_change(ChangeType::ModelVersion, serde_json::json!({}), serde_json::json!({}));
+        let (impact, risks) = model_impact_from_metadata(&metadata, &change);
+
+        assert_eq!(impact.level, ImpactLevel::Critical);
+        assert!(impact.description.contains("deprecated version 0314"));
+        assert!(impact.description.contains("250 downstream deployments"));
+        assert_eq!(risks.len(), 2);
+        assert!(risks.iter().all(|r| r.severity == GovernanceSeverity::Critical));
+        assert!(risks.iter().any(|r| r.category == RiskIndicatorCategory::DependencyRisk));
+    }
+
+    #[test]
+    fn test_low_usage_active_model_keeps_baseline_severity() {
+        let mut metadata: ModelMetadata = serde_json::from_str(DEPRECATED_MODEL_BODY).unwrap();
+        metadata.status = super::super::registry::ModelStatus::Active;
+        metadata.downstream_usage_count = 3;
+
+        let change = model_change(ChangeType::Update, serde_json::json!({}), serde_json::json!({}));
+        let (impact, risks) = model_impact_from_metadata(&metadata, &change);
+
+        assert_eq!(impact.level, ImpactLevel::Moderate);
+        assert!(risks.is_empty());
+    }
+
+    fn risk(severity: GovernanceSeverity) -> RiskIndicator {
+        RiskIndicator {
+            id: "risk-1".to_string(),
+            category: RiskIndicatorCategory::DependencyRisk,
+            severity,
+            description: "Model change".to_string(),
+            evidence: vec![],
+            mitigation_suggestions: vec![],
+        }
+    }
+
+    #[test]
+    fn test_severity_policy_escalates_model_change_to_critical() {
+        let policy = SeverityPolicy::new().escalate(
+            ChangeSubjectType::LlmModel,
+            None,
+            GovernanceSeverity::Critical,
+        );
+        let change = model_change(ChangeType::ModelVersion, serde_json::json!({}), serde_json::json!({}));
+        let mut risks = vec![risk(GovernanceSeverity::High)];
+
+        policy.apply(&change, &mut risks);
+
+        assert_eq!(risks[0].severity, GovernanceSeverity::Critical);
+        assert!(risks[0].evidence[0].contains("escalated from High to Critical"));
+    }
+
+    #[test]
+    fn test_severity_policy_never_de_escalates() {
+        let policy = SeverityPolicy::new()
+            .escalate(ChangeSubjectType::LlmModel, None, GovernanceSeverity::Low)
+            .escalate(ChangeSubjectType::LlmModel, Some(ChangeType::Update), GovernanceSeverity::Medium);
+        let change = model_change(ChangeType::Update, serde_json::json!({}), serde_json::json!({}));
+        assert_eq!(policy.minimum_for(&change), Some(GovernanceSeverity::Medium));
+
+        let mut risks = vec![risk(GovernanceSeverity::High), risk(GovernanceSeverity::Info)];
+        policy.apply(&change, &mut risks);
+
+        assert_eq!(risks[0].severity, GovernanceSeverity::High);
+        assert!(risks[0].evidence.is_empty());
+        assert_eq!(risks[1].severity, GovernanceSeverity::Medium);
+    }
+
+    #[test]
+    fn test_severity_policy_parses_rules() {
+        let policy = SeverityPolicy::parse("llm_model=critical, policy:delete=high").unwrap();
+        assert_eq!(
+            policy.rules(),
+            &[
+                SeverityRule {
+                    subject_type: ChangeSubjectType::LlmModel,
+                    change_type: None,
+                    minimum: GovernanceSeverity::Critical,
+                },
+                SeverityRule {
+                    subject_type: ChangeSubjectType::Policy,
+                    change_type: Some(ChangeType::Delete),
+                    minimum: GovernanceSeverity::High,
+                },
+            ]
+        );
+
+        assert!(SeverityPolicy::parse("").unwrap().rules().is_empty());
+        assert!(SeverityPolicy::parse("llm_model").is_err());
+        assert!(SeverityPolicy::parse("spaceship=high").unwrap_err().contains("spaceship"));
+        assert!(SeverityPolicy::parse("llm_model:explode=high").is_err());
+        assert!(SeverityPolicy::parse("llm_model=dire").is_err());
+    }
+
+    #[test]
+    fn test_severity_rule_scoped_to_change_type() {
+        let policy = SeverityPolicy::new().escalate(
+            ChangeSubjectType::LlmModel,
+            Some(ChangeType::Delete),
+            GovernanceSeverity::Critical,
+        );
+        let change = model_change(ChangeType::Update, serde_json::json!({}), serde_json::json!({}));
+        assert_eq!(policy.minimum_for(&change), None);
+
+        let mut risks = vec![risk(GovernanceSeverity::High)];
+        policy.apply(&change, &mut risks);
+        assert_eq!(risks[0].severity, GovernanceSeverity::High);
+    }
+
+    fn categorized(category: RiskIndicatorCategory, severity: GovernanceSeverity) -> RiskIndicator {
+        RiskIndicator {
+            category,
+            ..risk(severity)
+        }
+    }
+
+    fn impact(level: ImpactLevel) -> ImpactDetail {
+        ImpactDetail {
+            area: ImpactArea::Cost,
+            level,
+            description: "Cost change".to_string(),
+            affected_entities: vec![],
+            metrics: None,
+        }
+    }
+
+    #[test]
+    fn test_security_weight_raises_score_of_security_dominated_change() {
+        let impacts = vec![impact(ImpactLevel::Low)];
+        let risks = vec![
+            categorized(RiskIndicatorCategory::SecurityRisk, GovernanceSeverity::Critical),
+            categorized(RiskIndicatorCategory::FinancialRisk, GovernanceSeverity::Low),
+        ];
+
+        let agent = ChangeImpactAgent::new(UpstreamConfig::default()).unwrap();
+        let unweighted = agent.calculate_risk_score(&impacts, &risks, &[]);
+
+        let agent = agent.with_category_weights(
+            CategoryWeights::new().with_weight(RiskIndicatorCategory::SecurityRisk, 3.0),
+        );
+        let weighted = agent.calculate_risk_score(&impacts, &risks, &[]);
+
+        assert!(weighted > unweighted, "{} should exceed {}", weighted, unweighted);
+        assert!(weighted <= 1.0);
+    }
+
+    #[test]
+    fn test_security_outweighs_financial_at_same_severity() {
+        let agent = ChangeImpactAgent::new(UpstreamConfig::default())
+            .unwrap()
+            .with_category_weights(
+                CategoryWeights::new().with_weight(RiskIndicatorCategory::SecurityRisk, 2.0),
+            );
+        let impacts = vec![impact(ImpactLevel::Minimal)];
+
+        let security = agent.calculate_risk_score(
+            &impacts,
+            &[categorized(RiskIndicatorCategory::SecurityRisk, GovernanceSeverity::High)],
+            &[],
+        );
+        let financial = agent.calculate_risk_score(
+            &impacts,
+            &[categorized(RiskIndicatorCategory::FinancialRisk, GovernanceSeverity::High)],
+            &[],
+        );
+
+        assert!(security > financial);
+    }
+
+    #[test]
+    fn test_parse_category_weights() {
+        let weights = CategoryWeights::parse("security_risk=2.5, financial_risk = 0.5").unwrap();
+        assert_eq!(weights.weight(&RiskIndicatorCategory::SecurityRisk), 2.5);
+        assert_eq!(weights.weight(&RiskIndicatorCategory::FinancialRisk), 0.5);
+        assert_eq!(weights.weight(&RiskIndicatorCategory::AccessRisk), 1.0);
+        assert_eq!(CategoryWeights::parse("").unwrap(), CategoryWeights::default());
+
+        for invalid in ["security_risk", "security=2", "security_risk=high", "security_risk=0"] {
+            assert!(CategoryWeights::parse(invalid).is_err(), "{} should be rejected", invalid);
+        }
+    }
+
+    /// Store that records persisted events and has no history
+    #[derive(Clone, Default)]
+    struct RecordingStore {
+        persisted: std::sync::Arc<std::sync::Mutex<Vec<DecisionEvent>>>,
+    }
+
+    #[async_trait]
+    impl EcosystemConsumer for RecordingStore {
+        fn service_name(&self) -> &'static str {
+            "recording-store"
+        }
+
+        async fn health_check(&self) -> Result<bool> {
+            Ok(true)
+        }
+    }
+
+    #[async_trait]
+    impl DecisionStore for RecordingStore {
+        async fn persist_decision_event(&self, event: DecisionEvent) -> Result<PersistDecisionResponse> {
+            let event_id = event.id.clone();
+            self.persisted.lock().unwrap().push(event);
+            Ok(PersistDecisionResponse {
+                success: true,
+                storage_ref: format!("test://decisions/{}", event_id),
+                event_id,
+                persisted_at: Timestamp::now().to_string(),
+                error: None,
+            })
+        }
+
+        async fn query_decision_events(&self, _query: DecisionEventQuery) -> Result<DecisionEventPage> {
+            Ok(DecisionEventPage {
+                items: vec![],
+                total: 0,
+                page: 1,
+                page_size: 100,
+                total_pages: 0,
+            })
+        }
+
+        async fn get_decision_event(&self, event_id: &str) -> Result<DecisionEvent> {
+            Err(AppError::NotFound(format!("Decision event {} not found", event_id)))
+        }
+
+        async fn find_events_by_finding_id(&self, _organization_id: &str, _finding_id: &str) -> Result<Vec<DecisionEvent>> {
+            Ok(vec![])
+        }
+    }
+
+    fn policy_change_input() -> ChangeImpactInput {
+        ChangeImpactInput {
+            organization_id: "org-1".to_string(),
+            change_request: ChangeRequest {
+                change_id: "change-1".to_string(),
+                change_type: ChangeType::Update,
+                subject_type: ChangeSubjectType::Policy,
+                subject_id: "policy-1".to_string(),
+                description: "Tighten rate limits".to_string(),
+                timestamp: "2024-01-01T00:00:00Z".to_string(),
+                initiator: "user-1".to_string(),
+                previous_state: None,
+                new_state: None,
+                metadata: None,
+            },
+            scope: None,
+            historical_range: None,
+            include_downstream: None,
+            include_risk_projection: None,
+            baseline_ref: None,
+        }
+    }
+
+    fn api_context() -> ExecutionContext {
+        ExecutionContext {
+            request_id: None,
+            trace_id: None,
+            invoker: None,
+            source: InvocationSource::Api,
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_assessment_persists_exactly_one_decision_event() {
+        let store = RecordingStore::default();
+        let agent = ChangeImpactAgent::new(UpstreamConfig::default())
+            .unwrap()
+            .with_decision_store(Arc::new(store.clone()));
+
+        let output = agent.assess_change(policy_change_input(), api_context()).await.unwrap();
+
+        let persisted = store.persisted.lock().unwrap();
+        assert_eq!(persisted.len(), 1);
+        assert_eq!(persisted[0].id, output.decision_event.id);
+        assert_eq!(output.telemetry_status, None);
+    }
+
+    #[actix_web::test]
+    async fn test_agents_sharing_a_store_see_each_others_events() {
+        let store = RecordingStore::default();
+        let shared: Arc<dyn DecisionStore> = Arc::new(store.clone());
+        let agent = |store: Arc<dyn DecisionStore>| {
+            ChangeImpactAgent::new(UpstreamConfig::default())
+                .unwrap()
+                .with_decision_store(store)
+        };
+        let (first, second) = (agent(shared.clone()), agent(shared));
+
+        first.assess_change(policy_change_input(), api_context()).await.unwrap();
+        second.assess_change(policy_change_input(), api_context()).await.unwrap();
+
+        assert_eq!(store.persisted.lock().unwrap().len(), 2);
+    }
+
+    const TELEMETRY_ACCEPTED_BODY: &str =
+        r#"{"event_id": "tel-1", "timestamp": "2024-01-01T00:00:00Z", "acknowledged": true}"#;
+
+    /// Agent emitting telemetry to a mock Observatory answering `responses` in order
+    async fn agent_with_mock_observatory(responses: Vec<MockResponse>) -> ChangeImpactAgent {
+        let server = MockServer::start(responses).await;
+        let observatory = UpstreamConfig {
+            base_url: server.url().to_string(),
+            retry_config: super::super::RetryConfig {
+                max_retries: 0,
+                ..super::super::RetryConfig::default()
+            },
+            ..UpstreamConfig::default()
+        };
+
+        ChangeImpactAgent::with_consumers(UpstreamConfig::default(), None, None, None, Some(observatory))
+            .unwrap()
+            .with_decision_store(Arc::new(RecordingStore::default()))
+    }
+
+    fn telemetry_count(status: TelemetryStatus) -> u64 {
+        crate::metrics::AGENT_TELEMETRY_EMISSIONS_TOTAL
+            .with_label_values(&[AGENT_ID, status.as_str()])
+            .get()
+    }
+
+    #[tokio::test]
+    async fn test_rejected_span_degrades_telemetry_without_failing_assessment() {
+        // The event is accepted, then the span is rejected
+        let agent = agent_with_mock_observatory(vec![
+            MockResponse::new(200).with_body(TELEMETRY_ACCEPTED_BODY),
+            MockResponse::new(500).with_body("{}"),
+        ])
+        .await;
+        let degraded_before = telemetry_count(TelemetryStatus::Degraded);
+
+        let output = agent.assess_change(policy_change_input(), api_context()).await.unwrap();
+
+        assert_eq!(output.telemetry_status, Some(TelemetryStatus::Degraded));
+        assert!(output.telemetry_ref.ends_with("/tel-1"));
+        assert!(telemetry_count(TelemetryStatus::Degraded) > degraded_before);
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_observatory_fails_telemetry_without_failing_assessment() {
+        let agent = agent_with_mock_observatory(vec![MockResponse::new(503).with_body("{}")]).await;
+        let failed_before = telemetry_count(TelemetryStatus::Failed);
+
+        let output = agent.assess_change(policy_change_input(), api_context()).await.unwrap();
+
+        assert_eq!(output.telemetry_status, Some(TelemetryStatus::Failed));
+        assert!(output.telemetry_ref.ends_with(&output.decision_event.id));
+        assert!(telemetry_count(TelemetryStatus::Failed) > failed_before);
+
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(json["telemetry_status"], "failed");
+    }
 }
@@ -1,13 +1,18 @@
 //! CostOps Consumer Adapter
 //!
-//! Consumes cost summaries, projections, and detailed breakdowns
+//! Consumes cost summaries, projections, detailed breakdowns and budgets
 //! from the LLM-CostOps upstream service.
+//!
+//! Concurrent identical cost-summary requests are coalesced (singleflight) so a
+//! dashboard refresh fans out to a single upstream call per org and window.
 
-use super::{EcosystemConsumer, UpstreamConfig};
+use super::{EcosystemConsumer, HttpConsumer, UpstreamConfig};
 use crate::error::{AppError, Result};
 use async_trait::async_trait;
+use futures::future::{BoxFuture, FutureExt, Shared};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
 /// Cost summary from CostOps
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -112,6 +117,19 @@ pub struct CostAlert {
     pub triggered_at: String,
 }
 
+/// Active budget of an organization from CostOps
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CostBudget {
+    pub budget_id: String,
+    pub name: String,
+    pub amount: f64,
+    /// One of `daily`, `weekly`, `monthly`, `yearly`
+    pub period: String,
+    pub alert_threshold_percentage: f64,
+    /// Spend so far in the current period
+    pub current_spend: f64,
+}
+
 /// Type of cost alert
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -121,35 +139,86 @@ pub enum AlertType {
     Exceeded,
 }
 
+/// In-progress summary fetch shared by every caller awaiting the same key
+type InFlightSummary = Shared<BoxFuture<'static, std::result::Result<CostSummary, String>>>;
+
 /// Consumer adapter for LLM-CostOps
 pub struct CostOpsConsumer {
     config: UpstreamConfig,
-    client: reqwest::Client,
+    http: Arc<HttpConsumer>,
+    /// In-flight `get_cost_summary` calls keyed by org and window
+    in_flight: Arc<Mutex<HashMap<String, InFlightSummary>>>,
 }
 
 impl CostOpsConsumer {
     /// Create a new CostOps consumer
     pub fn new(config: UpstreamConfig) -> Result<Self> {
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_millis(config.timeout_ms))
-            .build()
-            .map_err(|e| AppError::Internal(format!("Failed to create HTTP client: {}", e)))?;
+        let http = Arc::new(HttpConsumer::new("CostOps", config.clone())?);
 
-        Ok(Self { config, client })
+        Ok(Self {
+            config,
+            http,
+            in_flight: Arc::new(Mutex::new(HashMap::new())),
+        })
     }
 
     /// Consume cost summary for an organization
+    ///
+    /// Concurrent calls for the same org and window share one upstream request
+    /// and all receive its result.
     pub async fn get_cost_summary(
         &self,
         organization_id: &str,
         period_start: &str,
         period_end: &str,
     ) -> Result<CostSummary> {
-        let url = format!(
-            "{}/api/v1/costs/summary?org_id={}&start={}&end={}",
-            self.config.base_url, organization_id, period_start, period_end
-        );
-        self.fetch_json(&url).await
+        let key = format!("{}|{}|{}", organization_id, period_start, period_end);
+
+        let flight = {
+            let mut in_flight = self.in_flight.lock().unwrap_or_else(|e| e.into_inner());
+            match in_flight.get(&key) {
+                Some(flight) => flight.clone(),
+                None => {
+                    let url = format!(
+                        "{}/api/v1/costs/summary?org_id={}&start={}&end={}",
+                        self.config.base_url, organization_id, period_start, period_end
+                    );
+                    let flight = Self::fetch_summary(
+                        self.http.clone(),
+                        self.in_flight.clone(),
+                        key.clone(),
+                        url,
+                    );
+                    in_flight.insert(key, flight.clone());
+                    flight
+                }
+            }
+        };
+
+        flight.await.map_err(AppError::Internal)
+    }
+
+    /// Build the shared upstream fetch; it removes its own map entry once the
+    /// response arrives so later calls start a fresh request
+    fn fetch_summary(
+        http: Arc<HttpConsumer>,
+        in_flight: Arc<Mutex<HashMap<String, InFlightSummary>>>,
+        key: String,
+        url: String,
+    ) -> InFlightSummary {
+        async move {
+            let result = http.get_json::<CostSummary>(&url).await.map_err(|e| match e {
+                AppError::Internal(msg) => msg,
+                other => other.to_string(),
+            });
+            in_flight
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .remove(&key);
+            result
+        }
+        .boxed()
+        .shared()
     }
 
     /// Consume cost projections
@@ -158,7 +227,7 @@ impl CostOpsConsumer {
             "{}/api/v1/costs/projection?org_id={}",
             self.config.base_url, organization_id
         );
-        self.fetch_json(&url).await
+        self.http.get_json(&url).await
     }
 
     /// Consume detailed cost breakdown
@@ -178,7 +247,16 @@ impl CostOpsConsumer {
             "{}/api/v1/costs/breakdown?org_id={}&granularity={}&start={}&end={}",
             self.config.base_url, organization_id, granularity_str, period_start, period_end
         );
-        self.fetch_json(&url).await
+        self.http.get_json(&url).await
+    }
+
+    /// Consume the active budgets of an organization
+    pub async fn get_budgets(&self, organization_id: &str) -> Result<Vec<CostBudget>> {
+        let url = format!(
+            "{}/api/v1/costs/budgets?org_id={}&active=true",
+            self.config.base_url, organization_id
+        );
+        self.http.get_json(&url).await
     }
 
     /// Consume active cost alerts
@@ -187,33 +265,7 @@ impl CostOpsConsumer {
             "{}/api/v1/costs/alerts?org_id={}",
             self.config.base_url, organization_id
         );
-        self.fetch_json(&url).await
-    }
-
-    /// Internal helper to fetch JSON from upstream
-    async fn fetch_json<T: for<'de> Deserialize<'de>>(&self, url: &str) -> Result<T> {
-        let mut request = self.client.get(url);
-
-        if let Some(ref api_key) = self.config.api_key {
-            request = request.header("Authorization", format!("Bearer {}", api_key));
-        }
-
-        let response = request
-            .send()
-            .await
-            .map_err(|e| AppError::Internal(format!("CostOps request failed: {}", e)))?;
-
-        if !response.status().is_success() {
-            return Err(AppError::Internal(format!(
-                "CostOps returned status: {}",
-                response.status()
-            )));
-        }
-
-        response
-            .json()
-            .await
-            .map_err(|e| AppError::Internal(format!("Failed to parse CostOps response: {}", e)))
+        self.http.get_json(&url).await
     }
 }
 
@@ -224,17 +276,14 @@ impl EcosystemConsumer for CostOpsConsumer {
     }
 
     async fn health_check(&self) -> Result<bool> {
-        let url = format!("{}/health", self.config.base_url);
-        match self.client.get(&url).send().await {
-            Ok(resp) => Ok(resp.status().is_success()),
-            Err(_) => Ok(false),
-        }
+        Ok(self.http.health_check().await)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::testing::{MockResponse, MockServer};
 
     #[test]
     fn test_cost_trend_serialization() {
@@ -256,4 +305,66 @@ mod tests {
         let json = serde_json::to_string(&granularity).unwrap();
         assert_eq!(json, "\"daily\"");
     }
+
+    const SUMMARY_BODY: &str = r#"{
+        "organization_id": "org-1",
+        "period_start": "2025-01-01",
+        "period_end": "2025-01-31",
+        "total_cost": 42.5,
+        "currency": "USD",
+        "cost_by_provider": {},
+        "cost_by_model": {},
+        "cost_by_team": {},
+        "request_count": 10,
+        "token_count": 1000
+    }"#;
+
+    #[tokio::test]
+    async fn test_budgets_are_decoded() {
+        let server = MockServer::start(vec![MockResponse::new(200).with_body(
+            r#"[{"budget_id": "b-1", "name": "Monthly", "amount": 500.0, "period": "monthly",
+                "alert_threshold_percentage": 80.0, "current_spend": 350.0}]"#,
+        )])
+        .await;
+        let consumer = CostOpsConsumer::new(UpstreamConfig {
+            base_url: server.url().to_string(),
+            ..UpstreamConfig::default()
+        })
+        .unwrap();
+
+        let budgets = consumer.get_budgets("org-1").await.unwrap();
+        assert_eq!(budgets.len(), 1);
+        assert_eq!(budgets[0].budget_id, "b-1");
+        assert_eq!(budgets[0].current_spend, 350.0);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_identical_summaries_share_one_upstream_call() {
+        let server = MockServer::start_with_delay(
+            vec![MockResponse::new(200).with_body(SUMMARY_BODY)],
+            std::time::Duration::from_millis(100),
+        )
+        .await;
+        let consumer = CostOpsConsumer::new(UpstreamConfig {
+            base_url: server.url().to_string(),
+            ..UpstreamConfig::default()
+        })
+        .unwrap();
+
+        let calls = (0..10).map(|_| consumer.get_cost_summary("org-1", "2025-01-01", "2025-01-31"));
+        let results = futures::future::join_all(calls).await;
+
+        assert_eq!(server.hits(), 1, "identical requests should coalesce");
+        for result in results {
+            assert_eq!(result.unwrap().total_cost, 42.5);
+        }
+        assert!(consumer.in_flight.lock().unwrap().is_empty());
+
+        // A different window is a different key
+        consumer
+            .get_cost_summary("org-1", "2025-02-01", "2025-02-28")
+            .await
+            .unwrap();
+        assert_eq!(server.hits(), 2);
+    }
 }
@@ -0,0 +1,325 @@
+//! In-memory DecisionEvent store for local development and tests
+//!
+//! Mirrors the persist/query interface of [`RuVectorConsumer`] without an
+//! upstream, so the agents can be run end to end on a laptop. Events live as
+//! long as the process; TTLs are accepted and ignored. Selected with
+//! `RUVECTOR_MODE=memory` (see [`decision_store_from_env`]) and only compiled
+//! with the `memory-store` feature, so production builds cannot fall back to it.
+//!
+//! [`RuVectorConsumer`]: super::ruvector::RuVectorConsumer
+//! [`decision_store_from_env`]: super::ruvector::decision_store_from_env
+
+use super::ruvector::{
+    find_finding, idempotency_key, DecisionEvent, DecisionEventPage, DecisionEventQuery,
+    DecisionStore, PersistDecisionResponse,
+};
+use super::EcosystemConsumer;
+use crate::error::{AppError, Result};
+use crate::timestamp::Timestamp;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Page size when a query does not ask for one, as ruvector-service applies
+const DEFAULT_QUERY_LIMIT: u32 = 100;
+
+#[derive(Default)]
+struct StoreState {
+    /// Events in the order they were persisted
+    events: Vec<DecisionEvent>,
+    /// Event id already stored under each idempotency key
+    keys: HashMap<String, String>,
+}
+
+#[derive(Default)]
+pub struct InMemoryDecisionStore {
+    state: Mutex<StoreState>,
+}
+
+impl InMemoryDecisionStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Persist a DecisionEvent; a repeated event returns the stored one's id
+    pub async fn persist_decision_event(&self, event: DecisionEvent) -> Result<PersistDecisionResponse> {
+        let mut state = self.lock()?;
+        let key = idempotency_key(&event);
+
+        let event_id = match state.keys.get(&key) {
+            Some(existing) => existing.clone(),
+            None => {
+                let event_id = event.id.clone();
+                state.keys.insert(key, event_id.clone());
+                state.events.push(event);
+                event_id
+            }
+        };
+
+        Ok(PersistDecisionResponse {
+            success: true,
+            storage_ref: format!("memory://decisions/{}", event_id),
+            event_id,
+            persisted_at: Timestamp::now().to_string(),
+            error: None,
+        })
+    }
+
+    /// Same as [`persist_decision_event`](Self::persist_decision_event); events never expire
+    pub async fn persist_decision_event_with_ttl(
+        &self,
+        event: DecisionEvent,
+        _ttl_days: u32,
+    ) -> Result<PersistDecisionResponse> {
+        self.persist_decision_event(event).await
+    }
+
+    /// Persist many DecisionEvents, returning results in input order
+    pub async fn persist_decision_events(
+        &self,
+        events: Vec<DecisionEvent>,
+    ) -> Result<Vec<PersistDecisionResponse>> {
+        let mut responses = Vec::with_capacity(events.len());
+        for event in events {
+            responses.push(self.persist_decision_event(event).await?);
+        }
+        Ok(responses)
+    }
+
+    /// Query DecisionEvents, newest first
+    pub async fn query_decision_events(&self, query: DecisionEventQuery) -> Result<DecisionEventPage> {
+        let state = self.lock()?;
+        let matching: Vec<&DecisionEvent> = state
+            .events
+            .iter()
+            .rev()
+            .filter(|e| e.organization_id == query.organization_id)
+            .filter(|e| query.agent_id.as_ref().is_none_or(|agent| &e.agent_id == agent))
+            .filter(|e| query.decision_type.as_ref().is_none_or(|t| &e.decision_type == t))
+            .filter(|e| {
+                query
+                    .time_range
+                    .as_ref()
+                    .is_none_or(|r| r.start <= e.timestamp && e.timestamp <= r.end)
+            })
+            .collect();
+
+        let limit = query.limit.unwrap_or(DEFAULT_QUERY_LIMIT).max(1);
+        let offset = query.offset.unwrap_or(0);
+        let total = matching.len() as u64;
+
+        Ok(DecisionEventPage {
+            items: matching
+                .into_iter()
+                .skip(offset as usize)
+                .take(limit as usize)
+                .cloned()
+                .collect(),
+            total,
+            page: offset / limit + 1,
+            page_size: limit,
+            total_pages: total.div_ceil(limit as u64) as u32,
+        })
+    }
+
+    /// Get a specific DecisionEvent by ID
+    pub async fn get_decision_event(&self, event_id: &str) -> Result<DecisionEvent> {
+        self.lock()?
+            .events
+            .iter()
+            .find(|e| e.id == event_id)
+            .cloned()
+            .ok_or_else(|| AppError::NotFound(format!("Decision event {} not found", event_id)))
+    }
+
+    /// Find the DecisionEvents whose outputs contain a finding id
+    pub async fn find_events_by_finding_id(
+        &self,
+        organization_id: &str,
+        finding_id: &str,
+    ) -> Result<Vec<DecisionEvent>> {
+        Ok(self
+            .lock()?
+            .events
+            .iter()
+            .filter(|e| e.organization_id == organization_id)
+            .filter(|e| find_finding(e, finding_id).is_some())
+            .cloned()
+            .collect())
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, StoreState>> {
+        self.state
+            .lock()
+            .map_err(|_| AppError::Internal("in-memory decision store lock poisoned".to_string()))
+    }
+}
+
+#[async_trait]
+impl EcosystemConsumer for InMemoryDecisionStore {
+    fn service_name(&self) -> &'static str {
+        "ruvector-service (in-memory)"
+    }
+
+    async fn health_check(&self) -> Result<bool> {
+        Ok(true)
+    }
+}
+
+#[async_trait]
+impl DecisionStore for InMemoryDecisionStore {
+    async fn persist_decision_event(&self, event: DecisionEvent) -> Result<PersistDecisionResponse> {
+        InMemoryDecisionStore::persist_decision_event(self, event).await
+    }
+
+    async fn query_decision_events(&self, query: DecisionEventQuery) -> Result<DecisionEventPage> {
+        InMemoryDecisionStore::query_decision_events(self, query).await
+    }
+
+    async fn get_decision_event(&self, event_id: &str) -> Result<DecisionEvent> {
+        InMemoryDecisionStore::get_decision_event(self, event_id).await
+    }
+
+    async fn find_events_by_finding_id(&self, organization_id: &str, finding_id: &str) -> Result<Vec<DecisionEvent>> {
+        InMemoryDecisionStore::find_events_by_finding_id(self, organization_id, finding_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::ruvector::{
+        create_decision_event, default_confidence, execution_ref_from_request, DateRange,
+        DecisionOutputs, FindingCategory, GovernanceDecisionType, GovernanceFinding,
+        GovernanceMetrics, GovernanceSeverity, InvocationSource, TrendDirection,
+    };
+
+    fn event(organization_id: &str, decision_type: GovernanceDecisionType, input: &str) -> DecisionEvent {
+        create_decision_event(
+            "governance-audit-agent",
+            "1.0.0",
+            decision_type,
+            organization_id,
+            DecisionOutputs {
+                summary: input.to_string(),
+                findings: vec![],
+                metrics: GovernanceMetrics {
+                    events_analyzed: 0,
+                    time_range: DateRange {
+                        start: "2024-01-01T00:00:00Z".parse().unwrap(),
+                        end: "2024-01-02T00:00:00Z".parse().unwrap(),
+                    },
+                    coverage_percentage: 100.0,
+                    policies_evaluated: 0,
+                    compliance_rate: 100.0,
+                    findings_by_severity: HashMap::new(),
+                    trend: TrendDirection::Stable,
+                },
+                recommendations: vec![],
+                data_refs: vec![],
+            },
+            default_confidence(0.9, 0.9),
+            vec![],
+            execution_ref_from_request(None, None, None, InvocationSource::Api),
+            &serde_json::json!({ "input": input }),
+        )
+    }
+
+    fn query(organization_id: &str) -> DecisionEventQuery {
+        DecisionEventQuery {
+            organization_id: organization_id.to_string(),
+            agent_id: None,
+            decision_type: None,
+            time_range: None,
+            limit: None,
+            offset: None,
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_persisted_events_round_trip_through_queries() {
+        let store = InMemoryDecisionStore::new();
+        let audit = event("org-1", GovernanceDecisionType::AuditSummary, "audit");
+        let impact = event("org-1", GovernanceDecisionType::ChangeImpact, "impact");
+        let other_org = event("org-2", GovernanceDecisionType::AuditSummary, "audit");
+
+        let response = store.persist_decision_event(audit.clone()).await.unwrap();
+        assert!(response.success);
+        assert_eq!(response.event_id, audit.id);
+        store
+            .persist_decision_events(vec![impact.clone(), other_org])
+            .await
+            .unwrap();
+
+        let fetched = store.get_decision_event(&audit.id).await.unwrap();
+        assert_eq!(fetched.outputs.summary, "audit");
+        assert_eq!(fetched.timestamp, audit.timestamp);
+
+        let page = store.query_decision_events(query("org-1")).await.unwrap();
+        let ids: Vec<&str> = page.items.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, vec![impact.id.as_str(), audit.id.as_str()]);
+        assert_eq!(page.total, 2);
+
+        let mut by_type = query("org-1");
+        by_type.decision_type = Some(GovernanceDecisionType::ChangeImpact);
+        let page = store.query_decision_events(by_type).await.unwrap();
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0].id, impact.id);
+
+        let mut paged = query("org-1");
+        paged.limit = Some(1);
+        paged.offset = Some(1);
+        let page = store.query_decision_events(paged).await.unwrap();
+        assert_eq!(page.items[0].id, audit.id);
+        assert_eq!((page.page, page.total_pages), (2, 2));
+
+        assert!(matches!(store.get_decision_event("missing").await, Err(AppError::NotFound(_))));
+    }
+
+    #[actix_web::test]
+    async fn test_repeated_persist_is_idempotent() {
+        let store = InMemoryDecisionStore::new();
+        let original = event("org-1", GovernanceDecisionType::AuditSummary, "audit");
+        let mut retry = original.clone();
+        retry.id = "evt-retry".to_string();
+
+        store.persist_decision_event(original.clone()).await.unwrap();
+        let response = store.persist_decision_event(retry).await.unwrap();
+
+        assert_eq!(response.event_id, original.id);
+        assert_eq!(store.query_decision_events(query("org-1")).await.unwrap().total, 1);
+    }
+
+    #[actix_web::test]
+    async fn test_time_range_and_finding_filters() {
+        let store = InMemoryDecisionStore::new();
+        let mut old = event("org-1", GovernanceDecisionType::AuditSummary, "old");
+        old.timestamp = "2024-01-01T00:00:00Z".parse().unwrap();
+        old.outputs.findings.push(GovernanceFinding {
+            id: "finding-1".to_string(),
+            category: FindingCategory::AuditGap,
+            severity: GovernanceSeverity::Low,
+            title: "Gap".to_string(),
+            description: "Gap".to_string(),
+            affected_resources: vec![],
+            evidence_refs: vec![],
+            first_detected: old.timestamp,
+            last_seen: old.timestamp,
+        });
+        let recent = event("org-1", GovernanceDecisionType::AuditSummary, "recent");
+        store.persist_decision_events(vec![old.clone(), recent]).await.unwrap();
+
+        let mut in_january = query("org-1");
+        in_january.time_range = Some(DateRange {
+            start: "2024-01-01T00:00:00Z".parse().unwrap(),
+            end: "2024-01-31T00:00:00Z".parse().unwrap(),
+        });
+        let page = store.query_decision_events(in_january).await.unwrap();
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0].id, old.id);
+
+        let found = store.find_events_by_finding_id("org-1", "finding-1").await.unwrap();
+        assert_eq!(found.len(), 1);
+        assert!(store.find_events_by_finding_id("org-2", "finding-1").await.unwrap().is_empty());
+    }
+}
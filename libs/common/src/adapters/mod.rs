@@ -20,6 +20,9 @@
 //! - Caching via infra cache module
 //! - Rate limiting via infra rate-limit module
 //! - Error handling via infra errors module
+//!
+//! All consumers share [`HttpConsumer`], which applies `retry_config`, the
+//! response cache and the rate limit uniformly to every upstream call.
 
 pub mod policy_engine;
 pub mod registry;
@@ -28,10 +31,16 @@ pub mod observatory;
 pub mod analytics_hub;
 pub mod ruvector;
 pub mod change_impact;
+#[cfg(feature = "memory-store")]
+pub mod memory_store;
 
-use crate::error::Result;
+use crate::error::{AppError, Result};
+use crate::utils::backoff_delay;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
 /// Common trait for all ecosystem consumer adapters
 #[async_trait]
@@ -59,6 +68,8 @@ pub struct UpstreamConfig {
     /// Connection timeout in milliseconds
     pub timeout_ms: u64,
     /// Number of retry attempts (Infra retry module compatible)
+    ///
+    /// Superseded by `retry_config.max_retries`, which `HttpConsumer` honors.
     pub retry_count: u32,
     /// Retry configuration from Infra module
     #[serde(default)]
@@ -69,6 +80,9 @@ pub struct UpstreamConfig {
     /// Rate limit configuration from Infra module
     #[serde(default)]
     pub rate_limit_config: RateLimitConfig,
+    /// Timeout and caching of `/health` probes
+    #[serde(default)]
+    pub health_check_config: HealthCheckConfig,
 }
 
 /// Retry configuration aligned with llm-infra-core retry module
@@ -137,6 +151,25 @@ impl Default for RateLimitConfig {
     }
 }
 
+/// Health probe settings, kept apart from `timeout_ms` so a hung upstream
+/// cannot stall a health check for the full request timeout
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthCheckConfig {
+    /// Time after which a probe reports the upstream unhealthy, in milliseconds
+    pub timeout_ms: u64,
+    /// How long a probe result is reused, in milliseconds; 0 disables reuse
+    pub cache_ttl_ms: u64,
+}
+
+impl Default for HealthCheckConfig {
+    fn default() -> Self {
+        Self {
+            timeout_ms: 2000,
+            cache_ttl_ms: 5000,
+        }
+    }
+}
+
 impl Default for UpstreamConfig {
     fn default() -> Self {
         Self {
@@ -147,6 +180,425 @@ impl Default for UpstreamConfig {
             retry_config: RetryConfig::default(),
             cache_config: CacheConfig::default(),
             rate_limit_config: RateLimitConfig::default(),
+            health_check_config: HealthCheckConfig::default(),
+        }
+    }
+}
+
+// ============================================================================
+// Shared HTTP Consumer
+// ============================================================================
+
+/// HTTP client wrapper applying the retry, cache and rate-limit settings of an
+/// `UpstreamConfig` uniformly across all consumer adapters
+///
+/// - Retries transport errors, `429` and `5xx` responses with exponential backoff
+/// - Caches successful GET responses per URL when `cache_config.enabled`
+/// - Waits for the next window when `rate_limit_config` is exhausted
+/// - Bounds and briefly reuses `/health` probes per `health_check_config`
+pub struct HttpConsumer {
+    /// Human-readable upstream name used in error messages
+    label: &'static str,
+    config: UpstreamConfig,
+    client: reqwest::Client,
+    cache: Mutex<HashMap<String, CachedResponse>>,
+    rate_window: Mutex<RateWindow>,
+    last_health: Mutex<Option<HealthProbe>>,
+}
+
+struct CachedResponse {
+    stored_at: Instant,
+    body: serde_json::Value,
+}
+
+struct HealthProbe {
+    checked_at: Instant,
+    healthy: bool,
+}
+
+struct RateWindow {
+    started_at: Instant,
+    count: u32,
+}
+
+/// Outcome of a single attempt, deciding whether another attempt is worthwhile
+enum AttemptError {
+    Retryable(AppError),
+    Fatal(AppError),
+}
+
+impl HttpConsumer {
+    /// Create a consumer for the given upstream
+    pub fn new(label: &'static str, config: UpstreamConfig) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(config.timeout_ms))
+            .build()
+            .map_err(|e| AppError::Internal(format!("Failed to create HTTP client: {}", e)))?;
+
+        Ok(Self {
+            label,
+            config,
+            client,
+            cache: Mutex::new(HashMap::new()),
+            rate_window: Mutex::new(RateWindow {
+                started_at: Instant::now(),
+                count: 0,
+            }),
+            last_health: Mutex::new(None),
+        })
+    }
+
+    /// Upstream configuration this consumer was built from
+    pub fn config(&self) -> &UpstreamConfig {
+        &self.config
+    }
+
+    /// GET a JSON document, served from cache when enabled and fresh
+    pub async fn get_json<T: for<'de> Deserialize<'de>>(&self, url: &str) -> Result<T> {
+        if let Some(body) = self.cached(url) {
+            return self.decode(body);
+        }
+
+        let body = self
+            .execute(|| self.authorized(self.client.get(url)))
+            .await?;
+        self.store(url, &body);
+        self.decode(body)
+    }
+
+    /// POST a JSON body and decode the JSON response; never cached
+    pub async fn post_json<T: Serialize, R: for<'de> Deserialize<'de>>(
+        &self,
+        url: &str,
+        body: &T,
+    ) -> Result<R> {
+        let body = self
+            .execute(|| self.authorized(self.client.post(url).json(body)))
+            .await?;
+        self.decode(body)
+    }
+
+    /// GET `{base_url}/health`, mapping any failure or timeout to `false`
+    ///
+    /// A result younger than `health_check_config.cache_ttl_ms` is returned
+    /// without contacting the upstream.
+    pub async fn health_check(&self) -> bool {
+        let health = &self.config.health_check_config;
+        let ttl = Duration::from_millis(health.cache_ttl_ms);
+        if let Some(probe) = self.last_health.lock().unwrap_or_else(|e| e.into_inner()).as_ref() {
+            if probe.checked_at.elapsed() < ttl {
+                return probe.healthy;
+            }
+        }
+
+        let url = format!("{}/health", self.config.base_url);
+        let healthy = match self
+            .client
+            .get(&url)
+            .timeout(Duration::from_millis(health.timeout_ms))
+            .send()
+            .await
+        {
+            Ok(resp) => resp.status().is_success(),
+            Err(_) => false,
+        };
+
+        *self.last_health.lock().unwrap_or_else(|e| e.into_inner()) = Some(HealthProbe {
+            checked_at: Instant::now(),
+            healthy,
+        });
+        healthy
+    }
+
+    fn authorized(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match self.config.api_key {
+            Some(ref api_key) => request.header("Authorization", format!("Bearer {}", api_key)),
+            None => request,
+        }
+    }
+
+    /// Run a request with rate limiting and retries
+    async fn execute<F>(&self, build: F) -> Result<serde_json::Value>
+    where
+        F: Fn() -> reqwest::RequestBuilder,
+    {
+        let retry = &self.config.retry_config;
+        let mut attempt = 0;
+
+        loop {
+            self.acquire_rate_slot().await;
+
+            match self.attempt(build()).await {
+                Ok(body) => return Ok(body),
+                Err(AttemptError::Fatal(e)) => return Err(e),
+                Err(AttemptError::Retryable(e)) => {
+                    if attempt >= retry.max_retries {
+                        return Err(e);
+                    }
+                    tokio::time::sleep(self.backoff_delay(attempt)).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    async fn attempt(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> std::result::Result<serde_json::Value, AttemptError> {
+        let response = request.send().await.map_err(|e| {
+            AttemptError::Retryable(AppError::Internal(format!(
+                "{} request failed: {}",
+                self.label, e
+            )))
+        })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error = AppError::Internal(format!("{} returned status: {}", self.label, status));
+            return Err(if status.is_server_error() || status.as_u16() == 429 {
+                AttemptError::Retryable(error)
+            } else {
+                AttemptError::Fatal(error)
+            });
         }
+
+        response.json().await.map_err(|e| {
+            AttemptError::Fatal(AppError::Internal(format!(
+                "Failed to parse {} response: {}",
+                self.label, e
+            )))
+        })
+    }
+
+    fn decode<T: for<'de> Deserialize<'de>>(&self, body: serde_json::Value) -> Result<T> {
+        serde_json::from_value(body).map_err(|e| {
+            AppError::Internal(format!("Failed to parse {} response: {}", self.label, e))
+        })
+    }
+
+    fn backoff_delay(&self, attempt: u32) -> Duration {
+        let retry = &self.config.retry_config;
+        Duration::from_millis(backoff_delay(
+            retry.initial_delay_ms,
+            retry.backoff_multiplier,
+            attempt,
+            retry.max_delay_ms,
+        ))
+    }
+
+    /// Block until a request slot is free in the current rate-limit window
+    async fn acquire_rate_slot(&self) {
+        let limits = &self.config.rate_limit_config;
+        if !limits.enabled {
+            return;
+        }
+
+        let window = Duration::from_secs(limits.window_seconds);
+        loop {
+            let wait = {
+                let mut state = self.rate_window.lock().unwrap_or_else(|e| e.into_inner());
+                let elapsed = state.started_at.elapsed();
+                if elapsed >= window {
+                    state.started_at = Instant::now();
+                    state.count = 0;
+                }
+
+                if state.count < limits.max_requests {
+                    state.count += 1;
+                    return;
+                }
+                window.saturating_sub(elapsed)
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    fn cached(&self, url: &str) -> Option<serde_json::Value> {
+        let cache_config = &self.config.cache_config;
+        if !cache_config.enabled {
+            return None;
+        }
+
+        let ttl = Duration::from_secs(cache_config.ttl_seconds);
+        let mut cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+        match cache.get(url) {
+            Some(entry) if entry.stored_at.elapsed() < ttl => Some(entry.body.clone()),
+            Some(_) => {
+                cache.remove(url);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn store(&self, url: &str, body: &serde_json::Value) {
+        let cache_config = &self.config.cache_config;
+        if !cache_config.enabled || cache_config.max_entries == 0 {
+            return;
+        }
+
+        let ttl = Duration::from_secs(cache_config.ttl_seconds);
+        let mut cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+        if cache.len() >= cache_config.max_entries && !cache.contains_key(url) {
+            cache.retain(|_, entry| entry.stored_at.elapsed() < ttl);
+        }
+        if cache.len() >= cache_config.max_entries && !cache.contains_key(url) {
+            // Evict the oldest entry
+            if let Some(oldest) = cache
+                .iter()
+                .min_by_key(|(_, entry)| entry.stored_at)
+                .map(|(key, _)| key.clone())
+            {
+                cache.remove(&oldest);
+            }
+        }
+
+        cache.insert(
+            url.to_string(),
+            CachedResponse {
+                stored_at: Instant::now(),
+                body: body.clone(),
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{MockResponse, MockServer};
+
+    fn fast_config(base_url: String) -> UpstreamConfig {
+        UpstreamConfig {
+            base_url,
+            timeout_ms: 2000,
+            retry_config: RetryConfig {
+                max_retries: 2,
+                initial_delay_ms: 1,
+                max_delay_ms: 5,
+                backoff_multiplier: 2.0,
+            },
+            ..UpstreamConfig::default()
+        }
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Payload {
+        value: u32,
+    }
+
+    #[tokio::test]
+    async fn test_retry_then_cache_hit() {
+        let server = MockServer::start(vec![
+            MockResponse::new(503).with_body("{}"),
+            MockResponse::new(200).with_body(r#"{"value":7}"#),
+        ])
+        .await;
+        let base_url = server.url().to_string();
+        let mut config = fast_config(base_url.clone());
+        config.cache_config.enabled = true;
+        let consumer = HttpConsumer::new("Test", config).unwrap();
+        let url = format!("{}/api/v1/thing", base_url);
+
+        let first: Payload = consumer.get_json(&url).await.unwrap();
+        assert_eq!(first, Payload { value: 7 });
+        assert_eq!(server.hits(), 2, "503 should be retried once");
+
+        let second: Payload = consumer.get_json(&url).await.unwrap();
+        assert_eq!(second, Payload { value: 7 });
+        assert_eq!(server.hits(), 2, "second call should be served from cache");
+    }
+
+    #[tokio::test]
+    async fn test_retries_exhausted_returns_error() {
+        let server = MockServer::start(vec![MockResponse::new(503).with_body("{}")]).await;
+        let base_url = server.url().to_string();
+        let consumer = HttpConsumer::new("Test", fast_config(base_url.clone())).unwrap();
+
+        let result: Result<Payload> = consumer.get_json(&format!("{}/x", base_url)).await;
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains("Test returned status: 503"));
+        assert_eq!(server.hits(), 3, "initial attempt plus two retries");
+    }
+
+    #[tokio::test]
+    async fn test_client_errors_are_not_retried() {
+        let server = MockServer::start(vec![MockResponse::new(404).with_body("{}")]).await;
+        let base_url = server.url().to_string();
+        let consumer = HttpConsumer::new("Test", fast_config(base_url.clone())).unwrap();
+
+        let result: Result<Payload> = consumer.get_json(&format!("{}/missing", base_url)).await;
+        assert!(result.is_err());
+        assert_eq!(server.hits(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_cache_disabled_always_fetches() {
+        let server = MockServer::start(vec![MockResponse::new(200).with_body(r#"{"value":1}"#)]).await;
+        let base_url = server.url().to_string();
+        let consumer = HttpConsumer::new("Test", fast_config(base_url.clone())).unwrap();
+        let url = format!("{}/x", base_url);
+
+        let _: Payload = consumer.get_json(&url).await.unwrap();
+        let _: Payload = consumer.get_json(&url).await.unwrap();
+        assert_eq!(server.hits(), 2);
+    }
+
+    #[test]
+    fn test_backoff_delay_is_capped() {
+        let config = UpstreamConfig {
+            retry_config: RetryConfig {
+                max_retries: 10,
+                initial_delay_ms: 100,
+                max_delay_ms: 1000,
+                backoff_multiplier: 2.0,
+            },
+            ..Default::default()
+        };
+        let consumer = HttpConsumer::new("Test", config).unwrap();
+
+        assert_eq!(consumer.backoff_delay(0), Duration::from_millis(100));
+        assert_eq!(consumer.backoff_delay(2), Duration::from_millis(400));
+        assert_eq!(consumer.backoff_delay(8), Duration::from_millis(1000));
+    }
+
+    #[tokio::test]
+    async fn test_health_check_timeout_reports_unhealthy() {
+        let server =
+            MockServer::start_with_delay(vec![MockResponse::new(200).with_body("{}")], Duration::from_millis(500))
+                .await;
+        let base_url = server.url().to_string();
+        let mut config = fast_config(base_url);
+        config.health_check_config = HealthCheckConfig {
+            timeout_ms: 50,
+            cache_ttl_ms: 0,
+        };
+        let consumer = HttpConsumer::new("Test", config).unwrap();
+
+        let started = Instant::now();
+        assert!(!consumer.health_check().await);
+        assert!(started.elapsed() < Duration::from_millis(500));
+    }
+
+    #[tokio::test]
+    async fn test_health_check_result_is_reused_within_ttl() {
+        let server = MockServer::start(vec![
+            MockResponse::new(200).with_body("{}"),
+            MockResponse::new(503).with_body("{}"),
+        ])
+        .await;
+        let base_url = server.url().to_string();
+        let consumer = HttpConsumer::new("Test", fast_config(base_url.clone())).unwrap();
+
+        assert!(consumer.health_check().await);
+        assert!(consumer.health_check().await);
+        assert_eq!(server.hits(), 1, "second probe should reuse the first");
+
+        let mut config = fast_config(base_url);
+        config.health_check_config.cache_ttl_ms = 0;
+        let uncached = HttpConsumer::new("Test", config).unwrap();
+        assert!(!uncached.health_check().await);
+        assert_eq!(server.hits(), 2);
     }
 }
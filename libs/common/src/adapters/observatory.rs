@@ -3,8 +3,8 @@
 //! Consumes telemetry events, trace spans, and system health indicators
 //! from the LLM-Observatory upstream service.
 
-use super::{EcosystemConsumer, UpstreamConfig};
-use crate::error::{AppError, Result};
+use super::{EcosystemConsumer, HttpConsumer, UpstreamConfig};
+use crate::error::Result;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -134,18 +134,15 @@ pub struct SystemHealthSummary {
 /// Consumer adapter for LLM-Observatory
 pub struct ObservatoryConsumer {
     config: UpstreamConfig,
-    client: reqwest::Client,
+    http: HttpConsumer,
 }
 
 impl ObservatoryConsumer {
     /// Create a new Observatory consumer
     pub fn new(config: UpstreamConfig) -> Result<Self> {
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_millis(config.timeout_ms))
-            .build()
-            .map_err(|e| AppError::Internal(format!("Failed to create HTTP client: {}", e)))?;
+        let http = HttpConsumer::new("Observatory", config.clone())?;
 
-        Ok(Self { config, client })
+        Ok(Self { config, http })
     }
 
     /// Consume recent telemetry events
@@ -167,7 +164,7 @@ impl ObservatoryConsumer {
             url.push_str(&format!("&type={}", type_str));
         }
 
-        self.fetch_json(&url).await
+        self.http.get_json(&url).await
     }
 
     /// Consume trace spans for a specific trace
@@ -176,7 +173,7 @@ impl ObservatoryConsumer {
             "{}/api/v1/traces/{}/spans",
             self.config.base_url, trace_id
         );
-        self.fetch_json(&url).await
+        self.http.get_json(&url).await
     }
 
     /// Consume trace spans within a time range
@@ -190,13 +187,13 @@ impl ObservatoryConsumer {
             "{}/api/v1/traces?org_id={}&from={}&to={}",
             self.config.base_url, organization_id, from_timestamp, to_timestamp
         );
-        self.fetch_json(&url).await
+        self.http.get_json(&url).await
     }
 
     /// Consume system health indicators
     pub async fn get_health_indicators(&self) -> Result<SystemHealthSummary> {
         let url = format!("{}/api/v1/health/summary", self.config.base_url);
-        self.fetch_json(&url).await
+        self.http.get_json(&url).await
     }
 
     /// Consume health indicator for a specific service
@@ -205,33 +202,7 @@ impl ObservatoryConsumer {
             "{}/api/v1/health/services/{}",
             self.config.base_url, service_name
         );
-        self.fetch_json(&url).await
-    }
-
-    /// Internal helper to fetch JSON from upstream
-    async fn fetch_json<T: for<'de> Deserialize<'de>>(&self, url: &str) -> Result<T> {
-        let mut request = self.client.get(url);
-
-        if let Some(ref api_key) = self.config.api_key {
-            request = request.header("Authorization", format!("Bearer {}", api_key));
-        }
-
-        let response = request
-            .send()
-            .await
-            .map_err(|e| AppError::Internal(format!("Observatory request failed: {}", e)))?;
-
-        if !response.status().is_success() {
-            return Err(AppError::Internal(format!(
-                "Observatory returned status: {}",
-                response.status()
-            )));
-        }
-
-        response
-            .json()
-            .await
-            .map_err(|e| AppError::Internal(format!("Failed to parse Observatory response: {}", e)))
+        self.http.get_json(&url).await
     }
 }
 
@@ -242,11 +213,7 @@ impl EcosystemConsumer for ObservatoryConsumer {
     }
 
     async fn health_check(&self) -> Result<bool> {
-        let url = format!("{}/health", self.config.base_url);
-        match self.client.get(&url).send().await {
-            Ok(resp) => Ok(resp.status().is_success()),
-            Err(_) => Ok(false),
-        }
+        Ok(self.http.health_check().await)
     }
 }
 
@@ -315,13 +282,13 @@ impl ObservatoryConsumer {
     /// Emit a telemetry event to Observatory
     pub async fn emit_telemetry(&self, request: EmitTelemetryRequest) -> Result<EmitTelemetryResponse> {
         let url = format!("{}/api/v1/telemetry/events", self.config.base_url);
-        self.post_json(&url, &request).await
+        self.http.post_json(&url, &request).await
     }
 
     /// Emit a trace span to Observatory
     pub async fn emit_span(&self, request: EmitSpanRequest) -> Result<EmitSpanResponse> {
         let url = format!("{}/api/v1/traces/spans", self.config.base_url);
-        self.post_json(&url, &request).await
+        self.http.post_json(&url, &request).await
     }
 
     /// Emit agent-specific telemetry for governance agents
@@ -354,36 +321,6 @@ impl ObservatoryConsumer {
 
         self.emit_telemetry(request).await
     }
-
-    /// Internal helper to POST JSON to upstream
-    async fn post_json<T, R>(&self, url: &str, body: &T) -> Result<R>
-    where
-        T: Serialize,
-        R: for<'de> Deserialize<'de>,
-    {
-        let mut request = self.client.post(url).json(body);
-
-        if let Some(ref api_key) = self.config.api_key {
-            request = request.header("Authorization", format!("Bearer {}", api_key));
-        }
-
-        let response = request
-            .send()
-            .await
-            .map_err(|e| AppError::Internal(format!("Observatory request failed: {}", e)))?;
-
-        if !response.status().is_success() {
-            return Err(AppError::Internal(format!(
-                "Observatory returned status: {}",
-                response.status()
-            )));
-        }
-
-        response
-            .json()
-            .await
-            .map_err(|e| AppError::Internal(format!("Failed to parse Observatory response: {}", e)))
-    }
 }
 
 #[cfg(test)]
@@ -3,8 +3,8 @@
 //! Consumes policy evaluation results, compliance rule states, and enforcement
 //! decisions from the LLM-Policy-Engine upstream service.
 
-use super::{EcosystemConsumer, UpstreamConfig};
-use crate::error::{AppError, Result};
+use super::{EcosystemConsumer, HttpConsumer, UpstreamConfig};
+use crate::error::Result;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -65,18 +65,15 @@ pub struct ComplianceStatus {
 /// Consumer adapter for LLM-Policy-Engine
 pub struct PolicyEngineConsumer {
     config: UpstreamConfig,
-    client: reqwest::Client,
+    http: HttpConsumer,
 }
 
 impl PolicyEngineConsumer {
     /// Create a new Policy Engine consumer
     pub fn new(config: UpstreamConfig) -> Result<Self> {
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_millis(config.timeout_ms))
-            .build()
-            .map_err(|e| AppError::Internal(format!("Failed to create HTTP client: {}", e)))?;
+        let http = HttpConsumer::new("Policy Engine", config.clone())?;
 
-        Ok(Self { config, client })
+        Ok(Self { config, http })
     }
 
     /// Consume policy evaluation results for a given context
@@ -92,7 +89,7 @@ impl PolicyEngineConsumer {
             limit.unwrap_or(100)
         );
 
-        self.fetch_json(&url).await
+        self.http.get_json(&url).await
     }
 
     /// Consume compliance rule states
@@ -106,7 +103,7 @@ impl PolicyEngineConsumer {
             organization_id
         );
 
-        self.fetch_json(&url).await
+        self.http.get_json(&url).await
     }
 
     /// Consume enforcement decisions for audit trail
@@ -125,33 +122,7 @@ impl PolicyEngineConsumer {
             url.push_str(&format!("&from={}", ts));
         }
 
-        self.fetch_json(&url).await
-    }
-
-    /// Internal helper to fetch JSON from upstream
-    async fn fetch_json<T: for<'de> Deserialize<'de>>(&self, url: &str) -> Result<T> {
-        let mut request = self.client.get(url);
-
-        if let Some(ref api_key) = self.config.api_key {
-            request = request.header("Authorization", format!("Bearer {}", api_key));
-        }
-
-        let response = request
-            .send()
-            .await
-            .map_err(|e| AppError::Internal(format!("Policy Engine request failed: {}", e)))?;
-
-        if !response.status().is_success() {
-            return Err(AppError::Internal(format!(
-                "Policy Engine returned status: {}",
-                response.status()
-            )));
-        }
-
-        response
-            .json()
-            .await
-            .map_err(|e| AppError::Internal(format!("Failed to parse Policy Engine response: {}", e)))
+        self.http.get_json(&url).await
     }
 }
 
@@ -162,11 +133,7 @@ impl EcosystemConsumer for PolicyEngineConsumer {
     }
 
     async fn health_check(&self) -> Result<bool> {
-        let url = format!("{}/health", self.config.base_url);
-        match self.client.get(&url).send().await {
-            Ok(resp) => Ok(resp.status().is_success()),
-            Err(_) => Ok(false),
-        }
+        Ok(self.http.health_check().await)
     }
 }
 
@@ -3,8 +3,8 @@
 //! Consumes model metadata, versioning information, and registry states
 //! from the LLM-Registry upstream service.
 
-use super::{EcosystemConsumer, UpstreamConfig};
-use crate::error::{AppError, Result};
+use super::{EcosystemConsumer, HttpConsumer, UpstreamConfig};
+use crate::error::Result;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -23,6 +23,19 @@ pub struct ModelMetadata {
     pub updated_at: String,
     pub status: ModelStatus,
     pub tags: HashMap<String, String>,
+    /// Scheduled or past deprecation date, if any
+    #[serde(default)]
+    pub deprecation_date: Option<String>,
+    /// Number of downstream deployments and integrations using this model
+    #[serde(default)]
+    pub downstream_usage_count: u64,
+}
+
+impl ModelMetadata {
+    /// Whether the model is deprecated or already retired
+    pub fn is_deprecated(&self) -> bool {
+        matches!(self.status, ModelStatus::Deprecated | ModelStatus::Retired)
+    }
 }
 
 /// Model parameters specification
@@ -83,24 +96,22 @@ pub enum RegistryHealth {
 /// Consumer adapter for LLM-Registry
 pub struct RegistryConsumer {
     config: UpstreamConfig,
-    client: reqwest::Client,
+    http: HttpConsumer,
 }
 
 impl RegistryConsumer {
     /// Create a new Registry consumer
     pub fn new(config: UpstreamConfig) -> Result<Self> {
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_millis(config.timeout_ms))
-            .build()
-            .map_err(|e| AppError::Internal(format!("Failed to create HTTP client: {}", e)))?;
+        let http = HttpConsumer::new("Registry", config.clone())?;
 
-        Ok(Self { config, client })
+        Ok(Self { config, http })
     }
 
-    /// Consume model metadata for a specific model
+    /// Consume model metadata for a specific model, including version,
+    /// provider, deprecation status and downstream usage
     pub async fn get_model_metadata(&self, model_id: &str) -> Result<ModelMetadata> {
         let url = format!("{}/api/v1/models/{}", self.config.base_url, model_id);
-        self.fetch_json(&url).await
+        self.http.get_json(&url).await
     }
 
     /// Consume all models for an organization
@@ -123,45 +134,19 @@ impl RegistryConsumer {
             url.push_str(&format!("?{}", params.join("&")));
         }
 
-        self.fetch_json(&url).await
+        self.http.get_json(&url).await
     }
 
     /// Consume version history for a model
     pub async fn get_model_versions(&self, model_id: &str) -> Result<Vec<ModelVersion>> {
         let url = format!("{}/api/v1/models/{}/versions", self.config.base_url, model_id);
-        self.fetch_json(&url).await
+        self.http.get_json(&url).await
     }
 
     /// Consume registry state summary
     pub async fn get_registry_state(&self) -> Result<RegistryState> {
         let url = format!("{}/api/v1/registry/state", self.config.base_url);
-        self.fetch_json(&url).await
-    }
-
-    /// Internal helper to fetch JSON from upstream
-    async fn fetch_json<T: for<'de> Deserialize<'de>>(&self, url: &str) -> Result<T> {
-        let mut request = self.client.get(url);
-
-        if let Some(ref api_key) = self.config.api_key {
-            request = request.header("Authorization", format!("Bearer {}", api_key));
-        }
-
-        let response = request
-            .send()
-            .await
-            .map_err(|e| AppError::Internal(format!("Registry request failed: {}", e)))?;
-
-        if !response.status().is_success() {
-            return Err(AppError::Internal(format!(
-                "Registry returned status: {}",
-                response.status()
-            )));
-        }
-
-        response
-            .json()
-            .await
-            .map_err(|e| AppError::Internal(format!("Failed to parse Registry response: {}", e)))
+        self.http.get_json(&url).await
     }
 }
 
@@ -172,11 +157,7 @@ impl EcosystemConsumer for RegistryConsumer {
     }
 
     async fn health_check(&self) -> Result<bool> {
-        let url = format!("{}/health", self.config.base_url);
-        match self.client.get(&url).send().await {
-            Ok(resp) => Ok(resp.status().is_success()),
-            Err(_) => Ok(false),
-        }
+        Ok(self.http.health_check().await)
     }
 }
 
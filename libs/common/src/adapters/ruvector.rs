@@ -15,8 +15,10 @@
 //! - All writes are async and non-blocking
 //! - Reads are eventually consistent
 
-use super::{EcosystemConsumer, UpstreamConfig};
+use super::{EcosystemConsumer, HttpConsumer, UpstreamConfig};
 use crate::error::{AppError, Result};
+use crate::timestamp::Timestamp;
+use crate::utils::hash_inputs;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
@@ -27,10 +29,22 @@ use uuid::Uuid;
 // DecisionEvent Types (aligned with packages/types)
 // ============================================================================
 
+/// Schema version written by `create_decision_event`
+///
+/// Compatibility policy: persisted events are never rewritten, so the struct
+/// must keep deserializing every earlier version. Fields added after v1 must
+/// be `Option` or carry a `#[serde(default)]`, existing fields are never
+/// renamed or retyped, and the version is bumped with each addition. Events
+/// written before versioning existed have no `schema_version` and read as 0.
+pub const DECISION_EVENT_SCHEMA_VERSION: u16 = 1;
+
 /// DecisionEvent - Core schema for all agent decisions
 /// Persisted to ruvector-service for audit trail
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DecisionEvent {
+    /// Schema version the event was written with (0 = unversioned)
+    #[serde(default)]
+    pub schema_version: u16,
     /// Unique identifier for this decision event
     pub id: String,
     /// Agent identifier (e.g., "governance-audit-agent")
@@ -50,10 +64,11 @@ pub struct DecisionEvent {
     /// Reference to execution context
     pub execution_ref: ExecutionReference,
     /// UTC timestamp of decision
-    pub timestamp: String,
+    pub timestamp: Timestamp,
     /// Organization context
     pub organization_id: String,
     /// Optional correlation ID for tracing across systems
+    #[serde(default)]
     pub correlation_id: Option<String>,
 }
 
@@ -102,8 +117,8 @@ pub struct GovernanceFinding {
     pub description: String,
     pub affected_resources: Vec<String>,
     pub evidence_refs: Vec<String>,
-    pub first_detected: String,
-    pub last_seen: String,
+    pub first_detected: Timestamp,
+    pub last_seen: Timestamp,
 }
 
 /// Finding category
@@ -119,8 +134,8 @@ pub enum FindingCategory {
     CostAnomaly,
 }
 
-/// Governance severity levels
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+/// Governance severity levels, ordered from least to most severe
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 #[serde(rename_all = "snake_case")]
 pub enum GovernanceSeverity {
     Info,
@@ -152,8 +167,8 @@ pub struct GovernanceMetrics {
 /// Date range for queries
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DateRange {
-    pub start: String,
-    pub end: String,
+    pub start: Timestamp,
+    pub end: Timestamp,
 }
 
 /// Trend direction
@@ -304,6 +319,42 @@ pub struct PersistDecisionResponse {
     pub event_id: String,
     pub persisted_at: String,
     pub storage_ref: String,
+    /// Why the event was not persisted, when `success` is false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Upper bound on events sent in one batched persist request
+pub const MAX_DECISION_BATCH: usize = 100;
+
+/// Days a DecisionEvent is retained when persisted without an explicit TTL
+pub const DEFAULT_RETENTION_DAYS: u32 = 365;
+
+/// Request to persist several DecisionEvents in one call
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchPersistDecisionRequest {
+    pub items: Vec<PersistDecisionRequest>,
+}
+
+/// Per-item outcome of a batched persist, keyed by position in the batch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchPersistItemResult {
+    pub index: usize,
+    pub success: bool,
+    #[serde(default)]
+    pub event_id: Option<String>,
+    #[serde(default)]
+    pub persisted_at: Option<String>,
+    #[serde(default)]
+    pub storage_ref: Option<String>,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+/// Response from a batched persist operation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchPersistDecisionResponse {
+    pub results: Vec<BatchPersistItemResult>,
 }
 
 /// Query for retrieving DecisionEvents
@@ -337,18 +388,15 @@ pub struct DecisionEventPage {
 /// All persistence flows through this adapter to ruvector-service.
 pub struct RuVectorConsumer {
     config: UpstreamConfig,
-    client: reqwest::Client,
+    http: HttpConsumer,
 }
 
 impl RuVectorConsumer {
     /// Create a new RuVector consumer
     pub fn new(config: UpstreamConfig) -> Result<Self> {
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_millis(config.timeout_ms))
-            .build()
-            .map_err(|e| AppError::Internal(format!("Failed to create HTTP client: {}", e)))?;
+        let http = HttpConsumer::new("RuVector", config.clone())?;
 
-        Ok(Self { config, client })
+        Ok(Self { config, http })
     }
 
     /// Persist a DecisionEvent to ruvector-service
@@ -356,16 +404,14 @@ impl RuVectorConsumer {
     /// This is an async, non-blocking write with exactly-once semantics.
     /// The idempotency_key ensures duplicate writes are safely ignored.
     pub async fn persist_decision_event(&self, event: DecisionEvent) -> Result<PersistDecisionResponse> {
-        let idempotency_key = self.generate_idempotency_key(&event);
-
         let request = PersistDecisionRequest {
+            idempotency_key: idempotency_key(&event),
             event,
-            idempotency_key,
-            ttl_days: Some(365), // 1 year retention by default
+            ttl_days: Some(DEFAULT_RETENTION_DAYS),
         };
 
         let url = format!("{}/api/v1/decisions", self.config.base_url);
-        self.post_json(&url, &request).await
+        self.http.post_json(&url, &request).await
     }
 
     /// Persist with custom TTL for data retention compliance
@@ -374,16 +420,52 @@ impl RuVectorConsumer {
         event: DecisionEvent,
         ttl_days: u32,
     ) -> Result<PersistDecisionResponse> {
-        let idempotency_key = self.generate_idempotency_key(&event);
-
         let request = PersistDecisionRequest {
+            idempotency_key: idempotency_key(&event),
             event,
-            idempotency_key,
             ttl_days: Some(ttl_days),
         };
 
         let url = format!("{}/api/v1/decisions", self.config.base_url);
-        self.post_json(&url, &request).await
+        self.http.post_json(&url, &request).await
+    }
+
+    /// Persist many DecisionEvents, batching up to `MAX_DECISION_BATCH` per call
+    ///
+    /// Each event keeps its own idempotency key, so retrying a batch never
+    /// duplicates events that were already written. Results are returned in
+    /// input order; an event that failed, or whose whole batch failed after
+    /// retries, is reported with `success: false` instead of failing the call.
+    pub async fn persist_decision_events(
+        &self,
+        events: Vec<DecisionEvent>,
+    ) -> Result<Vec<PersistDecisionResponse>> {
+        let url = format!("{}/api/v1/decisions/batch", self.config.base_url);
+        let mut responses = Vec::with_capacity(events.len());
+
+        for chunk in events.chunks(MAX_DECISION_BATCH) {
+            let request = BatchPersistDecisionRequest {
+                items: chunk
+                    .iter()
+                    .map(|event| PersistDecisionRequest {
+                        event: event.clone(),
+                        idempotency_key: idempotency_key(event),
+                        ttl_days: Some(DEFAULT_RETENTION_DAYS),
+                    })
+                    .collect(),
+            };
+
+            match self.http.post_json::<_, BatchPersistDecisionResponse>(&url, &request).await {
+                Ok(batch) => responses.extend(merge_batch_results(chunk, batch.results)),
+                Err(e) => {
+                    tracing::warn!("Batch persist of {} decision events failed: {}", chunk.len(), e);
+                    let error = e.to_string();
+                    responses.extend(chunk.iter().map(|event| failed_persist(event, error.clone())));
+                }
+            }
+        }
+
+        Ok(responses)
     }
 
     /// Query DecisionEvents from ruvector-service
@@ -391,16 +473,14 @@ impl RuVectorConsumer {
         &self,
         query: DecisionEventQuery,
     ) -> Result<DecisionEventPage> {
-        let mut url = format!(
-            "{}/api/v1/decisions?organization_id={}&limit={}&offset={}",
-            self.config.base_url,
-            query.organization_id,
-            query.limit.unwrap_or(100),
-            query.offset.unwrap_or(0)
-        );
+        let mut params = vec![
+            ("organization_id", query.organization_id.clone()),
+            ("limit", query.limit.unwrap_or(100).to_string()),
+            ("offset", query.offset.unwrap_or(0).to_string()),
+        ];
 
         if let Some(ref agent_id) = query.agent_id {
-            url.push_str(&format!("&agent_id={}", agent_id));
+            params.push(("agent_id", agent_id.clone()));
         }
 
         if let Some(ref decision_type) = query.decision_type {
@@ -408,20 +488,52 @@ impl RuVectorConsumer {
                 .unwrap()
                 .trim_matches('"')
                 .to_string();
-            url.push_str(&format!("&decision_type={}", type_str));
+            params.push(("decision_type", type_str));
         }
 
         if let Some(ref time_range) = query.time_range {
-            url.push_str(&format!("&from={}&to={}", time_range.start, time_range.end));
+            params.push(("from", time_range.start.to_string()));
+            params.push(("to", time_range.end.to_string()));
         }
 
-        self.fetch_json(&url).await
+        let url = self.decisions_url(&params)?;
+        self.http.get_json(&url).await
     }
 
     /// Get a specific DecisionEvent by ID
     pub async fn get_decision_event(&self, event_id: &str) -> Result<DecisionEvent> {
         let url = format!("{}/api/v1/decisions/{}", self.config.base_url, event_id);
-        self.fetch_json(&url).await
+        self.http.get_json(&url).await
+    }
+
+    /// Find the DecisionEvents whose outputs contain a finding id
+    ///
+    /// Results are re-checked locally so an upstream that ignores the
+    /// `finding_id` filter cannot return unrelated events.
+    pub async fn find_events_by_finding_id(
+        &self,
+        organization_id: &str,
+        finding_id: &str,
+    ) -> Result<Vec<DecisionEvent>> {
+        let url = self.decisions_url(&[
+            ("organization_id", organization_id.to_string()),
+            ("finding_id", finding_id.to_string()),
+        ])?;
+
+        let page: DecisionEventPage = self.http.get_json(&url).await?;
+        Ok(page
+            .items
+            .into_iter()
+            .filter(|event| event.organization_id == organization_id)
+            .filter(|event| find_finding(event, finding_id).is_some())
+            .collect())
+    }
+
+    /// `/api/v1/decisions` with `params` percent-encoded into the query string
+    fn decisions_url(&self, params: &[(&str, String)]) -> Result<String> {
+        reqwest::Url::parse_with_params(&format!("{}/api/v1/decisions", self.config.base_url), params)
+            .map(String::from)
+            .map_err(|e| AppError::Internal(format!("Invalid ruvector-service URL: {}", e)))
     }
 
     /// Get DecisionEvents for a specific agent
@@ -444,72 +556,120 @@ impl RuVectorConsumer {
         let page = self.query_decision_events(query).await?;
         Ok(page.items)
     }
+}
+
+/// Idempotency key of an event, derived from its content
+pub(crate) fn idempotency_key(event: &DecisionEvent) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(&event.agent_id);
+    hasher.update(&event.agent_version);
+    hasher.update(&event.inputs_hash);
+    hasher.update(event.timestamp.to_string());
+    hasher.update(&event.organization_id);
+    format!("{:x}", hasher.finalize())
+}
+
+/// The finding with `finding_id` in an event's outputs, if any
+pub fn find_finding<'a>(event: &'a DecisionEvent, finding_id: &str) -> Option<&'a GovernanceFinding> {
+    event.outputs.findings.iter().find(|f| f.id == finding_id)
+}
 
-    /// Generate idempotency key from event content
-    fn generate_idempotency_key(&self, event: &DecisionEvent) -> String {
-        let mut hasher = Sha256::new();
-        hasher.update(&event.agent_id);
-        hasher.update(&event.agent_version);
-        hasher.update(&event.inputs_hash);
-        hasher.update(&event.timestamp);
-        hasher.update(&event.organization_id);
-        format!("{:x}", hasher.finalize())
+fn failed_persist(event: &DecisionEvent, error: String) -> PersistDecisionResponse {
+    PersistDecisionResponse {
+        success: false,
+        event_id: event.id.clone(),
+        persisted_at: String::new(),
+        storage_ref: String::new(),
+        error: Some(error),
     }
+}
 
-    /// Internal helper to fetch JSON from upstream
-    async fn fetch_json<T: for<'de> Deserialize<'de>>(&self, url: &str) -> Result<T> {
-        let mut request = self.client.get(url);
+/// Align per-item batch results with the events sent, in input order
+fn merge_batch_results(
+    events: &[DecisionEvent],
+    results: Vec<BatchPersistItemResult>,
+) -> Vec<PersistDecisionResponse> {
+    let mut by_index: HashMap<usize, BatchPersistItemResult> =
+        results.into_iter().map(|r| (r.index, r)).collect();
+
+    events
+        .iter()
+        .enumerate()
+        .map(|(index, event)| match by_index.remove(&index) {
+            Some(result) if result.success => PersistDecisionResponse {
+                success: true,
+                event_id: result.event_id.unwrap_or_else(|| event.id.clone()),
+                persisted_at: result.persisted_at.unwrap_or_default(),
+                storage_ref: result.storage_ref.unwrap_or_default(),
+                error: None,
+            },
+            Some(result) => failed_persist(
+                event,
+                result.error.unwrap_or_else(|| "rejected by ruvector-service".to_string()),
+            ),
+            None => failed_persist(event, "no result returned for event".to_string()),
+        })
+        .collect()
+}
 
-        if let Some(ref api_key) = self.config.api_key {
-            request = request.header("Authorization", format!("Bearer {}", api_key));
-        }
+/// Environment variable selecting where DecisionEvents are persisted
+pub const RUVECTOR_MODE_ENV: &str = "RUVECTOR_MODE";
 
-        let response = request
-            .send()
-            .await
-            .map_err(|e| AppError::Internal(format!("RuVector request failed: {}", e)))?;
+/// Store DecisionEvents are persisted to and read back from
+///
+/// Implemented by [`RuVectorConsumer`]; agents hold a `Box<dyn DecisionStore>`
+/// so tests and local development can substitute another store.
+#[async_trait]
+pub trait DecisionStore: EcosystemConsumer {
+    async fn persist_decision_event(&self, event: DecisionEvent) -> Result<PersistDecisionResponse>;
 
-        if !response.status().is_success() {
-            return Err(AppError::Internal(format!(
-                "RuVector returned status: {}",
-                response.status()
-            )));
-        }
+    async fn query_decision_events(&self, query: DecisionEventQuery) -> Result<DecisionEventPage>;
 
-        response
-            .json()
-            .await
-            .map_err(|e| AppError::Internal(format!("Failed to parse RuVector response: {}", e)))
+    async fn get_decision_event(&self, event_id: &str) -> Result<DecisionEvent>;
+
+    async fn find_events_by_finding_id(&self, organization_id: &str, finding_id: &str) -> Result<Vec<DecisionEvent>>;
+}
+
+#[async_trait]
+impl DecisionStore for RuVectorConsumer {
+    async fn persist_decision_event(&self, event: DecisionEvent) -> Result<PersistDecisionResponse> {
+        RuVectorConsumer::persist_decision_event(self, event).await
     }
 
-    /// Internal helper to POST JSON to upstream
-    async fn post_json<T: Serialize, R: for<'de> Deserialize<'de>>(
-        &self,
-        url: &str,
-        body: &T,
-    ) -> Result<R> {
-        let mut request = self.client.post(url).json(body);
+    async fn query_decision_events(&self, query: DecisionEventQuery) -> Result<DecisionEventPage> {
+        RuVectorConsumer::query_decision_events(self, query).await
+    }
 
-        if let Some(ref api_key) = self.config.api_key {
-            request = request.header("Authorization", format!("Bearer {}", api_key));
-        }
+    async fn get_decision_event(&self, event_id: &str) -> Result<DecisionEvent> {
+        RuVectorConsumer::get_decision_event(self, event_id).await
+    }
 
-        let response = request
-            .send()
-            .await
-            .map_err(|e| AppError::Internal(format!("RuVector request failed: {}", e)))?;
+    async fn find_events_by_finding_id(&self, organization_id: &str, finding_id: &str) -> Result<Vec<DecisionEvent>> {
+        RuVectorConsumer::find_events_by_finding_id(self, organization_id, finding_id).await
+    }
+}
 
-        if !response.status().is_success() {
-            return Err(AppError::Internal(format!(
-                "RuVector returned status: {}",
-                response.status()
-            )));
-        }
+/// Store selected by `RUVECTOR_MODE`; `config` is used for ruvector-service
+///
+/// `RUVECTOR_MODE=memory` selects the in-process store of the `memory-store`
+/// feature for local development.
+pub fn decision_store_from_env(config: UpstreamConfig) -> Result<Box<dyn DecisionStore>> {
+    decision_store_for_mode(std::env::var(RUVECTOR_MODE_ENV).ok().as_deref(), config)
+}
 
-        response
-            .json()
-            .await
-            .map_err(|e| AppError::Internal(format!("Failed to parse RuVector response: {}", e)))
+fn decision_store_for_mode(mode: Option<&str>, config: UpstreamConfig) -> Result<Box<dyn DecisionStore>> {
+    match mode.map(str::trim) {
+        #[cfg(feature = "memory-store")]
+        Some(mode) if mode.eq_ignore_ascii_case("memory") => {
+            tracing::warn!("Persisting decision events in memory; they are lost on restart");
+            Ok(Box::new(super::memory_store::InMemoryDecisionStore::new()))
+        }
+        #[cfg(not(feature = "memory-store"))]
+        Some(mode) if mode.eq_ignore_ascii_case("memory") => Err(AppError::Internal(format!(
+            "{}=memory requires building with the memory-store feature",
+            RUVECTOR_MODE_ENV
+        ))),
+        _ => Ok(Box::new(RuVectorConsumer::new(config)?)),
     }
 }
 
@@ -520,11 +680,7 @@ impl EcosystemConsumer for RuVectorConsumer {
     }
 
     async fn health_check(&self) -> Result<bool> {
-        let url = format!("{}/health", self.config.base_url);
-        match self.client.get(&url).send().await {
-            Ok(resp) => Ok(resp.status().is_success()),
-            Err(_) => Ok(false),
-        }
+        Ok(self.http.health_check().await)
     }
 }
 
@@ -544,12 +700,10 @@ pub fn create_decision_event(
     execution_ref: ExecutionReference,
     inputs: &impl Serialize,
 ) -> DecisionEvent {
-    let inputs_json = serde_json::to_string(inputs).unwrap_or_default();
-    let mut hasher = Sha256::new();
-    hasher.update(inputs_json.as_bytes());
-    let inputs_hash = format!("{:x}", hasher.finalize());
+    let inputs_hash = hash_inputs(inputs);
 
     DecisionEvent {
+        schema_version: DECISION_EVENT_SCHEMA_VERSION,
         id: Uuid::new_v4().to_string(),
         agent_id: agent_id.to_string(),
         agent_version: agent_version.to_string(),
@@ -559,16 +713,37 @@ pub fn create_decision_event(
         confidence,
         constraints_applied,
         execution_ref,
-        timestamp: chrono::Utc::now().to_rfc3339(),
+        timestamp: Timestamp::now(),
         organization_id: organization_id.to_string(),
         correlation_id: None,
     }
 }
 
-/// Create default confidence for simple audits
+/// Create default confidence for simple audits (equal-weight blend)
 pub fn default_confidence(completeness: f64, certainty: f64) -> DecisionConfidence {
+    weighted_confidence(completeness, certainty, 0.5, 0.5)
+}
+
+/// Create confidence whose overall score is a weighted blend
+///
+/// Weights are normalized, so `(1.0, 3.0)` and `(0.25, 0.75)` are equivalent.
+/// Non-positive or non-finite weight totals fall back to the equal blend.
+pub fn weighted_confidence(
+    completeness: f64,
+    certainty: f64,
+    w_completeness: f64,
+    w_certainty: f64,
+) -> DecisionConfidence {
+    let (w_completeness, w_certainty) = (w_completeness.max(0.0), w_certainty.max(0.0));
+    let total = w_completeness + w_certainty;
+    let overall = if total > 0.0 && total.is_finite() {
+        (completeness * w_completeness + certainty * w_certainty) / total
+    } else {
+        (completeness + certainty) / 2.0
+    };
+
     DecisionConfidence {
-        overall: (completeness + certainty) / 2.0,
+        overall,
         completeness,
         certainty,
         bands: vec![],
@@ -596,6 +771,7 @@ pub fn execution_ref_from_request(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::testing::{MockResponse, MockServer};
 
     #[test]
     fn test_decision_type_serialization() {
@@ -626,8 +802,8 @@ mod tests {
             metrics: GovernanceMetrics {
                 events_analyzed: 100,
                 time_range: DateRange {
-                    start: "2024-01-01T00:00:00Z".to_string(),
-                    end: "2024-01-02T00:00:00Z".to_string(),
+                    start: "2024-01-01T00:00:00Z".parse().unwrap(),
+                    end: "2024-01-02T00:00:00Z".parse().unwrap(),
                 },
                 coverage_percentage: 95.0,
                 policies_evaluated: 10,
@@ -661,6 +837,226 @@ mod tests {
 
         assert_eq!(event.agent_id, "governance-audit-agent");
         assert_eq!(event.agent_version, "1.0.0");
+        assert_eq!(event.schema_version, DECISION_EVENT_SCHEMA_VERSION);
         assert!(!event.inputs_hash.is_empty());
     }
+
+    #[test]
+    fn test_weighted_confidence_blend() {
+        let confidence = weighted_confidence(0.6, 0.9, 0.25, 0.75);
+        assert!((confidence.overall - 0.825).abs() < 1e-9);
+        assert_eq!(confidence.completeness, 0.6);
+        assert_eq!(confidence.certainty, 0.9);
+
+        // Weights are relative
+        let scaled = weighted_confidence(0.6, 0.9, 1.0, 3.0);
+        assert!((scaled.overall - confidence.overall).abs() < 1e-9);
+
+        // Degenerate weights fall back to the equal blend
+        let fallback = weighted_confidence(0.6, 0.9, 0.0, -1.0);
+        assert!((fallback.overall - 0.75).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_default_confidence_is_equal_weight() {
+        let default = default_confidence(0.4, 0.8);
+        let equal = weighted_confidence(0.4, 0.8, 0.5, 0.5);
+        assert!((default.overall - 0.6).abs() < 1e-9);
+        assert!((default.overall - equal.overall).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_unversioned_event_deserializes() {
+        // Shape persisted before schema_version existed; correlation_id also absent
+        let v0 = serde_json::json!({
+            "id": "evt-legacy",
+            "agent_id": "governance-audit-agent",
+            "agent_version": "0.9.0",
+            "decision_type": "audit_summary",
+            "inputs_hash": "abc123",
+            "outputs": {
+                "summary": "legacy audit",
+                "findings": [],
+                "metrics": {
+                    "events_analyzed": 1,
+                    "time_range": {"start": "2024-01-01T00:00:00Z", "end": "2024-01-02T00:00:00Z"},
+                    "coverage_percentage": 100.0,
+                    "policies_evaluated": 0,
+                    "compliance_rate": 100.0,
+                    "findings_by_severity": {},
+                    "trend": "stable"
+                },
+                "recommendations": [],
+                "data_refs": []
+            },
+            "confidence": serde_json::to_value(default_confidence(0.9, 0.9)).unwrap(),
+            "constraints_applied": [],
+            "execution_ref": {
+                "execution_id": "exec-1",
+                "request_id": null,
+                "trace_id": null,
+                "span_id": null,
+                "source": "api",
+                "invoker": null
+            },
+            "timestamp": "2024-01-02T00:00:00Z",
+            "organization_id": "org-123"
+        });
+
+        let event: DecisionEvent = serde_json::from_value(v0).unwrap();
+        assert_eq!(event.schema_version, 0);
+        assert_eq!(event.id, "evt-legacy");
+        assert!(event.correlation_id.is_none());
+
+        // Round-tripping writes the version it was read with
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["schema_version"], 0);
+    }
+
+    #[test]
+    fn test_store_follows_ruvector_mode() {
+        let store = |mode| decision_store_for_mode(mode, UpstreamConfig::default());
+
+        assert_eq!(store(None).unwrap().service_name(), "ruvector-service");
+        assert_eq!(store(Some("remote")).unwrap().service_name(), "ruvector-service");
+        #[cfg(feature = "memory-store")]
+        assert_eq!(store(Some("Memory")).unwrap().service_name(), "ruvector-service (in-memory)");
+        #[cfg(not(feature = "memory-store"))]
+        assert!(store(Some("memory")).is_err());
+    }
+
+    #[test]
+    fn test_malformed_timestamps_fail_deserialization() {
+        let event = serde_json::to_value(sample_event("evt-1")).unwrap();
+
+        let mut bad_event = event.clone();
+        bad_event["timestamp"] = serde_json::json!("not a timestamp");
+        assert!(serde_json::from_value::<DecisionEvent>(bad_event).is_err());
+
+        let mut bad_range = event.clone();
+        bad_range["outputs"]["metrics"]["time_range"]["end"] = serde_json::json!("2024-01-32");
+        assert!(serde_json::from_value::<DecisionEvent>(bad_range).is_err());
+
+        let finding = serde_json::json!({
+            "id": "finding-1",
+            "category": "audit_gap",
+            "severity": "low",
+            "title": "Gap",
+            "description": "Gap",
+            "affected_resources": [],
+            "evidence_refs": [],
+            "first_detected": "2024-01-01T00:00:00Z",
+            "last_seen": "yesterday"
+        });
+        assert!(serde_json::from_value::<GovernanceFinding>(finding).is_err());
+
+        assert!(serde_json::from_value::<DecisionEvent>(event).is_ok());
+    }
+
+    fn sample_event(id: &str) -> DecisionEvent {
+        let mut event = create_decision_event(
+            "governance-audit-agent",
+            "1.0.0",
+            GovernanceDecisionType::AuditSummary,
+            "org-123",
+            DecisionOutputs {
+                summary: id.to_string(),
+                findings: vec![],
+                metrics: GovernanceMetrics {
+                    events_analyzed: 0,
+                    time_range: DateRange {
+                        start: "2024-01-01T00:00:00Z".parse().unwrap(),
+                        end: "2024-01-02T00:00:00Z".parse().unwrap(),
+                    },
+                    coverage_percentage: 0.0,
+                    policies_evaluated: 0,
+                    compliance_rate: 100.0,
+                    findings_by_severity: HashMap::new(),
+                    trend: TrendDirection::Stable,
+                },
+                recommendations: vec![],
+                data_refs: vec![],
+            },
+            default_confidence(0.9, 0.9),
+            vec![],
+            execution_ref_from_request(None, None, None, InvocationSource::Api),
+            &serde_json::json!({"id": id}),
+        );
+        event.id = id.to_string();
+        event
+    }
+
+    #[test]
+    fn test_find_finding_in_event() {
+        let mut event = sample_event("evt-1");
+        event.outputs.findings.push(GovernanceFinding {
+            id: "finding-42".to_string(),
+            category: FindingCategory::PolicyViolation,
+            severity: GovernanceSeverity::High,
+            title: "Policy violations".to_string(),
+            description: "Repeated violations".to_string(),
+            affected_resources: vec![],
+            evidence_refs: vec![],
+            first_detected: "2024-01-01T00:00:00Z".parse().unwrap(),
+            last_seen: "2024-01-02T00:00:00Z".parse().unwrap(),
+        });
+
+        assert_eq!(find_finding(&event, "finding-42").unwrap().title, "Policy violations");
+        assert!(find_finding(&event, "finding-43").is_none());
+    }
+
+    const MIXED_BATCH_BODY: &str = r#"{"results": [
+        {"index": 0, "success": true, "event_id": "evt-a", "persisted_at": "2024-01-01T00:00:00Z", "storage_ref": "rv://a"},
+        {"index": 1, "success": false, "error": "schema validation failed"}
+    ]}"#;
+
+    #[tokio::test]
+    async fn test_batch_persist_preserves_partial_success() {
+        let server = MockServer::start(vec![MockResponse::new(200).with_body(MIXED_BATCH_BODY)]).await;
+        let consumer = RuVectorConsumer::new(UpstreamConfig {
+            base_url: server.url().to_string(),
+            ..UpstreamConfig::default()
+        })
+        .unwrap();
+
+        let results = consumer
+            .persist_decision_events(vec![sample_event("a"), sample_event("b"), sample_event("c")])
+            .await
+            .unwrap();
+
+        assert_eq!(server.hits(), 1);
+        assert_eq!(results.len(), 3);
+
+        assert!(results[0].success);
+        assert_eq!(results[0].storage_ref, "rv://a");
+
+        assert!(!results[1].success);
+        assert_eq!(results[1].event_id, "b");
+        assert_eq!(results[1].error.as_deref(), Some("schema validation failed"));
+
+        // Missing from the upstream response counts as a failure, not an error
+        assert!(!results[2].success);
+        assert_eq!(results[2].event_id, "c");
+    }
+
+    #[test]
+    fn test_decision_query_parameters_are_encoded() {
+        let consumer = RuVectorConsumer::new(UpstreamConfig {
+            base_url: "http://ruvector.local".to_string(),
+            ..UpstreamConfig::default()
+        })
+        .unwrap();
+
+        let url = consumer
+            .decisions_url(&[
+                ("organization_id", "org-1".to_string()),
+                ("finding_id", "a&limit=1000#x".to_string()),
+            ])
+            .unwrap();
+
+        assert_eq!(
+            url,
+            "http://ruvector.local/api/v1/decisions?organization_id=org-1&finding_id=a%26limit%3D1000%23x"
+        );
+    }
 }
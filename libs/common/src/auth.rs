@@ -0,0 +1,102 @@
+//! Account and access token checks shared by the services that accept tokens
+
+use jsonwebtoken::{decode, DecodingKey, Validation};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::{AppError, Result};
+
+/// Issuer of access tokens unless configured otherwise
+pub const DEFAULT_TOKEN_ISSUER: &str = "llm-governance-auth";
+
+/// Audience of access tokens unless configured otherwise
+pub const DEFAULT_TOKEN_AUDIENCE: &str = "llm-governance-api";
+
+/// Claims of an access token issued by the auth-service
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccessClaims {
+    pub sub: String,
+    pub exp: usize,
+    pub iat: usize,
+    pub iss: String,
+    pub aud: String,
+    pub user_id: Uuid,
+    pub email: String,
+    /// User's `token_version` when the token was issued
+    #[serde(default)]
+    pub token_version: i32,
+}
+
+/// Checks the signature, expiry, issuer and audience of access tokens
+#[derive(Clone)]
+pub struct TokenVerifier {
+    decoding_key: DecodingKey,
+    issuer: String,
+    accepted_audiences: Vec<String>,
+}
+
+impl TokenVerifier {
+    /// Accept tokens from `issuer` for `audience` only, until
+    /// `with_accepted_audiences` widens it
+    pub fn new(secret: &str, issuer: &str, audience: &str) -> Self {
+        Self {
+            decoding_key: DecodingKey::from_secret(secret.as_bytes()),
+            issuer: issuer.to_string(),
+            accepted_audiences: vec![audience.to_string()],
+        }
+    }
+
+    /// Accept tokens minted for any of `audiences` as well
+    pub fn with_accepted_audiences(mut self, audiences: Vec<String>) -> Self {
+        for aud in audiences {
+            if !self.accepted_audiences.contains(&aud) {
+                self.accepted_audiences.push(aud);
+            }
+        }
+        self
+    }
+
+    pub fn verify(&self, token: &str) -> Result<AccessClaims> {
+        // Tokens without iss/aud are rejected, not just ones with wrong values
+        let mut validation = Validation::default();
+        validation.set_issuer(&[&self.issuer]);
+        validation.set_audience(&self.accepted_audiences);
+        validation.set_required_spec_claims(&["exp", "iss", "aud"]);
+
+        decode::<AccessClaims>(token, &self.decoding_key, &validation)
+            .map(|data| data.claims)
+            .map_err(|e| AppError::Auth(format!("Invalid token: {}", e)))
+    }
+}
+
+/// Only active accounts may sign in or mint new tokens
+pub fn ensure_can_login(status: &str) -> Result<()> {
+    if status != "active" {
+        return Err(AppError::Auth(format!("Account is {}", status)));
+    }
+    Ok(())
+}
+
+/// An access token is valid only for an active account whose token
+/// version has not been bumped since the token was issued
+pub fn ensure_token_current(status: &str, current_version: i32, token_version: i32) -> Result<()> {
+    ensure_can_login(status)?;
+    if token_version != current_version {
+        return Err(AppError::Auth("Token has been revoked".to_string()));
+    }
+    Ok(())
+}
+
+/// Reject access tokens issued before the user was deactivated, or for a
+/// user that no longer exists
+pub async fn verify_token_version(pool: &PgPool, user_id: Uuid, token_version: i32) -> Result<()> {
+    let (status, current_version): (String, i32) =
+        sqlx::query_as("SELECT status, token_version FROM users WHERE id = $1")
+            .bind(user_id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| AppError::Auth("Token has been revoked".to_string()))?;
+
+    ensure_token_current(&status, current_version, token_version)
+}
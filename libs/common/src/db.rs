@@ -0,0 +1,109 @@
+//! Paginated list queries
+//!
+//! List handlers build two [`DynamicQuery`]s over the same filters, one
+//! selecting the ordered rows and one counting them, and hand both to
+//! [`paginate`] with the request's [`Page`]. The page clamp and the
+//! `LIMIT`/`OFFSET` clause are applied here so every list endpoint pages the
+//! same way and reports a total that matches its filters. Handlers that page
+//! without [`paginate`] still take their limit from [`Page`], so the default
+//! and maximum page sizes below apply to every list endpoint.
+
+use sqlx::postgres::PgRow;
+use sqlx::{FromRow, PgPool};
+
+use crate::error::Result;
+use crate::query::DynamicQuery;
+
+/// Page size when a request does not ask for one
+pub const DEFAULT_PAGE_SIZE: u32 = 20;
+
+/// Largest page a request may ask for
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+const _: () = assert!(DEFAULT_PAGE_SIZE <= MAX_PAGE_SIZE);
+
+/// Clamped `limit`/`offset` of a list request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Page {
+    pub limit: u32,
+    pub offset: u32,
+}
+
+impl Page {
+    pub fn new(limit: Option<u32>, offset: Option<u32>) -> Self {
+        Self {
+            limit: limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE),
+            offset: offset.unwrap_or(0),
+        }
+    }
+
+    /// Append `LIMIT $n OFFSET $m` to an ordered query
+    pub fn apply(&self, query: &mut DynamicQuery<'_>) {
+        query
+            .push(" LIMIT ")
+            .push_bind(self.limit as i64)
+            .push(" OFFSET ")
+            .push_bind(self.offset as i64);
+    }
+}
+
+/// Fetch one page of `rows` and the `COUNT(*)` of `count`
+///
+/// `rows` must end with its `ORDER BY` clause; `count` must select a single
+/// `BIGINT` over the same filters.
+pub async fn paginate<O>(
+    pool: &PgPool,
+    mut rows: DynamicQuery<'_>,
+    mut count: DynamicQuery<'_>,
+    page: Page,
+) -> Result<(Vec<O>, i64)>
+where
+    O: for<'r> FromRow<'r, PgRow> + Send + Unpin,
+{
+    page.apply(&mut rows);
+    let items = rows.build_query_as::<O>().fetch_all(pool).await?;
+    let (total,): (i64,) = count.build_query_as().fetch_one(pool).await?;
+
+    Ok((items, total))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_limit_defaults_and_is_clamped() {
+        assert_eq!(Page::new(None, None), Page { limit: DEFAULT_PAGE_SIZE, offset: 0 });
+        assert_eq!(Page::new(Some(500), None).limit, MAX_PAGE_SIZE);
+        assert_eq!(Page::new(Some(0), None).limit, 1);
+        assert_eq!(Page::new(Some(35), None).limit, 35);
+    }
+
+    #[test]
+    fn test_clamp_uses_shared_max() {
+        assert_eq!(Page::new(Some(MAX_PAGE_SIZE), None).limit, MAX_PAGE_SIZE);
+        assert_eq!(Page::new(Some(MAX_PAGE_SIZE + 1), None).limit, MAX_PAGE_SIZE);
+        assert_eq!(Page::new(Some(u32::MAX), None).limit, MAX_PAGE_SIZE);
+    }
+
+    #[test]
+    fn test_offset_is_kept_as_requested() {
+        assert_eq!(Page::new(Some(10), Some(40)), Page { limit: 10, offset: 40 });
+        assert_eq!(Page::new(None, Some(u32::MAX)).offset, u32::MAX);
+    }
+
+    #[test]
+    fn test_page_clause_follows_filter_placeholders() {
+        let mut query = DynamicQuery::new("SELECT id FROM budgets");
+        query.filter("team_id", Uuid::new_v4());
+        query.push(" ORDER BY created_at DESC");
+
+        Page::new(Some(10), Some(30)).apply(&mut query);
+
+        assert_eq!(
+            query.sql(),
+            "SELECT id FROM budgets WHERE team_id = $1 ORDER BY created_at DESC LIMIT $2 OFFSET $3"
+        );
+    }
+}
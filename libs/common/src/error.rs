@@ -1,9 +1,14 @@
 use actix_web::{error::ResponseError, http::StatusCode, HttpResponse};
 use serde::Serialize;
+use std::collections::BTreeMap;
 use thiserror::Error;
+use validator::{ValidationError, ValidationErrors, ValidationErrorsKind};
 
 pub type Result<T> = std::result::Result<T, AppError>;
 
+/// Validation messages keyed by field path, e.g. `name` or `rules[0].field`
+pub type FieldErrors = BTreeMap<String, Vec<String>>;
+
 #[derive(Debug, Error)]
 pub enum AppError {
     #[error("Database error: {0}")]
@@ -18,6 +23,10 @@ pub enum AppError {
     #[error("Validation error: {0}")]
     Validation(String),
 
+    /// Request validation failed on one or more fields; every failure is reported
+    #[error("Validation error: {}", describe_fields(.0))]
+    FieldValidation(FieldErrors),
+
     #[error("Not found: {0}")]
     NotFound(String),
 
@@ -32,12 +41,96 @@ pub enum AppError {
 
     #[error("Bad request: {0}")]
     BadRequest(String),
+
+    /// The request conflicts with one that is still being processed
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
+    /// An upstream dependency timed out or is temporarily unreachable
+    #[error("Upstream unavailable: {0}")]
+    UpstreamUnavailable(String),
+
+    /// An upstream provider refused the request itself; retrying will not help
+    #[error("Upstream rejected request ({status} {code}): {message}")]
+    UpstreamRejected {
+        /// Status the provider answered with
+        status: u16,
+        /// Provider's machine-readable error code or type
+        code: String,
+        message: String,
+    },
+
+    /// The caller has too many requests in flight
+    #[error("Too many requests: {0}")]
+    TooManyRequests(String),
+
+    /// None of the response formats the endpoint supports are acceptable
+    #[error("Not acceptable: {0}")]
+    NotAcceptable(String),
 }
 
 #[derive(Serialize)]
 struct ErrorResponse {
     error: String,
     message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fields: Option<FieldErrors>,
+}
+
+impl From<ValidationErrors> for AppError {
+    fn from(errors: ValidationErrors) -> Self {
+        let mut fields = FieldErrors::new();
+        collect_field_errors("", &errors, &mut fields);
+        AppError::FieldValidation(fields)
+    }
+}
+
+fn collect_field_errors(prefix: &str, errors: &ValidationErrors, out: &mut FieldErrors) {
+    for (field, kind) in errors.errors() {
+        let path = if prefix.is_empty() {
+            field.to_string()
+        } else {
+            format!("{}.{}", prefix, field)
+        };
+        match kind {
+            ValidationErrorsKind::Field(errors) => {
+                out.entry(path).or_default().extend(errors.iter().map(field_message));
+            }
+            ValidationErrorsKind::Struct(inner) => collect_field_errors(&path, inner, out),
+            ValidationErrorsKind::List(items) => {
+                for (index, inner) in items {
+                    collect_field_errors(&format!("{}[{}]", path, index), inner, out);
+                }
+            }
+        }
+    }
+}
+
+/// The rule's own message, or one derived from its code and bounds
+fn field_message(error: &ValidationError) -> String {
+    if let Some(message) = &error.message {
+        return message.to_string();
+    }
+    let (min, max) = (error.params.get("min"), error.params.get("max"));
+    match (error.code.as_ref(), min, max) {
+        ("length", Some(min), Some(max)) => format!("length must be between {} and {}", min, max),
+        ("length", Some(min), None) => format!("length must be at least {}", min),
+        ("length", None, Some(max)) => format!("length must be at most {}", max),
+        ("range", Some(min), Some(max)) => format!("must be between {} and {}", min, max),
+        ("range", Some(min), None) => format!("must be at least {}", min),
+        ("range", None, Some(max)) => format!("must be at most {}", max),
+        ("email", ..) => "must be a valid email address".to_string(),
+        ("url", ..) => "must be a valid URL".to_string(),
+        (code, ..) => format!("failed {} validation", code),
+    }
+}
+
+fn describe_fields(fields: &FieldErrors) -> String {
+    fields
+        .iter()
+        .map(|(field, messages)| format!("{}: {}", field, messages.join(", ")))
+        .collect::<Vec<_>>()
+        .join("; ")
 }
 
 impl ResponseError for AppError {
@@ -47,18 +140,119 @@ impl ResponseError for AppError {
             AppError::Redis(_) => StatusCode::INTERNAL_SERVER_ERROR,
             AppError::Auth(_) => StatusCode::UNAUTHORIZED,
             AppError::Validation(_) => StatusCode::BAD_REQUEST,
+            AppError::FieldValidation(_) => StatusCode::BAD_REQUEST,
             AppError::NotFound(_) => StatusCode::NOT_FOUND,
             AppError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
             AppError::Unauthorized => StatusCode::UNAUTHORIZED,
             AppError::Forbidden => StatusCode::FORBIDDEN,
             AppError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            AppError::Conflict(_) => StatusCode::CONFLICT,
+            AppError::UpstreamUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+            AppError::NotAcceptable(_) => StatusCode::NOT_ACCEPTABLE,
+            AppError::TooManyRequests(_) => StatusCode::TOO_MANY_REQUESTS,
+            // A provider refusing our credentials is not the caller's fault
+            AppError::UpstreamRejected { status: 401 | 403, .. } => StatusCode::BAD_GATEWAY,
+            AppError::UpstreamRejected { status, .. } => StatusCode::from_u16(*status)
+                .ok()
+                .filter(|s| s.is_client_error())
+                .unwrap_or(StatusCode::BAD_GATEWAY),
         }
     }
 
     fn error_response(&self) -> HttpResponse {
+        let fields = match self {
+            AppError::FieldValidation(fields) => Some(fields.clone()),
+            _ => None,
+        };
         HttpResponse::build(self.status_code()).json(ErrorResponse {
             error: self.status_code().to_string(),
             message: self.to_string(),
+            fields,
         })
     }
 }
+
+/// Distinguish "no rows" from "query failed" on a query result
+pub trait QueryResultExt<T> {
+    /// Map `RowNotFound` to the empty value and propagate every other error
+    fn or_empty(self) -> Result<T>;
+}
+
+impl<T: Default> QueryResultExt<T> for std::result::Result<T, sqlx::Error> {
+    fn or_empty(self) -> Result<T> {
+        match self {
+            Ok(value) => Ok(value),
+            Err(sqlx::Error::RowNotFound) => Ok(T::default()),
+            Err(e) => Err(AppError::Database(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{test, web, App};
+
+    async fn list(fail: web::Path<bool>) -> Result<HttpResponse> {
+        let rows: std::result::Result<Vec<i64>, sqlx::Error> = if *fail {
+            Err(sqlx::Error::PoolClosed)
+        } else {
+            Err(sqlx::Error::RowNotFound)
+        };
+        Ok(HttpResponse::Ok().json(rows.or_empty()?))
+    }
+
+    #[actix_web::test]
+    async fn test_query_error_is_500_not_empty_200() {
+        let app = test::init_service(App::new().route("/list/{fail}", web::get().to(list))).await;
+
+        let req = test::TestRequest::get().uri("/list/true").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+        let req = test::TestRequest::get().uri("/list/false").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(test::read_body(resp).await, "[]");
+    }
+
+    #[derive(validator::Validate)]
+    struct SignupForm {
+        #[validate(length(min = 3, max = 50))]
+        name: String,
+        #[validate(email, length(max = 10))]
+        email: String,
+        #[validate(range(min = 1, max = 10))]
+        seats: u32,
+    }
+
+    #[actix_web::test]
+    async fn test_every_failing_field_is_reported() {
+        use validator::Validate;
+
+        let form = SignupForm {
+            name: "a".to_string(),
+            email: "not-an-email-address".to_string(),
+            seats: 5,
+        };
+        let err = AppError::from(form.validate().unwrap_err());
+        assert_eq!(err.status_code(), StatusCode::BAD_REQUEST);
+
+        let body = actix_web::body::to_bytes(err.error_response().into_body()).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let fields = body["fields"].as_object().unwrap();
+
+        assert_eq!(fields.len(), 2);
+        assert_eq!(body["fields"]["name"], serde_json::json!(["length must be between 3 and 50"]));
+        let email: Vec<&str> = body["fields"]["email"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| m.as_str().unwrap())
+            .collect();
+        assert_eq!(email.len(), 2);
+        assert!(email.contains(&"must be a valid email address"));
+        assert!(email.contains(&"length must be at most 10"));
+        assert!(body["message"].as_str().unwrap().contains("name: length must be between 3 and 50"));
+    }
+}
@@ -0,0 +1,530 @@
+//! `Idempotency-Key` support for mutating endpoints
+//!
+//! The first request carrying a key claims it, performs the mutation and
+//! stores the resulting resource id and response. Repeats of the same key (per
+//! scope and user) within the expiry window replay the stored response instead
+//! of creating the resource again. A key is bound to a fingerprint of the
+//! request body, so reusing it for a different request is rejected rather than
+//! answered with another request's response.
+
+use actix_web::{http::StatusCode, HttpRequest, HttpResponse};
+use async_trait::async_trait;
+use serde::Serialize;
+use sqlx::PgPool;
+use std::future::Future;
+use uuid::Uuid;
+
+use crate::error::{AppError, Result};
+use crate::response::ApiResponse;
+use crate::utils::hash_inputs;
+
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+/// Response header set when a stored response is replayed
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "Idempotent-Replayed";
+
+/// Default window during which a key replays its original response (24 hours)
+pub const DEFAULT_IDEMPOTENCY_TTL_SECONDS: i64 = 86_400;
+
+/// How long a claim without a stored response blocks its key
+///
+/// A request that crashed after claiming its key never completes or releases
+/// it; once the lease passes, a retry takes the key over.
+pub const CLAIM_LEASE_SECONDS: i64 = 60;
+
+const MAX_KEY_LENGTH: usize = 255;
+
+/// An `Idempotency-Key` and the fingerprint of the request it was sent with
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdempotencyKey {
+    pub key: String,
+    /// SHA-256 of the canonical JSON request body
+    pub fingerprint: String,
+}
+
+impl IdempotencyKey {
+    pub fn new(key: impl Into<String>, body: &impl Serialize) -> Self {
+        Self {
+            key: key.into(),
+            fingerprint: hash_inputs(body),
+        }
+    }
+}
+
+/// Read the `Idempotency-Key` header, rejecting empty or oversized keys, and
+/// bind it to a fingerprint of `body`
+pub fn extract_idempotency_key(req: &HttpRequest, body: &impl Serialize) -> Result<Option<IdempotencyKey>> {
+    let value = match req.headers().get(IDEMPOTENCY_KEY_HEADER) {
+        Some(value) => value,
+        None => return Ok(None),
+    };
+
+    let key = value
+        .to_str()
+        .map_err(|_| AppError::BadRequest("Idempotency-Key must be ASCII".to_string()))?
+        .trim();
+
+    if key.is_empty() || key.len() > MAX_KEY_LENGTH {
+        return Err(AppError::BadRequest(format!(
+            "Idempotency-Key must be between 1 and {} characters",
+            MAX_KEY_LENGTH
+        )));
+    }
+
+    Ok(Some(IdempotencyKey::new(key, body)))
+}
+
+/// Response recorded for a completed idempotent request
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredResponse {
+    pub resource_id: Uuid,
+    pub status_code: u16,
+    pub body: serde_json::Value,
+}
+
+/// State of a key when a request tries to claim it
+#[derive(Debug, Clone, PartialEq)]
+pub enum KeyState {
+    /// The key was free and now belongs to this request
+    Claimed,
+    /// Another request holding the key has not finished yet
+    InProgress,
+    /// The key was used for a request with a different body
+    Mismatch,
+    /// The key has a stored response to replay
+    Completed(StoredResponse),
+}
+
+/// Persistence for idempotency keys
+#[async_trait]
+pub trait IdempotencyStore: Send + Sync {
+    /// Claim `key` for a request with `fingerprint`, replacing it if expired or
+    /// if its claim outlived [`CLAIM_LEASE_SECONDS`], or report its current state
+    async fn claim(
+        &self,
+        scope: &str,
+        user_id: Uuid,
+        key: &str,
+        fingerprint: &str,
+        ttl_seconds: i64,
+    ) -> Result<KeyState>;
+
+    /// Record the outcome of the request that claimed `key`
+    async fn complete(&self, scope: &str, user_id: Uuid, key: &str, response: &StoredResponse) -> Result<()>;
+
+    /// Release a claim after the mutation failed so the client can retry
+    async fn release(&self, scope: &str, user_id: Uuid, key: &str) -> Result<()>;
+}
+
+#[async_trait]
+impl IdempotencyStore for PgPool {
+    async fn claim(
+        &self,
+        scope: &str,
+        user_id: Uuid,
+        key: &str,
+        fingerprint: &str,
+        ttl_seconds: i64,
+    ) -> Result<KeyState> {
+        sqlx::query(
+            r#"
+            DELETE FROM idempotency_keys
+            WHERE scope = $1 AND user_id = $2 AND idempotency_key = $3
+              AND (expires_at <= NOW()
+                   OR (resource_id IS NULL AND created_at <= NOW() - make_interval(secs => $4)))
+            "#,
+        )
+        .bind(scope)
+        .bind(user_id)
+        .bind(key)
+        .bind(CLAIM_LEASE_SECONDS as f64)
+        .execute(self)
+        .await?;
+
+        let claimed = sqlx::query(
+            r#"
+            INSERT INTO idempotency_keys (scope, user_id, idempotency_key, request_fingerprint, expires_at)
+            VALUES ($1, $2, $3, $4, NOW() + make_interval(secs => $5))
+            ON CONFLICT (scope, user_id, idempotency_key) DO NOTHING
+            "#,
+        )
+        .bind(scope)
+        .bind(user_id)
+        .bind(key)
+        .bind(fingerprint)
+        .bind(ttl_seconds as f64)
+        .execute(self)
+        .await?;
+
+        if claimed.rows_affected() == 1 {
+            return Ok(KeyState::Claimed);
+        }
+
+        type Existing = (String, Option<Uuid>, Option<i32>, Option<serde_json::Value>);
+        let existing: Option<Existing> = sqlx::query_as(
+            r#"
+            SELECT request_fingerprint, resource_id, status_code, response_body
+            FROM idempotency_keys
+            WHERE scope = $1 AND user_id = $2 AND idempotency_key = $3
+            "#,
+        )
+        .bind(scope)
+        .bind(user_id)
+        .bind(key)
+        .fetch_optional(self)
+        .await?;
+
+        Ok(match existing {
+            Some((stored, ..)) if stored != fingerprint => KeyState::Mismatch,
+            Some((_, Some(resource_id), Some(status_code), Some(body))) => {
+                KeyState::Completed(StoredResponse {
+                    resource_id,
+                    status_code: status_code as u16,
+                    body,
+                })
+            }
+            _ => KeyState::InProgress,
+        })
+    }
+
+    async fn complete(&self, scope: &str, user_id: Uuid, key: &str, response: &StoredResponse) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE idempotency_keys
+            SET resource_id = $4, status_code = $5, response_body = $6
+            WHERE scope = $1 AND user_id = $2 AND idempotency_key = $3
+            "#,
+        )
+        .bind(scope)
+        .bind(user_id)
+        .bind(key)
+        .bind(response.resource_id)
+        .bind(response.status_code as i32)
+        .bind(&response.body)
+        .execute(self)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn release(&self, scope: &str, user_id: Uuid, key: &str) -> Result<()> {
+        sqlx::query(
+            r#"
+            DELETE FROM idempotency_keys
+            WHERE scope = $1 AND user_id = $2 AND idempotency_key = $3 AND resource_id IS NULL
+            "#,
+        )
+        .bind(scope)
+        .bind(user_id)
+        .bind(key)
+        .execute(self)
+        .await?;
+
+        Ok(())
+    }
+}
+
+/// How often services purge expired keys
+pub const PURGE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(3600);
+
+/// Delete keys whose replay window has passed, returning how many were removed
+///
+/// An expired key is replaced when it is claimed again; this removes the keys
+/// that never are.
+pub async fn purge_expired_keys(pool: &PgPool) -> Result<u64> {
+    let result = sqlx::query("DELETE FROM idempotency_keys WHERE expires_at <= NOW()")
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Run `create` at most once per idempotency key
+///
+/// `create` returns the new resource id and the resource, which is sent as
+/// `ApiResponse::success(resource)` with `status`. Without a key the mutation
+/// simply runs. A key still held by another request answers 409, and a key
+/// reused with a different body answers 400.
+pub async fn with_idempotency<S, F, Fut, T>(
+    store: &S,
+    scope: &str,
+    user_id: Uuid,
+    key: Option<IdempotencyKey>,
+    ttl_seconds: i64,
+    status: StatusCode,
+    create: F,
+) -> Result<HttpResponse>
+where
+    S: IdempotencyStore + ?Sized,
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<(Uuid, T)>>,
+    T: Serialize,
+{
+    let IdempotencyKey { key, fingerprint } = match key {
+        Some(key) => key,
+        None => {
+            let (_, resource) = create().await?;
+            return Ok(HttpResponse::build(status).json(ApiResponse::success(resource)));
+        }
+    };
+
+    match store.claim(scope, user_id, &key, &fingerprint, ttl_seconds).await? {
+        KeyState::Completed(stored) => {
+            let status = StatusCode::from_u16(stored.status_code).unwrap_or(status);
+            Ok(HttpResponse::build(status)
+                .insert_header((IDEMPOTENT_REPLAYED_HEADER, "true"))
+                .json(stored.body))
+        }
+        KeyState::InProgress => Err(AppError::Conflict(
+            "A request with this Idempotency-Key is still in progress".to_string(),
+        )),
+        KeyState::Mismatch => Err(AppError::BadRequest(
+            "Idempotency-Key was already used with a different request body".to_string(),
+        )),
+        KeyState::Claimed => match create().await {
+            Ok((resource_id, resource)) => {
+                let body = match serde_json::to_value(ApiResponse::success(resource)) {
+                    Ok(body) => body,
+                    Err(e) => {
+                        store.release(scope, user_id, &key).await?;
+                        return Err(AppError::Internal(format!(
+                            "Failed to serialize response: {}",
+                            e
+                        )));
+                    }
+                };
+                let stored = StoredResponse {
+                    resource_id,
+                    status_code: status.as_u16(),
+                    body,
+                };
+                store.complete(scope, user_id, &key, &stored).await?;
+                Ok(HttpResponse::build(status).json(stored.body))
+            }
+            Err(e) => {
+                store.release(scope, user_id, &key).await?;
+                Err(e)
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{body::to_bytes, test::TestRequest, ResponseError};
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    type StoredKey = (String, Option<StoredResponse>);
+
+    /// In-memory store; expiry is not simulated
+    #[derive(Default)]
+    struct MemoryStore {
+        keys: Mutex<HashMap<(String, Uuid, String), StoredKey>>,
+    }
+
+    #[async_trait]
+    impl IdempotencyStore for MemoryStore {
+        async fn claim(
+            &self,
+            scope: &str,
+            user_id: Uuid,
+            key: &str,
+            fingerprint: &str,
+            _ttl: i64,
+        ) -> Result<KeyState> {
+            let mut keys = self.keys.lock().unwrap();
+            let id = (scope.to_string(), user_id, key.to_string());
+            Ok(match keys.get(&id) {
+                Some((stored, _)) if stored != fingerprint => KeyState::Mismatch,
+                Some((_, Some(stored))) => KeyState::Completed(stored.clone()),
+                Some((_, None)) => KeyState::InProgress,
+                None => {
+                    keys.insert(id, (fingerprint.to_string(), None));
+                    KeyState::Claimed
+                }
+            })
+        }
+
+        async fn complete(&self, scope: &str, user_id: Uuid, key: &str, response: &StoredResponse) -> Result<()> {
+            if let Some((_, stored)) = self
+                .keys
+                .lock()
+                .unwrap()
+                .get_mut(&(scope.to_string(), user_id, key.to_string()))
+            {
+                *stored = Some(response.clone());
+            }
+            Ok(())
+        }
+
+        async fn release(&self, scope: &str, user_id: Uuid, key: &str) -> Result<()> {
+            self.keys
+                .lock()
+                .unwrap()
+                .remove(&(scope.to_string(), user_id, key.to_string()));
+            Ok(())
+        }
+    }
+
+    fn budget_key(key: &str, amount: f64) -> IdempotencyKey {
+        IdempotencyKey::new(key, &serde_json::json!({ "name": "Team budget", "amount": amount }))
+    }
+
+    async fn create_once(
+        store: &MemoryStore,
+        user_id: Uuid,
+        key: Option<&str>,
+        created: &AtomicUsize,
+    ) -> HttpResponse {
+        with_idempotency(
+            store,
+            "costs.create_budget",
+            user_id,
+            key.map(|key| budget_key(key, 100.0)),
+            DEFAULT_IDEMPOTENCY_TTL_SECONDS,
+            StatusCode::CREATED,
+            || async {
+                created.fetch_add(1, Ordering::SeqCst);
+                let id = Uuid::new_v4();
+                Ok((id, serde_json::json!({ "id": id })))
+            },
+        )
+        .await
+        .unwrap()
+    }
+
+    async fn body_of(resp: HttpResponse) -> serde_json::Value {
+        let bytes = to_bytes(resp.into_body()).await.unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[actix_web::test]
+    async fn test_same_key_creates_once_and_replays_response() {
+        let store = MemoryStore::default();
+        let created = AtomicUsize::new(0);
+        let user_id = Uuid::new_v4();
+
+        let first = create_once(&store, user_id, Some("retry-1"), &created).await;
+        assert_eq!(first.status(), StatusCode::CREATED);
+        assert!(first.headers().get(IDEMPOTENT_REPLAYED_HEADER).is_none());
+
+        let second = create_once(&store, user_id, Some("retry-1"), &created).await;
+        assert_eq!(second.status(), StatusCode::CREATED);
+        assert_eq!(second.headers().get(IDEMPOTENT_REPLAYED_HEADER).unwrap(), "true");
+
+        assert_eq!(created.load(Ordering::SeqCst), 1, "only one resource should be created");
+        assert_eq!(body_of(first).await, body_of(second).await);
+    }
+
+    #[actix_web::test]
+    async fn test_keys_are_scoped_per_user_and_optional() {
+        let store = MemoryStore::default();
+        let created = AtomicUsize::new(0);
+
+        create_once(&store, Uuid::new_v4(), Some("shared"), &created).await;
+        create_once(&store, Uuid::new_v4(), Some("shared"), &created).await;
+        create_once(&store, Uuid::new_v4(), None, &created).await;
+
+        assert_eq!(created.load(Ordering::SeqCst), 3);
+    }
+
+    #[actix_web::test]
+    async fn test_failed_create_releases_key() {
+        let store = MemoryStore::default();
+        let user_id = Uuid::new_v4();
+
+        let failed = with_idempotency(
+            &store,
+            "policies.create_policy",
+            user_id,
+            Some(budget_key("k", 100.0)),
+            DEFAULT_IDEMPOTENCY_TTL_SECONDS,
+            StatusCode::CREATED,
+            || async { Err::<(Uuid, ()), _>(AppError::Validation("bad".to_string())) },
+        )
+        .await;
+        assert!(failed.is_err());
+
+        let created = AtomicUsize::new(0);
+        create_once(&store, user_id, Some("k"), &created).await;
+        assert_eq!(created.load(Ordering::SeqCst), 1, "retry after failure should run");
+    }
+
+    #[actix_web::test]
+    async fn test_key_in_progress_is_a_conflict() {
+        let store = MemoryStore::default();
+        let user_id = Uuid::new_v4();
+        let key = budget_key("k", 100.0);
+        store
+            .claim("costs.create_budget", user_id, &key.key, &key.fingerprint, DEFAULT_IDEMPOTENCY_TTL_SECONDS)
+            .await
+            .unwrap();
+
+        let created = AtomicUsize::new(0);
+        let err = with_idempotency(
+            &store,
+            "costs.create_budget",
+            user_id,
+            Some(key),
+            DEFAULT_IDEMPOTENCY_TTL_SECONDS,
+            StatusCode::CREATED,
+            || async {
+                created.fetch_add(1, Ordering::SeqCst);
+                Ok((Uuid::new_v4(), ()))
+            },
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(err.status_code(), StatusCode::CONFLICT);
+        assert_eq!(created.load(Ordering::SeqCst), 0);
+    }
+
+    #[actix_web::test]
+    async fn test_key_reused_with_a_different_body_is_rejected() {
+        let store = MemoryStore::default();
+        let created = AtomicUsize::new(0);
+        let user_id = Uuid::new_v4();
+        create_once(&store, user_id, Some("k"), &created).await;
+
+        let err = with_idempotency(
+            &store,
+            "costs.create_budget",
+            user_id,
+            Some(budget_key("k", 250.0)),
+            DEFAULT_IDEMPOTENCY_TTL_SECONDS,
+            StatusCode::CREATED,
+            || async {
+                created.fetch_add(1, Ordering::SeqCst);
+                Ok((Uuid::new_v4(), ()))
+            },
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(err.status_code(), StatusCode::BAD_REQUEST);
+        assert_eq!(created.load(Ordering::SeqCst), 1, "the second body must not be created");
+    }
+
+    #[test]
+    fn test_extract_idempotency_key() {
+        let body = serde_json::json!({ "name": "Team budget" });
+        let req = TestRequest::default()
+            .insert_header((IDEMPOTENCY_KEY_HEADER, " abc-123 "))
+            .to_http_request();
+        let key = extract_idempotency_key(&req, &body).unwrap().unwrap();
+        assert_eq!(key.key, "abc-123");
+        assert_eq!(key.fingerprint, hash_inputs(&body));
+
+        let req = TestRequest::default().to_http_request();
+        assert_eq!(extract_idempotency_key(&req, &body).unwrap(), None);
+
+        let req = TestRequest::default()
+            .insert_header((IDEMPOTENCY_KEY_HEADER, "x".repeat(256)))
+            .to_http_request();
+        assert!(extract_idempotency_key(&req, &body).is_err());
+    }
+}
@@ -1,9 +1,24 @@
+pub mod auth;
+pub mod db;
 pub mod error;
+pub mod idempotency;
+pub mod metrics;
+pub mod negotiate;
+pub mod pricing;
+pub mod query;
+pub mod request_context;
 pub mod response;
+pub mod scheduler;
+pub mod telemetry;
+#[cfg(any(test, feature = "test-util"))]
+pub mod testing;
+pub mod timestamp;
+pub mod trace_context;
 pub mod utils;
+pub mod versioning;
 pub mod adapters;
 
-pub use error::{AppError, Result};
+pub use error::{AppError, QueryResultExt, Result};
 pub use response::ApiResponse;
 
 // Re-export adapter types for convenience (Phase 2B Infra-compatible)
@@ -0,0 +1,261 @@
+//! Prometheus metrics shared by all services
+//!
+//! Provides a process-wide registry, an actix middleware recording per-request
+//! counts, latencies and in-flight requests, and a `/metrics` scrape endpoint
+//! rendering the registry in Prometheus text exposition format.
+
+use actix_web::{
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    get, Error, HttpResponse,
+};
+use once_cell::sync::Lazy;
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounterVec, IntGaugeVec, Opts, Registry, TextEncoder,
+};
+use std::future::{ready, Future, Ready};
+use std::pin::Pin;
+use std::rc::Rc;
+use std::time::Instant;
+
+use crate::error::AppError;
+
+/// Process-wide registry scraped by the `/metrics` endpoint
+pub static REGISTRY: Lazy<Registry> = Lazy::new(Registry::new);
+
+/// Total HTTP requests by service, method, matched route and status
+pub static HTTP_REQUESTS_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    let counter = IntCounterVec::new(
+        Opts::new("http_requests_total", "Total number of HTTP requests"),
+        &["service", "method", "path", "status"],
+    )
+    .expect("valid http_requests_total metric");
+    REGISTRY
+        .register(Box::new(counter.clone()))
+        .expect("register http_requests_total");
+    counter
+});
+
+/// HTTP request latency by service, method and matched route
+pub static HTTP_REQUEST_DURATION_SECONDS: Lazy<HistogramVec> = Lazy::new(|| {
+    let histogram = HistogramVec::new(
+        HistogramOpts::new(
+            "http_request_duration_seconds",
+            "HTTP request latency in seconds",
+        ),
+        &["service", "method", "path"],
+    )
+    .expect("valid http_request_duration_seconds metric");
+    REGISTRY
+        .register(Box::new(histogram.clone()))
+        .expect("register http_request_duration_seconds");
+    histogram
+});
+
+/// Requests currently being processed, by service
+pub static HTTP_REQUESTS_IN_FLIGHT: Lazy<IntGaugeVec> = Lazy::new(|| {
+    let gauge = IntGaugeVec::new(
+        Opts::new("http_requests_in_flight", "Number of in-flight HTTP requests"),
+        &["service"],
+    )
+    .expect("valid http_requests_in_flight metric");
+    REGISTRY
+        .register(Box::new(gauge.clone()))
+        .expect("register http_requests_in_flight");
+    gauge
+});
+
+/// Circuit breaker state per provider key (0 = closed, 1 = half-open, 2 = open)
+pub static CIRCUIT_BREAKER_STATE: Lazy<IntGaugeVec> = Lazy::new(|| {
+    let gauge = IntGaugeVec::new(
+        Opts::new(
+            "circuit_breaker_state",
+            "Circuit breaker state (0 = closed, 1 = half-open, 2 = open)",
+        ),
+        &["provider"],
+    )
+    .expect("valid circuit_breaker_state metric");
+    REGISTRY
+        .register(Box::new(gauge.clone()))
+        .expect("register circuit_breaker_state");
+    gauge
+});
+
+/// Agent telemetry emissions to LLM-Observatory by agent and outcome
+/// (accepted, degraded or failed)
+pub static AGENT_TELEMETRY_EMISSIONS_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    let counter = IntCounterVec::new(
+        Opts::new(
+            "agent_telemetry_emissions_total",
+            "Total agent telemetry emissions to LLM-Observatory by outcome",
+        ),
+        &["agent", "status"],
+    )
+    .expect("valid agent_telemetry_emissions_total metric");
+    REGISTRY
+        .register(Box::new(counter.clone()))
+        .expect("register agent_telemetry_emissions_total");
+    counter
+});
+
+/// Render the registry in Prometheus text exposition format
+pub fn render() -> crate::Result<String> {
+    // Touch the HTTP metrics so they are registered even before the first request
+    Lazy::force(&HTTP_REQUESTS_TOTAL);
+    Lazy::force(&HTTP_REQUEST_DURATION_SECONDS);
+    Lazy::force(&HTTP_REQUESTS_IN_FLIGHT);
+
+    let mut buffer = Vec::new();
+    TextEncoder::new()
+        .encode(&REGISTRY.gather(), &mut buffer)
+        .map_err(|e| AppError::Internal(format!("Failed to encode metrics: {}", e)))?;
+
+    String::from_utf8(buffer)
+        .map_err(|e| AppError::Internal(format!("Metrics output is not valid UTF-8: {}", e)))
+}
+
+/// Prometheus scrape endpoint
+#[get("/metrics")]
+pub async fn metrics_endpoint() -> crate::Result<HttpResponse> {
+    Ok(HttpResponse::Ok()
+        .content_type(prometheus::TEXT_FORMAT)
+        .body(render()?))
+}
+
+/// Middleware recording request count, latency and in-flight gauge
+pub struct RequestMetrics {
+    service: &'static str,
+}
+
+impl RequestMetrics {
+    pub fn new(service: &'static str) -> Self {
+        Self { service }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RequestMetrics
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = RequestMetricsMiddleware<S>;
+    type Future = Ready<std::result::Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RequestMetricsMiddleware {
+            service: Rc::new(service),
+            service_name: self.service,
+        }))
+    }
+}
+
+pub struct RequestMetricsMiddleware<S> {
+    service: Rc<S>,
+    service_name: &'static str,
+}
+
+impl<S, B> Service<ServiceRequest> for RequestMetricsMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = std::result::Result<Self::Response, Self::Error>>>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let service_name = self.service_name;
+        let method = req.method().to_string();
+        // Use the matched route pattern rather than the raw path to keep label cardinality bounded
+        let path = req
+            .match_pattern()
+            .unwrap_or_else(|| "unmatched".to_string());
+
+        Box::pin(async move {
+            let in_flight = HTTP_REQUESTS_IN_FLIGHT.with_label_values(&[service_name]);
+            in_flight.inc();
+            let start = Instant::now();
+
+            let result = service.call(req).await;
+
+            in_flight.dec();
+            let status = match &result {
+                Ok(res) => res.status().as_u16().to_string(),
+                Err(e) => e.as_response_error().status_code().as_u16().to_string(),
+            };
+
+            HTTP_REQUEST_DURATION_SECONDS
+                .with_label_values(&[service_name, &method, &path])
+                .observe(start.elapsed().as_secs_f64());
+            HTTP_REQUESTS_TOTAL
+                .with_label_values(&[service_name, &method, &path, &status])
+                .inc();
+
+            result
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{web, App};
+
+    #[actix_web::test]
+    async fn test_metrics_endpoint_emits_request_counter() {
+        let app = actix_web::test::init_service(
+            App::new()
+                .wrap(RequestMetrics::new("test-service"))
+                .service(metrics_endpoint)
+                .route("/ping", web::get().to(|| async { HttpResponse::Ok().finish() })),
+        )
+        .await;
+
+        let req = actix_web::test::TestRequest::get().uri("/ping").to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+
+        let req = actix_web::test::TestRequest::get().uri("/metrics").to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+
+        let content_type = resp
+            .headers()
+            .get("content-type")
+            .and_then(|h| h.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        assert!(content_type.starts_with("text/plain"));
+
+        let body = actix_web::test::read_body(resp).await;
+        let body = String::from_utf8(body.to_vec()).unwrap();
+
+        assert!(body.contains("# TYPE http_requests_total counter"));
+        assert!(body.contains("# TYPE http_request_duration_seconds histogram"));
+        assert!(body
+            .lines()
+            .any(|l| l.starts_with("http_requests_total{")
+                && l.contains("service=\"test-service\"")
+                && l.contains("path=\"/ping\"")));
+
+        // Every sample line must be `name{labels} value` or `name value`
+        for line in body.lines().filter(|l| !l.is_empty() && !l.starts_with('#')) {
+            let value = line.rsplit(' ').next().unwrap();
+            assert!(value.parse::<f64>().is_ok(), "invalid sample line: {}", line);
+        }
+    }
+
+    #[test]
+    fn test_circuit_breaker_gauge_is_exported() {
+        CIRCUIT_BREAKER_STATE.with_label_values(&["openai:gpt-4"]).set(2);
+        let output = render().unwrap();
+        assert!(output.contains("circuit_breaker_state{provider=\"openai:gpt-4\"} 2"));
+    }
+}
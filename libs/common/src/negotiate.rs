@@ -0,0 +1,225 @@
+//! Response format negotiation
+//!
+//! Endpoints that can render more than JSON call [`preferred_format`] with the
+//! formats they support, most preferred first. A `?format=` query parameter
+//! wins over the `Accept` header; otherwise the supported format with the
+//! highest q-value in `Accept` is chosen, ties going to the endpoint's order.
+//! A request that accepts none of them is rejected with `406 Not Acceptable`.
+
+use actix_web::HttpRequest;
+
+use crate::error::{AppError, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Csv,
+    Markdown,
+    /// SARIF 2.1.0 log, for findings consumed by security tooling
+    Sarif,
+}
+
+impl Format {
+    /// Name used in `?format=` and in endpoint `supported` lists
+    pub fn name(&self) -> &'static str {
+        match self {
+            Format::Json => "json",
+            Format::Csv => "csv",
+            Format::Markdown => "markdown",
+            Format::Sarif => "sarif",
+        }
+    }
+
+    pub fn media_type(&self) -> &'static str {
+        match self {
+            Format::Json => "application/json",
+            Format::Csv => "text/csv",
+            Format::Markdown => "text/markdown",
+            Format::Sarif => "application/sarif+json",
+        }
+    }
+
+    /// Content type for the response body
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Format::Json => "application/json",
+            Format::Csv => "text/csv; charset=utf-8",
+            Format::Markdown => "text/markdown; charset=utf-8",
+            Format::Sarif => "application/sarif+json",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "json" => Some(Format::Json),
+            "csv" => Some(Format::Csv),
+            "markdown" | "md" => Some(Format::Markdown),
+            "sarif" => Some(Format::Sarif),
+            _ => None,
+        }
+    }
+}
+
+/// One media range from an `Accept` header
+#[derive(Debug, Clone, PartialEq)]
+struct MediaRange {
+    kind: String,
+    subtype: String,
+    q: f32,
+}
+
+impl MediaRange {
+    /// Specificity of the match against `media_type`, or `None` if it does not match
+    fn matches(&self, media_type: &str) -> Option<u8> {
+        let (kind, subtype) = media_type.split_once('/')?;
+        match (self.kind.as_str(), self.subtype.as_str()) {
+            ("*", "*") => Some(0),
+            (k, "*") if k == kind => Some(1),
+            (k, s) if k == kind && s == subtype => Some(2),
+            _ => None,
+        }
+    }
+}
+
+fn parse_accept(header: &str) -> Vec<MediaRange> {
+    header
+        .split(',')
+        .filter_map(|part| {
+            let mut params = part.split(';');
+            let (kind, subtype) = params.next()?.trim().split_once('/')?;
+            let q = params
+                .filter_map(|p| p.trim().split_once('='))
+                .find(|(k, _)| k.trim().eq_ignore_ascii_case("q"))
+                .and_then(|(_, v)| v.trim().parse::<f32>().ok())
+                .unwrap_or(1.0)
+                .clamp(0.0, 1.0);
+            Some(MediaRange {
+                kind: kind.trim().to_ascii_lowercase(),
+                subtype: subtype.trim().to_ascii_lowercase(),
+                q,
+            })
+        })
+        .collect()
+}
+
+/// q-value the ranges give `format`, taken from the most specific matching range
+fn quality(ranges: &[MediaRange], format: Format) -> f32 {
+    ranges
+        .iter()
+        .filter_map(|r| r.matches(format.media_type()).map(|specificity| (specificity, r.q)))
+        .max_by(|a, b| a.0.cmp(&b.0).then(a.1.total_cmp(&b.1)))
+        .map(|(_, q)| q)
+        .unwrap_or(0.0)
+}
+
+fn format_override(req: &HttpRequest) -> Option<String> {
+    req.query_string()
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(k, _)| *k == "format")
+        .map(|(_, v)| v.to_string())
+}
+
+fn not_acceptable(supported: &[Format]) -> AppError {
+    let names: Vec<&str> = supported.iter().map(|f| f.name()).collect();
+    AppError::NotAcceptable(format!("supported formats: {}", names.join(", ")))
+}
+
+/// Pick the response format for `req` among `supported`, most preferred first
+///
+/// Unknown names in `supported` are ignored. A missing or empty `Accept`
+/// header selects the first supported format.
+pub fn preferred_format(req: &HttpRequest, supported: &[&str]) -> Result<Format> {
+    let supported: Vec<Format> = supported.iter().filter_map(|name| Format::from_name(name)).collect();
+
+    if let Some(requested) = format_override(req) {
+        return Format::from_name(&requested)
+            .filter(|f| supported.contains(f))
+            .ok_or_else(|| not_acceptable(&supported));
+    }
+
+    let ranges = req
+        .headers()
+        .get(actix_web::http::header::ACCEPT)
+        .and_then(|h| h.to_str().ok())
+        .map(parse_accept)
+        .unwrap_or_default();
+
+    if ranges.is_empty() {
+        return supported.first().copied().ok_or_else(|| not_acceptable(&supported));
+    }
+
+    // Strictly greater keeps the earlier format on ties and skips q=0
+    let mut best = None;
+    let mut best_q = 0.0;
+    for format in &supported {
+        let q = quality(&ranges, *format);
+        if q > best_q {
+            best = Some(*format);
+            best_q = q;
+        }
+    }
+
+    best.ok_or_else(|| not_acceptable(&supported))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::http::StatusCode;
+    use actix_web::test::TestRequest;
+    use actix_web::ResponseError;
+
+    const SUPPORTED: &[&str] = &["json", "csv"];
+
+    fn with_accept(accept: &str) -> HttpRequest {
+        TestRequest::default()
+            .insert_header(("Accept", accept))
+            .to_http_request()
+    }
+
+    #[test]
+    fn test_q_values_rank_formats() {
+        let req = with_accept("application/json;q=0.5, text/csv;q=0.9");
+        assert_eq!(preferred_format(&req, SUPPORTED).unwrap(), Format::Csv);
+
+        // The most specific range decides, even when a wildcard ranks higher
+        let req = with_accept("*/*;q=1.0, text/csv;q=0.1, application/json;q=0.2");
+        assert_eq!(preferred_format(&req, SUPPORTED).unwrap(), Format::Json);
+
+        // Equal q-values fall back to the endpoint's order
+        let req = with_accept("text/csv, application/json");
+        assert_eq!(preferred_format(&req, SUPPORTED).unwrap(), Format::Json);
+
+        // q=0 excludes a format
+        let req = with_accept("text/*, application/json;q=0");
+        assert_eq!(preferred_format(&req, SUPPORTED).unwrap(), Format::Csv);
+    }
+
+    #[test]
+    fn test_missing_accept_uses_first_supported() {
+        let req = TestRequest::default().to_http_request();
+        assert_eq!(preferred_format(&req, &["markdown", "json"]).unwrap(), Format::Markdown);
+    }
+
+    #[test]
+    fn test_format_parameter_overrides_accept() {
+        let req = TestRequest::with_uri("/report?team=a&format=csv")
+            .insert_header(("Accept", "application/json"))
+            .to_http_request();
+        assert_eq!(preferred_format(&req, SUPPORTED).unwrap(), Format::Csv);
+
+        let req = TestRequest::with_uri("/report?format=markdown").to_http_request();
+        let err = preferred_format(&req, SUPPORTED).unwrap_err();
+        assert_eq!(err.status_code(), StatusCode::NOT_ACCEPTABLE);
+    }
+
+    #[test]
+    fn test_unsupported_accept_is_406() {
+        let req = with_accept("application/xml, text/html;q=0.8");
+        let err = preferred_format(&req, SUPPORTED).unwrap_err();
+
+        assert_eq!(err.status_code(), StatusCode::NOT_ACCEPTABLE);
+        assert!(err.to_string().contains("json, csv"));
+    }
+}
@@ -0,0 +1,357 @@
+//! Model pricing shared by the proxy and the cost service
+//!
+//! Both services price requests through [`cost_for`] so the cost recorded at
+//! proxy time and the cost the cost service reports cannot drift apart. The
+//! built-in table can be replaced at startup with [`set_pricing_source`].
+//!
+//! Models missing from the table are priced by the configured
+//! [`FallbackPricing`] strategy, which logs a warning each time it applies so
+//! the real price can be added.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+
+use crate::error::{AppError, Result};
+
+/// USD price per one million tokens
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ModelPrice {
+    pub input_per_million: f64,
+    pub output_per_million: f64,
+}
+
+/// Price applied to unknown models by the default fallback strategy
+pub const DEFAULT_PRICE: ModelPrice = ModelPrice {
+    input_per_million: 1.0,
+    output_per_million: 2.0,
+};
+
+impl ModelPrice {
+    pub const fn new(input_per_million: f64, output_per_million: f64) -> Self {
+        Self {
+            input_per_million,
+            output_per_million,
+        }
+    }
+
+    pub fn input_cost(&self, tokens_in: i64) -> f64 {
+        (tokens_in as f64 / 1_000_000.0) * self.input_per_million
+    }
+
+    pub fn output_cost(&self, tokens_out: i64) -> f64 {
+        (tokens_out as f64 / 1_000_000.0) * self.output_per_million
+    }
+
+    pub fn cost(&self, tokens_in: i64, tokens_out: i64) -> f64 {
+        self.input_cost(tokens_in) + self.output_cost(tokens_out)
+    }
+}
+
+/// Where per-model prices come from
+pub trait PricingSource: Send + Sync {
+    /// Price for a model, or `None` when the model is unknown
+    fn price(&self, provider: &str, model: &str) -> Option<ModelPrice>;
+}
+
+/// Prices published by the providers (as of 2024)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BuiltinPricing;
+
+impl PricingSource for BuiltinPricing {
+    fn price(&self, provider: &str, model: &str) -> Option<ModelPrice> {
+        let price = match (provider, model) {
+            ("openai", "gpt-4") => ModelPrice::new(30.0, 60.0),
+            ("openai", "gpt-4-turbo") => ModelPrice::new(10.0, 30.0),
+            ("openai", "gpt-3.5-turbo") => ModelPrice::new(0.5, 1.5),
+            ("anthropic", "claude-3-opus") => ModelPrice::new(15.0, 75.0),
+            ("anthropic", "claude-3-sonnet") => ModelPrice::new(3.0, 15.0),
+            ("anthropic", "claude-3-haiku") => ModelPrice::new(0.25, 1.25),
+            _ => return None,
+        };
+        Some(price)
+    }
+}
+
+/// Explicit prices keyed by `provider:model`, falling back to the built-in table
+#[derive(Debug, Clone, Default)]
+pub struct PricingTable {
+    prices: HashMap<String, ModelPrice>,
+}
+
+impl PricingTable {
+    /// Parse `{"provider:model": {"input_per_million": .., "output_per_million": ..}}`
+    pub fn from_json(json: &str) -> Result<Self> {
+        let prices: HashMap<String, ModelPrice> = serde_json::from_str(json)
+            .map_err(|e| AppError::Validation(format!("Invalid pricing table: {}", e)))?;
+
+        if let Some((key, _)) = prices.iter().find(|(_, p)| {
+            !p.input_per_million.is_finite()
+                || p.input_per_million < 0.0
+                || !p.output_per_million.is_finite()
+                || p.output_per_million < 0.0
+        }) {
+            return Err(AppError::Validation(format!(
+                "Prices for '{}' must be non-negative numbers",
+                key
+            )));
+        }
+
+        Ok(Self { prices })
+    }
+
+    pub fn with_price(mut self, provider: &str, model: &str, price: ModelPrice) -> Self {
+        self.prices.insert(format!("{}:{}", provider, model), price);
+        self
+    }
+}
+
+impl PricingSource for PricingTable {
+    fn price(&self, provider: &str, model: &str) -> Option<ModelPrice> {
+        self.prices
+            .get(&format!("{}:{}", provider, model))
+            .copied()
+            .or_else(|| BuiltinPricing.price(provider, model))
+    }
+}
+
+/// How to price a model the pricing source does not know
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FallbackPricing {
+    /// Reject requests for unknown models
+    Error,
+    /// Record zero cost and flag the request
+    Zero,
+    /// Charge a fixed price
+    Default(ModelPrice),
+}
+
+impl Default for FallbackPricing {
+    fn default() -> Self {
+        FallbackPricing::Default(DEFAULT_PRICE)
+    }
+}
+
+impl FallbackPricing {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FallbackPricing::Error => "error",
+            FallbackPricing::Zero => "zero",
+            FallbackPricing::Default(_) => "default",
+        }
+    }
+}
+
+impl FromStr for FallbackPricing {
+    type Err = AppError;
+
+    /// Accepts `error`, `zero`, `default`, or an `input,output` price pair
+    /// per million tokens such as `1.0,2.0`
+    fn from_str(value: &str) -> Result<Self> {
+        match value.trim() {
+            "error" => Ok(FallbackPricing::Error),
+            "zero" => Ok(FallbackPricing::Zero),
+            "default" => Ok(FallbackPricing::default()),
+            pair => {
+                let invalid = || {
+                    AppError::Validation(format!(
+                        "Invalid pricing fallback '{}': expected error, zero, default or input,output",
+                        pair
+                    ))
+                };
+                let (input, output) = pair.split_once(',').ok_or_else(invalid)?;
+                let input: f64 = input.trim().parse().map_err(|_| invalid())?;
+                let output: f64 = output.trim().parse().map_err(|_| invalid())?;
+                if !(input.is_finite() && input >= 0.0 && output.is_finite() && output >= 0.0) {
+                    return Err(invalid());
+                }
+                Ok(FallbackPricing::Default(ModelPrice::new(input, output)))
+            }
+        }
+    }
+}
+
+/// Price chosen for a model, noting when it came from the fallback strategy
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResolvedPrice {
+    pub price: ModelPrice,
+    pub fallback: Option<FallbackPricing>,
+}
+
+impl ResolvedPrice {
+    pub fn cost(&self, tokens_in: i64, tokens_out: i64) -> f64 {
+        self.price.cost(tokens_in, tokens_out)
+    }
+}
+
+/// Prices requests against a pricing source
+#[derive(Clone)]
+pub struct Pricing {
+    source: Arc<dyn PricingSource>,
+    fallback: FallbackPricing,
+}
+
+impl Default for Pricing {
+    fn default() -> Self {
+        Self::new(Arc::new(BuiltinPricing))
+    }
+}
+
+impl Pricing {
+    pub fn new(source: Arc<dyn PricingSource>) -> Self {
+        Self {
+            source,
+            fallback: FallbackPricing::default(),
+        }
+    }
+
+    pub fn with_fallback(mut self, fallback: FallbackPricing) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    /// Price for a model, applying the fallback strategy when it is unknown
+    pub fn resolve(&self, provider: &str, model: &str) -> Result<ResolvedPrice> {
+        if let Some(price) = self.source.price(provider, model) {
+            return Ok(ResolvedPrice { price, fallback: None });
+        }
+
+        let price = match self.fallback {
+            FallbackPricing::Error => {
+                return Err(AppError::Validation(format!(
+                    "No price configured for model {}:{}",
+                    provider, model
+                )))
+            }
+            FallbackPricing::Zero => ModelPrice::new(0.0, 0.0),
+            FallbackPricing::Default(price) => price,
+        };
+
+        tracing::warn!(
+            "No price configured for {}:{}; applying '{}' fallback pricing",
+            provider,
+            model,
+            self.fallback.as_str()
+        );
+        Ok(ResolvedPrice {
+            price,
+            fallback: Some(self.fallback),
+        })
+    }
+
+    pub fn cost_for(&self, provider: &str, model: &str, tokens_in: i64, tokens_out: i64) -> Result<f64> {
+        Ok(self.resolve(provider, model)?.cost(tokens_in, tokens_out))
+    }
+}
+
+static PRICING: Lazy<RwLock<Pricing>> = Lazy::new(|| RwLock::new(Pricing::default()));
+
+/// Replace the process-wide pricing, e.g. with a table loaded from config
+pub fn set_pricing(pricing: Pricing) {
+    *PRICING.write().unwrap_or_else(|e| e.into_inner()) = pricing;
+}
+
+/// Replace the process-wide pricing source, keeping the fallback strategy
+pub fn set_pricing_source(source: Arc<dyn PricingSource>) {
+    let fallback = pricing().fallback;
+    set_pricing(Pricing::new(source).with_fallback(fallback));
+}
+
+/// Install the process-wide pricing from service config
+///
+/// Loads the pricing table at `path` (the built-in table when unset) and the
+/// fallback strategy for unknown models.
+pub fn configure_pricing(path: Option<&str>, fallback: &str) -> Result<()> {
+    let source: Arc<dyn PricingSource> = match path {
+        Some(path) => {
+            let json = std::fs::read_to_string(path)
+                .map_err(|e| AppError::Internal(format!("Failed to read pricing table {}: {}", path, e)))?;
+            Arc::new(PricingTable::from_json(&json)?)
+        }
+        None => Arc::new(BuiltinPricing),
+    };
+
+    set_pricing(Pricing::new(source).with_fallback(fallback.parse()?));
+    Ok(())
+}
+
+/// Process-wide pricing used by every service
+pub fn pricing() -> Pricing {
+    PRICING.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Price for a model from the process-wide pricing
+pub fn resolve(provider: &str, model: &str) -> Result<ResolvedPrice> {
+    pricing().resolve(provider, model)
+}
+
+/// Cost in USD of a request from the process-wide pricing
+pub fn cost_for(provider: &str, model: &str, tokens_in: i64, tokens_out: i64) -> Result<f64> {
+    pricing().cost_for(provider, model, tokens_in, tokens_out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_cost() {
+        let cost = Pricing::default().cost_for("openai", "gpt-4", 1_000_000, 500_000).unwrap();
+        assert!((cost - 60.0).abs() < 1e-9);
+
+        let resolved = Pricing::default().resolve("openai", "gpt-4").unwrap();
+        assert!(resolved.fallback.is_none());
+    }
+
+    #[test]
+    fn test_error_fallback_rejects_unknown_model() {
+        let pricing = Pricing::default().with_fallback("error".parse().unwrap());
+
+        let err = pricing.resolve("acme", "new-model").unwrap_err();
+        assert!(err.to_string().contains("acme:new-model"));
+        assert!(pricing.resolve("openai", "gpt-4").is_ok());
+    }
+
+    #[test]
+    fn test_zero_fallback_flags_unknown_model() {
+        let pricing = Pricing::default().with_fallback("zero".parse().unwrap());
+
+        let resolved = pricing.resolve("acme", "new-model").unwrap();
+        assert_eq!(resolved.cost(1_000_000, 1_000_000), 0.0);
+        assert_eq!(resolved.fallback, Some(FallbackPricing::Zero));
+    }
+
+    #[test]
+    fn test_default_pair_fallback_prices_unknown_model() {
+        let resolved = Pricing::default().resolve("acme", "new-model").unwrap();
+        assert!((resolved.cost(1_000_000, 1_000_000) - 3.0).abs() < 1e-9);
+        assert_eq!(resolved.fallback.map(|f| f.as_str()), Some("default"));
+
+        let pricing = Pricing::default().with_fallback("4.0, 8.0".parse().unwrap());
+        let resolved = pricing.resolve("acme", "new-model").unwrap();
+        assert_eq!(resolved.price, ModelPrice::new(4.0, 8.0));
+        assert_eq!(resolved.fallback, Some(FallbackPricing::Default(ModelPrice::new(4.0, 8.0))));
+
+        for invalid in ["", "cheap", "1.0", "1.0,x", "-1.0,2.0"] {
+            assert!(invalid.parse::<FallbackPricing>().is_err(), "{:?} should be rejected", invalid);
+        }
+    }
+
+    #[test]
+    fn test_pricing_table_overrides_builtin() {
+        let table = PricingTable::from_json(r#"{"openai:gpt-4": {"input_per_million": 5.0, "output_per_million": 10.0}}"#)
+            .unwrap()
+            .with_price("acme", "new-model", ModelPrice::new(2.0, 4.0));
+        let pricing = Pricing::new(Arc::new(table));
+
+        let price = |provider, model| pricing.resolve(provider, model).unwrap().price;
+        assert_eq!(price("openai", "gpt-4"), ModelPrice::new(5.0, 10.0));
+        assert_eq!(price("acme", "new-model"), ModelPrice::new(2.0, 4.0));
+        assert_eq!(price("anthropic", "claude-3-haiku"), ModelPrice::new(0.25, 1.25));
+
+        assert!(PricingTable::from_json(r#"{"x:y": {"input_per_million": -1.0, "output_per_million": 1.0}}"#).is_err());
+        assert!(PricingTable::from_json("not json").is_err());
+    }
+}
@@ -0,0 +1,194 @@
+//! Dynamic SQL for optional filters and partial updates
+//!
+//! Handlers whose WHERE or SET clauses depend on which fields a request
+//! carries build them with [`DynamicQuery`] instead of concatenating strings.
+//! Every value is bound at the moment its placeholder is written, so the
+//! `$n` indices and the bind order cannot drift apart.
+
+use sqlx::postgres::{PgArguments, PgRow};
+use sqlx::query::{Query, QueryAs};
+use sqlx::{Encode, FromRow, Postgres, QueryBuilder, Type};
+
+pub struct DynamicQuery<'args> {
+    builder: QueryBuilder<'args, Postgres>,
+    assignments: usize,
+    conditions: usize,
+}
+
+impl<'args> DynamicQuery<'args> {
+    /// Start from a statement head such as `SELECT ... FROM t` or `UPDATE t`
+    pub fn new(head: impl Into<String>) -> Self {
+        Self {
+            builder: QueryBuilder::new(head),
+            assignments: 0,
+            conditions: 0,
+        }
+    }
+
+    /// Append `column = $n` to the SET clause
+    pub fn set<T>(&mut self, column: &str, value: T) -> &mut Self
+    where
+        T: 'args + Encode<'args, Postgres> + Send + Type<Postgres>,
+    {
+        self.next_assignment();
+        self.builder.push(column).push(" = ").push_bind(value);
+        self
+    }
+
+    /// Append a literal assignment such as `updated_at = NOW()` to the SET clause
+    pub fn set_raw(&mut self, assignment: &str) -> &mut Self {
+        self.next_assignment();
+        self.builder.push(assignment);
+        self
+    }
+
+    /// Whether any assignment has been added
+    pub fn has_assignments(&self) -> bool {
+        self.assignments > 0
+    }
+
+    /// Append `column = $n` to the WHERE clause
+    pub fn filter<T>(&mut self, column: &str, value: T) -> &mut Self
+    where
+        T: 'args + Encode<'args, Postgres> + Send + Type<Postgres>,
+    {
+        self.filter_op(column, "=", value)
+    }
+
+    /// Append `column <op> $n` to the WHERE clause
+    pub fn filter_op<T>(&mut self, column: &str, op: &str, value: T) -> &mut Self
+    where
+        T: 'args + Encode<'args, Postgres> + Send + Type<Postgres>,
+    {
+        self.and_where();
+        self.builder.push(column).push(" ").push(op).push(" ").push_bind(value);
+        self
+    }
+
+    /// Start the next WHERE condition, to be written with [`push`](Self::push)
+    /// and [`push_bind`](Self::push_bind) when the shorthands do not fit
+    pub fn and_where(&mut self) -> &mut Self {
+        self.builder.push(if self.conditions == 0 { " WHERE " } else { " AND " });
+        self.conditions += 1;
+        self
+    }
+
+    /// [`filter`](Self::filter) when `value` is present; `None` adds nothing
+    pub fn filter_opt<T>(&mut self, column: &str, value: Option<T>) -> &mut Self
+    where
+        T: 'args + Encode<'args, Postgres> + Send + Type<Postgres>,
+    {
+        if let Some(value) = value {
+            self.filter(column, value);
+        }
+        self
+    }
+
+    /// Append raw SQL, e.g. `ORDER BY` or `RETURNING` clauses
+    pub fn push(&mut self, sql: &str) -> &mut Self {
+        self.builder.push(sql);
+        self
+    }
+
+    /// Append the next placeholder and bind `value` to it
+    pub fn push_bind<T>(&mut self, value: T) -> &mut Self
+    where
+        T: 'args + Encode<'args, Postgres> + Send + Type<Postgres>,
+    {
+        self.builder.push_bind(value);
+        self
+    }
+
+    pub fn sql(&self) -> &str {
+        self.builder.sql()
+    }
+
+    pub fn build(&mut self) -> Query<'_, Postgres, PgArguments> {
+        self.builder.build()
+    }
+
+    pub fn build_query_as<'q, O>(&'q mut self) -> QueryAs<'q, Postgres, O, PgArguments>
+    where
+        O: for<'r> FromRow<'r, PgRow>,
+    {
+        self.builder.build_query_as()
+    }
+
+    fn next_assignment(&mut self) {
+        self.builder.push(if self.assignments == 0 { " SET " } else { ", " });
+        self.assignments += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    const HEAD: &str = "SELECT id FROM budgets";
+
+    #[test]
+    fn test_no_conditions_adds_no_where_clause() {
+        let mut query = DynamicQuery::new(HEAD);
+        query.filter_opt::<Uuid>("team_id", None);
+        query.push(" LIMIT ").push_bind(20_i64);
+
+        assert_eq!(query.sql(), "SELECT id FROM budgets LIMIT $1");
+    }
+
+    #[test]
+    fn test_one_condition() {
+        let mut query = DynamicQuery::new(HEAD);
+        query.filter_opt("team_id", Some(Uuid::new_v4()));
+
+        assert_eq!(query.sql(), "SELECT id FROM budgets WHERE team_id = $1");
+    }
+
+    #[test]
+    fn test_three_conditions_number_placeholders_in_order() {
+        let mut query = DynamicQuery::new(HEAD);
+        query
+            .filter("organization_id", Uuid::new_v4())
+            .filter_opt::<Uuid>("team_id", None)
+            .filter_op("amount", ">=", 10.0_f64)
+            .filter("is_active", true);
+        query.push(" LIMIT ").push_bind(20_i64).push(" OFFSET ").push_bind(0_i64);
+
+        assert_eq!(
+            query.sql(),
+            "SELECT id FROM budgets WHERE organization_id = $1 AND amount >= $2 AND is_active = $3 \
+             LIMIT $4 OFFSET $5"
+        );
+    }
+
+    #[test]
+    fn test_set_clause_then_filter() {
+        let mut query = DynamicQuery::new("UPDATE organizations");
+        assert!(!query.has_assignments());
+
+        query
+            .set("name", "acme".to_string())
+            .set("is_active", false)
+            .set_raw("updated_at = NOW()")
+            .filter("id", Uuid::new_v4());
+
+        assert!(query.has_assignments());
+        assert_eq!(
+            query.sql(),
+            "UPDATE organizations SET name = $1, is_active = $2, updated_at = NOW() WHERE id = $3"
+        );
+    }
+
+    #[test]
+    fn test_hand_written_condition_shares_numbering() {
+        let mut query = DynamicQuery::new(HEAD);
+        query.filter("id", Uuid::new_v4());
+        query.and_where().push("(team_id IS NULL OR team_id = ").push_bind(Uuid::new_v4()).push(")");
+        query.filter("is_active", true);
+
+        assert_eq!(
+            query.sql(),
+            "SELECT id FROM budgets WHERE id = $1 AND (team_id IS NULL OR team_id = $2) AND is_active = $3"
+        );
+    }
+}
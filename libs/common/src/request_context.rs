@@ -0,0 +1,170 @@
+//! Per-request correlation span
+//!
+//! Opens a tracing span for every request carrying `request_id`, `user_id`,
+//! `org_id` and `trace_id` from the inbound headers, so every `info!` /
+//! `warn!` emitted while handling the request inherits them. A request id is
+//! generated when the caller did not send one; it is written back into the
+//! request headers for handlers and echoed in the response.
+
+use actix_web::{
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    http::header::{HeaderName, HeaderValue},
+    Error,
+};
+use std::future::{ready, Future, Ready};
+use std::pin::Pin;
+use std::rc::Rc;
+use tracing::Instrument;
+use uuid::Uuid;
+
+use crate::trace_context::extract_trace_id;
+
+pub const REQUEST_ID_HEADER: &str = "X-Request-Id";
+pub const USER_ID_HEADER: &str = "X-User-Id";
+pub const ORG_ID_HEADER: &str = "X-Organization-Id";
+
+fn header_value(req: &ServiceRequest, name: &str) -> Option<String> {
+    req.headers()
+        .get(name)
+        .and_then(|h| h.to_str().ok())
+        .filter(|v| !v.trim().is_empty())
+        .map(String::from)
+}
+
+/// Middleware opening the per-request correlation span
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RequestContext;
+
+impl<S, B> Transform<S, ServiceRequest> for RequestContext
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = RequestContextMiddleware<S>;
+    type Future = Ready<std::result::Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RequestContextMiddleware {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+pub struct RequestContextMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for RequestContextMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = std::result::Result<Self::Response, Self::Error>>>>;
+
+    forward_ready!(service);
+
+    fn call(&self, mut req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+
+        let request_id = header_value(&req, REQUEST_ID_HEADER)
+            .unwrap_or_else(|| Uuid::new_v4().to_string());
+        let user_id = header_value(&req, USER_ID_HEADER);
+        let org_id = header_value(&req, ORG_ID_HEADER);
+        let trace_id = extract_trace_id(req.request());
+
+        // Make a generated id visible to handlers that read the header directly
+        let header = HeaderValue::from_str(&request_id).ok();
+        if let Some(ref value) = header {
+            req.headers_mut()
+                .insert(HeaderName::from_static("x-request-id"), value.clone());
+        }
+
+        let span = tracing::info_span!(
+            "request",
+            request_id = %request_id,
+            user_id = user_id.as_deref().unwrap_or(""),
+            org_id = org_id.as_deref().unwrap_or(""),
+            trace_id = trace_id.as_deref().unwrap_or(""),
+        );
+
+        Box::pin(
+            async move {
+                let mut res = service.call(req).await?;
+                if let Some(value) = header {
+                    res.headers_mut()
+                        .insert(HeaderName::from_static("x-request-id"), value);
+                }
+                Ok(res)
+            }
+            .instrument(span),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{test, web, App, HttpRequest, HttpResponse};
+
+    async fn echo_request_id(req: HttpRequest) -> HttpResponse {
+        let seen = req
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .and_then(|h| h.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        HttpResponse::Ok().body(seen)
+    }
+
+    #[actix_web::test]
+    async fn test_generated_request_id_is_echoed() {
+        let app = test::init_service(
+            App::new()
+                .wrap(RequestContext)
+                .route("/ping", web::get().to(echo_request_id)),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri("/ping").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+
+        let request_id = resp
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .and_then(|h| h.to_str().ok())
+            .unwrap()
+            .to_string();
+        assert!(Uuid::parse_str(&request_id).is_ok());
+
+        // The handler saw the same generated id
+        let body = test::read_body(resp).await;
+        assert_eq!(body, request_id.as_bytes());
+    }
+
+    #[actix_web::test]
+    async fn test_inbound_request_id_is_preserved() {
+        let app = test::init_service(
+            App::new()
+                .wrap(RequestContext)
+                .route("/ping", web::get().to(echo_request_id)),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/ping")
+            .insert_header((REQUEST_ID_HEADER, "req-123"))
+            .insert_header((USER_ID_HEADER, Uuid::new_v4().to_string()))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(resp.headers().get(REQUEST_ID_HEADER).unwrap(), "req-123");
+    }
+}
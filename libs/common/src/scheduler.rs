@@ -0,0 +1,240 @@
+//! Periodic background tasks
+//!
+//! A service registers its background work on a [`Scheduler`] in `main` and
+//! starts it next to the HTTP server. Every task runs on its own loop: a run
+//! that fails or panics is logged and the task runs again at its next slot,
+//! without affecting any other task. [`SchedulerHandle::shutdown`] stops the
+//! loops once the runs in progress have finished.
+
+use chrono::{DateTime, Days, NaiveTime, Utc};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+use tracing::{debug, error, info, warn};
+
+use crate::error::Result;
+
+type TaskFuture = Pin<Box<dyn Future<Output = Result<()>> + Send>>;
+type TaskFn = Arc<dyn Fn() -> TaskFuture + Send + Sync>;
+
+/// When a task runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Schedule {
+    /// On start, then `interval` after each run began
+    Every(Duration),
+    /// Once a day at this UTC time
+    DailyAt(NaiveTime),
+}
+
+impl Schedule {
+    /// When to run next, after a run that began at `started`
+    fn next_run(&self, started: Instant, now: DateTime<Utc>) -> Instant {
+        match self {
+            Schedule::Every(interval) => started + *interval,
+            Schedule::DailyAt(time) => Instant::now() + until_next(*time, now),
+        }
+    }
+}
+
+/// Time from `now` until the next occurrence of `time` (UTC)
+fn until_next(time: NaiveTime, now: DateTime<Utc>) -> Duration {
+    let today = now.date_naive().and_time(time).and_utc();
+    let next = if today > now {
+        today
+    } else {
+        today.checked_add_days(Days::new(1)).unwrap_or(today)
+    };
+    (next - now).to_std().unwrap_or_default()
+}
+
+struct Task {
+    name: String,
+    schedule: Schedule,
+    run: TaskFn,
+}
+
+/// Registry of background tasks, started with [`start`](Scheduler::start)
+#[derive(Default)]
+pub struct Scheduler {
+    tasks: Vec<Task>,
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run `task` on start and then every `interval`
+    pub fn every<F, Fut>(&mut self, interval: Duration, name: impl Into<String>, task: F) -> &mut Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        self.register(Schedule::Every(interval), name.into(), task)
+    }
+
+    /// Run `task` once a day at `time` (UTC)
+    pub fn daily_at<F, Fut>(&mut self, time: NaiveTime, name: impl Into<String>, task: F) -> &mut Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        self.register(Schedule::DailyAt(time), name.into(), task)
+    }
+
+    fn register<F, Fut>(&mut self, schedule: Schedule, name: String, task: F) -> &mut Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        self.tasks.push(Task {
+            name,
+            schedule,
+            run: Arc::new(move || Box::pin(task()) as TaskFuture),
+        });
+        self
+    }
+
+    /// Spawn a loop per task on the current Tokio runtime
+    pub fn start(self) -> SchedulerHandle {
+        let (shutdown, receiver) = watch::channel(false);
+        let runners = self
+            .tasks
+            .into_iter()
+            .map(|task| tokio::spawn(run_task(task, receiver.clone())))
+            .collect();
+
+        SchedulerHandle { shutdown, runners }
+    }
+}
+
+/// Running scheduler; dropping it stops the tasks as well
+pub struct SchedulerHandle {
+    shutdown: watch::Sender<bool>,
+    runners: Vec<JoinHandle<()>>,
+}
+
+impl SchedulerHandle {
+    /// Stop every task loop, waiting for runs in progress to finish
+    pub async fn shutdown(self) {
+        let _ = self.shutdown.send(true);
+        for runner in self.runners {
+            let _ = runner.await;
+        }
+    }
+}
+
+async fn run_task(task: Task, mut shutdown: watch::Receiver<bool>) {
+    let mut next = match task.schedule {
+        Schedule::Every(_) => Instant::now(),
+        Schedule::DailyAt(time) => Instant::now() + until_next(time, Utc::now()),
+    };
+
+    loop {
+        tokio::select! {
+            _ = tokio::time::sleep_until(next) => {}
+            // A dropped handle stops the task as well
+            _ = shutdown.changed() => break,
+        }
+
+        let started = Instant::now();
+        // Spawned so a panic ends this run only, not the task's loop
+        match tokio::spawn((task.run)()).await {
+            Ok(Ok(())) => debug!(task = %task.name, "Scheduled task finished"),
+            Ok(Err(e)) => warn!(task = %task.name, error = %e, "Scheduled task failed"),
+            Err(e) => error!(task = %task.name, error = %e, "Scheduled task panicked"),
+        }
+        next = task.schedule.next_run(started, Utc::now());
+    }
+
+    info!(task = %task.name, "Scheduled task stopped");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::AppError;
+    use chrono::TimeZone;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    async fn bump(count: Arc<AtomicUsize>) -> Result<()> {
+        count.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+
+    async fn blow_up(count: Arc<AtomicUsize>) -> Result<()> {
+        count.fetch_add(1, Ordering::SeqCst);
+        panic!("task blew up");
+    }
+
+    #[actix_web::test]
+    async fn test_registered_task_runs() {
+        let runs = Arc::new(AtomicUsize::new(0));
+        let counted = runs.clone();
+        let mut scheduler = Scheduler::new();
+        scheduler.every(Duration::from_millis(10), "count", move || bump(counted.clone()));
+
+        let handle = scheduler.start();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        handle.shutdown().await;
+
+        assert!(runs.load(Ordering::SeqCst) >= 1);
+    }
+
+    #[actix_web::test]
+    async fn test_failing_and_panicking_tasks_do_not_stop_others() {
+        let panics = Arc::new(AtomicUsize::new(0));
+        let runs = Arc::new(AtomicUsize::new(0));
+        let (panicking, counted) = (panics.clone(), runs.clone());
+        let mut scheduler = Scheduler::new();
+        scheduler
+            .every(Duration::from_millis(5), "panics", move || blow_up(panicking.clone()))
+            .every(Duration::from_millis(5), "fails", || async {
+                Err::<(), _>(AppError::Internal("task failed".to_string()))
+            })
+            .every(Duration::from_millis(5), "count", move || bump(counted.clone()));
+
+        let handle = scheduler.start();
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        handle.shutdown().await;
+
+        assert!(runs.load(Ordering::SeqCst) >= 2);
+        // The panicking task itself keeps being scheduled
+        assert!(panics.load(Ordering::SeqCst) >= 2);
+    }
+
+    #[actix_web::test]
+    async fn test_shutdown_waits_for_run_in_progress() {
+        let finished = Arc::new(AtomicUsize::new(0));
+        let counted = finished.clone();
+        let mut scheduler = Scheduler::new();
+        scheduler.every(Duration::from_secs(3600), "slow", move || {
+            let counted = counted.clone();
+            async move {
+                tokio::time::sleep(Duration::from_millis(30)).await;
+                bump(counted).await
+            }
+        });
+
+        let handle = scheduler.start();
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        handle.shutdown().await;
+
+        assert_eq!(finished.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_daily_schedule_waits_for_next_occurrence() {
+        let two_am = NaiveTime::from_hms_opt(2, 0, 0).unwrap();
+
+        let before = Utc.with_ymd_and_hms(2025, 3, 10, 1, 30, 0).unwrap();
+        assert_eq!(until_next(two_am, before), Duration::from_secs(30 * 60));
+
+        let after = Utc.with_ymd_and_hms(2025, 3, 10, 2, 0, 0).unwrap();
+        assert_eq!(until_next(two_am, after), Duration::from_secs(24 * 3600));
+    }
+}
@@ -0,0 +1,111 @@
+//! Tracing initialization shared by all services
+//!
+//! Always installs a stdout `fmt` layer. When `OTEL_EXPORTER_OTLP_ENDPOINT` is
+//! set, spans are additionally exported to that collector over OTLP/gRPC and
+//! the W3C trace-context propagator is installed so inbound `traceparent`
+//! headers continue the caller's trace.
+
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::{propagation::TraceContextPropagator, runtime, trace, Resource};
+use tracing::Subscriber;
+use tracing_subscriber::{layer::SubscriberExt, EnvFilter};
+
+use crate::error::{AppError, Result};
+
+/// Environment variable naming the OTLP collector endpoint
+pub const OTLP_ENDPOINT_ENV: &str = "OTEL_EXPORTER_OTLP_ENDPOINT";
+
+/// Read the OTLP endpoint from the environment, ignoring empty values
+pub fn otlp_endpoint_from_env() -> Option<String> {
+    std::env::var(OTLP_ENDPOINT_ENV)
+        .ok()
+        .filter(|v| !v.trim().is_empty())
+}
+
+/// Build the subscriber without installing it
+///
+/// Log level defaults to `info` and can be overridden with `RUST_LOG`.
+pub fn build_subscriber(
+    service_name: &str,
+    otlp_endpoint: Option<&str>,
+) -> Result<impl Subscriber + Send + Sync + 'static> {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+
+    let otel_layer = match otlp_endpoint {
+        Some(endpoint) => {
+            let provider = opentelemetry_otlp::new_pipeline()
+                .tracing()
+                .with_exporter(
+                    opentelemetry_otlp::new_exporter()
+                        .tonic()
+                        .with_endpoint(endpoint),
+                )
+                .with_trace_config(trace::Config::default().with_resource(Resource::new(vec![
+                    KeyValue::new("service.name", service_name.to_string()),
+                ])))
+                .install_batch(runtime::Tokio)
+                .map_err(|e| AppError::Internal(format!("Failed to initialize OTLP exporter: {}", e)))?;
+
+            let tracer = provider.tracer(service_name.to_string());
+            opentelemetry::global::set_tracer_provider(provider);
+            opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
+
+            Some(tracing_opentelemetry::layer().with_tracer(tracer))
+        }
+        None => None,
+    };
+
+    Ok(tracing_subscriber::registry()
+        .with(filter)
+        .with(tracing_subscriber::fmt::layer())
+        .with(otel_layer))
+}
+
+/// Install the global tracing subscriber for a service
+pub fn init(service_name: &str) -> Result<()> {
+    let endpoint = otlp_endpoint_from_env();
+    let subscriber = build_subscriber(service_name, endpoint.as_deref())?;
+
+    tracing::subscriber::set_global_default(subscriber)
+        .map_err(|e| AppError::Internal(format!("Failed to install tracing subscriber: {}", e)))?;
+
+    if let Some(endpoint) = endpoint {
+        tracing::info!("Exporting spans for {} to {}", service_name, endpoint);
+    }
+
+    Ok(())
+}
+
+/// Flush pending spans; call before the process exits
+pub fn shutdown() {
+    opentelemetry::global::shutdown_tracer_provider();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Shutting down the batch exporter blocks until its task flushes, which
+    // needs a second worker thread
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_init_with_and_without_otlp_endpoint() {
+        std::env::remove_var(OTLP_ENDPOINT_ENV);
+        assert!(otlp_endpoint_from_env().is_none());
+        assert!(build_subscriber("test-service", None).is_ok());
+
+        std::env::set_var(OTLP_ENDPOINT_ENV, "http://localhost:4317");
+        let endpoint = otlp_endpoint_from_env();
+        assert_eq!(endpoint.as_deref(), Some("http://localhost:4317"));
+        // The exporter connects lazily, so no collector is needed here
+        assert!(build_subscriber("test-service", endpoint.as_deref()).is_ok());
+
+        std::env::set_var(OTLP_ENDPOINT_ENV, "  ");
+        assert!(otlp_endpoint_from_env().is_none());
+
+        std::env::remove_var(OTLP_ENDPOINT_ENV);
+        assert!(init("test-service").is_ok());
+        shutdown();
+    }
+}
@@ -0,0 +1,228 @@
+//! Test doubles shared by the workspace's unit tests (`test-util` feature)
+//!
+//! Also holds the database pool and seed helpers used by each crate's
+//! `tests/integration` suite. Those tests run against the migrated database
+//! at `DATABASE_URL` and are `#[ignore]`d by default:
+//! `cargo test --test integration -- --ignored`.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use sqlx::PgPool;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use uuid::Uuid;
+
+/// Canned reply served by [`MockServer`]
+#[derive(Debug, Clone)]
+pub struct MockResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl MockResponse {
+    /// Empty JSON reply with `status`
+    pub fn new(status: u16) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: String::new(),
+        }
+    }
+
+    pub fn with_body(mut self, body: &str) -> Self {
+        self.body = body.to_string();
+        self
+    }
+
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    fn to_http(&self) -> String {
+        let headers: String = self
+            .headers
+            .iter()
+            .map(|(name, value)| format!("{}: {}\r\n", name, value))
+            .collect();
+        format!(
+            "HTTP/1.1 {} X\r\n{}content-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+            self.status,
+            headers,
+            self.body.len(),
+            self.body
+        )
+    }
+}
+
+/// Minimal HTTP server answering each request with the next canned
+/// response, repeating the last one once exhausted, and capturing each
+/// request's raw text
+#[derive(Clone)]
+pub struct MockServer {
+    url: String,
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl MockServer {
+    pub async fn start(responses: Vec<MockResponse>) -> Self {
+        Self::start_with_delay(responses, Duration::ZERO).await
+    }
+
+    /// Like `start`, but waits `delay` before answering each request
+    pub async fn start_with_delay(responses: Vec<MockResponse>, delay: Duration) -> Self {
+        assert!(!responses.is_empty(), "MockServer needs at least one response");
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let captured = requests.clone();
+
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = match listener.accept().await {
+                    Ok(conn) => conn,
+                    Err(_) => return,
+                };
+
+                // Answer each connection on its own task so a delayed reply
+                // does not hold back the client's retries
+                let captured = captured.clone();
+                let responses = responses.clone();
+                tokio::spawn(async move {
+                    let raw = read_request(&mut socket).await;
+                    let n = {
+                        let mut requests = captured.lock().unwrap();
+                        requests.push(raw);
+                        requests.len() - 1
+                    };
+                    let response = responses[n.min(responses.len() - 1)].to_http();
+
+                    tokio::time::sleep(delay).await;
+                    let _ = socket.write_all(response.as_bytes()).await;
+                    let _ = socket.shutdown().await;
+                });
+            }
+        });
+
+        Self {
+            url: format!("http://{}", addr),
+            requests,
+        }
+    }
+
+    /// Like `start`, for synchronous tests: the server runs on its own
+    /// runtime in a background thread
+    pub fn start_blocking(responses: Vec<MockResponse>) -> Self {
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap();
+            runtime.block_on(async move {
+                let _ = tx.send(Self::start(responses).await);
+                std::future::pending::<()>().await;
+            });
+        });
+        rx.recv().unwrap()
+    }
+
+    /// Base URL, e.g. `http://127.0.0.1:PORT`
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Number of requests received so far
+    pub fn hits(&self) -> usize {
+        self.requests.lock().unwrap().len()
+    }
+
+    /// Raw text of each request received so far
+    pub fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+/// Read one request: its head and as much body as `content-length` announces
+async fn read_request(socket: &mut TcpStream) -> String {
+    let mut raw = Vec::new();
+    let mut buf = [0u8; 4096];
+    while let Ok(n) = socket.read(&mut buf).await {
+        if n == 0 {
+            break;
+        }
+        raw.extend_from_slice(&buf[..n]);
+        if request_complete(&raw) {
+            break;
+        }
+    }
+    String::from_utf8_lossy(&raw).into_owned()
+}
+
+fn request_complete(raw: &[u8]) -> bool {
+    let text = String::from_utf8_lossy(raw);
+    let Some((head, body)) = text.split_once("\r\n\r\n") else {
+        return false;
+    };
+    let length = head
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+        .unwrap_or(0);
+    body.len() >= length
+}
+
+/// Pool for the migrated database at `DATABASE_URL`
+pub async fn database() -> PgPool {
+    let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    PgPool::connect(&url).await.expect("Failed to connect to DATABASE_URL")
+}
+
+/// Insert a user called `name` with a unique `name-<uuid>@example.com` email
+pub async fn seed_user(pool: &PgPool, name: &str) -> Uuid {
+    let (id,): (Uuid,) = sqlx::query_as(
+        "INSERT INTO users (email, name, password_hash) VALUES ($1, $2, 'x') RETURNING id",
+    )
+    .bind(format!("{}-{}@example.com", name, Uuid::new_v4()))
+    .bind(name)
+    .fetch_one(pool)
+    .await
+    .expect("Failed to seed user");
+    id
+}
+
+/// Insert an organization called `name` with a unique slug
+pub async fn seed_organization(pool: &PgPool, name: &str) -> Uuid {
+    let (id,): (Uuid,) = sqlx::query_as("INSERT INTO organizations (name, slug) VALUES ($1, $2) RETURNING id")
+        .bind(name)
+        .bind(format!("{}-{}", name, Uuid::new_v4()))
+        .fetch_one(pool)
+        .await
+        .expect("Failed to seed organization");
+    id
+}
+
+/// Add `user_id` to `organization_id` as `role` (`owner`, `admin`, `member` or `viewer`)
+pub async fn seed_member(pool: &PgPool, organization_id: Uuid, user_id: Uuid, role: &str) {
+    sqlx::query("INSERT INTO organization_members (organization_id, user_id, role) VALUES ($1, $2, $3)")
+        .bind(organization_id)
+        .bind(user_id)
+        .bind(role)
+        .execute(pool)
+        .await
+        .expect("Failed to seed organization member");
+}
+
+/// Insert a team called `name` in `organization_id`
+pub async fn seed_team(pool: &PgPool, organization_id: Uuid, name: &str) -> Uuid {
+    let (id,): (Uuid,) = sqlx::query_as("INSERT INTO teams (organization_id, name) VALUES ($1, $2) RETURNING id")
+        .bind(organization_id)
+        .bind(name)
+        .fetch_one(pool)
+        .await
+        .expect("Failed to seed team");
+    id
+}
@@ -0,0 +1,99 @@
+//! UTC timestamps exchanged as RFC3339 strings
+//!
+//! [`Timestamp`] wraps a `DateTime<Utc>` and serializes to the same RFC3339
+//! form the governance types have always carried, so persisted events read
+//! back unchanged. Deserializing a value that is not RFC3339 fails instead
+//! of carrying the malformed string along.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp(DateTime<Utc>);
+
+impl Timestamp {
+    pub fn now() -> Self {
+        Self(Utc::now())
+    }
+
+    /// Parse an RFC3339 timestamp, normalizing its offset to UTC
+    pub fn parse(value: &str) -> Result<Self, chrono::ParseError> {
+        DateTime::parse_from_rfc3339(value).map(|t| Self(t.with_timezone(&Utc)))
+    }
+
+    pub fn as_datetime(&self) -> &DateTime<Utc> {
+        &self.0
+    }
+}
+
+impl From<DateTime<Utc>> for Timestamp {
+    fn from(value: DateTime<Utc>) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Timestamp> for DateTime<Utc> {
+    fn from(value: Timestamp) -> Self {
+        value.0
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = chrono::ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::parse(value)
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0.to_rfc3339())
+    }
+}
+
+impl Serialize for Timestamp {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Self::parse(&value).map_err(|e| {
+            serde::de::Error::custom(format!("'{}' is not an RFC3339 timestamp: {}", value, e))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_round_trips_as_rfc3339() {
+        let ts = Timestamp::from(Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap());
+
+        let json = serde_json::to_string(&ts).unwrap();
+        assert_eq!(json, "\"2024-01-02T03:04:05+00:00\"");
+        assert_eq!(serde_json::from_str::<Timestamp>(&json).unwrap(), ts);
+        assert_eq!(ts.to_string(), "2024-01-02T03:04:05+00:00");
+    }
+
+    #[test]
+    fn test_offsets_are_normalized_to_utc() {
+        let ts: Timestamp = serde_json::from_str("\"2024-01-02T05:04:05+02:00\"").unwrap();
+        assert_eq!(ts, "2024-01-02T03:04:05Z".parse::<Timestamp>().unwrap());
+    }
+
+    #[test]
+    fn test_malformed_timestamp_fails_deserialization() {
+        for value in ["\"yesterday\"", "\"2024-01-02\"", "\"2024-13-01T00:00:00Z\"", "\"\"", "42"] {
+            assert!(serde_json::from_str::<Timestamp>(value).is_err(), "{} was accepted", value);
+        }
+    }
+}
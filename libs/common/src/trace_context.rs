@@ -0,0 +1,215 @@
+//! W3C trace-context propagation helpers
+//!
+//! Extracts the caller's trace from inbound `traceparent` / `X-Trace-Id`
+//! headers and injects `traceparent` / `tracestate` into outgoing upstream
+//! requests so their latency is correlated with ours.
+
+use actix_web::HttpRequest;
+use uuid::Uuid;
+
+pub const TRACEPARENT_HEADER: &str = "traceparent";
+pub const TRACESTATE_HEADER: &str = "tracestate";
+pub const TRACE_ID_HEADER: &str = "X-Trace-Id";
+
+/// Raw trace identifier from `X-Trace-Id`, falling back to `traceparent`
+pub fn extract_trace_id(req: &HttpRequest) -> Option<String> {
+    req.headers()
+        .get(TRACE_ID_HEADER)
+        .or_else(|| req.headers().get(TRACEPARENT_HEADER))
+        .and_then(|h| h.to_str().ok())
+        .map(String::from)
+}
+
+/// Trace context for one outgoing call
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceContext {
+    /// 32 lowercase hex characters
+    pub trace_id: String,
+    /// Span id of the caller, if the inbound request carried one
+    pub parent_span_id: Option<String>,
+    /// Fresh 16-hex span id identifying our outgoing call
+    pub span_id: String,
+    pub sampled: bool,
+    pub tracestate: Option<String>,
+}
+
+impl TraceContext {
+    /// Start a new trace with no parent
+    pub fn new_root() -> Self {
+        Self {
+            trace_id: Uuid::new_v4().simple().to_string(),
+            parent_span_id: None,
+            span_id: new_span_id(),
+            sampled: true,
+            tracestate: None,
+        }
+    }
+
+    /// Continue the inbound trace, or start a new one if none is present
+    pub fn from_request(req: &HttpRequest) -> Self {
+        let tracestate = req
+            .headers()
+            .get(TRACESTATE_HEADER)
+            .and_then(|h| h.to_str().ok())
+            .map(String::from);
+
+        let traceparent = req
+            .headers()
+            .get(TRACEPARENT_HEADER)
+            .and_then(|h| h.to_str().ok());
+
+        if let Some((trace_id, parent_span_id, sampled)) = traceparent.and_then(parse_traceparent) {
+            return Self {
+                trace_id,
+                parent_span_id: Some(parent_span_id),
+                span_id: new_span_id(),
+                sampled,
+                tracestate,
+            };
+        }
+
+        let mut ctx = extract_trace_id(req)
+            .and_then(|id| normalize_trace_id(&id))
+            .map(|trace_id| Self {
+                trace_id,
+                ..Self::new_root()
+            })
+            .unwrap_or_else(Self::new_root);
+        ctx.tracestate = tracestate;
+        ctx
+    }
+
+    /// Render as a W3C `traceparent` header value
+    pub fn traceparent(&self) -> String {
+        format!(
+            "00-{}-{}-{}",
+            self.trace_id,
+            self.span_id,
+            if self.sampled { "01" } else { "00" }
+        )
+    }
+
+    /// Inject trace headers into an outgoing request
+    pub fn inject(&self, builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        let mut builder = builder
+            .header(TRACEPARENT_HEADER, self.traceparent())
+            .header(TRACE_ID_HEADER, &self.trace_id);
+
+        if let Some(ref state) = self.tracestate {
+            builder = builder.header(TRACESTATE_HEADER, state);
+        }
+
+        builder
+    }
+}
+
+fn new_span_id() -> String {
+    Uuid::new_v4().simple().to_string()[..16].to_string()
+}
+
+fn is_lower_hex(s: &str, len: usize) -> bool {
+    s.len() == len && s.bytes().all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+}
+
+/// Parse `00-<trace-id>-<parent-id>-<flags>`; all-zero ids are invalid
+fn parse_traceparent(value: &str) -> Option<(String, String, bool)> {
+    let parts: Vec<&str> = value.trim().split('-').collect();
+    if parts.len() != 4 || !is_lower_hex(parts[0], 2) || parts[0] == "ff" {
+        return None;
+    }
+
+    let (trace_id, parent_id, flags) = (parts[1], parts[2], parts[3]);
+    if !is_lower_hex(trace_id, 32) || trace_id.bytes().all(|b| b == b'0') {
+        return None;
+    }
+    if !is_lower_hex(parent_id, 16) || parent_id.bytes().all(|b| b == b'0') {
+        return None;
+    }
+    if !is_lower_hex(flags, 2) {
+        return None;
+    }
+
+    let sampled = u8::from_str_radix(flags, 16).map(|f| f & 0x01 == 1).unwrap_or(false);
+    Some((trace_id.to_string(), parent_id.to_string(), sampled))
+}
+
+/// Accept a bare 32-hex id or a hyphenated UUID from `X-Trace-Id`
+fn normalize_trace_id(value: &str) -> Option<String> {
+    if let Some((trace_id, _, _)) = parse_traceparent(value) {
+        return Some(trace_id);
+    }
+
+    let candidate = value.trim().replace('-', "").to_ascii_lowercase();
+    if is_lower_hex(&candidate, 32) && !candidate.bytes().all(|b| b == b'0') {
+        Some(candidate)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+
+    const TRACE_ID: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+    const PARENT_ID: &str = "00f067aa0ba902b7";
+
+    #[test]
+    fn test_continues_inbound_traceparent() {
+        let req = TestRequest::default()
+            .insert_header((TRACEPARENT_HEADER, format!("00-{}-{}-01", TRACE_ID, PARENT_ID)))
+            .insert_header((TRACESTATE_HEADER, "vendor=abc"))
+            .to_http_request();
+
+        let ctx = TraceContext::from_request(&req);
+        assert_eq!(ctx.trace_id, TRACE_ID);
+        assert_eq!(ctx.parent_span_id.as_deref(), Some(PARENT_ID));
+        assert_ne!(ctx.span_id, PARENT_ID);
+        assert!(ctx.sampled);
+        assert_eq!(ctx.tracestate.as_deref(), Some("vendor=abc"));
+        assert!(ctx.traceparent().starts_with(&format!("00-{}-", TRACE_ID)));
+    }
+
+    #[test]
+    fn test_uses_x_trace_id_when_no_traceparent() {
+        let req = TestRequest::default()
+            .insert_header((TRACE_ID_HEADER, "4BF92F35-77B3-4DA6-A3CE-929D0E0E4736"))
+            .to_http_request();
+
+        let ctx = TraceContext::from_request(&req);
+        assert_eq!(ctx.trace_id, TRACE_ID);
+        assert!(ctx.parent_span_id.is_none());
+    }
+
+    #[test]
+    fn test_invalid_headers_start_new_trace() {
+        let req = TestRequest::default()
+            .insert_header((TRACEPARENT_HEADER, "00-00000000000000000000000000000000-00f067aa0ba902b7-01"))
+            .insert_header((TRACE_ID_HEADER, "not-a-trace"))
+            .to_http_request();
+
+        let ctx = TraceContext::from_request(&req);
+        assert!(is_lower_hex(&ctx.trace_id, 32));
+        assert!(is_lower_hex(&ctx.span_id, 16));
+        assert!(ctx.parent_span_id.is_none());
+    }
+
+    #[test]
+    fn test_inject_sets_outgoing_headers() {
+        let req = TestRequest::default()
+            .insert_header((TRACEPARENT_HEADER, format!("00-{}-{}-00", TRACE_ID, PARENT_ID)))
+            .to_http_request();
+        let ctx = TraceContext::from_request(&req);
+
+        let outgoing = ctx
+            .inject(reqwest::Client::new().post("http://provider.invalid/v1"))
+            .build()
+            .unwrap();
+
+        let traceparent = outgoing.headers().get(TRACEPARENT_HEADER).unwrap().to_str().unwrap();
+        assert_eq!(traceparent, format!("00-{}-{}-00", TRACE_ID, ctx.span_id));
+        assert_eq!(outgoing.headers().get(TRACE_ID_HEADER).unwrap(), TRACE_ID);
+        assert!(outgoing.headers().get(TRACESTATE_HEADER).is_none());
+    }
+}
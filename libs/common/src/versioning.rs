@@ -0,0 +1,244 @@
+//! API versioning and route deprecation
+//!
+//! Every service mounts its routes under `/api/v1`. [`ApiVersioning`] stamps
+//! each response with the served version and, for routes listed in
+//! [`DEPRECATED_ROUTES`], adds the `Deprecation` (RFC 9745), `Sunset`
+//! (RFC 8594) and successor `Link` headers so clients can migrate before a
+//! route is removed. [`version_endpoint`] reports the versions a service serves.
+
+use actix_web::{
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    get,
+    http::header::{HeaderName, HeaderValue},
+    Error, HttpResponse,
+};
+use chrono::DateTime;
+use std::future::{ready, Future, Ready};
+use std::pin::Pin;
+use std::rc::Rc;
+
+use crate::response::ApiResponse;
+
+/// Version of the API served under `/api/v1`
+pub const API_VERSION: &str = "v1";
+
+/// Every version this build still serves, oldest first
+pub const SUPPORTED_VERSIONS: &[&str] = &[API_VERSION];
+
+pub const API_VERSION_HEADER: &str = "Api-Version";
+
+/// A route scheduled for removal
+#[derive(Debug, Clone, Copy)]
+pub struct DeprecatedRoute {
+    pub method: &'static str,
+    /// Route pattern including the scope, e.g. `/api/v1/policies/{id}`
+    pub pattern: &'static str,
+    /// Unix time from which the route is deprecated
+    pub deprecated_at: i64,
+    /// Unix time after which the route may be removed
+    pub sunset_at: Option<i64>,
+    /// Route that replaces this one
+    pub successor: Option<&'static str>,
+}
+
+impl DeprecatedRoute {
+    fn headers(&self) -> Vec<(HeaderName, String)> {
+        let mut headers = vec![(
+            HeaderName::from_static("deprecation"),
+            format!("@{}", self.deprecated_at),
+        )];
+        if let Some(sunset) = self.sunset_at.and_then(|secs| DateTime::from_timestamp(secs, 0)) {
+            headers.push((
+                HeaderName::from_static("sunset"),
+                sunset.format("%a, %d %b %Y %H:%M:%S GMT").to_string(),
+            ));
+        }
+        if let Some(successor) = self.successor {
+            headers.push((
+                HeaderName::from_static("link"),
+                format!("<{}>; rel=\"successor-version\"", successor),
+            ));
+        }
+        headers
+    }
+}
+
+/// Routes answered with `Deprecation`/`Sunset` headers until they are removed
+///
+/// One registry for every service: each matches it against the patterns of
+/// its own routes, so an entry takes effect in the service that serves it.
+pub const DEPRECATED_ROUTES: &[DeprecatedRoute] = &[];
+
+/// The entry of `routes` matching a request, if any
+fn find_deprecated<'a>(routes: &'a [DeprecatedRoute], method: &str, pattern: &str) -> Option<&'a DeprecatedRoute> {
+    routes
+        .iter()
+        .find(|r| r.method.eq_ignore_ascii_case(method) && r.pattern == pattern)
+}
+
+/// Report the served and supported API versions
+#[get("/api/version")]
+pub async fn version_endpoint() -> crate::Result<HttpResponse> {
+    Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
+        "version": API_VERSION,
+        "supported_versions": SUPPORTED_VERSIONS,
+    }))))
+}
+
+/// Middleware adding the version and deprecation headers
+///
+/// Deprecated routes are matched against the pattern of the resource that
+/// served the request.
+pub struct ApiVersioning {
+    routes: &'static [DeprecatedRoute],
+}
+
+impl ApiVersioning {
+    pub fn new() -> Self {
+        Self::with_routes(DEPRECATED_ROUTES)
+    }
+
+    pub fn with_routes(routes: &'static [DeprecatedRoute]) -> Self {
+        Self { routes }
+    }
+}
+
+impl Default for ApiVersioning {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for ApiVersioning
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = ApiVersioningMiddleware<S>;
+    type Future = Ready<std::result::Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(ApiVersioningMiddleware {
+            service: Rc::new(service),
+            routes: self.routes,
+        }))
+    }
+}
+
+pub struct ApiVersioningMiddleware<S> {
+    service: Rc<S>,
+    routes: &'static [DeprecatedRoute],
+}
+
+impl<S, B> Service<ServiceRequest> for ApiVersioningMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = std::result::Result<Self::Response, Self::Error>>>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let routes = self.routes;
+
+        Box::pin(async move {
+            let mut res = service.call(req).await?;
+
+            let deprecated = res
+                .request()
+                .match_pattern()
+                .and_then(|pattern| find_deprecated(routes, res.request().method().as_str(), &pattern))
+                .map(DeprecatedRoute::headers)
+                .unwrap_or_default();
+
+            let headers = res.headers_mut();
+            headers.insert(
+                HeaderName::from_static("api-version"),
+                HeaderValue::from_static(API_VERSION),
+            );
+            for (name, value) in deprecated {
+                if let Ok(value) = HeaderValue::from_str(&value) {
+                    headers.insert(name, value);
+                }
+            }
+            Ok(res)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{test, web, App};
+
+    const DEPRECATED: &[DeprecatedRoute] = &[DeprecatedRoute {
+        method: "GET",
+        pattern: "/api/v1/reports/{id}",
+        deprecated_at: 1_767_225_600,
+        sunset_at: Some(1_798_761_600),
+        successor: Some("/api/v2/reports/{id}"),
+    }];
+
+    async fn ok() -> HttpResponse {
+        HttpResponse::Ok().finish()
+    }
+
+    fn header<'a, B>(resp: &'a ServiceResponse<B>, name: &str) -> Option<&'a str> {
+        resp.headers().get(name).and_then(|h| h.to_str().ok())
+    }
+
+    #[actix_web::test]
+    async fn test_deprecated_route_emits_deprecation_headers() {
+        let app = test::init_service(
+            App::new().wrap(ApiVersioning::with_routes(DEPRECATED)).service(
+                web::scope("/api/v1")
+                    .route("/reports/{id}", web::get().to(ok))
+                    .route("/reports/{id}", web::delete().to(ok))
+                    .route("/summary", web::get().to(ok)),
+            ),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri("/api/v1/reports/42").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+        assert_eq!(header(&resp, "Deprecation"), Some("@1767225600"));
+        assert_eq!(header(&resp, "Sunset"), Some("Fri, 01 Jan 2027 00:00:00 GMT"));
+        assert_eq!(
+            header(&resp, "Link"),
+            Some("</api/v2/reports/{id}>; rel=\"successor-version\"")
+        );
+        assert_eq!(header(&resp, API_VERSION_HEADER), Some(API_VERSION));
+
+        // Other routes, and other methods on the same route, are not deprecated
+        for req in [
+            test::TestRequest::get().uri("/api/v1/summary").to_request(),
+            test::TestRequest::delete().uri("/api/v1/reports/42").to_request(),
+        ] {
+            let resp = test::call_service(&app, req).await;
+            assert!(header(&resp, "Deprecation").is_none());
+            assert!(header(&resp, "Sunset").is_none());
+            assert_eq!(header(&resp, API_VERSION_HEADER), Some(API_VERSION));
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_version_endpoint_lists_supported_versions() {
+        let app = test::init_service(App::new().service(version_endpoint)).await;
+
+        let req = test::TestRequest::get().uri("/api/version").to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+
+        assert_eq!(body["data"]["version"], API_VERSION);
+        assert_eq!(body["data"]["supported_versions"], serde_json::json!(["v1"]));
+    }
+}
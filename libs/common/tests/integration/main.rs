@@ -0,0 +1,5 @@
+//! Tests against the migrated database at `DATABASE_URL`
+//!
+//! Run with `cargo test -p llm-governance-common --features test-util --test integration -- --ignored`.
+
+mod utils;
@@ -0,0 +1,29 @@
+use std::time::{Duration, Instant};
+
+use llm_governance_common::testing::database;
+use llm_governance_common::utils::{begin_with_timeout, with_timeout};
+use llm_governance_common::AppError;
+
+#[tokio::test]
+#[ignore] // Requires Postgres at DATABASE_URL
+async fn test_with_timeout_cancels_slow_query_on_the_server() {
+    let pool = database().await;
+    let limit = Duration::from_millis(200);
+
+    let started = Instant::now();
+    let mut tx = begin_with_timeout(&pool, limit).await.unwrap();
+    let err = with_timeout(limit, sqlx::query("SELECT pg_sleep(5)").execute(&mut *tx))
+        .await
+        .unwrap_err();
+    drop(tx);
+
+    assert!(matches!(err, AppError::UpstreamUnavailable(_)), "got {:?}", err);
+    assert!(started.elapsed() < Duration::from_secs(2));
+
+    // The limit was local to the transaction
+    let (timeout,): (String,) = sqlx::query_as("SHOW statement_timeout")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(timeout, "0");
+}
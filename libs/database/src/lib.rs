@@ -1,16 +1,106 @@
-use sqlx::postgres::{PgPool, PgPoolOptions};
+use sqlx::postgres::{PgConnectOptions, PgPool, PgPoolOptions};
 use std::time::Duration;
 
-pub async fn create_pool(database_url: &str) -> Result<PgPool, sqlx::Error> {
+fn pool_options() -> PgPoolOptions {
     PgPoolOptions::new()
         .max_connections(50)
         .min_connections(5)
         .acquire_timeout(Duration::from_secs(30))
         .idle_timeout(Duration::from_secs(600))
-        .connect(database_url)
-        .await
+}
+
+pub async fn create_pool(database_url: &str) -> Result<PgPool, sqlx::Error> {
+    pool_options().connect(database_url).await
+}
+
+/// Pool for read-heavy queries such as cost and audit aggregation
+///
+/// Backed by a read replica when one is configured and by the primary pool
+/// otherwise, so handlers can take it unconditionally. Writes must keep using
+/// the primary `PgPool`.
+#[derive(Debug, Clone)]
+pub struct ReadPool {
+    pool: PgPool,
+    replica: bool,
+}
+
+impl ReadPool {
+    pub fn new(primary: &PgPool, replica: Option<PgPool>) -> Self {
+        match replica {
+            Some(pool) => Self { pool, replica: true },
+            None => Self {
+                pool: primary.clone(),
+                replica: false,
+            },
+        }
+    }
+
+    pub fn pool(&self) -> &PgPool {
+        &self.pool
+    }
+
+    /// Whether queries go to a replica rather than the primary
+    pub fn is_replica(&self) -> bool {
+        self.replica
+    }
+}
+
+/// Build the read pool from `database_read_url`, falling back to `primary`
+///
+/// Replica sessions default to read-only transactions, so a write routed
+/// there by mistake fails instead of silently diverging.
+pub async fn create_read_pool(
+    primary: &PgPool,
+    database_read_url: Option<&str>,
+) -> Result<ReadPool, sqlx::Error> {
+    let replica = match database_read_url.map(str::trim).filter(|url| !url.is_empty()) {
+        Some(url) => {
+            let options = url
+                .parse::<PgConnectOptions>()?
+                .options([("default_transaction_read_only", "on")]);
+            Some(pool_options().connect_with(options).await?)
+        }
+        None => None,
+    };
+
+    Ok(ReadPool::new(primary, replica))
 }
 
 pub async fn run_migrations(pool: &PgPool) -> Result<(), sqlx::migrate::MigrateError> {
     sqlx::migrate!("./migrations").run(pool).await
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lazy_pool(url: &str) -> PgPool {
+        PgPoolOptions::new().connect_lazy(url).unwrap()
+    }
+
+    fn host(pool: &PgPool) -> String {
+        pool.connect_options().get_host().to_string()
+    }
+
+    #[tokio::test]
+    async fn test_reads_use_replica_when_configured() {
+        let primary = lazy_pool("postgres://app@primary.db/governance");
+        let replica = lazy_pool("postgres://app@replica.db/governance");
+
+        let reads = ReadPool::new(&primary, Some(replica));
+        assert!(reads.is_replica());
+        assert_eq!(host(reads.pool()), "replica.db");
+    }
+
+    #[tokio::test]
+    async fn test_reads_fall_back_to_primary() {
+        let primary = lazy_pool("postgres://app@primary.db/governance");
+
+        let reads = create_read_pool(&primary, None).await.unwrap();
+        assert!(!reads.is_replica());
+        assert_eq!(host(reads.pool()), "primary.db");
+
+        let reads = create_read_pool(&primary, Some("  ")).await.unwrap();
+        assert!(!reads.is_replica());
+    }
+}
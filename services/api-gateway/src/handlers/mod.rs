@@ -3,5 +3,7 @@ use actix_web::web;
 pub mod health;
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(llm_governance_common::metrics::metrics_endpoint);
+    cfg.service(llm_governance_common::versioning::version_endpoint);
     cfg.service(web::scope("/api/v1").configure(health::configure));
 }
@@ -0,0 +1,7 @@
+//! Authenticating front door that routes requests to the services, served by `main.rs`
+
+pub mod config;
+pub mod handlers;
+pub mod middleware;
+pub mod models;
+pub mod services;
@@ -1,44 +1,60 @@
 use actix_web::{web, App, HttpServer};
-use tracing::{info, Level};
-use tracing_subscriber;
+use tracing::info;
 
-mod config;
-mod handlers;
-mod middleware;
-mod models;
-mod services;
-
-use config::Config;
-use middleware::CsrfProtection;
+use api_gateway::config::Config;
+use api_gateway::handlers;
+use api_gateway::middleware::{build_cors, AuthMiddleware, CsrfProtection};
+use llm_governance_common::metrics::RequestMetrics;
+use llm_governance_common::request_context::RequestContext;
+use llm_governance_common::telemetry;
+use llm_governance_common::versioning::ApiVersioning;
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
-    tracing_subscriber::fmt()
-        .with_max_level(Level::INFO)
-        .init();
-
     dotenv::dotenv().ok();
-    let config = Config::from_env().expect("Failed to load configuration");
+    telemetry::init("api-gateway").expect("Failed to initialize telemetry");
+
+    let config = Config::from_env()
+        .unwrap_or_else(|e| panic!("Failed to load configuration: {}", e));
+    if let Err(problems) = config.validate() {
+        panic!("Invalid configuration:\n  - {}", problems.join("\n  - "));
+    }
 
     info!("Starting api-gateway on {}:{}", config.host, config.port);
 
     let redis_client = redis::Client::open(config.redis_url.clone())
         .expect("Failed to create Redis client");
 
+    let db_pool = llm_governance_database::create_pool(&config.database_url)
+        .await
+        .expect("Failed to create database pool");
+
     let csrf_secret = config.csrf_secret.clone();
+    let token_verifier = config.token_verifier();
     let host = config.host.clone();
     let port = config.port;
 
-    HttpServer::new(move || {
+    let result = HttpServer::new(move || {
         App::new()
             .app_data(web::Data::new(redis_client.clone()))
             .app_data(web::Data::new(config.clone()))
+            // Innermost, so CORS preflights are answered before authentication;
+            // every authenticated request costs one users lookup for revocation
+            .wrap(AuthMiddleware::new(token_verifier.clone(), db_pool.clone()))
+            .wrap(RequestContext)
             .wrap(tracing_actix_web::TracingLogger::default())
-            .wrap(actix_cors::Cors::permissive())
+            .wrap(RequestMetrics::new("api-gateway"))
+            // Proxied responses keep the deprecation headers of the service
+            // that answered them, so the gateway only stamps the version
+            .wrap(ApiVersioning::with_routes(&[]))
+            .wrap(build_cors(&config))
             .wrap(CsrfProtection::new(csrf_secret.clone()))
             .configure(handlers::configure)
     })
     .bind((host.as_str(), port))?
     .run()
-    .await
+    .await;
+
+    telemetry::shutdown();
+    result
 }
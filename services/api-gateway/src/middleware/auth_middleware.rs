@@ -1,26 +1,29 @@
 use actix_web::{
     dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
-    Error, HttpMessage, HttpResponse,
+    Error, HttpMessage,
 };
 use futures::future::LocalBoxFuture;
 use std::future::{ready, Ready};
-use jsonwebtoken::{decode, DecodingKey, Validation};
-use serde::{Deserialize, Serialize};
-use uuid::Uuid;
+use std::rc::Rc;
+use llm_governance_common::auth::{verify_token_version, TokenVerifier};
+use sqlx::PgPool;
 
+/// Verifies the bearer token of every non-public request and forwards the
+/// caller as `X-User-Id`; tokens revoked by a `token_version` bump are rejected
 pub struct AuthMiddleware {
-    pub jwt_secret: String,
+    verifier: TokenVerifier,
+    pool: PgPool,
 }
 
 impl AuthMiddleware {
-    pub fn new(jwt_secret: String) -> Self {
-        Self { jwt_secret }
+    pub fn new(verifier: TokenVerifier, pool: PgPool) -> Self {
+        Self { verifier, pool }
     }
 }
 
 impl<S, B> Transform<S, ServiceRequest> for AuthMiddleware
 where
-    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
     S::Future: 'static,
     B: 'static,
 {
@@ -32,20 +35,22 @@ where
 
     fn new_transform(&self, service: S) -> Self::Future {
         ready(Ok(AuthMiddlewareService {
-            service,
-            jwt_secret: self.jwt_secret.clone(),
+            service: Rc::new(service),
+            verifier: self.verifier.clone(),
+            pool: self.pool.clone(),
         }))
     }
 }
 
 pub struct AuthMiddlewareService<S> {
-    service: S,
-    jwt_secret: String,
+    service: Rc<S>,
+    verifier: TokenVerifier,
+    pool: PgPool,
 }
 
 impl<S, B> Service<ServiceRequest> for AuthMiddlewareService<S>
 where
-    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
     S::Future: 'static,
     B: 'static,
 {
@@ -55,9 +60,7 @@ where
 
     forward_ready!(service);
 
-    fn call(&self, req: ServiceRequest) -> Self::Future {
-        let jwt_secret = self.jwt_secret.clone();
-
+    fn call(&self, mut req: ServiceRequest) -> Self::Future {
         // Skip auth for public endpoints
         if is_public_endpoint(req.path()) {
             let fut = self.service.call(req);
@@ -65,63 +68,145 @@ where
         }
 
         // Extract and verify JWT token
-        let auth_header = req.headers().get("Authorization");
-
-        if let Some(auth_value) = auth_header {
-            if let Ok(auth_str) = auth_value.to_str() {
-                if let Some(token) = auth_str.strip_prefix("Bearer ") {
-                    match verify_token(token, &jwt_secret) {
-                        Ok(claims) => {
-                            // Add user ID to request headers for downstream services
-                            req.headers_mut().insert(
-                                actix_web::http::header::HeaderName::from_static("x-user-id"),
-                                actix_web::http::header::HeaderValue::from_str(&claims.user_id.to_string())
-                                    .unwrap(),
-                            );
-
-                            // Store claims in request extensions
-                            req.extensions_mut().insert(claims);
-
-                            let fut = self.service.call(req);
-                            return Box::pin(async move { fut.await });
-                        }
-                        Err(_) => {
-                            return Box::pin(async move {
-                                Err(actix_web::error::ErrorUnauthorized("Invalid token"))
-                            });
-                        }
-                    }
-                }
+        let claims = req
+            .headers()
+            .get("Authorization")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .map(|token| self.verifier.verify(token));
+
+        let claims = match claims {
+            Some(Ok(claims)) => claims,
+            Some(Err(_)) => {
+                return Box::pin(async move { Err(actix_web::error::ErrorUnauthorized("Invalid token")) });
             }
-        }
+            None => {
+                return Box::pin(async move {
+                    Err(actix_web::error::ErrorUnauthorized("Missing or invalid authentication"))
+                });
+            }
+        };
+
+        let service = Rc::clone(&self.service);
+        let pool = self.pool.clone();
 
         Box::pin(async move {
-            Err(actix_web::error::ErrorUnauthorized("Missing or invalid authentication"))
-        })
-    }
-}
+            if verify_token_version(&pool, claims.user_id, claims.token_version).await.is_err() {
+                return Err(actix_web::error::ErrorUnauthorized("Token has been revoked"));
+            }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Claims {
-    pub sub: String,
-    pub exp: usize,
-    pub iat: usize,
-    pub user_id: Uuid,
-    pub email: String,
-}
+            // Add user ID to request headers for downstream services
+            req.headers_mut().insert(
+                actix_web::http::header::HeaderName::from_static("x-user-id"),
+                actix_web::http::header::HeaderValue::from_str(&claims.user_id.to_string()).unwrap(),
+            );
 
-fn verify_token(token: &str, secret: &str) -> Result<Claims, jsonwebtoken::errors::Error> {
-    let decoding_key = DecodingKey::from_secret(secret.as_bytes());
-    let validation = Validation::default();
+            // Store claims in request extensions
+            req.extensions_mut().insert(claims);
 
-    let token_data = decode::<Claims>(token, &decoding_key, &validation)?;
-    Ok(token_data.claims)
+            service.call(req).await
+        })
+    }
 }
 
 fn is_public_endpoint(path: &str) -> bool {
     path.starts_with("/api/v1/auth/login") ||
     path.starts_with("/api/v1/auth/register") ||
     path.starts_with("/api/v1/auth/password-reset") ||
+    path.starts_with("/api/v1/auth/refresh") ||
+    path.starts_with("/api/v1/auth/verify-email") ||
     path.starts_with("/api/v1/health") ||
-    path == "/health"
+    path == "/health" ||
+    path == "/metrics" ||
+    path == "/api/version"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{test, web, App, HttpResponse};
+    use jsonwebtoken::{encode, EncodingKey, Header};
+    use llm_governance_common::auth::{AccessClaims, DEFAULT_TOKEN_AUDIENCE, DEFAULT_TOKEN_ISSUER};
+    use uuid::Uuid;
+
+    const SECRET: &str = "test-secret";
+
+    fn token(user_id: Uuid, token_version: i32) -> String {
+        let now = chrono::Utc::now().timestamp() as usize;
+        let claims = AccessClaims {
+            sub: user_id.to_string(),
+            exp: now + 3600,
+            iat: now,
+            iss: DEFAULT_TOKEN_ISSUER.to_string(),
+            aud: DEFAULT_TOKEN_AUDIENCE.to_string(),
+            user_id,
+            email: "gateway@example.com".to_string(),
+            token_version,
+        };
+        encode(&Header::default(), &claims, &EncodingKey::from_secret(SECRET.as_bytes())).unwrap()
+    }
+
+    /// Middleware whose pool never connects, so any revocation lookup fails
+    fn without_database() -> AuthMiddleware {
+        let pool = sqlx::postgres::PgPoolOptions::new()
+            .acquire_timeout(std::time::Duration::from_millis(100))
+            .connect_lazy("postgres://app@127.0.0.1:1/unused")
+            .unwrap();
+        AuthMiddleware::new(TokenVerifier::new(SECRET, DEFAULT_TOKEN_ISSUER, DEFAULT_TOKEN_AUDIENCE), pool)
+    }
+
+    #[actix_web::test]
+    async fn test_public_endpoints_skip_authentication() {
+        let app = test::init_service(
+            App::new()
+                .wrap(without_database())
+                .route("/health", web::get().to(HttpResponse::Ok))
+                .route("/api/v1/policies", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        let resp = test::call_service(&app, test::TestRequest::get().uri("/health").to_request()).await;
+        assert!(resp.status().is_success());
+    }
+
+    #[actix_web::test]
+    async fn test_requests_without_a_valid_token_are_rejected_before_any_lookup() {
+        let app = test::init_service(
+            App::new()
+                .wrap(without_database())
+                .route("/health", web::get().to(HttpResponse::Ok))
+                .route("/api/v1/policies", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        let missing = test::TestRequest::get().uri("/api/v1/policies").to_request();
+        let err = test::try_call_service(&app, missing).await.unwrap_err();
+        assert_eq!(err.error_response().status(), actix_web::http::StatusCode::UNAUTHORIZED);
+        assert_eq!(err.to_string(), "Missing or invalid authentication");
+
+        let forged = test::TestRequest::get()
+            .uri("/api/v1/policies")
+            .insert_header(("Authorization", "Bearer not-a-token"))
+            .to_request();
+        let err = test::try_call_service(&app, forged).await.unwrap_err();
+        assert_eq!(err.to_string(), "Invalid token");
+    }
+
+    #[actix_web::test]
+    async fn test_valid_token_is_rejected_when_revocation_cannot_be_checked() {
+        let app = test::init_service(
+            App::new()
+                .wrap(without_database())
+                .route("/health", web::get().to(HttpResponse::Ok))
+                .route("/api/v1/policies", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/api/v1/policies")
+            .insert_header(("Authorization", format!("Bearer {}", token(Uuid::new_v4(), 0))))
+            .to_request();
+        let err = test::try_call_service(&app, req).await.unwrap_err();
+        assert_eq!(err.to_string(), "Token has been revoked");
+    }
 }
@@ -0,0 +1,117 @@
+use actix_cors::Cors;
+
+use crate::config::Config;
+
+/// Build the CORS policy from configuration
+///
+/// With no `allowed_origins` configured every cross-origin request is
+/// rejected; the policy only widens to the origins listed explicitly.
+pub fn build_cors(config: &Config) -> Cors {
+    let mut cors = Cors::default()
+        .allowed_methods(config.allowed_methods.iter().map(String::as_str))
+        .allowed_headers(config.allowed_headers.iter().map(String::as_str))
+        .expose_headers(config.exposed_headers.iter().map(String::as_str))
+        .max_age(config.max_age_seconds)
+        .block_on_origin_mismatch(true);
+
+    for origin in &config.allowed_origins {
+        cors = cors.allowed_origin(origin);
+    }
+
+    if config.allow_credentials {
+        cors = cors.supports_credentials();
+    }
+
+    cors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::http::{header, header::HeaderMap, StatusCode};
+    use actix_web::{test, web, App, HttpResponse};
+
+    async fn send_with_origin(config: &Config, req: test::TestRequest, origin: &str) -> (StatusCode, HeaderMap) {
+        let app = test::init_service(
+            App::new()
+                .wrap(build_cors(config))
+                .route("/ping", web::get().to(|| async { HttpResponse::Ok().finish() })),
+        )
+        .await;
+
+        let req = req.uri("/ping").insert_header((header::ORIGIN, origin)).to_request();
+        let resp = test::call_service(&app, req).await;
+        (resp.status(), resp.headers().clone())
+    }
+
+    async fn get_with_origin(config: &Config, origin: &str) -> (StatusCode, HeaderMap) {
+        send_with_origin(config, test::TestRequest::get(), origin).await
+    }
+
+    fn allowing(origin: &str) -> Config {
+        Config {
+            allowed_origins: vec![origin.to_string()],
+            ..Config::default()
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_allowlisted_origin_is_permitted() {
+        let (status, headers) = get_with_origin(&allowing("https://app.example.com"), "https://app.example.com").await;
+        assert!(status.is_success());
+        assert_eq!(
+            headers.get(header::ACCESS_CONTROL_ALLOW_ORIGIN).unwrap(),
+            "https://app.example.com"
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_non_allowlisted_origin_is_rejected() {
+        let (status, headers) = get_with_origin(&allowing("https://app.example.com"), "https://evil.example.com").await;
+        assert!(!status.is_success());
+        assert!(headers.get(header::ACCESS_CONTROL_ALLOW_ORIGIN).is_none());
+    }
+
+    #[actix_web::test]
+    async fn test_default_policy_is_closed() {
+        let (status, _) = get_with_origin(&Config::default(), "http://localhost:3000").await;
+        assert!(!status.is_success());
+    }
+
+    #[actix_web::test]
+    async fn test_preflight_allows_the_headers_services_read() {
+        let preflight = test::TestRequest::default()
+            .method(actix_web::http::Method::OPTIONS)
+            .insert_header((header::ACCESS_CONTROL_REQUEST_METHOD, "POST"))
+            .insert_header((
+                header::ACCESS_CONTROL_REQUEST_HEADERS,
+                "idempotency-key, x-request-id, x-team-id, x-organization-id",
+            ));
+
+        let (status, headers) =
+            send_with_origin(&allowing("https://app.example.com"), preflight, "https://app.example.com").await;
+        assert!(status.is_success());
+        let allowed = headers
+            .get(header::ACCESS_CONTROL_ALLOW_HEADERS)
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_ascii_lowercase();
+        assert!(allowed.contains("idempotency-key"));
+        assert!(allowed.contains("x-organization-id"));
+    }
+
+    #[actix_web::test]
+    async fn test_deprecation_headers_are_exposed() {
+        let (_, headers) = get_with_origin(&allowing("https://app.example.com"), "https://app.example.com").await;
+        let exposed = headers
+            .get(header::ACCESS_CONTROL_EXPOSE_HEADERS)
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_ascii_lowercase();
+        for name in ["deprecation", "sunset", "link"] {
+            assert!(exposed.contains(name), "{} is not exposed: {}", name, exposed);
+        }
+    }
+}
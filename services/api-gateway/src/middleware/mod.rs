@@ -1,3 +1,7 @@
+pub mod auth_middleware;
+pub mod cors;
 pub mod csrf;
 
+pub use auth_middleware::AuthMiddleware;
+pub use cors::build_cors;
 pub use csrf::CsrfProtection;
@@ -0,0 +1,62 @@
+use actix_web::{test, web, App, HttpResponse};
+use jsonwebtoken::{encode, EncodingKey, Header};
+use uuid::Uuid;
+
+use api_gateway::middleware::AuthMiddleware;
+use llm_governance_common::auth::{AccessClaims, TokenVerifier, DEFAULT_TOKEN_AUDIENCE, DEFAULT_TOKEN_ISSUER};
+use llm_governance_common::testing::{database, seed_user};
+
+const SECRET: &str = "test-secret";
+
+fn token(user_id: Uuid, token_version: i32) -> String {
+    let now = chrono::Utc::now().timestamp() as usize;
+    let claims = AccessClaims {
+        sub: user_id.to_string(),
+        exp: now + 3600,
+        iat: now,
+        iss: DEFAULT_TOKEN_ISSUER.to_string(),
+        aud: DEFAULT_TOKEN_AUDIENCE.to_string(),
+        user_id,
+        email: "gateway@example.com".to_string(),
+        token_version,
+    };
+    encode(&Header::default(), &claims, &EncodingKey::from_secret(SECRET.as_bytes())).unwrap()
+}
+
+#[actix_web::test]
+#[ignore] // Requires Postgres at DATABASE_URL
+async fn test_token_issued_before_version_bump_is_rejected() {
+    let pool = database().await;
+
+    // Deactivating and reactivating a user leaves token_version bumped
+    let user_id = seed_user(&pool, "gateway").await;
+    sqlx::query("UPDATE users SET token_version = 1 WHERE id = $1")
+        .bind(user_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let app = test::init_service(
+        App::new()
+            .wrap(AuthMiddleware::new(
+                TokenVerifier::new(SECRET, DEFAULT_TOKEN_ISSUER, DEFAULT_TOKEN_AUDIENCE),
+                pool,
+            ))
+            .route("/api/v1/policies", web::get().to(HttpResponse::Ok)),
+    )
+    .await;
+
+    let stale = test::TestRequest::get()
+        .uri("/api/v1/policies")
+        .insert_header(("Authorization", format!("Bearer {}", token(user_id, 0))))
+        .to_request();
+    let err = test::try_call_service(&app, stale).await.unwrap_err();
+    assert_eq!(err.error_response().status(), actix_web::http::StatusCode::UNAUTHORIZED);
+
+    let current = test::TestRequest::get()
+        .uri("/api/v1/policies")
+        .insert_header(("Authorization", format!("Bearer {}", token(user_id, 1))))
+        .to_request();
+    let resp = test::call_service(&app, current).await;
+    assert!(resp.status().is_success());
+}
@@ -0,0 +1,5 @@
+//! API gateway tests against the migrated database at `DATABASE_URL`
+//!
+//! Run with `cargo test -p api-gateway --test integration -- --ignored`.
+
+mod auth_middleware;
@@ -1,17 +1,149 @@
+use llm_governance_common::adapters::change_impact::{CategoryWeights, SeverityPolicy};
+use llm_governance_common::utils::{DEFAULT_MAX_QUERY_DAYS, DEFAULT_QUERY_TIMEOUT_SECS, DEFAULT_WINDOW_DAYS};
 use serde::Deserialize;
+use std::collections::HashMap;
+use std::time::Duration;
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct Config {
     pub host: String,
     pub port: u16,
     pub database_url: String,
+    /// Read replica for aggregation queries; the primary serves them when unset
+    #[serde(default)]
+    pub database_read_url: Option<String>,
     pub redis_url: String,
+    /// Base URL of ruvector-service, where DecisionEvents are persisted to and
+    /// read from unless `RUVECTOR_MODE=memory`
+    #[serde(default)]
+    pub ruvector_url: String,
+    /// Base URL of LLM-Observatory, which change-impact assessments emit
+    /// telemetry and trace spans to; nothing is emitted when unset
+    #[serde(default)]
+    pub observatory_url: Option<String>,
+    /// Span of the analysis window when a request omits its bounds
+    #[serde(default = "default_window_days")]
+    pub default_window_days: i64,
+    /// Longest analysis window a request may ask for
+    #[serde(default = "default_max_query_days")]
+    pub max_query_days: i64,
+    /// Seconds an aggregation query may run before the request fails with `503`
+    #[serde(default = "default_query_timeout_secs")]
+    pub query_timeout_secs: u64,
+    /// Change-impact risk score multipliers per risk category, e.g.
+    /// `security_risk=2.0,financial_risk=0.5`; unlisted categories weigh 1.0
+    #[serde(default)]
+    pub risk_category_weights: String,
+    /// Minimum change-impact risk severities as `subject[:change_type]=severity`
+    /// rules, e.g. `llm_model=critical,policy:delete=high`
+    #[serde(default)]
+    pub severity_escalations: String,
+    /// Most findings an audit returns and records unless the request asks
+    /// otherwise; the most severe are kept
+    #[serde(default = "default_max_findings")]
+    pub max_findings: usize,
+    /// Most recommendations an audit returns and records unless the request
+    /// asks otherwise
+    #[serde(default = "default_max_recommendations")]
+    pub max_recommendations: usize,
+    /// Credentials of the services allowed to forward audit events, as
+    /// `source=token` pairs, e.g. `integration-service=s3cret`; ingestion is
+    /// refused for every source when empty
+    #[serde(default)]
+    pub ingest_tokens: String,
+    /// How far in the future a forwarded event's `occurred_at` may lie
+    #[serde(default = "default_ingest_max_clock_skew_secs")]
+    pub ingest_max_clock_skew_secs: i64,
+}
+
+fn default_ingest_max_clock_skew_secs() -> i64 {
+    300
+}
+
+fn default_max_findings() -> usize {
+    100
+}
+
+fn default_max_recommendations() -> usize {
+    20
+}
+
+fn default_window_days() -> i64 {
+    DEFAULT_WINDOW_DAYS
+}
+
+fn default_max_query_days() -> i64 {
+    DEFAULT_MAX_QUERY_DAYS
+}
+
+fn default_query_timeout_secs() -> u64 {
+    DEFAULT_QUERY_TIMEOUT_SECS
 }
 
 impl Config {
+    pub fn query_timeout(&self) -> Duration {
+        Duration::from_secs(self.query_timeout_secs)
+    }
+
+    pub fn category_weights(&self) -> Result<CategoryWeights, String> {
+        CategoryWeights::parse(&self.risk_category_weights)
+    }
+
+    /// Ingestion token of each forwarding service
+    pub fn ingest_tokens(&self) -> Result<HashMap<String, String>, String> {
+        let mut tokens = HashMap::new();
+        for pair in self.ingest_tokens.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            match pair.split_once('=') {
+                Some((source, token)) if !source.trim().is_empty() && !token.trim().is_empty() => {
+                    tokens.insert(source.trim().to_string(), token.trim().to_string());
+                }
+                _ => return Err(format!("expected source=token, got '{}'", pair)),
+            }
+        }
+        Ok(tokens)
+    }
+
+    pub fn severity_policy(&self) -> Result<SeverityPolicy, String> {
+        SeverityPolicy::parse(&self.severity_escalations)
+    }
+
     pub fn from_env() -> Result<Self, envy::Error> {
         envy::prefixed("AUDIT-SERVICE_").from_env::<Self>()
     }
+
+    /// Check the settings the service cannot run with, reporting every problem
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut problems = Vec::new();
+
+        // Cost projections scale spend per day of the window
+        if self.default_window_days <= 0 {
+            problems.push(format!(
+                "AUDIT-SERVICE_DEFAULT_WINDOW_DAYS must be positive (got {})",
+                self.default_window_days
+            ));
+        }
+        if let Err(e) = self.category_weights() {
+            problems.push(format!("AUDIT-SERVICE_RISK_CATEGORY_WEIGHTS is invalid: {}", e));
+        }
+        if let Err(e) = self.severity_policy() {
+            problems.push(format!("AUDIT-SERVICE_SEVERITY_ESCALATIONS is invalid: {}", e));
+        }
+        if let Err(e) = self.ingest_tokens() {
+            problems.push(format!("AUDIT-SERVICE_INGEST_TOKENS is invalid: {}", e));
+        }
+        if self.ingest_max_clock_skew_secs < 0 {
+            problems.push(format!(
+                "AUDIT-SERVICE_INGEST_MAX_CLOCK_SKEW_SECS must not be negative (got {})",
+                self.ingest_max_clock_skew_secs
+            ));
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
 }
 
 impl Default for Config {
@@ -20,7 +152,55 @@ impl Default for Config {
             host: "0.0.0.0".to_string(),
             port: 8084,
             database_url: String::new(),
+            database_read_url: None,
             redis_url: "redis://127.0.0.1:6379".to_string(),
+            ruvector_url: String::new(),
+            observatory_url: None,
+            default_window_days: default_window_days(),
+            max_query_days: default_max_query_days(),
+            query_timeout_secs: default_query_timeout_secs(),
+            risk_category_weights: String::new(),
+            severity_escalations: String::new(),
+            max_findings: default_max_findings(),
+            max_recommendations: default_max_recommendations(),
+            ingest_tokens: String::new(),
+            ingest_max_clock_skew_secs: default_ingest_max_clock_skew_secs(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zero_window_is_rejected() {
+        assert!(Config::default().validate().is_ok());
+
+        let config = Config {
+            default_window_days: 0,
+            ..Config::default()
+        };
+        let problems = config.validate().unwrap_err();
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("AUDIT-SERVICE_DEFAULT_WINDOW_DAYS"));
+    }
+
+    #[test]
+    fn test_ingest_tokens_are_parsed_per_source() {
+        let config = Config {
+            ingest_tokens: "integration-service=abc, user-service = def".to_string(),
+            ..Config::default()
+        };
+        let tokens = config.ingest_tokens().unwrap();
+        assert_eq!(tokens["integration-service"], "abc");
+        assert_eq!(tokens["user-service"], "def");
+
+        let config = Config {
+            ingest_tokens: "integration-service".to_string(),
+            ..Config::default()
+        };
+        let problems = config.validate().unwrap_err();
+        assert!(problems[0].contains("AUDIT-SERVICE_INGEST_TOKENS"));
+    }
+}
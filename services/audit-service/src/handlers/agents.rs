@@ -0,0 +1,160 @@
+//! Governance agent self-description
+//!
+//! Each agent module declares an [`AgentDescriptor`] whose endpoint table
+//! lists the routes it mounts. Registration responses are rendered from that
+//! table, and the tests below check every advertised route is actually served.
+
+use actix_web::{get, HttpResponse, Responder};
+use serde_json::{Map, Value};
+
+use llm_governance_common::adapters::ruvector::GovernanceDecisionType;
+use llm_governance_common::{ApiResponse, Result};
+
+use super::{change_impact, governance};
+
+/// Scope every agent route is mounted under
+pub const API_PREFIX: &str = "/api/v1";
+
+/// One route an agent serves, relative to [`API_PREFIX`]
+#[derive(Debug, Clone, Copy)]
+pub struct AgentEndpoint {
+    pub name: &'static str,
+    pub method: &'static str,
+    pub path: &'static str,
+}
+
+impl AgentEndpoint {
+    /// Full route, e.g. `GET /api/v1/governance/summary`
+    pub fn route(&self) -> String {
+        format!("{} {}{}", self.method, API_PREFIX, self.path)
+    }
+}
+
+/// Static metadata describing a governance agent
+#[derive(Debug, Clone, Copy)]
+pub struct AgentDescriptor {
+    pub agent_id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub version: &'static str,
+    pub classification: &'static str,
+    pub decision_types: &'static [GovernanceDecisionType],
+    pub capabilities: &'static [&'static str],
+    pub non_responsibilities: &'static [&'static str],
+    pub endpoints: &'static [AgentEndpoint],
+}
+
+impl AgentDescriptor {
+    pub fn to_json(&self) -> Value {
+        let endpoints: Map<String, Value> = self
+            .endpoints
+            .iter()
+            .map(|e| (e.name.to_string(), Value::String(e.route())))
+            .collect();
+
+        serde_json::json!({
+            "agent_id": self.agent_id,
+            "name": self.name,
+            "description": self.description,
+            "version": self.version,
+            "classification": self.classification,
+            "decision_types": self.decision_types,
+            "capabilities": self.capabilities,
+            "non_responsibilities": self.non_responsibilities,
+            "endpoints": endpoints,
+        })
+    }
+}
+
+/// Every governance agent served by this service
+pub const GOVERNANCE_AGENTS: &[AgentDescriptor] = &[
+    governance::AGENT_DESCRIPTOR,
+    change_impact::AGENT_DESCRIPTOR,
+];
+
+/// List all governance agents
+///
+/// GET /api/v1/governance/agents
+#[get("/governance/agents")]
+pub async fn list_governance_agents() -> Result<impl Responder> {
+    let agents: Vec<Value> = GOVERNANCE_AGENTS.iter().map(AgentDescriptor::to_json).collect();
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
+        "agents": agents,
+        "total": agents.len()
+    }))))
+}
+
+pub fn configure(cfg: &mut actix_web::web::ServiceConfig) {
+    cfg.service(list_governance_agents);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::http::{Method, StatusCode};
+    use actix_web::{test, App};
+
+    /// Fill path parameters with a value every handler's extractor accepts
+    fn concrete_path(path: &str) -> String {
+        path.split('/')
+            .map(|segment| {
+                if segment.starts_with('{') {
+                    "00000000-0000-0000-0000-000000000000"
+                } else {
+                    segment
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    #[actix_web::test]
+    async fn test_every_advertised_endpoint_is_mounted() {
+        let app = test::init_service(App::new().configure(crate::handlers::configure)).await;
+
+        for agent in GOVERNANCE_AGENTS {
+            for endpoint in agent.endpoints {
+                let req = test::TestRequest::default()
+                    .method(Method::from_bytes(endpoint.method.as_bytes()).unwrap())
+                    .uri(&format!("{}{}", API_PREFIX, concrete_path(endpoint.path)))
+                    .to_request();
+                let resp = test::call_service(&app, req).await;
+
+                // Handlers without their app data fail with 4xx/5xx, never 404
+                assert_ne!(
+                    resp.status(),
+                    StatusCode::NOT_FOUND,
+                    "{} advertises {} but it is not mounted",
+                    agent.agent_id,
+                    endpoint.route()
+                );
+            }
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_registration_endpoints_describe_their_own_agent() {
+        let app = test::init_service(App::new().configure(crate::handlers::configure)).await;
+
+        for agent in GOVERNANCE_AGENTS {
+            let endpoint = agent
+                .endpoints
+                .iter()
+                .find(|e| e.name == "agent")
+                .expect("every agent advertises its registration endpoint");
+            let req = test::TestRequest::get()
+                .uri(&format!("{}{}", API_PREFIX, endpoint.path))
+                .to_request();
+            let body: Value = test::call_and_read_body_json(&app, req).await;
+
+            assert_eq!(body["data"], agent.to_json());
+        }
+
+        let req = test::TestRequest::get()
+            .uri(&format!("{}/governance/agents", API_PREFIX))
+            .to_request();
+        let body: Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body["data"]["total"], GOVERNANCE_AGENTS.len());
+    }
+}
@@ -1,10 +1,18 @@
 use actix_web::{get, post, web, HttpResponse, Responder};
+use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
+use std::collections::HashMap;
 use uuid::Uuid;
 use llm_governance_common::{AppError, Result, ApiResponse};
 use chrono::{DateTime, Utc, NaiveDateTime};
 use sha2::{Sha256, Digest};
+use llm_governance_common::db::{paginate, Page};
+use llm_governance_common::query::DynamicQuery;
+use llm_governance_common::utils::resolve_window;
+
+use crate::config::Config;
+use super::validation::parse_timestamp;
 
 #[derive(Debug, Deserialize)]
 pub struct CreateAuditLogRequest {
@@ -39,6 +47,54 @@ pub struct AuditQuery {
     pub offset: Option<u32>,
 }
 
+/// Largest batch accepted by `POST /audit/events`
+const MAX_INGEST_BATCH: usize = 500;
+const MAX_INGEST_KEY_LENGTH: usize = 255;
+const MAX_INGEST_SOURCE_LENGTH: usize = 100;
+
+#[derive(Debug, Deserialize)]
+pub struct IngestAuditEventsRequest {
+    /// Service forwarding the events; idempotency keys are scoped to it
+    pub source: String,
+    pub events: Vec<IngestAuditEvent>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct IngestAuditEvent {
+    pub idempotency_key: String,
+    pub user_id: Option<Uuid>,
+    pub action: String,
+    pub resource_type: String,
+    pub resource_id: String,
+    pub ip_address: Option<String>,
+    #[serde(default)]
+    pub details: serde_json::Value,
+    /// When the action happened at the source; receipt time when omitted
+    pub occurred_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IngestStatus {
+    Accepted,
+    /// The key was ingested before; `audit_log_id` is the original entry
+    Duplicate,
+    /// The event itself is invalid and was not written; the per-event
+    /// equivalent of a `400`
+    Rejected,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct IngestResult {
+    pub idempotency_key: String,
+    pub status: IngestStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audit_log_id: Option<Uuid>,
+    /// Why a rejected event was not written
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ExportRequest {
     pub format: String, // "csv" or "json"
@@ -85,87 +141,85 @@ pub async fn create_audit_log(
     Ok(HttpResponse::Created().json(ApiResponse::success(log)))
 }
 
-#[get("/audit/logs")]
-pub async fn query_audit_logs(
+/// Append audit events forwarded by other services
+///
+/// Only services holding an ingestion token may call this, each under its own
+/// `source`. Each event carries an idempotency key; resending a key (within
+/// the batch or in a later one) reports it as a duplicate of the original
+/// entry instead of writing it again.
+#[post("/audit/events")]
+pub async fn ingest_audit_events(
     pool: web::Data<PgPool>,
-    query: web::Query<AuditQuery>,
+    config: web::Data<Config>,
+    req: web::Json<IngestAuditEventsRequest>,
+    http_req: actix_web::HttpRequest,
 ) -> Result<impl Responder> {
-    let limit = query.limit.unwrap_or(50).min(1000);
-    let offset = query.offset.unwrap_or(0);
-
-    // Build dynamic query
-    let mut sql = String::from(
-        "SELECT id, timestamp, user_id, action, resource_type, resource_id, ip_address, details, checksum FROM audit_logs WHERE 1=1"
-    );
+    let tokens = config.ingest_tokens().map_err(AppError::Internal)?;
+    authorize_source(&tokens, &req.source, bearer_token(&http_req))?;
 
-    let mut bindings = Vec::new();
-    let mut bind_index = 1;
-
-    if let Some(user_id) = query.user_id {
-        sql.push_str(&format!(" AND user_id = ${}", bind_index));
-        bind_index += 1;
-    }
-
-    if let Some(ref action) = query.action {
-        sql.push_str(&format!(" AND action = ${}", bind_index));
-        bind_index += 1;
-    }
-
-    if let Some(ref resource_type) = query.resource_type {
-        sql.push_str(&format!(" AND resource_type = ${}", bind_index));
-        bind_index += 1;
-    }
-
-    if let Some(ref resource_id) = query.resource_id {
-        sql.push_str(&format!(" AND resource_id = ${}", bind_index));
-        bind_index += 1;
-    }
+    let max_skew = chrono::Duration::seconds(config.ingest_max_clock_skew_secs);
+    let results = ingest_batch(pool.get_ref(), &req.source, &req.events, Utc::now(), max_skew).await?;
+    let count = |status| results.iter().filter(|r| r.status == status).count();
 
-    if let Some(ref start_date) = query.start_date {
-        sql.push_str(&format!(" AND timestamp >= ${}", bind_index));
-        bind_index += 1;
-    }
-
-    if let Some(ref end_date) = query.end_date {
-        sql.push_str(&format!(" AND timestamp <= ${}", bind_index));
-        bind_index += 1;
-    }
-
-    sql.push_str(&format!(" ORDER BY timestamp DESC LIMIT ${} OFFSET ${}", bind_index, bind_index + 1));
-
-    // Execute query with dynamic bindings
-    let mut query_builder = sqlx::query_as::<_, AuditLogResponse>(&sql);
+    Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
+        "accepted": count(IngestStatus::Accepted),
+        "duplicates": count(IngestStatus::Duplicate),
+        "rejected": count(IngestStatus::Rejected),
+        "results": results,
+    }))))
+}
 
-    if let Some(user_id) = query.user_id {
-        query_builder = query_builder.bind(user_id);
-    }
-    if let Some(ref action) = query.action {
-        query_builder = query_builder.bind(action);
-    }
-    if let Some(ref resource_type) = query.resource_type {
-        query_builder = query_builder.bind(resource_type);
-    }
-    if let Some(ref resource_id) = query.resource_id {
-        query_builder = query_builder.bind(resource_id);
-    }
-    if let Some(ref start_date) = query.start_date {
-        query_builder = query_builder.bind(start_date);
-    }
-    if let Some(ref end_date) = query.end_date {
-        query_builder = query_builder.bind(end_date);
-    }
+#[get("/audit/logs")]
+pub async fn query_audit_logs(
+    pool: web::Data<PgPool>,
+    query: web::Query<AuditQuery>,
+) -> Result<impl Responder> {
+    let page = Page::new(query.limit, query.offset);
 
-    query_builder = query_builder.bind(limit as i64).bind(offset as i64);
+    let mut logs = filtered_logs(
+        "SELECT id, timestamp, user_id, action, resource_type, resource_id, ip_address, details, checksum FROM audit_logs",
+        &query,
+    )?;
+    logs.push(" ORDER BY timestamp DESC, id DESC");
+    let total = filtered_logs("SELECT COUNT(*) FROM audit_logs", &query)?;
 
-    let logs = query_builder.fetch_all(pool.get_ref()).await?;
+    let (logs, total): (Vec<AuditLogResponse>, i64) = paginate(pool.get_ref(), logs, total, page).await?;
 
     Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
         "logs": logs,
-        "limit": limit,
-        "offset": offset
+        "total": total,
+        "limit": page.limit,
+        "offset": page.offset
     }))))
 }
 
+/// `head` restricted by the log filters, shared by the page and its total
+fn filtered_logs(head: &str, query: &AuditQuery) -> Result<DynamicQuery<'static>> {
+    let start = query
+        .start_date
+        .as_deref()
+        .map(|value| parse_timestamp("start_date", value))
+        .transpose()?;
+    let end = query
+        .end_date
+        .as_deref()
+        .map(|value| parse_timestamp("end_date", value))
+        .transpose()?;
+
+    let mut logs = DynamicQuery::new(head);
+    logs.filter_opt("user_id", query.user_id)
+        .filter_opt("action", query.action.clone())
+        .filter_opt("resource_type", query.resource_type.clone())
+        .filter_opt("resource_id", query.resource_id.clone());
+    if let Some(start) = start {
+        logs.filter_op("timestamp", ">=", start);
+    }
+    if let Some(end) = end {
+        logs.filter_op("timestamp", "<=", end);
+    }
+    Ok(logs)
+}
+
 #[get("/audit/logs/{id}")]
 pub async fn get_audit_log(
     pool: web::Data<PgPool>,
@@ -279,6 +333,7 @@ pub async fn export_audit_logs(
 #[get("/audit/reports/compliance")]
 pub async fn generate_compliance_report(
     pool: web::Data<PgPool>,
+    config: web::Data<Config>,
     query: web::Query<ComplianceQuery>,
 ) -> Result<impl Responder> {
     #[derive(Debug, Serialize, sqlx::FromRow)]
@@ -288,13 +343,12 @@ pub async fn generate_compliance_report(
         actions_by_type: serde_json::Value,
     }
 
-    let start_date = query.start_date.clone().unwrap_or_else(|| {
-        chrono::Utc::now().checked_sub_signed(chrono::Duration::days(30)).unwrap().to_rfc3339()
-    });
-
-    let end_date = query.end_date.clone().unwrap_or_else(|| {
-        chrono::Utc::now().to_rfc3339()
-    });
+    let window = resolve_window(
+        query.start_date.as_deref(),
+        query.end_date.as_deref(),
+        config.default_window_days,
+    );
+    let (start_date, end_date) = (window.start, window.end);
 
     // Get total actions
     let total_actions: (i64,) = sqlx::query_as(
@@ -367,6 +421,205 @@ fn calculate_checksum(
     format!("{:x}", hasher.finalize())
 }
 
+/// Outcome of appending one forwarded event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AppendOutcome {
+    Accepted(Uuid),
+    /// The key was already ingested; holds the original entry
+    Duplicate(Uuid),
+    /// The event names a user that does not exist
+    UnknownUser,
+}
+
+/// Storage for forwarded audit events
+#[async_trait]
+pub trait AuditEventStore: Send + Sync {
+    /// Append `event` unless its key was already ingested from `source`
+    async fn append(&self, source: &str, event: &IngestAuditEvent) -> Result<AppendOutcome>;
+}
+
+#[async_trait]
+impl AuditEventStore for PgPool {
+    async fn append(&self, source: &str, event: &IngestAuditEvent) -> Result<AppendOutcome> {
+        let mut tx = self.begin().await?;
+
+        // Concurrent deliveries of one key wait here, so the check below sees
+        // the first one's receipt and a duplicate never reaches the log
+        sqlx::query("SELECT pg_advisory_xact_lock(hashtextextended($1 || ':' || $2, 0))")
+            .bind(source)
+            .bind(&event.idempotency_key)
+            .execute(&mut *tx)
+            .await?;
+
+        let original: Option<(Uuid,)> = sqlx::query_as(
+            "SELECT audit_log_id FROM audit_event_receipts WHERE source = $1 AND idempotency_key = $2",
+        )
+        .bind(source)
+        .bind(&event.idempotency_key)
+        .fetch_optional(&mut *tx)
+        .await?;
+        if let Some((audit_log_id,)) = original {
+            return Ok(AppendOutcome::Duplicate(audit_log_id));
+        }
+
+        if let Some(user_id) = event.user_id {
+            let (known,): (bool,) = sqlx::query_as("SELECT EXISTS(SELECT 1 FROM users WHERE id = $1)")
+                .bind(user_id)
+                .fetch_one(&mut *tx)
+                .await?;
+            if !known {
+                return Ok(AppendOutcome::UnknownUser);
+            }
+        }
+
+        let checksum = calculate_checksum(
+            event.user_id,
+            &event.action,
+            &event.resource_type,
+            &event.resource_id,
+            &event.details,
+        );
+        let (audit_log_id,): (Uuid,) = sqlx::query_as(
+            r#"
+            INSERT INTO audit_logs (timestamp, user_id, action, resource_type, resource_id, ip_address, details, checksum)
+            VALUES (COALESCE($1, CURRENT_TIMESTAMP), $2, $3, $4, $5, $6::inet, $7, $8)
+            RETURNING id
+            "#,
+        )
+        .bind(event.occurred_at.map(|t| t.naive_utc()))
+        .bind(event.user_id)
+        .bind(&event.action)
+        .bind(&event.resource_type)
+        .bind(&event.resource_id)
+        .bind(&event.ip_address)
+        .bind(&event.details)
+        .bind(&checksum)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query("INSERT INTO audit_event_receipts (source, idempotency_key, audit_log_id) VALUES ($1, $2, $3)")
+            .bind(source)
+            .bind(&event.idempotency_key)
+            .bind(audit_log_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(AppendOutcome::Accepted(audit_log_id))
+    }
+}
+
+/// Require the ingestion token configured for `source`
+///
+/// A missing token is `401`; a wrong one, or a source with no token, is `403`.
+fn authorize_source(tokens: &HashMap<String, String>, source: &str, presented: Option<&str>) -> Result<()> {
+    let presented = presented.ok_or(AppError::Unauthorized)?;
+    match tokens.get(source) {
+        Some(expected) if constant_time_eq(expected.as_bytes(), presented.as_bytes()) => Ok(()),
+        _ => Err(AppError::Forbidden),
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+fn bearer_token(req: &actix_web::HttpRequest) -> Option<&str> {
+    req.headers()
+        .get("Authorization")
+        .and_then(|h| h.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+}
+
+fn validate_ingest_batch(
+    source: &str,
+    events: &[IngestAuditEvent],
+    now: DateTime<Utc>,
+    max_skew: chrono::Duration,
+) -> Result<()> {
+    if source.trim().is_empty() || source.len() > MAX_INGEST_SOURCE_LENGTH {
+        return Err(AppError::Validation(format!(
+            "source must be 1-{} characters",
+            MAX_INGEST_SOURCE_LENGTH
+        )));
+    }
+    if events.is_empty() || events.len() > MAX_INGEST_BATCH {
+        return Err(AppError::Validation(format!(
+            "events must contain 1-{} entries",
+            MAX_INGEST_BATCH
+        )));
+    }
+
+    for (i, event) in events.iter().enumerate() {
+        if event.idempotency_key.trim().is_empty() || event.idempotency_key.len() > MAX_INGEST_KEY_LENGTH {
+            return Err(AppError::Validation(format!(
+                "events[{}].idempotency_key must be 1-{} characters",
+                i, MAX_INGEST_KEY_LENGTH
+            )));
+        }
+        if event.action.is_empty() || event.resource_type.is_empty() {
+            return Err(AppError::Validation(format!(
+                "events[{}] must have an action and resource_type",
+                i
+            )));
+        }
+        if event.occurred_at.is_some_and(|at| at > now + max_skew) {
+            return Err(AppError::Validation(format!(
+                "events[{}].occurred_at is more than {}s in the future",
+                i,
+                max_skew.num_seconds()
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate a batch, then append each event in order
+///
+/// A key repeated within the batch is a duplicate of its first occurrence.
+/// An event naming an unknown user is rejected on its own; the rest of the
+/// batch is still written.
+async fn ingest_batch<S: AuditEventStore + ?Sized>(
+    store: &S,
+    source: &str,
+    events: &[IngestAuditEvent],
+    now: DateTime<Utc>,
+    max_skew: chrono::Duration,
+) -> Result<Vec<IngestResult>> {
+    validate_ingest_batch(source, events, now, max_skew)?;
+
+    let mut seen: HashMap<&str, Uuid> = HashMap::new();
+    let mut results = Vec::with_capacity(events.len());
+
+    for event in events {
+        let outcome = match seen.get(event.idempotency_key.as_str()) {
+            Some(id) => AppendOutcome::Duplicate(*id),
+            None => store.append(source, event).await?,
+        };
+        let (status, audit_log_id, error) = match outcome {
+            AppendOutcome::Accepted(id) => (IngestStatus::Accepted, Some(id), None),
+            AppendOutcome::Duplicate(id) => (IngestStatus::Duplicate, Some(id), None),
+            AppendOutcome::UnknownUser => (
+                IngestStatus::Rejected,
+                None,
+                Some(format!("user_id {} does not exist", event.user_id.unwrap_or_default())),
+            ),
+        };
+        if let Some(id) = audit_log_id {
+            seen.insert(&event.idempotency_key, id);
+        }
+        results.push(IngestResult {
+            idempotency_key: event.idempotency_key.clone(),
+            status,
+            audit_log_id,
+            error,
+        });
+    }
+
+    Ok(results)
+}
+
 fn extract_user_id_optional(req: &actix_web::HttpRequest) -> Option<Uuid> {
     req.headers()
         .get("X-User-Id")
@@ -402,9 +655,189 @@ fn generate_csv(logs: &[AuditLogResponse]) -> Result<String> {
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.service(create_audit_log)
+        .service(ingest_audit_events)
         .service(query_audit_logs)
         .service(get_audit_log)
         .service(verify_audit_log)
         .service(export_audit_logs)
         .service(generate_compliance_report);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    fn audit_query() -> AuditQuery {
+        AuditQuery {
+            user_id: None,
+            action: None,
+            resource_type: None,
+            resource_id: None,
+            start_date: None,
+            end_date: None,
+            limit: None,
+            offset: None,
+        }
+    }
+
+    #[test]
+    fn test_log_filters_are_shared_by_page_and_total() {
+        let query = AuditQuery {
+            action: Some("login".to_string()),
+            start_date: Some("2025-01-01T00:00:00Z".to_string()),
+            ..audit_query()
+        };
+
+        let total = filtered_logs("SELECT COUNT(*) FROM audit_logs", &query).unwrap();
+        assert_eq!(
+            total.sql(),
+            "SELECT COUNT(*) FROM audit_logs WHERE action = $1 AND timestamp >= $2"
+        );
+
+        let bad = AuditQuery {
+            end_date: Some("yesterday".to_string()),
+            ..audit_query()
+        };
+        assert!(matches!(
+            filtered_logs("SELECT COUNT(*) FROM audit_logs", &bad),
+            Err(AppError::Validation(_))
+        ));
+    }
+
+    #[derive(Default)]
+    struct MemoryStore {
+        entries: Mutex<HashMap<(String, String), Uuid>>,
+        unknown_users: Vec<Uuid>,
+        appended: Mutex<usize>,
+    }
+
+    #[async_trait]
+    impl AuditEventStore for MemoryStore {
+        async fn append(&self, source: &str, event: &IngestAuditEvent) -> Result<AppendOutcome> {
+            let mut entries = self.entries.lock().unwrap();
+            let id = (source.to_string(), event.idempotency_key.clone());
+            if let Some(existing) = entries.get(&id) {
+                return Ok(AppendOutcome::Duplicate(*existing));
+            }
+            if event.user_id.is_some_and(|user| self.unknown_users.contains(&user)) {
+                return Ok(AppendOutcome::UnknownUser);
+            }
+            *self.appended.lock().unwrap() += 1;
+            let audit_log_id = Uuid::new_v4();
+            entries.insert(id, audit_log_id);
+            Ok(AppendOutcome::Accepted(audit_log_id))
+        }
+    }
+
+    fn event(key: &str) -> IngestAuditEvent {
+        IngestAuditEvent {
+            idempotency_key: key.to_string(),
+            user_id: None,
+            action: "LLM_REQUEST".to_string(),
+            resource_type: "openai:gpt-4".to_string(),
+            resource_id: "chatcmpl-1".to_string(),
+            ip_address: None,
+            details: serde_json::json!({}),
+            occurred_at: None,
+        }
+    }
+
+    async fn ingest(store: &MemoryStore, source: &str, events: &[IngestAuditEvent]) -> Result<Vec<IngestResult>> {
+        ingest_batch(store, source, events, Utc::now(), chrono::Duration::minutes(5)).await
+    }
+
+    #[tokio::test]
+    async fn test_batch_is_ingested() {
+        let store = MemoryStore::default();
+
+        let results = ingest(&store, "integration-service", &[event("a"), event("b")])
+            .await
+            .unwrap();
+
+        assert!(results.iter().all(|r| r.status == IngestStatus::Accepted));
+        assert_ne!(results[0].audit_log_id, results[1].audit_log_id);
+        assert_eq!(*store.appended.lock().unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_duplicates_are_suppressed() {
+        let store = MemoryStore::default();
+        let first = ingest(&store, "integration-service", &[event("a")]).await.unwrap();
+
+        // A retried key, and a key repeated within the batch
+        let retry = ingest(&store, "integration-service", &[event("a"), event("c"), event("c")])
+            .await
+            .unwrap();
+        let statuses: Vec<IngestStatus> = retry.iter().map(|r| r.status).collect();
+        assert_eq!(
+            statuses,
+            vec![IngestStatus::Duplicate, IngestStatus::Accepted, IngestStatus::Duplicate]
+        );
+        assert_eq!(retry[0].audit_log_id, first[0].audit_log_id);
+        assert_eq!(retry[1].audit_log_id, retry[2].audit_log_id);
+        assert_eq!(*store.appended.lock().unwrap(), 2);
+
+        // Keys are scoped to the forwarding service
+        let other = ingest(&store, "user-service", &[event("a")]).await.unwrap();
+        assert_eq!(other[0].status, IngestStatus::Accepted);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_batch_is_rejected() {
+        let store = MemoryStore::default();
+
+        assert!(ingest(&store, "integration-service", &[]).await.is_err());
+        assert!(ingest(&store, " ", &[event("a")]).await.is_err());
+        let err = ingest(&store, "integration-service", &[event("a"), event("")])
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::Validation(ref msg) if msg.contains("events[1]")));
+        assert_eq!(*store.appended.lock().unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_future_events_beyond_the_skew_are_rejected() {
+        let store = MemoryStore::default();
+        let mut late = event("late");
+        late.occurred_at = Some(Utc::now() + chrono::Duration::minutes(1));
+        let mut early = event("early");
+        early.occurred_at = Some(Utc::now() + chrono::Duration::hours(1));
+
+        assert!(ingest(&store, "integration-service", &[late.clone()]).await.is_ok());
+        let err = ingest(&store, "integration-service", &[early]).await.unwrap_err();
+        assert!(matches!(err, AppError::Validation(ref msg) if msg.contains("occurred_at")));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_user_is_rejected_per_event() {
+        let ghost = Uuid::new_v4();
+        let store = MemoryStore {
+            unknown_users: vec![ghost],
+            ..MemoryStore::default()
+        };
+        let mut orphan = event("orphan");
+        orphan.user_id = Some(ghost);
+
+        let results = ingest(&store, "integration-service", &[orphan, event("b")]).await.unwrap();
+        assert_eq!(results[0].status, IngestStatus::Rejected);
+        assert!(results[0].audit_log_id.is_none());
+        assert!(results[0].error.as_deref().unwrap().contains(&ghost.to_string()));
+        assert_eq!(results[1].status, IngestStatus::Accepted);
+        assert_eq!(*store.appended.lock().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_only_the_sources_own_token_is_accepted() {
+        let tokens = HashMap::from([
+            ("integration-service".to_string(), "abc".to_string()),
+            ("user-service".to_string(), "def".to_string()),
+        ]);
+
+        assert!(authorize_source(&tokens, "integration-service", Some("abc")).is_ok());
+        assert!(matches!(authorize_source(&tokens, "integration-service", None), Err(AppError::Unauthorized)));
+        assert!(matches!(authorize_source(&tokens, "integration-service", Some("def")), Err(AppError::Forbidden)));
+        assert!(matches!(authorize_source(&tokens, "metrics-service", Some("abc")), Err(AppError::Forbidden)));
+        assert!(matches!(authorize_source(&HashMap::new(), "integration-service", Some("abc")), Err(AppError::Forbidden)));
+    }
+}
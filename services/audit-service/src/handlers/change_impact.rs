@@ -40,7 +40,8 @@ use actix_web::{get, post, web, HttpResponse, Responder};
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use uuid::Uuid;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use futures::stream::{self, StreamExt, TryStreamExt};
 use sha2::{Sha256, Digest};
 use std::collections::HashMap;
 use tracing::{info, warn, instrument, span, Level};
@@ -53,6 +54,7 @@ use llm_governance_common::adapters::ruvector::{
     TrendDirection, InvocationSource, DataReferenceType, ConstraintType, ConstraintScope,
     create_decision_event, default_confidence, execution_ref_from_request,
 };
+use llm_governance_common::utils::hash_inputs;
 use llm_governance_common::adapters::change_impact::{
     ChangeImpactAgent, ChangeImpactInput, ChangeImpactOutput, ChangeRequest,
     ChangeType, ChangeSubjectType, ChangeImpactScope, ChangeImpactAssessment,
@@ -60,16 +62,40 @@ use llm_governance_common::adapters::change_impact::{
     PolicyImplication, PolicyImplicationType, ComplianceImplication, ComplianceImpactStatus,
     CostImplication, RiskIndicator, RiskIndicatorCategory, ImpactRecommendation,
     RecommendationPriority, RecommendationType, HistoricalContext, HistoricalOutcome,
-    ExecutionContext, AGENT_ID, AGENT_VERSION,
+    ExecutionContext, AGENT_ID, AGENT_VERSION, effective_analysis_depth, expand_downstream,
+    downstream_coverage,
+    BaselineComparison, BaselineSnapshot, BaselineSource, baseline_confidence_factor,
+    compare_to_baseline, BudgetThreshold, CategorySpend, build_cost_implication,
+    CategoryWeights, SeverityPolicy, AssessmentTelemetry, TelemetryStatus,
 };
 use llm_governance_common::adapters::UpstreamConfig;
+use llm_governance_common::db::{paginate, Page};
+use llm_governance_common::negotiate::{preferred_format, Format};
+use llm_governance_common::query::DynamicQuery;
+use llm_governance_common::trace_context::extract_trace_id;
+use llm_governance_common::timestamp::Timestamp;
+use llm_governance_common::utils::check_range_span;
+
+use crate::config::Config;
+use crate::services::authorization::{authorize_org_audit, authorize_record_audit};
+use crate::services::decision_log::{record_decision_event, store_decision_event, CHANGE_IMPACT_RESOURCE};
+use super::agents::{AgentDescriptor, AgentEndpoint};
+use super::validation::{parse_date_range, parse_timestamp, DateRangeInput};
+
+/// Most changes one batch request may hold
+const MAX_BATCH_CHANGES: usize = 100;
+
+/// Assessments of a batch that run at the same time
+const BATCH_CONCURRENCY: usize = 4;
 
 // ============================================================================
 // Request/Response Types
 // ============================================================================
 
 /// Request to assess change impact
-#[derive(Debug, Deserialize)]
+///
+/// Recorded with the assessment as `inputs`, which `inputs_hash` is taken over.
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ChangeImpactRequest {
     /// Organization ID
     pub organization_id: String,
@@ -85,12 +111,14 @@ pub struct ChangeImpactRequest {
     pub include_risk_projection: bool,
     /// Historical time range for context
     pub historical_range: Option<DateRangeInput>,
+    /// Prior assessment to compare against
+    pub baseline_ref: Option<String>,
 }
 
 fn default_true() -> bool { true }
 
 /// Change request input from API
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ChangeRequestInput {
     pub change_id: String,
     pub change_type: String,
@@ -105,7 +133,7 @@ pub struct ChangeRequestInput {
 }
 
 /// Scope input from API
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChangeImpactScopeInput {
     pub teams: Option<Vec<String>>,
     pub users: Option<Vec<String>>,
@@ -116,13 +144,6 @@ pub struct ChangeImpactScopeInput {
     pub include_compliance_impact: Option<bool>,
 }
 
-/// Date range input
-#[derive(Debug, Deserialize)]
-pub struct DateRangeInput {
-    pub start: String,
-    pub end: String,
-}
-
 /// Change impact assessment response
 #[derive(Debug, Serialize)]
 pub struct ChangeImpactResponse {
@@ -134,12 +155,57 @@ pub struct ChangeImpactResponse {
     pub assessment: ChangeImpactAssessmentResponse,
     pub confidence: ConfidenceResponse,
     pub telemetry_ref: String,
+    /// Whether LLM-Observatory accepted the telemetry; absent when it is not configured
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub telemetry_status: Option<TelemetryStatus>,
+}
+
+/// Request to assess several changes with the same scope and options
+#[derive(Debug, Deserialize)]
+pub struct ChangeImpactBatchRequest {
+    /// Organization ID
+    pub organization_id: String,
+    /// Changes to assess, at most `MAX_BATCH_CHANGES`
+    pub changes: Vec<ChangeRequestInput>,
+    /// Analysis scope applied to every change
+    pub scope: Option<ChangeImpactScopeInput>,
+    #[serde(default = "default_true")]
+    pub include_downstream: bool,
+    #[serde(default)]
+    pub include_risk_projection: bool,
+    pub historical_range: Option<DateRangeInput>,
+}
+
+/// Batch assessment response
+#[derive(Debug, Serialize)]
+pub struct ChangeImpactBatchResponse {
+    pub organization_id: String,
+    /// One assessment per change, in request order
+    pub results: Vec<ChangeImpactResponse>,
+    pub aggregate: ChangeImpactBatchAggregate,
+}
+
+/// Combined impact of every change in a batch
+#[derive(Debug, Serialize)]
+pub struct ChangeImpactBatchAggregate {
+    pub change_count: usize,
+    /// Risk of the riskiest change
+    pub max_risk_score: f64,
+    pub max_risk_classification: Option<String>,
+    pub max_impact_level: Option<String>,
+    pub riskiest_change_id: Option<String>,
+    /// Every system affected by any change, once each
+    pub affected_systems: Vec<String>,
+    /// Critical risk indicators across all assessments
+    pub critical_findings: u32,
 }
 
 /// Assessment in response format
 #[derive(Debug, Serialize)]
 pub struct ChangeImpactAssessmentResponse {
     pub id: String,
+    /// Change this assessment was made for; its assessments are listed by
+    /// `GET /governance/change-impact/by-change/{change_id}`
     pub change_request_id: String,
     pub impact_level: String,
     pub risk_score: f64,
@@ -153,6 +219,11 @@ pub struct ChangeImpactAssessmentResponse {
     pub risk_indicators: Vec<RiskIndicatorResponse>,
     pub recommendations: Vec<RecommendationResponse>,
     pub historical_context: Option<HistoricalContextResponse>,
+    /// Downstream traversal depth actually used (after defaulting and clamping)
+    pub analysis_depth: u8,
+    /// Share of the systems reachable from the change that were analyzed
+    pub coverage_percentage: f64,
+    pub baseline_comparison: Option<BaselineComparison>,
     pub assessed_at: String,
 }
 
@@ -213,7 +284,7 @@ pub struct CostBreakdownResponse {
     pub delta: f64,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct RiskIndicatorResponse {
     pub id: String,
     pub category: String,
@@ -248,7 +319,31 @@ pub struct ConfidenceResponse {
     pub certainty: f64,
 }
 
-/// Query parameters for listing assessments
+/// Outcome of re-checking a recorded assessment
+#[derive(Debug, Serialize)]
+pub struct AssessmentVerification {
+    pub event_id: String,
+    /// Inputs re-hash to `inputs_hash` and reproduce the recorded risk
+    pub verified: bool,
+    /// Whether the inputs were recorded with the assessment
+    pub inputs_available: bool,
+    pub divergences: Vec<Divergence>,
+}
+
+/// A recorded value that the inputs do not reproduce
+#[derive(Debug, Serialize, PartialEq)]
+pub struct Divergence {
+    pub field: String,
+    pub recorded: serde_json::Value,
+    pub recomputed: serde_json::Value,
+}
+
+/// Query parameters for listing the assessments of one change
+#[derive(Debug, Deserialize)]
+pub struct ByChangeQuery {
+    pub organization_id: String,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ListAssessmentsQuery {
     pub organization_id: String,
@@ -280,9 +375,13 @@ pub struct ListAssessmentsQuery {
 /// NOTE: This endpoint does NOT enforce policies, block changes, or execute changes.
 /// It provides read-only analysis for governance visibility.
 #[post("/governance/change-impact")]
-#[instrument(skip(pool, http_req), fields(organization_id, change_id))]
+#[instrument(skip(pool, config, category_weights, severity_policy, agent, http_req), fields(organization_id, change_id))]
 pub async fn assess_change_impact(
     pool: web::Data<PgPool>,
+    config: web::Data<Config>,
+    category_weights: web::Data<CategoryWeights>,
+    severity_policy: web::Data<SeverityPolicy>,
+    agent: web::Data<ChangeImpactAgent>,
     req: web::Json<ChangeImpactRequest>,
     http_req: actix_web::HttpRequest,
 ) -> Result<impl Responder> {
@@ -292,51 +391,188 @@ pub async fn assess_change_impact(
     );
     let _enter = span.enter();
 
+    let caller = authorize_org_audit(pool.get_ref(), &http_req, &req.organization_id).await?;
+    let response = run_assessment(
+        &Assessor::from_app_data(&pool, &config, &category_weights, &severity_policy, &agent),
+        caller,
+        &req,
+        &http_req,
+    ).await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(response)))
+}
+
+/// Assess a batch of proposed changes, e.g. everything planned for a release
+///
+/// POST /api/v1/governance/change-impact/batch
+///
+/// Each change is assessed and recorded exactly as by the single-change
+/// endpoint, at most `BATCH_CONCURRENCY` at a time, with the shared scope and
+/// options applied to all of them. Every change is validated before the
+/// first is assessed. Results keep the order of `changes`.
+#[post("/governance/change-impact/batch")]
+#[instrument(skip(pool, config, category_weights, severity_policy, agent, http_req), fields(organization_id))]
+pub async fn assess_change_impact_batch(
+    pool: web::Data<PgPool>,
+    config: web::Data<Config>,
+    category_weights: web::Data<CategoryWeights>,
+    severity_policy: web::Data<SeverityPolicy>,
+    agent: web::Data<ChangeImpactAgent>,
+    req: web::Json<ChangeImpactBatchRequest>,
+    http_req: actix_web::HttpRequest,
+) -> Result<impl Responder> {
+    let caller = authorize_org_audit(pool.get_ref(), &http_req, &req.organization_id).await?;
+
+    let batch = req.into_inner();
+    if batch.changes.is_empty() {
+        return Err(AppError::Validation("changes must not be empty".to_string()));
+    }
+    if batch.changes.len() > MAX_BATCH_CHANGES {
+        return Err(AppError::Validation(format!(
+            "changes must hold at most {} changes, got {}",
+            MAX_BATCH_CHANGES,
+            batch.changes.len()
+        )));
+    }
+
+    info!(
+        "Assessing {} changes for organization: {}",
+        batch.changes.len(),
+        batch.organization_id
+    );
+
+    let requests: Vec<ChangeImpactRequest> = batch
+        .changes
+        .into_iter()
+        .map(|change_request| ChangeImpactRequest {
+            organization_id: batch.organization_id.clone(),
+            change_request,
+            scope: batch.scope.clone(),
+            include_downstream: batch.include_downstream,
+            include_risk_projection: batch.include_risk_projection,
+            historical_range: batch.historical_range.clone(),
+            baseline_ref: None,
+        })
+        .collect();
+    validate_batch(config.get_ref(), &requests)?;
+
+    let assessor = Assessor::from_app_data(&pool, &config, &category_weights, &severity_policy, &agent);
+    let results: Vec<ChangeImpactResponse> = stream::iter(&requests)
+        .map(|request| run_assessment(&assessor, caller, request, &http_req))
+        .buffered(BATCH_CONCURRENCY)
+        .try_collect()
+        .await?;
+
+    let aggregate = aggregate_batch(&results);
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(ChangeImpactBatchResponse {
+        organization_id: batch.organization_id,
+        results,
+        aggregate,
+    })))
+}
+
+/// Validated inputs of one assessment
+struct AssessmentInputs {
+    change_request: ChangeRequest,
+    analysis_depth: u8,
+    historical_range: Option<DateRange>,
+}
+
+fn assessment_inputs(config: &Config, req: &ChangeImpactRequest) -> Result<AssessmentInputs> {
+    let change_request = change_request_from_input(&req.change_request)?;
+    let analysis_depth =
+        effective_analysis_depth(req.scope.as_ref().and_then(|s| s.analysis_depth))?;
+    let historical_range = req.historical_range.as_ref().map(parse_date_range).transpose()?;
+    if let Some(range) = &historical_range {
+        check_range_span(range, config.max_query_days)?;
+    }
+
+    Ok(AssessmentInputs { change_request, analysis_depth, historical_range })
+}
+
+/// Validate every change of a batch up front, so a bad one rejects the batch
+/// before any assessment is recorded
+fn validate_batch(config: &Config, requests: &[ChangeImpactRequest]) -> Result<()> {
+    for (index, request) in requests.iter().enumerate() {
+        if let Err(e) = assessment_inputs(config, request) {
+            return Err(match e {
+                AppError::Validation(message) => AppError::Validation(format!("changes[{}]: {}", index, message)),
+                other => other,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Shared state assessments run against
+#[derive(Clone, Copy)]
+pub struct Assessor<'a> {
+    pub pool: &'a PgPool,
+    pub config: &'a Config,
+    pub category_weights: &'a CategoryWeights,
+    pub severity_policy: &'a SeverityPolicy,
+    pub agent: &'a ChangeImpactAgent,
+}
+
+impl<'a> Assessor<'a> {
+    fn from_app_data(
+        pool: &'a web::Data<PgPool>,
+        config: &'a web::Data<Config>,
+        category_weights: &'a web::Data<CategoryWeights>,
+        severity_policy: &'a web::Data<SeverityPolicy>,
+        agent: &'a web::Data<ChangeImpactAgent>,
+    ) -> Self {
+        Self {
+            pool: pool.get_ref(),
+            config: config.get_ref(),
+            category_weights: category_weights.get_ref(),
+            severity_policy: severity_policy.get_ref(),
+            agent: agent.get_ref(),
+        }
+    }
+}
+
+/// Run, record and return one change impact assessment for `caller`
+pub async fn run_assessment(
+    assessor: &Assessor<'_>,
+    caller: Uuid,
+    req: &ChangeImpactRequest,
+    http_req: &actix_web::HttpRequest,
+) -> Result<ChangeImpactResponse> {
+    let Assessor { pool, config, category_weights, severity_policy, agent } = *assessor;
+    let start_time = std::time::Instant::now();
+
     info!(
         "Assessing change impact for organization: {}, change: {}",
         req.organization_id,
         req.change_request.change_id
     );
 
-    // Parse change type
-    let change_type = parse_change_type(&req.change_request.change_type)?;
-    let subject_type = parse_subject_type(&req.change_request.subject_type)?;
+    // Build internal input, validating types and timestamps before anything
+    // is derived from them
+    let AssessmentInputs { change_request, analysis_depth, historical_range } = assessment_inputs(config, req)?;
 
     // Extract execution context
-    let request_id = extract_request_id(&http_req);
-    let trace_id = extract_trace_id(&http_req);
-    let invoker = extract_invoker(&http_req);
-
-    // Build internal input
-    let timestamp = req.change_request.timestamp.clone()
-        .unwrap_or_else(|| chrono::Utc::now().to_rfc3339());
-
-    let change_request = ChangeRequest {
-        change_id: req.change_request.change_id.clone(),
-        change_type,
-        subject_type: subject_type.clone(),
-        subject_id: req.change_request.subject_id.clone(),
-        description: req.change_request.description.clone(),
-        timestamp,
-        initiator: req.change_request.initiator.clone(),
-        previous_state: req.change_request.previous_state.clone(),
-        new_state: req.change_request.new_state.clone(),
-        metadata: req.change_request.metadata.clone(),
-    };
+    let request_id = extract_request_id(http_req);
+    let trace_id = extract_trace_id(http_req);
+    let invoker = extract_invoker(http_req);
 
     // Step 1: Analyze impact areas
-    let impacts = analyze_impact_areas(pool.get_ref(), &change_request, &req.scope).await?;
+    let impacts = analyze_impact_areas(pool, &change_request, &req.scope).await?;
 
     // Step 2: Identify affected systems
+    let direct_systems = direct_affected_systems(&change_request);
     let affected_systems = if req.include_downstream {
-        analyze_affected_systems(pool.get_ref(), &change_request).await?
+        expand_downstream(direct_systems.clone(), analysis_depth)
     } else {
         Vec::new()
     };
+    let coverage_percentage = downstream_coverage(&direct_systems, &affected_systems);
 
     // Step 3: Analyze policy implications
     let policy_implications = analyze_policy_implications(
-        pool.get_ref(),
+        pool,
         &req.organization_id,
         &change_request,
     ).await?;
@@ -350,16 +586,22 @@ pub async fn assess_change_impact(
 
     // Step 5: Analyze cost implications (if requested)
     let cost_implications = if req.scope.as_ref().map_or(false, |s| s.include_cost_impact.unwrap_or(false)) {
-        analyze_cost_implications(pool.get_ref(), &req.organization_id, &change_request).await?
+        analyze_cost_implications(pool, &req.organization_id, &change_request, config.default_window_days).await?
     } else {
         None
     };
 
-    // Step 6: Surface risk indicators
-    let risk_indicators = generate_risk_indicators(&impacts, &policy_implications, &change_request);
+    // Step 6: Surface risk indicators, raised to any configured minimum severity
+    let mut risk_indicators = generate_risk_indicators(&impacts, &policy_implications, &change_request);
+    severity_policy.apply(&change_request, &mut risk_indicators);
 
     // Step 7: Calculate risk score and classification
-    let risk_score = calculate_risk_score(&impacts, &risk_indicators, &policy_implications);
+    let risk_score = calculate_risk_score(
+        &impacts,
+        &risk_indicators,
+        &policy_implications,
+        category_weights,
+    );
     let impact_level = ImpactLevel::from_score(risk_score);
     let risk_classification = RiskClassification::from_score(risk_score);
 
@@ -372,14 +614,21 @@ pub async fn assess_change_impact(
 
     // Step 9: Get historical context (if requested)
     let historical_context = if req.include_risk_projection {
-        get_historical_context(pool.get_ref(), &change_request, &req.historical_range).await?
+        get_historical_context(pool, &change_request, historical_range.as_ref()).await?
     } else {
         None
     };
 
-    // Step 10: Build assessment
+    // Step 10: Build assessment, comparing against the baseline (if requested and resolvable)
+    let baseline_comparison = match req.baseline_ref {
+        Some(ref baseline_ref) => resolve_baseline(pool, &req.organization_id, baseline_ref)
+            .await
+            .map(|baseline| compare_to_baseline(&baseline, risk_score, &risk_indicators)),
+        None => None,
+    };
+
     let assessment_id = Uuid::new_v4().to_string();
-    let assessed_at = chrono::Utc::now().to_rfc3339();
+    let assessed_at = Timestamp::now();
 
     let summary = build_assessment_summary(
         &change_request,
@@ -390,15 +639,19 @@ pub async fn assess_change_impact(
     );
 
     // Step 11: Calculate confidence
-    let confidence = calculate_assessment_confidence(
+    let mut confidence = calculate_assessment_confidence(
         &impacts,
         &affected_systems,
         &historical_context,
         req.scope.as_ref(),
     );
+    confidence.factors.extend(baseline_confidence_factor(
+        req.baseline_ref.as_deref(),
+        baseline_comparison.as_ref(),
+    ));
 
     // Step 12: Build constraints applied
-    let constraints = build_constraints(&req);
+    let constraints = build_constraints(req, historical_range.as_ref());
 
     // Step 13: Create execution reference
     let execution_ref = execution_ref_from_request(
@@ -415,7 +668,8 @@ pub async fn assess_change_impact(
         &risk_indicators,
         &recommendations,
         &affected_systems,
-        &assessed_at,
+        coverage_percentage,
+        assessed_at,
     );
 
     // Step 15: Create DecisionEvent
@@ -428,14 +682,30 @@ pub async fn assess_change_impact(
         confidence.clone(),
         constraints,
         execution_ref,
-        &req.0,
+        req,
     );
 
     let event_id = decision_event.id.clone();
-    let timestamp = decision_event.timestamp.clone();
-
-    // Step 16: Generate telemetry reference
-    let telemetry_ref = format!("observatory://telemetry/{}/{}", AGENT_ID, event_id);
+    let timestamp = decision_event.timestamp.to_string();
+
+    // Step 16: Emit telemetry to LLM-Observatory (if configured)
+    let (telemetry_ref, telemetry_status) = agent.emit_assessment_telemetry(&AssessmentTelemetry {
+        organization_id: &req.organization_id,
+        change_request: &change_request,
+        decision_event: &decision_event,
+        impact_level: &impact_level,
+        risk_classification: &risk_classification,
+        risk_score,
+        confidence_score: confidence.overall,
+        affected_systems_count: affected_systems.len(),
+        policy_implications_count: policy_implications.len(),
+        recommendations_count: recommendations.len(),
+        findings_count: risk_indicators.len(),
+        assessed_at,
+        execution_duration_ms: start_time.elapsed().as_millis() as f64,
+        request_id: request_id.as_deref(),
+        trace_id: trace_id.as_deref(),
+    }).await;
 
     info!(
         "Change impact assessment completed: event_id={}, risk_classification={:?}",
@@ -523,7 +793,10 @@ pub async fn assess_change_impact(
                 common_issues: h.common_issues,
                 success_patterns: h.success_patterns,
             }),
-            assessed_at,
+            analysis_depth,
+            coverage_percentage,
+            baseline_comparison,
+            assessed_at: assessed_at.to_string(),
         },
         confidence: ConfidenceResponse {
             overall: confidence.overall,
@@ -531,80 +804,161 @@ pub async fn assess_change_impact(
             certainty: confidence.certainty,
         },
         telemetry_ref,
+        telemetry_status,
     };
 
-    Ok(HttpResponse::Ok().json(ApiResponse::success(response)))
+    // Step 18: Record the assessment in audit_logs with the fields history,
+    // lookups and baselines read, and in the shared decision store
+    record_decision_event(
+        pool,
+        caller,
+        CHANGE_IMPACT_RESOURCE,
+        &decision_event,
+        serde_json::json!({
+            "assessment_id": response.assessment.id,
+            "change_request_id": response.assessment.change_request_id,
+            "subject_type": req.change_request.subject_type,
+            "impact_level": response.assessment.impact_level,
+            "risk_classification": response.assessment.risk_classification,
+            "risk_score": response.assessment.risk_score,
+            "risk_indicators": response.assessment.risk_indicators,
+            "inputs": req,
+        }),
+    ).await?;
+    store_decision_event(agent.decision_store(), &decision_event).await;
+
+    Ok(response)
 }
 
 /// Simulate change impact (same analysis, marked as simulation)
 ///
 /// POST /api/v1/governance/change-impact/simulate
 #[post("/governance/change-impact/simulate")]
-#[instrument(skip(pool, http_req), fields(organization_id))]
+#[instrument(skip(pool, config, category_weights, severity_policy, agent, http_req), fields(organization_id))]
 pub async fn simulate_change_impact(
     pool: web::Data<PgPool>,
+    config: web::Data<Config>,
+    category_weights: web::Data<CategoryWeights>,
+    severity_policy: web::Data<SeverityPolicy>,
+    agent: web::Data<ChangeImpactAgent>,
     req: web::Json<ChangeImpactRequest>,
     http_req: actix_web::HttpRequest,
 ) -> Result<impl Responder> {
-    // Use the same logic as assess_change_impact
-    // The difference is only in the metadata (marked as simulation)
     info!(
         "Simulating change impact for organization: {}, change: {}",
         req.organization_id,
         req.change_request.change_id
     );
 
-    // Call the same handler - the difference is semantic and in metadata
-    assess_change_impact(pool, req, http_req).await
+    let caller = authorize_org_audit(pool.get_ref(), &http_req, &req.organization_id).await?;
+    let response = run_assessment(
+        &Assessor::from_app_data(&pool, &config, &category_weights, &severity_policy, &agent),
+        caller,
+        &req,
+        &http_req,
+    ).await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(response)))
 }
 
 /// List previous change impact assessments
 ///
 /// GET /api/v1/governance/change-impact/history
 #[get("/governance/change-impact/history")]
-#[instrument(skip(pool), fields(organization_id))]
+#[instrument(skip(pool, http_req), fields(organization_id))]
 pub async fn list_change_impact_assessments(
     pool: web::Data<PgPool>,
     query: web::Query<ListAssessmentsQuery>,
+    http_req: actix_web::HttpRequest,
 ) -> Result<impl Responder> {
-    let limit = query.limit.unwrap_or(50).min(100);
-    let offset = query.offset.unwrap_or(0);
+    authorize_org_audit(pool.get_ref(), &http_req, &query.organization_id).await?;
+
+    let page = Page::new(query.limit, query.offset);
 
     // Query stored assessments (in production, would query ruvector-service)
-    let assessments = sqlx::query_as::<_, (Uuid, DateTime<Utc>, serde_json::Value)>(
+    let mut assessments = organization_assessments("SELECT id, timestamp, details FROM audit_logs", &query.organization_id);
+    assessments.push(" ORDER BY timestamp DESC, id DESC");
+    let (assessments, total): (Vec<(Uuid, NaiveDateTime, serde_json::Value)>, i64) = paginate(
+        pool.get_ref(),
+        assessments,
+        organization_assessments("SELECT COUNT(*) FROM audit_logs", &query.organization_id),
+        page,
+    )
+    .await?;
+
+    let response_assessments: Vec<serde_json::Value> = assessments
+        .iter()
+        .map(|(id, ts, details)| assessment_listing(*id, ts.and_utc(), details))
+        .collect();
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
+        "assessments": response_assessments,
+        "total": total,
+        "limit": page.limit,
+        "offset": page.offset
+    }))))
+}
+
+/// `head` restricted to the recorded assessments of one organization
+fn organization_assessments(head: &str, organization_id: &str) -> DynamicQuery<'static> {
+    let mut query = DynamicQuery::new(head);
+    query
+        .filter("resource_type", CHANGE_IMPACT_RESOURCE)
+        .filter("details->>'organization_id'", organization_id.to_string());
+    query
+}
+
+/// Summary of a recorded assessment as returned by the list endpoints
+fn assessment_listing(id: Uuid, assessed_at: DateTime<Utc>, details: &serde_json::Value) -> serde_json::Value {
+    let field = |name: &str| details.get(name).cloned().unwrap_or(serde_json::Value::Null);
+    serde_json::json!({
+        "id": id,
+        "assessment_id": field("assessment_id"),
+        "event_id": field("event_id"),
+        "change_request_id": field("change_request_id"),
+        "subject_type": field("subject_type"),
+        "impact_level": field("impact_level"),
+        "risk_classification": field("risk_classification"),
+        "risk_score": field("risk_score"),
+        "assessed_at": assessed_at
+    })
+}
+
+/// List the assessments made for a change, newest first
+///
+/// GET /api/v1/governance/change-impact/by-change/{change_id}
+#[get("/governance/change-impact/by-change/{change_id}")]
+#[instrument(skip(pool, http_req), fields(change_id))]
+pub async fn list_assessments_for_change(
+    pool: web::Data<PgPool>,
+    change_id: web::Path<String>,
+    query: web::Query<ByChangeQuery>,
+    http_req: actix_web::HttpRequest,
+) -> Result<impl Responder> {
+    authorize_org_audit(pool.get_ref(), &http_req, &query.organization_id).await?;
+
+    let assessments = sqlx::query_as::<_, (Uuid, NaiveDateTime, serde_json::Value)>(
         r#"
         SELECT id, timestamp, details
         FROM audit_logs
         WHERE resource_type = 'change_impact_assessment'
-        AND details->>'organization_id' = $1
+        AND details->>'change_request_id' = $1
+        AND details->>'organization_id' = $2
         ORDER BY timestamp DESC
-        LIMIT $2 OFFSET $3
+        LIMIT 100
         "#
     )
+    .bind(change_id.as_str())
     .bind(&query.organization_id)
-    .bind(limit as i64)
-    .bind(offset as i64)
     .fetch_all(pool.get_ref())
-    .await
-    .unwrap_or_default();
-
-    let response_assessments: Vec<serde_json::Value> = assessments.iter().map(|(id, ts, details)| {
-        serde_json::json!({
-            "id": id,
-            "change_request_id": details.get("change_request_id").unwrap_or(&serde_json::Value::Null),
-            "subject_type": details.get("subject_type").unwrap_or(&serde_json::Value::Null),
-            "impact_level": details.get("impact_level").unwrap_or(&serde_json::Value::Null),
-            "risk_classification": details.get("risk_classification").unwrap_or(&serde_json::Value::Null),
-            "risk_score": details.get("risk_score").unwrap_or(&serde_json::Value::Null),
-            "assessed_at": ts
-        })
-    }).collect();
+    .await?;
 
     Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
-        "assessments": response_assessments,
-        "total": response_assessments.len(),
-        "limit": limit,
-        "offset": offset
+        "change_id": change_id.as_str(),
+        "assessments": assessments
+            .iter()
+            .map(|(id, ts, details)| assessment_listing(*id, ts.and_utc(), details))
+            .collect::<Vec<_>>(),
     }))))
 }
 
@@ -612,82 +966,138 @@ pub async fn list_change_impact_assessments(
 ///
 /// GET /api/v1/governance/change-impact/{assessment_id}
 #[get("/governance/change-impact/{assessment_id}")]
-#[instrument(skip(pool), fields(assessment_id))]
+#[instrument(skip(pool, http_req), fields(assessment_id))]
 pub async fn get_change_impact_assessment(
     pool: web::Data<PgPool>,
     assessment_id: web::Path<String>,
+    http_req: actix_web::HttpRequest,
 ) -> Result<impl Responder> {
-    let assessment = sqlx::query_as::<_, (Uuid, DateTime<Utc>, serde_json::Value)>(
-        r#"
-        SELECT id, timestamp, details
-        FROM audit_logs
-        WHERE resource_type = 'change_impact_assessment'
-        AND (id::text = $1 OR details->>'event_id' = $1 OR details->>'assessment_id' = $1)
-        "#
-    )
-    .bind(assessment_id.as_str())
-    .fetch_optional(pool.get_ref())
-    .await?
-    .ok_or_else(|| AppError::NotFound("Change impact assessment not found".to_string()))?;
+    let assessment = fetch_assessment(pool.get_ref(), &assessment_id).await?;
+
+    authorize_record_audit(pool.get_ref(), &http_req, &assessment.2).await?;
 
     Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
         "id": assessment.0,
-        "timestamp": assessment.1,
+        "timestamp": assessment.1.and_utc(),
         "details": assessment.2
     }))))
 }
 
+/// Export an assessment's risk indicators as findings
+///
+/// GET /api/v1/governance/change-impact/{assessment_id}/findings
+///
+/// Responds with the indicators as JSON, or a SARIF 2.1.0 log when negotiated
+/// via `Accept: application/sarif+json` or `?format=sarif`.
+#[get("/governance/change-impact/{assessment_id}/findings")]
+#[instrument(skip(pool, http_req), fields(assessment_id))]
+pub async fn get_change_impact_findings(
+    pool: web::Data<PgPool>,
+    assessment_id: web::Path<String>,
+    http_req: actix_web::HttpRequest,
+) -> Result<impl Responder> {
+    let format = preferred_format(&http_req, &["json", "sarif"])?;
+    let (id, _, details) = fetch_assessment(pool.get_ref(), &assessment_id).await?;
+
+    authorize_record_audit(pool.get_ref(), &http_req, &details).await?;
+
+    let assessment_id = details
+        .get("assessment_id")
+        .and_then(|v| v.as_str())
+        .map(String::from)
+        .unwrap_or_else(|| id.to_string());
+    let indicators = stored_risk_indicators(&details)?;
+
+    if format == Format::Sarif {
+        return Ok(HttpResponse::Ok()
+            .content_type(format.content_type())
+            .json(render_findings_sarif(&assessment_id, &indicators)));
+    }
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
+        "assessment_id": assessment_id,
+        "findings": indicators
+    }))))
+}
+
+/// Endpoints, capabilities and boundaries of the Change Impact Agent
+pub const AGENT_DESCRIPTOR: AgentDescriptor = AgentDescriptor {
+    agent_id: AGENT_ID,
+    name: "Change Impact Agent",
+    description: "Assess downstream governance and compliance impact of configuration or policy changes",
+    version: AGENT_VERSION,
+    classification: "governance",
+    decision_types: &[
+        GovernanceDecisionType::ChangeImpact,
+        GovernanceDecisionType::RiskAggregation,
+    ],
+    capabilities: &[
+        "analyze_historical_changes",
+        "evaluate_affected_systems",
+        "surface_risk_indicators",
+        "assess_policy_implications",
+        "assess_compliance_implications",
+        "estimate_cost_impact",
+        "provide_historical_context",
+        "generate_recommendations",
+    ],
+    non_responsibilities: &[
+        "intercept_execution",
+        "trigger_retries_or_workflows",
+        "enforce_policies",
+        "modify_configurations",
+        "emit_anomaly_detections",
+        "apply_optimizations",
+        "execute_sql_directly",
+        "connect_to_google_sql_directly",
+        "block_or_approve_changes",
+        "execute_changes",
+    ],
+    endpoints: &[
+        AgentEndpoint { name: "assess", method: "POST", path: "/governance/change-impact" },
+        AgentEndpoint { name: "batch", method: "POST", path: "/governance/change-impact/batch" },
+        AgentEndpoint { name: "simulate", method: "POST", path: "/governance/change-impact/simulate" },
+        AgentEndpoint { name: "history", method: "GET", path: "/governance/change-impact/history" },
+        AgentEndpoint { name: "by_change", method: "GET", path: "/governance/change-impact/by-change/{change_id}" },
+        AgentEndpoint { name: "get", method: "GET", path: "/governance/change-impact/{assessment_id}" },
+        AgentEndpoint { name: "findings", method: "GET", path: "/governance/change-impact/{assessment_id}/findings" },
+        AgentEndpoint { name: "verify", method: "POST", path: "/governance/change-impact/{assessment_id}/verify" },
+        AgentEndpoint { name: "agent", method: "GET", path: "/governance/change-impact/agent" },
+    ],
+};
+
 /// Get Change Impact Agent registration metadata
 ///
 /// GET /api/v1/governance/change-impact/agent
 #[get("/governance/change-impact/agent")]
 pub async fn get_change_impact_agent_registration() -> Result<impl Responder> {
-    Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
-        "agent_id": AGENT_ID,
-        "name": "Change Impact Agent",
-        "description": "Assess downstream governance and compliance impact of configuration or policy changes",
-        "version": AGENT_VERSION,
-        "classification": "governance",
-        "decision_types": [
-            "change_impact",
-            "risk_aggregation"
-        ],
-        "capabilities": [
-            "analyze_historical_changes",
-            "evaluate_affected_systems",
-            "surface_risk_indicators",
-            "assess_policy_implications",
-            "assess_compliance_implications",
-            "estimate_cost_impact",
-            "provide_historical_context",
-            "generate_recommendations"
-        ],
-        "non_responsibilities": [
-            "intercept_execution",
-            "trigger_retries_or_workflows",
-            "enforce_policies",
-            "modify_configurations",
-            "emit_anomaly_detections",
-            "apply_optimizations",
-            "execute_sql_directly",
-            "connect_to_google_sql_directly",
-            "block_or_approve_changes",
-            "execute_changes"
-        ],
-        "endpoints": {
-            "assess": "POST /api/v1/governance/change-impact",
-            "simulate": "POST /api/v1/governance/change-impact/simulate",
-            "history": "GET /api/v1/governance/change-impact/history",
-            "get": "GET /api/v1/governance/change-impact/{assessment_id}",
-            "agent": "GET /api/v1/governance/change-impact/agent"
-        }
-    }))))
+    Ok(HttpResponse::Ok().json(ApiResponse::success(AGENT_DESCRIPTOR.to_json())))
 }
 
 // ============================================================================
 // Internal Helper Functions
 // ============================================================================
 
+fn change_request_from_input(input: &ChangeRequestInput) -> Result<ChangeRequest> {
+    let timestamp = match input.timestamp {
+        Some(ref ts) => parse_timestamp("change_request.timestamp", ts)?.to_rfc3339(),
+        None => chrono::Utc::now().to_rfc3339(),
+    };
+
+    Ok(ChangeRequest {
+        change_id: input.change_id.clone(),
+        change_type: parse_change_type(&input.change_type)?,
+        subject_type: parse_subject_type(&input.subject_type)?,
+        subject_id: input.subject_id.clone(),
+        description: input.description.clone(),
+        timestamp,
+        initiator: input.initiator.clone(),
+        previous_state: input.previous_state.clone(),
+        new_state: input.new_state.clone(),
+        metadata: input.metadata.clone(),
+    })
+}
+
 fn parse_change_type(change_type: &str) -> Result<ChangeType> {
     match change_type.to_lowercase().as_str() {
         "create" => Ok(ChangeType::Create),
@@ -795,10 +1205,8 @@ async fn analyze_impact_areas(
     Ok(impacts)
 }
 
-async fn analyze_affected_systems(
-    _pool: &PgPool,
-    change: &ChangeRequest,
-) -> Result<Vec<AffectedSystem>> {
+/// Systems the change affects directly, before downstream expansion
+fn direct_affected_systems(change: &ChangeRequest) -> Vec<AffectedSystem> {
     let mut systems = Vec::new();
 
     match change.subject_type {
@@ -843,7 +1251,287 @@ async fn analyze_affected_systems(
         _ => {}
     }
 
-    Ok(systems)
+    systems
+}
+
+/// Re-check a recorded assessment against its inputs
+///
+/// POST /api/v1/governance/change-impact/{assessment_id}/verify
+///
+/// Re-hashes the recorded inputs against the event's `inputs_hash` and re-runs
+/// the deterministic part of the analysis (impacts, policy implications, risk
+/// indicators and score) to confirm the recorded risk. Assessments recorded
+/// without their inputs cannot be verified.
+#[post("/governance/change-impact/{assessment_id}/verify")]
+#[instrument(skip(pool, category_weights, severity_policy, http_req), fields(assessment_id))]
+pub async fn verify_change_impact_assessment(
+    pool: web::Data<PgPool>,
+    category_weights: web::Data<CategoryWeights>,
+    severity_policy: web::Data<SeverityPolicy>,
+    assessment_id: web::Path<String>,
+    http_req: actix_web::HttpRequest,
+) -> Result<impl Responder> {
+    let (_, _, details) = fetch_assessment(pool.get_ref(), &assessment_id).await?;
+
+    authorize_record_audit(pool.get_ref(), &http_req, &details).await?;
+
+    let verification = verify_recorded_assessment(
+        pool.get_ref(),
+        &details,
+        category_weights.get_ref(),
+        severity_policy.get_ref(),
+    ).await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(verification)))
+}
+
+/// Recorded assessment by row id, event id or assessment id
+async fn fetch_assessment(
+    pool: &PgPool,
+    assessment_ref: &str,
+) -> Result<(Uuid, NaiveDateTime, serde_json::Value)> {
+    sqlx::query_as::<_, (Uuid, NaiveDateTime, serde_json::Value)>(
+        r#"
+        SELECT id, timestamp, details
+        FROM audit_logs
+        WHERE resource_type = 'change_impact_assessment'
+        AND (id::text = $1 OR details->>'event_id' = $1 OR details->>'assessment_id' = $1)
+        "#
+    )
+    .bind(assessment_ref)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Change impact assessment not found".to_string()))
+}
+
+/// Check a recorded assessment's `inputs_hash` and risk against its inputs
+async fn verify_recorded_assessment(
+    pool: &PgPool,
+    details: &serde_json::Value,
+    category_weights: &CategoryWeights,
+    severity_policy: &SeverityPolicy,
+) -> Result<AssessmentVerification> {
+    let event: DecisionEvent = serde_json::from_value(details.clone())
+        .map_err(|e| AppError::Internal(format!("Stored decision event is unreadable: {}", e)))?;
+
+    let Some(inputs) = details.get("inputs") else {
+        return Ok(AssessmentVerification {
+            event_id: event.id,
+            verified: false,
+            inputs_available: false,
+            divergences: Vec::new(),
+        });
+    };
+
+    let mut divergences = Vec::new();
+    let inputs_hash = hash_inputs(inputs);
+    if inputs_hash != event.inputs_hash {
+        divergences.push(Divergence {
+            field: "inputs_hash".to_string(),
+            recorded: event.inputs_hash.clone().into(),
+            recomputed: inputs_hash.into(),
+        });
+    }
+
+    match serde_json::from_value::<ChangeImpactRequest>(inputs.clone()) {
+        Ok(req) => {
+            let risk_score = recompute_risk_score(pool, &req, category_weights, severity_policy).await?;
+            divergences.extend(risk_divergences(details, risk_score));
+        }
+        Err(e) => divergences.push(Divergence {
+            field: "inputs".to_string(),
+            recorded: inputs.clone(),
+            recomputed: format!("unreadable: {}", e).into(),
+        }),
+    }
+
+    Ok(AssessmentVerification {
+        event_id: event.id,
+        verified: divergences.is_empty(),
+        inputs_available: true,
+        divergences,
+    })
+}
+
+/// Risk score of `req` from the deterministic steps of [`run_assessment`]
+async fn recompute_risk_score(
+    pool: &PgPool,
+    req: &ChangeImpactRequest,
+    category_weights: &CategoryWeights,
+    severity_policy: &SeverityPolicy,
+) -> Result<f64> {
+    let change_request = change_request_from_input(&req.change_request)?;
+    let impacts = analyze_impact_areas(pool, &change_request, &req.scope).await?;
+    let policy_implications =
+        analyze_policy_implications(pool, &req.organization_id, &change_request).await?;
+    let mut risk_indicators = generate_risk_indicators(&impacts, &policy_implications, &change_request);
+    severity_policy.apply(&change_request, &mut risk_indicators);
+
+    Ok(calculate_risk_score(&impacts, &risk_indicators, &policy_implications, category_weights))
+}
+
+/// Recorded risk fields that `risk_score` does not reproduce
+fn risk_divergences(details: &serde_json::Value, risk_score: f64) -> Vec<Divergence> {
+    let recomputed = [
+        ("risk_score", serde_json::json!(risk_score)),
+        (
+            "risk_classification",
+            format!("{:?}", RiskClassification::from_score(risk_score)).to_lowercase().into(),
+        ),
+        (
+            "impact_level",
+            format!("{:?}", ImpactLevel::from_score(risk_score)).to_lowercase().into(),
+        ),
+    ];
+
+    recomputed
+        .into_iter()
+        .filter_map(|(field, recomputed)| {
+            let recorded = details.get(field).cloned().unwrap_or(serde_json::Value::Null);
+            let matches = match (recorded.as_f64(), recomputed.as_f64()) {
+                (Some(a), Some(b)) => (a - b).abs() < 1e-9,
+                _ => recorded == recomputed,
+            };
+            (!matches).then(|| Divergence {
+                field: field.to_string(),
+                recorded,
+                recomputed,
+            })
+        })
+        .collect()
+}
+
+/// Risk indicators recorded with an assessment; none for records without them
+fn stored_risk_indicators(details: &serde_json::Value) -> Result<Vec<RiskIndicatorResponse>> {
+    match details.get("risk_indicators") {
+        Some(indicators) => serde_json::from_value(indicators.clone()).map_err(|e| {
+            AppError::Internal(format!("Stored risk indicators are unreadable: {}", e))
+        }),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// SARIF result level for a governance severity
+fn sarif_level(severity: &GovernanceSeverity) -> &'static str {
+    match severity {
+        GovernanceSeverity::Critical | GovernanceSeverity::High => "error",
+        GovernanceSeverity::Medium => "warning",
+        GovernanceSeverity::Low => "note",
+        GovernanceSeverity::Info => "none",
+    }
+}
+
+/// Render risk indicators as a SARIF 2.1.0 log with one rule per category
+///
+/// Severities that do not parse are reported as `warning`.
+fn render_findings_sarif(assessment_id: &str, indicators: &[RiskIndicatorResponse]) -> serde_json::Value {
+    let mut rules: Vec<&str> = Vec::new();
+    let results: Vec<serde_json::Value> = indicators
+        .iter()
+        .map(|indicator| {
+            let rule_index = match rules.iter().position(|r| *r == indicator.category) {
+                Some(index) => index,
+                None => {
+                    rules.push(&indicator.category);
+                    rules.len() - 1
+                }
+            };
+            let level = serde_json::from_value::<GovernanceSeverity>(serde_json::json!(indicator.severity))
+                .map(|severity| sarif_level(&severity))
+                .unwrap_or("warning");
+
+            serde_json::json!({
+                "ruleId": indicator.category,
+                "ruleIndex": rule_index,
+                "level": level,
+                "message": { "text": indicator.description },
+                "partialFingerprints": { "riskIndicatorId": indicator.id },
+                "properties": {
+                    "severity": indicator.severity,
+                    "evidence": indicator.evidence,
+                    "mitigation_suggestions": indicator.mitigation_suggestions,
+                },
+            })
+        })
+        .collect();
+
+    serde_json::json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": AGENT_ID,
+                    "version": AGENT_VERSION,
+                    "rules": rules
+                        .iter()
+                        .map(|category| serde_json::json!({
+                            "id": category,
+                            "name": category,
+                            "shortDescription": { "text": format!("Change impact {} indicator", category) },
+                        }))
+                        .collect::<Vec<_>>(),
+                },
+            },
+            "automationDetails": { "id": format!("change-impact/{}", assessment_id) },
+            "results": results,
+        }],
+    })
+}
+
+/// Resolve a prior stored assessment to compare against
+///
+/// Returns `None` when the baseline is missing or unreadable so the
+/// assessment still completes; the gap is reported in confidence factors.
+async fn resolve_baseline(
+    pool: &PgPool,
+    organization_id: &str,
+    baseline_ref: &str,
+) -> Option<BaselineSnapshot> {
+    let details = sqlx::query_scalar::<_, serde_json::Value>(
+        r#"
+        SELECT details
+        FROM audit_logs
+        WHERE resource_type = 'change_impact_assessment'
+        AND details->>'organization_id' = $1
+        AND (id::text = $2 OR details->>'event_id' = $2 OR details->>'assessment_id' = $2)
+        "#
+    )
+    .bind(organization_id)
+    .bind(baseline_ref)
+    .fetch_optional(pool)
+    .await;
+
+    match details {
+        Ok(Some(details)) => baseline_from_details(baseline_ref, &details),
+        Ok(None) => None,
+        Err(e) => {
+            warn!("Failed to load baseline assessment {}: {}", baseline_ref, e);
+            None
+        }
+    }
+}
+
+fn baseline_from_details(baseline_ref: &str, details: &serde_json::Value) -> Option<BaselineSnapshot> {
+    let risk_score = details.get("risk_score")?.as_f64()?;
+    let findings = details
+        .get("risk_indicators")
+        .and_then(|v| v.as_array())
+        .map(|indicators| {
+            indicators
+                .iter()
+                .filter_map(|i| i.get("description").and_then(|d| d.as_str()))
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Some(BaselineSnapshot {
+        baseline_ref: baseline_ref.to_string(),
+        source: BaselineSource::PriorAssessment,
+        risk_score,
+        findings,
+    })
 }
 
 async fn analyze_policy_implications(
@@ -913,23 +1601,82 @@ async fn analyze_compliance_implications(
 }
 
 async fn analyze_cost_implications(
-    _pool: &PgPool,
-    _organization_id: &str,
+    pool: &PgPool,
+    organization_id: &str,
     change: &ChangeRequest,
+    window_days: i64,
 ) -> Result<Option<CostImplication>> {
-    match change.subject_type {
-        ChangeSubjectType::Budget | ChangeSubjectType::Quota | ChangeSubjectType::LlmModel => {
-            Ok(Some(CostImplication {
-                estimated_delta: 0.0, // Would calculate from actual data
-                currency: "USD".to_string(),
-                period: "monthly".to_string(),
-                confidence: 0.6,
-                breakdown: vec![],
-                budget_alerts_triggered: vec![],
-            }))
-        }
-        _ => Ok(None)
+    // Spend is only tracked for registered organizations
+    let Ok(org_id) = Uuid::parse_str(organization_id) else {
+        return Ok(None);
+    };
+
+    // Providers are broken down per provider; everything else per model
+    let spend = if change.subject_type == ChangeSubjectType::LlmProvider {
+        sqlx::query_as::<_, (String, String, f64)>(
+            r#"
+            SELECT p.id::text, p.display_name, COALESCE(SUM(r.total_cost), 0)::float8
+            FROM llm_requests r
+            JOIN llm_models m ON m.id = r.model_id
+            JOIN llm_providers p ON p.id = m.provider_id
+            WHERE r.organization_id = $1
+            AND r.timestamp >= NOW() - make_interval(days => $2)
+            GROUP BY p.id, p.display_name
+            "#
+        )
+    } else {
+        sqlx::query_as::<_, (String, String, f64)>(
+            r#"
+            SELECT m.id::text, m.model_name, COALESCE(SUM(r.total_cost), 0)::float8
+            FROM llm_requests r
+            JOIN llm_models m ON m.id = r.model_id
+            WHERE r.organization_id = $1
+            AND r.timestamp >= NOW() - make_interval(days => $2)
+            GROUP BY m.id, m.model_name
+            "#
+        )
     }
+    .bind(org_id)
+    .bind(window_days as i32)
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|(category_id, category, current_cost)| CategorySpend {
+        category_id,
+        category,
+        current_cost,
+    })
+    .collect::<Vec<_>>();
+
+    let budgets = sqlx::query_as::<_, (Uuid, String, f64, String, i32, f64)>(
+        r#"
+        SELECT id, name, amount::float8, period,
+               COALESCE(alert_threshold_percentage, 80), COALESCE(current_spend, 0)::float8
+        FROM budgets
+        WHERE organization_id = $1
+        AND is_active = true
+        AND period_end > NOW()
+        "#
+    )
+    .bind(org_id)
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|(id, name, amount, period, threshold, current_spend)| BudgetThreshold {
+        budget_id: id.to_string(),
+        name,
+        amount,
+        period,
+        alert_threshold_percentage: threshold as f64,
+        current_spend,
+    })
+    .collect::<Vec<_>>();
+
+    if spend.is_empty() && budgets.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(build_cost_implication(change, &spend, &budgets, "USD", window_days)))
 }
 
 fn generate_risk_indicators(
@@ -985,13 +1732,16 @@ fn generate_risk_indicators(
     indicators
 }
 
+/// Mean of the impact, risk and policy contributions, with each risk
+/// indicator counted `weights.weight(category)` times
 fn calculate_risk_score(
     impacts: &[ImpactDetail],
     risks: &[RiskIndicator],
     policy_implications: &[PolicyImplication],
+    weights: &CategoryWeights,
 ) -> f64 {
     let mut score = 0.0;
-    let mut count = 0;
+    let mut count = 0.0;
 
     for impact in impacts {
         score += match impact.level {
@@ -1002,26 +1752,27 @@ fn calculate_risk_score(
             ImpactLevel::High => 0.75,
             ImpactLevel::Critical => 1.0,
         };
-        count += 1;
+        count += 1.0;
     }
 
     for risk in risks {
-        score += match risk.severity {
+        let weight = weights.weight(&risk.category);
+        score += weight * match risk.severity {
             GovernanceSeverity::Info => 0.1,
             GovernanceSeverity::Low => 0.25,
             GovernanceSeverity::Medium => 0.5,
             GovernanceSeverity::High => 0.75,
             GovernanceSeverity::Critical => 1.0,
         };
-        count += 1;
+        count += weight;
     }
 
     for implication in policy_implications {
         score += if !implication.policy_remains_valid { 0.8 } else { 0.2 };
-        count += 1;
+        count += 1.0;
     }
 
-    if count > 0 { (score / count as f64).min(1.0) } else { 0.0 }
+    if count > 0.0 { (score / count).min(1.0) } else { 0.0 }
 }
 
 fn generate_recommendations_from_analysis(
@@ -1096,7 +1847,7 @@ fn generate_recommendations_from_analysis(
 async fn get_historical_context(
     _pool: &PgPool,
     change: &ChangeRequest,
-    _time_range: &Option<DateRangeInput>,
+    _time_range: Option<&DateRange>,
 ) -> Result<Option<HistoricalContext>> {
     // In production, would query ruvector-service for similar past changes
     Ok(Some(HistoricalContext {
@@ -1158,7 +1909,42 @@ fn calculate_assessment_confidence(
     default_confidence(completeness.min(1.0), certainty)
 }
 
-fn build_constraints(req: &ChangeImpactRequest) -> Vec<ConstraintApplication> {
+/// Aggregate of a batch: the riskiest change, the union of affected systems
+/// and the number of critical risk indicators
+fn aggregate_batch(results: &[ChangeImpactResponse]) -> ChangeImpactBatchAggregate {
+    let riskiest = results
+        .iter()
+        .map(|r| &r.assessment)
+        .max_by(|a, b| a.risk_score.total_cmp(&b.risk_score));
+
+    let mut affected_systems: Vec<String> = Vec::new();
+    for system in results.iter().flat_map(|r| &r.assessment.affected_systems) {
+        if !affected_systems.contains(&system.system_id) {
+            affected_systems.push(system.system_id.clone());
+        }
+    }
+
+    let critical_findings = results
+        .iter()
+        .flat_map(|r| &r.assessment.risk_indicators)
+        .filter(|r| r.severity == "critical")
+        .count() as u32;
+
+    ChangeImpactBatchAggregate {
+        change_count: results.len(),
+        max_risk_score: riskiest.map_or(0.0, |a| a.risk_score),
+        max_risk_classification: riskiest.map(|a| a.risk_classification.clone()),
+        max_impact_level: riskiest.map(|a| a.impact_level.clone()),
+        riskiest_change_id: riskiest.map(|a| a.change_request_id.clone()),
+        affected_systems,
+        critical_findings,
+    }
+}
+
+fn build_constraints(
+    req: &ChangeImpactRequest,
+    historical_range: Option<&DateRange>,
+) -> Vec<ConstraintApplication> {
     vec![
         ConstraintApplication {
             constraint_id: "org-boundary".to_string(),
@@ -1168,10 +1954,7 @@ fn build_constraints(req: &ChangeImpactRequest) -> Vec<ConstraintApplication> {
                 organizations: vec![req.organization_id.clone()],
                 teams: req.scope.as_ref().and_then(|s| s.teams.clone()).unwrap_or_default(),
                 resource_types: vec![],
-                time_range: req.historical_range.as_ref().map(|r| DateRange {
-                    start: r.start.clone(),
-                    end: r.end.clone(),
-                }),
+                time_range: historical_range.cloned(),
             },
             satisfied: true,
             details: "Analysis scoped to organization".to_string(),
@@ -1198,7 +1981,8 @@ fn build_decision_outputs(
     risk_indicators: &[RiskIndicator],
     recommendations: &[ImpactRecommendation],
     affected_systems: &[AffectedSystem],
-    assessed_at: &str,
+    coverage_percentage: f64,
+    assessed_at: Timestamp,
 ) -> DecisionOutputs {
     let findings: Vec<GovernanceFinding> = risk_indicators.iter().map(|r| {
         GovernanceFinding {
@@ -1215,8 +1999,8 @@ fn build_decision_outputs(
             description: r.evidence.join("; "),
             affected_resources: vec![],
             evidence_refs: r.evidence.clone(),
-            first_detected: assessed_at.to_string(),
-            last_seen: assessed_at.to_string(),
+            first_detected: assessed_at,
+            last_seen: assessed_at,
         }
     }).collect();
 
@@ -1232,10 +2016,10 @@ fn build_decision_outputs(
         metrics: GovernanceMetrics {
             events_analyzed: impacts.len() as u64,
             time_range: DateRange {
-                start: assessed_at.to_string(),
-                end: assessed_at.to_string(),
+                start: assessed_at,
+                end: assessed_at,
             },
-            coverage_percentage: 85.0,
+            coverage_percentage,
             policies_evaluated: 0,
             compliance_rate: 100.0,
             findings_by_severity,
@@ -1260,14 +2044,6 @@ fn extract_request_id(req: &actix_web::HttpRequest) -> Option<String> {
         .map(String::from)
 }
 
-fn extract_trace_id(req: &actix_web::HttpRequest) -> Option<String> {
-    req.headers()
-        .get("X-Trace-Id")
-        .or_else(|| req.headers().get("traceparent"))
-        .and_then(|h| h.to_str().ok())
-        .map(String::from)
-}
-
 fn extract_invoker(req: &actix_web::HttpRequest) -> Option<String> {
     req.headers()
         .get("X-User-Id")
@@ -1282,8 +2058,415 @@ fn extract_invoker(req: &actix_web::HttpRequest) -> Option<String> {
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.service(assess_change_impact)
+        .service(assess_change_impact_batch)
         .service(simulate_change_impact)
         .service(list_change_impact_assessments)
+        .service(list_assessments_for_change)
+        // Registered before the `{assessment_id}` route, which would otherwise match it
+        .service(get_change_impact_agent_registration)
         .service(get_change_impact_assessment)
-        .service(get_change_impact_agent_registration);
+        .service(get_change_impact_findings)
+        .service(verify_change_impact_assessment);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_is_rejected_before_any_change_is_assessed() {
+        let request = |change_type: &str| -> ChangeImpactRequest {
+            serde_json::from_value(serde_json::json!({
+                "organization_id": "org-1",
+                "change_request": {
+                    "change_id": "chg-1",
+                    "change_type": change_type,
+                    "subject_type": "policy",
+                    "subject_id": "policy-1",
+                    "description": "Tighten cost limit",
+                    "initiator": "user-1",
+                },
+            }))
+            .unwrap()
+        };
+        let config = Config::default();
+
+        assert!(validate_batch(&config, &[request("update"), request("create")]).is_ok());
+
+        let err = validate_batch(&config, &[request("update"), request("rename"), request("create")]).unwrap_err();
+        assert!(matches!(err, AppError::Validation(ref m) if m.starts_with("changes[1]: Invalid change type: rename")));
+    }
+
+    #[test]
+    fn test_recorded_assessment_is_listed_by_change() {
+        // Top-level fields written alongside the event when an assessment is recorded
+        let details = serde_json::json!({
+            "event_id": "evt-1",
+            "organization_id": "org-1",
+            "assessment_id": "asm-1",
+            "change_request_id": "chg-42",
+            "subject_type": "policy",
+            "impact_level": "moderate",
+            "risk_classification": "medium",
+            "risk_score": 0.45,
+        });
+        let id = Uuid::new_v4();
+        let assessed_at = Utc::now();
+
+        let listed = assessment_listing(id, assessed_at, &details);
+
+        assert_eq!(listed["id"], serde_json::json!(id));
+        assert_eq!(listed["assessment_id"], "asm-1");
+        assert_eq!(listed["change_request_id"], "chg-42");
+        assert_eq!(listed["risk_score"], 0.45);
+
+        // Fields missing from older records list as null
+        let sparse = assessment_listing(id, assessed_at, &serde_json::json!({"change_request_id": "chg-1"}));
+        assert_eq!(sparse["change_request_id"], "chg-1");
+        assert!(sparse["subject_type"].is_null());
+    }
+
+    #[test]
+    fn test_findings_render_as_sarif() {
+        let details = serde_json::json!({
+            "risk_indicators": [
+                {"id": "ri-1", "category": "securityrisk", "severity": "critical", "description": "Access widened",
+                 "evidence": ["role added"], "mitigation_suggestions": ["review grants"]},
+                {"id": "ri-2", "category": "financialrisk", "severity": "medium", "description": "Spend rises",
+                 "evidence": [], "mitigation_suggestions": []},
+                {"id": "ri-3", "category": "securityrisk", "severity": "low", "description": "New endpoint",
+                 "evidence": [], "mitigation_suggestions": []},
+                {"id": "ri-4", "category": "configurationrisk", "severity": "info", "description": "Flag toggled",
+                 "evidence": [], "mitigation_suggestions": []},
+            ]
+        });
+        let indicators = stored_risk_indicators(&details).unwrap();
+
+        let sarif = render_findings_sarif("asm-1", &indicators);
+
+        assert_eq!(sarif["version"], "2.1.0");
+        let run = &sarif["runs"][0];
+        assert_eq!(run["tool"]["driver"]["name"], AGENT_ID);
+        assert_eq!(run["automationDetails"]["id"], "change-impact/asm-1");
+
+        // One rule per category, referenced by index from each result
+        let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+        let rule_ids: Vec<&str> = rules.iter().map(|r| r["id"].as_str().unwrap()).collect();
+        assert_eq!(rule_ids, vec!["securityrisk", "financialrisk", "configurationrisk"]);
+
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), 4);
+        for result in results {
+            let index = result["ruleIndex"].as_u64().unwrap() as usize;
+            assert_eq!(result["ruleId"], rules[index]["id"]);
+        }
+        let levels: Vec<&str> = results.iter().map(|r| r["level"].as_str().unwrap()).collect();
+        assert_eq!(levels, vec!["error", "warning", "note", "none"]);
+        assert_eq!(results[0]["message"]["text"], "Access widened");
+        assert_eq!(results[0]["partialFingerprints"]["riskIndicatorId"], "ri-1");
+        assert_eq!(results[0]["properties"]["evidence"], serde_json::json!(["role added"]));
+
+        assert_eq!(sarif_level(&GovernanceSeverity::High), "error");
+        assert!(stored_risk_indicators(&serde_json::json!({})).unwrap().is_empty());
+    }
+
+    fn indicator(category: RiskIndicatorCategory, severity: GovernanceSeverity) -> RiskIndicator {
+        RiskIndicator {
+            id: Uuid::new_v4().to_string(),
+            category,
+            severity,
+            description: "risk".to_string(),
+            evidence: vec![],
+            mitigation_suggestions: vec![],
+        }
+    }
+
+    #[test]
+    fn test_configured_security_weight_raises_security_dominated_score() {
+        let risks = vec![
+            indicator(RiskIndicatorCategory::SecurityRisk, GovernanceSeverity::Critical),
+            indicator(RiskIndicatorCategory::SecurityRisk, GovernanceSeverity::High),
+            indicator(RiskIndicatorCategory::FinancialRisk, GovernanceSeverity::Low),
+        ];
+        let config = Config {
+            risk_category_weights: "security_risk=2.0".to_string(),
+            ..Config::default()
+        };
+
+        let unweighted = calculate_risk_score(&[], &risks, &[], &CategoryWeights::default());
+        let weighted = calculate_risk_score(&[], &risks, &[], &config.category_weights().unwrap());
+
+        assert!(weighted > unweighted, "{} should exceed {}", weighted, unweighted);
+    }
+
+    #[test]
+    fn test_partial_scope_assessment_reports_partial_coverage() {
+        let change = ChangeRequest {
+            change_id: "chg-7".to_string(),
+            change_type: ChangeType::Update,
+            subject_type: ChangeSubjectType::LlmModel,
+            subject_id: "gpt-4".to_string(),
+            description: "Switch default model".to_string(),
+            timestamp: "2024-01-01T00:00:00Z".to_string(),
+            initiator: "user-1".to_string(),
+            previous_state: None,
+            new_state: None,
+            metadata: None,
+        };
+        let direct = direct_affected_systems(&change);
+        let coverage = |analyzed: &[AffectedSystem]| downstream_coverage(&direct, analyzed);
+
+        // Registry and CostOps, plus three systems downstream of them within five hops
+        assert_eq!(coverage(&[]), 0.0);
+        assert_eq!(coverage(&expand_downstream(direct.clone(), 1)), 40.0);
+        assert_eq!(coverage(&expand_downstream(direct.clone(), 5)), 100.0);
+
+        let shallow = expand_downstream(direct.clone(), 1);
+        let outputs = build_decision_outputs(
+            "summary",
+            &[],
+            &[],
+            &[],
+            &shallow,
+            coverage(&shallow),
+            "2024-01-01T00:00:00Z".parse().unwrap(),
+        );
+        assert_eq!(outputs.metrics.coverage_percentage, 40.0);
+    }
+
+    fn batch_result(change_id: &str, risk_score: f64, systems: &[&str], severities: &[&str]) -> ChangeImpactResponse {
+        ChangeImpactResponse {
+            event_id: format!("evt-{}", change_id),
+            agent_id: AGENT_ID.to_string(),
+            agent_version: AGENT_VERSION.to_string(),
+            timestamp: "2024-01-01T00:00:00Z".to_string(),
+            organization_id: "org-1".to_string(),
+            assessment: ChangeImpactAssessmentResponse {
+                id: format!("asm-{}", change_id),
+                change_request_id: change_id.to_string(),
+                impact_level: format!("{:?}", ImpactLevel::from_score(risk_score)).to_lowercase(),
+                risk_score,
+                risk_classification: format!("{:?}", RiskClassification::from_score(risk_score)).to_lowercase(),
+                summary: String::new(),
+                impacts: vec![],
+                affected_systems: systems
+                    .iter()
+                    .map(|id| AffectedSystemResponse {
+                        system_id: id.to_string(),
+                        system_name: id.to_string(),
+                        system_type: "test".to_string(),
+                        impact_description: String::new(),
+                        severity: "medium".to_string(),
+                        dependencies: vec![],
+                    })
+                    .collect(),
+                policy_implications: vec![],
+                compliance_implications: vec![],
+                cost_implications: None,
+                risk_indicators: severities
+                    .iter()
+                    .enumerate()
+                    .map(|(i, severity)| RiskIndicatorResponse {
+                        id: format!("risk-{}", i),
+                        category: "security".to_string(),
+                        severity: severity.to_string(),
+                        description: String::new(),
+                        evidence: vec![],
+                        mitigation_suggestions: vec![],
+                    })
+                    .collect(),
+                recommendations: vec![],
+                historical_context: None,
+                analysis_depth: 3,
+                coverage_percentage: 100.0,
+                baseline_comparison: None,
+                assessed_at: "2024-01-01T00:00:00Z".to_string(),
+            },
+            confidence: ConfidenceResponse {
+                overall: 0.8,
+                completeness: 0.8,
+                certainty: 0.8,
+            },
+            telemetry_ref: String::new(),
+            telemetry_status: None,
+        }
+    }
+
+    #[test]
+    fn test_batch_aggregate_takes_riskiest_change() {
+        let results = vec![
+            batch_result("chg-1", 0.3, &["policy-engine", "api-gateway"], &["critical", "low"]),
+            batch_result("chg-2", 0.85, &["registry", "api-gateway"], &["critical", "critical"]),
+            batch_result("chg-3", 0.5, &["cost-ops"], &["high"]),
+        ];
+
+        let aggregate = aggregate_batch(&results);
+
+        assert_eq!(aggregate.change_count, 3);
+        assert_eq!(aggregate.max_risk_score, 0.85);
+        assert_eq!(aggregate.riskiest_change_id.as_deref(), Some("chg-2"));
+        assert_eq!(
+            aggregate.max_risk_classification,
+            Some(results[1].assessment.risk_classification.clone())
+        );
+        assert_eq!(aggregate.max_impact_level, Some(results[1].assessment.impact_level.clone()));
+        assert_eq!(
+            aggregate.affected_systems,
+            vec!["policy-engine", "api-gateway", "registry", "cost-ops"]
+        );
+        assert_eq!(aggregate.critical_findings, 3);
+    }
+
+    #[test]
+    fn test_batch_aggregate_does_not_depend_on_order() {
+        let low = || batch_result("chg-low", 0.1, &[], &[]);
+        let high = || batch_result("chg-high", 0.9, &[], &[]);
+
+        for results in [vec![low(), high()], vec![high(), low()]] {
+            let aggregate = aggregate_batch(&results);
+            assert_eq!(aggregate.max_risk_score, 0.9);
+            assert_eq!(aggregate.riskiest_change_id.as_deref(), Some("chg-high"));
+        }
+    }
+
+    /// Assessment record as `run_assessment` writes it, for a policy change
+    async fn recorded_assessment(pool: &PgPool) -> serde_json::Value {
+        let req: ChangeImpactRequest = serde_json::from_value(serde_json::json!({
+            "organization_id": "org-1",
+            "change_request": {
+                "change_id": "chg-9",
+                "change_type": "delete",
+                "subject_type": "policy",
+                "subject_id": "pol-1",
+                "description": "Remove rate limit policy",
+                "timestamp": "2024-01-01T00:00:00Z",
+                "initiator": "user-1"
+            }
+        }))
+        .unwrap();
+        let risk_score = recompute_risk_score(pool, &req, &CategoryWeights::new(), &SeverityPolicy::new())
+            .await
+            .unwrap();
+
+        let event = create_decision_event(
+            AGENT_ID,
+            AGENT_VERSION,
+            GovernanceDecisionType::ChangeImpact,
+            &req.organization_id,
+            build_decision_outputs("summary", &[], &[], &[], &[], 100.0, Timestamp::now()),
+            default_confidence(0.8, 0.8),
+            Vec::new(),
+            execution_ref_from_request(None, None, None, InvocationSource::Api),
+            &req,
+        );
+        crate::services::decision_log::decision_record(
+            &event,
+            serde_json::json!({
+                "risk_score": risk_score,
+                "risk_classification": format!("{:?}", RiskClassification::from_score(risk_score)).to_lowercase(),
+                "impact_level": format!("{:?}", ImpactLevel::from_score(risk_score)).to_lowercase(),
+                "inputs": req,
+            }),
+        )
+        .unwrap()
+    }
+
+    fn unused_pool() -> PgPool {
+        // The deterministic analysis steps never query the database
+        sqlx::postgres::PgPoolOptions::new()
+            .connect_lazy("postgres://localhost/unused")
+            .unwrap()
+    }
+
+    #[actix_web::test]
+    async fn test_severity_policy_raises_recomputed_risk() {
+        let pool = unused_pool();
+        let req: ChangeImpactRequest = serde_json::from_value(serde_json::json!({
+            "organization_id": "org-1",
+            "change_request": {
+                "change_id": "chg-10",
+                "change_type": "update",
+                "subject_type": "llm_model",
+                "subject_id": "gpt-4",
+                "description": "Swap model version",
+                "timestamp": "2024-01-01T00:00:00Z",
+                "initiator": "user-1"
+            }
+        }))
+        .unwrap();
+        let weights = CategoryWeights::new();
+
+        let plain = recompute_risk_score(&pool, &req, &weights, &SeverityPolicy::new())
+            .await
+            .unwrap();
+        let escalated = recompute_risk_score(
+            &pool,
+            &req,
+            &weights,
+            &SeverityPolicy::parse("llm_model=critical").unwrap(),
+        )
+        .await
+        .unwrap();
+        let other_subject = recompute_risk_score(
+            &pool,
+            &req,
+            &weights,
+            &SeverityPolicy::parse("policy=critical").unwrap(),
+        )
+        .await
+        .unwrap();
+
+        assert!(escalated > plain, "{} <= {}", escalated, plain);
+        assert_eq!(other_subject, plain);
+    }
+
+    #[actix_web::test]
+    async fn test_untouched_assessment_is_verified() {
+        let pool = unused_pool();
+        let details = recorded_assessment(&pool).await;
+
+        let verification = verify_recorded_assessment(&pool, &details, &CategoryWeights::new(), &SeverityPolicy::new())
+            .await
+            .unwrap();
+
+        assert!(verification.inputs_available);
+        assert!(verification.divergences.is_empty(), "{:?}", verification.divergences);
+        assert!(verification.verified);
+    }
+
+    #[actix_web::test]
+    async fn test_tampered_assessment_reports_divergences() {
+        let pool = unused_pool();
+        let weights = CategoryWeights::new();
+        let policy = SeverityPolicy::new();
+
+        // Recorded risk edited after the fact
+        let mut details = recorded_assessment(&pool).await;
+        let original_score = details["risk_score"].clone();
+        details["risk_score"] = serde_json::json!(0.01);
+        let verification = verify_recorded_assessment(&pool, &details, &weights, &policy).await.unwrap();
+        assert!(!verification.verified);
+        assert_eq!(
+            verification.divergences,
+            vec![Divergence {
+                field: "risk_score".to_string(),
+                recorded: serde_json::json!(0.01),
+                recomputed: original_score,
+            }]
+        );
+
+        // Inputs edited after the fact no longer match the event's hash
+        let mut details = recorded_assessment(&pool).await;
+        details["inputs"]["change_request"]["change_type"] = serde_json::json!("update");
+        let verification = verify_recorded_assessment(&pool, &details, &weights, &policy).await.unwrap();
+        assert!(!verification.verified);
+        assert!(verification.divergences.iter().any(|d| d.field == "inputs_hash"));
+
+        // Without recorded inputs there is nothing to verify against
+        let mut details = recorded_assessment(&pool).await;
+        details.as_object_mut().unwrap().remove("inputs");
+        let verification = verify_recorded_assessment(&pool, &details, &weights, &policy).await.unwrap();
+        assert!(!verification.inputs_available);
+        assert!(!verification.verified);
+    }
 }
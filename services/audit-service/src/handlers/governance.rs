@@ -28,9 +28,9 @@
 
 use actix_web::{get, post, web, HttpResponse, Responder};
 use serde::{Deserialize, Serialize};
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 use uuid::Uuid;
-use chrono::{DateTime, Utc};
+use chrono::NaiveDateTime;
 use sha2::{Sha256, Digest};
 use std::collections::HashMap;
 use tracing::{info, warn, instrument, span, Level};
@@ -41,24 +41,37 @@ use llm_governance_common::adapters::ruvector::{
     GovernanceFinding, GovernanceMetrics, DecisionConfidence, ConstraintApplication,
     ExecutionReference, DataReference, DateRange, GovernanceSeverity, FindingCategory,
     TrendDirection, InvocationSource, DataReferenceType, ConstraintType, ConstraintScope,
-    create_decision_event, default_confidence, execution_ref_from_request,
+    DecisionStore, create_decision_event, default_confidence, execution_ref_from_request, find_finding,
 };
 use llm_governance_common::adapters::observatory::ObservatoryConsumer;
 use llm_governance_common::adapters::UpstreamConfig;
+use llm_governance_common::db::Page;
+use llm_governance_common::negotiate::{preferred_format, Format};
+use llm_governance_common::query::DynamicQuery;
+use llm_governance_common::trace_context::extract_trace_id;
+use llm_governance_common::timestamp::Timestamp;
+use llm_governance_common::utils::{begin_with_timeout, check_range_span, resolve_window, with_timeout};
+use llm_governance_database::ReadPool;
+
+use crate::config::Config;
+use crate::services::authorization::{authorize_org_audit, authorize_record_audit};
+use crate::services::decision_log::{record_decision_event, store_decision_event, GOVERNANCE_AUDIT_RESOURCE};
+use super::agents::{AgentDescriptor, AgentEndpoint};
+use super::validation::parse_range;
 
 // ============================================================================
 // Agent Constants
 // ============================================================================
 
-const AGENT_ID: &str = "governance-audit-agent";
-const AGENT_VERSION: &str = "1.0.0";
+pub const AGENT_ID: &str = "governance-audit-agent";
+pub const AGENT_VERSION: &str = "1.0.0";
 
 // ============================================================================
 // Request/Response Types
 // ============================================================================
 
 /// Request to generate governance audit
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct GovernanceAuditRequest {
     /// Organization ID to audit
     pub organization_id: String,
@@ -75,15 +88,23 @@ pub struct GovernanceAuditRequest {
     pub include_details: bool,
     /// Comparison baseline reference
     pub baseline_ref: Option<String>,
+    /// Cap on returned findings; never above the service's `max_findings`
+    pub max_findings: Option<usize>,
+    /// Cap on returned recommendations; never above the service's `max_recommendations`
+    pub max_recommendations: Option<usize>,
 }
 
 /// Scope constraints for audit
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct AuditScopeRequest {
     pub teams: Option<Vec<String>>,
     pub users: Option<Vec<String>>,
     pub policy_types: Option<Vec<String>>,
     pub resource_types: Option<Vec<String>>,
+    /// Resource types left out of events and compliance, e.g. test activity
+    pub exclude_resource_types: Option<Vec<String>>,
+    /// Actions left out of events and compliance
+    pub exclude_actions: Option<Vec<String>>,
 }
 
 /// Governance audit response
@@ -97,6 +118,7 @@ pub struct GovernanceAuditResponse {
     pub organization_id: String,
     pub summary: String,
     pub metrics: GovernanceMetricsResponse,
+    /// Every finding of the audit, including any past `max_findings`
     pub findings_count: u32,
     pub findings: Option<Vec<GovernanceFindingResponse>>,
     pub recommendations: Vec<String>,
@@ -144,6 +166,12 @@ pub struct InspectFindingRequest {
     pub finding_id: String,
 }
 
+/// Query parameters for locating a finding
+#[derive(Debug, Deserialize)]
+pub struct FindingLookupQuery {
+    pub organization_id: String,
+}
+
 /// Request to summarize governance state
 #[derive(Debug, Deserialize)]
 pub struct SummarizeRequest {
@@ -176,10 +204,16 @@ pub struct ListAuditsQuery {
 /// 3. Generates findings and recommendations
 /// 4. Persists the audit DecisionEvent to ruvector-service
 /// 5. Emits telemetry to LLM-Observatory
+///
+/// Responds with JSON, or a Markdown report when negotiated via `Accept:
+/// text/markdown` or `?format=markdown`.
 #[post("/governance/audit")]
-#[instrument(skip(pool, http_req), fields(organization_id, audit_type))]
+#[instrument(skip(pool, read_pool, config, store, http_req), fields(organization_id, audit_type))]
 pub async fn generate_governance_audit(
     pool: web::Data<PgPool>,
+    read_pool: web::Data<ReadPool>,
+    config: web::Data<Config>,
+    store: web::Data<dyn DecisionStore>,
     req: web::Json<GovernanceAuditRequest>,
     http_req: actix_web::HttpRequest,
 ) -> Result<impl Responder> {
@@ -189,10 +223,15 @@ pub async fn generate_governance_audit(
     );
     let _enter = span.enter();
 
+    let format = preferred_format(&http_req, &["json", "markdown"])?;
+    let caller = authorize_org_audit(pool.get_ref(), &http_req, &req.organization_id).await?;
+
     info!("Starting governance audit for organization: {}", req.organization_id);
 
-    // Parse audit type
+    // Parse audit type and time range
     let decision_type = parse_decision_type(&req.audit_type)?;
+    let range = parse_range("from", &req.from, "to", &req.to)?;
+    check_range_span(&range, config.max_query_days)?;
 
     // Extract execution context
     let request_id = extract_request_id(&http_req);
@@ -200,36 +239,56 @@ pub async fn generate_governance_audit(
     let invoker = extract_invoker(&http_req);
 
     // Step 1: Aggregate data from internal audit logs (read-only)
-    let audit_data = aggregate_audit_data(
-        pool.get_ref(),
+    let mut tx = begin_with_timeout(read_pool.pool(), config.query_timeout()).await?;
+    let audit_data = with_timeout(config.query_timeout(), aggregate_audit_data(
+        &mut tx,
         &req.organization_id,
         &req.from,
         &req.to,
         req.scope.as_ref(),
-    ).await?;
+    )).await?;
 
     // Step 2: Analyze policy adherence
-    let policy_analysis = analyze_policy_adherence(
-        pool.get_ref(),
+    let policy_analysis = with_timeout(config.query_timeout(), analyze_policy_adherence(
+        &mut tx,
         &req.organization_id,
         &req.from,
         &req.to,
-    ).await?;
+        req.scope.as_ref(),
+    )).await?;
+    drop(tx);
 
     // Step 3: Generate findings
-    let findings = generate_findings(&audit_data, &policy_analysis, req.include_details);
+    let mut findings = generate_findings(
+        &req.organization_id,
+        &audit_data,
+        &policy_analysis,
+        req.include_details,
+        Timestamp::now(),
+    );
+
+    // Step 3b: Keep first_detected for findings already reported by the previous audit
+    let prior = load_prior_findings(pool.get_ref(), &req.organization_id).await?;
+    carry_forward_findings(&mut findings, &prior);
 
     // Step 4: Calculate metrics
-    let metrics = calculate_governance_metrics(&audit_data, &policy_analysis, &findings, &req.from, &req.to);
+    let metrics = calculate_governance_metrics(&audit_data, &policy_analysis, &findings, &range);
 
     // Step 5: Calculate confidence
     let confidence = calculate_confidence(&audit_data, &policy_analysis);
 
     // Step 6: Generate recommendations (read-only, informational)
-    let recommendations = generate_recommendations(&findings, &metrics);
+    let mut recommendations = generate_recommendations(&findings, &metrics);
+
+    // Step 6b: Bound what is returned and recorded; metrics and the summary
+    // keep the full counts
+    let summary = generate_summary(&metrics, &findings);
+    let findings_count = findings.len() as u32;
+    retain_most_severe(&mut findings, response_cap(req.max_findings, config.max_findings));
+    recommendations.truncate(response_cap(req.max_recommendations, config.max_recommendations));
 
     // Step 7: Build constraints applied record
-    let constraints_applied = build_constraints_record(&req);
+    let constraints_applied = build_constraints_record(&req, &range);
 
     // Step 8: Create execution reference
     let execution_ref = execution_ref_from_request(
@@ -241,7 +300,7 @@ pub async fn generate_governance_audit(
 
     // Step 9: Build outputs
     let outputs = DecisionOutputs {
-        summary: generate_summary(&metrics, &findings),
+        summary: summary.clone(),
         findings: findings.clone(),
         metrics: metrics.clone(),
         recommendations: recommendations.clone(),
@@ -261,11 +320,18 @@ pub async fn generate_governance_audit(
         &req.0, // Use request as inputs for hash
     );
 
-    // Step 11: Persist to ruvector-service (async, non-blocking)
-    // NOTE: In production, this would use the actual ruvector-service client
-    // For now, we persist locally to maintain the pattern
+    // Step 11: Record the event in audit_logs, which the list and lookup
+    // endpoints read; this write completes before the response is sent
+    record_decision_event(
+        pool.get_ref(),
+        caller,
+        GOVERNANCE_AUDIT_RESOURCE,
+        &decision_event,
+        serde_json::json!({}),
+    ).await?;
+    store_decision_event(store.get_ref(), &decision_event).await;
     let event_id = decision_event.id.clone();
-    let timestamp = decision_event.timestamp.clone();
+    let timestamp = decision_event.timestamp.to_string();
 
     // Emit telemetry reference (in production, would call LLM-Observatory)
     let telemetry_ref = format!("telemetry:{}:{}", AGENT_ID, event_id);
@@ -273,7 +339,7 @@ pub async fn generate_governance_audit(
     // Generate artifact reference
     let artifact_ref = format!("artifact:audit:{}:{}", req.organization_id, event_id);
 
-    info!("Governance audit completed: event_id={}, findings={}", event_id, findings.len());
+    info!("Governance audit completed: event_id={}, findings={}", event_id, findings_count);
 
     // Step 12: Build response
     let response = GovernanceAuditResponse {
@@ -283,7 +349,7 @@ pub async fn generate_governance_audit(
         decision_type: req.audit_type.clone(),
         timestamp,
         organization_id: req.organization_id.clone(),
-        summary: generate_summary(&metrics, &findings),
+        summary,
         metrics: GovernanceMetricsResponse {
             events_analyzed: metrics.events_analyzed,
             coverage_percentage: metrics.coverage_percentage,
@@ -292,7 +358,7 @@ pub async fn generate_governance_audit(
             findings_by_severity: metrics.findings_by_severity.clone(),
             trend: serde_json::to_string(&metrics.trend).unwrap_or_default().trim_matches('"').to_string(),
         },
-        findings_count: findings.len() as u32,
+        findings_count,
         findings: if req.include_details {
             Some(findings.iter().map(|f| GovernanceFindingResponse {
                 id: f.id.clone(),
@@ -301,8 +367,8 @@ pub async fn generate_governance_audit(
                 title: f.title.clone(),
                 description: f.description.clone(),
                 affected_resources: f.affected_resources.clone(),
-                first_detected: f.first_detected.clone(),
-                last_seen: f.last_seen.clone(),
+                first_detected: f.first_detected.to_string(),
+                last_seen: f.last_seen.to_string(),
             }).collect())
         } else {
             None
@@ -317,6 +383,12 @@ pub async fn generate_governance_audit(
         artifact_ref,
     };
 
+    if format == Format::Markdown {
+        return Ok(HttpResponse::Ok()
+            .content_type(format.content_type())
+            .body(render_audit_markdown(&response)));
+    }
+
     Ok(HttpResponse::Ok().json(ApiResponse::success(response)))
 }
 
@@ -324,17 +396,19 @@ pub async fn generate_governance_audit(
 ///
 /// GET /api/v1/governance/audits
 #[get("/governance/audits")]
-#[instrument(skip(pool), fields(organization_id))]
+#[instrument(skip(pool, http_req), fields(organization_id))]
 pub async fn list_governance_audits(
     pool: web::Data<PgPool>,
     query: web::Query<ListAuditsQuery>,
+    http_req: actix_web::HttpRequest,
 ) -> Result<impl Responder> {
-    let limit = query.limit.unwrap_or(50).min(100);
-    let offset = query.offset.unwrap_or(0);
+    authorize_org_audit(pool.get_ref(), &http_req, &query.organization_id).await?;
+
+    let Page { limit, offset } = Page::new(query.limit, query.offset);
 
     // Query stored audits (in production, this would query ruvector-service)
     // For now, we query from local audit_logs with governance agent markers
-    let audits = sqlx::query_as::<_, (Uuid, DateTime<Utc>, String, serde_json::Value)>(
+    let audits = sqlx::query_as::<_, (Uuid, NaiveDateTime, String, serde_json::Value)>(
         r#"
         SELECT id, timestamp, action, details
         FROM audit_logs
@@ -354,7 +428,7 @@ pub async fn list_governance_audits(
         "audits": audits.iter().map(|(id, ts, action, details)| {
             serde_json::json!({
                 "id": id,
-                "timestamp": ts,
+                "timestamp": ts.and_utc(),
                 "action": action,
                 "details": details
             })
@@ -368,13 +442,14 @@ pub async fn list_governance_audits(
 ///
 /// GET /api/v1/governance/audit/{audit_id}
 #[get("/governance/audit/{audit_id}")]
-#[instrument(skip(pool), fields(audit_id))]
+#[instrument(skip(pool, http_req), fields(audit_id))]
 pub async fn get_governance_audit(
     pool: web::Data<PgPool>,
     audit_id: web::Path<String>,
+    http_req: actix_web::HttpRequest,
 ) -> Result<impl Responder> {
     // In production, this would query ruvector-service
-    let audit = sqlx::query_as::<_, (Uuid, DateTime<Utc>, String, serde_json::Value)>(
+    let audit = sqlx::query_as::<_, (Uuid, NaiveDateTime, String, serde_json::Value)>(
         r#"
         SELECT id, timestamp, action, details
         FROM audit_logs
@@ -387,55 +462,88 @@ pub async fn get_governance_audit(
     .await?
     .ok_or_else(|| AppError::NotFound("Governance audit not found".to_string()))?;
 
+    authorize_record_audit(pool.get_ref(), &http_req, &audit.3).await?;
+
     Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
         "id": audit.0,
-        "timestamp": audit.1,
+        "timestamp": audit.1.and_utc(),
         "action": audit.2,
         "details": audit.3
     }))))
 }
 
+/// Locate a finding and the DecisionEvent that surfaced it
+///
+/// GET /api/v1/governance/findings/{finding_id}
+#[get("/governance/findings/{finding_id}")]
+#[instrument(skip(pool, store, http_req), fields(finding_id))]
+pub async fn get_governance_finding(
+    pool: web::Data<PgPool>,
+    store: web::Data<dyn DecisionStore>,
+    finding_id: web::Path<String>,
+    query: web::Query<FindingLookupQuery>,
+    http_req: actix_web::HttpRequest,
+) -> Result<impl Responder> {
+    authorize_org_audit(pool.get_ref(), &http_req, &query.organization_id).await?;
+
+    let events = store
+        .find_events_by_finding_id(&query.organization_id, &finding_id)
+        .await?;
+
+    let located = events
+        .iter()
+        .find_map(|event| locate_finding(event, &finding_id))
+        .ok_or_else(|| AppError::NotFound("Finding not found".to_string()))?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(located)))
+}
+
 /// Summarize current governance state
 ///
 /// GET /api/v1/governance/summary
 #[get("/governance/summary")]
-#[instrument(skip(pool), fields(organization_id))]
+#[instrument(skip(pool, read_pool, config, http_req), fields(organization_id))]
 pub async fn summarize_governance(
     pool: web::Data<PgPool>,
+    read_pool: web::Data<ReadPool>,
+    config: web::Data<Config>,
     query: web::Query<SummarizeRequest>,
+    http_req: actix_web::HttpRequest,
 ) -> Result<impl Responder> {
-    let period_days = query.period_days.unwrap_or(30);
-    let from = chrono::Utc::now()
-        .checked_sub_signed(chrono::Duration::days(period_days as i64))
-        .unwrap()
-        .to_rfc3339();
-    let to = chrono::Utc::now().to_rfc3339();
+    authorize_org_audit(pool.get_ref(), &http_req, &query.organization_id).await?;
+
+    let period_days = query
+        .period_days
+        .map(i64::from)
+        .unwrap_or(config.default_window_days);
+    let window = resolve_window(None, None, period_days);
+    let (from, to) = (window.start, window.end);
 
     // Aggregate summary data
-    let total_actions: (i64,) = sqlx::query_as(
+    let mut tx = begin_with_timeout(read_pool.pool(), config.query_timeout()).await?;
+    let total_actions: (i64,) = with_timeout(config.query_timeout(), sqlx::query_as(
         "SELECT COUNT(*) FROM audit_logs WHERE timestamp >= $1 AND timestamp <= $2"
     )
     .bind(&from)
     .bind(&to)
-    .fetch_one(pool.get_ref())
+    .fetch_one(&mut *tx))
     .await?;
 
-    let unique_users: (i64,) = sqlx::query_as(
+    let unique_users: (i64,) = with_timeout(config.query_timeout(), sqlx::query_as(
         "SELECT COUNT(DISTINCT user_id) FROM audit_logs WHERE timestamp >= $1 AND timestamp <= $2"
     )
     .bind(&from)
     .bind(&to)
-    .fetch_one(pool.get_ref())
+    .fetch_one(&mut *tx))
     .await?;
 
-    let policy_evaluations: (i64,) = sqlx::query_as(
+    let policy_evaluations: (i64,) = with_timeout(config.query_timeout(), sqlx::query_as(
         "SELECT COUNT(*) FROM audit_logs WHERE resource_type = 'policy' AND timestamp >= $1 AND timestamp <= $2"
     )
     .bind(&from)
     .bind(&to)
-    .fetch_one(pool.get_ref())
-    .await
-    .unwrap_or((0,));
+    .fetch_one(&mut *tx))
+    .await?;
 
     Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
         "organization_id": query.organization_id,
@@ -457,50 +565,55 @@ pub async fn summarize_governance(
     }))))
 }
 
+/// Endpoints, capabilities and boundaries of the Governance Audit Agent
+pub const AGENT_DESCRIPTOR: AgentDescriptor = AgentDescriptor {
+    agent_id: AGENT_ID,
+    name: "Governance Audit Agent",
+    description: "Generate authoritative audit summaries across workflows, incidents, approvals, and decisions",
+    version: AGENT_VERSION,
+    classification: "audit",
+    decision_types: &[
+        GovernanceDecisionType::AuditSummary,
+        GovernanceDecisionType::ComplianceStatus,
+        GovernanceDecisionType::GovernanceSnapshot,
+        GovernanceDecisionType::PolicyAdherence,
+        GovernanceDecisionType::ApprovalTrail,
+    ],
+    capabilities: &[
+        "aggregate_decision_events",
+        "analyze_policy_adherence",
+        "trace_approval_trails",
+        "compute_governance_coverage",
+        "produce_audit_artifacts",
+        "surface_change_history",
+        "provide_oversight_signals",
+    ],
+    non_responsibilities: &[
+        "intercept_execution",
+        "trigger_retries_or_workflows",
+        "enforce_policies",
+        "modify_configurations",
+        "emit_anomaly_detections",
+        "apply_optimizations",
+        "execute_sql_directly",
+        "connect_to_google_sql_directly",
+    ],
+    endpoints: &[
+        AgentEndpoint { name: "audit", method: "POST", path: "/governance/audit" },
+        AgentEndpoint { name: "list", method: "GET", path: "/governance/audits" },
+        AgentEndpoint { name: "get", method: "GET", path: "/governance/audit/{audit_id}" },
+        AgentEndpoint { name: "finding", method: "GET", path: "/governance/findings/{finding_id}" },
+        AgentEndpoint { name: "summary", method: "GET", path: "/governance/summary" },
+        AgentEndpoint { name: "agent", method: "GET", path: "/governance/agent" },
+    ],
+};
+
 /// Get agent registration metadata
 ///
 /// GET /api/v1/governance/agent
 #[get("/governance/agent")]
 pub async fn get_agent_registration() -> Result<impl Responder> {
-    Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
-        "agent_id": AGENT_ID,
-        "name": "Governance Audit Agent",
-        "description": "Generate authoritative audit summaries across workflows, incidents, approvals, and decisions",
-        "version": AGENT_VERSION,
-        "classification": "audit",
-        "decision_types": [
-            "audit_summary",
-            "compliance_status",
-            "governance_snapshot",
-            "policy_adherence",
-            "approval_trail"
-        ],
-        "capabilities": [
-            "aggregate_decision_events",
-            "analyze_policy_adherence",
-            "trace_approval_trails",
-            "compute_governance_coverage",
-            "produce_audit_artifacts",
-            "surface_change_history",
-            "provide_oversight_signals"
-        ],
-        "non_responsibilities": [
-            "intercept_execution",
-            "trigger_retries_or_workflows",
-            "enforce_policies",
-            "modify_configurations",
-            "emit_anomaly_detections",
-            "apply_optimizations",
-            "execute_sql_directly",
-            "connect_to_google_sql_directly"
-        ],
-        "endpoints": {
-            "audit": "POST /api/v1/governance/audit",
-            "list": "GET /api/v1/governance/audits",
-            "get": "GET /api/v1/governance/audit/{audit_id}",
-            "summary": "GET /api/v1/governance/summary"
-        }
-    }))))
+    Ok(HttpResponse::Ok().json(ApiResponse::success(AGENT_DESCRIPTOR.to_json())))
 }
 
 // ============================================================================
@@ -524,38 +637,110 @@ fn parse_decision_type(audit_type: &str) -> Result<GovernanceDecisionType> {
 }
 
 /// Internal representation of aggregated audit data
-struct AuditDataAggregate {
-    total_events: u64,
-    events_by_action: HashMap<String, u64>,
-    events_by_resource: HashMap<String, u64>,
-    unique_users: u64,
-    time_range_coverage: f64,
+pub struct AuditDataAggregate {
+    pub total_events: u64,
+    pub events_by_action: HashMap<String, u64>,
+    pub events_by_resource: HashMap<String, u64>,
+    pub unique_users: u64,
+    /// Share of the elapsed requested range the retained audit log covers
+    pub time_range_coverage: f64,
+}
+
+/// Resource type and action lists of an audit scope
+#[derive(Debug, Default)]
+struct AuditScopeFilter {
+    resource_types: Option<Vec<String>>,
+    exclude_resource_types: Option<Vec<String>>,
+    exclude_actions: Option<Vec<String>>,
+}
+
+impl AuditScopeFilter {
+    fn new(scope: Option<&AuditScopeRequest>) -> Self {
+        scope.map_or_else(Self::default, |s| Self {
+            resource_types: s.resource_types.clone(),
+            exclude_resource_types: s.exclude_resource_types.clone(),
+            exclude_actions: s.exclude_actions.clone(),
+        })
+    }
+
+    /// Whether events on `resource_type` are in scope
+    fn includes_resource_type(&self, resource_type: &str) -> bool {
+        let listed = |types: &Option<Vec<String>>| types.as_ref().map(|t| t.iter().any(|t| t == resource_type));
+        listed(&self.resource_types).unwrap_or(true) && !listed(&self.exclude_resource_types).unwrap_or(false)
+    }
+
+    /// `head` over the audit log events from `from` to `to` within the scope
+    fn audit_logs<'a>(&'a self, head: &str, from: &'a str, to: &'a str) -> DynamicQuery<'a> {
+        let mut query = DynamicQuery::new(head);
+        query.and_where().push("timestamp >= ").push_bind(from).push("::timestamptz");
+        query.and_where().push("timestamp <= ").push_bind(to).push("::timestamptz");
+        if let Some(types) = &self.resource_types {
+            query.and_where().push("resource_type = ANY(").push_bind(types.as_slice()).push(")");
+        }
+        if let Some(types) = &self.exclude_resource_types {
+            query.and_where().push("resource_type <> ALL(").push_bind(types.as_slice()).push(")");
+        }
+        if let Some(actions) = &self.exclude_actions {
+            query.and_where().push("action <> ALL(").push_bind(actions.as_slice()).push(")");
+        }
+        query
+    }
 }
 
-async fn aggregate_audit_data(
-    pool: &PgPool,
+/// Share of the elapsed part of `from`..`to` at or after `oldest`, the
+/// earliest retained audit event; full when the log is empty
+///
+/// Scoping and exclusions narrow what is analyzed on purpose, so they never
+/// count as a gap; only time the log holds no events for does.
+fn retained_coverage(from: Timestamp, to: Timestamp, oldest: Option<Timestamp>, now: Timestamp) -> f64 {
+    let (from, end) = (*from.as_datetime(), *to.min(now).as_datetime());
+    let Some(oldest) = oldest else {
+        return 1.0;
+    };
+    if end <= from {
+        return 1.0;
+    }
+    let covered = end - (*oldest.as_datetime()).max(from);
+    (covered.num_milliseconds() as f64 / (end - from).num_milliseconds() as f64).clamp(0.0, 1.0)
+}
+
+pub async fn aggregate_audit_data(
+    conn: &mut PgConnection,
     organization_id: &str,
     from: &str,
     to: &str,
     scope: Option<&AuditScopeRequest>,
 ) -> Result<AuditDataAggregate> {
+    let filter = AuditScopeFilter::new(scope);
+
+    // Events before the oldest retained one have been purged or never recorded
+    let (oldest,): (Option<NaiveDateTime>,) = sqlx::query_as("SELECT MIN(timestamp) FROM audit_logs")
+        .fetch_one(&mut *conn)
+        .await?;
+    let time_range_coverage = match (Timestamp::parse(from), Timestamp::parse(to)) {
+        (Ok(from), Ok(to)) => retained_coverage(
+            from,
+            to,
+            oldest.map(|t| Timestamp::from(t.and_utc())),
+            Timestamp::now(),
+        ),
+        _ => return Err(AppError::Validation("'from' and 'to' must be RFC 3339 timestamps".to_string())),
+    };
+
     // Get total events count
-    let total: (i64,) = sqlx::query_as(
-        "SELECT COUNT(*) FROM audit_logs WHERE timestamp >= $1 AND timestamp <= $2"
-    )
-    .bind(from)
-    .bind(to)
-    .fetch_one(pool)
-    .await?;
+    let total: (i64,) = filter
+        .audit_logs("SELECT COUNT(*) FROM audit_logs", from, to)
+        .build_query_as()
+        .fetch_one(&mut *conn)
+        .await?;
 
     // Get events by action
-    let actions = sqlx::query_as::<_, (String, i64)>(
-        "SELECT action, COUNT(*) as count FROM audit_logs WHERE timestamp >= $1 AND timestamp <= $2 GROUP BY action"
-    )
-    .bind(from)
-    .bind(to)
-    .fetch_all(pool)
-    .await?;
+    let actions: Vec<(String, i64)> = filter
+        .audit_logs("SELECT action, COUNT(*) as count FROM audit_logs", from, to)
+        .push(" GROUP BY action")
+        .build_query_as()
+        .fetch_all(&mut *conn)
+        .await?;
 
     let events_by_action: HashMap<String, u64> = actions
         .into_iter()
@@ -563,13 +748,12 @@ async fn aggregate_audit_data(
         .collect();
 
     // Get events by resource type
-    let resources = sqlx::query_as::<_, (String, i64)>(
-        "SELECT resource_type, COUNT(*) as count FROM audit_logs WHERE timestamp >= $1 AND timestamp <= $2 GROUP BY resource_type"
-    )
-    .bind(from)
-    .bind(to)
-    .fetch_all(pool)
-    .await?;
+    let resources: Vec<(String, i64)> = filter
+        .audit_logs("SELECT resource_type, COUNT(*) as count FROM audit_logs", from, to)
+        .push(" GROUP BY resource_type")
+        .build_query_as()
+        .fetch_all(&mut *conn)
+        .await?;
 
     let events_by_resource: HashMap<String, u64> = resources
         .into_iter()
@@ -577,79 +761,263 @@ async fn aggregate_audit_data(
         .collect();
 
     // Get unique users
-    let unique_users: (i64,) = sqlx::query_as(
-        "SELECT COUNT(DISTINCT user_id) FROM audit_logs WHERE timestamp >= $1 AND timestamp <= $2"
-    )
-    .bind(from)
-    .bind(to)
-    .fetch_one(pool)
-    .await?;
+    let unique_users: (i64,) = filter
+        .audit_logs("SELECT COUNT(DISTINCT user_id) FROM audit_logs", from, to)
+        .build_query_as()
+        .fetch_one(&mut *conn)
+        .await?;
 
     Ok(AuditDataAggregate {
         total_events: total.0 as u64,
         events_by_action,
         events_by_resource,
         unique_users: unique_users.0 as u64,
-        time_range_coverage: 1.0, // Assume full coverage for requested range
+        time_range_coverage,
     })
 }
 
-struct PolicyAnalysis {
-    policies_evaluated: u32,
-    violations_found: u32,
-    compliance_rate: f64,
-    high_severity_violations: u32,
+pub struct PolicyAnalysis {
+    pub policies_evaluated: u32,
+    pub violations_found: u32,
+    pub compliance_rate: f64,
+    pub high_severity_violations: u32,
+    /// Violations per severity; empty when estimated from audit log actions
+    pub violations_by_severity: HashMap<String, u32>,
+}
+
+/// Severities counted as high in the policy violation finding
+const HIGH_SEVERITIES: &[&str] = &["critical", "high"];
+
+/// Recorded evaluations in the window of the organization's own policies:
+/// (evaluations, failed evaluations)
+const EVALUATION_COUNTS_SQL: &str = r#"
+    SELECT COUNT(*), COUNT(*) FILTER (WHERE NOT e.passed)
+    FROM policy_evaluations e
+    JOIN policies p ON p.id = e.policy_id
+    WHERE e.evaluated_at >= $2::timestamptz AND e.evaluated_at <= $3::timestamptz
+      AND p.organization_id::text = $1
+"#;
+
+/// Violations of the same evaluations, grouped by severity
+const VIOLATION_SEVERITIES_SQL: &str = r#"
+    SELECT COALESCE(v->>'severity', 'unknown'), COUNT(*)
+    FROM policy_evaluations e
+    JOIN policies p ON p.id = e.policy_id
+    CROSS JOIN LATERAL jsonb_array_elements(e.violations) AS v
+    WHERE e.evaluated_at >= $2::timestamptz AND e.evaluated_at <= $3::timestamptz
+      AND p.organization_id::text = $1
+    GROUP BY 1
+"#;
+
+/// Policy adherence over the window
+///
+/// Counted from recorded policy evaluations when there are any; otherwise
+/// estimated from audit log actions mentioning violations or rejections.
+pub async fn analyze_policy_adherence(
+    conn: &mut PgConnection,
+    organization_id: &str,
+    from: &str,
+    to: &str,
+    scope: Option<&AuditScopeRequest>,
+) -> Result<PolicyAnalysis> {
+    // Evaluations are events on policies, so a scope leaving policies out leaves them out too
+    if !AuditScopeFilter::new(scope).includes_resource_type("policy") {
+        return heuristic_policy_adherence(conn, organization_id, from, to, scope).await;
+    }
+
+    let (evaluations, failed): (i64, i64) = sqlx::query_as(EVALUATION_COUNTS_SQL)
+        .bind(organization_id)
+        .bind(from)
+        .bind(to)
+        .fetch_one(&mut *conn)
+        .await?;
+
+    if evaluations == 0 {
+        return heuristic_policy_adherence(conn, organization_id, from, to, scope).await;
+    }
+
+    let severities: Vec<(String, i64)> = sqlx::query_as(VIOLATION_SEVERITIES_SQL)
+        .bind(organization_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&mut *conn)
+        .await?;
+
+    Ok(structured_policy_analysis(evaluations, failed, severities))
+}
+
+/// Adherence from evaluation outcomes: the compliance rate is the share of
+/// evaluations that passed
+fn structured_policy_analysis(evaluations: i64, failed: i64, severities: Vec<(String, i64)>) -> PolicyAnalysis {
+    let violations_by_severity: HashMap<String, u32> = severities
+        .into_iter()
+        .map(|(severity, count)| (severity, count as u32))
+        .collect();
+    let violations_found = violations_by_severity.values().sum();
+    let high_severity_violations = HIGH_SEVERITIES
+        .iter()
+        .filter_map(|s| violations_by_severity.get(*s))
+        .sum();
+    let compliance_rate = if evaluations > 0 {
+        (evaluations - failed) as f64 / evaluations as f64 * 100.0
+    } else {
+        100.0
+    };
+
+    PolicyAnalysis {
+        policies_evaluated: evaluations as u32,
+        violations_found,
+        compliance_rate: compliance_rate.clamp(0.0, 100.0),
+        high_severity_violations,
+        violations_by_severity,
+    }
 }
 
-async fn analyze_policy_adherence(
-    pool: &PgPool,
+/// Adherence estimated from the organization's audit log action names, for
+/// deployments that do not record policy evaluations
+pub async fn heuristic_policy_adherence(
+    conn: &mut PgConnection,
     organization_id: &str,
     from: &str,
     to: &str,
+    scope: Option<&AuditScopeRequest>,
 ) -> Result<PolicyAnalysis> {
+    let filter = AuditScopeFilter::new(scope);
+
     // Count policy-related audit events
-    let policy_events: (i64,) = sqlx::query_as(
-        "SELECT COUNT(*) FROM audit_logs WHERE resource_type = 'policy' AND timestamp >= $1 AND timestamp <= $2"
-    )
-    .bind(from)
-    .bind(to)
-    .fetch_one(pool)
-    .await
-    .unwrap_or((0,));
+    let mut query = filter.audit_logs("SELECT COUNT(*) FROM audit_logs", from, to);
+    query.filter("resource_type", "policy");
+    organization_events(&mut query, organization_id);
+    let policy_events: (i64,) = query.build_query_as().fetch_one(&mut *conn).await?;
 
     // Count violations (events with action containing 'violation' or 'reject')
-    let violations: (i64,) = sqlx::query_as(
-        "SELECT COUNT(*) FROM audit_logs WHERE (action LIKE '%violation%' OR action LIKE '%reject%') AND timestamp >= $1 AND timestamp <= $2"
-    )
-    .bind(from)
-    .bind(to)
-    .fetch_one(pool)
-    .await
-    .unwrap_or((0,));
-
-    let total = policy_events.0.max(1) as f64;
-    let compliance_rate = ((total - violations.0 as f64) / total * 100.0).max(0.0).min(100.0);
-
-    Ok(PolicyAnalysis {
-        policies_evaluated: policy_events.0 as u32,
-        violations_found: violations.0 as u32,
+    let mut query = filter.audit_logs("SELECT COUNT(*) FROM audit_logs", from, to);
+    query.and_where().push("(action LIKE '%violation%' OR action LIKE '%reject%')");
+    organization_events(&mut query, organization_id);
+    let violations: (i64,) = query.build_query_as().fetch_one(&mut *conn).await?;
+
+    Ok(heuristic_policy_analysis(policy_events.0, violations.0))
+}
+
+/// Restrict `query` to events of the organization: those by its members or
+/// on its policies
+fn organization_events<'a>(query: &mut DynamicQuery<'a>, organization_id: &'a str) {
+    query
+        .and_where()
+        .push("(user_id IN (SELECT user_id FROM organization_members WHERE organization_id::text = ")
+        .push_bind(organization_id)
+        .push(") OR (resource_type = 'policy' AND resource_id IN (SELECT id::text FROM policies WHERE organization_id::text = ")
+        .push_bind(organization_id)
+        .push(")))");
+}
+
+fn heuristic_policy_analysis(policy_events: i64, violations: i64) -> PolicyAnalysis {
+    let total = policy_events.max(1) as f64;
+    let compliance_rate = ((total - violations as f64) / total * 100.0).max(0.0).min(100.0);
+
+    PolicyAnalysis {
+        policies_evaluated: policy_events as u32,
+        violations_found: violations as u32,
         compliance_rate,
-        high_severity_violations: (violations.0 / 3) as u32, // Estimate ~1/3 are high severity
-    })
+        high_severity_violations: (violations / 3) as u32, // Estimate ~1/3 are high severity
+        violations_by_severity: HashMap::new(),
+    }
+}
+
+/// Finding id derived from what the finding is about rather than when it was
+/// generated, so an ongoing issue keeps its id across audits
+///
+/// Titles are kept free of counts and rates for the same reason; those belong
+/// in the description.
+fn stable_finding_id(
+    organization_id: &str,
+    category: &FindingCategory,
+    affected_resources: &[String],
+    title: &str,
+) -> String {
+    let mut resources = affected_resources.to_vec();
+    resources.sort();
+    let category = serde_json::to_string(category).unwrap_or_default();
+    let resources = resources.join(",");
+
+    let mut hasher = Sha256::new();
+    for part in [organization_id, category.as_str(), resources.as_str(), title] {
+        hasher.update(part.as_bytes());
+        hasher.update([0u8]);
+    }
+
+    let digest = hasher.finalize();
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&digest[..16]);
+    Uuid::from_bytes(bytes).to_string()
+}
+
+/// `first_detected` of each finding in a stored audit record, by finding id
+fn prior_first_detected(details: &serde_json::Value) -> HashMap<String, Timestamp> {
+    details
+        .pointer("/outputs/findings")
+        .or_else(|| details.get("findings"))
+        .and_then(|f| f.as_array())
+        .map(|findings| {
+            findings
+                .iter()
+                .filter_map(|f| {
+                    let id = f.get("id")?.as_str()?;
+                    let first_detected = Timestamp::parse(f.get("first_detected")?.as_str()?).ok()?;
+                    Some((id.to_string(), first_detected))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Findings of the organization's most recent governance audit
+async fn load_prior_findings(pool: &PgPool, organization_id: &str) -> Result<HashMap<String, Timestamp>> {
+    let details: Option<(serde_json::Value,)> = sqlx::query_as(
+        r#"
+        SELECT details
+        FROM audit_logs
+        WHERE resource_type = 'governance_audit'
+        AND details->>'organization_id' = $1
+        ORDER BY timestamp DESC
+        LIMIT 1
+        "#,
+    )
+    .bind(organization_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(details.map(|(d,)| prior_first_detected(&d)).unwrap_or_default())
+}
+
+/// Recurring findings keep their original `first_detected`; `last_seen` stays
+/// at this audit's time
+fn carry_forward_findings(findings: &mut [GovernanceFinding], prior: &HashMap<String, Timestamp>) {
+    for finding in findings.iter_mut() {
+        if let Some(first_detected) = prior.get(&finding.id) {
+            finding.first_detected = *first_detected;
+        }
+    }
 }
 
 fn generate_findings(
+    organization_id: &str,
     audit_data: &AuditDataAggregate,
     policy_analysis: &PolicyAnalysis,
     include_details: bool,
+    now: Timestamp,
 ) -> Vec<GovernanceFinding> {
     let mut findings = Vec::new();
-    let now = chrono::Utc::now().to_rfc3339();
+    let finding_id = |category: &FindingCategory, resources: &[String], title: &str| {
+        stable_finding_id(organization_id, category, resources, title)
+    };
 
     // Generate findings based on policy violations
     if policy_analysis.violations_found > 0 {
+        let title = "Policy violations detected".to_string();
+        let affected_resources = vec!["policies".to_string()];
         findings.push(GovernanceFinding {
-            id: Uuid::new_v4().to_string(),
+            id: finding_id(&FindingCategory::PolicyViolation, &affected_resources, &title),
             category: FindingCategory::PolicyViolation,
             severity: if policy_analysis.high_severity_violations > 5 {
                 GovernanceSeverity::High
@@ -658,55 +1026,60 @@ fn generate_findings(
             } else {
                 GovernanceSeverity::Low
             },
-            title: format!("{} policy violations detected", policy_analysis.violations_found),
+            title,
             description: format!(
-                "During the audit period, {} policy violations were detected, of which {} are high severity.",
+                "During the audit period, {} policy violations were detected, of which {} are high severity.{}",
                 policy_analysis.violations_found,
-                policy_analysis.high_severity_violations
+                policy_analysis.high_severity_violations,
+                severity_breakdown(&policy_analysis.violations_by_severity)
             ),
-            affected_resources: vec!["policies".to_string()],
+            affected_resources,
             evidence_refs: vec![],
-            first_detected: now.clone(),
-            last_seen: now.clone(),
+            first_detected: now,
+            last_seen: now,
         });
     }
 
     // Check for compliance gaps
     if policy_analysis.compliance_rate < 95.0 {
+        let title = "Compliance rate below target".to_string();
+        let affected_resources = vec!["compliance".to_string()];
         findings.push(GovernanceFinding {
-            id: Uuid::new_v4().to_string(),
+            id: finding_id(&FindingCategory::ComplianceDeviation, &affected_resources, &title),
             category: FindingCategory::ComplianceDeviation,
             severity: if policy_analysis.compliance_rate < 80.0 {
                 GovernanceSeverity::High
             } else {
                 GovernanceSeverity::Medium
             },
-            title: format!("Compliance rate below target: {:.1}%", policy_analysis.compliance_rate),
+            title,
             description: format!(
                 "The current compliance rate of {:.1}% is below the target threshold of 95%.",
                 policy_analysis.compliance_rate
             ),
-            affected_resources: vec!["compliance".to_string()],
+            affected_resources,
             evidence_refs: vec![],
-            first_detected: now.clone(),
-            last_seen: now.clone(),
+            first_detected: now,
+            last_seen: now,
         });
     }
 
     // Check for audit coverage
     if audit_data.time_range_coverage < 1.0 {
+        let title = "Incomplete audit coverage for time range".to_string();
+        let affected_resources = vec!["audit_logs".to_string()];
         findings.push(GovernanceFinding {
-            id: Uuid::new_v4().to_string(),
+            id: finding_id(&FindingCategory::AuditGap, &affected_resources, &title),
             category: FindingCategory::AuditGap,
             severity: GovernanceSeverity::Info,
-            title: "Incomplete audit coverage for time range".to_string(),
+            title,
             description: format!(
                 "Audit coverage for the requested time range is {:.1}%.",
                 audit_data.time_range_coverage * 100.0
             ),
-            affected_resources: vec!["audit_logs".to_string()],
+            affected_resources,
             evidence_refs: vec![],
-            first_detected: now.clone(),
+            first_detected: now,
             last_seen: now,
         });
     }
@@ -714,12 +1087,22 @@ fn generate_findings(
     findings
 }
 
+/// ` By severity: high 3, medium 1.`, or nothing when severities are unknown
+fn severity_breakdown(by_severity: &HashMap<String, u32>) -> String {
+    if by_severity.is_empty() {
+        return String::new();
+    }
+    let mut counts: Vec<_> = by_severity.iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+    let counts: Vec<String> = counts.iter().map(|(s, n)| format!("{} {}", s, n)).collect();
+    format!(" By severity: {}.", counts.join(", "))
+}
+
 fn calculate_governance_metrics(
     audit_data: &AuditDataAggregate,
     policy_analysis: &PolicyAnalysis,
     findings: &[GovernanceFinding],
-    from: &str,
-    to: &str,
+    range: &DateRange,
 ) -> GovernanceMetrics {
     let mut findings_by_severity = HashMap::new();
 
@@ -733,10 +1116,7 @@ fn calculate_governance_metrics(
 
     GovernanceMetrics {
         events_analyzed: audit_data.total_events,
-        time_range: DateRange {
-            start: from.to_string(),
-            end: to.to_string(),
-        },
+        time_range: range.clone(),
         coverage_percentage: audit_data.time_range_coverage * 100.0,
         policies_evaluated: policy_analysis.policies_evaluated,
         compliance_rate: policy_analysis.compliance_rate,
@@ -766,6 +1146,19 @@ fn calculate_confidence(audit_data: &AuditDataAggregate, policy_analysis: &Polic
     default_confidence(completeness, certainty)
 }
 
+/// Cap requested by the caller, bounded by the configured one
+fn response_cap(requested: Option<usize>, configured: usize) -> usize {
+    requested.map_or(configured, |requested| requested.min(configured))
+}
+
+/// Keep the `max` most severe findings, most severe first
+///
+/// The sort is stable, so findings of equal severity keep their order.
+fn retain_most_severe(findings: &mut Vec<GovernanceFinding>, max: usize) {
+    findings.sort_by(|a, b| b.severity.cmp(&a.severity));
+    findings.truncate(max);
+}
+
 fn generate_recommendations(findings: &[GovernanceFinding], metrics: &GovernanceMetrics) -> Vec<String> {
     let mut recommendations = Vec::new();
 
@@ -801,7 +1194,7 @@ fn generate_recommendations(findings: &[GovernanceFinding], metrics: &Governance
     recommendations
 }
 
-fn build_constraints_record(req: &GovernanceAuditRequest) -> Vec<ConstraintApplication> {
+fn build_constraints_record(req: &GovernanceAuditRequest, range: &DateRange) -> Vec<ConstraintApplication> {
     let mut constraints = Vec::new();
 
     // Time range constraint
@@ -813,10 +1206,7 @@ fn build_constraints_record(req: &GovernanceAuditRequest) -> Vec<ConstraintAppli
             organizations: vec![req.organization_id.clone()],
             teams: req.scope.as_ref().and_then(|s| s.teams.clone()).unwrap_or_default(),
             resource_types: req.scope.as_ref().and_then(|s| s.resource_types.clone()).unwrap_or_default(),
-            time_range: Some(DateRange {
-                start: req.from.clone(),
-                end: req.to.clone(),
-            }),
+            time_range: Some(range.clone()),
         },
         satisfied: true,
         details: format!("Audit scoped to time range {} to {}", req.from, req.to),
@@ -874,6 +1264,63 @@ fn generate_summary(metrics: &GovernanceMetrics, findings: &[GovernanceFinding])
     )
 }
 
+/// Keep table cells on one line and stop `|` from splitting them
+fn markdown_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace(['\n', '\r'], " ")
+}
+
+/// Human-readable report of an audit response
+fn render_audit_markdown(audit: &GovernanceAuditResponse) -> String {
+    let mut md = format!(
+        "# Governance Audit: {}\n\n\
+         - **Event:** `{}`\n\
+         - **Type:** {}\n\
+         - **Generated:** {}\n\
+         - **Confidence:** {:.0}%\n\n\
+         {}\n\n",
+        audit.organization_id,
+        audit.event_id,
+        audit.decision_type,
+        audit.timestamp,
+        audit.confidence.overall * 100.0,
+        audit.summary
+    );
+
+    md.push_str("## Metrics\n\n| Metric | Value |\n| --- | --- |\n");
+    md.push_str(&format!("| Events analyzed | {} |\n", audit.metrics.events_analyzed));
+    md.push_str(&format!("| Policies evaluated | {} |\n", audit.metrics.policies_evaluated));
+    md.push_str(&format!("| Compliance rate | {:.1}% |\n", audit.metrics.compliance_rate));
+    md.push_str(&format!("| Coverage | {:.1}% |\n", audit.metrics.coverage_percentage));
+    md.push_str(&format!("| Trend | {} |\n", audit.metrics.trend));
+
+    md.push_str(&format!("\n## Findings ({})\n\n", audit.findings_count));
+    match &audit.findings {
+        Some(findings) if !findings.is_empty() => {
+            md.push_str("| Severity | Category | Title | Description |\n| --- | --- | --- | --- |\n");
+            for finding in findings {
+                md.push_str(&format!(
+                    "| {} | {} | {} | {} |\n",
+                    markdown_cell(&finding.severity),
+                    markdown_cell(&finding.category),
+                    markdown_cell(&finding.title),
+                    markdown_cell(&finding.description)
+                ));
+            }
+        }
+        Some(_) => md.push_str("No findings detected.\n"),
+        None => md.push_str("Request the audit with `include_details` to list findings.\n"),
+    }
+
+    if !audit.recommendations.is_empty() {
+        md.push_str("\n## Recommendations\n\n");
+        for recommendation in &audit.recommendations {
+            md.push_str(&format!("- {}\n", recommendation));
+        }
+    }
+
+    md
+}
+
 fn determine_governance_status(total_events: i64, unique_users: i64) -> &'static str {
     if total_events > 100 && unique_users > 5 {
         "healthy"
@@ -884,17 +1331,26 @@ fn determine_governance_status(total_events: i64, unique_users: i64) -> &'static
     }
 }
 
-fn extract_request_id(req: &actix_web::HttpRequest) -> Option<String> {
-    req.headers()
-        .get("X-Request-Id")
-        .and_then(|h| h.to_str().ok())
-        .map(String::from)
+/// Extract a finding and the metadata of the DecisionEvent that surfaced it
+fn locate_finding(event: &DecisionEvent, finding_id: &str) -> Option<serde_json::Value> {
+    let finding = find_finding(event, finding_id)?;
+
+    Some(serde_json::json!({
+        "finding": finding,
+        "event": {
+            "event_id": event.id,
+            "agent_id": event.agent_id,
+            "agent_version": event.agent_version,
+            "decision_type": event.decision_type,
+            "organization_id": event.organization_id,
+            "timestamp": event.timestamp,
+        }
+    }))
 }
 
-fn extract_trace_id(req: &actix_web::HttpRequest) -> Option<String> {
+fn extract_request_id(req: &actix_web::HttpRequest) -> Option<String> {
     req.headers()
-        .get("X-Trace-Id")
-        .or_else(|| req.headers().get("traceparent"))
+        .get("X-Request-Id")
         .and_then(|h| h.to_str().ok())
         .map(String::from)
 }
@@ -915,6 +1371,321 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.service(generate_governance_audit)
         .service(list_governance_audits)
         .service(get_governance_audit)
+        .service(get_governance_finding)
         .service(summarize_governance)
         .service(get_agent_registration);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event_with_finding(finding_id: &str) -> DecisionEvent {
+        let finding = GovernanceFinding {
+            id: finding_id.to_string(),
+            category: FindingCategory::PolicyViolation,
+            severity: GovernanceSeverity::High,
+            title: "Policy violations detected".to_string(),
+            description: "12 violations".to_string(),
+            affected_resources: vec![],
+            evidence_refs: vec![],
+            first_detected: "2024-01-01T00:00:00Z".parse().unwrap(),
+            last_seen: "2024-01-02T00:00:00Z".parse().unwrap(),
+        };
+
+        create_decision_event(
+            AGENT_ID,
+            AGENT_VERSION,
+            GovernanceDecisionType::AuditSummary,
+            "org-1",
+            DecisionOutputs {
+                summary: "audit".to_string(),
+                findings: vec![finding],
+                metrics: GovernanceMetrics {
+                    events_analyzed: 10,
+                    time_range: DateRange {
+                        start: "2024-01-01T00:00:00Z".parse().unwrap(),
+                        end: "2024-01-02T00:00:00Z".parse().unwrap(),
+                    },
+                    coverage_percentage: 100.0,
+                    policies_evaluated: 1,
+                    compliance_rate: 90.0,
+                    findings_by_severity: HashMap::new(),
+                    trend: TrendDirection::Stable,
+                },
+                recommendations: vec![],
+                data_refs: vec![],
+            },
+            default_confidence(0.9, 0.9),
+            vec![],
+            execution_ref_from_request(None, None, None, InvocationSource::Api),
+            &serde_json::json!({"organization_id": "org-1"}),
+        )
+    }
+
+    #[test]
+    fn test_finding_id_resolves_to_parent_event() {
+        let event = event_with_finding("finding-7");
+
+        let located = locate_finding(&event, "finding-7").unwrap();
+
+        assert_eq!(located["finding"]["id"], "finding-7");
+        assert_eq!(located["event"]["event_id"], event.id.as_str());
+        assert_eq!(located["event"]["agent_id"], AGENT_ID);
+        assert_eq!(located["event"]["decision_type"], "audit_summary");
+        assert_eq!(located["event"]["organization_id"], "org-1");
+
+        assert!(locate_finding(&event, "finding-8").is_none());
+    }
+
+    #[test]
+    fn test_audit_renders_as_markdown() {
+        let audit = GovernanceAuditResponse {
+            event_id: "evt-1".to_string(),
+            agent_id: AGENT_ID.to_string(),
+            agent_version: AGENT_VERSION.to_string(),
+            decision_type: "audit_summary".to_string(),
+            timestamp: "2024-01-02T00:00:00Z".to_string(),
+            organization_id: "org-1".to_string(),
+            summary: "Governance audit analyzed 10 events.".to_string(),
+            metrics: GovernanceMetricsResponse {
+                events_analyzed: 10,
+                coverage_percentage: 100.0,
+                policies_evaluated: 1,
+                compliance_rate: 90.0,
+                findings_by_severity: HashMap::new(),
+                trend: "stable".to_string(),
+            },
+            findings_count: 1,
+            findings: Some(vec![GovernanceFindingResponse {
+                id: "finding-1".to_string(),
+                category: "policy_violation".to_string(),
+                severity: "high".to_string(),
+                title: "Violations | spikes".to_string(),
+                description: "12 violations\nacross 3 teams".to_string(),
+                affected_resources: vec![],
+                first_detected: "2024-01-01T00:00:00Z".to_string(),
+                last_seen: "2024-01-02T00:00:00Z".to_string(),
+            }]),
+            recommendations: vec!["Review policy assignments".to_string()],
+            confidence: ConfidenceResponse {
+                overall: 0.9,
+                completeness: 0.9,
+                certainty: 0.9,
+            },
+            telemetry_ref: String::new(),
+            artifact_ref: String::new(),
+        };
+
+        let md = render_audit_markdown(&audit);
+
+        assert!(md.starts_with("# Governance Audit: org-1\n"));
+        assert!(md.contains("| Compliance rate | 90.0% |"));
+        assert!(md.contains("| high | policy_violation | Violations \\| spikes | 12 violations across 3 teams |"));
+        assert!(md.contains("- Review policy assignments"));
+    }
+
+    fn audit_range() -> DateRange {
+        parse_range("from", "2024-01-01T00:00:00Z", "to", "2024-01-02T00:00:00Z").unwrap()
+    }
+
+    fn violating_analysis(violations_found: u32) -> (AuditDataAggregate, PolicyAnalysis) {
+        (
+            AuditDataAggregate {
+                total_events: 100,
+                events_by_action: HashMap::new(),
+                events_by_resource: HashMap::new(),
+                unique_users: 4,
+                time_range_coverage: 1.0,
+            },
+            PolicyAnalysis {
+                policies_evaluated: 50,
+                violations_found,
+                compliance_rate: 100.0,
+                high_severity_violations: 0,
+                violations_by_severity: HashMap::new(),
+            },
+        )
+    }
+
+    #[test]
+    fn test_recurring_finding_keeps_id_and_advances_last_seen() {
+        let first_run: Timestamp = "2024-01-01T00:00:00+00:00".parse().unwrap();
+        let second_run: Timestamp = "2024-01-08T00:00:00+00:00".parse().unwrap();
+
+        let (audit_data, analysis) = violating_analysis(4);
+        let previous = generate_findings("org-1", &audit_data, &analysis, true, first_run);
+        let stored = serde_json::json!({ "organization_id": "org-1", "findings": previous });
+
+        // The violation count changed, but it is the same ongoing issue
+        let (audit_data, analysis) = violating_analysis(9);
+        let mut current = generate_findings("org-1", &audit_data, &analysis, true, second_run);
+        carry_forward_findings(&mut current, &prior_first_detected(&stored));
+
+        assert_eq!(current.len(), 1);
+        assert_eq!(current[0].id, previous[0].id);
+        assert_eq!(current[0].first_detected, first_run);
+        assert_eq!(current[0].last_seen, second_run);
+    }
+
+    #[test]
+    fn test_finding_ids_differ_by_subject() {
+        let id = stable_finding_id("org-1", &FindingCategory::PolicyViolation, &["policies".to_string()], "Policy violations detected");
+
+        assert_eq!(
+            id,
+            stable_finding_id("org-1", &FindingCategory::PolicyViolation, &["policies".to_string()], "Policy violations detected")
+        );
+        assert_ne!(
+            id,
+            stable_finding_id("org-2", &FindingCategory::PolicyViolation, &["policies".to_string()], "Policy violations detected")
+        );
+        assert_ne!(
+            id,
+            stable_finding_id("org-1", &FindingCategory::AuditGap, &["policies".to_string()], "Policy violations detected")
+        );
+
+        // A new finding has no prior first_detected to inherit
+        let (audit_data, analysis) = violating_analysis(2);
+        let now = Timestamp::now();
+        let mut findings = generate_findings("org-1", &audit_data, &analysis, true, now);
+        carry_forward_findings(&mut findings, &HashMap::new());
+        assert_eq!(findings[0].first_detected, now);
+    }
+
+    #[test]
+    fn test_structured_counts_replace_heuristic_estimate() {
+        // 20 evaluations, 4 of which failed with 6 violations between them;
+        // the audit log holds one 'policy_violation' action per failure
+        let structured = structured_policy_analysis(
+            20,
+            4,
+            vec![("high".to_string(), 2), ("medium".to_string(), 3), ("critical".to_string(), 1)],
+        );
+        let heuristic = heuristic_policy_analysis(20, 4);
+
+        assert_eq!(structured.policies_evaluated, 20);
+        assert_eq!(structured.violations_found, 6);
+        assert_eq!(structured.high_severity_violations, 3);
+        assert_eq!(structured.compliance_rate, 80.0);
+        assert_eq!(structured.violations_by_severity["medium"], 3);
+
+        // The heuristic sees one violation per action and guesses a third are high
+        assert_eq!(heuristic.violations_found, 4);
+        assert_eq!(heuristic.high_severity_violations, 1);
+        assert_eq!(heuristic.compliance_rate, 80.0);
+        assert!(heuristic.violations_by_severity.is_empty());
+    }
+
+    #[test]
+    fn test_severity_breakdown_in_finding_description() {
+        let (audit_data, _) = violating_analysis(0);
+        let analysis = structured_policy_analysis(10, 2, vec![("medium".to_string(), 1), ("high".to_string(), 3)]);
+        let findings = generate_findings("org-1", &audit_data, &analysis, false, Timestamp::now());
+
+        assert!(findings[0]
+            .description
+            .ends_with("4 policy violations were detected, of which 3 are high severity. By severity: high 3, medium 1."));
+
+        let heuristic = heuristic_policy_analysis(10, 2);
+        let findings = generate_findings("org-1", &audit_data, &heuristic, false, Timestamp::now());
+        assert!(findings[0].description.ends_with("high severity."));
+    }
+
+    #[test]
+    fn test_retention_gap_reports_partial_coverage() {
+        let at = |value: &str| Timestamp::parse(value).unwrap();
+        let (from, to) = (at("2024-01-01T00:00:00Z"), at("2024-01-02T00:00:00Z"));
+        let later = at("2024-06-01T00:00:00Z");
+
+        assert_eq!(retained_coverage(from, to, None, later), 1.0);
+        assert_eq!(retained_coverage(from, to, Some(at("2023-12-01T00:00:00Z")), later), 1.0);
+        // Only the part of the range that has already passed is expected to hold events
+        assert_eq!(retained_coverage(from, to, Some(from), at("2024-01-01T12:00:00Z")), 1.0);
+        assert_eq!(retained_coverage(from, to, Some(later), later), 0.0);
+
+        let (mut audit_data, analysis) = violating_analysis(0);
+        // The log only reaches back to the last 6 of the 24 hours requested
+        audit_data.time_range_coverage = retained_coverage(from, to, Some(at("2024-01-01T18:00:00Z")), later);
+        let metrics = calculate_governance_metrics(&audit_data, &analysis, &[], &audit_range());
+        assert_eq!(metrics.coverage_percentage, 25.0);
+
+        let findings = generate_findings("org-1", &audit_data, &analysis, false, Timestamp::now());
+        assert!(findings
+            .iter()
+            .any(|f| f.description == "Audit coverage for the requested time range is 25.0%."));
+    }
+
+    #[test]
+    fn test_scope_filter_binds_each_list_once() {
+        let filter = AuditScopeFilter {
+            resource_types: Some(vec!["policy".to_string(), "budget".to_string()]),
+            exclude_resource_types: None,
+            exclude_actions: Some(vec!["heartbeat".to_string()]),
+        };
+        let mut query = filter.audit_logs("SELECT COUNT(*) FROM audit_logs", "2024-01-01", "2024-01-02");
+        organization_events(&mut query, "org-1");
+
+        assert_eq!(
+            query.sql(),
+            "SELECT COUNT(*) FROM audit_logs WHERE timestamp >= $1::timestamptz AND timestamp <= $2::timestamptz \
+             AND resource_type = ANY($3) AND action <> ALL($4) \
+             AND (user_id IN (SELECT user_id FROM organization_members WHERE organization_id::text = $5) \
+             OR (resource_type = 'policy' AND resource_id IN (SELECT id::text FROM policies WHERE organization_id::text = $6)))"
+        );
+
+        assert!(filter.includes_resource_type("policy"));
+        assert!(!filter.includes_resource_type("team"));
+        let excluding = AuditScopeFilter {
+            exclude_resource_types: Some(vec!["policy".to_string()]),
+            ..AuditScopeFilter::default()
+        };
+        assert!(!excluding.includes_resource_type("policy"));
+        assert!(AuditScopeFilter::default().includes_resource_type("policy"));
+    }
+
+    fn finding(id: &str, severity: GovernanceSeverity) -> GovernanceFinding {
+        GovernanceFinding {
+            id: id.to_string(),
+            category: FindingCategory::PolicyViolation,
+            severity,
+            title: id.to_string(),
+            description: String::new(),
+            affected_resources: vec![],
+            evidence_refs: vec![],
+            first_detected: "2024-01-01T00:00:00Z".parse().unwrap(),
+            last_seen: "2024-01-01T00:00:00Z".parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_requested_caps_never_exceed_configured_ones() {
+        assert_eq!(response_cap(None, 20), 20);
+        assert_eq!(response_cap(Some(5), 20), 5);
+        assert_eq!(response_cap(Some(500), 20), 20);
+    }
+
+    #[test]
+    fn test_truncation_keeps_most_severe_findings_and_full_metrics() {
+        let mut findings: Vec<GovernanceFinding> = (0..50)
+            .map(|i| finding(&format!("low-{}", i), GovernanceSeverity::Low))
+            .collect();
+        findings.insert(10, finding("critical-1", GovernanceSeverity::Critical));
+        findings.push(finding("critical-2", GovernanceSeverity::Critical));
+        findings.insert(30, finding("high-1", GovernanceSeverity::High));
+
+        let (audit_data, analysis) = violating_analysis(0);
+        let metrics = calculate_governance_metrics(&audit_data, &analysis, &findings, &audit_range());
+        retain_most_severe(&mut findings, 3);
+
+        let ids: Vec<&str> = findings.iter().map(|f| f.id.as_str()).collect();
+        assert_eq!(ids, vec!["critical-1", "critical-2", "high-1"]);
+        assert_eq!(metrics.findings_by_severity.values().sum::<u32>(), 53);
+        assert_eq!(metrics.findings_by_severity.get("low"), Some(&50));
+        assert_eq!(metrics.findings_by_severity.get("critical"), Some(&2));
+
+        // A cap above the count keeps everything
+        retain_most_severe(&mut findings, 100);
+        assert_eq!(findings.len(), 3);
+    }
+}
@@ -1,15 +1,20 @@
 use actix_web::web;
 
+pub mod agents;
 pub mod health;
 pub mod audit;
 pub mod governance;
 pub mod change_impact;
+mod validation;
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(llm_governance_common::metrics::metrics_endpoint);
+    cfg.service(llm_governance_common::versioning::version_endpoint);
     cfg.service(
         web::scope("/api/v1")
             .configure(health::configure)
             .configure(audit::configure)
+            .configure(agents::configure)
             .configure(governance::configure)
             .configure(change_impact::configure)
     );
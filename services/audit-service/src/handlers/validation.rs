@@ -0,0 +1,60 @@
+//! Shared request validation for the governance and change-impact handlers
+
+use serde::{Deserialize, Serialize};
+
+use llm_governance_common::adapters::ruvector::DateRange;
+use llm_governance_common::Result;
+
+pub use llm_governance_common::utils::{parse_range, parse_timestamp};
+
+/// Date range input
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DateRangeInput {
+    pub start: String,
+    pub end: String,
+}
+
+pub fn parse_date_range(input: &DateRangeInput) -> Result<DateRange> {
+    parse_range("start", &input.start, "end", &input.end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use llm_governance_common::AppError;
+
+    #[test]
+    fn test_malformed_timestamp_is_rejected() {
+        let err = parse_timestamp("change_request.timestamp", "yesterday").unwrap_err();
+        match err {
+            AppError::Validation(msg) => assert!(msg.contains("change_request.timestamp")),
+            other => panic!("unexpected error: {:?}", other),
+        }
+
+        let range = DateRangeInput {
+            start: "2024-01-01".to_string(),
+            end: "2024-02-01T00:00:00Z".to_string(),
+        };
+        assert!(matches!(parse_date_range(&range), Err(AppError::Validation(msg)) if msg.contains("start")));
+    }
+
+    #[test]
+    fn test_reversed_range_is_rejected() {
+        let range = DateRangeInput {
+            start: "2024-02-01T00:00:00Z".to_string(),
+            end: "2024-01-01T00:00:00Z".to_string(),
+        };
+        assert!(matches!(parse_date_range(&range), Err(AppError::Validation(_))));
+    }
+
+    #[test]
+    fn test_valid_range_is_normalized_to_utc() {
+        let range = DateRangeInput {
+            start: "2024-01-01T02:00:00+02:00".to_string(),
+            end: "2024-01-01T00:00:00Z".to_string(),
+        };
+        let parsed = parse_date_range(&range).unwrap();
+        assert_eq!(parsed.start.to_string(), "2024-01-01T00:00:00+00:00");
+        assert_eq!(parsed.end, parsed.start);
+    }
+}
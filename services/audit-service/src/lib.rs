@@ -0,0 +1,7 @@
+//! Audit logs, governance audits and change impact assessment, served by `main.rs`
+
+pub mod config;
+pub mod handlers;
+pub mod middleware;
+pub mod models;
+pub mod services;
@@ -1,38 +1,94 @@
 use actix_web::{web, App, HttpServer};
-use tracing::{info, Level};
-use tracing_subscriber;
+use std::sync::Arc;
+use tracing::info;
 
-mod config;
-mod handlers;
-mod middleware;
-mod models;
-mod services;
-
-use config::Config;
+use audit_service::config::Config;
+use audit_service::handlers;
+use llm_governance_common::adapters::change_impact::ChangeImpactAgent;
+use llm_governance_common::adapters::ruvector::{decision_store_from_env, DecisionStore};
+use llm_governance_common::adapters::UpstreamConfig;
+use llm_governance_common::metrics::RequestMetrics;
+use llm_governance_common::request_context::RequestContext;
+use llm_governance_common::telemetry;
+use llm_governance_common::versioning::ApiVersioning;
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
-    tracing_subscriber::fmt()
-        .with_max_level(Level::INFO)
-        .init();
-
     dotenv::dotenv().ok();
+    telemetry::init("audit-service").expect("Failed to initialize telemetry");
+
     let config = Config::from_env().expect("Failed to load configuration");
+    if let Err(problems) = config.validate() {
+        panic!("Invalid configuration:\n  - {}", problems.join("\n  - "));
+    }
 
     info!("Starting audit-service on {}:{}", config.host, config.port);
 
     let db_pool = llm_governance_database::create_pool(&config.database_url)
         .await
         .expect("Failed to create database pool");
+    let read_pool = llm_governance_database::create_read_pool(&db_pool, config.database_read_url.as_deref())
+        .await
+        .expect("Failed to create read replica pool");
+    let category_weights = web::Data::new(
+        config.category_weights().expect("Invalid risk category weight configuration"),
+    );
+    let severity_policy = web::Data::new(
+        config.severity_policy().expect("Invalid severity escalation configuration"),
+    );
+
+    let decision_store: web::Data<dyn DecisionStore> = web::Data::from(Arc::<dyn DecisionStore>::from(
+        decision_store_from_env(UpstreamConfig {
+            base_url: config.ruvector_url.clone(),
+            ..UpstreamConfig::default()
+        })
+        .expect("Failed to create decision store"),
+    ));
+    let change_impact_agent = web::Data::new(
+        change_impact_agent(&config, decision_store.clone().into_inner())
+            .expect("Failed to create change impact agent"),
+    );
 
-    HttpServer::new(move || {
+    let result = HttpServer::new(move || {
         App::new()
             .app_data(web::Data::new(db_pool.clone()))
+            .app_data(web::Data::new(read_pool.clone()))
             .app_data(web::Data::new(config.clone()))
+            .app_data(category_weights.clone())
+            .app_data(severity_policy.clone())
+            .app_data(decision_store.clone())
+            .app_data(change_impact_agent.clone())
+            .wrap(RequestContext)
             .wrap(tracing_actix_web::TracingLogger::default())
+            .wrap(RequestMetrics::new("audit-service"))
+            .wrap(ApiVersioning::new())
             .configure(handlers::configure)
     })
     .bind((config.host.as_str(), config.port))?
     .run()
-    .await
+    .await;
+
+    telemetry::shutdown();
+    result
+}
+
+/// Agent the change-impact handlers persist and emit telemetry through,
+/// sharing the service's decision store
+fn change_impact_agent(
+    config: &Config,
+    store: Arc<dyn DecisionStore>,
+) -> llm_governance_common::Result<ChangeImpactAgent> {
+    let agent = ChangeImpactAgent::new(UpstreamConfig {
+        base_url: config.ruvector_url.clone(),
+        ..UpstreamConfig::default()
+    })?
+    .with_decision_store(store);
+
+    match config.observatory_url {
+        Some(ref url) => agent.with_observatory(UpstreamConfig {
+            base_url: url.clone(),
+            ..UpstreamConfig::default()
+        }),
+        None => Ok(agent),
+    }
 }
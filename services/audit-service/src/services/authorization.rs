@@ -0,0 +1,97 @@
+//! Organization access checks for the governance and change-impact endpoints
+//!
+//! Callers are identified by the `X-User-Id` header set by the gateway and
+//! must hold an audit-capable role in the target organization.
+
+use actix_web::HttpRequest;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use llm_governance_common::{AppError, Result};
+
+/// Organization roles allowed to run and read governance analyses
+pub const AUDIT_ROLES: &[&str] = &["owner", "admin"];
+
+pub fn extract_user_id(req: &HttpRequest) -> Result<Uuid> {
+    req.headers()
+        .get("X-User-Id")
+        .and_then(|h| h.to_str().ok())
+        .and_then(|s| Uuid::parse_str(s).ok())
+        .ok_or_else(|| AppError::Unauthorized)
+}
+
+/// Accept the caller's membership role, or reject with `Forbidden`
+pub fn check_audit_role(role: Option<&str>) -> Result<()> {
+    match role {
+        Some(role) if AUDIT_ROLES.contains(&role) => Ok(()),
+        _ => Err(AppError::Forbidden),
+    }
+}
+
+/// Verify the caller may audit `organization_id`, returning the caller's id
+pub async fn authorize_org_audit(
+    pool: &PgPool,
+    req: &HttpRequest,
+    organization_id: &str,
+) -> Result<Uuid> {
+    let user_id = extract_user_id(req)?;
+
+    // Organizations are keyed by UUID, so anything else cannot be a member org
+    let Ok(org_id) = Uuid::parse_str(organization_id) else {
+        return Err(AppError::Forbidden);
+    };
+
+    let role: Option<(String,)> = sqlx::query_as(
+        "SELECT role FROM organization_members WHERE organization_id = $1 AND user_id = $2"
+    )
+    .bind(org_id)
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?;
+
+    check_audit_role(role.as_ref().map(|(r,)| r.as_str()))?;
+    Ok(user_id)
+}
+
+/// Authorize access to a stored record by the organization recorded in its details
+pub async fn authorize_record_audit(
+    pool: &PgPool,
+    req: &HttpRequest,
+    details: &serde_json::Value,
+) -> Result<Uuid> {
+    let organization_id = details
+        .get("organization_id")
+        .and_then(|v| v.as_str())
+        .ok_or(AppError::Forbidden)?;
+
+    authorize_org_audit(pool, req, organization_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+
+    #[test]
+    fn test_authorized_caller() {
+        assert!(check_audit_role(Some("owner")).is_ok());
+        assert!(check_audit_role(Some("admin")).is_ok());
+
+        let user_id = Uuid::new_v4();
+        let req = TestRequest::default()
+            .insert_header(("X-User-Id", user_id.to_string()))
+            .to_http_request();
+        assert_eq!(extract_user_id(&req).unwrap(), user_id);
+    }
+
+    #[test]
+    fn test_unauthorized_caller() {
+        assert!(matches!(check_audit_role(Some("member")), Err(AppError::Forbidden)));
+        assert!(matches!(check_audit_role(Some("viewer")), Err(AppError::Forbidden)));
+        // Not a member of the organization at all
+        assert!(matches!(check_audit_role(None), Err(AppError::Forbidden)));
+
+        let req = TestRequest::default().to_http_request();
+        assert!(matches!(extract_user_id(&req), Err(AppError::Unauthorized)));
+    }
+}
@@ -0,0 +1,234 @@
+//! Local record of DecisionEvents
+//!
+//! Each DecisionEvent is persisted to the service's shared decision store
+//! (ruvector-service, or in memory with `RUVECTOR_MODE=memory`) and also
+//! written to `audit_logs` so the list and lookup endpoints, baselines and
+//! finding carry-forward can read it back. `details` holds the serialized
+//! event plus the top-level fields those readers filter on.
+
+use serde_json::Value;
+use sqlx::PgPool;
+use tracing::warn;
+use uuid::Uuid;
+
+use llm_governance_common::adapters::ruvector::{DecisionEvent, DecisionStore};
+use llm_governance_common::{AppError, Result};
+
+pub const GOVERNANCE_AUDIT_RESOURCE: &str = "governance_audit";
+pub const CHANGE_IMPACT_RESOURCE: &str = "change_impact_assessment";
+
+/// `audit_logs.details` for `event`, with `extra` merged in at the top level
+///
+/// Event fields win over `extra` so the stored event stays intact.
+pub fn decision_record(event: &DecisionEvent, extra: Value) -> Result<Value> {
+    let mut record = serde_json::to_value(event)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize decision event: {}", e)))?;
+    let fields = record
+        .as_object_mut()
+        .ok_or_else(|| AppError::Internal("Decision event is not a JSON object".to_string()))?;
+
+    fields.insert("event_id".to_string(), Value::String(event.id.clone()));
+    if let Value::Object(extra) = extra {
+        for (key, value) in extra {
+            fields.entry(key).or_insert(value);
+        }
+    }
+
+    Ok(record)
+}
+
+/// Append `event` to `audit_logs` under `resource_type`, returning the row id
+pub async fn record_decision_event(
+    pool: &PgPool,
+    user_id: Uuid,
+    resource_type: &str,
+    event: &DecisionEvent,
+    extra: Value,
+) -> Result<Uuid> {
+    let details = decision_record(event, extra)?;
+    let action = serde_json::to_value(&event.decision_type)
+        .ok()
+        .and_then(|v| v.as_str().map(String::from))
+        .unwrap_or_else(|| resource_type.to_string());
+
+    let (id,): (Uuid,) = sqlx::query_as(
+        r#"
+        INSERT INTO audit_logs (user_id, action, resource_type, resource_id, details, checksum)
+        VALUES ($1, $2, $3, $4, $5, '')
+        RETURNING id
+        "#,
+    )
+    .bind(user_id)
+    .bind(&action)
+    .bind(resource_type)
+    .bind(&event.id)
+    .bind(&details)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(id)
+}
+
+/// Persist `event` to the shared decision store
+///
+/// `audit_logs` holds the record this service reads back, so a store that
+/// cannot take the event is logged rather than failing the request.
+pub async fn store_decision_event(store: &dyn DecisionStore, event: &DecisionEvent) {
+    let failure = match store.persist_decision_event(event.clone()).await {
+        Ok(response) if response.success => return,
+        Ok(response) => response.error.unwrap_or_else(|| "rejected by the decision store".to_string()),
+        Err(e) => e.to_string(),
+    };
+    warn!(event_id = %event.id, error = %failure, "Failed to persist decision event");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use llm_governance_common::adapters::ruvector::{
+        create_decision_event, default_confidence, execution_ref_from_request, DateRange,
+        DecisionEventPage, DecisionEventQuery, DecisionOutputs, GovernanceDecisionType, GovernanceMetrics,
+        InvocationSource, PersistDecisionResponse, TrendDirection,
+    };
+
+    fn change_impact_event() -> DecisionEvent {
+        create_decision_event(
+            "change-impact-agent",
+            "1.0.0",
+            GovernanceDecisionType::ChangeImpact,
+            "org-1",
+            DecisionOutputs {
+                summary: "Low impact".to_string(),
+                findings: Vec::new(),
+                metrics: GovernanceMetrics {
+                    events_analyzed: 0,
+                    time_range: DateRange {
+                        start: "2025-01-01T00:00:00Z".parse().unwrap(),
+                        end: "2025-02-01T00:00:00Z".parse().unwrap(),
+                    },
+                    coverage_percentage: 0.0,
+                    policies_evaluated: 0,
+                    compliance_rate: 100.0,
+                    findings_by_severity: Default::default(),
+                    trend: TrendDirection::Stable,
+                },
+                recommendations: Vec::new(),
+                data_refs: Vec::new(),
+            },
+            default_confidence(0.8, 0.8),
+            Vec::new(),
+            execution_ref_from_request(None, None, None, InvocationSource::Api),
+            &serde_json::json!({"change_id": "chg-1"}),
+        )
+    }
+
+    /// Store keeping persisted events, or rejecting all of them
+    #[derive(Default)]
+    struct RecordingStore {
+        unavailable: bool,
+        persisted: std::sync::Mutex<Vec<DecisionEvent>>,
+    }
+
+    #[async_trait::async_trait]
+    impl llm_governance_common::EcosystemConsumer for RecordingStore {
+        fn service_name(&self) -> &'static str {
+            "recording-store"
+        }
+
+        async fn health_check(&self) -> Result<bool> {
+            Ok(!self.unavailable)
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl DecisionStore for RecordingStore {
+        async fn persist_decision_event(&self, event: DecisionEvent) -> Result<PersistDecisionResponse> {
+            if self.unavailable {
+                return Err(AppError::UpstreamUnavailable("recording-store".to_string()));
+            }
+            let event_id = event.id.clone();
+            self.persisted.lock().unwrap().push(event);
+            Ok(PersistDecisionResponse {
+                success: true,
+                event_id,
+                persisted_at: String::new(),
+                storage_ref: String::new(),
+                error: None,
+            })
+        }
+
+        async fn query_decision_events(&self, _query: DecisionEventQuery) -> Result<DecisionEventPage> {
+            let items = self.persisted.lock().unwrap().clone();
+            Ok(DecisionEventPage {
+                total: items.len() as u64,
+                page: 1,
+                page_size: items.len() as u32,
+                total_pages: 1,
+                items,
+            })
+        }
+
+        async fn get_decision_event(&self, event_id: &str) -> Result<DecisionEvent> {
+            self.persisted
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|event| event.id == event_id)
+                .cloned()
+                .ok_or_else(|| AppError::NotFound(format!("Decision event {} not found", event_id)))
+        }
+
+        async fn find_events_by_finding_id(&self, organization_id: &str, finding_id: &str) -> Result<Vec<DecisionEvent>> {
+            Ok(self
+                .persisted
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|event| event.organization_id == organization_id)
+                .filter(|event| event.outputs.findings.iter().any(|finding| finding.id == finding_id))
+                .cloned()
+                .collect())
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_events_reach_the_shared_store_and_outages_do_not_fail() {
+        let event = change_impact_event();
+
+        let store = RecordingStore::default();
+        store_decision_event(&store, &event).await;
+        assert_eq!(store.get_decision_event(&event.id).await.unwrap().id, event.id);
+
+        let unavailable = RecordingStore { unavailable: true, ..RecordingStore::default() };
+        store_decision_event(&unavailable, &event).await;
+        assert!(unavailable.persisted.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_assessment_record_is_found_by_lookup_fields() {
+        let event = change_impact_event();
+        let record = decision_record(
+            &event,
+            serde_json::json!({
+                "assessment_id": "asm-1",
+                "change_request_id": "chg-1",
+                "risk_score": 0.2,
+                "organization_id": "someone-else",
+            }),
+        )
+        .unwrap();
+
+        // get_change_impact_assessment matches on event_id or assessment_id,
+        // and authorization and history filter on organization_id
+        assert_eq!(record["event_id"], event.id.as_str());
+        assert_eq!(record["assessment_id"], "asm-1");
+        assert_eq!(record["organization_id"], "org-1");
+        assert_eq!(record["change_request_id"], "chg-1");
+        assert_eq!(record["risk_score"], 0.2);
+
+        // The stored event round-trips
+        let stored: DecisionEvent = serde_json::from_value(record).unwrap();
+        assert_eq!(stored.id, event.id);
+        assert_eq!(stored.decision_type, GovernanceDecisionType::ChangeImpact);
+    }
+}
@@ -1 +1,4 @@
 // Add your business logic services here
+
+pub mod authorization;
+pub mod decision_log;
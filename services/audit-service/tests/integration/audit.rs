@@ -0,0 +1,43 @@
+use uuid::Uuid;
+
+use audit_service::handlers::audit::*;
+use llm_governance_common::testing::database;
+
+fn event(key: &str) -> IngestAuditEvent {
+    IngestAuditEvent {
+        idempotency_key: key.to_string(),
+        user_id: None,
+        action: "LLM_REQUEST".to_string(),
+        resource_type: "openai:gpt-4".to_string(),
+        resource_id: "chatcmpl-1".to_string(),
+        ip_address: None,
+        details: serde_json::json!({}),
+        occurred_at: None,
+    }
+}
+
+#[tokio::test]
+#[ignore] // Requires Postgres at DATABASE_URL
+async fn test_duplicate_key_never_writes_a_second_log_row() {
+    let pool = database().await;
+    let mut first = event(&Uuid::new_v4().to_string());
+    first.resource_id = format!("ingest-{}", first.idempotency_key);
+
+    let original = pool.append("integration-service", &first).await.unwrap();
+    let AppendOutcome::Accepted(audit_log_id) = original else {
+        panic!("expected the first delivery to be accepted, got {:?}", original);
+    };
+    let retry = pool.append("integration-service", &first).await.unwrap();
+    assert_eq!(retry, AppendOutcome::Duplicate(audit_log_id));
+
+    let (rows,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM audit_logs WHERE resource_id = $1")
+        .bind(&first.resource_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(rows, 1);
+
+    let mut orphan = event(&Uuid::new_v4().to_string());
+    orphan.user_id = Some(Uuid::new_v4());
+    assert_eq!(pool.append("integration-service", &orphan).await.unwrap(), AppendOutcome::UnknownUser);
+}
@@ -0,0 +1,65 @@
+use uuid::Uuid;
+
+use audit_service::config::Config;
+use audit_service::handlers::change_impact::*;
+use llm_governance_common::adapters::change_impact::*;
+use llm_governance_common::adapters::UpstreamConfig;
+use llm_governance_common::testing::{database, seed_user, MockResponse, MockServer};
+
+#[actix_web::test]
+#[ignore] // Requires Postgres at DATABASE_URL
+async fn test_served_assessment_emits_and_reports_telemetry() {
+    let pool = database().await;
+    let user_id = seed_user(&pool, "assessor").await;
+
+    // The event is accepted, then the span is rejected
+    let observatory = MockServer::start(vec![
+        MockResponse::new(200)
+            .with_body(r#"{"event_id": "tel-1", "timestamp": "2024-01-01T00:00:00Z", "acknowledged": true}"#),
+        MockResponse::new(500).with_body("{}"),
+    ])
+    .await;
+    let agent = ChangeImpactAgent::new(UpstreamConfig::default())
+        .unwrap()
+        .with_observatory(UpstreamConfig {
+            base_url: observatory.url().to_string(),
+            retry_config: llm_governance_common::adapters::RetryConfig {
+                max_retries: 0,
+                ..Default::default()
+            },
+            ..UpstreamConfig::default()
+        })
+        .unwrap();
+    let config = Config::default();
+    let category_weights = CategoryWeights::default();
+    let severity_policy = SeverityPolicy::default();
+    let assessor = Assessor {
+        pool: &pool,
+        config: &config,
+        category_weights: &category_weights,
+        severity_policy: &severity_policy,
+        agent: &agent,
+    };
+    let req: ChangeImpactRequest = serde_json::from_value(serde_json::json!({
+        "organization_id": Uuid::new_v4().to_string(),
+        "change_request": {
+            "change_id": "chg-1",
+            "change_type": "update",
+            "subject_type": "policy",
+            "subject_id": "policy-1",
+            "description": "Tighten cost limit",
+            "initiator": "user-1",
+        },
+    }))
+    .unwrap();
+    let http_req = actix_web::test::TestRequest::default().to_http_request();
+
+    let response = run_assessment(&assessor, user_id, &req, &http_req).await.unwrap();
+
+    assert_eq!(response.telemetry_status, Some(TelemetryStatus::Degraded));
+    assert!(response.telemetry_ref.ends_with("/tel-1"));
+    assert!(observatory.requests()[0].contains(&response.event_id));
+
+    let json = serde_json::to_value(&response).unwrap();
+    assert_eq!(json["telemetry_status"], "degraded");
+}
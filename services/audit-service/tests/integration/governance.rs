@@ -0,0 +1,267 @@
+use actix_web::web;
+use chrono::{Duration, TimeZone, Utc};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use audit_service::handlers::governance::*;
+use audit_service::services::decision_log::{record_decision_event, GOVERNANCE_AUDIT_RESOURCE};
+use llm_governance_common::adapters::ruvector::*;
+use llm_governance_common::testing::{database, seed_member, seed_organization, seed_user};
+
+fn event_with_finding(finding_id: &str) -> DecisionEvent {
+    let finding = GovernanceFinding {
+        id: finding_id.to_string(),
+        category: FindingCategory::PolicyViolation,
+        severity: GovernanceSeverity::High,
+        title: "Policy violations detected".to_string(),
+        description: "12 violations".to_string(),
+        affected_resources: vec![],
+        evidence_refs: vec![],
+        first_detected: "2024-01-01T00:00:00Z".parse().unwrap(),
+        last_seen: "2024-01-02T00:00:00Z".parse().unwrap(),
+    };
+
+    create_decision_event(
+        AGENT_ID,
+        AGENT_VERSION,
+        GovernanceDecisionType::AuditSummary,
+        "org-1",
+        DecisionOutputs {
+            summary: "audit".to_string(),
+            findings: vec![finding],
+            metrics: GovernanceMetrics {
+                events_analyzed: 10,
+                time_range: DateRange {
+                    start: "2024-01-01T00:00:00Z".parse().unwrap(),
+                    end: "2024-01-02T00:00:00Z".parse().unwrap(),
+                },
+                coverage_percentage: 100.0,
+                policies_evaluated: 1,
+                compliance_rate: 90.0,
+                findings_by_severity: HashMap::new(),
+                trend: TrendDirection::Stable,
+            },
+            recommendations: vec![],
+            data_refs: vec![],
+        },
+        default_confidence(0.9, 0.9),
+        vec![],
+        execution_ref_from_request(None, None, None, InvocationSource::Api),
+        &serde_json::json!({"organization_id": "org-1"}),
+    )
+}
+
+#[actix_web::test]
+#[ignore] // Requires Postgres at DATABASE_URL
+async fn test_recorded_evaluations_are_preferred_over_action_names() {
+    let pool = database().await;
+    let mut conn = pool.acquire().await.unwrap();
+
+    // A minute of its own, long ago, so other rows do not leak into the window
+    let suffix = Uuid::new_v4();
+    let minute = (suffix.as_u128() % 500_000) as i64;
+    let start = Utc.with_ymd_and_hms(2001, 1, 1, 0, 0, 0).unwrap() + Duration::minutes(minute);
+    let (from, to) = (start.to_rfc3339(), (start + Duration::seconds(59)).to_rfc3339());
+
+    let organization_id = seed_organization(&pool, "adherence").await;
+    let other_org = seed_organization(&pool, "adherence").await;
+    let insert_policy = |org: Uuid| {
+        sqlx::query_as::<_, (Uuid,)>(
+            "INSERT INTO policies (name, policy_type, organization_id) VALUES ('adherence', 'cost', $1) RETURNING id",
+        )
+        .bind(org)
+        .fetch_one(&pool)
+    };
+    let (policy_id,) = insert_policy(organization_id).await.unwrap();
+    let (foreign_policy,) = insert_policy(other_org).await.unwrap();
+    let member = seed_user(&pool, "adherence").await;
+    seed_member(&pool, organization_id, member, "member").await;
+
+    // The member's failed evaluation against another organization's policy is not counted
+    let violation = |severity: &str| serde_json::json!({"rule_violated": "max_cost_per_request", "severity": severity});
+    for (policy, passed, violations) in [
+        (policy_id, true, vec![]),
+        (policy_id, true, vec![]),
+        (policy_id, true, vec![]),
+        (policy_id, false, vec![violation("high"), violation("medium")]),
+        (foreign_policy, false, vec![violation("critical")]),
+    ] {
+        sqlx::query(
+            r#"
+            INSERT INTO policy_evaluations
+                (policy_id, policy_version, context_hash, passed, violation_count, violations, subject_id, evaluated_at)
+            VALUES ($1, 1, $2, $3, $4, $5, $6, $7)
+            "#,
+        )
+        .bind(policy)
+        .bind("0".repeat(64))
+        .bind(passed)
+        .bind(violations.len() as i32)
+        .bind(serde_json::Value::Array(violations))
+        .bind(member)
+        .bind(start + Duration::seconds(10))
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+
+    // The action-name heuristic sees two 'violations' out of four events on the
+    // organization's policy; the other organization's violation is not its own
+    for (policy, action) in [
+        (policy_id, "policy_violation"),
+        (policy_id, "policy_violation_acknowledged"),
+        (policy_id, "policy_update"),
+        (policy_id, "policy_create"),
+        (foreign_policy, "policy_violation"),
+    ] {
+        sqlx::query(
+            "INSERT INTO audit_logs (timestamp, action, resource_type, resource_id, checksum) VALUES ($1, $2, 'policy', $3, '')",
+        )
+        .bind((start + Duration::seconds(20)).naive_utc())
+        .bind(action)
+        .bind(policy.to_string())
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+
+    let structured = analyze_policy_adherence(&mut conn, &organization_id.to_string(), &from, &to, None)
+        .await
+        .unwrap();
+    assert_eq!(structured.policies_evaluated, 4);
+    assert_eq!(structured.violations_found, 2);
+    assert_eq!(structured.high_severity_violations, 1);
+    assert_eq!(structured.compliance_rate, 75.0);
+
+    let heuristic = heuristic_policy_adherence(&mut conn, &organization_id.to_string(), &from, &to, None)
+        .await
+        .unwrap();
+    assert_eq!(heuristic.policies_evaluated, 4);
+    assert_eq!(heuristic.violations_found, 2);
+    assert_eq!(heuristic.compliance_rate, 50.0);
+
+    // An organization without recorded evaluations falls back to its own audit events
+    let other = analyze_policy_adherence(&mut conn, &Uuid::new_v4().to_string(), &from, &to, None)
+        .await
+        .unwrap();
+    assert_eq!(other.policies_evaluated, 0);
+    assert_eq!(other.violations_found, 0);
+    assert!(other.violations_by_severity.is_empty());
+
+    // Excluding policies leaves the recorded evaluations out as well
+    let without_policies = AuditScopeRequest {
+        teams: None,
+        users: None,
+        policy_types: None,
+        resource_types: None,
+        exclude_resource_types: Some(vec!["policy".to_string()]),
+        exclude_actions: None,
+    };
+    let excluded = analyze_policy_adherence(&mut conn, &organization_id.to_string(), &from, &to, Some(&without_policies))
+        .await
+        .unwrap();
+    assert_eq!(excluded.policies_evaluated, 0);
+    assert!(excluded.violations_by_severity.is_empty());
+}
+
+#[actix_web::test]
+#[ignore] // Requires Postgres at DATABASE_URL
+async fn test_excluded_rows_do_not_count_as_analyzed_events() {
+    let pool = database().await;
+    let mut conn = pool.acquire().await.unwrap();
+
+    // A minute of its own, long ago, so other rows do not leak into the window
+    let suffix = Uuid::new_v4();
+    let minute = (suffix.as_u128() % 500_000) as i64;
+    let start = Utc.with_ymd_and_hms(2002, 1, 1, 0, 0, 0).unwrap() + Duration::minutes(minute);
+    let (from, to) = (start.to_rfc3339(), (start + Duration::seconds(59)).to_rfc3339());
+
+    let (kept, test_org) = (format!("kept-{}", suffix), format!("test-org-{}", suffix));
+    for (action, resource_type) in [
+        ("update", &kept),
+        ("update", &kept),
+        ("heartbeat", &kept),
+        ("update", &test_org),
+        ("update", &test_org),
+    ] {
+        sqlx::query(
+            "INSERT INTO audit_logs (timestamp, action, resource_type, resource_id, checksum) VALUES ($1, $2, $3, 'r-1', '')",
+        )
+        .bind((start + Duration::seconds(30)).naive_utc())
+        .bind(action)
+        .bind(resource_type)
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+
+    let scope = |exclude_resource_types: Option<Vec<String>>, exclude_actions: Option<Vec<String>>| AuditScopeRequest {
+        teams: None,
+        users: None,
+        policy_types: None,
+        resource_types: None,
+        exclude_resource_types,
+        exclude_actions,
+    };
+
+    let all = aggregate_audit_data(&mut conn, "org-1", &from, &to, None).await.unwrap();
+    assert_eq!(all.total_events, 5);
+
+    let without_test_org = scope(Some(vec![test_org.clone()]), None);
+    let data = aggregate_audit_data(&mut conn, "org-1", &from, &to, Some(&without_test_org))
+        .await
+        .unwrap();
+    assert_eq!(data.total_events, 3);
+    assert!(!data.events_by_resource.contains_key(&test_org));
+    // Excluded rows are left out on purpose, not missing
+    assert_eq!(data.time_range_coverage, all.time_range_coverage);
+
+    let without_heartbeats = scope(Some(vec![test_org]), Some(vec!["heartbeat".to_string()]));
+    let data = aggregate_audit_data(&mut conn, "org-1", &from, &to, Some(&without_heartbeats))
+        .await
+        .unwrap();
+    assert_eq!(data.total_events, 2);
+    assert_eq!(data.events_by_action.get("update"), Some(&2));
+    assert!(!data.events_by_action.contains_key("heartbeat"));
+}
+
+#[actix_web::test]
+#[ignore] // Requires Postgres at DATABASE_URL
+async fn test_recorded_audit_round_trips_through_list_and_get() {
+    let pool = database().await;
+    let user_id = seed_user(&pool, "auditor").await;
+    let organization_id = seed_organization(&pool, "audited").await;
+    seed_member(&pool, organization_id, user_id, "admin").await;
+
+    let mut event = event_with_finding("finding-1");
+    event.organization_id = organization_id.to_string();
+    record_decision_event(&pool, user_id, GOVERNANCE_AUDIT_RESOURCE, &event, serde_json::json!({}))
+        .await
+        .unwrap();
+
+    let app = actix_web::test::init_service(
+        actix_web::App::new()
+            .app_data(web::Data::new(pool.clone()))
+            .service(list_governance_audits)
+            .service(get_governance_audit),
+    )
+    .await;
+
+    // The TIMESTAMP column decodes and is reported in UTC
+    let req = actix_web::test::TestRequest::get()
+        .uri(&format!("/governance/audits?organization_id={}", organization_id))
+        .insert_header(("X-User-Id", user_id.to_string()))
+        .to_request();
+    let listed: serde_json::Value = actix_web::test::call_and_read_body_json(&app, req).await;
+    let audits = listed["data"]["audits"].as_array().unwrap();
+    assert_eq!(audits.len(), 1);
+    assert!(audits[0]["timestamp"].as_str().unwrap().ends_with('Z'));
+
+    let req = actix_web::test::TestRequest::get()
+        .uri(&format!("/governance/audit/{}", event.id))
+        .insert_header(("X-User-Id", user_id.to_string()))
+        .to_request();
+    let fetched: serde_json::Value = actix_web::test::call_and_read_body_json(&app, req).await;
+    assert_eq!(fetched["data"]["id"], audits[0]["id"]);
+    assert_eq!(fetched["data"]["timestamp"], audits[0]["timestamp"]);
+}
@@ -0,0 +1,7 @@
+//! Audit service tests against the migrated database at `DATABASE_URL`
+//!
+//! Run with `cargo test -p audit-service --test integration -- --ignored`.
+
+mod audit;
+mod change_impact;
+mod governance;
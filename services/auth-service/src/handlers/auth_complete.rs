@@ -4,8 +4,11 @@ use validator::Validate;
 use sqlx::PgPool;
 use redis::Client as RedisClient;
 use uuid::Uuid;
+use llm_governance_common::auth::ensure_can_login;
 use llm_governance_common::{AppError, Result, ApiResponse};
 use crate::config::Config;
+use crate::models::PublicUser;
+use crate::services::auth_audit::{audit_auth_event, client_ip, AuthAuditEntry, AuthEvent};
 use crate::services::auth_service::AuthService;
 use crate::services::jwt_service::JwtService;
 use sha2::{Sha256, Digest};
@@ -55,19 +58,10 @@ pub struct AuthResponse {
     pub refresh_token: String,
     pub token_type: String,
     pub expires_in: i64,
-    pub user: UserInfo,
+    pub user: PublicUser,
     pub requires_mfa: bool,
 }
 
-#[derive(Debug, Serialize)]
-pub struct UserInfo {
-    pub id: Uuid,
-    pub email: String,
-    pub name: String,
-    pub status: String,
-    pub mfa_enabled: bool,
-}
-
 // Helper function to hash tokens with SHA-256
 fn hash_token(token: &str) -> String {
     let mut hasher = Sha256::new();
@@ -100,14 +94,9 @@ pub async fn register(
         .register_user(&req.email, &req.password, &req.name)
         .await?;
 
-    Ok(HttpResponse::Created().json(ApiResponse::success(
-        serde_json::json!({
-            "id": user.id,
-            "email": user.email,
-            "name": user.name,
-            "status": user.status,
-            "message": "User registered successfully. Please verify your email."
-        })
+    Ok(HttpResponse::Created().json(ApiResponse::success_with_message(
+        PublicUser::from(user),
+        "User registered successfully. Please verify your email.",
     )))
 }
 
@@ -117,16 +106,38 @@ pub async fn login(
     redis_client: web::Data<RedisClient>,
     config: web::Data<Config>,
     req: web::Json<LoginRequest>,
+    http_req: HttpRequest,
 ) -> Result<impl Responder> {
     req.validate()
         .map_err(|e| AppError::Validation(format!("{}", e)))?;
 
+    let ip_address = client_ip(&http_req);
     let auth_service = AuthService::new(pool.get_ref().clone());
-    let user = auth_service
-        .authenticate_user(&req.email, &req.password)
-        .await?;
+    let user = match auth_service.authenticate_user(&req.email, &req.password).await {
+        Ok(user) => user,
+        Err(e) => {
+            audit_auth_event(
+                pool.get_ref(),
+                AuthAuditEntry::failure(AuthEvent::Login, None, ip_address, &e.to_string())
+                    .with_detail("email", req.email.as_str()),
+            )
+            .await;
+            return Err(e);
+        }
+    };
+
+    // Organizations that require MFA get no session until the user enrolls
+    if let Err(e) = auth_service.ensure_mfa_enrolled(user.id, user.mfa_enabled).await {
+        audit_auth_event(
+            pool.get_ref(),
+            AuthAuditEntry::failure(AuthEvent::Login, Some(user.id), ip_address, "MFA enrollment required"),
+        )
+        .await;
+        return Err(e);
+    }
 
-    // If MFA is enabled, create temporary session and require MFA
+    // If MFA is enabled, create temporary session and require MFA; the login
+    // is audited once the second factor is checked
     if user.mfa_enabled {
         let session_id = Uuid::new_v4().to_string();
 
@@ -155,9 +166,16 @@ pub async fn login(
         )));
     }
 
+    audit_auth_event(
+        pool.get_ref(),
+        AuthAuditEntry::success(AuthEvent::Login, Some(user.id), ip_address)
+            .with_detail("mfa", false),
+    )
+    .await;
+
     // Generate tokens
-    let jwt_service = JwtService::new(&config.jwt_secret, config.jwt_expiration);
-    let access_token = jwt_service.generate_token(user.id, &user.email)
+    let jwt_service = JwtService::from_config(&config);
+    let access_token = jwt_service.generate_token(user.id, &user.email, user.token_version)
         .map_err(|e| AppError::Internal(format!("Failed to generate token: {}", e)))?;
 
     let refresh_token = jwt_service.generate_refresh_token();
@@ -179,13 +197,7 @@ pub async fn login(
         refresh_token,
         token_type: "Bearer".to_string(),
         expires_in: config.jwt_expiration,
-        user: UserInfo {
-            id: user.id,
-            email: user.email,
-            name: user.name,
-            status: user.status,
-            mfa_enabled: user.mfa_enabled,
-        },
+        user: PublicUser::from(user),
         requires_mfa: false,
     })))
 }
@@ -196,6 +208,7 @@ pub async fn verify_mfa(
     redis_client: web::Data<RedisClient>,
     config: web::Data<Config>,
     req: web::Json<MfaVerifyRequest>,
+    http_req: HttpRequest,
 ) -> Result<impl Responder> {
     use crate::services::mfa_service_impl::MfaService;
 
@@ -215,16 +228,33 @@ pub async fn verify_mfa(
         .map_err(|e| AppError::Internal(format!("Invalid user ID: {}", e)))?;
 
     // Verify MFA code
+    let ip_address = client_ip(&http_req);
     let mfa_service = MfaService::new(pool.get_ref().clone(), config.mfa_issuer.clone());
-    mfa_service.verify_mfa(user_id, &req.code).await?;
+    if let Err(e) = mfa_service.verify_mfa(user_id, &req.code).await {
+        audit_auth_event(
+            pool.get_ref(),
+            AuthAuditEntry::failure(AuthEvent::Login, Some(user_id), ip_address, &e.to_string())
+                .with_detail("mfa", true),
+        )
+        .await;
+        return Err(e);
+    }
 
     // Get user details
     let auth_service = AuthService::new(pool.get_ref().clone());
     let user = auth_service.get_user_by_id(user_id).await?;
+    ensure_can_login(&user.status)?;
+
+    audit_auth_event(
+        pool.get_ref(),
+        AuthAuditEntry::success(AuthEvent::Login, Some(user.id), ip_address)
+            .with_detail("mfa", true),
+    )
+    .await;
 
     // Generate tokens
-    let jwt_service = JwtService::new(&config.jwt_secret, config.jwt_expiration);
-    let access_token = jwt_service.generate_token(user.id, &user.email)
+    let jwt_service = JwtService::from_config(&config);
+    let access_token = jwt_service.generate_token(user.id, &user.email, user.token_version)
         .map_err(|e| AppError::Internal(format!("Failed to generate token: {}", e)))?;
 
     let refresh_token = jwt_service.generate_refresh_token();
@@ -252,13 +282,7 @@ pub async fn verify_mfa(
         refresh_token,
         token_type: "Bearer".to_string(),
         expires_in: config.jwt_expiration,
-        user: UserInfo {
-            id: user.id,
-            email: user.email,
-            name: user.name,
-            status: user.status,
-            mfa_enabled: user.mfa_enabled,
-        },
+        user: PublicUser::from(user),
         requires_mfa: false,
     })))
 }
@@ -272,24 +296,29 @@ pub async fn refresh_token(
     let token_hash = hash_token(&req.refresh_token);
 
     // Verify refresh token exists and is valid
-    let result: Option<(Uuid, String, String)> = sqlx::query_as(
+    let result: Option<(Uuid, String, bool, i32)> = sqlx::query_as(
         r#"
-        SELECT u.id, u.email, u.name
+        SELECT u.id, u.email, u.mfa_enabled, u.token_version
         FROM sessions s
         JOIN users u ON s.user_id = u.id
-        WHERE s.token_hash = $1 AND s.expires_at > NOW()
+        WHERE s.token_hash = $1 AND s.expires_at > NOW() AND u.status = 'active'
         "#,
     )
     .bind(&token_hash)
     .fetch_optional(pool.get_ref())
     .await?;
 
-    let (user_id, email, name) = result
+    let (user_id, email, mfa_enabled, token_version) = result
         .ok_or_else(|| AppError::Auth("Invalid or expired refresh token".to_string()))?;
 
+    // Sessions from before an organization turned on require_mfa end here
+    AuthService::new(pool.get_ref().clone())
+        .ensure_mfa_enrolled(user_id, mfa_enabled)
+        .await?;
+
     // Generate new access token
-    let jwt_service = JwtService::new(&config.jwt_secret, config.jwt_expiration);
-    let access_token = jwt_service.generate_token(user_id, &email)
+    let jwt_service = JwtService::from_config(&config);
+    let access_token = jwt_service.generate_token(user_id, &email, token_version)
         .map_err(|e| AppError::Internal(format!("Failed to generate token: {}", e)))?;
 
     Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
@@ -315,7 +344,7 @@ pub async fn logout(
         .ok_or_else(|| AppError::Unauthorized)?;
 
     // Decode JWT to get user_id and expiration
-    let jwt_service = JwtService::new(&config.jwt_secret, config.jwt_expiration);
+    let jwt_service = JwtService::from_config(&config);
     let claims = jwt_service
         .decode_token(token)
         .map_err(|_| AppError::Unauthorized)?;
@@ -353,6 +382,19 @@ pub async fn logout(
         .await
         .map_err(|e| AppError::Redis(e))?;
 
+    let ip_address = client_ip(&req);
+    audit_auth_event(
+        pool.get_ref(),
+        AuthAuditEntry::success(AuthEvent::TokenRevocation, Some(user_id), ip_address.clone())
+            .with_detail("token_expires_at", claims.exp),
+    )
+    .await;
+    audit_auth_event(
+        pool.get_ref(),
+        AuthAuditEntry::success(AuthEvent::Logout, Some(user_id), ip_address),
+    )
+    .await;
+
     Ok(HttpResponse::Ok().json(ApiResponse::success(
         serde_json::json!({"message": "Logged out successfully"})
     )))
@@ -413,9 +455,15 @@ pub async fn change_password(
         .ok_or_else(|| AppError::Unauthorized)?;
 
     let auth_service = AuthService::new(pool.get_ref().clone());
-    auth_service
+    let result = auth_service
         .change_password(user_id, &req.current_password, &req.new_password)
-        .await?;
+        .await;
+    audit_auth_event(
+        pool.get_ref(),
+        AuthAuditEntry::from_result(AuthEvent::PasswordChange, Some(user_id), client_ip(&http_req), &result),
+    )
+    .await;
+    result?;
 
     Ok(HttpResponse::Ok().json(ApiResponse::success(
         serde_json::json!({"message": "Password changed successfully"})
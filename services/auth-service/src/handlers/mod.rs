@@ -22,6 +22,8 @@ impl ErrorResponse {
 }
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(llm_governance_common::metrics::metrics_endpoint);
+    cfg.service(llm_governance_common::versioning::version_endpoint);
     cfg.service(
         web::scope("/api/v1")
             .configure(health::configure)
@@ -0,0 +1,7 @@
+//! Login, MFA, OAuth and token issuance, served by `main.rs`
+
+pub mod config;
+pub mod handlers;
+pub mod middleware;
+pub mod models;
+pub mod services;
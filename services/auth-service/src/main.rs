@@ -1,25 +1,24 @@
 use actix_web::{web, App, HttpServer};
-use tracing::{info, Level};
-use tracing_subscriber;
+use tracing::info;
 
-mod config;
-mod handlers;
-mod middleware;
-mod models;
-mod services;
-
-use config::Config;
+use auth_service::config::Config;
+use auth_service::handlers;
+use llm_governance_common::metrics::RequestMetrics;
+use llm_governance_common::request_context::RequestContext;
+use llm_governance_common::telemetry;
+use llm_governance_common::versioning::ApiVersioning;
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
-    // Initialize tracing
-    tracing_subscriber::fmt()
-        .with_max_level(Level::INFO)
-        .init();
+    dotenv::dotenv().ok();
+    telemetry::init("auth-service").expect("Failed to initialize telemetry");
 
     // Load configuration
-    dotenv::dotenv().ok();
-    let config = Config::from_env().expect("Failed to load configuration");
+    let config = Config::from_env()
+        .unwrap_or_else(|e| panic!("Failed to load configuration: {}", e));
+    if let Err(problems) = config.validate() {
+        panic!("Invalid configuration:\n  - {}", problems.join("\n  - "));
+    }
 
     info!("Starting auth-service on {}:{}", config.host, config.port);
 
@@ -33,15 +32,21 @@ async fn main() -> std::io::Result<()> {
         .expect("Failed to create Redis client");
 
     // Start HTTP server
-    HttpServer::new(move || {
+    let result = HttpServer::new(move || {
         App::new()
             .app_data(web::Data::new(db_pool.clone()))
             .app_data(web::Data::new(redis_client.clone()))
             .app_data(web::Data::new(config.clone()))
+            .wrap(RequestContext)
             .wrap(tracing_actix_web::TracingLogger::default())
+            .wrap(RequestMetrics::new("auth-service"))
+            .wrap(ApiVersioning::new())
             .configure(handlers::configure)
     })
     .bind((config.host.as_str(), config.port))?
     .run()
-    .await
+    .await;
+
+    telemetry::shutdown();
+    result
 }
@@ -3,20 +3,67 @@ use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
 
+/// Full user row; never return this from a handler, convert to `PublicUser`
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct User {
     pub id: Uuid,
     pub email: String,
+    #[serde(skip_serializing)]
     pub password_hash: String,
     pub name: String,
     pub is_active: bool,
     pub is_verified: bool,
     pub mfa_enabled: bool,
+    #[serde(skip_serializing)]
     pub mfa_secret: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// User fields safe to include in API responses
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublicUser {
+    pub id: Uuid,
+    pub email: String,
+    pub name: String,
+    pub is_active: bool,
+    pub is_verified: bool,
+    pub mfa_enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<User> for PublicUser {
+    fn from(user: User) -> Self {
+        Self {
+            id: user.id,
+            email: user.email,
+            name: user.name,
+            is_active: user.is_active,
+            is_verified: user.is_verified,
+            mfa_enabled: user.mfa_enabled,
+            created_at: user.created_at,
+            updated_at: user.updated_at,
+        }
+    }
+}
+
+impl From<crate::services::auth_service::User> for PublicUser {
+    fn from(user: crate::services::auth_service::User) -> Self {
+        Self {
+            id: user.id,
+            email: user.email,
+            name: user.name,
+            is_active: user.status == "active",
+            // Accounts stay pending until their email is verified
+            is_verified: user.status != "pending",
+            mfa_enabled: user.mfa_enabled,
+            created_at: user.created_at,
+            updated_at: user.updated_at,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct RefreshToken {
     pub id: Uuid,
@@ -32,8 +79,48 @@ pub struct OAuthProvider {
     pub user_id: Uuid,
     pub provider: String,
     pub provider_user_id: String,
+    #[serde(skip_serializing)]
     pub access_token: Option<String>,
+    #[serde(skip_serializing)]
     pub refresh_token: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user() -> User {
+        User {
+            id: Uuid::new_v4(),
+            email: "alice@example.com".to_string(),
+            password_hash: "$argon2id$v=19$secret-hash".to_string(),
+            name: "Alice".to_string(),
+            is_active: true,
+            is_verified: true,
+            mfa_enabled: true,
+            mfa_secret: Some("JBSWY3DPEHPK3PXP".to_string()),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_public_user_contains_no_secrets() {
+        let json = serde_json::to_string(&PublicUser::from(user())).unwrap();
+
+        assert!(json.contains("alice@example.com"));
+        assert!(!json.contains("password"));
+        assert!(!json.contains("argon2"));
+        assert!(!json.contains("mfa_secret"));
+        assert!(!json.contains("JBSWY3DPEHPK3PXP"));
+    }
+
+    #[test]
+    fn test_user_serialization_skips_secrets() {
+        let json = serde_json::to_value(user()).unwrap();
+        assert!(json.get("password_hash").is_none());
+        assert!(json.get("mfa_secret").is_none());
+    }
+}
@@ -0,0 +1,217 @@
+//! Audit trail for security-sensitive authentication actions
+//!
+//! Every login, logout, MFA change, password change and token revocation is
+//! written to `audit_logs`, where the insert trigger chains it into the
+//! tamper-evident checksum sequence. Entries carry the acting user, source IP
+//! and outcome; passwords, codes and tokens are never recorded.
+
+use actix_web::HttpRequest;
+use serde_json::json;
+use sqlx::PgPool;
+use std::net::{IpAddr, SocketAddr};
+use uuid::Uuid;
+
+use llm_governance_common::Result;
+
+/// Resource type used for every authentication audit entry
+pub const AUTH_RESOURCE_TYPE: &str = "auth";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthEvent {
+    Login,
+    Logout,
+    MfaEnable,
+    MfaDisable,
+    PasswordChange,
+    TokenRevocation,
+}
+
+impl AuthEvent {
+    pub fn action(&self) -> &'static str {
+        match self {
+            AuthEvent::Login => "auth.login",
+            AuthEvent::Logout => "auth.logout",
+            AuthEvent::MfaEnable => "auth.mfa_enable",
+            AuthEvent::MfaDisable => "auth.mfa_disable",
+            AuthEvent::PasswordChange => "auth.password_change",
+            AuthEvent::TokenRevocation => "auth.token_revocation",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthOutcome {
+    Success,
+    Failure,
+}
+
+impl AuthOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuthOutcome::Success => "success",
+            AuthOutcome::Failure => "failure",
+        }
+    }
+}
+
+/// One row destined for `audit_logs`
+#[derive(Debug, Clone)]
+pub struct AuthAuditEntry {
+    pub event: AuthEvent,
+    pub user_id: Option<Uuid>,
+    pub ip_address: Option<String>,
+    pub outcome: AuthOutcome,
+    pub details: serde_json::Value,
+}
+
+impl AuthAuditEntry {
+    pub fn new(event: AuthEvent, user_id: Option<Uuid>, ip_address: Option<String>, outcome: AuthOutcome) -> Self {
+        Self {
+            event,
+            user_id,
+            ip_address,
+            outcome,
+            details: json!({ "outcome": outcome.as_str() }),
+        }
+    }
+
+    pub fn success(event: AuthEvent, user_id: Option<Uuid>, ip_address: Option<String>) -> Self {
+        Self::new(event, user_id, ip_address, AuthOutcome::Success)
+    }
+
+    /// Failed attempt; `reason` is the error message returned to the caller,
+    /// which never contains credentials
+    pub fn failure(event: AuthEvent, user_id: Option<Uuid>, ip_address: Option<String>, reason: &str) -> Self {
+        Self::new(event, user_id, ip_address, AuthOutcome::Failure).with_detail("reason", reason)
+    }
+
+    /// Entry reflecting the outcome of an operation
+    pub fn from_result<T>(
+        event: AuthEvent,
+        user_id: Option<Uuid>,
+        ip_address: Option<String>,
+        result: &Result<T>,
+    ) -> Self {
+        match result {
+            Ok(_) => Self::success(event, user_id, ip_address),
+            Err(e) => Self::failure(event, user_id, ip_address, &e.to_string()),
+        }
+    }
+
+    pub fn with_detail(mut self, key: &str, value: impl Into<serde_json::Value>) -> Self {
+        if let Some(map) = self.details.as_object_mut() {
+            map.insert(key.to_string(), value.into());
+        }
+        self
+    }
+
+    /// Audited user, or `unknown` when the attempt could not be attributed
+    pub fn resource_id(&self) -> String {
+        self.user_id
+            .map(|id| id.to_string())
+            .unwrap_or_else(|| "unknown".to_string())
+    }
+}
+
+/// Client IP from the forwarded headers or peer address, without the port
+pub fn client_ip(req: &HttpRequest) -> Option<String> {
+    let addr = req.connection_info().realip_remote_addr()?.to_string();
+
+    addr.parse::<IpAddr>()
+        .ok()
+        .or_else(|| addr.parse::<SocketAddr>().ok().map(|s| s.ip()))
+        .map(|ip| ip.to_string())
+}
+
+/// Record an authentication event
+///
+/// A failed audit write is logged rather than surfaced so it cannot turn a
+/// successful login or logout into an error for the user.
+pub async fn audit_auth_event(pool: &PgPool, entry: AuthAuditEntry) {
+    let result = sqlx::query(
+        r#"
+        INSERT INTO audit_logs (user_id, action, resource_type, resource_id, ip_address, details, checksum)
+        VALUES ($1, $2, $3, $4, $5::inet, $6, '')
+        "#,
+    )
+    .bind(entry.user_id)
+    .bind(entry.event.action())
+    .bind(AUTH_RESOURCE_TYPE)
+    .bind(entry.resource_id())
+    .bind(&entry.ip_address)
+    .bind(&entry.details)
+    .execute(pool)
+    .await;
+
+    if let Err(e) = result {
+        tracing::warn!(
+            "Failed to record audit event {} for {}: {}",
+            entry.event.action(),
+            entry.resource_id(),
+            e
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+    use llm_governance_common::AppError;
+
+    #[test]
+    fn test_failed_login_produces_failure_entry() {
+        let password = "hunter2-secret";
+        let result: Result<()> = Err(AppError::Auth("Invalid credentials".to_string()));
+
+        let entry = AuthAuditEntry::from_result(AuthEvent::Login, None, Some("203.0.113.7".to_string()), &result)
+            .with_detail("email", "alice@example.com");
+
+        assert_eq!(entry.event.action(), "auth.login");
+        assert_eq!(entry.outcome, AuthOutcome::Failure);
+        assert_eq!(entry.resource_id(), "unknown");
+        assert_eq!(entry.details["outcome"], "failure");
+        assert!(entry.details["reason"].as_str().unwrap().contains("Invalid credentials"));
+        assert!(!entry.details.to_string().contains(password));
+    }
+
+    #[test]
+    fn test_rejected_mfa_code_produces_login_failure() {
+        let user_id = Uuid::new_v4();
+        let code = "123456";
+
+        let entry = AuthAuditEntry::failure(AuthEvent::Login, Some(user_id), None, "Invalid MFA code")
+            .with_detail("mfa", true);
+
+        assert_eq!(entry.event.action(), "auth.login");
+        assert_eq!(entry.outcome, AuthOutcome::Failure);
+        assert_eq!(entry.details["mfa"], true);
+        assert!(!entry.details.to_string().contains(code));
+    }
+
+    #[test]
+    fn test_successful_mfa_enable_produces_success_entry() {
+        let user_id = Uuid::new_v4();
+        let result: Result<()> = Ok(());
+
+        let entry = AuthAuditEntry::from_result(AuthEvent::MfaEnable, Some(user_id), None, &result);
+
+        assert_eq!(entry.event.action(), "auth.mfa_enable");
+        assert_eq!(entry.outcome, AuthOutcome::Success);
+        assert_eq!(entry.resource_id(), user_id.to_string());
+        assert_eq!(entry.details, json!({ "outcome": "success" }));
+    }
+
+    #[test]
+    fn test_client_ip_strips_port() {
+        let req = TestRequest::default()
+            .insert_header(("X-Forwarded-For", "198.51.100.4"))
+            .to_http_request();
+        assert_eq!(client_ip(&req).as_deref(), Some("198.51.100.4"));
+
+        let req = TestRequest::default()
+            .peer_addr("192.0.2.1:54321".parse().unwrap())
+            .to_http_request();
+        assert_eq!(client_ip(&req).as_deref(), Some("192.0.2.1"));
+    }
+}
@@ -4,6 +4,7 @@ use argon2::{
     password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
     Argon2,
 };
+use llm_governance_common::auth::ensure_can_login;
 use llm_governance_common::{AppError, Result};
 use chrono::Utc;
 use sha2::{Sha256, Digest};
@@ -16,10 +17,18 @@ pub struct User {
     pub name: String,
     pub status: String,
     pub mfa_enabled: bool,
+    /// Must match the `token_version` claim of an access token for it to be accepted
+    pub token_version: i32,
     pub created_at: chrono::DateTime<Utc>,
     pub updated_at: chrono::DateTime<Utc>,
 }
 
+/// A user without MFA must enroll before signing in when any of their
+/// organizations requires it
+pub fn mfa_enrollment_required(mfa_enabled: bool, orgs_requiring_mfa: &[Uuid]) -> bool {
+    !mfa_enabled && !orgs_requiring_mfa.is_empty()
+}
+
 pub struct AuthService {
     pool: PgPool,
 }
@@ -55,7 +64,7 @@ impl AuthService {
             r#"
             INSERT INTO users (email, password_hash, name, status, mfa_enabled)
             VALUES ($1, $2, $3, 'pending', false)
-            RETURNING id, email, password_hash, name, status, mfa_enabled, created_at, updated_at
+            RETURNING id, email, password_hash, name, status, mfa_enabled, token_version, created_at, updated_at
             "#,
         )
         .bind(email)
@@ -81,7 +90,7 @@ impl AuthService {
         // Fetch user by email
         let user = sqlx::query_as::<_, User>(
             r#"
-            SELECT id, email, password_hash, name, status, mfa_enabled, created_at, updated_at
+            SELECT id, email, password_hash, name, status, mfa_enabled, token_version, created_at, updated_at
             FROM users
             WHERE email = $1
             "#,
@@ -91,10 +100,7 @@ impl AuthService {
         .await?
         .ok_or_else(|| AppError::Auth("Invalid credentials".to_string()))?;
 
-        // Verify status
-        if user.status != "active" {
-            return Err(AppError::Auth(format!("Account is {}", user.status)));
-        }
+        ensure_can_login(&user.status)?;
 
         // Verify password
         let parsed_hash = PasswordHash::new(&user.password_hash)
@@ -110,7 +116,7 @@ impl AuthService {
     pub async fn get_user_by_id(&self, user_id: Uuid) -> Result<User> {
         let user = sqlx::query_as::<_, User>(
             r#"
-            SELECT id, email, password_hash, name, status, mfa_enabled, created_at, updated_at
+            SELECT id, email, password_hash, name, status, mfa_enabled, token_version, created_at, updated_at
             FROM users
             WHERE id = $1
             "#,
@@ -126,7 +132,7 @@ impl AuthService {
     pub async fn get_user_by_email(&self, email: &str) -> Result<User> {
         let user = sqlx::query_as::<_, User>(
             r#"
-            SELECT id, email, password_hash, name, status, mfa_enabled, created_at, updated_at
+            SELECT id, email, password_hash, name, status, mfa_enabled, token_version, created_at, updated_at
             FROM users
             WHERE email = $1
             "#,
@@ -139,6 +145,39 @@ impl AuthService {
         Ok(user)
     }
 
+    /// Active organizations of the user whose settings set `require_mfa`
+    pub async fn organizations_requiring_mfa(&self, user_id: Uuid) -> Result<Vec<Uuid>> {
+        let org_ids = sqlx::query_scalar(
+            r#"
+            SELECT o.id
+            FROM organizations o
+            JOIN organization_members om ON om.organization_id = o.id
+            WHERE om.user_id = $1
+            AND o.is_active = true
+            AND o.settings->>'require_mfa' = 'true'
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(org_ids)
+    }
+
+    /// `Forbidden` until a user without MFA enrolls, when any of their
+    /// organizations requires it
+    pub async fn ensure_mfa_enrolled(&self, user_id: Uuid, mfa_enabled: bool) -> Result<()> {
+        if mfa_enabled {
+            return Ok(());
+        }
+
+        let orgs_requiring_mfa = self.organizations_requiring_mfa(user_id).await?;
+        if mfa_enrollment_required(mfa_enabled, &orgs_requiring_mfa) {
+            return Err(AppError::Forbidden);
+        }
+        Ok(())
+    }
+
     pub async fn verify_email(&self, user_id: Uuid) -> Result<()> {
         sqlx::query(
             r#"
@@ -268,3 +307,34 @@ impl AuthService {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use llm_governance_common::auth::ensure_token_current;
+
+    #[test]
+    fn test_deactivation_blocks_login_and_reactivation_restores_it() {
+        assert!(ensure_can_login("active").is_ok());
+
+        // Deactivation sets status to inactive and bumps token_version
+        assert!(matches!(ensure_can_login("inactive"), Err(AppError::Auth(_))));
+        assert!(ensure_token_current("inactive", 1, 0).is_err());
+
+        // After reactivation new logins work, but tokens from before stay revoked
+        assert!(ensure_can_login("active").is_ok());
+        assert!(ensure_token_current("active", 1, 0).is_err());
+        assert!(ensure_token_current("active", 1, 1).is_ok());
+    }
+
+    #[test]
+    fn test_org_requiring_mfa_blocks_login_without_mfa() {
+        let strict_org = vec![Uuid::new_v4()];
+
+        assert!(mfa_enrollment_required(false, &strict_org));
+        assert!(!mfa_enrollment_required(true, &strict_org));
+
+        // Orgs that don't require MFA leave it opt-in
+        assert!(!mfa_enrollment_required(false, &[]));
+    }
+}
@@ -1,32 +1,49 @@
-use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
-use serde::{Deserialize, Serialize};
+use jsonwebtoken::{encode, EncodingKey, Header};
+use llm_governance_common::auth::TokenVerifier;
 use uuid::Uuid;
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Claims {
-    pub sub: String,
-    pub exp: usize,
-    pub iat: usize,
-    pub user_id: Uuid,
-    pub email: String,
-}
+use crate::config::Config;
+
+pub use llm_governance_common::auth::AccessClaims as Claims;
 
 pub struct JwtService {
     encoding_key: EncodingKey,
-    decoding_key: DecodingKey,
+    verifier: TokenVerifier,
     expiration: i64,
+    issuer: String,
+    audience: String,
 }
 
 impl JwtService {
-    pub fn new(secret: &str, expiration: i64) -> Self {
+    /// Tokens are issued for `audience` and only that audience is accepted
+    /// until `with_accepted_audiences` widens it
+    pub fn new(secret: &str, expiration: i64, issuer: &str, audience: &str) -> Self {
         Self {
             encoding_key: EncodingKey::from_secret(secret.as_bytes()),
-            decoding_key: DecodingKey::from_secret(secret.as_bytes()),
+            verifier: TokenVerifier::new(secret, issuer, audience),
             expiration,
+            issuer: issuer.to_string(),
+            audience: audience.to_string(),
         }
     }
 
-    pub fn generate_token(&self, user_id: Uuid, email: &str) -> Result<String, anyhow::Error> {
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(&config.jwt_secret, config.jwt_expiration, &config.jwt_issuer, &config.jwt_audience)
+            .with_accepted_audiences(config.accepted_audiences())
+    }
+
+    /// Accept tokens minted for any of `audiences` in addition to our own
+    pub fn with_accepted_audiences(mut self, audiences: Vec<String>) -> Self {
+        self.verifier = self.verifier.with_accepted_audiences(audiences);
+        self
+    }
+
+    pub fn generate_token(
+        &self,
+        user_id: Uuid,
+        email: &str,
+        token_version: i32,
+    ) -> Result<String, anyhow::Error> {
         let now = chrono::Utc::now().timestamp() as usize;
         let exp = (chrono::Utc::now().timestamp() + self.expiration) as usize;
 
@@ -34,8 +51,11 @@ impl JwtService {
             sub: user_id.to_string(),
             exp,
             iat: now,
+            iss: self.issuer.clone(),
+            aud: self.audience.clone(),
             user_id,
             email: email.to_string(),
+            token_version,
         };
 
         encode(&Header::default(), &claims, &self.encoding_key)
@@ -43,13 +63,92 @@ impl JwtService {
     }
 
     pub fn verify_token(&self, token: &str) -> Result<Claims, anyhow::Error> {
-        let token_data = decode::<Claims>(token, &self.decoding_key, &Validation::default())
-            .map_err(|e| anyhow::anyhow!("Invalid token: {}", e))?;
-
-        Ok(token_data.claims)
+        self.verifier.verify(token).map_err(|e| anyhow::anyhow!("{}", e))
     }
 
     pub fn generate_refresh_token(&self) -> String {
         Uuid::new_v4().to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use llm_governance_common::auth::{DEFAULT_TOKEN_AUDIENCE, DEFAULT_TOKEN_ISSUER};
+
+    const SECRET: &str = "test-secret";
+    const ISSUER: &str = "llm-governance-auth";
+
+    fn service(audience: &str) -> JwtService {
+        JwtService::new(SECRET, 3600, ISSUER, audience)
+    }
+
+    #[test]
+    fn test_correct_audience_passes() {
+        let jwt = service("llm-governance-api");
+        let token = jwt.generate_token(Uuid::new_v4(), "a@example.com", 0).unwrap();
+
+        let claims = jwt.verify_token(&token).unwrap();
+        assert_eq!(claims.aud, "llm-governance-api");
+        assert_eq!(claims.iss, ISSUER);
+    }
+
+    #[test]
+    fn test_wrong_audience_is_rejected() {
+        let token = service("billing-api")
+            .generate_token(Uuid::new_v4(), "a@example.com", 0)
+            .unwrap();
+
+        assert!(service("llm-governance-api").verify_token(&token).is_err());
+
+        // Explicitly shared audiences are accepted
+        let shared = service("llm-governance-api").with_accepted_audiences(vec!["billing-api".to_string()]);
+        assert!(shared.verify_token(&token).is_ok());
+    }
+
+    #[test]
+    fn test_wrong_issuer_or_absent_audience_is_rejected() {
+        let foreign = JwtService::new(SECRET, 3600, "someone-else", "llm-governance-api")
+            .generate_token(Uuid::new_v4(), "a@example.com", 0)
+            .unwrap();
+        assert!(service("llm-governance-api").verify_token(&foreign).is_err());
+
+        let exp = (chrono::Utc::now().timestamp() + 3600) as usize;
+        let no_aud = encode(
+            &Header::default(),
+            &serde_json::json!({
+                "sub": "user",
+                "exp": exp,
+                "iat": exp - 3600,
+                "iss": ISSUER,
+                "user_id": Uuid::new_v4(),
+                "email": "a@example.com"
+            }),
+            &EncodingKey::from_secret(SECRET.as_bytes()),
+        )
+        .unwrap();
+        assert!(service("llm-governance-api").verify_token(&no_aud).is_err());
+    }
+
+    #[test]
+    fn test_minted_token_passes_gateway_verification() {
+        let config = Config {
+            jwt_secret: SECRET.to_string(),
+            ..Config::default()
+        };
+        let user_id = Uuid::new_v4();
+        let token = JwtService::from_config(&config)
+            .generate_token(user_id, "a@example.com", 2)
+            .unwrap();
+
+        // The api-gateway verifies with the same secret and default issuer/audience
+        let gateway = TokenVerifier::new(SECRET, DEFAULT_TOKEN_ISSUER, DEFAULT_TOKEN_AUDIENCE);
+        let claims = gateway.verify(&token).unwrap();
+        assert_eq!(claims.user_id, user_id);
+        assert_eq!(claims.token_version, 2);
+
+        // A gateway expecting another audience refuses it
+        let other = TokenVerifier::new(SECRET, DEFAULT_TOKEN_ISSUER, "billing-api");
+        assert!(other.verify(&token).is_err());
+    }
+}
@@ -5,6 +5,7 @@ use qrcode::QrCode;
 use qrcode::render::svg;
 use llm_governance_common::{AppError, Result};
 use chrono::Utc;
+use crate::services::auth_audit::{audit_auth_event, AuthAuditEntry, AuthEvent};
 
 pub struct MfaService {
     pool: PgPool,
@@ -77,7 +78,17 @@ impl MfaService {
         })
     }
 
-    pub async fn enable_mfa(&self, user_id: Uuid, verification_code: &str) -> Result<()> {
+    pub async fn enable_mfa(&self, user_id: Uuid, verification_code: &str, ip_address: Option<String>) -> Result<()> {
+        let result = self.apply_enable_mfa(user_id, verification_code).await;
+        audit_auth_event(
+            &self.pool,
+            AuthAuditEntry::from_result(AuthEvent::MfaEnable, Some(user_id), ip_address, &result),
+        )
+        .await;
+        result
+    }
+
+    async fn apply_enable_mfa(&self, user_id: Uuid, verification_code: &str) -> Result<()> {
         // Get user's secret
         let result: Option<(String,)> = sqlx::query_as(
             r#"
@@ -110,7 +121,17 @@ impl MfaService {
         Ok(())
     }
 
-    pub async fn disable_mfa(&self, user_id: Uuid, verification_code: &str) -> Result<()> {
+    pub async fn disable_mfa(&self, user_id: Uuid, verification_code: &str, ip_address: Option<String>) -> Result<()> {
+        let result = self.apply_disable_mfa(user_id, verification_code).await;
+        audit_auth_event(
+            &self.pool,
+            AuthAuditEntry::from_result(AuthEvent::MfaDisable, Some(user_id), ip_address, &result),
+        )
+        .await;
+        result
+    }
+
+    async fn apply_disable_mfa(&self, user_id: Uuid, verification_code: &str) -> Result<()> {
         // Get user's secret
         let result: Option<(String,)> = sqlx::query_as(
             r#"
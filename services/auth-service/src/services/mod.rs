@@ -1,3 +1,4 @@
+pub mod auth_audit;
 pub mod auth_service;
 pub mod jwt_service;
 pub mod mfa_service;
@@ -0,0 +1,31 @@
+use auth_service::services::auth_service::AuthService;
+use llm_governance_common::testing::{database, seed_member, seed_organization, seed_user};
+use llm_governance_common::AppError;
+
+#[actix_web::test]
+#[ignore] // Requires Postgres at DATABASE_URL
+async fn test_require_mfa_setting_is_read_as_text() {
+    let pool = database().await;
+    let service = AuthService::new(pool.clone());
+
+    let user_id = seed_user(&pool, "mfa").await;
+
+    // A malformed setting must not fail the login query for every member
+    for setting in [serde_json::json!("yes"), serde_json::json!(true)] {
+        let org_id = seed_organization(&pool, "mfa").await;
+        sqlx::query("UPDATE organizations SET settings = $2 WHERE id = $1")
+            .bind(org_id)
+            .bind(serde_json::json!({ "require_mfa": setting }))
+            .execute(&pool)
+            .await
+            .unwrap();
+        seed_member(&pool, org_id, user_id, "member").await;
+    }
+
+    assert_eq!(service.organizations_requiring_mfa(user_id).await.unwrap().len(), 1);
+    assert!(matches!(
+        service.ensure_mfa_enrolled(user_id, false).await,
+        Err(AppError::Forbidden)
+    ));
+    assert!(service.ensure_mfa_enrolled(user_id, true).await.is_ok());
+}
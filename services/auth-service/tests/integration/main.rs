@@ -0,0 +1,5 @@
+//! Auth service tests against the migrated database at `DATABASE_URL`
+//!
+//! Run with `cargo test -p auth-service --test integration -- --ignored`.
+
+mod auth_service;
@@ -1,17 +1,125 @@
+use llm_governance_common::idempotency::DEFAULT_IDEMPOTENCY_TTL_SECONDS;
+use llm_governance_common::utils::{DEFAULT_MAX_QUERY_DAYS, DEFAULT_QUERY_TIMEOUT_SECS, DEFAULT_WINDOW_DAYS};
 use serde::Deserialize;
+use std::time::Duration;
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct Config {
     pub host: String,
     pub port: u16,
     pub database_url: String,
+    /// Read replica for aggregation queries; the primary serves them when unset
+    #[serde(default)]
+    pub database_read_url: Option<String>,
     pub redis_url: String,
+    /// How long an `Idempotency-Key` replays its original response
+    #[serde(default = "default_idempotency_ttl_seconds")]
+    pub idempotency_ttl_seconds: i64,
+    /// Span of the analysis window when a request omits its bounds
+    #[serde(default = "default_window_days")]
+    pub default_window_days: i64,
+    /// Longest analysis window a request may ask for
+    #[serde(default = "default_max_query_days")]
+    pub max_query_days: i64,
+    /// Seconds an aggregation query may run before the request fails with `503`
+    #[serde(default = "default_query_timeout_secs")]
+    pub query_timeout_secs: u64,
+    /// Relative difference (percent) between computed and provider-reported
+    /// cost above which reconciliation flags a provider
+    #[serde(default = "default_reconciliation_threshold_percent")]
+    pub reconciliation_threshold_percent: f64,
+    /// JSON pricing table shared with the other services; the built-in prices are used when unset
+    #[serde(default)]
+    pub pricing_table_path: Option<String>,
+    /// Pricing for unknown models: `error`, `zero`, or an `input,output` pair per million tokens
+    #[serde(default = "default_pricing_fallback")]
+    pub pricing_fallback: String,
+    /// Comma-separated ascending per-request cost (USD) bucket edges for the
+    /// cost distribution histogram
+    #[serde(default = "default_cost_bucket_edges")]
+    pub cost_bucket_edges: String,
+}
+
+fn default_idempotency_ttl_seconds() -> i64 {
+    DEFAULT_IDEMPOTENCY_TTL_SECONDS
+}
+
+fn default_window_days() -> i64 {
+    DEFAULT_WINDOW_DAYS
+}
+
+fn default_max_query_days() -> i64 {
+    DEFAULT_MAX_QUERY_DAYS
+}
+
+fn default_query_timeout_secs() -> u64 {
+    DEFAULT_QUERY_TIMEOUT_SECS
+}
+
+fn default_reconciliation_threshold_percent() -> f64 {
+    5.0
+}
+
+fn default_pricing_fallback() -> String {
+    "1.0,2.0".to_string()
+}
+
+fn default_cost_bucket_edges() -> String {
+    "0.001,0.01,0.1,1,10".to_string()
+}
+
+/// Most bucket edges a cost distribution may use
+pub const MAX_BUCKET_EDGES: usize = 50;
+
+/// Parse comma-separated, strictly ascending, non-negative bucket edges
+pub fn parse_bucket_edges(value: &str) -> Result<Vec<f64>, String> {
+    let invalid = || {
+        format!(
+            "buckets must be 1-{} comma-separated, strictly ascending, non-negative costs",
+            MAX_BUCKET_EDGES
+        )
+    };
+
+    let edges = value
+        .split(',')
+        .map(|edge| edge.trim().parse::<f64>().map_err(|_| invalid()))
+        .collect::<Result<Vec<f64>, String>>()?;
+
+    let ascending = edges.windows(2).all(|pair| pair[0] < pair[1]);
+    if edges.is_empty()
+        || edges.len() > MAX_BUCKET_EDGES
+        || !ascending
+        || edges.iter().any(|edge| !edge.is_finite() || *edge < 0.0)
+    {
+        return Err(invalid());
+    }
+
+    Ok(edges)
 }
 
 impl Config {
+    pub fn query_timeout(&self) -> Duration {
+        Duration::from_secs(self.query_timeout_secs)
+    }
+
     pub fn from_env() -> Result<Self, envy::Error> {
         envy::prefixed("COST-SERVICE_").from_env::<Self>()
     }
+
+    /// Check the settings the service cannot run with, reporting every problem
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut problems = Vec::new();
+
+        if let Err(e) = parse_bucket_edges(&self.cost_bucket_edges) {
+            problems.push(format!("COST-SERVICE_COST_BUCKET_EDGES is invalid: {}", e));
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
 }
 
 impl Default for Config {
@@ -20,7 +128,44 @@ impl Default for Config {
             host: "0.0.0.0".to_string(),
             port: 8086,
             database_url: String::new(),
+            database_read_url: None,
             redis_url: "redis://127.0.0.1:6379".to_string(),
+            idempotency_ttl_seconds: default_idempotency_ttl_seconds(),
+            default_window_days: default_window_days(),
+            max_query_days: default_max_query_days(),
+            query_timeout_secs: default_query_timeout_secs(),
+            reconciliation_threshold_percent: default_reconciliation_threshold_percent(),
+            pricing_table_path: None,
+            pricing_fallback: default_pricing_fallback(),
+            cost_bucket_edges: default_cost_bucket_edges(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_edges_must_ascend() {
+        assert_eq!(parse_bucket_edges("0.01, 0.1,1").unwrap(), vec![0.01, 0.1, 1.0]);
+        assert_eq!(parse_bucket_edges(&Config::default().cost_bucket_edges).unwrap().len(), 5);
+
+        for invalid in ["", "0.1,0.01", "0.1,0.1", "-1,2", "a,b", "1,inf"] {
+            assert!(parse_bucket_edges(invalid).is_err(), "{:?}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_invalid_bucket_edges_are_rejected_at_startup() {
+        assert!(Config::default().validate().is_ok());
+
+        let config = Config {
+            cost_bucket_edges: "1,0.1".to_string(),
+            ..Config::default()
+        };
+        let problems = config.validate().unwrap_err();
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("COST-SERVICE_COST_BUCKET_EDGES"));
+    }
+}
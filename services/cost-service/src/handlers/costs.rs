@@ -1,12 +1,21 @@
-use actix_web::{delete, get, post, put, web, HttpResponse, Responder, HttpRequest};
+use actix_web::{delete, get, http::StatusCode, post, put, web, HttpResponse, Responder, HttpRequest};
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 use sqlx::PgPool;
 use uuid::Uuid;
 use llm_governance_common::{AppError, Result, ApiResponse};
-use chrono::{DateTime, Utc};
+use llm_governance_common::db::{paginate, Page, MAX_PAGE_SIZE};
+use llm_governance_common::idempotency::{extract_idempotency_key, with_idempotency};
+use llm_governance_common::negotiate::{preferred_format, Format};
+use llm_governance_common::pricing;
+use llm_governance_common::query::DynamicQuery;
+use llm_governance_common::utils::{begin_with_timeout, check_window_span, resolve_window, with_timeout, QueryWindow};
+use chrono::{DateTime, Datelike, Utc};
+use llm_governance_database::ReadPool;
 use rust_decimal::Decimal;
 
+use crate::config::{parse_bucket_edges, Config};
+
 #[derive(Debug, Deserialize)]
 pub struct CalculateCostRequest {
     pub provider: String,
@@ -24,9 +33,12 @@ pub struct CostCalculationResponse {
     pub input_cost: f64,
     pub output_cost: f64,
     pub total_cost: f64,
+    /// Fallback strategy that priced an unknown model
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pricing_fallback: Option<&'static str>,
 }
 
-#[derive(Debug, Deserialize, Validate)]
+#[derive(Debug, Serialize, Deserialize, Validate)]
 pub struct CreateBudgetRequest {
     pub name: String,
     pub organization_id: Uuid,
@@ -39,6 +51,33 @@ pub struct CreateBudgetRequest {
     pub hard_limit: Option<bool>,
 }
 
+/// Desired state of a budget, applied by `PUT /costs/budgets/by-name/{name}`
+#[derive(Debug, Deserialize)]
+pub struct UpsertBudgetRequest {
+    pub organization_id: Uuid,
+    pub team_id: Option<Uuid>,
+    pub user_id: Option<Uuid>,
+    pub amount: f64,
+    pub period: String,
+    pub alert_threshold_percentage: Option<i32>,
+    pub hard_limit: Option<bool>,
+}
+
+impl UpsertBudgetRequest {
+    pub fn named(self, name: String) -> CreateBudgetRequest {
+        CreateBudgetRequest {
+            name,
+            organization_id: self.organization_id,
+            team_id: self.team_id,
+            user_id: self.user_id,
+            amount: self.amount,
+            period: self.period,
+            alert_threshold_percentage: self.alert_threshold_percentage,
+            hard_limit: self.hard_limit,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct UpdateBudgetRequest {
     pub amount: Option<f64>,
@@ -65,6 +104,77 @@ pub struct BudgetResponse {
     pub updated_at: DateTime<Utc>,
 }
 
+/// Budget health derived from spend against amount and alert threshold
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetStatus {
+    Ok,
+    Warning,
+    Exceeded,
+}
+
+/// Budget with its utilization, as returned by the list endpoint
+#[derive(Debug, Serialize)]
+pub struct BudgetListItem {
+    #[serde(flatten)]
+    pub budget: BudgetResponse,
+    pub utilization_percent: f64,
+    pub status: BudgetStatus,
+}
+
+impl From<BudgetResponse> for BudgetListItem {
+    fn from(budget: BudgetResponse) -> Self {
+        let utilization_percent = budget_utilization(budget.current_spend, budget.amount);
+        let status = budget_status(utilization_percent, budget.alert_threshold_percentage);
+        Self {
+            budget,
+            utilization_percent,
+            status,
+        }
+    }
+}
+
+/// A budget as it would be created, with spend already incurred in its first period
+#[derive(Debug, Serialize)]
+pub struct BudgetPreview {
+    pub organization_id: Uuid,
+    pub team_id: Option<Uuid>,
+    pub user_id: Option<Uuid>,
+    pub name: String,
+    pub amount: f64,
+    pub period: String,
+    pub alert_threshold_percentage: i32,
+    pub hard_limit: bool,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    pub current_spend: f64,
+    pub utilization_percent: f64,
+    pub status: BudgetStatus,
+}
+
+impl BudgetPreview {
+    fn new(req: &CreateBudgetRequest, now: DateTime<Utc>, current_spend: f64) -> Self {
+        let (period_start, period_end) = calculate_period_bounds(&req.period, now);
+        let alert_threshold_percentage = req.alert_threshold_percentage.unwrap_or(80);
+        let utilization_percent = budget_utilization(current_spend, req.amount);
+        Self {
+            organization_id: req.organization_id,
+            team_id: req.team_id,
+            user_id: req.user_id,
+            name: req.name.clone(),
+            amount: req.amount,
+            period: req.period.clone(),
+            alert_threshold_percentage,
+            hard_limit: req.hard_limit.unwrap_or(false),
+            period_start,
+            period_end,
+            current_spend,
+            utilization_percent,
+            status: budget_status(utilization_percent, alert_threshold_percentage),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, sqlx::FromRow)]
 pub struct CostForecast {
     pub period: String,
@@ -78,38 +188,49 @@ pub async fn calculate_cost(
     pool: web::Data<PgPool>,
     req: web::Json<CalculateCostRequest>,
 ) -> Result<impl Responder> {
-    let pricing = get_model_pricing(&req.provider, &req.model).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success(cost_calculation(&req)?)))
+}
 
-    let input_cost = (req.tokens_in as f64 / 1_000_000.0) * pricing.input_price;
-    let output_cost = (req.tokens_out as f64 / 1_000_000.0) * pricing.output_price;
-    let total_cost = input_cost + output_cost;
+/// Price a request from the shared pricing table used by the proxy
+fn cost_calculation(req: &CalculateCostRequest) -> Result<CostCalculationResponse> {
+    let resolved = pricing::resolve(&req.provider, &req.model)?;
+    let price = resolved.price;
 
-    Ok(HttpResponse::Ok().json(ApiResponse::success(CostCalculationResponse {
+    Ok(CostCalculationResponse {
         provider: req.provider.clone(),
         model: req.model.clone(),
         tokens_in: req.tokens_in,
         tokens_out: req.tokens_out,
-        input_cost,
-        output_cost,
-        total_cost,
-    })))
+        input_cost: price.input_cost(req.tokens_in),
+        output_cost: price.output_cost(req.tokens_out),
+        total_cost: price.cost(req.tokens_in, req.tokens_out),
+        pricing_fallback: resolved.fallback.map(|f| f.as_str()),
+    })
 }
 
 #[get("/costs/team/{team_id}")]
 pub async fn get_team_costs(
     pool: web::Data<PgPool>,
+    read_pool: web::Data<ReadPool>,
+    config: web::Data<Config>,
     team_id: web::Path<Uuid>,
     query: web::Query<CostQuery>,
+    http_req: HttpRequest,
 ) -> Result<impl Responder> {
-    let start_date = query.start_date.clone().unwrap_or_else(|| {
-        chrono::Utc::now().checked_sub_signed(chrono::Duration::days(30)).unwrap().to_rfc3339()
-    });
-
-    let end_date = query.end_date.clone().unwrap_or_else(|| {
-        chrono::Utc::now().to_rfc3339()
-    });
-
-    let costs = sqlx::query_as::<_, CostBreakdown>(
+    let caller_orgs = caller_organizations(pool.get_ref(), extract_user_id(&http_req)?).await?;
+    let team_org = team_organization(pool.get_ref(), *team_id).await?;
+    let org_ids = authorized_organizations(&caller_orgs, &[team_org])?;
+
+    let window = resolve_window(
+        query.start_date.as_deref(),
+        query.end_date.as_deref(),
+        config.default_window_days,
+    );
+    check_window_span(&window, config.max_query_days)?;
+    let (start_date, end_date) = (window.start, window.end);
+
+    let mut tx = begin_with_timeout(read_pool.pool(), config.query_timeout()).await?;
+    let costs = with_timeout(config.query_timeout(), sqlx::query_as::<_, CostBreakdown>(
         r#"
         SELECT
             p.provider_name as provider,
@@ -122,6 +243,7 @@ pub async fn get_team_costs(
         JOIN llm_models m ON r.model_id = m.id
         JOIN llm_providers p ON m.provider_id = p.id
         WHERE r.team_id = $1
+        AND r.organization_id = ANY($4)
         AND r.timestamp BETWEEN $2::timestamptz AND $3::timestamptz
         GROUP BY p.provider_name, m.model_name
         ORDER BY total_cost DESC
@@ -130,7 +252,8 @@ pub async fn get_team_costs(
     .bind(team_id.as_ref())
     .bind(&start_date)
     .bind(&end_date)
-    .fetch_all(pool.get_ref())
+    .bind(&org_ids)
+    .fetch_all(&mut *tx))
     .await?;
 
     let total_cost: f64 = costs.iter().map(|c| c.total_cost).sum();
@@ -149,18 +272,26 @@ pub async fn get_team_costs(
 #[get("/costs/user/{user_id}")]
 pub async fn get_user_costs(
     pool: web::Data<PgPool>,
+    read_pool: web::Data<ReadPool>,
+    config: web::Data<Config>,
     user_id: web::Path<Uuid>,
     query: web::Query<CostQuery>,
+    http_req: HttpRequest,
 ) -> Result<impl Responder> {
-    let start_date = query.start_date.clone().unwrap_or_else(|| {
-        chrono::Utc::now().checked_sub_signed(chrono::Duration::days(30)).unwrap().to_rfc3339()
-    });
-
-    let end_date = query.end_date.clone().unwrap_or_else(|| {
-        chrono::Utc::now().to_rfc3339()
-    });
-
-    let costs = sqlx::query_as::<_, CostBreakdown>(
+    let caller_orgs = caller_organizations(pool.get_ref(), extract_user_id(&http_req)?).await?;
+    let user_orgs = caller_organizations(pool.get_ref(), *user_id).await?;
+    let org_ids = authorized_organizations(&caller_orgs, &user_orgs)?;
+
+    let window = resolve_window(
+        query.start_date.as_deref(),
+        query.end_date.as_deref(),
+        config.default_window_days,
+    );
+    check_window_span(&window, config.max_query_days)?;
+    let (start_date, end_date) = (window.start, window.end);
+
+    let mut tx = begin_with_timeout(read_pool.pool(), config.query_timeout()).await?;
+    let costs = with_timeout(config.query_timeout(), sqlx::query_as::<_, CostBreakdown>(
         r#"
         SELECT
             p.provider_name as provider,
@@ -173,6 +304,7 @@ pub async fn get_user_costs(
         JOIN llm_models m ON r.model_id = m.id
         JOIN llm_providers p ON m.provider_id = p.id
         WHERE r.user_id = $1
+        AND r.organization_id = ANY($4)
         AND r.timestamp BETWEEN $2::timestamptz AND $3::timestamptz
         GROUP BY p.provider_name, m.model_name
         ORDER BY total_cost DESC
@@ -181,7 +313,8 @@ pub async fn get_user_costs(
     .bind(user_id.as_ref())
     .bind(&start_date)
     .bind(&end_date)
-    .fetch_all(pool.get_ref())
+    .bind(&org_ids)
+    .fetch_all(&mut *tx))
     .await?;
 
     let total_cost: f64 = costs.iter().map(|c| c.total_cost).sum();
@@ -200,84 +333,293 @@ pub async fn get_user_costs(
 #[post("/costs/budgets")]
 pub async fn create_budget(
     pool: web::Data<PgPool>,
+    config: web::Data<Config>,
     req: web::Json<CreateBudgetRequest>,
     http_req: HttpRequest,
 ) -> Result<impl Responder> {
-    req.validate()
-        .map_err(|e| AppError::Validation(format!("{}", e)))?;
+    validate_budget_request(&req)?;
 
     let current_user_id = extract_user_id(&http_req)?;
-
-    // Validate that either team_id or user_id is provided
-    if (req.team_id.is_some() && req.user_id.is_some()) ||
-       (req.team_id.is_none() && req.user_id.is_none()) {
-        return Err(AppError::Validation("Provide either team_id or user_id, not both".to_string()));
-    }
+    let idempotency_key = extract_idempotency_key(&http_req, &*req)?;
+    authorize_budget_admin(pool.get_ref(), current_user_id, req.organization_id).await?;
+    verify_budget_scope(pool.get_ref(), &req).await?;
 
     // Calculate period start and end
     let now = chrono::Utc::now();
     let (period_start, period_end) = calculate_period_bounds(&req.period, now);
 
-    let budget = sqlx::query_as::<_, BudgetResponse>(
+    // A retried request with the same Idempotency-Key replays the original budget
+    with_idempotency(
+        pool.get_ref(),
+        "costs.create_budget",
+        current_user_id,
+        idempotency_key,
+        config.idempotency_ttl_seconds,
+        StatusCode::CREATED,
+        || async {
+            let budget = sqlx::query_as::<_, BudgetResponse>(
+                r#"
+                INSERT INTO budgets (
+                    organization_id, team_id, user_id, name, amount, period,
+                    alert_threshold_percentage, hard_limit, current_spend,
+                    period_start, period_end, is_active
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, 0, $9, $10, true)
+                RETURNING id, organization_id, team_id, user_id, name, amount, period,
+                          alert_threshold_percentage, hard_limit, current_spend,
+                          period_start, period_end, is_active, created_at, updated_at
+                "#,
+            )
+            .bind(req.organization_id)
+            .bind(req.team_id)
+            .bind(req.user_id)
+            .bind(&req.name)
+            .bind(req.amount)
+            .bind(&req.period)
+            .bind(req.alert_threshold_percentage.unwrap_or(80))
+            .bind(req.hard_limit.unwrap_or(false))
+            .bind(period_start)
+            .bind(period_end)
+            .fetch_one(pool.get_ref())
+            .await
+            .map_err(budget_insert_error)?;
+
+            Ok((budget.id, budget))
+        },
+    )
+    .await
+}
+
+/// Create or update a budget by its natural key
+///
+/// The budget is matched on organization, team or user, and name. A missing
+/// one is created; an existing one takes the request's amount, period, alert
+/// threshold and hard limit, and new period bounds only when its period
+/// changes, so applying the same request again changes nothing.
+#[put("/costs/budgets/by-name/{name}")]
+pub async fn upsert_budget(
+    pool: web::Data<PgPool>,
+    name: web::Path<String>,
+    req: web::Json<UpsertBudgetRequest>,
+    http_req: HttpRequest,
+) -> Result<impl Responder> {
+    let req = req.into_inner().named(name.into_inner());
+    validate_budget_request(&req)?;
+
+    authorize_budget_admin(pool.get_ref(), extract_user_id(&http_req)?, req.organization_id).await?;
+    verify_budget_scope(pool.get_ref(), &req).await?;
+
+    let (budget, created) = apply_budget(pool.get_ref(), &req, Utc::now()).await?;
+    let status = if created { StatusCode::CREATED } else { StatusCode::OK };
+
+    Ok(HttpResponse::build(status).json(ApiResponse::success(serde_json::json!({
+        "budget": budget,
+        "created": created
+    }))))
+}
+
+/// Unique indexes on the budget natural key, one per scope
+const BUDGET_KEY_INDEXES: &[&str] = &[
+    "idx_budgets_org_name_unique",
+    "idx_budgets_team_name_unique",
+    "idx_budgets_user_name_unique",
+];
+
+/// `Conflict` for an insert that collides with another budget's natural key
+pub fn budget_insert_error(e: sqlx::Error) -> AppError {
+    match e {
+        sqlx::Error::Database(ref db_err)
+            if db_err.constraint().map_or(false, |c| BUDGET_KEY_INDEXES.contains(&c)) =>
+        {
+            AppError::Conflict("A budget with this name already exists for this scope".to_string())
+        }
+        _ => AppError::Database(e),
+    }
+}
+
+/// Insert or update the budget keyed by `req`'s scope and name in one
+/// transaction, returning it and whether it was created
+pub async fn apply_budget(
+    pool: &PgPool,
+    req: &CreateBudgetRequest,
+    now: DateTime<Utc>,
+) -> Result<(BudgetResponse, bool)> {
+    let (period_start, period_end) = calculate_period_bounds(&req.period, now);
+    let mut tx = pool.begin().await?;
+
+    // A row lock cannot cover a budget that does not exist yet, so concurrent
+    // applies of the same key queue on an advisory lock instead
+    sqlx::query("SELECT pg_advisory_xact_lock(hashtext($1))")
+        .bind(budget_natural_key(req))
+        .execute(&mut *tx)
+        .await?;
+
+    let existing: Option<(Uuid,)> = sqlx::query_as(
         r#"
-        INSERT INTO budgets (
-            organization_id, team_id, user_id, name, amount, period,
-            alert_threshold_percentage, hard_limit, current_spend,
-            period_start, period_end, is_active
-        )
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, 0, $9, $10, true)
-        RETURNING id, organization_id, team_id, user_id, name, amount, period,
-                  alert_threshold_percentage, hard_limit, current_spend,
-                  period_start, period_end, is_active, created_at, updated_at
+        SELECT id FROM budgets
+        WHERE organization_id = $1
+        AND team_id IS NOT DISTINCT FROM $2
+        AND user_id IS NOT DISTINCT FROM $3
+        AND name = $4
         "#,
     )
     .bind(req.organization_id)
     .bind(req.team_id)
     .bind(req.user_id)
     .bind(&req.name)
-    .bind(req.amount)
-    .bind(&req.period)
-    .bind(req.alert_threshold_percentage.unwrap_or(80))
-    .bind(req.hard_limit.unwrap_or(false))
-    .bind(period_start)
-    .bind(period_end)
-    .fetch_one(pool.get_ref())
+    .fetch_optional(&mut *tx)
     .await?;
 
-    Ok(HttpResponse::Created().json(ApiResponse::success(budget)))
+    let budget = match existing {
+        Some((id,)) => {
+            sqlx::query_as::<_, BudgetResponse>(
+                r#"
+                UPDATE budgets SET
+                    amount = $1,
+                    alert_threshold_percentage = $3,
+                    hard_limit = $4,
+                    period_start = CASE WHEN period = $2 THEN period_start ELSE $5 END,
+                    period_end = CASE WHEN period = $2 THEN period_end ELSE $6 END,
+                    period = $2,
+                    updated_at = NOW()
+                WHERE id = $7
+                RETURNING id, organization_id, team_id, user_id, name, amount, period,
+                          alert_threshold_percentage, hard_limit, current_spend,
+                          period_start, period_end, is_active, created_at, updated_at
+                "#,
+            )
+            .bind(req.amount)
+            .bind(&req.period)
+            .bind(req.alert_threshold_percentage.unwrap_or(80))
+            .bind(req.hard_limit.unwrap_or(false))
+            .bind(period_start)
+            .bind(period_end)
+            .bind(id)
+            .fetch_one(&mut *tx)
+            .await?
+        }
+        None => {
+            sqlx::query_as::<_, BudgetResponse>(
+                r#"
+                INSERT INTO budgets (
+                    organization_id, team_id, user_id, name, amount, period,
+                    alert_threshold_percentage, hard_limit, current_spend,
+                    period_start, period_end, is_active
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, 0, $9, $10, true)
+                RETURNING id, organization_id, team_id, user_id, name, amount, period,
+                          alert_threshold_percentage, hard_limit, current_spend,
+                          period_start, period_end, is_active, created_at, updated_at
+                "#,
+            )
+            .bind(req.organization_id)
+            .bind(req.team_id)
+            .bind(req.user_id)
+            .bind(&req.name)
+            .bind(req.amount)
+            .bind(&req.period)
+            .bind(req.alert_threshold_percentage.unwrap_or(80))
+            .bind(req.hard_limit.unwrap_or(false))
+            .bind(period_start)
+            .bind(period_end)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(budget_insert_error)?
+        }
+    };
+
+    tx.commit().await?;
+    Ok((budget, existing.is_none()))
 }
 
-#[get("/costs/budgets")]
-pub async fn list_budgets(
+/// `organization:team:user:name`, with an empty segment for the unset scope
+fn budget_natural_key(req: &CreateBudgetRequest) -> String {
+    let id = |id: Option<Uuid>| id.map(|id| id.to_string()).unwrap_or_default();
+    format!("{}:{}:{}:{}", req.organization_id, id(req.team_id), id(req.user_id), req.name)
+}
+
+/// Preview a budget without creating it
+///
+/// Runs the create validation and period calculation, and sums the spend the
+/// budget's scope has already incurred in that period. The caller must belong
+/// to the budget's organization, and its team or user must belong there too.
+#[post("/costs/budgets/preview")]
+pub async fn preview_budget(
     pool: web::Data<PgPool>,
-    query: web::Query<BudgetQuery>,
+    read_pool: web::Data<ReadPool>,
+    config: web::Data<Config>,
+    req: web::Json<CreateBudgetRequest>,
+    http_req: HttpRequest,
 ) -> Result<impl Responder> {
-    let limit = query.limit.unwrap_or(20).min(100);
-    let offset = query.offset.unwrap_or(0);
+    let caller_orgs = caller_organizations(pool.get_ref(), extract_user_id(&http_req)?).await?;
+    authorized_organizations(&caller_orgs, &[req.organization_id])?;
+    verify_budget_scope(pool.get_ref(), &req).await?;
 
-    let mut sql = "SELECT id, organization_id, team_id, user_id, name, amount, period, alert_threshold_percentage, hard_limit, current_spend, period_start, period_end, is_active, created_at, updated_at FROM budgets WHERE 1=1".to_string();
+    let preview = budget_preview(read_pool.get_ref(), config.query_timeout(), &req, Utc::now()).await?;
 
-    if query.team_id.is_some() {
-        sql.push_str(" AND team_id = $1");
-    }
-    if query.user_id.is_some() {
-        sql.push_str(" AND user_id = $2");
-    }
+    Ok(HttpResponse::Ok().json(ApiResponse::success(preview)))
+}
 
-    sql.push_str(&format!(" ORDER BY created_at DESC LIMIT {} OFFSET {}", limit, offset));
+pub async fn budget_preview(
+    read_pool: &ReadPool,
+    timeout: std::time::Duration,
+    req: &CreateBudgetRequest,
+    now: DateTime<Utc>,
+) -> Result<BudgetPreview> {
+    validate_budget_request(req)?;
+
+    let (period_start, period_end) = calculate_period_bounds(&req.period, now);
+    let mut tx = begin_with_timeout(read_pool.pool(), timeout).await?;
+    let (current_spend,): (f64,) = with_timeout(timeout, sqlx::query_as(
+        r#"
+        SELECT COALESCE(SUM(total_cost), 0)::FLOAT8
+        FROM llm_requests
+        WHERE organization_id = $1
+        AND ($2::uuid IS NULL OR team_id = $2)
+        AND ($3::uuid IS NULL OR user_id = $3)
+        AND timestamp >= $4 AND timestamp < $5
+        "#,
+    )
+    .bind(req.organization_id)
+    .bind(req.team_id)
+    .bind(req.user_id)
+    .bind(period_start)
+    .bind(period_end)
+    .fetch_one(&mut *tx))
+    .await?;
 
-    let mut query_builder = sqlx::query_as::<_, BudgetResponse>(&sql);
+    Ok(BudgetPreview::new(req, now, current_spend))
+}
 
-    if let Some(team_id) = query.team_id {
-        query_builder = query_builder.bind(team_id);
-    }
-    if let Some(user_id) = query.user_id {
-        query_builder = query_builder.bind(user_id);
-    }
+#[get("/costs/budgets")]
+pub async fn list_budgets(
+    pool: web::Data<PgPool>,
+    query: web::Query<BudgetQuery>,
+    http_req: HttpRequest,
+) -> Result<impl Responder> {
+    let user_id = extract_user_id(&http_req)?;
+    let caller_orgs = caller_organizations(pool.get_ref(), user_id).await?;
+    let organizations = match query.organization_id {
+        Some(organization_id) => authorized_organizations(&caller_orgs, &[organization_id])?,
+        None => caller_orgs,
+    };
+    let params = BudgetListParams::new(&query, organizations);
 
-    let budgets = query_builder.fetch_all(pool.get_ref()).await?;
+    let (budgets, total): (Vec<BudgetResponse>, i64) = paginate(
+        pool.get_ref(),
+        list_budgets_query(&params),
+        filtered_budgets("SELECT COUNT(*) FROM budgets", &params),
+        params.page,
+    )
+    .await?;
+    let budgets: Vec<BudgetListItem> = budgets.into_iter().map(BudgetListItem::from).collect();
 
-    Ok(HttpResponse::Ok().json(ApiResponse::success(budgets)))
+    Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
+        "budgets": budgets,
+        "total": total,
+        "limit": params.page.limit,
+        "offset": params.page.offset
+    }))))
 }
 
 #[get("/costs/budgets/{id}")]
@@ -299,11 +641,7 @@ pub async fn get_budget(
     .await?
     .ok_or_else(|| AppError::NotFound("Budget not found".to_string()))?;
 
-    let utilization = if budget.amount > 0.0 {
-        (budget.current_spend / budget.amount) * 100.0
-    } else {
-        0.0
-    };
+    let utilization = budget_utilization(budget.current_spend, budget.amount);
 
     Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
         "budget": budget,
@@ -316,7 +654,11 @@ pub async fn update_budget(
     pool: web::Data<PgPool>,
     budget_id: web::Path<Uuid>,
     req: web::Json<UpdateBudgetRequest>,
+    http_req: HttpRequest,
 ) -> Result<impl Responder> {
+    let organization_id = budget_organization(pool.get_ref(), *budget_id).await?;
+    authorize_budget_admin(pool.get_ref(), extract_user_id(&http_req)?, organization_id).await?;
+
     if let Some(amount) = req.amount {
         sqlx::query("UPDATE budgets SET amount = $1 WHERE id = $2")
             .bind(amount)
@@ -361,7 +703,11 @@ pub async fn update_budget(
 pub async fn delete_budget(
     pool: web::Data<PgPool>,
     budget_id: web::Path<Uuid>,
+    http_req: HttpRequest,
 ) -> Result<impl Responder> {
+    let organization_id = budget_organization(pool.get_ref(), *budget_id).await?;
+    authorize_budget_admin(pool.get_ref(), extract_user_id(&http_req)?, organization_id).await?;
+
     let result = sqlx::query("DELETE FROM budgets WHERE id = $1")
         .bind(budget_id.as_ref())
         .execute(pool.get_ref())
@@ -379,22 +725,31 @@ pub async fn delete_budget(
 #[get("/costs/forecast")]
 pub async fn forecast_costs(
     pool: web::Data<PgPool>,
+    config: web::Data<Config>,
     query: web::Query<ForecastQuery>,
+    http_req: HttpRequest,
 ) -> Result<impl Responder> {
     let team_id = query.team_id.ok_or_else(|| AppError::Validation("team_id required".to_string()))?;
 
-    // Get historical data for last 30 days
+    let caller_orgs = caller_organizations(pool.get_ref(), extract_user_id(&http_req)?).await?;
+    let team_org = team_organization(pool.get_ref(), team_id).await?;
+    let org_ids = authorized_organizations(&caller_orgs, &[team_org])?;
+
+    // Get historical data over the configured analysis window
     let historical: Vec<(f64,)> = sqlx::query_as(
         r#"
         SELECT SUM(total_cost) as daily_cost
         FROM llm_requests
         WHERE team_id = $1
-        AND timestamp >= NOW() - INTERVAL '30 days'
+        AND organization_id = ANY($2)
+        AND timestamp >= NOW() - make_interval(days => $3)
         GROUP BY DATE(timestamp)
         ORDER BY DATE(timestamp)
         "#,
     )
     .bind(team_id)
+    .bind(&org_ids)
+    .bind(config.default_window_days as i32)
     .fetch_all(pool.get_ref())
     .await?;
 
@@ -412,10 +767,12 @@ pub async fn forecast_costs(
         SELECT COALESCE(SUM(total_cost), 0) as total
         FROM llm_requests
         WHERE team_id = $1
+        AND organization_id = ANY($2)
         AND timestamp >= DATE_TRUNC('month', NOW())
         "#,
     )
     .bind(team_id)
+    .bind(&org_ids)
     .fetch_one(pool.get_ref())
     .await?;
 
@@ -436,26 +793,28 @@ pub async fn forecast_costs(
 #[get("/costs/reports/chargeback")]
 pub async fn generate_chargeback_report(
     pool: web::Data<PgPool>,
+    read_pool: web::Data<ReadPool>,
+    config: web::Data<Config>,
     query: web::Query<ChargebackQuery>,
+    http_req: HttpRequest,
 ) -> Result<impl Responder> {
-    let start_date = query.start_date.clone().unwrap_or_else(|| {
-        chrono::Utc::now().checked_sub_signed(chrono::Duration::days(30)).unwrap().to_rfc3339()
-    });
-
-    let end_date = query.end_date.clone().unwrap_or_else(|| {
-        chrono::Utc::now().to_rfc3339()
-    });
+    let format = preferred_format(&http_req, &["json", "csv"])?;
+    let caller_orgs = caller_organizations(pool.get_ref(), extract_user_id(&http_req)?).await?;
+    let org_ids = match query.organization_id {
+        Some(org_id) => authorized_organizations(&caller_orgs, &[org_id])?,
+        None => authorized_organizations(&caller_orgs, &caller_orgs)?,
+    };
 
-    #[derive(Debug, Serialize, sqlx::FromRow)]
-    struct ChargebackEntry {
-        team_id: Option<Uuid>,
-        team_name: Option<String>,
-        total_cost: f64,
-        total_requests: i64,
-        total_tokens: i64,
-    }
+    let window = resolve_window(
+        query.start_date.as_deref(),
+        query.end_date.as_deref(),
+        config.default_window_days,
+    );
+    check_window_span(&window, config.max_query_days)?;
+    let (start_date, end_date) = (window.start, window.end);
 
-    let entries = sqlx::query_as::<_, ChargebackEntry>(
+    let mut tx = begin_with_timeout(read_pool.pool(), config.query_timeout()).await?;
+    let entries = with_timeout(config.query_timeout(), sqlx::query_as::<_, ChargebackEntry>(
         r#"
         SELECT
             r.team_id,
@@ -465,16 +824,25 @@ pub async fn generate_chargeback_report(
             SUM(r.total_tokens) as total_tokens
         FROM llm_requests r
         LEFT JOIN teams t ON r.team_id = t.id
-        WHERE r.timestamp BETWEEN $1::timestamptz AND $2::timestamptz
+        WHERE r.organization_id = ANY($3)
+        AND r.timestamp BETWEEN $1::timestamptz AND $2::timestamptz
         GROUP BY r.team_id, t.name
         ORDER BY total_cost DESC
         "#,
     )
     .bind(&start_date)
     .bind(&end_date)
-    .fetch_all(pool.get_ref())
+    .bind(&org_ids)
+    .fetch_all(&mut *tx))
     .await?;
 
+    if format == Format::Csv {
+        return Ok(HttpResponse::Ok()
+            .content_type(format.content_type())
+            .insert_header(("Content-Disposition", "attachment; filename=chargeback.csv"))
+            .body(chargeback_csv(&entries)));
+    }
+
     Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
         "period": {
             "start": start_date,
@@ -492,8 +860,9 @@ pub struct CostQuery {
     pub end_date: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 pub struct BudgetQuery {
+    pub organization_id: Option<Uuid>,
     pub team_id: Option<Uuid>,
     pub user_id: Option<Uuid>,
     pub limit: Option<u32>,
@@ -505,40 +874,196 @@ pub struct ForecastQuery {
     pub team_id: Option<Uuid>,
 }
 
+#[derive(Debug, Serialize, sqlx::FromRow)]
+struct ChargebackEntry {
+    team_id: Option<Uuid>,
+    team_name: Option<String>,
+    total_cost: f64,
+    total_requests: i64,
+    total_tokens: i64,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ChargebackQuery {
     pub start_date: Option<String>,
     pub end_date: Option<String>,
+    /// Limit the report to one of the caller's organizations
+    pub organization_id: Option<Uuid>,
 }
 
-#[derive(Debug, Serialize, sqlx::FromRow)]
-pub struct CostBreakdown {
+#[derive(Debug, Deserialize)]
+pub struct ReconciliationQuery {
+    pub from: Option<String>,
+    pub to: Option<String>,
+    /// Overrides the configured discrepancy threshold, in percent
+    pub threshold_percent: Option<f64>,
+}
+
+/// Computed vs provider-reported cost for one provider
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProviderReconciliation {
     pub provider: String,
-    pub model: String,
-    pub total_tokens_in: i64,
-    pub total_tokens_out: i64,
+    /// Requests for which the provider reported a billed amount
+    pub reported_requests: i64,
+    /// Our computed cost over those same requests
+    pub computed_cost: f64,
+    pub reported_cost: f64,
+    /// `computed_cost - reported_cost`
+    pub difference: f64,
+    /// Difference relative to the reported cost, in percent
+    pub difference_percent: f64,
+    pub discrepancy: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TagCostQuery {
+    pub tag_key: String,
+    pub from: Option<String>,
+    pub to: Option<String>,
+}
+
+/// Longest tag key accepted by the proxy
+const MAX_TAG_KEY_LEN: usize = 64;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TagSpend {
+    pub tag_value: String,
     pub total_cost: f64,
     pub request_count: i64,
+    /// Share of tagged spend, 0-100
+    pub percentage: f64,
 }
 
-struct ModelPricing {
-    input_price: f64,
-    output_price: f64,
+#[derive(Debug, PartialEq, Serialize)]
+pub struct TagSpendReport {
+    pub tag_key: String,
+    pub groups: Vec<TagSpend>,
+    /// Spend on requests without the tag
+    pub untagged_cost: f64,
+    pub untagged_request_count: i64,
 }
 
-async fn get_model_pricing(provider: &str, model: &str) -> Result<ModelPricing> {
-    // Pricing per 1M tokens (as of 2024)
-    let pricing = match (provider, model) {
-        ("openai", "gpt-4") => ModelPricing { input_price: 30.0, output_price: 60.0 },
-        ("openai", "gpt-4-turbo") => ModelPricing { input_price: 10.0, output_price: 30.0 },
-        ("openai", "gpt-3.5-turbo") => ModelPricing { input_price: 0.5, output_price: 1.5 },
-        ("anthropic", "claude-3-opus") => ModelPricing { input_price: 15.0, output_price: 75.0 },
-        ("anthropic", "claude-3-sonnet") => ModelPricing { input_price: 3.0, output_price: 15.0 },
-        ("anthropic", "claude-3-haiku") => ModelPricing { input_price: 0.25, output_price: 1.25 },
-        _ => ModelPricing { input_price: 1.0, output_price: 2.0 }, // Default pricing
-    };
+#[derive(Debug, Deserialize)]
+pub struct LeaderboardQuery {
+    pub dimension: LeaderboardDimension,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub limit: Option<u32>,
+    /// Limit the leaderboard to one of the caller's organizations
+    pub organization_id: Option<Uuid>,
+}
+
+const DEFAULT_LEADERBOARD_LIMIT: u32 = 10;
 
-    Ok(pricing)
+/// What the spend leaderboard groups requests by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LeaderboardDimension {
+    Team,
+    User,
+    Provider,
+    Model,
+}
+
+impl LeaderboardDimension {
+    /// `(key, label, group by, join)` SQL over `llm_requests r` joined to its
+    /// model `m` and provider `p`
+    fn columns(self) -> (&'static str, &'static str, &'static str, &'static str) {
+        match self {
+            Self::Team => (
+                "r.team_id::text",
+                "t.name",
+                "r.team_id, t.name",
+                " LEFT JOIN teams t ON r.team_id = t.id",
+            ),
+            Self::User => (
+                "r.user_id::text",
+                "u.email",
+                "r.user_id, u.email",
+                " LEFT JOIN users u ON r.user_id = u.id",
+            ),
+            Self::Provider => ("p.provider_name", "NULL::text", "p.provider_name", ""),
+            Self::Model => ("m.model_name", "p.provider_name", "m.model_name, p.provider_name", ""),
+        }
+    }
+}
+
+/// `(key, label, total cost, request count, spend across every group)`
+pub type LeaderboardRow = (String, Option<String>, f64, i64, f64);
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LeaderboardEntry {
+    pub rank: usize,
+    /// Team or user id, provider name or model name
+    pub key: String,
+    /// Team name, user email or, for models, the provider
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    pub total_cost: f64,
+    pub request_count: i64,
+    /// Share of all spend in the window, 0-100
+    pub share_percent: f64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DistributionQuery {
+    pub scope: DistributionScope,
+    pub id: Uuid,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    /// Comma-separated bucket edges overriding the configured ones
+    pub buckets: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DistributionScope {
+    Organization,
+    Team,
+    User,
+}
+
+impl DistributionScope {
+    /// `llm_metrics` column holding the scope's id; organizations are matched
+    /// through their teams and members instead
+    fn column(self) -> Option<&'static str> {
+        match self {
+            Self::Organization => None,
+            Self::Team => Some("m.team_id"),
+            Self::User => Some("m.user_id"),
+        }
+    }
+}
+
+/// Requests whose cost falls in `[lower, upper)`; the last bucket has no upper edge
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CostBucket {
+    pub lower: f64,
+    pub upper: Option<f64>,
+    pub request_count: usize,
+    pub total_cost: f64,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct CostDistribution {
+    pub request_count: usize,
+    pub total_cost: f64,
+    /// Nearest-rank (`percentile_disc`) per-request cost percentiles, absent
+    /// without requests
+    pub p50: Option<f64>,
+    pub p90: Option<f64>,
+    pub p99: Option<f64>,
+    pub buckets: Vec<CostBucket>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct CostBreakdown {
+    pub provider: String,
+    pub model: String,
+    pub total_tokens_in: i64,
+    pub total_tokens_out: i64,
+    pub total_cost: f64,
+    pub request_count: i64,
 }
 
 fn extract_user_id(req: &HttpRequest) -> Result<Uuid> {
@@ -549,7 +1074,444 @@ fn extract_user_id(req: &HttpRequest) -> Result<Uuid> {
         .ok_or_else(|| AppError::Unauthorized)
 }
 
-fn calculate_period_bounds(period: &str, now: DateTime<Utc>) -> (DateTime<Utc>, DateTime<Utc>) {
+/// Organizations the user is a member of
+async fn caller_organizations(pool: &PgPool, user_id: Uuid) -> Result<Vec<Uuid>> {
+    let rows: Vec<(Uuid,)> = sqlx::query_as(
+        "SELECT organization_id FROM organization_members WHERE user_id = $1"
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().map(|(id,)| id).collect())
+}
+
+async fn team_organization(pool: &PgPool, team_id: Uuid) -> Result<Uuid> {
+    let team: (Uuid,) = sqlx::query_as("SELECT organization_id FROM teams WHERE id = $1")
+        .bind(team_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Team not found".to_string()))?;
+
+    Ok(team.0)
+}
+
+/// Organizations the user is an owner or admin of
+async fn caller_admin_organizations(pool: &PgPool, user_id: Uuid) -> Result<Vec<Uuid>> {
+    let rows: Vec<(Uuid,)> = sqlx::query_as(
+        "SELECT organization_id FROM organization_members WHERE user_id = $1 AND role IN ('owner', 'admin')"
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().map(|(id,)| id).collect())
+}
+
+/// Budgets are managed by the owners and admins of their organization
+pub async fn authorize_budget_admin(pool: &PgPool, user_id: Uuid, organization_id: Uuid) -> Result<()> {
+    let admin_orgs = caller_admin_organizations(pool, user_id).await?;
+    authorized_organizations(&admin_orgs, &[organization_id])?;
+    Ok(())
+}
+
+async fn budget_organization(pool: &PgPool, budget_id: Uuid) -> Result<Uuid> {
+    let budget: (Uuid,) = sqlx::query_as("SELECT organization_id FROM budgets WHERE id = $1")
+        .bind(budget_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Budget not found".to_string()))?;
+
+    Ok(budget.0)
+}
+
+/// Check the budget's team or user belongs to its organization
+pub async fn verify_budget_scope(pool: &PgPool, req: &CreateBudgetRequest) -> Result<()> {
+    if let Some(team_id) = req.team_id {
+        if team_organization(pool, team_id).await? != req.organization_id {
+            return Err(AppError::Validation(
+                "team_id belongs to a different organization".to_string(),
+            ));
+        }
+    }
+    if let Some(user_id) = req.user_id {
+        if !caller_organizations(pool, user_id).await?.contains(&req.organization_id) {
+            return Err(AppError::Validation(
+                "user_id is not a member of the organization".to_string(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Target organizations the caller also belongs to; `Forbidden` if there are none
+fn authorized_organizations(caller_orgs: &[Uuid], target_orgs: &[Uuid]) -> Result<Vec<Uuid>> {
+    let shared: Vec<Uuid> = target_orgs
+        .iter()
+        .filter(|id| caller_orgs.contains(id))
+        .copied()
+        .collect();
+
+    if shared.is_empty() {
+        return Err(AppError::Forbidden);
+    }
+
+    Ok(shared)
+}
+
+/// Quote a CSV field when it contains a delimiter, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn chargeback_csv(entries: &[ChargebackEntry]) -> String {
+    let mut csv = String::from("team_id,team_name,total_cost,total_requests,total_tokens\n");
+
+    for entry in entries {
+        csv.push_str(&format!(
+            "{},{},{:.6},{},{}\n",
+            entry.team_id.map(|id| id.to_string()).unwrap_or_default(),
+            csv_field(entry.team_name.as_deref().unwrap_or("")),
+            entry.total_cost,
+            entry.total_requests,
+            entry.total_tokens
+        ));
+    }
+
+    csv
+}
+
+/// Checks shared by budget creation and preview
+fn validate_budget_request(req: &CreateBudgetRequest) -> Result<()> {
+    req.validate()?;
+
+    // Validate that either team_id or user_id is provided
+    if (req.team_id.is_some() && req.user_id.is_some()) ||
+       (req.team_id.is_none() && req.user_id.is_none()) {
+        return Err(AppError::Validation("Provide either team_id or user_id, not both".to_string()));
+    }
+
+    Ok(())
+}
+
+fn budget_utilization(current_spend: f64, amount: f64) -> f64 {
+    if amount > 0.0 {
+        (current_spend / amount) * 100.0
+    } else {
+        0.0
+    }
+}
+
+fn budget_status(utilization_percent: f64, alert_threshold_percentage: i32) -> BudgetStatus {
+    if utilization_percent >= 100.0 {
+        BudgetStatus::Exceeded
+    } else if utilization_percent >= alert_threshold_percentage as f64 {
+        BudgetStatus::Warning
+    } else {
+        BudgetStatus::Ok
+    }
+}
+
+/// Filters and paging for `list_budgets`
+#[derive(Debug, PartialEq)]
+struct BudgetListParams {
+    /// Organizations the caller may see budgets of
+    organizations: Vec<Uuid>,
+    team_id: Option<Uuid>,
+    user_id: Option<Uuid>,
+    page: Page,
+}
+
+impl BudgetListParams {
+    fn new(query: &BudgetQuery, organizations: Vec<Uuid>) -> Self {
+        Self {
+            organizations,
+            team_id: query.team_id,
+            user_id: query.user_id,
+            page: Page::new(query.limit, query.offset),
+        }
+    }
+}
+
+/// `head` restricted by the list filters, shared by the page and its total
+fn filtered_budgets(head: &str, params: &BudgetListParams) -> DynamicQuery<'static> {
+    let mut query = DynamicQuery::new(head);
+    query
+        .and_where()
+        .push("organization_id = ANY(")
+        .push_bind(params.organizations.clone())
+        .push(")");
+    query
+        .filter_opt("team_id", params.team_id)
+        .filter_opt("user_id", params.user_id);
+    query
+}
+
+fn list_budgets_query(params: &BudgetListParams) -> DynamicQuery<'static> {
+    let mut query = filtered_budgets(
+        "SELECT id, organization_id, team_id, user_id, name, amount, period, \
+         alert_threshold_percentage, hard_limit, current_spend, \
+         period_start, period_end, is_active, created_at, updated_at \
+         FROM budgets",
+        params,
+    );
+    query.push(" ORDER BY created_at DESC, id DESC");
+    query
+}
+
+/// Group `(tag value, cost, request count)` rows by tag value, most expensive first
+fn build_tag_report(tag_key: &str, rows: Vec<(Option<String>, f64, i64)>) -> TagSpendReport {
+    let mut grouped: std::collections::HashMap<String, (f64, i64)> = std::collections::HashMap::new();
+    let mut untagged_cost = 0.0;
+    let mut untagged_request_count = 0;
+
+    for (tag_value, cost, count) in rows {
+        match tag_value {
+            Some(value) => {
+                let entry = grouped.entry(value).or_default();
+                entry.0 += cost;
+                entry.1 += count;
+            }
+            None => {
+                untagged_cost += cost;
+                untagged_request_count += count;
+            }
+        }
+    }
+
+    let tagged_total: f64 = grouped.values().map(|(cost, _)| cost).sum();
+    let mut groups: Vec<TagSpend> = grouped
+        .into_iter()
+        .map(|(tag_value, (total_cost, request_count))| TagSpend {
+            tag_value,
+            total_cost,
+            request_count,
+            percentage: if tagged_total > 0.0 { total_cost / tagged_total * 100.0 } else { 0.0 },
+        })
+        .collect();
+    groups.sort_by(|a, b| {
+        b.total_cost
+            .partial_cmp(&a.total_cost)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.tag_value.cmp(&b.tag_value))
+    });
+
+    TagSpendReport {
+        tag_key: tag_key.to_string(),
+        groups,
+        untagged_cost,
+        untagged_request_count,
+    }
+}
+
+/// Compare computed and reported cost per provider from
+/// `(provider, reported_requests, computed_cost, reported_cost)` rows
+fn reconcile_costs(rows: Vec<(String, i64, f64, f64)>, threshold_percent: f64) -> Vec<ProviderReconciliation> {
+    let mut providers: Vec<ProviderReconciliation> = rows
+        .into_iter()
+        .map(|(provider, reported_requests, computed_cost, reported_cost)| {
+            let difference = computed_cost - reported_cost;
+            let difference_percent = if reported_cost != 0.0 {
+                difference / reported_cost * 100.0
+            } else if computed_cost != 0.0 {
+                100.0
+            } else {
+                0.0
+            };
+
+            ProviderReconciliation {
+                provider,
+                reported_requests,
+                computed_cost,
+                reported_cost,
+                difference,
+                difference_percent,
+                discrepancy: reported_requests > 0 && difference_percent.abs() > threshold_percent,
+            }
+        })
+        .collect();
+
+    providers.sort_by(|a, b| {
+        b.difference_percent
+            .abs()
+            .partial_cmp(&a.difference_percent.abs())
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.provider.cmp(&b.provider))
+    });
+    providers
+}
+
+fn leaderboard_limit(limit: Option<u32>) -> u32 {
+    limit
+        .unwrap_or(DEFAULT_LEADERBOARD_LIMIT)
+        .clamp(1, MAX_PAGE_SIZE)
+}
+
+/// Top `limit` groups by spend, each row carrying the window's total spend
+///
+/// The total is a window over every group, so requests outside the top `limit`
+/// and without a team still count towards the share of the listed ones.
+pub fn leaderboard_query(
+    dimension: LeaderboardDimension,
+    org_ids: Vec<Uuid>,
+    window: &QueryWindow,
+    limit: u32,
+) -> DynamicQuery<'static> {
+    let (key, label, group_by, join) = dimension.columns();
+    let mut query = DynamicQuery::new(format!(
+        "SELECT group_key, group_label, total_cost, request_count, grand_total FROM (\
+         SELECT {key} AS group_key, {label} AS group_label, \
+         SUM(r.total_cost)::FLOAT8 AS total_cost, COUNT(*) AS request_count, \
+         (SUM(SUM(r.total_cost)) OVER ())::FLOAT8 AS grand_total \
+         FROM llm_requests r \
+         JOIN llm_models m ON r.model_id = m.id \
+         JOIN llm_providers p ON m.provider_id = p.id{join}"
+    ));
+    query.and_where().push("r.organization_id = ANY(").push_bind(org_ids).push(")");
+    query
+        .and_where()
+        .push("r.timestamp BETWEEN ")
+        .push_bind(window.start.clone())
+        .push("::timestamptz AND ")
+        .push_bind(window.end.clone())
+        .push("::timestamptz");
+    query.push(&format!(
+        " GROUP BY {group_by}) spend WHERE group_key IS NOT NULL \
+         ORDER BY total_cost DESC, group_key, group_label LIMIT "
+    ));
+    query.push_bind(limit as i64);
+    query
+}
+
+/// Rank leaderboard rows by spend and work out each one's share of the total
+pub fn rank_spenders(rows: Vec<LeaderboardRow>) -> (f64, Vec<LeaderboardEntry>) {
+    let total_cost = rows.first().map(|row| row.4).unwrap_or(0.0);
+
+    let mut entries: Vec<LeaderboardEntry> = rows
+        .into_iter()
+        .map(|(key, label, cost, request_count, _)| LeaderboardEntry {
+            rank: 0,
+            key,
+            label,
+            total_cost: cost,
+            request_count,
+            share_percent: if total_cost > 0.0 { cost / total_cost * 100.0 } else { 0.0 },
+        })
+        .collect();
+    entries.sort_by(|a, b| {
+        b.total_cost
+            .partial_cmp(&a.total_cost)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.key.cmp(&b.key))
+            .then_with(|| a.label.cmp(&b.label))
+    });
+    for (i, entry) in entries.iter_mut().enumerate() {
+        entry.rank = i + 1;
+    }
+
+    (total_cost, entries)
+}
+
+/// `(bucket index, request count, total cost)` from `width_bucket` over the edges
+pub type BucketRow = (i32, i64, f64);
+
+/// `(p50, p90, p99)` per-request cost
+pub type CostPercentiles = (Option<f64>, Option<f64>, Option<f64>);
+
+/// `head` over the `llm_metrics` rows of `query`'s scope in `org_ids` and `window`
+///
+/// `llm_metrics` has no organization column, so rows are scoped through their
+/// team and user.
+fn distribution_filter(
+    head: DynamicQuery<'static>,
+    query: &DistributionQuery,
+    org_ids: Vec<Uuid>,
+    window: &QueryWindow,
+) -> DynamicQuery<'static> {
+    let mut sql = head;
+    sql.and_where()
+        .push("m.time BETWEEN ")
+        .push_bind(window.start.clone())
+        .push("::timestamptz AND ")
+        .push_bind(window.end.clone())
+        .push("::timestamptz");
+    if let Some(column) = query.scope.column() {
+        sql.filter(column, query.id);
+    }
+    sql.and_where()
+        .push("(m.team_id IN (SELECT id FROM teams WHERE organization_id = ANY(")
+        .push_bind(org_ids.clone())
+        .push(")) OR m.user_id IN (SELECT user_id FROM organization_members WHERE organization_id = ANY(")
+        .push_bind(org_ids)
+        .push(")))");
+    sql
+}
+
+/// Requests and spend per bucket; `width_bucket` puts costs below the first
+/// edge in bucket 0 and costs at or above edge `i` in bucket `i`
+pub fn bucket_query(
+    query: &DistributionQuery,
+    org_ids: Vec<Uuid>,
+    window: &QueryWindow,
+    edges: &[f64],
+) -> DynamicQuery<'static> {
+    let mut head = DynamicQuery::new("SELECT width_bucket(m.cost::FLOAT8, ");
+    head.push_bind(edges.to_vec()).push(
+        "::FLOAT8[]) AS bucket, COUNT(*) AS request_count, COALESCE(SUM(m.cost), 0)::FLOAT8 AS total_cost \
+         FROM llm_metrics m",
+    );
+    let mut sql = distribution_filter(head, query, org_ids, window);
+    sql.push(" GROUP BY bucket ORDER BY bucket");
+    sql
+}
+
+pub fn percentile_query(query: &DistributionQuery, org_ids: Vec<Uuid>, window: &QueryWindow) -> DynamicQuery<'static> {
+    let head = DynamicQuery::new(
+        "SELECT percentile_disc(0.50) WITHIN GROUP (ORDER BY m.cost::FLOAT8), \
+         percentile_disc(0.90) WITHIN GROUP (ORDER BY m.cost::FLOAT8), \
+         percentile_disc(0.99) WITHIN GROUP (ORDER BY m.cost::FLOAT8) \
+         FROM llm_metrics m",
+    );
+    distribution_filter(head, query, org_ids, window)
+}
+
+/// Histogram over ascending `edges` from the per-bucket rows, with empty
+/// buckets filled in
+pub fn cost_distribution(rows: Vec<BucketRow>, percentiles: CostPercentiles, edges: &[f64]) -> CostDistribution {
+    let mut buckets: Vec<CostBucket> = std::iter::once(0.0)
+        .chain(edges.iter().copied())
+        .zip(edges.iter().copied().map(Some).chain(std::iter::once(None)))
+        .map(|(lower, upper)| CostBucket {
+            lower,
+            upper,
+            request_count: 0,
+            total_cost: 0.0,
+        })
+        .collect();
+    for (index, request_count, total_cost) in rows {
+        if let Some(bucket) = usize::try_from(index).ok().and_then(|i| buckets.get_mut(i)) {
+            bucket.request_count += request_count as usize;
+            bucket.total_cost += total_cost;
+        }
+    }
+
+    let (p50, p90, p99) = percentiles;
+    CostDistribution {
+        request_count: buckets.iter().map(|b| b.request_count).sum(),
+        total_cost: buckets.iter().map(|b| b.total_cost).sum(),
+        p50,
+        p90,
+        p99,
+        buckets,
+    }
+}
+
+pub fn calculate_period_bounds(period: &str, now: DateTime<Utc>) -> (DateTime<Utc>, DateTime<Utc>) {
     match period {
         "daily" => {
             let start = now.date_naive().and_hms_opt(0, 0, 0).unwrap().and_local_timezone(chrono::Utc).unwrap();
@@ -580,21 +1542,229 @@ fn calculate_period_bounds(period: &str, now: DateTime<Utc>) -> (DateTime<Utc>,
     }
 }
 
+#[get("/costs/by-tag")]
+pub async fn get_costs_by_tag(
+    pool: web::Data<PgPool>,
+    read_pool: web::Data<ReadPool>,
+    config: web::Data<Config>,
+    query: web::Query<TagCostQuery>,
+    http_req: HttpRequest,
+) -> Result<impl Responder> {
+    if query.tag_key.is_empty() || query.tag_key.len() > MAX_TAG_KEY_LEN {
+        return Err(AppError::Validation(format!(
+            "tag_key must be 1-{} characters",
+            MAX_TAG_KEY_LEN
+        )));
+    }
+
+    let caller_orgs = caller_organizations(pool.get_ref(), extract_user_id(&http_req)?).await?;
+    let org_ids = authorized_organizations(&caller_orgs, &caller_orgs)?;
+
+    let window = resolve_window(query.from.as_deref(), query.to.as_deref(), config.default_window_days);
+    check_window_span(&window, config.max_query_days)?;
+
+    // llm_metrics has no organization column, so scope through team and membership
+    let mut tx = begin_with_timeout(read_pool.pool(), config.query_timeout()).await?;
+    let rows: Vec<(Option<String>, f64, i64)> = with_timeout(config.query_timeout(), sqlx::query_as(
+        r#"
+        SELECT m.tags->>$1 as tag_value, COALESCE(SUM(m.cost), 0)::FLOAT8 as total_cost, COUNT(*) as request_count
+        FROM llm_metrics m
+        WHERE m.time BETWEEN $2::timestamptz AND $3::timestamptz
+        AND (
+            m.team_id IN (SELECT id FROM teams WHERE organization_id = ANY($4))
+            OR m.user_id IN (SELECT user_id FROM organization_members WHERE organization_id = ANY($4))
+        )
+        GROUP BY tag_value
+        "#,
+    )
+    .bind(&query.tag_key)
+    .bind(&window.start)
+    .bind(&window.end)
+    .bind(&org_ids)
+    .fetch_all(&mut *tx))
+    .await?;
+
+    let report = build_tag_report(&query.tag_key, rows);
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
+        "period": {
+            "start": window.start,
+            "end": window.end
+        },
+        "report": report
+    }))))
+}
+
+#[get("/costs/reconciliation")]
+pub async fn get_cost_reconciliation(
+    pool: web::Data<PgPool>,
+    config: web::Data<Config>,
+    query: web::Query<ReconciliationQuery>,
+    http_req: HttpRequest,
+) -> Result<impl Responder> {
+    let threshold_percent = query
+        .threshold_percent
+        .unwrap_or(config.reconciliation_threshold_percent);
+    if !threshold_percent.is_finite() || threshold_percent < 0.0 {
+        return Err(AppError::Validation(
+            "threshold_percent must be a non-negative number".to_string(),
+        ));
+    }
+
+    let caller_orgs = caller_organizations(pool.get_ref(), extract_user_id(&http_req)?).await?;
+    let org_ids = authorized_organizations(&caller_orgs, &caller_orgs)?;
+
+    let window = resolve_window(query.from.as_deref(), query.to.as_deref(), config.default_window_days);
+    check_window_span(&window, config.max_query_days)?;
+
+    // Only requests with a reported amount are compared, so unreported traffic
+    // does not show up as drift
+    let rows: Vec<(String, i64, f64, f64)> = sqlx::query_as(
+        r#"
+        SELECT
+            m.provider,
+            COUNT(*) as reported_requests,
+            COALESCE(SUM(m.cost), 0)::FLOAT8 as computed_cost,
+            COALESCE(SUM(m.reported_cost), 0)::FLOAT8 as reported_cost
+        FROM llm_metrics m
+        WHERE m.reported_cost IS NOT NULL
+        AND m.time BETWEEN $1::timestamptz AND $2::timestamptz
+        AND (
+            m.team_id IN (SELECT id FROM teams WHERE organization_id = ANY($3))
+            OR m.user_id IN (SELECT user_id FROM organization_members WHERE organization_id = ANY($3))
+        )
+        GROUP BY m.provider
+        "#,
+    )
+    .bind(&window.start)
+    .bind(&window.end)
+    .bind(&org_ids)
+    .fetch_all(pool.get_ref())
+    .await?;
+
+    let providers = reconcile_costs(rows, threshold_percent);
+    let discrepancies = providers.iter().filter(|p| p.discrepancy).count();
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
+        "period": {
+            "start": window.start,
+            "end": window.end
+        },
+        "threshold_percent": threshold_percent,
+        "discrepancies": discrepancies,
+        "providers": providers
+    }))))
+}
+
+#[get("/costs/leaderboard")]
+pub async fn get_spend_leaderboard(
+    pool: web::Data<PgPool>,
+    read_pool: web::Data<ReadPool>,
+    config: web::Data<Config>,
+    query: web::Query<LeaderboardQuery>,
+    http_req: HttpRequest,
+) -> Result<impl Responder> {
+    let caller_orgs = caller_organizations(pool.get_ref(), extract_user_id(&http_req)?).await?;
+    let org_ids = match query.organization_id {
+        Some(org_id) => authorized_organizations(&caller_orgs, &[org_id])?,
+        None => authorized_organizations(&caller_orgs, &caller_orgs)?,
+    };
+
+    let window = resolve_window(query.from.as_deref(), query.to.as_deref(), config.default_window_days);
+    check_window_span(&window, config.max_query_days)?;
+
+    let mut sql = leaderboard_query(query.dimension, org_ids, &window, leaderboard_limit(query.limit));
+    let mut tx = begin_with_timeout(read_pool.pool(), config.query_timeout()).await?;
+    let rows: Vec<LeaderboardRow> = with_timeout(
+        config.query_timeout(),
+        sql.build_query_as().fetch_all(&mut *tx),
+    )
+    .await?;
+
+    let (total_cost, entries) = rank_spenders(rows);
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
+        "dimension": query.dimension,
+        "period": {
+            "start": window.start,
+            "end": window.end
+        },
+        "total_cost": total_cost,
+        "entries": entries
+    }))))
+}
+
+#[get("/costs/distribution")]
+pub async fn get_cost_distribution(
+    pool: web::Data<PgPool>,
+    read_pool: web::Data<ReadPool>,
+    config: web::Data<Config>,
+    query: web::Query<DistributionQuery>,
+    http_req: HttpRequest,
+) -> Result<impl Responder> {
+    let edges = parse_bucket_edges(query.buckets.as_deref().unwrap_or(&config.cost_bucket_edges))
+        .map_err(AppError::Validation)?;
+
+    let caller_orgs = caller_organizations(pool.get_ref(), extract_user_id(&http_req)?).await?;
+    let target_orgs = match query.scope {
+        DistributionScope::Organization => vec![query.id],
+        DistributionScope::Team => vec![team_organization(pool.get_ref(), query.id).await?],
+        DistributionScope::User => caller_organizations(pool.get_ref(), query.id).await?,
+    };
+    let org_ids = authorized_organizations(&caller_orgs, &target_orgs)?;
+
+    let window = resolve_window(query.from.as_deref(), query.to.as_deref(), config.default_window_days);
+    check_window_span(&window, config.max_query_days)?;
+
+    let mut tx = begin_with_timeout(read_pool.pool(), config.query_timeout()).await?;
+    let mut bucket_sql = bucket_query(&query, org_ids.clone(), &window, &edges);
+    let rows: Vec<BucketRow> = with_timeout(
+        config.query_timeout(),
+        bucket_sql.build_query_as().fetch_all(&mut *tx),
+    )
+    .await?;
+    let mut percentile_sql = percentile_query(&query, org_ids, &window);
+    let percentiles: CostPercentiles = with_timeout(
+        config.query_timeout(),
+        percentile_sql.build_query_as().fetch_one(&mut *tx),
+    )
+    .await?;
+
+    let distribution = cost_distribution(rows, percentiles, &edges);
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
+        "scope": query.scope,
+        "id": query.id,
+        "period": {
+            "start": window.start,
+            "end": window.end
+        },
+        "distribution": distribution
+    }))))
+}
+
 #[get("/costs/organization/{organization_id}")]
 pub async fn get_organization_costs(
     pool: web::Data<PgPool>,
+    read_pool: web::Data<ReadPool>,
+    config: web::Data<Config>,
     organization_id: web::Path<Uuid>,
     query: web::Query<CostQuery>,
+    http_req: HttpRequest,
 ) -> Result<impl Responder> {
-    let start_date = query.start_date.clone().unwrap_or_else(|| {
-        chrono::Utc::now().checked_sub_signed(chrono::Duration::days(30)).unwrap().to_rfc3339()
-    });
-
-    let end_date = query.end_date.clone().unwrap_or_else(|| {
-        chrono::Utc::now().to_rfc3339()
-    });
-
-    let costs = sqlx::query_as::<_, CostBreakdown>(
+    let caller_orgs = caller_organizations(pool.get_ref(), extract_user_id(&http_req)?).await?;
+    authorized_organizations(&caller_orgs, &[*organization_id])?;
+
+    let window = resolve_window(
+        query.start_date.as_deref(),
+        query.end_date.as_deref(),
+        config.default_window_days,
+    );
+    check_window_span(&window, config.max_query_days)?;
+    let (start_date, end_date) = (window.start, window.end);
+
+    let mut tx = begin_with_timeout(read_pool.pool(), config.query_timeout()).await?;
+    let costs = with_timeout(config.query_timeout(), sqlx::query_as::<_, CostBreakdown>(
         r#"
         SELECT
             p.provider_name as provider,
@@ -615,7 +1785,7 @@ pub async fn get_organization_costs(
     .bind(organization_id.as_ref())
     .bind(&start_date)
     .bind(&end_date)
-    .fetch_all(pool.get_ref())
+    .fetch_all(&mut *tx))
     .await?;
 
     let total_cost: f64 = costs.iter().map(|c| c.total_cost).sum();
@@ -636,7 +1806,13 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
         .service(get_organization_costs)
         .service(get_team_costs)
         .service(get_user_costs)
+        .service(get_costs_by_tag)
+        .service(get_cost_reconciliation)
+        .service(get_spend_leaderboard)
+        .service(get_cost_distribution)
+        .service(preview_budget)
         .service(create_budget)
+        .service(upsert_budget)
         .service(list_budgets)
         .service(get_budget)
         .service(update_budget)
@@ -644,3 +1820,390 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
         .service(forecast_costs)
         .service(generate_chargeback_report);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use llm_governance_common::db::DEFAULT_PAGE_SIZE;
+
+    #[test]
+    fn test_chargeback_csv_quotes_team_names() {
+        let team_id = Uuid::new_v4();
+        let entries = vec![
+            ChargebackEntry {
+                team_id: Some(team_id),
+                team_name: Some("Research, \"Applied\"".to_string()),
+                total_cost: 12.5,
+                total_requests: 40,
+                total_tokens: 9000,
+            },
+            ChargebackEntry {
+                team_id: None,
+                team_name: None,
+                total_cost: 0.25,
+                total_requests: 1,
+                total_tokens: 10,
+            },
+        ];
+
+        let csv = chargeback_csv(&entries);
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines[0], "team_id,team_name,total_cost,total_requests,total_tokens");
+        assert_eq!(lines[1], format!("{},\"Research, \"\"Applied\"\"\",12.500000,40,9000", team_id));
+        assert_eq!(lines[2], ",,0.250000,1,10");
+    }
+
+    #[test]
+    fn test_cross_org_team_is_rejected() {
+        let caller_org = Uuid::new_v4();
+        let other_org = Uuid::new_v4();
+
+        assert!(matches!(
+            authorized_organizations(&[caller_org], &[other_org]),
+            Err(AppError::Forbidden)
+        ));
+        assert_eq!(
+            authorized_organizations(&[caller_org], &[caller_org]).unwrap(),
+            vec![caller_org]
+        );
+    }
+
+    #[test]
+    fn test_user_costs_scoped_to_shared_organizations() {
+        let shared = Uuid::new_v4();
+        let caller_only = Uuid::new_v4();
+        let target_only = Uuid::new_v4();
+
+        let orgs = authorized_organizations(&[shared, caller_only], &[shared, target_only]).unwrap();
+        assert_eq!(orgs, vec![shared]);
+
+        assert!(authorized_organizations(&[], &[shared]).is_err());
+    }
+
+    fn params(organizations: Vec<Uuid>, team_id: Option<Uuid>, user_id: Option<Uuid>) -> BudgetListParams {
+        BudgetListParams::new(
+            &BudgetQuery {
+                team_id,
+                user_id,
+                ..Default::default()
+            },
+            organizations,
+        )
+    }
+
+    #[test]
+    fn test_list_budgets_sql_placeholders_match_binds() {
+        let p = params(vec![Uuid::new_v4()], Some(Uuid::new_v4()), None);
+        let mut query = list_budgets_query(&p);
+        p.page.apply(&mut query);
+        assert!(query.sql().ends_with(
+            "WHERE organization_id = ANY($1) AND team_id = $2 ORDER BY created_at DESC, id DESC LIMIT $3 OFFSET $4"
+        ));
+
+        let p = params(vec![Uuid::new_v4()], None, Some(Uuid::new_v4()));
+        let mut query = list_budgets_query(&p);
+        p.page.apply(&mut query);
+        assert!(query.sql().ends_with(
+            "WHERE organization_id = ANY($1) AND user_id = $2 ORDER BY created_at DESC, id DESC LIMIT $3 OFFSET $4"
+        ));
+
+        let total = filtered_budgets("SELECT COUNT(*) FROM budgets", &p);
+        assert_eq!(
+            total.sql(),
+            "SELECT COUNT(*) FROM budgets WHERE organization_id = ANY($1) AND user_id = $2"
+        );
+    }
+
+    #[test]
+    fn test_list_budgets_always_scoped_to_organizations() {
+        let org = Uuid::new_v4();
+        let team = Some(Uuid::new_v4());
+        let user = Some(Uuid::new_v4());
+
+        for (team_id, user_id) in [(None, None), (team, None), (None, user), (team, user)] {
+            let p = params(vec![org], team_id, user_id);
+            // Each filter keeps its own slot regardless of which others are set
+            assert_eq!(p.organizations, vec![org]);
+            assert_eq!(p.team_id, team_id);
+            assert_eq!(p.user_id, user_id);
+            assert_eq!(p.page, Page { limit: DEFAULT_PAGE_SIZE, offset: 0 });
+
+            let total = filtered_budgets("SELECT COUNT(*) FROM budgets", &p);
+            assert!(total.sql().starts_with("SELECT COUNT(*) FROM budgets WHERE organization_id = ANY($1)"));
+        }
+    }
+
+    #[test]
+    fn test_list_budgets_limit_is_capped() {
+        let p = BudgetListParams::new(
+            &BudgetQuery {
+                limit: Some(500),
+                offset: Some(40),
+                ..Default::default()
+            },
+            vec![],
+        );
+        assert_eq!(p.page, Page { limit: MAX_PAGE_SIZE, offset: 40 });
+    }
+
+    fn budget_request(period: &str) -> CreateBudgetRequest {
+        CreateBudgetRequest {
+            name: format!("preview-{}", Uuid::new_v4()),
+            organization_id: Uuid::new_v4(),
+            team_id: Some(Uuid::new_v4()),
+            user_id: None,
+            amount: 200.0,
+            period: period.to_string(),
+            alert_threshold_percentage: None,
+            hard_limit: Some(true),
+        }
+    }
+
+    #[test]
+    fn test_budget_preview_bounds_and_utilization() {
+        let now = Utc.with_ymd_and_hms(2025, 3, 10, 15, 30, 0).unwrap();
+        let preview = BudgetPreview::new(&budget_request("weekly"), now, 170.0);
+
+        assert_eq!(preview.period_start, Utc.with_ymd_and_hms(2025, 3, 10, 0, 0, 0).unwrap());
+        assert_eq!(preview.period_end, Utc.with_ymd_and_hms(2025, 3, 17, 0, 0, 0).unwrap());
+        assert_eq!(preview.alert_threshold_percentage, 80);
+        assert!(preview.hard_limit);
+        assert_eq!(preview.utilization_percent, 85.0);
+        assert_eq!(preview.status, BudgetStatus::Warning);
+    }
+
+    #[test]
+    fn test_budget_preview_rejects_ambiguous_scope() {
+        let mut req = budget_request("daily");
+        req.user_id = Some(Uuid::new_v4());
+        assert!(matches!(validate_budget_request(&req), Err(AppError::Validation(_))));
+    }
+
+    fn upsert_request(team_id: Option<Uuid>, user_id: Option<Uuid>) -> UpsertBudgetRequest {
+        UpsertBudgetRequest {
+            organization_id: Uuid::new_v4(),
+            team_id,
+            user_id,
+            amount: 500.0,
+            period: "monthly".to_string(),
+            alert_threshold_percentage: Some(75),
+            hard_limit: None,
+        }
+    }
+
+    #[test]
+    fn test_upsert_rejects_conflicting_scope() {
+        let req = upsert_request(Some(Uuid::new_v4()), Some(Uuid::new_v4())).named("ml-platform".to_string());
+        assert!(matches!(validate_budget_request(&req), Err(AppError::Validation(_))));
+
+        let req = upsert_request(None, None).named("ml-platform".to_string());
+        assert!(matches!(validate_budget_request(&req), Err(AppError::Validation(_))));
+    }
+
+    #[test]
+    fn test_natural_key_separates_team_and_user_scopes() {
+        let id = Uuid::new_v4();
+        let team = upsert_request(Some(id), None).named("ml-platform".to_string());
+        let mut user = upsert_request(None, Some(id)).named("ml-platform".to_string());
+        user.organization_id = team.organization_id;
+
+        assert_ne!(budget_natural_key(&team), budget_natural_key(&user));
+        assert_eq!(budget_natural_key(&team), format!("{}:{}::ml-platform", team.organization_id, id));
+        assert_eq!(budget_natural_key(&user), format!("{}::{}:ml-platform", user.organization_id, id));
+    }
+
+    #[test]
+    fn test_budget_status_thresholds() {
+        assert_eq!(budget_utilization(50.0, 200.0), 25.0);
+        assert_eq!(budget_utilization(10.0, 0.0), 0.0);
+
+        assert_eq!(budget_status(25.0, 80), BudgetStatus::Ok);
+        assert_eq!(budget_status(80.0, 80), BudgetStatus::Warning);
+        assert_eq!(budget_status(100.0, 80), BudgetStatus::Exceeded);
+        assert_eq!(budget_status(120.0, 80), BudgetStatus::Exceeded);
+    }
+
+    #[test]
+    fn test_tag_report_groups_by_tag_value() {
+        let rows = vec![
+            (Some("search".to_string()), 2.0, 4),
+            (Some("chatbot".to_string()), 5.0, 10),
+            (None, 1.5, 3),
+            (Some("search".to_string()), 3.0, 6),
+        ];
+
+        let report = build_tag_report("project", rows);
+
+        assert_eq!(report.tag_key, "project");
+        assert_eq!(report.groups.len(), 2);
+        assert_eq!(report.groups[0].tag_value, "chatbot");
+        assert_eq!(report.groups[1].tag_value, "search");
+        assert_eq!(report.groups[1].total_cost, 5.0);
+        assert_eq!(report.groups[1].request_count, 10);
+        assert!((report.groups[0].percentage - 50.0).abs() < 1e-9);
+        assert_eq!(report.untagged_cost, 1.5);
+        assert_eq!(report.untagged_request_count, 3);
+    }
+
+    const DIMENSIONS: [LeaderboardDimension; 4] = [
+        LeaderboardDimension::Team,
+        LeaderboardDimension::User,
+        LeaderboardDimension::Provider,
+        LeaderboardDimension::Model,
+    ];
+
+    #[test]
+    fn test_leaderboard_sql_groups_by_dimension() {
+        let window = resolve_window(Some("2025-03-01T00:00:00Z"), Some("2025-03-31T00:00:00Z"), 30);
+
+        for (dimension, group_by) in DIMENSIONS.into_iter().zip([
+            "GROUP BY r.team_id, t.name)",
+            "GROUP BY r.user_id, u.email)",
+            "GROUP BY p.provider_name)",
+            "GROUP BY m.model_name, p.provider_name)",
+        ]) {
+            let query = leaderboard_query(dimension, vec![Uuid::new_v4()], &window, 5);
+            let sql = query.sql();
+
+            assert!(sql.contains(group_by), "{:?}: {}", dimension, sql);
+            assert!(sql.contains("WHERE r.organization_id = ANY($1) AND r.timestamp BETWEEN $2::timestamptz AND $3::timestamptz"));
+            assert!(sql.ends_with("ORDER BY total_cost DESC, group_key, group_label LIMIT $4"));
+        }
+    }
+
+    #[test]
+    fn test_leaderboard_without_spend() {
+        assert_eq!(rank_spenders(Vec::new()), (0.0, Vec::new()));
+
+        let (_, entries) = rank_spenders(vec![("openai".to_string(), None, 0.0, 3, 0.0)]);
+        assert_eq!(entries[0].share_percent, 0.0);
+    }
+
+    #[test]
+    fn test_leaderboard_limit_is_clamped() {
+        assert_eq!(leaderboard_limit(None), DEFAULT_LEADERBOARD_LIMIT);
+        assert_eq!(leaderboard_limit(Some(0)), 1);
+        assert_eq!(leaderboard_limit(Some(25)), 25);
+        assert_eq!(leaderboard_limit(Some(5000)), MAX_PAGE_SIZE);
+    }
+
+    fn distribution_query(scope: DistributionScope) -> DistributionQuery {
+        DistributionQuery {
+            scope,
+            id: Uuid::new_v4(),
+            from: None,
+            to: None,
+            buckets: None,
+        }
+    }
+
+    #[test]
+    fn test_cost_buckets_are_filled_from_bucket_rows() {
+        // width_bucket indices: 0 below the first edge, 3 at or above the last
+        let rows = vec![(0, 1, 0.0005), (1, 2, 0.021), (3, 2, 42.0)];
+        let distribution = cost_distribution(rows, (Some(0.02), Some(30.0), Some(30.0)), &[0.001, 0.1, 10.0]);
+
+        let counts: Vec<(f64, Option<f64>, usize)> = distribution
+            .buckets
+            .iter()
+            .map(|b| (b.lower, b.upper, b.request_count))
+            .collect();
+        assert_eq!(
+            counts,
+            vec![
+                (0.0, Some(0.001), 1),
+                (0.001, Some(0.1), 2),
+                (0.1, Some(10.0), 0),
+                (10.0, None, 2),
+            ]
+        );
+        assert_eq!(distribution.request_count, 5);
+        assert!((distribution.total_cost - 42.0215).abs() < 1e-9);
+        assert_eq!(distribution.p50, Some(0.02));
+
+        let empty = cost_distribution(Vec::new(), (None, None, None), &[1.0]);
+        assert!(empty.buckets.iter().all(|b| b.request_count == 0));
+        assert_eq!((empty.request_count, empty.p99), (0, None));
+    }
+
+    #[test]
+    fn test_distribution_sql_reads_llm_metrics_by_scope() {
+        let window = resolve_window(Some("2025-03-01T00:00:00Z"), Some("2025-03-31T00:00:00Z"), 30);
+
+        for (scope, condition) in [
+            (DistributionScope::Organization, None),
+            (DistributionScope::Team, Some("AND m.team_id = $4 AND")),
+            (DistributionScope::User, Some("AND m.user_id = $4 AND")),
+        ] {
+            let query = distribution_query(scope);
+            let buckets = bucket_query(&query, vec![Uuid::new_v4()], &window, &[0.1, 1.0]);
+            let sql = buckets.sql();
+
+            assert!(sql.starts_with("SELECT width_bucket(m.cost::FLOAT8, $1::FLOAT8[]) AS bucket"), "{}", sql);
+            assert!(sql.contains("FROM llm_metrics m WHERE m.time BETWEEN $2::timestamptz AND $3::timestamptz"));
+            assert!(sql.ends_with("GROUP BY bucket ORDER BY bucket"));
+            match condition {
+                Some(condition) => assert!(sql.contains(condition), "{:?}: {}", scope, sql),
+                None => assert!(!sql.contains("m.team_id = ") && !sql.contains("m.user_id = ")),
+            }
+
+            let percentiles = percentile_query(&query, vec![Uuid::new_v4()], &window);
+            assert!(percentiles.sql().contains("percentile_disc(0.99) WITHIN GROUP (ORDER BY m.cost::FLOAT8)"));
+            assert!(!percentiles.sql().contains("GROUP BY"));
+        }
+    }
+
+    #[test]
+    fn test_calculation_matches_shared_pricing() {
+        for (provider, model) in [("openai", "gpt-4"), ("anthropic", "claude-3-opus"), ("acme", "unknown")] {
+            let req = CalculateCostRequest {
+                provider: provider.to_string(),
+                model: model.to_string(),
+                tokens_in: 1200,
+                tokens_out: 800,
+            };
+            let calc = cost_calculation(&req).unwrap();
+            assert_eq!(calc.total_cost, pricing::cost_for(provider, model, 1200, 800).unwrap());
+            assert_eq!(calc.pricing_fallback.is_some(), provider == "acme");
+            assert!((calc.input_cost + calc.output_cost - calc.total_cost).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_matching_costs_reconcile() {
+        let rows = vec![
+            ("openai".to_string(), 120, 10.00, 10.02),
+            ("anthropic".to_string(), 40, 4.0, 4.0),
+        ];
+
+        let providers = reconcile_costs(rows, 5.0);
+
+        assert_eq!(providers.len(), 2);
+        assert!(providers.iter().all(|p| !p.discrepancy));
+        let openai = providers.iter().find(|p| p.provider == "openai").unwrap();
+        assert!((openai.difference + 0.02).abs() < 1e-9);
+        assert!(openai.difference_percent.abs() < 1.0);
+    }
+
+    #[test]
+    fn test_divergent_costs_are_flagged() {
+        let rows = vec![
+            ("openai".to_string(), 120, 10.0, 10.1),
+            ("anthropic".to_string(), 40, 3.0, 4.0),
+            ("google".to_string(), 5, 0.5, 0.0),
+        ];
+
+        let providers = reconcile_costs(rows, 5.0);
+
+        // Largest relative drift first
+        assert_eq!(providers[0].provider, "google");
+        assert!(providers[0].discrepancy);
+        assert_eq!(providers[1].provider, "anthropic");
+        assert!(providers[1].discrepancy);
+        assert!((providers[1].difference_percent + 25.0).abs() < 1e-9);
+        assert_eq!(providers[2].provider, "openai");
+        assert!(!providers[2].discrepancy);
+    }
+}
@@ -1,7 +1,35 @@
 use actix_web::web;
 
 pub mod health;
+pub mod costs;
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
-    cfg.service(web::scope("/api/v1").configure(health::configure));
+    cfg.service(llm_governance_common::metrics::metrics_endpoint);
+    cfg.service(llm_governance_common::versioning::version_endpoint);
+    cfg.service(
+        web::scope("/api/v1")
+            .configure(health::configure)
+            .configure(costs::configure),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{http::StatusCode, App};
+
+    #[actix_web::test]
+    async fn test_cost_routes_are_mounted() {
+        let app = actix_web::test::init_service(App::new().configure(configure)).await;
+
+        // Without app data the handler fails to extract its pool, but the
+        // route itself resolves
+        let req = actix_web::test::TestRequest::get().uri("/api/v1/costs/budgets").to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_ne!(resp.status(), StatusCode::NOT_FOUND);
+
+        let req = actix_web::test::TestRequest::get().uri("/api/v1/costs/unknown/route").to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
 }
@@ -0,0 +1,7 @@
+//! Cost tracking, budgets and spend analytics, served by `main.rs`
+
+pub mod config;
+pub mod handlers;
+pub mod middleware;
+pub mod models;
+pub mod services;
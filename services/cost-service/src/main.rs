@@ -1,38 +1,61 @@
 use actix_web::{web, App, HttpServer};
-use tracing::{info, Level};
-use tracing_subscriber;
+use tracing::info;
 
-mod config;
-mod handlers;
-mod middleware;
-mod models;
-mod services;
-
-use config::Config;
+use cost_service::config::Config;
+use cost_service::handlers;
+use llm_governance_common::metrics::RequestMetrics;
+use llm_governance_common::pricing;
+use llm_governance_common::idempotency;
+use llm_governance_common::request_context::RequestContext;
+use llm_governance_common::scheduler::Scheduler;
+use llm_governance_common::telemetry;
+use llm_governance_common::versioning::ApiVersioning;
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
-    tracing_subscriber::fmt()
-        .with_max_level(Level::INFO)
-        .init();
-
     dotenv::dotenv().ok();
+    telemetry::init("cost-service").expect("Failed to initialize telemetry");
+
     let config = Config::from_env().expect("Failed to load configuration");
+    if let Err(problems) = config.validate() {
+        panic!("Invalid configuration:\n  - {}", problems.join("\n  - "));
+    }
+    pricing::configure_pricing(config.pricing_table_path.as_deref(), &config.pricing_fallback)
+        .expect("Failed to configure pricing");
 
     info!("Starting cost-service on {}:{}", config.host, config.port);
 
     let db_pool = llm_governance_database::create_pool(&config.database_url)
         .await
         .expect("Failed to create database pool");
+    let read_pool = llm_governance_database::create_read_pool(&db_pool, config.database_read_url.as_deref())
+        .await
+        .expect("Failed to create read replica pool");
+
+    let mut scheduler = Scheduler::new();
+    let purge_pool = db_pool.clone();
+    scheduler.every(idempotency::PURGE_INTERVAL, "purge_idempotency_keys", move || {
+        let pool = purge_pool.clone();
+        async move { idempotency::purge_expired_keys(&pool).await.map(|_| ()) }
+    });
+    let scheduler = scheduler.start();
 
-    HttpServer::new(move || {
+    let result = HttpServer::new(move || {
         App::new()
             .app_data(web::Data::new(db_pool.clone()))
+            .app_data(web::Data::new(read_pool.clone()))
             .app_data(web::Data::new(config.clone()))
+            .wrap(RequestContext)
             .wrap(tracing_actix_web::TracingLogger::default())
+            .wrap(RequestMetrics::new("cost-service"))
+            .wrap(ApiVersioning::new())
             .configure(handlers::configure)
     })
     .bind((config.host.as_str(), config.port))?
     .run()
-    .await
+    .await;
+
+    scheduler.shutdown().await;
+    telemetry::shutdown();
+    result
 }
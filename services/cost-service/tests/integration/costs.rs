@@ -0,0 +1,385 @@
+use actix_web::web;
+use chrono::Utc;
+use uuid::Uuid;
+
+use cost_service::config::Config;
+use cost_service::handlers::costs::*;
+use llm_governance_common::testing::{database, seed_member, seed_organization, seed_team, seed_user};
+use llm_governance_common::utils::resolve_window;
+use llm_governance_common::AppError;
+use llm_governance_database::ReadPool;
+
+fn budget_request(period: &str) -> CreateBudgetRequest {
+    CreateBudgetRequest {
+        name: format!("preview-{}", Uuid::new_v4()),
+        organization_id: Uuid::new_v4(),
+        team_id: Some(Uuid::new_v4()),
+        user_id: None,
+        amount: 200.0,
+        period: period.to_string(),
+        alert_threshold_percentage: None,
+        hard_limit: Some(true),
+    }
+}
+
+fn upsert_request(team_id: Option<Uuid>, user_id: Option<Uuid>) -> UpsertBudgetRequest {
+    UpsertBudgetRequest {
+        organization_id: Uuid::new_v4(),
+        team_id,
+        user_id,
+        amount: 500.0,
+        period: "monthly".to_string(),
+        alert_threshold_percentage: Some(75),
+        hard_limit: None,
+    }
+}
+
+fn distribution_query(scope: DistributionScope) -> DistributionQuery {
+    DistributionQuery {
+        scope,
+        id: Uuid::new_v4(),
+        from: None,
+        to: None,
+        buckets: None,
+    }
+}
+
+#[actix_web::test]
+#[ignore] // Requires Postgres at DATABASE_URL
+async fn test_budget_preview_does_not_create_budget() {
+    let pool = database().await;
+    let read_pool = ReadPool::new(&pool, None);
+    let req = budget_request("monthly");
+    let now = Utc::now();
+
+    let preview = budget_preview(&read_pool, std::time::Duration::from_secs(5), &req, now)
+        .await
+        .unwrap();
+    assert_eq!((preview.period_start, preview.period_end), calculate_period_bounds("monthly", now));
+    assert_eq!(preview.current_spend, 0.0);
+
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM budgets WHERE name = $1")
+        .bind(&req.name)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(count, 0);
+}
+
+#[actix_web::test]
+#[ignore] // Requires Postgres at DATABASE_URL
+async fn test_organization_costs_are_read_from_the_read_pool() {
+    let pool = database().await;
+    let user_id = seed_user(&pool, "reads").await;
+    let organization_id = seed_organization(&pool, "reads").await;
+    seed_member(&pool, organization_id, user_id, "member").await;
+
+    // Authorization runs on the primary; only the aggregation can reach
+    // the replica, so an unreachable one fails the request
+    let unreachable = sqlx::postgres::PgPoolOptions::new()
+        .acquire_timeout(std::time::Duration::from_millis(200))
+        .connect_lazy("postgres://app@127.0.0.1:1/replica")
+        .unwrap();
+
+    let cases = [
+        (ReadPool::new(&pool, Some(unreachable)), false),
+        (ReadPool::new(&pool, None), true),
+    ];
+    for (read_pool, expect_ok) in cases {
+        let app = actix_web::test::init_service(
+            actix_web::App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .app_data(web::Data::new(read_pool))
+                .app_data(web::Data::new(Config::default()))
+                .service(get_organization_costs),
+        )
+        .await;
+        let req = actix_web::test::TestRequest::get()
+            .uri(&format!("/costs/organization/{}", organization_id))
+            .insert_header(("X-User-Id", user_id.to_string()))
+            .to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_eq!(resp.status().is_success(), expect_ok, "status {}", resp.status());
+    }
+}
+
+#[actix_web::test]
+#[ignore] // Requires Postgres at DATABASE_URL
+async fn test_budget_preview_requires_organization_membership() {
+    let pool = database().await;
+    let user_id = seed_user(&pool, "preview").await;
+    let own_org = seed_organization(&pool, "own").await;
+    let other_org = seed_organization(&pool, "other").await;
+    seed_member(&pool, own_org, user_id, "member").await;
+
+    let app = actix_web::test::init_service(
+        actix_web::App::new()
+            .app_data(web::Data::new(pool.clone()))
+            .app_data(web::Data::new(ReadPool::new(&pool, None)))
+            .app_data(web::Data::new(Config::default()))
+            .service(preview_budget),
+    )
+    .await;
+
+    let mut budget = budget_request("monthly");
+    budget.team_id = None;
+    budget.user_id = Some(user_id);
+    let cases = [
+        (own_org, actix_web::http::StatusCode::OK),
+        (other_org, actix_web::http::StatusCode::FORBIDDEN),
+    ];
+    for (organization_id, expected) in cases {
+        budget.organization_id = organization_id;
+        let req = actix_web::test::TestRequest::post()
+            .uri("/costs/budgets/preview")
+            .insert_header(("X-User-Id", user_id.to_string()))
+            .set_json(&budget)
+            .to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_eq!(resp.status(), expected);
+    }
+
+    // A team from another organization is rejected even for a member
+    budget.organization_id = own_org;
+    budget.user_id = None;
+    budget.team_id = Some(Uuid::new_v4());
+    let req = actix_web::test::TestRequest::post()
+        .uri("/costs/budgets/preview")
+        .insert_header(("X-User-Id", user_id.to_string()))
+        .set_json(&budget)
+        .to_request();
+    let resp = actix_web::test::call_service(&app, req).await;
+    assert!(resp.status().is_client_error(), "status {}", resp.status());
+}
+
+#[actix_web::test]
+#[ignore] // Requires Postgres at DATABASE_URL
+async fn test_upsert_budget_creates_then_updates() {
+    let pool = database().await;
+    let organization_id = seed_organization(&pool, "upsert").await;
+    let team_id = seed_team(&pool, organization_id, "platform").await;
+
+    let mut req = upsert_request(Some(team_id), None).named(format!("iac-{}", Uuid::new_v4()));
+    req.organization_id = organization_id;
+    let now = Utc::now();
+
+    let (first, created) = apply_budget(&pool, &req, now).await.unwrap();
+    assert!(created);
+
+    // Re-applying the same state matches the same budget and leaves it as it was
+    let (again, created) = apply_budget(&pool, &req, now + chrono::Duration::days(3)).await.unwrap();
+    assert!(!created);
+    assert_eq!(again.id, first.id);
+    assert_eq!(again.period_start, first.period_start);
+
+    req.amount = 750.0;
+    req.period = "weekly".to_string();
+    let (updated, created) = apply_budget(&pool, &req, now).await.unwrap();
+    assert!(!created);
+    assert_eq!(updated.id, first.id);
+    assert_eq!(updated.amount, 750.0);
+    assert_eq!((updated.period_start, updated.period_end), calculate_period_bounds("weekly", now));
+
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM budgets WHERE name = $1")
+        .bind(&req.name)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(count, 1);
+}
+
+#[actix_web::test]
+#[ignore] // Requires Postgres at DATABASE_URL
+async fn test_duplicate_budget_key_is_a_conflict() {
+    let pool = database().await;
+    let organization_id = seed_organization(&pool, "duplicates").await;
+    let suffix = Uuid::new_v4();
+    let insert = |name: String| {
+        let pool = pool.clone();
+        async move {
+            sqlx::query(
+                "INSERT INTO budgets (organization_id, name, amount, period, period_start, period_end) \
+                 VALUES ($1, $2, 100, 'monthly', NOW(), NOW() + INTERVAL '30 days')",
+            )
+            .bind(organization_id)
+            .bind(name)
+            .execute(&pool)
+            .await
+            .map_err(budget_insert_error)
+        }
+    };
+
+    let name = format!("org-wide-{}", suffix);
+    assert!(insert(name.clone()).await.is_ok());
+    assert!(matches!(insert(name).await, Err(AppError::Conflict(_))));
+    assert!(insert(format!("other-{}", suffix)).await.is_ok());
+}
+
+#[actix_web::test]
+#[ignore] // Requires Postgres at DATABASE_URL
+async fn test_budgets_are_managed_by_org_admins_only() {
+    let pool = database().await;
+    let organization_id = seed_organization(&pool, "budget-admins").await;
+    let admin = seed_user(&pool, "admin").await;
+    let member = seed_user(&pool, "member").await;
+    seed_member(&pool, organization_id, admin, "admin").await;
+    seed_member(&pool, organization_id, member, "member").await;
+
+    assert!(authorize_budget_admin(&pool, admin, organization_id).await.is_ok());
+    assert!(matches!(
+        authorize_budget_admin(&pool, member, organization_id).await,
+        Err(AppError::Forbidden)
+    ));
+
+    // A user budget must name a member of the organization
+    let mut req = upsert_request(None, Some(member)).named(format!("scope-{}", Uuid::new_v4()));
+    req.organization_id = organization_id;
+    assert!(verify_budget_scope(&pool, &req).await.is_ok());
+    req.user_id = Some(Uuid::new_v4());
+    assert!(matches!(verify_budget_scope(&pool, &req).await, Err(AppError::Validation(_))));
+}
+
+#[actix_web::test]
+#[ignore] // Requires Postgres at DATABASE_URL
+async fn test_leaderboard_ranks_each_dimension_by_spend() {
+    let pool = database().await;
+    let organization_id = seed_organization(&pool, "leaderboard").await;
+    let alice = seed_user(&pool, "alice").await;
+    let bob = seed_user(&pool, "bob").await;
+    let team_id = seed_team(&pool, organization_id, "search").await;
+    let mut models = Vec::new();
+    for (provider, model) in [("openai", "gpt-4"), ("anthropic", "claude-3-opus")] {
+        let (provider_id,): (Uuid,) = sqlx::query_as(
+            "INSERT INTO llm_providers (organization_id, provider_name, display_name) VALUES ($1, $2, $2) RETURNING id",
+        )
+        .bind(organization_id)
+        .bind(provider)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let (model_id,): (Uuid,) = sqlx::query_as(
+            "INSERT INTO llm_models (provider_id, model_name, display_name, cost_per_1k_prompt_tokens, cost_per_1k_completion_tokens) \
+             VALUES ($1, $2, $2, 0, 0) RETURNING id",
+        )
+        .bind(provider_id)
+        .bind(model)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        models.push(model_id);
+    }
+    let (gpt, claude) = (models[0], models[1]);
+
+    // 10 of the 60 spent has no team, so it only counts towards the total
+    for (user_id, team, model_id, cost) in [
+        (alice, Some(team_id), gpt, 30.0),
+        (alice, Some(team_id), claude, 20.0),
+        (bob, None, gpt, 10.0),
+    ] {
+        sqlx::query(
+            "INSERT INTO llm_requests (organization_id, team_id, user_id, model_id, prompt_tokens, completion_tokens, \
+             total_tokens, prompt_cost, completion_cost, total_cost, status) \
+             VALUES ($1, $2, $3, $4, 10, 10, 20, 0, 0, $5, 'success')",
+        )
+        .bind(organization_id)
+        .bind(team)
+        .bind(user_id)
+        .bind(model_id)
+        .bind(cost)
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+    let window = resolve_window(None, None, 1);
+
+    let expected = [
+        (LeaderboardDimension::Team, vec![(team_id.to_string(), Some("search"), 50.0)]),
+        (
+            LeaderboardDimension::User,
+            vec![
+                (alice.to_string(), Some("alice"), 50.0),
+                (bob.to_string(), Some("bob"), 10.0),
+            ],
+        ),
+        (
+            LeaderboardDimension::Provider,
+            vec![("openai".to_string(), None, 40.0), ("anthropic".to_string(), None, 20.0)],
+        ),
+        (
+            LeaderboardDimension::Model,
+            vec![
+                ("gpt-4".to_string(), Some("openai"), 40.0),
+                ("claude-3-opus".to_string(), Some("anthropic"), 20.0),
+            ],
+        ),
+    ];
+    for (dimension, ranked) in expected {
+        let rows: Vec<LeaderboardRow> = leaderboard_query(dimension, vec![organization_id], &window, 5)
+            .build_query_as()
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        let (total_cost, entries) = rank_spenders(rows);
+
+        assert_eq!(total_cost, 60.0, "{:?}", dimension);
+        let listed: Vec<(String, Option<&str>, f64)> = entries
+            .iter()
+            .map(|e| (e.key.clone(), e.label.as_deref(), e.total_cost))
+            .collect();
+        assert_eq!(listed, ranked, "{:?}", dimension);
+        assert_eq!(entries[0].rank, 1);
+        assert_eq!(entries[0].share_percent, ranked[0].2 / 60.0 * 100.0);
+    }
+}
+
+#[actix_web::test]
+#[ignore] // Requires Postgres at DATABASE_URL
+async fn test_cost_distribution_is_computed_in_sql() {
+    let pool = database().await;
+    let organization_id = seed_organization(&pool, "distribution").await;
+    let team_id = seed_team(&pool, organization_id, "distribution").await;
+    // 1..=100 cents, as the proxy records them
+    sqlx::query(
+        "INSERT INTO llm_metrics (time, provider, model, team_id, cost, status) \
+         SELECT NOW(), 'openai', 'gpt-4', $1, cents / 100.0, 'success' FROM generate_series(1, 100) AS cents",
+    )
+    .bind(team_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let mut query = distribution_query(DistributionScope::Team);
+    query.id = team_id;
+    let window = resolve_window(None, None, 1);
+    let edges = [0.5];
+
+    let rows: Vec<BucketRow> = bucket_query(&query, vec![organization_id], &window, &edges)
+        .build_query_as()
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+    let percentiles: CostPercentiles = percentile_query(&query, vec![organization_id], &window)
+        .build_query_as()
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let distribution = cost_distribution(rows, percentiles, &edges);
+
+    assert_eq!(distribution.request_count, 100);
+    assert!((distribution.total_cost - 50.5).abs() < 1e-9);
+    assert_eq!(
+        distribution.buckets.iter().map(|b| b.request_count).collect::<Vec<_>>(),
+        vec![49, 51]
+    );
+    assert_eq!(distribution.p50, Some(0.50));
+    assert_eq!(distribution.p90, Some(0.90));
+    assert_eq!(distribution.p99, Some(0.99));
+
+    // Another organization's scope sees none of it
+    let rows: Vec<BucketRow> = bucket_query(&query, vec![Uuid::new_v4()], &window, &edges)
+        .build_query_as()
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+    assert!(rows.is_empty());
+}
@@ -0,0 +1,5 @@
+//! Cost service tests against the migrated database at `DATABASE_URL`
+//!
+//! Run with `cargo test -p cost-service --test integration -- --ignored`.
+
+mod costs;
@@ -1,4 +1,6 @@
 use serde::Deserialize;
+use std::collections::HashMap;
+use std::time::Duration;
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct Config {
@@ -6,6 +8,116 @@ pub struct Config {
     pub port: u16,
     pub database_url: String,
     pub redis_url: String,
+    /// JSON provider/model catalog; the built-in catalog is used when unset
+    #[serde(default)]
+    pub model_catalog_path: Option<String>,
+    /// Attempts per provider call, including the first
+    #[serde(default = "default_provider_max_attempts")]
+    pub provider_max_attempts: u32,
+    #[serde(default = "default_provider_retry_initial_delay_ms")]
+    pub provider_retry_initial_delay_ms: u64,
+    #[serde(default = "default_provider_retry_max_delay_ms")]
+    pub provider_retry_max_delay_ms: u64,
+    /// Per-provider request timeouts in seconds, e.g. `openai=30,bedrock=60`
+    #[serde(default = "default_provider_timeouts")]
+    pub provider_timeouts: String,
+    /// Timeout for providers not listed in `provider_timeouts`
+    #[serde(default = "default_provider_timeout_secs")]
+    pub provider_default_timeout_secs: u64,
+    /// JSON pricing table shared with the other services; the built-in prices are used when unset
+    #[serde(default)]
+    pub pricing_table_path: Option<String>,
+    /// Pricing for unknown models: `error`, `zero`, or an `input,output` pair per million tokens
+    #[serde(default = "default_pricing_fallback")]
+    pub pricing_fallback: String,
+    /// How long a live budget utilization is reused by the hard-limit check
+    #[serde(default = "default_budget_cache_ttl_secs")]
+    pub budget_cache_ttl_secs: u64,
+    /// Webhook attempts, including the first, before a delivery is dead-lettered
+    #[serde(default = "default_webhook_max_attempts")]
+    pub webhook_max_attempts: i32,
+    #[serde(default = "default_webhook_retry_initial_delay_secs")]
+    pub webhook_retry_initial_delay_secs: i64,
+    #[serde(default = "default_webhook_retry_max_delay_secs")]
+    pub webhook_retry_max_delay_secs: i64,
+    #[serde(default = "default_webhook_timeout_secs")]
+    pub webhook_timeout_secs: u64,
+    /// How often the retry worker looks for due deliveries
+    #[serde(default = "default_webhook_worker_interval_secs")]
+    pub webhook_worker_interval_secs: u64,
+    #[serde(default = "default_webhook_worker_batch_size")]
+    pub webhook_worker_batch_size: i64,
+    /// Base64 of the 32-byte key webhook signing secrets are encrypted with
+    #[serde(default)]
+    pub webhook_secret_key: String,
+    /// Proxy requests one organization may have in flight; `0` disables the limit
+    #[serde(default = "default_proxy_max_in_flight_per_org")]
+    pub proxy_max_in_flight_per_org: usize,
+    /// Replay responses to repeated deterministic (`temperature` 0) proxy requests from Redis
+    #[serde(default)]
+    pub response_cache_enabled: bool,
+    #[serde(default = "default_response_cache_ttl_secs")]
+    pub response_cache_ttl_secs: u64,
+}
+
+fn default_provider_max_attempts() -> u32 {
+    3
+}
+
+fn default_provider_retry_initial_delay_ms() -> u64 {
+    200
+}
+
+fn default_provider_retry_max_delay_ms() -> u64 {
+    5000
+}
+
+fn default_provider_timeouts() -> String {
+    "openai=30,anthropic=30,google=30,azure=30,bedrock=60".to_string()
+}
+
+fn default_provider_timeout_secs() -> u64 {
+    30
+}
+
+fn default_pricing_fallback() -> String {
+    "1.0,2.0".to_string()
+}
+
+fn default_budget_cache_ttl_secs() -> u64 {
+    5
+}
+
+fn default_webhook_max_attempts() -> i32 {
+    8
+}
+
+fn default_webhook_retry_initial_delay_secs() -> i64 {
+    30
+}
+
+fn default_webhook_retry_max_delay_secs() -> i64 {
+    3600
+}
+
+fn default_webhook_timeout_secs() -> u64 {
+    10
+}
+
+fn default_webhook_worker_interval_secs() -> u64 {
+    15
+}
+
+fn default_webhook_worker_batch_size() -> i64 {
+    50
+}
+
+fn default_proxy_max_in_flight_per_org() -> usize {
+    50
+}
+
+fn default_response_cache_ttl_secs() -> u64 {
+    3600
 }
 
 impl Config {
@@ -21,6 +133,86 @@ impl Default for Config {
             port: 8087,
             database_url: String::new(),
             redis_url: "redis://127.0.0.1:6379".to_string(),
+            model_catalog_path: None,
+            provider_max_attempts: default_provider_max_attempts(),
+            provider_retry_initial_delay_ms: default_provider_retry_initial_delay_ms(),
+            provider_retry_max_delay_ms: default_provider_retry_max_delay_ms(),
+            provider_timeouts: default_provider_timeouts(),
+            provider_default_timeout_secs: default_provider_timeout_secs(),
+            pricing_table_path: None,
+            pricing_fallback: default_pricing_fallback(),
+            budget_cache_ttl_secs: default_budget_cache_ttl_secs(),
+            webhook_max_attempts: default_webhook_max_attempts(),
+            webhook_retry_initial_delay_secs: default_webhook_retry_initial_delay_secs(),
+            webhook_retry_max_delay_secs: default_webhook_retry_max_delay_secs(),
+            webhook_timeout_secs: default_webhook_timeout_secs(),
+            webhook_worker_interval_secs: default_webhook_worker_interval_secs(),
+            webhook_worker_batch_size: default_webhook_worker_batch_size(),
+            webhook_secret_key: String::new(),
+            proxy_max_in_flight_per_org: default_proxy_max_in_flight_per_org(),
+            response_cache_enabled: false,
+            response_cache_ttl_secs: default_response_cache_ttl_secs(),
+        }
+    }
+}
+
+/// Request timeout applied to each outgoing provider call
+#[derive(Debug, Clone)]
+pub struct ProviderTimeouts {
+    default: Duration,
+    per_provider: HashMap<String, Duration>,
+}
+
+impl ProviderTimeouts {
+    pub fn from_config(config: &Config) -> Result<Self, String> {
+        let mut per_provider = HashMap::new();
+        for entry in config.provider_timeouts.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (provider, secs) = entry
+                .split_once('=')
+                .ok_or_else(|| format!("expected provider=seconds, got '{}'", entry))?;
+            let secs: u64 = secs
+                .trim()
+                .parse()
+                .map_err(|_| format!("invalid timeout for provider '{}': '{}'", provider.trim(), secs))?;
+            if secs == 0 {
+                return Err(format!("timeout for provider '{}' must be positive", provider.trim()));
+            }
+            per_provider.insert(provider.trim().to_string(), Duration::from_secs(secs));
+        }
+
+        Ok(Self {
+            default: Duration::from_secs(config.provider_default_timeout_secs.max(1)),
+            per_provider,
+        })
+    }
+
+    pub fn for_provider(&self, provider: &str) -> Duration {
+        self.per_provider.get(provider).copied().unwrap_or(self.default)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_provider_timeouts() {
+        let config = Config {
+            provider_timeouts: "openai=30, bedrock = 60".to_string(),
+            provider_default_timeout_secs: 45,
+            ..Config::default()
+        };
+        let timeouts = ProviderTimeouts::from_config(&config).unwrap();
+        assert_eq!(timeouts.for_provider("openai"), Duration::from_secs(30));
+        assert_eq!(timeouts.for_provider("bedrock"), Duration::from_secs(60));
+        assert_eq!(timeouts.for_provider("anthropic"), Duration::from_secs(45));
+
+        for invalid in ["openai", "openai=fast", "openai=0"] {
+            let config = Config {
+                provider_timeouts: invalid.to_string(),
+                ..Config::default()
+            };
+            assert!(ProviderTimeouts::from_config(&config).is_err(), "{} should be rejected", invalid);
         }
     }
 }
@@ -1,13 +1,28 @@
 use actix_web::{get, post, web, HttpResponse, Responder, HttpRequest};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use uuid::Uuid;
 use llm_governance_common::{AppError, Result, ApiResponse};
+use llm_governance_common::pricing;
+use llm_governance_common::trace_context::TraceContext;
+use llm_governance_common::utils::{check_window_span, parse_window_bound, resolve_window};
 use reqwest::Client;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
 
+use crate::config::{Config, ProviderTimeouts};
+use crate::services::budget_guard::{enforce_hard_limits, BudgetUtilizationCache};
+use crate::services::model_catalog::ModelCatalog;
+use crate::services::org_concurrency::OrgConcurrencyLimiter;
+use crate::services::provider_allowlist::{blocking_organization, organization_allowlists};
+use crate::services::provider_retry::{provider_error, response_error, send_with_retry, RetryPolicy};
+use crate::services::request_scope::request_organizations;
+use crate::services::response_cache::{cache_key, is_deterministic, ResponseCache};
+use crate::services::system_prompt::{combined_prompt, estimate_tokens, organization_system_prompts, OrgSystemPrompt};
+
 #[derive(Debug, Deserialize)]
 pub struct ProxyRequest {
     pub provider: String,
@@ -16,15 +31,23 @@ pub struct ProxyRequest {
     pub temperature: Option<f32>,
     pub max_tokens: Option<i32>,
     pub stream: Option<bool>,
+    /// Cost attribution tags, e.g. `{"project": "x", "cost_center": "y"}`
+    #[serde(default)]
+    pub tags: HashMap<String, String>,
 }
 
+/// Limits on cost attribution tags
+const MAX_TAGS: usize = 16;
+const MAX_TAG_KEY_LEN: usize = 64;
+const MAX_TAG_VALUE_LEN: usize = 256;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Message {
     pub role: String,
     pub content: String,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ProxyResponse {
     pub id: String,
     pub provider: String,
@@ -32,15 +55,24 @@ pub struct ProxyResponse {
     pub choices: Vec<Choice>,
     pub usage: Usage,
     pub cost: f64,
+    /// Replayed from the response cache; `cost` is then zero as the provider was not called
+    #[serde(default)]
+    pub cache_hit: bool,
+    /// Set by streaming calls when the first token arrives; buffered calls leave it empty
+    #[serde(skip)]
+    pub time_to_first_token_ms: Option<i32>,
+    /// Billed amount when the provider reports one, kept for cost reconciliation
+    #[serde(skip)]
+    pub reported_cost: Option<f64>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Choice {
     pub message: Message,
     pub finish_reason: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Usage {
     pub prompt_tokens: i32,
     pub completion_tokens: i32,
@@ -49,7 +81,7 @@ pub struct Usage {
 
 // Circuit breaker state
 #[derive(Debug, Clone)]
-struct CircuitBreakerState {
+pub struct CircuitBreakerState {
     failures: i32,
     last_failure_time: Option<std::time::Instant>,
     state: CircuitState,
@@ -62,18 +94,77 @@ enum CircuitState {
     HalfOpen, // Testing if service recovered
 }
 
-type CircuitBreakers = Arc<RwLock<HashMap<String, CircuitBreakerState>>>;
+pub type CircuitBreakers = Arc<RwLock<HashMap<String, CircuitBreakerState>>>;
+
+impl CircuitState {
+    /// Numeric encoding exported via the `circuit_breaker_state` gauge
+    fn as_gauge_value(&self) -> i64 {
+        match self {
+            CircuitState::Closed => 0,
+            CircuitState::HalfOpen => 1,
+            CircuitState::Open => 2,
+        }
+    }
+}
+
+fn publish_circuit_state(provider_key: &str, state: &CircuitState) {
+    llm_governance_common::metrics::CIRCUIT_BREAKER_STATE
+        .with_label_values(&[provider_key])
+        .set(state.as_gauge_value());
+}
 
 #[post("/integrations/proxy")]
 pub async fn proxy_llm_request(
     pool: web::Data<PgPool>,
     circuit_breakers: web::Data<CircuitBreakers>,
     http_client: web::Data<Client>,
+    catalog: web::Data<ModelCatalog>,
+    config: web::Data<Config>,
+    timeouts: web::Data<ProviderTimeouts>,
+    budget_cache: web::Data<BudgetUtilizationCache>,
+    org_limiter: web::Data<OrgConcurrencyLimiter>,
+    response_cache: web::Data<ResponseCache>,
     req: web::Json<ProxyRequest>,
     http_req: HttpRequest,
 ) -> Result<impl Responder> {
     let user_id = extract_user_id_optional(&http_req);
     let team_id = extract_team_id_optional(&http_req);
+    let request_id = extract_request_id(&http_req);
+    let trace = TraceContext::from_request(&http_req);
+    let mut req = req.into_inner();
+
+    validate_tags(&req.tags)?;
+
+    // Retired models are rejected outright; deprecated ones are served with a warning
+    let deprecation = catalog.check_model(&req.provider, &req.model)?;
+    catalog.check_request(&req.provider, &req.model, req.max_tokens, req.stream.unwrap_or(false))?;
+
+    // A claimed team must be the caller's own; its organization scopes the request
+    let organizations = request_organizations(pool.get_ref(), user_id, team_id).await?;
+
+    // Held for the rest of the request; dropping them on any return frees the slots
+    let _in_flight = org_limiter.try_acquire(&organizations)?;
+
+    // Organizations may restrict which providers their members can use
+    let allowlists = organization_allowlists(pool.get_ref(), &organizations).await?;
+    if let Some(organization_id) = blocking_organization(&allowlists, &req.provider) {
+        record_audit_log(
+            pool.get_ref(),
+            user_id,
+            "forbidden_provider",
+            &format!("{}:{}", req.provider, req.model),
+            &organization_id.to_string(),
+            &serde_json::json!({ "organization_id": organization_id, "team_id": team_id }),
+        ).await?;
+        return Err(AppError::BadRequest(format!(
+            "forbidden_provider: '{}' is not allowed by organization {}",
+            req.provider, organization_id
+        )));
+    }
+
+    // Organization guardrail prompts go first unless the caller set their own
+    let system_prompts = organization_system_prompts(pool.get_ref(), &organizations).await?;
+    let guardrail = inject_system_prompt(&mut req.messages, &system_prompts);
 
     // Check circuit breaker
     let provider_key = format!("{}:{}", req.provider, req.model);
@@ -81,17 +172,47 @@ pub async fn proxy_llm_request(
         return Err(AppError::Internal("Service temporarily unavailable".to_string()));
     }
 
+    // Unknown models are rejected here when the pricing fallback is `error`
+    let price = pricing::resolve(&req.provider, &req.model)?;
+
     // Check policies
     check_policies(pool.get_ref(), user_id, team_id, &req).await?;
 
+    // A replayed response costs nothing, so it is served before the budget check
+    if let Some(response) = cached_response(&response_cache, &organizations, &req).await {
+        let mut details = serde_json::json!({ "cache_hit": true });
+        if let Some(ref guardrail) = guardrail {
+            details["guardrail"] = guardrail.clone();
+        }
+        record_audit_log(
+            pool.get_ref(),
+            user_id,
+            "LLM_REQUEST",
+            &provider_key,
+            &response.id,
+            &details,
+        ).await?;
+
+        let mut builder = HttpResponse::Ok();
+        if let Some(ref notice) = deprecation {
+            builder.insert_header(("Warning", notice.warning_header()));
+        }
+        return Ok(builder.json(ApiResponse::success(response)));
+    }
+
+    // Hard-limit budgets are checked against live spend, not the reconciled total
+    enforce_hard_limits(pool.get_ref(), &budget_cache, user_id, team_id).await?;
+
     // Route to appropriate provider
+    let retry = RetryPolicy::from_config(&config);
+    let timeout = timeouts.for_provider(&req.provider);
     let start_time = std::time::Instant::now();
     let result = match req.provider.as_str() {
-        "openai" => proxy_to_openai(&http_client, &req).await,
-        "anthropic" => proxy_to_anthropic(&http_client, &req).await,
-        "google" => proxy_to_google(&http_client, &req).await,
-        "azure" => proxy_to_azure(&http_client, &req).await,
-        "bedrock" => proxy_to_bedrock(&http_client, &req).await,
+        "openai" => proxy_to_openai(&http_client, &req, &trace, &retry, timeout).await,
+        "anthropic" => proxy_to_anthropic(&http_client, &req, &trace, &retry, timeout).await,
+        "google" => proxy_to_google(&http_client, &req, &trace).await,
+        "azure" => proxy_to_azure(&http_client, &req, &trace).await,
+        "bedrock" => proxy_to_bedrock(&http_client, &req, &trace).await,
         _ => Err(AppError::BadRequest(format!("Unsupported provider: {}", req.provider))),
     };
 
@@ -103,50 +224,77 @@ pub async fn proxy_llm_request(
             record_success(&circuit_breakers, &provider_key).await;
 
             // Calculate cost
-            let cost = calculate_cost(&req.provider, &req.model, response.usage.prompt_tokens, response.usage.completion_tokens);
+            let cost = request_cost(&price, &response.usage);
 
             // Record metrics
-            record_metrics(
-                pool.get_ref(),
+            record_metrics(pool.get_ref(), &ProxyMetrics {
                 user_id,
                 team_id,
-                &req.provider,
-                &req.model,
-                response.usage.prompt_tokens,
-                response.usage.completion_tokens,
+                provider: &req.provider,
+                model: &req.model,
+                tokens_in: response.usage.prompt_tokens,
+                tokens_out: response.usage.completion_tokens,
                 latency_ms,
+                time_to_first_token_ms: response.time_to_first_token_ms,
                 cost,
-                "success",
-            ).await?;
-
-            // Record audit log
+                reported_cost: response.reported_cost,
+                status: "success",
+                request_id: request_id.as_deref(),
+                trace: &trace,
+                tags: &req.tags,
+            }).await?;
+
+            // Record audit log, noting use of a deprecated model or fallback pricing
+            let mut details = serde_json::json!({});
+            if let Some(ref notice) = deprecation {
+                details["deprecated_model"] = serde_json::json!(notice);
+            }
+            if let Some(fallback) = price.fallback {
+                details["pricing_fallback"] = serde_json::json!(fallback.as_str());
+            }
+            if let Some(ref guardrail) = guardrail {
+                details["guardrail"] = guardrail.clone();
+            }
             record_audit_log(
                 pool.get_ref(),
                 user_id,
                 "LLM_REQUEST",
                 &format!("{}:{}", req.provider, req.model),
                 &response.id,
+                &details,
             ).await?;
 
-            Ok(HttpResponse::Ok().json(ApiResponse::success(response)))
+            cache_response(&response_cache, &organizations, &req, &response).await;
+
+            let mut builder = HttpResponse::Ok();
+            if let Some(ref notice) = deprecation {
+                builder.insert_header(("Warning", notice.warning_header()));
+            }
+            Ok(builder.json(ApiResponse::success(response)))
         }
         Err(e) => {
-            // Record failure in circuit breaker
-            record_failure(&circuit_breakers, &provider_key).await;
+            // A rejected request says nothing about the provider's health
+            if !matches!(e, AppError::UpstreamRejected { .. }) {
+                record_failure(&circuit_breakers, &provider_key).await;
+            }
 
             // Record failed metrics
-            record_metrics(
-                pool.get_ref(),
+            record_metrics(pool.get_ref(), &ProxyMetrics {
                 user_id,
                 team_id,
-                &req.provider,
-                &req.model,
-                0,
-                0,
+                provider: &req.provider,
+                model: &req.model,
+                tokens_in: 0,
+                tokens_out: 0,
                 latency_ms,
-                0.0,
-                "error",
-            ).await?;
+                time_to_first_token_ms: None,
+                cost: 0.0,
+                reported_cost: None,
+                status: "error",
+                request_id: request_id.as_deref(),
+                trace: &trace,
+                tags: &req.tags,
+            }).await?;
 
             Err(e)
         }
@@ -154,38 +302,48 @@ pub async fn proxy_llm_request(
 }
 
 #[get("/integrations/providers")]
-pub async fn list_providers() -> Result<impl Responder> {
-    let providers = vec![
-        serde_json::json!({
-            "name": "openai",
-            "models": ["gpt-4", "gpt-4-turbo", "gpt-3.5-turbo"],
-            "status": "active"
-        }),
-        serde_json::json!({
-            "name": "anthropic",
-            "models": ["claude-3-opus", "claude-3-sonnet", "claude-3-haiku"],
-            "status": "active"
-        }),
-        serde_json::json!({
-            "name": "google",
-            "models": ["gemini-pro", "gemini-pro-vision"],
-            "status": "active"
-        }),
-        serde_json::json!({
-            "name": "azure",
-            "models": ["gpt-4", "gpt-35-turbo"],
-            "status": "active"
-        }),
-        serde_json::json!({
-            "name": "bedrock",
-            "models": ["claude-v2", "titan-text"],
-            "status": "active"
-        }),
-    ];
+pub async fn list_providers(catalog: web::Data<ModelCatalog>) -> Result<impl Responder> {
+    let providers: Vec<serde_json::Value> = catalog
+        .providers
+        .iter()
+        .map(|provider| {
+            serde_json::json!({
+                "name": provider.name,
+                "models": provider.models,
+                "status": "active"
+            })
+        })
+        .collect();
 
     Ok(HttpResponse::Ok().json(ApiResponse::success(providers)))
 }
 
+/// Capability matrix for every catalogued provider/model
+#[get("/integrations/models")]
+pub async fn list_models(catalog: web::Data<ModelCatalog>) -> Result<impl Responder> {
+    let models: Vec<serde_json::Value> = catalog
+        .providers
+        .iter()
+        .flat_map(|provider| {
+            provider.models.iter().map(move |model| {
+                serde_json::json!({
+                    "provider": provider.name,
+                    "model": model.name,
+                    "status": model.status,
+                    "replacement": model.replacement,
+                    "context_tokens": model.capabilities.as_ref().map(|c| c.context_tokens),
+                    "max_output": model.capabilities.as_ref().map(|c| c.max_output),
+                    "modalities": model.capabilities.as_ref().map(|c| &c.modalities),
+                    "streaming": model.capabilities.as_ref().map(|c| c.streaming),
+                    "supports_vision": model.capabilities.as_ref().map(|c| c.supports_vision()),
+                })
+            })
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(models)))
+}
+
 #[get("/integrations/health")]
 pub async fn check_provider_health(
     circuit_breakers: web::Data<CircuitBreakers>,
@@ -203,9 +361,329 @@ pub async fn check_provider_health(
     Ok(HttpResponse::Ok().json(ApiResponse::success(health_status)))
 }
 
+/// Default and maximum look-back window for the metrics summary
+const DEFAULT_SUMMARY_HOURS: i64 = 24;
+const MAX_SUMMARY_HOURS: i64 = 24 * 7;
+
+#[derive(Debug, Deserialize)]
+pub struct MetricsSummaryQuery {
+    pub hours: Option<i64>,
+}
+
+/// Latency and throughput for one provider/model over the summary window
+#[derive(Debug, Clone, Serialize, PartialEq, sqlx::FromRow)]
+pub struct ProviderMetricsSummary {
+    pub provider: String,
+    pub model: String,
+    pub requests: i64,
+    pub p50_latency_ms: i32,
+    pub p95_latency_ms: i32,
+    /// Mean completion tokens per second; `None` when no request produced output
+    pub avg_tokens_per_second: Option<f64>,
+}
+
+#[get("/integrations/metrics/summary")]
+pub async fn get_metrics_summary(
+    pool: web::Data<PgPool>,
+    query: web::Query<MetricsSummaryQuery>,
+    http_req: HttpRequest,
+) -> Result<impl Responder> {
+    let user_id = extract_user_id_optional(&http_req).ok_or(AppError::Unauthorized)?;
+    let hours = query
+        .hours
+        .unwrap_or(DEFAULT_SUMMARY_HOURS)
+        .clamp(1, MAX_SUMMARY_HOURS);
+
+    let providers = metrics_summary(pool.get_ref(), user_id, hours).await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
+        "window_hours": hours,
+        "providers": providers,
+    }))))
+}
+
+/// Latency percentiles and mean TPS per provider/model of successful requests
+/// made by teams or members of the caller's organizations
+pub async fn metrics_summary(pool: &PgPool, user_id: Uuid, hours: i64) -> Result<Vec<ProviderMetricsSummary>> {
+    let summaries = sqlx::query_as::<_, ProviderMetricsSummary>(
+        r#"
+        WITH caller_orgs AS (
+            SELECT organization_id FROM organization_members WHERE user_id = $2
+        )
+        SELECT
+            provider,
+            model,
+            COUNT(*) AS requests,
+            ROUND(percentile_cont(0.5) WITHIN GROUP (ORDER BY latency_ms))::INT4 AS p50_latency_ms,
+            ROUND(percentile_cont(0.95) WITHIN GROUP (ORDER BY latency_ms))::INT4 AS p95_latency_ms,
+            AVG(tokens_per_second) AS avg_tokens_per_second
+        FROM llm_metrics
+        WHERE status = 'success'
+          AND time >= NOW() - make_interval(hours => $1::int)
+          AND (
+            team_id IN (SELECT id FROM teams WHERE organization_id IN (SELECT organization_id FROM caller_orgs))
+            OR user_id IN (
+                SELECT om.user_id FROM organization_members om
+                WHERE om.organization_id IN (SELECT organization_id FROM caller_orgs)
+            )
+          )
+        GROUP BY provider, model
+        ORDER BY provider, model
+        "#,
+    )
+    .bind(hours as i32)
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(summaries)
+}
+
+/// Default look-back and bucket width for the error-rate series
+const DEFAULT_ERROR_RATE_DAYS: i64 = 1;
+const DEFAULT_ERROR_RATE_WINDOW: &str = "1h";
+/// Longest range and most buckets one error-rate request may ask for
+const MAX_ERROR_RATE_DAYS: i64 = 31;
+const MAX_ERROR_RATE_BUCKETS: i64 = 2000;
+const MIN_ERROR_RATE_WINDOW_SECS: i64 = 60;
+
+#[derive(Debug, Deserialize)]
+pub struct ErrorRateQuery {
+    pub provider: Option<String>,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    /// Bucket width such as `5m`, `1h` or `1d`
+    pub window: Option<String>,
+    /// Narrow the series to one of the caller's organizations
+    pub organization_id: Option<Uuid>,
+    /// Narrow the series to a team the caller belongs to
+    pub team_id: Option<Uuid>,
+}
+
+/// Requests and failures in one bucket of the error-rate series
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ErrorRateBucket {
+    pub bucket: DateTime<Utc>,
+    pub requests: i64,
+    pub errors: i64,
+    pub error_rate: f64,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ErrorRateSeries {
+    pub requests: i64,
+    pub errors: i64,
+    /// Failed share of all requests in the range; `0.0` when there were none
+    pub error_rate: f64,
+    pub buckets: Vec<ErrorRateBucket>,
+}
+
+/// Requests and failures per bucket: (bucket, requests, errors)
+type BucketCount = (DateTime<Utc>, i64, i64);
+
+/// Range and bucket width of one error-rate series
+pub struct ErrorRateWindow {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub window_secs: i64,
+}
+
+#[get("/integrations/metrics/error-rate")]
+pub async fn get_error_rate(
+    pool: web::Data<PgPool>,
+    query: web::Query<ErrorRateQuery>,
+    http_req: HttpRequest,
+) -> Result<impl Responder> {
+    let user_id = extract_user_id_optional(&http_req).ok_or(AppError::Unauthorized)?;
+    let window = query.window.as_deref().unwrap_or(DEFAULT_ERROR_RATE_WINDOW);
+    let window_secs = parse_bucket_window(window)?;
+    let range = resolve_window(query.from.as_deref(), query.to.as_deref(), DEFAULT_ERROR_RATE_DAYS);
+    check_window_span(&range, MAX_ERROR_RATE_DAYS)?;
+    let (start, end) = match (parse_window_bound(&range.start), parse_window_bound(&range.end)) {
+        (Some(start), Some(end)) if start < end => (start, end),
+        _ => return Err(AppError::Validation("'from' must be before 'to'".to_string())),
+    };
+    if (end - start).num_seconds() / window_secs > MAX_ERROR_RATE_BUCKETS {
+        return Err(AppError::Validation(format!(
+            "window '{}' yields more than {} buckets over this range",
+            window, MAX_ERROR_RATE_BUCKETS
+        )));
+    }
+
+    // A team must be one of the caller's; an organization must be shared
+    let caller_orgs = request_organizations(pool.get_ref(), Some(user_id), query.team_id).await?;
+    let organizations = match query.organization_id {
+        Some(organization_id) if caller_orgs.contains(&organization_id) => vec![organization_id],
+        Some(_) => return Err(AppError::Forbidden),
+        None => caller_orgs,
+    };
+
+    let window = ErrorRateWindow { start, end, window_secs };
+    let counts = error_rate_counts(
+        pool.get_ref(),
+        &organizations,
+        query.team_id,
+        query.provider.as_deref(),
+        &window,
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
+        "provider": query.provider,
+        "organization_id": query.organization_id,
+        "team_id": query.team_id,
+        "from": start,
+        "to": end,
+        "window_secs": window_secs,
+        "series": error_rate_series(counts),
+    }))))
+}
+
+/// Requests and failures per bucket for teams or members of `organizations`
+///
+/// `time_bucket_gapfill` emits every bucket in the range, so buckets without
+/// traffic come back as zero counts rather than being left out. Every status
+/// other than `success` counts as an error, so timeouts and rate-limited
+/// calls raise the rate as well.
+pub async fn error_rate_counts(
+    pool: &PgPool,
+    organizations: &[Uuid],
+    team_id: Option<Uuid>,
+    provider: Option<&str>,
+    window: &ErrorRateWindow,
+) -> Result<Vec<BucketCount>> {
+    let rows: Vec<(chrono::NaiveDateTime, i64, i64)> = sqlx::query_as(
+        r#"
+        SELECT
+            time_bucket_gapfill(make_interval(secs => $1), time, $2, $3) AS bucket,
+            COALESCE(COUNT(*), 0) AS requests,
+            COALESCE(COUNT(*) FILTER (WHERE status <> 'success'), 0) AS errors
+        FROM llm_metrics
+        WHERE time >= $2 AND time < $3
+          AND ($4::text IS NULL OR provider = $4)
+          AND ($6::uuid IS NULL OR team_id = $6)
+          AND (
+            team_id IN (SELECT id FROM teams WHERE organization_id = ANY($5))
+            OR user_id IN (SELECT user_id FROM organization_members WHERE organization_id = ANY($5))
+          )
+        GROUP BY bucket
+        ORDER BY bucket
+        "#,
+    )
+    .bind(window.window_secs as f64)
+    .bind(window.start.naive_utc())
+    .bind(window.end.naive_utc())
+    .bind(provider)
+    .bind(organizations)
+    .bind(team_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(bucket, requests, errors)| (bucket.and_utc(), requests, errors))
+        .collect())
+}
+
+/// Parse a bucket width given as seconds or with an `s`/`m`/`h`/`d` suffix
+fn parse_bucket_window(value: &str) -> Result<i64> {
+    let value = value.trim();
+    let (amount, unit) = match value.char_indices().last() {
+        Some((i, c)) if c.is_ascii_alphabetic() => (&value[..i], c.to_ascii_lowercase()),
+        _ => (value, 's'),
+    };
+    let multiplier = match unit {
+        's' => 1,
+        'm' => 60,
+        'h' => 3600,
+        'd' => 86_400,
+        _ => 0,
+    };
+    let secs = amount
+        .parse::<i64>()
+        .ok()
+        .and_then(|n| n.checked_mul(multiplier))
+        .filter(|secs| *secs > 0)
+        .ok_or_else(|| {
+            AppError::Validation(format!("'{}' is not a window such as 5m, 1h or 1d", value))
+        })?;
+
+    if secs < MIN_ERROR_RATE_WINDOW_SECS {
+        return Err(AppError::Validation(format!(
+            "window must be at least {} seconds",
+            MIN_ERROR_RATE_WINDOW_SECS
+        )));
+    }
+    Ok(secs)
+}
+
+/// Error rate per bucket and over the whole range
+fn error_rate_series(counts: Vec<BucketCount>) -> ErrorRateSeries {
+    let buckets: Vec<ErrorRateBucket> = counts
+        .into_iter()
+        .map(|(bucket, requests, errors)| ErrorRateBucket {
+            bucket,
+            requests,
+            errors,
+            error_rate: error_rate(errors, requests),
+        })
+        .collect();
+    let requests = buckets.iter().map(|b| b.requests).sum();
+    let errors = buckets.iter().map(|b| b.errors).sum();
+
+    ErrorRateSeries {
+        requests,
+        errors,
+        error_rate: error_rate(errors, requests),
+        buckets,
+    }
+}
+
+fn error_rate(errors: i64, requests: i64) -> f64 {
+    if requests == 0 {
+        0.0
+    } else {
+        errors as f64 / requests as f64
+    }
+}
+
+/// Cache key of a request whose response may be replayed, if any
+fn deterministic_cache_key(cache: &ResponseCache, organizations: &[Uuid], req: &ProxyRequest) -> Option<String> {
+    (cache.is_enabled() && is_deterministic(req.temperature))
+        .then(|| cache_key(organizations, &req.provider, &req.model, &req.messages, req.max_tokens))
+}
+
+/// Earlier response to an identical deterministic request of the same
+/// organizations, at no cost and under an id of its own
+async fn cached_response(cache: &ResponseCache, organizations: &[Uuid], req: &ProxyRequest) -> Option<ProxyResponse> {
+    let key = deterministic_cache_key(cache, organizations, req)?;
+    let mut response: ProxyResponse = cache.get(&key).await?;
+    response.id = format!("cached-{}", Uuid::new_v4());
+    response.cache_hit = true;
+    response.cost = 0.0;
+    Some(response)
+}
+
+async fn cache_response(cache: &ResponseCache, organizations: &[Uuid], req: &ProxyRequest, response: &ProxyResponse) {
+    if let Some(key) = deterministic_cache_key(cache, organizations, req) {
+        cache.put(&key, response).await;
+    }
+}
+
 // Provider-specific implementations
 
-async fn proxy_to_openai(client: &Client, req: &ProxyRequest) -> Result<ProxyResponse> {
+/// Start an outgoing provider request carrying the caller's trace context
+fn provider_request(client: &Client, url: &str, trace: &TraceContext) -> reqwest::RequestBuilder {
+    trace.inject(client.post(url))
+}
+
+async fn proxy_to_openai(
+    client: &Client,
+    req: &ProxyRequest,
+    trace: &TraceContext,
+    retry: &RetryPolicy,
+    timeout: Duration,
+) -> Result<ProxyResponse> {
     let api_key = std::env::var("OPENAI_API_KEY")
         .map_err(|_| AppError::Internal("OpenAI API key not configured".to_string()))?;
 
@@ -246,18 +724,18 @@ async fn proxy_to_openai(client: &Client, req: &ProxyRequest) -> Result<ProxyRes
         max_tokens: req.max_tokens,
     };
 
-    let response = client
-        .post("https://api.openai.com/v1/chat/completions")
-        .header("Authorization", format!("Bearer {}", api_key))
-        .header("Content-Type", "application/json")
-        .json(&openai_req)
-        .send()
-        .await
-        .map_err(|e| AppError::Internal(format!("OpenAI API error: {}", e)))?;
+    let response = send_with_retry(retry, "OpenAI", || {
+        provider_request(client, "https://api.openai.com/v1/chat/completions", trace)
+            .header("Authorization", format!("Bearer {}", api_key))
+            .header("Content-Type", "application/json")
+            .json(&openai_req)
+            .timeout(timeout)
+    })
+    .await
+    .map_err(|e| provider_error("OpenAI", e))?;
 
     if !response.status().is_success() {
-        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(AppError::Internal(format!("OpenAI API error: {}", error_text)));
+        return Err(response_error("OpenAI", response).await);
     }
 
     let openai_response: OpenAIResponse = response
@@ -279,16 +757,27 @@ async fn proxy_to_openai(client: &Client, req: &ProxyRequest) -> Result<ProxyRes
             total_tokens: openai_response.usage.total_tokens,
         },
         cost: 0.0, // Will be calculated separately
+        cache_hit: false,
+        time_to_first_token_ms: None,
+        reported_cost: None,
     })
 }
 
-async fn proxy_to_anthropic(client: &Client, req: &ProxyRequest) -> Result<ProxyResponse> {
+async fn proxy_to_anthropic(
+    client: &Client,
+    req: &ProxyRequest,
+    trace: &TraceContext,
+    retry: &RetryPolicy,
+    timeout: Duration,
+) -> Result<ProxyResponse> {
     let api_key = std::env::var("ANTHROPIC_API_KEY")
         .map_err(|_| AppError::Internal("Anthropic API key not configured".to_string()))?;
 
     #[derive(Serialize)]
     struct AnthropicRequest {
         model: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        system: Option<String>,
         messages: Vec<Message>,
         max_tokens: i32,
         #[serde(skip_serializing_if = "Option::is_none")]
@@ -313,26 +802,32 @@ async fn proxy_to_anthropic(client: &Client, req: &ProxyRequest) -> Result<Proxy
         output_tokens: i32,
     }
 
+    // The Messages API rejects a "system" role; it takes a top-level field instead
+    let (system, messages): (Vec<Message>, Vec<Message>) =
+        req.messages.iter().cloned().partition(|m| m.role == "system");
+    let system: Vec<String> = system.into_iter().map(|m| m.content).collect();
+
     let anthropic_req = AnthropicRequest {
         model: req.model.clone(),
-        messages: req.messages.clone(),
+        system: (!system.is_empty()).then(|| system.join("\n\n")),
+        messages,
         max_tokens: req.max_tokens.unwrap_or(4096),
         temperature: req.temperature,
     };
 
-    let response = client
-        .post("https://api.anthropic.com/v1/messages")
-        .header("x-api-key", api_key)
-        .header("anthropic-version", "2023-06-01")
-        .header("Content-Type", "application/json")
-        .json(&anthropic_req)
-        .send()
-        .await
-        .map_err(|e| AppError::Internal(format!("Anthropic API error: {}", e)))?;
+    let response = send_with_retry(retry, "Anthropic", || {
+        provider_request(client, "https://api.anthropic.com/v1/messages", trace)
+            .header("x-api-key", &api_key)
+            .header("anthropic-version", "2023-06-01")
+            .header("Content-Type", "application/json")
+            .json(&anthropic_req)
+            .timeout(timeout)
+    })
+    .await
+    .map_err(|e| provider_error("Anthropic", e))?;
 
     if !response.status().is_success() {
-        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(AppError::Internal(format!("Anthropic API error: {}", error_text)));
+        return Err(response_error("Anthropic", response).await);
     }
 
     let anthropic_response: AnthropicResponse = response
@@ -359,20 +854,23 @@ async fn proxy_to_anthropic(client: &Client, req: &ProxyRequest) -> Result<Proxy
             total_tokens: anthropic_response.usage.input_tokens + anthropic_response.usage.output_tokens,
         },
         cost: 0.0,
+        cache_hit: false,
+        time_to_first_token_ms: None,
+        reported_cost: None,
     })
 }
 
-async fn proxy_to_google(_client: &Client, _req: &ProxyRequest) -> Result<ProxyResponse> {
+async fn proxy_to_google(_client: &Client, _req: &ProxyRequest, _trace: &TraceContext) -> Result<ProxyResponse> {
     // Simplified - would implement actual Google Gemini API integration
     Err(AppError::Internal("Google provider not yet implemented".to_string()))
 }
 
-async fn proxy_to_azure(_client: &Client, _req: &ProxyRequest) -> Result<ProxyResponse> {
+async fn proxy_to_azure(_client: &Client, _req: &ProxyRequest, _trace: &TraceContext) -> Result<ProxyResponse> {
     // Simplified - would implement actual Azure OpenAI API integration
     Err(AppError::Internal("Azure provider not yet implemented".to_string()))
 }
 
-async fn proxy_to_bedrock(_client: &Client, _req: &ProxyRequest) -> Result<ProxyResponse> {
+async fn proxy_to_bedrock(_client: &Client, _req: &ProxyRequest, _trace: &TraceContext) -> Result<ProxyResponse> {
     // Simplified - would implement actual AWS Bedrock API integration
     Err(AppError::Internal("Bedrock provider not yet implemented".to_string()))
 }
@@ -412,6 +910,7 @@ async fn check_circuit_breaker(breakers: &CircuitBreakers, provider_key: &str) -
             if let Some(last_failure) = state.last_failure_time {
                 if last_failure.elapsed().as_secs() > 30 {
                     state.state = CircuitState::HalfOpen;
+                    publish_circuit_state(provider_key, &state.state);
                     return true;
                 }
             }
@@ -427,6 +926,7 @@ async fn record_success(breakers: &CircuitBreakers, provider_key: &str) {
         state.failures = 0;
         state.state = CircuitState::Closed;
         state.last_failure_time = None;
+        publish_circuit_state(provider_key, &state.state);
     }
 }
 
@@ -446,55 +946,72 @@ async fn record_failure(breakers: &CircuitBreakers, provider_key: &str) {
     if state.failures >= 5 {
         state.state = CircuitState::Open;
     }
+    publish_circuit_state(provider_key, &state.state);
 }
 
-fn calculate_cost(provider: &str, model: &str, prompt_tokens: i32, completion_tokens: i32) -> f64 {
-    let (input_price, output_price) = match (provider, model) {
-        ("openai", "gpt-4") => (30.0, 60.0),
-        ("openai", "gpt-4-turbo") => (10.0, 30.0),
-        ("openai", "gpt-3.5-turbo") => (0.5, 1.5),
-        ("anthropic", "claude-3-opus") => (15.0, 75.0),
-        ("anthropic", "claude-3-sonnet") => (3.0, 15.0),
-        ("anthropic", "claude-3-haiku") => (0.25, 1.25),
-        _ => (1.0, 2.0),
-    };
-
-    let input_cost = (prompt_tokens as f64 / 1_000_000.0) * input_price;
-    let output_cost = (completion_tokens as f64 / 1_000_000.0) * output_price;
+/// Completion tokens generated per second of request latency
+fn tokens_per_second(completion_tokens: i32, latency_ms: i32) -> Option<f64> {
+    if completion_tokens <= 0 || latency_ms <= 0 {
+        return None;
+    }
+    Some(completion_tokens as f64 / (latency_ms as f64 / 1000.0))
+}
 
-    input_cost + output_cost
+/// Cost of a proxied request from the shared pricing table
+fn request_cost(price: &pricing::ResolvedPrice, usage: &Usage) -> f64 {
+    price.cost(usage.prompt_tokens as i64, usage.completion_tokens as i64)
 }
 
-async fn record_metrics(
-    pool: &PgPool,
+/// One proxied request as recorded in `llm_metrics`
+struct ProxyMetrics<'a> {
     user_id: Option<Uuid>,
     team_id: Option<Uuid>,
-    provider: &str,
-    model: &str,
+    provider: &'a str,
+    model: &'a str,
     tokens_in: i32,
     tokens_out: i32,
     latency_ms: i32,
+    time_to_first_token_ms: Option<i32>,
     cost: f64,
-    status: &str,
-) -> Result<()> {
+    reported_cost: Option<f64>,
+    status: &'a str,
+    request_id: Option<&'a str>,
+    trace: &'a TraceContext,
+    tags: &'a HashMap<String, String>,
+}
+
+async fn record_metrics(pool: &PgPool, metrics: &ProxyMetrics<'_>) -> Result<()> {
+    let metadata = serde_json::json!({
+        "trace_id": metrics.trace.trace_id,
+        "span_id": metrics.trace.span_id,
+        "parent_span_id": metrics.trace.parent_span_id,
+    });
+
     sqlx::query(
         r#"
         INSERT INTO llm_metrics (
             time, provider, model, user_id, team_id,
-            tokens_in, tokens_out, latency_ms, cost, status
+            tokens_in, tokens_out, latency_ms, tokens_per_second, time_to_first_token_ms,
+            cost, reported_cost, status, request_id, metadata, tags
         )
-        VALUES (NOW(), $1, $2, $3, $4, $5, $6, $7, $8, $9)
+        VALUES (NOW(), $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
         "#,
     )
-    .bind(provider)
-    .bind(model)
-    .bind(user_id)
-    .bind(team_id)
-    .bind(tokens_in)
-    .bind(tokens_out)
-    .bind(latency_ms)
-    .bind(cost)
-    .bind(status)
+    .bind(metrics.provider)
+    .bind(metrics.model)
+    .bind(metrics.user_id)
+    .bind(metrics.team_id)
+    .bind(metrics.tokens_in)
+    .bind(metrics.tokens_out)
+    .bind(metrics.latency_ms)
+    .bind(tokens_per_second(metrics.tokens_out, metrics.latency_ms))
+    .bind(metrics.time_to_first_token_ms)
+    .bind(metrics.cost)
+    .bind(metrics.reported_cost)
+    .bind(metrics.status)
+    .bind(metrics.request_id)
+    .bind(metadata)
+    .bind(sqlx::types::Json(metrics.tags))
     .execute(pool)
     .await?;
 
@@ -507,23 +1024,75 @@ async fn record_audit_log(
     action: &str,
     resource_type: &str,
     resource_id: &str,
+    details: &serde_json::Value,
 ) -> Result<()> {
     sqlx::query(
         r#"
         INSERT INTO audit_logs (user_id, action, resource_type, resource_id, details, checksum)
-        VALUES ($1, $2, $3, $4, '{}', '')
+        VALUES ($1, $2, $3, $4, $5, '')
         "#,
     )
     .bind(user_id)
     .bind(action)
     .bind(resource_type)
     .bind(resource_id)
+    .bind(details)
     .execute(pool)
     .await?;
 
     Ok(())
 }
 
+/// Prepend the organizations' system prompt unless a system message is present
+///
+/// Returns the audit record of the applied guardrail, including its estimated
+/// share of the prompt tokens the provider reports.
+fn inject_system_prompt(
+    messages: &mut Vec<Message>,
+    prompts: &[OrgSystemPrompt],
+) -> Option<serde_json::Value> {
+    if messages.iter().any(|m| m.role == "system") {
+        return None;
+    }
+    let content = combined_prompt(prompts)?;
+    let organizations: Vec<Uuid> = prompts.iter().map(|p| p.organization_id).collect();
+    let record = serde_json::json!({
+        "type": "system_prompt",
+        "organization_ids": organizations,
+        "estimated_prompt_tokens": estimate_tokens(&content),
+    });
+
+    messages.insert(0, Message {
+        role: "system".to_string(),
+        content,
+    });
+    Some(record)
+}
+
+/// Check tag count and key/value lengths
+fn validate_tags(tags: &HashMap<String, String>) -> Result<()> {
+    if tags.len() > MAX_TAGS {
+        return Err(AppError::Validation(format!("at most {} tags are allowed", MAX_TAGS)));
+    }
+
+    for (key, value) in tags {
+        if key.is_empty() || key.len() > MAX_TAG_KEY_LEN {
+            return Err(AppError::Validation(format!(
+                "tag key '{}' must be 1-{} characters",
+                key, MAX_TAG_KEY_LEN
+            )));
+        }
+        if value.is_empty() || value.len() > MAX_TAG_VALUE_LEN {
+            return Err(AppError::Validation(format!(
+                "value of tag '{}' must be 1-{} characters",
+                key, MAX_TAG_VALUE_LEN
+            )));
+        }
+    }
+
+    Ok(())
+}
+
 fn extract_user_id_optional(req: &HttpRequest) -> Option<Uuid> {
     req.headers()
         .get("X-User-Id")
@@ -538,8 +1107,267 @@ fn extract_team_id_optional(req: &HttpRequest) -> Option<Uuid> {
         .and_then(|s| Uuid::parse_str(s).ok())
 }
 
+fn extract_request_id(req: &HttpRequest) -> Option<String> {
+    req.headers()
+        .get("X-Request-Id")
+        .and_then(|h| h.to_str().ok())
+        .map(String::from)
+}
+
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.service(proxy_llm_request)
         .service(list_providers)
-        .service(check_provider_health);
+        .service(list_models)
+        .service(check_provider_health)
+        .service(get_metrics_summary)
+        .service(get_error_rate);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::response_cache::CacheStore;
+    use actix_web::test::TestRequest;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_inbound_trace_id_propagated_to_provider_request() {
+        let inbound = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let http_req = TestRequest::default()
+            .insert_header(("traceparent", inbound))
+            .insert_header(("tracestate", "governance=1"))
+            .to_http_request();
+        let trace = TraceContext::from_request(&http_req);
+
+        let outgoing = provider_request(&Client::new(), "http://mock-provider.invalid/v1/messages", &trace)
+            .build()
+            .unwrap();
+
+        let traceparent = outgoing.headers().get("traceparent").unwrap().to_str().unwrap();
+        assert!(traceparent.starts_with("00-4bf92f3577b34da6a3ce929d0e0e4736-"));
+        assert!(!traceparent.contains("00f067aa0ba902b7"), "outgoing call must use its own span id");
+        assert!(traceparent.contains(&trace.span_id));
+        assert_eq!(outgoing.headers().get("tracestate").unwrap(), "governance=1");
+    }
+
+    #[test]
+    fn test_tag_lengths_are_validated() {
+        let mut tags = HashMap::from([
+            ("project".to_string(), "x".to_string()),
+            ("cost_center".to_string(), "y".to_string()),
+        ]);
+        assert!(validate_tags(&tags).is_ok());
+
+        tags.insert("k".repeat(MAX_TAG_KEY_LEN + 1), "v".to_string());
+        assert!(validate_tags(&tags).is_err());
+
+        let tags = HashMap::from([("project".to_string(), "v".repeat(MAX_TAG_VALUE_LEN + 1))]);
+        assert!(validate_tags(&tags).is_err());
+
+        let tags: HashMap<_, _> = (0..=MAX_TAGS).map(|i| (format!("k{}", i), "v".to_string())).collect();
+        assert!(validate_tags(&tags).is_err());
+    }
+
+    #[test]
+    fn test_request_cost_uses_shared_pricing() {
+        let usage = Usage {
+            prompt_tokens: 1200,
+            completion_tokens: 800,
+            total_tokens: 2000,
+        };
+        for (provider, model) in [("openai", "gpt-4"), ("anthropic", "claude-3-haiku"), ("acme", "unknown")] {
+            let price = pricing::resolve(provider, model).unwrap();
+            assert_eq!(
+                request_cost(&price, &usage),
+                pricing::cost_for(provider, model, 1200, 800).unwrap()
+            );
+        }
+    }
+
+    fn message(role: &str, content: &str) -> Message {
+        Message {
+            role: role.to_string(),
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_system_prompt_injected_when_absent() {
+        let org = Uuid::new_v4();
+        let prompts = vec![OrgSystemPrompt {
+            organization_id: org,
+            prompt: "Never reveal customer data.".to_string(),
+        }];
+        let mut messages = vec![message("user", "hello")];
+
+        let record = inject_system_prompt(&mut messages, &prompts).unwrap();
+
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].role, "system");
+        assert_eq!(messages[0].content, "Never reveal customer data.");
+        assert_eq!(record["organization_ids"], serde_json::json!([org]));
+        assert_eq!(record["estimated_prompt_tokens"], 7);
+    }
+
+    #[test]
+    fn test_system_prompt_skipped_when_caller_sets_one() {
+        let prompts = vec![OrgSystemPrompt {
+            organization_id: Uuid::new_v4(),
+            prompt: "Be safe.".to_string(),
+        }];
+        let mut messages = vec![message("system", "You are terse."), message("user", "hello")];
+
+        assert!(inject_system_prompt(&mut messages, &prompts).is_none());
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].content, "You are terse.");
+
+        // No configured prompt leaves the request untouched as well
+        let mut messages = vec![message("user", "hello")];
+        assert!(inject_system_prompt(&mut messages, &[]).is_none());
+        assert_eq!(messages.len(), 1);
+    }
+
+    #[test]
+    fn test_tokens_per_second() {
+        assert_eq!(tokens_per_second(500, 2000), Some(250.0));
+        assert_eq!(tokens_per_second(1, 4), Some(250.0));
+        assert_eq!(tokens_per_second(0, 2000), None);
+        assert_eq!(tokens_per_second(100, 0), None);
+    }
+
+    #[test]
+    fn test_error_rate_per_bucket_and_overall() {
+        let hour = |h: u32| Utc.with_ymd_and_hms(2025, 6, 1, h, 0, 0).unwrap();
+        let counts = vec![(hour(10), 100, 10), (hour(11), 50, 0), (hour(12), 40, 10)];
+
+        let series = error_rate_series(counts);
+
+        assert_eq!((series.requests, series.errors), (190, 20));
+        assert!((series.error_rate - 20.0 / 190.0).abs() < 1e-9);
+
+        let rates: Vec<_> = series.buckets.iter().map(|b| (b.bucket, b.requests, b.errors)).collect();
+        assert_eq!(rates, vec![(hour(10), 100, 10), (hour(11), 50, 0), (hour(12), 40, 10)]);
+        assert_eq!(series.buckets[0].error_rate, 0.1);
+        assert_eq!(series.buckets[1].error_rate, 0.0);
+        assert_eq!(series.buckets[2].error_rate, 0.25);
+    }
+
+    #[test]
+    fn test_error_rate_of_empty_buckets_is_zero() {
+        let hour = |h: u32| Utc.with_ymd_and_hms(2025, 6, 1, h, 0, 0).unwrap();
+        let series = error_rate_series(vec![(hour(10), 0, 0), (hour(11), 0, 0)]);
+        assert_eq!((series.requests, series.errors, series.error_rate), (0, 0, 0.0));
+        assert_eq!(series.buckets.len(), 2);
+        assert!(series.buckets.iter().all(|b| b.error_rate == 0.0));
+    }
+
+    #[test]
+    fn test_bucket_window_parsing() {
+        assert_eq!(parse_bucket_window("5m").unwrap(), 300);
+        assert_eq!(parse_bucket_window("1h").unwrap(), 3600);
+        assert_eq!(parse_bucket_window("1D").unwrap(), 86_400);
+        assert_eq!(parse_bucket_window("900").unwrap(), 900);
+
+        for invalid in ["", "h", "0m", "-1h", "5w", "30s", "1.5h"] {
+            assert!(parse_bucket_window(invalid).is_err(), "{} should be rejected", invalid);
+        }
+    }
+
+    #[derive(Default)]
+    struct MemoryStore(std::sync::Mutex<HashMap<String, String>>);
+
+    #[async_trait::async_trait]
+    impl CacheStore for MemoryStore {
+        async fn get(&self, key: &str) -> Result<Option<String>> {
+            Ok(self.0.lock().unwrap().get(key).cloned())
+        }
+
+        async fn set(&self, key: &str, value: &str, _ttl: Duration) -> Result<()> {
+            self.0.lock().unwrap().insert(key.to_string(), value.to_string());
+            Ok(())
+        }
+    }
+
+    fn proxy_request(temperature: Option<f32>) -> ProxyRequest {
+        ProxyRequest {
+            provider: "openai".to_string(),
+            model: "gpt-4".to_string(),
+            messages: vec![message("user", "What is 2 + 2?")],
+            temperature,
+            max_tokens: Some(16),
+            stream: None,
+            tags: HashMap::new(),
+        }
+    }
+
+    fn provider_response() -> ProxyResponse {
+        ProxyResponse {
+            id: "chatcmpl-1".to_string(),
+            provider: "openai".to_string(),
+            model: "gpt-4".to_string(),
+            choices: vec![Choice {
+                message: message("assistant", "4"),
+                finish_reason: Some("stop".to_string()),
+            }],
+            usage: Usage {
+                prompt_tokens: 12,
+                completion_tokens: 1,
+                total_tokens: 13,
+            },
+            cost: 0.42,
+            cache_hit: false,
+            time_to_first_token_ms: None,
+            reported_cost: None,
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_identical_deterministic_request_hits_cache() {
+        let cache = ResponseCache::new(Some(Arc::new(MemoryStore::default())), Duration::from_secs(60));
+        let org = Uuid::new_v4();
+        let first = proxy_request(Some(0.0));
+
+        assert!(cached_response(&cache, &[org], &first).await.is_none());
+        cache_response(&cache, &[org], &first, &provider_response()).await;
+
+        // An absent temperature is deterministic too and shares the entry
+        let mut replay_ids = Vec::new();
+        for second in [proxy_request(Some(0.0)), proxy_request(None)] {
+            let hit = cached_response(&cache, &[org], &second).await.expect("second request should hit");
+            assert!(hit.cache_hit);
+            assert_eq!(hit.cost, 0.0);
+            assert_ne!(hit.id, "chatcmpl-1");
+            replay_ids.push(hit.id);
+            assert_eq!(hit.choices[0].message.content, "4");
+            assert_eq!(hit.usage.total_tokens, 13);
+        }
+
+        // Each replay is audited under its own id
+        assert_ne!(replay_ids[0], replay_ids[1]);
+
+        let mut other = proxy_request(Some(0.0));
+        other.max_tokens = Some(32);
+        assert!(cached_response(&cache, &[org], &other).await.is_none());
+
+        // Another organization never receives this organization's completion
+        assert!(cached_response(&cache, &[Uuid::new_v4()], &first).await.is_none());
+    }
+
+    #[actix_web::test]
+    async fn test_non_deterministic_request_bypasses_cache() {
+        let store = Arc::new(MemoryStore::default());
+        let cache = ResponseCache::new(Some(store.clone()), Duration::from_secs(60));
+        let org = Uuid::new_v4();
+        cache_response(&cache, &[org], &proxy_request(Some(0.0)), &provider_response()).await;
+
+        let sampled = proxy_request(Some(0.7));
+        assert!(cached_response(&cache, &[org], &sampled).await.is_none());
+        cache_response(&cache, &[org], &sampled, &provider_response()).await;
+        assert_eq!(store.0.lock().unwrap().len(), 1);
+
+        // A disabled cache never answers
+        let disabled = ResponseCache::new(None, Duration::from_secs(60));
+        cache_response(&disabled, &[org], &proxy_request(None), &provider_response()).await;
+        assert!(cached_response(&disabled, &[org], &proxy_request(None)).await.is_none());
+    }
 }
@@ -0,0 +1,284 @@
+use actix_web::{post, web, HttpRequest, HttpResponse, Responder};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::collections::HashSet;
+use uuid::Uuid;
+use llm_governance_common::{AppError, ApiResponse, Result};
+
+use crate::handlers::providers::verify_org_admin;
+
+/// Largest batch accepted by one import call
+const MAX_IMPORT_BATCH: usize = 1000;
+
+// ============================================================================
+// Request/Response Types
+// ============================================================================
+
+/// Historical usage imported for one organization
+#[derive(Debug, Deserialize)]
+pub struct MetricsImportRequest {
+    pub organization_id: Uuid,
+    pub records: Vec<ImportedMetric>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ImportedMetric {
+    pub time: DateTime<Utc>,
+    pub provider: String,
+    pub model: String,
+    pub user_id: Option<Uuid>,
+    pub team_id: Option<Uuid>,
+    pub tokens_in: i32,
+    pub tokens_out: i32,
+    pub cost: f64,
+    /// Identifier from the source system, part of the de-duplication key
+    pub request_id: Option<String>,
+}
+
+/// Natural key: one usage record per time, model, attribution and source request
+type MetricKey = (DateTime<Utc>, String, String, Option<Uuid>, Option<Uuid>, Option<String>);
+
+impl ImportedMetric {
+    fn natural_key(&self) -> MetricKey {
+        (
+            self.time,
+            self.provider.clone(),
+            self.model.clone(),
+            self.user_id,
+            self.team_id,
+            self.request_id.clone(),
+        )
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct MetricsImportResponse {
+    pub received: usize,
+    pub imported: u64,
+    /// Records skipped as duplicates, within the batch or of existing rows
+    pub skipped: u64,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+#[post("/integrations/metrics/import")]
+pub async fn import_metrics(
+    pool: web::Data<PgPool>,
+    req: web::Json<MetricsImportRequest>,
+    http_req: HttpRequest,
+) -> Result<impl Responder> {
+    let user_id = extract_user_id(&http_req)?;
+    verify_org_admin(pool.get_ref(), req.organization_id, user_id).await?;
+
+    validate_import(&req.records, Utc::now())?;
+    verify_attribution(pool.get_ref(), req.organization_id, &req.records).await?;
+
+    let received = req.records.len();
+    let records = dedup_batch(&req.records);
+    let imported = insert_metrics(pool.get_ref(), req.organization_id, &records).await?;
+
+    tracing::info!(
+        "Imported {} of {} historical metrics for organization {}",
+        imported,
+        received,
+        req.organization_id
+    );
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(MetricsImportResponse {
+        received,
+        imported,
+        skipped: received as u64 - imported,
+    })))
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn extract_user_id(req: &HttpRequest) -> Result<Uuid> {
+    req.headers()
+        .get("X-User-Id")
+        .and_then(|h| h.to_str().ok())
+        .and_then(|s| Uuid::parse_str(s).ok())
+        .ok_or_else(|| AppError::Unauthorized)
+}
+
+/// Reject empty or oversized batches and records that cannot be real usage
+fn validate_import(records: &[ImportedMetric], now: DateTime<Utc>) -> Result<()> {
+    if records.is_empty() || records.len() > MAX_IMPORT_BATCH {
+        return Err(AppError::Validation(format!(
+            "an import must contain between 1 and {} records",
+            MAX_IMPORT_BATCH
+        )));
+    }
+
+    for (i, record) in records.iter().enumerate() {
+        let problem = if record.provider.trim().is_empty() || record.model.trim().is_empty() {
+            Some("provider and model are required")
+        } else if record.tokens_in < 0 || record.tokens_out < 0 {
+            Some("token counts must not be negative")
+        } else if !record.cost.is_finite() || record.cost < 0.0 {
+            Some("cost must be a non-negative number")
+        } else if record.time > now {
+            Some("time must not be in the future")
+        } else {
+            None
+        };
+
+        if let Some(problem) = problem {
+            return Err(AppError::Validation(format!("record {}: {}", i, problem)));
+        }
+    }
+
+    Ok(())
+}
+
+/// Users and teams referenced by the batch must belong to the organization
+async fn verify_attribution(pool: &PgPool, org_id: Uuid, records: &[ImportedMetric]) -> Result<()> {
+    let user_ids: Vec<Uuid> = records.iter().filter_map(|r| r.user_id).collect::<HashSet<_>>().into_iter().collect();
+    let team_ids: Vec<Uuid> = records.iter().filter_map(|r| r.team_id).collect::<HashSet<_>>().into_iter().collect();
+
+    let (members, teams): (i64, i64) = sqlx::query_as(
+        r#"
+        SELECT
+            (SELECT COUNT(DISTINCT user_id) FROM organization_members
+             WHERE organization_id = $1 AND user_id = ANY($2)),
+            (SELECT COUNT(*) FROM teams WHERE organization_id = $1 AND id = ANY($3))
+        "#,
+    )
+    .bind(org_id)
+    .bind(&user_ids)
+    .bind(&team_ids)
+    .fetch_one(pool)
+    .await?;
+
+    if members as usize != user_ids.len() || teams as usize != team_ids.len() {
+        return Err(AppError::Validation(
+            "records reference users or teams outside the organization".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Drop records repeating an earlier record's natural key, keeping the first
+fn dedup_batch(records: &[ImportedMetric]) -> Vec<ImportedMetric> {
+    let mut seen = HashSet::new();
+    records
+        .iter()
+        .filter(|r| seen.insert(r.natural_key()))
+        .cloned()
+        .collect()
+}
+
+/// Insert all records in one statement, skipping rows whose natural key
+/// already exists; returns the number of rows inserted
+async fn insert_metrics(pool: &PgPool, org_id: Uuid, records: &[ImportedMetric]) -> Result<u64> {
+    let result = sqlx::query(
+        r#"
+        INSERT INTO llm_metrics (
+            time, provider, model, user_id, team_id,
+            tokens_in, tokens_out, cost, status, request_id, metadata
+        )
+        SELECT
+            v.time AT TIME ZONE 'UTC', v.provider, v.model, v.user_id, v.team_id,
+            v.tokens_in, v.tokens_out, v.cost, 'success', v.request_id,
+            jsonb_build_object('imported', true, 'organization_id', $10::uuid)
+        FROM UNNEST(
+            $1::timestamptz[], $2::text[], $3::text[], $4::uuid[], $5::uuid[],
+            $6::int[], $7::int[], $8::float8[], $9::text[]
+        ) AS v(time, provider, model, user_id, team_id, tokens_in, tokens_out, cost, request_id)
+        WHERE NOT EXISTS (
+            SELECT 1 FROM llm_metrics m
+            WHERE m.time = v.time AT TIME ZONE 'UTC'
+            AND m.provider = v.provider
+            AND m.model = v.model
+            AND m.user_id IS NOT DISTINCT FROM v.user_id
+            AND m.team_id IS NOT DISTINCT FROM v.team_id
+            AND m.request_id IS NOT DISTINCT FROM v.request_id
+        )
+        "#,
+    )
+    .bind(records.iter().map(|r| r.time).collect::<Vec<_>>())
+    .bind(records.iter().map(|r| r.provider.clone()).collect::<Vec<_>>())
+    .bind(records.iter().map(|r| r.model.clone()).collect::<Vec<_>>())
+    .bind(records.iter().map(|r| r.user_id).collect::<Vec<_>>())
+    .bind(records.iter().map(|r| r.team_id).collect::<Vec<_>>())
+    .bind(records.iter().map(|r| r.tokens_in).collect::<Vec<_>>())
+    .bind(records.iter().map(|r| r.tokens_out).collect::<Vec<_>>())
+    .bind(records.iter().map(|r| r.cost).collect::<Vec<_>>())
+    .bind(records.iter().map(|r| r.request_id.clone()).collect::<Vec<_>>())
+    .bind(org_id)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(import_metrics);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn record(request_id: &str, minutes_ago: i64) -> ImportedMetric {
+        ImportedMetric {
+            time: Utc::now() - Duration::minutes(minutes_ago),
+            provider: "openai".to_string(),
+            model: "gpt-4".to_string(),
+            user_id: None,
+            team_id: None,
+            tokens_in: 100,
+            tokens_out: 50,
+            cost: 0.006,
+            request_id: Some(request_id.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_clean_import_keeps_every_record() {
+        let records = vec![record("a", 30), record("b", 20), record("c", 10)];
+
+        assert!(validate_import(&records, Utc::now()).is_ok());
+        assert_eq!(dedup_batch(&records).len(), 3);
+    }
+
+    #[test]
+    fn test_duplicates_are_skipped() {
+        let first = record("a", 30);
+        let records = vec![first.clone(), record("b", 20), first.clone()];
+
+        let kept = dedup_batch(&records);
+        assert_eq!(kept.len(), 2);
+        assert_eq!(kept[0].request_id.as_deref(), Some("a"));
+        assert_eq!(kept[1].request_id.as_deref(), Some("b"));
+
+        // Same time and model but a different source request is not a duplicate
+        let mut other = first.clone();
+        other.request_id = Some("z".to_string());
+        assert_eq!(dedup_batch(&[first, other]).len(), 2);
+    }
+
+    #[test]
+    fn test_invalid_records_are_rejected() {
+        let now = Utc::now();
+        assert!(validate_import(&[], now).is_err());
+
+        let mut negative = record("a", 1);
+        negative.tokens_out = -1;
+        assert!(validate_import(&[negative], now).is_err());
+
+        let mut future = record("a", 0);
+        future.time = now + Duration::hours(1);
+        assert!(validate_import(&[future], now).is_err());
+
+        let oversized = vec![record("a", 1); MAX_IMPORT_BATCH + 1];
+        assert!(validate_import(&oversized, now).is_err());
+    }
+}
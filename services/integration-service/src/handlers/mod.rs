@@ -2,12 +2,18 @@ use actix_web::web;
 
 pub mod health;
 pub mod integrations;
+pub mod metrics_import;
 pub mod providers;
+pub mod webhooks;
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(llm_governance_common::metrics::metrics_endpoint);
+    cfg.service(llm_governance_common::versioning::version_endpoint);
     cfg.service(web::scope("/api/v1")
         .configure(health::configure)
         .configure(integrations::configure)
+        .configure(metrics_import::configure)
         .configure(providers::configure)
+        .configure(webhooks::configure)
     );
 }
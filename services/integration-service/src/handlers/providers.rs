@@ -142,6 +142,10 @@ pub async fn create_provider(
     let user_id = extract_user_id(&req)?;
     verify_org_admin(pool.get_ref(), *org_id, user_id).await?;
 
+    if req_body.api_key.is_some() {
+        verify_org_mfa(pool.get_ref(), *org_id, user_id).await?;
+    }
+
     // Encrypt API key if provided (simplified - use proper encryption in production)
     let encrypted_key = req_body.api_key.as_ref().map(|key| {
         // TODO: Implement proper encryption using a key management service
@@ -191,6 +195,10 @@ pub async fn update_provider(
 
     verify_org_admin(pool.get_ref(), provider.0, user_id).await?;
 
+    if req_body.api_key.is_some() {
+        verify_org_mfa(pool.get_ref(), provider.0, user_id).await?;
+    }
+
     // Build update query dynamically
     let mut updates = vec![];
 
@@ -425,7 +433,7 @@ async fn verify_org_access(pool: &PgPool, org_id: Uuid, user_id: Uuid) -> Result
     Ok(())
 }
 
-async fn verify_org_admin(pool: &PgPool, org_id: Uuid, user_id: Uuid) -> Result<()> {
+pub(crate) async fn verify_org_admin(pool: &PgPool, org_id: Uuid, user_id: Uuid) -> Result<()> {
     let role: Option<(String,)> = sqlx::query_as(
         "SELECT role FROM organization_members WHERE organization_id = $1 AND user_id = $2"
     )
@@ -441,6 +449,31 @@ async fn verify_org_admin(pool: &PgPool, org_id: Uuid, user_id: Uuid) -> Result<
     }
 }
 
+/// Storing an API key requires MFA when the organization's settings set `require_mfa`
+async fn verify_org_mfa(pool: &PgPool, org_id: Uuid, user_id: Uuid) -> Result<()> {
+    let (require_mfa, mfa_enabled): (bool, bool) = sqlx::query_as(
+        r#"
+        SELECT
+            COALESCE((SELECT settings->>'require_mfa' = 'true' FROM organizations WHERE id = $1), false),
+            COALESCE((SELECT mfa_enabled FROM users WHERE id = $2), false)
+        "#,
+    )
+    .bind(org_id)
+    .bind(user_id)
+    .fetch_one(pool)
+    .await?;
+
+    if api_key_change_allowed(require_mfa, mfa_enabled) {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden)
+    }
+}
+
+fn api_key_change_allowed(require_mfa: bool, mfa_enabled: bool) -> bool {
+    !require_mfa || mfa_enabled
+}
+
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.service(list_providers)
         .service(get_provider)
@@ -451,3 +484,15 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
         .service(create_model)
         .service(delete_model);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_key_requires_mfa_when_org_demands_it() {
+        assert!(!api_key_change_allowed(true, false));
+        assert!(api_key_change_allowed(true, true));
+        assert!(api_key_change_allowed(false, false));
+    }
+}
@@ -0,0 +1,222 @@
+use actix_web::{get, post, web, HttpRequest, HttpResponse, Responder};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+use llm_governance_common::{AppError, ApiResponse, Result};
+use llm_governance_common::db::Page;
+
+use crate::handlers::providers::verify_org_admin;
+use crate::services::secret_cipher::SecretCipher;
+use crate::services::webhooks::{validate_webhook_url, EVENT_TYPES};
+
+// ============================================================================
+// Request/Response Types
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct CreateSubscriptionRequest {
+    pub organization_id: Uuid,
+    pub url: String,
+    /// Event types to receive; all of them when empty
+    #[serde(default)]
+    pub event_types: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SubscriptionQuery {
+    pub organization_id: Uuid,
+}
+
+/// A subscription as listed; the secret is only returned on creation
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct WebhookSubscription {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub url: String,
+    pub event_types: Vec<String>,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeadLetterQuery {
+    pub organization_id: Uuid,
+    pub subscription_id: Option<Uuid>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
+
+/// A delivery that exhausted its attempts
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct DeadLetter {
+    pub id: Uuid,
+    pub subscription_id: Uuid,
+    pub url: String,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    pub attempts: i32,
+    pub last_status_code: Option<i32>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// When the final attempt failed
+    pub updated_at: DateTime<Utc>,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+#[post("/webhooks/subscriptions")]
+pub async fn create_subscription(
+    pool: web::Data<PgPool>,
+    cipher: web::Data<SecretCipher>,
+    req: web::Json<CreateSubscriptionRequest>,
+    http_req: HttpRequest,
+) -> Result<impl Responder> {
+    let user_id = extract_user_id(&http_req)?;
+    verify_org_admin(pool.get_ref(), req.organization_id, user_id).await?;
+
+    validate_event_types(&req.event_types)?;
+    let url = validate_webhook_url(&req.url).await?;
+
+    let secret = generate_secret();
+    let subscription = sqlx::query_as::<_, WebhookSubscription>(
+        r#"
+        INSERT INTO webhook_subscriptions (organization_id, url, secret_encrypted, event_types, created_by)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING id, organization_id, url, event_types, is_active, created_at
+        "#,
+    )
+    .bind(req.organization_id)
+    .bind(url.as_str())
+    .bind(cipher.encrypt(&secret)?)
+    .bind(&req.event_types)
+    .bind(user_id)
+    .fetch_one(pool.get_ref())
+    .await?;
+
+    Ok(HttpResponse::Created().json(ApiResponse::success(serde_json::json!({
+        "subscription": subscription,
+        "secret": secret,
+    }))))
+}
+
+#[get("/webhooks/subscriptions")]
+pub async fn list_subscriptions(
+    pool: web::Data<PgPool>,
+    query: web::Query<SubscriptionQuery>,
+    http_req: HttpRequest,
+) -> Result<impl Responder> {
+    let user_id = extract_user_id(&http_req)?;
+    verify_org_admin(pool.get_ref(), query.organization_id, user_id).await?;
+
+    let subscriptions = sqlx::query_as::<_, WebhookSubscription>(
+        r#"
+        SELECT id, organization_id, url, event_types, is_active, created_at
+        FROM webhook_subscriptions
+        WHERE organization_id = $1
+        ORDER BY created_at DESC
+        "#,
+    )
+    .bind(query.organization_id)
+    .fetch_all(pool.get_ref())
+    .await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(subscriptions)))
+}
+
+#[get("/webhooks/dead-letters")]
+pub async fn list_dead_letters(
+    pool: web::Data<PgPool>,
+    query: web::Query<DeadLetterQuery>,
+    http_req: HttpRequest,
+) -> Result<impl Responder> {
+    let user_id = extract_user_id(&http_req)?;
+    verify_org_admin(pool.get_ref(), query.organization_id, user_id).await?;
+
+    let Page { limit, offset } = Page::new(query.limit, query.offset);
+
+    let dead_letters = sqlx::query_as::<_, DeadLetter>(
+        r#"
+        SELECT d.id, d.subscription_id, s.url, d.event_type, d.payload, d.attempts,
+               d.last_status_code, d.last_error, d.created_at, d.updated_at
+        FROM webhook_deliveries d
+        JOIN webhook_subscriptions s ON s.id = d.subscription_id
+        WHERE s.organization_id = $1
+          AND d.status = 'dead_lettered'
+          AND ($2::uuid IS NULL OR d.subscription_id = $2)
+        ORDER BY d.updated_at DESC
+        LIMIT $3 OFFSET $4
+        "#,
+    )
+    .bind(query.organization_id)
+    .bind(query.subscription_id)
+    .bind(limit as i64)
+    .bind(offset as i64)
+    .fetch_all(pool.get_ref())
+    .await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
+        "dead_letters": dead_letters,
+        "limit": limit,
+        "offset": offset,
+    }))))
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn validate_event_types(event_types: &[String]) -> Result<()> {
+    match event_types.iter().find(|t| !EVENT_TYPES.contains(&t.as_str())) {
+        Some(unknown) => Err(AppError::Validation(format!(
+            "Unknown event type '{}'; expected one of: {}",
+            unknown,
+            EVENT_TYPES.join(", ")
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Signing secret shown to the caller once; 244 random bits
+fn generate_secret() -> String {
+    format!("whsec_{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple())
+}
+
+fn extract_user_id(req: &HttpRequest) -> Result<Uuid> {
+    req.headers()
+        .get("X-User-Id")
+        .and_then(|h| h.to_str().ok())
+        .and_then(|s| Uuid::parse_str(s).ok())
+        .ok_or_else(|| AppError::Unauthorized)
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(create_subscription)
+        .service(list_subscriptions)
+        .service(list_dead_letters);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unknown_event_types_are_rejected() {
+        assert!(validate_event_types(&[]).is_ok());
+        assert!(validate_event_types(&["budget.hard_limit_reached".to_string()]).is_ok());
+        assert!(matches!(
+            validate_event_types(&["budget.hard_limit_reached".to_string(), "user.created".to_string()]),
+            Err(AppError::Validation(msg)) if msg.contains("user.created")
+        ));
+    }
+
+    #[test]
+    fn test_generated_secrets_are_unique() {
+        let secret = generate_secret();
+        assert!(secret.starts_with("whsec_"));
+        assert_eq!(secret.len(), "whsec_".len() + 64);
+        assert_ne!(secret, generate_secret());
+    }
+}
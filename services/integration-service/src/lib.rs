@@ -0,0 +1,7 @@
+//! LLM provider integrations and the governed proxy, served by `main.rs`
+
+pub mod config;
+pub mod handlers;
+pub mod middleware;
+pub mod models;
+pub mod services;
@@ -1,23 +1,30 @@
 use actix_web::{web, App, HttpServer};
-use tracing::{info, Level};
-use tracing_subscriber;
+use tracing::info;
 
-mod config;
-mod handlers;
-mod middleware;
-mod models;
-mod services;
-
-use config::Config;
+use integration_service::config::{Config, ProviderTimeouts};
+use integration_service::handlers;
+use integration_service::handlers::integrations::CircuitBreakers;
+use integration_service::services::budget_guard::BudgetUtilizationCache;
+use integration_service::services::model_catalog::ModelCatalog;
+use integration_service::services::org_concurrency::OrgConcurrencyLimiter;
+use integration_service::services::response_cache::ResponseCache;
+use integration_service::services::secret_cipher::SecretCipher;
+use integration_service::services::webhooks::{self, WebhookRetryPolicy};
+use llm_governance_common::metrics::RequestMetrics;
+use llm_governance_common::pricing;
+use llm_governance_common::request_context::RequestContext;
+use llm_governance_common::scheduler::Scheduler;
+use llm_governance_common::telemetry;
+use llm_governance_common::versioning::ApiVersioning;
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
-    tracing_subscriber::fmt()
-        .with_max_level(Level::INFO)
-        .init();
-
     dotenv::dotenv().ok();
+    telemetry::init("integration-service").expect("Failed to initialize telemetry");
+
     let config = Config::from_env().expect("Failed to load configuration");
+    pricing::configure_pricing(config.pricing_table_path.as_deref(), &config.pricing_fallback)
+        .expect("Failed to configure pricing");
 
     info!("Starting integration-service on {}:{}", config.host, config.port);
 
@@ -28,15 +35,64 @@ async fn main() -> std::io::Result<()> {
     let redis_client = redis::Client::open(config.redis_url.clone())
         .expect("Failed to create Redis client");
 
-    HttpServer::new(move || {
+    // Shared across workers so breaker state and the exported gauge agree
+    let circuit_breakers = web::Data::new(CircuitBreakers::default());
+    let http_client = reqwest::Client::new();
+    let budget_cache = web::Data::new(BudgetUtilizationCache::new(
+        std::time::Duration::from_secs(config.budget_cache_ttl_secs),
+    ));
+    let org_limiter = web::Data::new(OrgConcurrencyLimiter::new(config.proxy_max_in_flight_per_org));
+    let response_cache = web::Data::new(ResponseCache::from_config(&config, redis_client.clone()));
+    let secret_cipher = SecretCipher::from_base64(&config.webhook_secret_key)
+        .expect("Invalid INTEGRATION-SERVICE_WEBHOOK_SECRET_KEY");
+    // A public endpoint must not be able to redirect deliveries to internal hosts
+    let webhook_client = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .expect("Failed to create webhook HTTP client");
+    let mut scheduler = Scheduler::new();
+    webhooks::schedule_retry_worker(
+        &mut scheduler,
+        db_pool.clone(),
+        webhook_client,
+        secret_cipher.clone(),
+        WebhookRetryPolicy::from_config(&config),
+        std::time::Duration::from_secs(config.webhook_worker_interval_secs.max(1)),
+        config.webhook_worker_batch_size,
+    );
+    let provider_timeouts = web::Data::new(
+        ProviderTimeouts::from_config(&config).expect("Invalid provider timeout configuration"),
+    );
+    let model_catalog = web::Data::new(
+        ModelCatalog::load(config.model_catalog_path.as_deref())
+            .expect("Failed to load model catalog"),
+    );
+    let scheduler = scheduler.start();
+
+    let result = HttpServer::new(move || {
         App::new()
             .app_data(web::Data::new(db_pool.clone()))
             .app_data(web::Data::new(redis_client.clone()))
+            .app_data(circuit_breakers.clone())
+            .app_data(web::Data::new(http_client.clone()))
+            .app_data(model_catalog.clone())
+            .app_data(provider_timeouts.clone())
+            .app_data(budget_cache.clone())
+            .app_data(org_limiter.clone())
+            .app_data(response_cache.clone())
+            .app_data(web::Data::new(secret_cipher.clone()))
             .app_data(web::Data::new(config.clone()))
+            .wrap(RequestContext)
             .wrap(tracing_actix_web::TracingLogger::default())
+            .wrap(RequestMetrics::new("integration-service"))
+            .wrap(ApiVersioning::new())
             .configure(handlers::configure)
     })
     .bind((config.host.as_str(), config.port))?
     .run()
-    .await
+    .await;
+
+    scheduler.shutdown().await;
+    telemetry::shutdown();
+    result
 }
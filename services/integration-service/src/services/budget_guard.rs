@@ -0,0 +1,282 @@
+//! Live budget checks for the proxy's hard-limit path
+//!
+//! `budgets.current_spend` is only as fresh as the last reconciliation run, so
+//! hard limits are enforced against the spend the proxy records in
+//! `llm_metrics`, summed over the budget's current period. Results are cached
+//! for a few seconds per budget to keep bursts of proxied requests from
+//! re-running the sum each time.
+
+use chrono::{DateTime, Duration, Months, Utc};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::time::Instant;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use llm_governance_common::{AppError, Result};
+
+use crate::services::webhooks::{publish_event, BUDGET_HARD_LIMIT_REACHED};
+
+/// Live spend against one budget
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BudgetUtilization {
+    pub spend: f64,
+    pub amount: f64,
+    /// Spend as a percentage of the amount
+    pub percent: f64,
+    pub exceeded: bool,
+    /// Start of the budget period the spend was summed over
+    pub period_start: DateTime<Utc>,
+}
+
+impl BudgetUtilization {
+    pub fn new(spend: f64, amount: f64, period_start: DateTime<Utc>) -> Self {
+        let percent = if amount > 0.0 { spend / amount * 100.0 } else { 0.0 };
+        Self {
+            spend,
+            amount,
+            percent,
+            exceeded: spend >= amount,
+            period_start,
+        }
+    }
+}
+
+/// Period containing `now` for a budget whose first period starts at `anchor`
+///
+/// Stored `period_start`/`period_end` are not rolled forward, so the current
+/// window is derived from the anchor. Calendar periods step by months from the
+/// anchor itself to avoid day-of-month drift.
+pub fn current_period_window(
+    period: &str,
+    anchor: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> (DateTime<Utc>, DateTime<Utc>) {
+    let nth = |n: i64| -> DateTime<Utc> {
+        match period {
+            "daily" => anchor + Duration::days(n),
+            "weekly" => anchor + Duration::weeks(n),
+            "yearly" => anchor
+                .checked_add_months(Months::new(12 * n as u32))
+                .unwrap_or(anchor),
+            // Budgets are validated to a known period; monthly is the default
+            _ => anchor.checked_add_months(Months::new(n as u32)).unwrap_or(anchor),
+        }
+    };
+
+    if now < anchor {
+        return (anchor, nth(1));
+    }
+
+    let mut n = match period {
+        "daily" => (now - anchor).num_days(),
+        "weekly" => (now - anchor).num_weeks(),
+        _ => 0,
+    };
+    while nth(n + 1) <= now {
+        n += 1;
+    }
+
+    (nth(n), nth(n + 1))
+}
+
+/// Sum the budget's scoped spend over its current period
+pub async fn budget_live_utilization(pool: &PgPool, budget_id: Uuid) -> Result<BudgetUtilization> {
+    let budget: Option<(Uuid, Option<Uuid>, Option<Uuid>, f64, String, DateTime<Utc>)> = sqlx::query_as(
+        r#"
+        SELECT organization_id, team_id, user_id, amount::FLOAT8, period, period_start
+        FROM budgets
+        WHERE id = $1
+        "#,
+    )
+    .bind(budget_id)
+    .fetch_optional(pool)
+    .await?;
+
+    let (organization_id, team_id, user_id, amount, period, anchor) =
+        budget.ok_or_else(|| AppError::NotFound("Budget not found".to_string()))?;
+    let (start, end) = current_period_window(&period, anchor, Utc::now());
+
+    // `llm_metrics` has no organization column: an org-wide budget covers
+    // requests made by the org's teams or members
+    let (spend,): (f64,) = sqlx::query_as(
+        r#"
+        SELECT COALESCE(SUM(cost), 0)::FLOAT8
+        FROM llm_metrics
+        WHERE ($2::uuid IS NULL OR team_id = $2)
+        AND ($3::uuid IS NULL OR user_id = $3)
+        AND (
+            team_id IN (SELECT id FROM teams WHERE organization_id = $1)
+            OR user_id IN (SELECT user_id FROM organization_members WHERE organization_id = $1)
+        )
+        AND time >= $4 AND time < $5
+        "#,
+    )
+    .bind(organization_id)
+    .bind(team_id)
+    .bind(user_id)
+    .bind(start.naive_utc())
+    .bind(end.naive_utc())
+    .fetch_one(pool)
+    .await?;
+
+    Ok(BudgetUtilization::new(spend, amount, start))
+}
+
+/// Short-lived cache of live utilization per budget
+pub struct BudgetUtilizationCache {
+    ttl: std::time::Duration,
+    entries: RwLock<HashMap<Uuid, (Instant, BudgetUtilization)>>,
+}
+
+impl BudgetUtilizationCache {
+    pub fn new(ttl: std::time::Duration) -> Self {
+        Self {
+            ttl,
+            entries: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub async fn utilization(&self, pool: &PgPool, budget_id: Uuid) -> Result<BudgetUtilization> {
+        if let Some((stored_at, cached)) = self.entries.read().await.get(&budget_id) {
+            if stored_at.elapsed() < self.ttl {
+                return Ok(*cached);
+            }
+        }
+
+        let fresh = budget_live_utilization(pool, budget_id).await?;
+        let mut entries = self.entries.write().await;
+        entries.retain(|_, (stored_at, _)| stored_at.elapsed() < self.ttl);
+        entries.insert(budget_id, (Instant::now(), fresh));
+        Ok(fresh)
+    }
+}
+
+/// Reject the request when any active hard-limit budget covering the caller
+/// has been spent, notifying the budget's organization once per period
+pub async fn enforce_hard_limits(
+    pool: &PgPool,
+    cache: &BudgetUtilizationCache,
+    user_id: Option<Uuid>,
+    team_id: Option<Uuid>,
+) -> Result<()> {
+    if user_id.is_none() && team_id.is_none() {
+        return Ok(());
+    }
+
+    let budgets: Vec<(Uuid, Uuid, String)> = sqlx::query_as(
+        r#"
+        SELECT id, organization_id, name
+        FROM budgets
+        WHERE is_active = true AND hard_limit = true
+        AND (
+            user_id = $1
+            OR team_id = $2
+            OR (
+                team_id IS NULL AND user_id IS NULL
+                AND organization_id IN (SELECT organization_id FROM organization_members WHERE user_id = $1)
+            )
+        )
+        "#,
+    )
+    .bind(user_id)
+    .bind(team_id)
+    .fetch_all(pool)
+    .await?;
+
+    for (budget_id, organization_id, name) in budgets {
+        let utilization = cache.utilization(pool, budget_id).await?;
+        if utilization.exceeded {
+            // Spend is left out so repeated rejections publish the same payload
+            let payload = serde_json::json!({
+                "budget_id": budget_id,
+                "organization_id": organization_id,
+                "name": name,
+                "amount": utilization.amount,
+                "period_start": utilization.period_start,
+            });
+            if let Err(e) = publish_event(pool, organization_id, BUDGET_HARD_LIMIT_REACHED, &payload).await {
+                tracing::warn!("Failed to queue {} webhook for budget {}: {}", BUDGET_HARD_LIMIT_REACHED, budget_id, e);
+            }
+
+            return Err(AppError::TooManyRequests(format!(
+                "Budget '{}' hard limit exceeded ({:.2} of {:.2})",
+                name, utilization.spend, utilization.amount
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(y: i32, m: u32, d: u32, h: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_window_rolls_forward_from_anchor() {
+        let anchor = at(2025, 1, 1, 0);
+
+        assert_eq!(
+            current_period_window("daily", anchor, at(2025, 3, 10, 15)),
+            (at(2025, 3, 10, 0), at(2025, 3, 11, 0))
+        );
+        assert_eq!(
+            current_period_window("weekly", anchor, at(2025, 1, 15, 0)),
+            (at(2025, 1, 15, 0), at(2025, 1, 22, 0))
+        );
+        assert_eq!(
+            current_period_window("monthly", anchor, at(2025, 3, 31, 23)),
+            (at(2025, 3, 1, 0), at(2025, 4, 1, 0))
+        );
+        assert_eq!(
+            current_period_window("yearly", anchor, at(2026, 6, 1, 0)),
+            (at(2026, 1, 1, 0), at(2027, 1, 1, 0))
+        );
+    }
+
+    #[test]
+    fn test_monthly_window_keeps_anchor_day() {
+        let anchor = at(2025, 1, 31, 0);
+
+        // February is clamped to its last day without shifting later months
+        assert_eq!(
+            current_period_window("monthly", anchor, at(2025, 3, 5, 0)),
+            (at(2025, 2, 28, 0), at(2025, 3, 31, 0))
+        );
+        assert_eq!(
+            current_period_window("monthly", anchor, at(2025, 4, 1, 0)),
+            (at(2025, 3, 31, 0), at(2025, 4, 30, 0))
+        );
+    }
+
+    #[test]
+    fn test_window_boundaries_are_half_open() {
+        let anchor = at(2025, 1, 1, 0);
+
+        // A request at the boundary belongs to the new period only
+        let (start, end) = current_period_window("daily", anchor, at(2025, 1, 2, 0));
+        assert_eq!((start, end), (at(2025, 1, 2, 0), at(2025, 1, 3, 0)));
+
+        // Before the first period starts, the first period applies
+        assert_eq!(
+            current_period_window("daily", anchor, at(2024, 12, 31, 0)),
+            (anchor, at(2025, 1, 2, 0))
+        );
+    }
+
+    #[test]
+    fn test_utilization() {
+        let under = BudgetUtilization::new(40.0, 100.0, at(2025, 1, 1, 0));
+        assert_eq!(under.percent, 40.0);
+        assert!(!under.exceeded);
+
+        let over = BudgetUtilization::new(100.0, 100.0, at(2025, 1, 1, 0));
+        assert!(over.exceeded);
+    }
+}
@@ -1 +1,10 @@
-// Add your business logic services here
+pub mod budget_guard;
+pub mod model_catalog;
+pub mod org_concurrency;
+pub mod provider_allowlist;
+pub mod provider_retry;
+pub mod request_scope;
+pub mod response_cache;
+pub mod secret_cipher;
+pub mod system_prompt;
+pub mod webhooks;
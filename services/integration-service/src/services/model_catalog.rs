@@ -0,0 +1,318 @@
+//! Provider/model catalog with per-model lifecycle status
+//!
+//! Loaded from the JSON file named by `model_catalog_path`, falling back to
+//! the built-in catalog. Requests for deprecated models are served with a
+//! warning; requests for retired models are rejected, as are requests that
+//! exceed a model's published capabilities.
+
+use serde::{Deserialize, Serialize};
+use llm_governance_common::{AppError, Result};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelStatus {
+    #[default]
+    Active,
+    Deprecated,
+    Retired,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CatalogModel {
+    pub name: String,
+    #[serde(default)]
+    pub status: ModelStatus,
+    /// Suggested model to migrate to
+    #[serde(default)]
+    pub replacement: Option<String>,
+    /// Limits and features; requests to models without them are not checked
+    #[serde(default)]
+    pub capabilities: Option<ModelCapabilities>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelCapabilities {
+    /// Context window in tokens, prompt and completion combined
+    pub context_tokens: u32,
+    /// Most completion tokens one request may ask for
+    pub max_output: u32,
+    /// Input modalities, e.g. `text` and `image`
+    #[serde(default = "default_modalities")]
+    pub modalities: Vec<String>,
+    #[serde(default)]
+    pub streaming: bool,
+}
+
+fn default_modalities() -> Vec<String> {
+    vec!["text".to_string()]
+}
+
+impl ModelCapabilities {
+    pub fn supports_vision(&self) -> bool {
+        self.modalities.iter().any(|m| m == "image")
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CatalogProvider {
+    pub name: String,
+    pub models: Vec<CatalogModel>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelCatalog {
+    pub providers: Vec<CatalogProvider>,
+}
+
+/// Notice attached to a request that targets a deprecated model
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DeprecationNotice {
+    pub provider: String,
+    pub model: String,
+    pub replacement: Option<String>,
+}
+
+impl DeprecationNotice {
+    /// Value for an HTTP `Warning` header (code 299, miscellaneous persistent warning)
+    pub fn warning_header(&self) -> String {
+        match self.replacement {
+            Some(ref replacement) => format!(
+                "299 - \"Model {}:{} is deprecated; use {}\"",
+                self.provider, self.model, replacement
+            ),
+            None => format!("299 - \"Model {}:{} is deprecated\"", self.provider, self.model),
+        }
+    }
+}
+
+fn model(
+    name: &str,
+    status: ModelStatus,
+    replacement: Option<&str>,
+    capabilities: ModelCapabilities,
+) -> CatalogModel {
+    CatalogModel {
+        name: name.to_string(),
+        status,
+        replacement: replacement.map(String::from),
+        capabilities: Some(capabilities),
+    }
+}
+
+/// Streaming-capable model limits; `vision` adds image input
+fn caps(context_tokens: u32, max_output: u32, vision: bool) -> ModelCapabilities {
+    let mut modalities = default_modalities();
+    if vision {
+        modalities.push("image".to_string());
+    }
+    ModelCapabilities {
+        context_tokens,
+        max_output,
+        modalities,
+        streaming: true,
+    }
+}
+
+fn provider(name: &str, models: Vec<CatalogModel>) -> CatalogProvider {
+    CatalogProvider {
+        name: name.to_string(),
+        models,
+    }
+}
+
+impl ModelCatalog {
+    /// Catalog used when no file is configured
+    pub fn builtin() -> Self {
+        use ModelStatus::*;
+
+        Self {
+            providers: vec![
+                provider("openai", vec![
+                    model("gpt-4", Active, None, caps(8_192, 8_192, false)),
+                    model("gpt-4-turbo", Active, None, caps(128_000, 4_096, true)),
+                    model("gpt-3.5-turbo", Active, None, caps(16_385, 4_096, false)),
+                ]),
+                provider("anthropic", vec![
+                    model("claude-3-opus", Active, None, caps(200_000, 4_096, true)),
+                    model("claude-3-sonnet", Deprecated, Some("claude-3-5-sonnet"), caps(200_000, 4_096, true)),
+                    model("claude-3-haiku", Active, None, caps(200_000, 4_096, true)),
+                ]),
+                provider("google", vec![
+                    model("gemini-pro", Active, None, caps(32_760, 8_192, false)),
+                    model("gemini-pro-vision", Retired, Some("gemini-pro"), caps(16_384, 2_048, true)),
+                ]),
+                provider("azure", vec![
+                    model("gpt-4", Active, None, caps(8_192, 8_192, false)),
+                    model("gpt-35-turbo", Active, None, caps(16_385, 4_096, false)),
+                ]),
+                provider("bedrock", vec![
+                    model("claude-v2", Deprecated, Some("claude-3-haiku"), caps(100_000, 4_096, false)),
+                    model("titan-text", Active, None, caps(8_192, 8_192, false)),
+                ]),
+            ],
+        }
+    }
+
+    /// Load the catalog from a JSON file, or the built-in one when no path is set
+    pub fn load(path: Option<&str>) -> Result<Self> {
+        let Some(path) = path else {
+            return Ok(Self::builtin());
+        };
+
+        let raw = std::fs::read_to_string(path)
+            .map_err(|e| AppError::Internal(format!("Failed to read model catalog {}: {}", path, e)))?;
+        serde_json::from_str(&raw)
+            .map_err(|e| AppError::Internal(format!("Invalid model catalog {}: {}", path, e)))
+    }
+
+    pub fn lookup(&self, provider: &str, model: &str) -> Option<&CatalogModel> {
+        self.providers
+            .iter()
+            .find(|p| p.name == provider)?
+            .models
+            .iter()
+            .find(|m| m.name == model)
+    }
+
+    /// Reject retired models and return a notice for deprecated ones
+    ///
+    /// Models missing from the catalog are treated as active.
+    pub fn check_model(&self, provider: &str, model: &str) -> Result<Option<DeprecationNotice>> {
+        let Some(entry) = self.lookup(provider, model) else {
+            return Ok(None);
+        };
+
+        match entry.status {
+            ModelStatus::Active => Ok(None),
+            ModelStatus::Deprecated => Ok(Some(DeprecationNotice {
+                provider: provider.to_string(),
+                model: model.to_string(),
+                replacement: entry.replacement.clone(),
+            })),
+            ModelStatus::Retired => Err(AppError::BadRequest(match entry.replacement {
+                Some(ref replacement) => format!(
+                    "Model {}:{} has been retired; use {} instead",
+                    provider, model, replacement
+                ),
+                None => format!("Model {}:{} has been retired", provider, model),
+            })),
+        }
+    }
+
+    pub fn capabilities(&self, provider: &str, model: &str) -> Option<&ModelCapabilities> {
+        self.lookup(provider, model)?.capabilities.as_ref()
+    }
+
+    /// Reject requests asking for more output than the model can produce, or
+    /// for streaming from a model that cannot stream
+    ///
+    /// Models without published capabilities are not checked.
+    pub fn check_request(
+        &self,
+        provider: &str,
+        model: &str,
+        max_tokens: Option<i32>,
+        stream: bool,
+    ) -> Result<()> {
+        let Some(caps) = self.capabilities(provider, model) else {
+            return Ok(());
+        };
+
+        if let Some(max_tokens) = max_tokens {
+            if max_tokens < 1 || max_tokens as u32 > caps.max_output {
+                return Err(AppError::BadRequest(format!(
+                    "max_tokens {} is outside 1..={} for model {}:{}",
+                    max_tokens, caps.max_output, provider, model
+                )));
+            }
+        }
+
+        if stream && !caps.streaming {
+            return Err(AppError::BadRequest(format!(
+                "Model {}:{} does not support streaming",
+                provider, model
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deprecated_model_produces_warning() {
+        let catalog = ModelCatalog::builtin();
+
+        let notice = catalog.check_model("anthropic", "claude-3-sonnet").unwrap().unwrap();
+        assert_eq!(notice.replacement.as_deref(), Some("claude-3-5-sonnet"));
+        assert_eq!(
+            notice.warning_header(),
+            "299 - \"Model anthropic:claude-3-sonnet is deprecated; use claude-3-5-sonnet\""
+        );
+
+        assert!(catalog.check_model("anthropic", "claude-3-opus").unwrap().is_none());
+        assert!(catalog.check_model("custom", "in-house-model").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_retired_model_is_rejected() {
+        let catalog = ModelCatalog::builtin();
+
+        match catalog.check_model("google", "gemini-pro-vision") {
+            Err(AppError::BadRequest(message)) => assert!(message.contains("use gemini-pro")),
+            other => panic!("expected retired model to be rejected, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_catalog_from_json() {
+        let catalog: ModelCatalog = serde_json::from_str(
+            r#"{"providers": [{"name": "openai", "models": [
+                {"name": "gpt-4"},
+                {"name": "gpt-3.5-turbo", "status": "retired", "replacement": "gpt-4"}
+            ]}]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(catalog.lookup("openai", "gpt-4").unwrap().status, ModelStatus::Active);
+        assert!(catalog.check_model("openai", "gpt-3.5-turbo").is_err());
+        assert!(catalog.capabilities("openai", "gpt-4").is_none());
+    }
+
+    #[test]
+    fn test_capability_lookup() {
+        let catalog = ModelCatalog::builtin();
+
+        let turbo = catalog.capabilities("openai", "gpt-4-turbo").unwrap();
+        assert_eq!((turbo.context_tokens, turbo.max_output), (128_000, 4_096));
+        assert!(turbo.supports_vision());
+        assert!(turbo.streaming);
+
+        assert!(!catalog.capabilities("openai", "gpt-3.5-turbo").unwrap().supports_vision());
+        assert!(catalog.capabilities("custom", "in-house-model").is_none());
+
+        let from_json: ModelCapabilities =
+            serde_json::from_str(r#"{"context_tokens": 4096, "max_output": 1024}"#).unwrap();
+        assert_eq!(from_json.modalities, vec!["text"]);
+        assert!(!from_json.streaming);
+    }
+
+    #[test]
+    fn test_max_tokens_above_model_limit_is_rejected() {
+        let catalog = ModelCatalog::builtin();
+
+        assert!(catalog.check_request("anthropic", "claude-3-haiku", Some(4_096), true).is_ok());
+        assert!(catalog.check_request("anthropic", "claude-3-haiku", None, false).is_ok());
+
+        match catalog.check_request("anthropic", "claude-3-haiku", Some(8_000), false) {
+            Err(AppError::BadRequest(message)) => assert!(message.contains("1..=4096")),
+            other => panic!("expected max_tokens to be rejected, got {:?}", other),
+        }
+
+        // Unknown models carry no limits to enforce
+        assert!(catalog.check_request("custom", "in-house-model", Some(100_000), true).is_ok());
+    }
+}
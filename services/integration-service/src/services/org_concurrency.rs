@@ -0,0 +1,122 @@
+//! Per-organization limit on in-flight proxy requests
+//!
+//! Each organization gets a semaphore of `max_in_flight` permits. A request
+//! that finds none free is rejected with `429` rather than queued, so one busy
+//! organization cannot hold gateway workers that other organizations need.
+//! Permits are released when the guard is dropped, whichever way the request
+//! ends.
+//!
+//! Organizations are those resolved from the caller's memberships, never a
+//! client-supplied header; a request governed by several organizations takes
+//! a permit in each.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use uuid::Uuid;
+
+use llm_governance_common::{AppError, Result};
+
+pub struct OrgConcurrencyLimiter {
+    max_in_flight: usize,
+    semaphores: Mutex<HashMap<Uuid, Arc<Semaphore>>>,
+}
+
+impl OrgConcurrencyLimiter {
+    /// `max_in_flight` of `0` disables the limit
+    pub fn new(max_in_flight: usize) -> Self {
+        Self {
+            max_in_flight,
+            semaphores: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Take one permit in each organization, held until the guards are dropped
+    ///
+    /// All or nothing: if any organization is full, permits already taken are
+    /// released and the request is rejected. Requests without an organization
+    /// are not limited.
+    pub fn try_acquire(&self, organizations: &[Uuid]) -> Result<Vec<OwnedSemaphorePermit>> {
+        if self.max_in_flight == 0 {
+            return Ok(Vec::new());
+        }
+
+        organizations
+            .iter()
+            .map(|&organization_id| self.acquire_one(organization_id))
+            .collect()
+    }
+
+    fn acquire_one(&self, organization_id: Uuid) -> Result<OwnedSemaphorePermit> {
+        let semaphore = {
+            let mut semaphores = self
+                .semaphores
+                .lock()
+                .map_err(|_| AppError::Internal("Concurrency limiter poisoned".to_string()))?;
+            // Outstanding permits hold a reference; drop organizations with none
+            semaphores.retain(|_, s| Arc::strong_count(s) > 1);
+            semaphores
+                .entry(organization_id)
+                .or_insert_with(|| Arc::new(Semaphore::new(self.max_in_flight)))
+                .clone()
+        };
+
+        semaphore.try_acquire_owned().map_err(|_| {
+            AppError::TooManyRequests(format!(
+                "organization {} has {} requests in flight; retry shortly",
+                organization_id, self.max_in_flight
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extra_request_rejected_without_affecting_other_orgs() {
+        let limiter = OrgConcurrencyLimiter::new(2);
+        let busy = Uuid::new_v4();
+        let other = Uuid::new_v4();
+
+        let first = limiter.try_acquire(&[busy]).unwrap();
+        let second = limiter.try_acquire(&[busy]).unwrap();
+        assert!(first.len() == 1 && second.len() == 1);
+
+        let err = limiter.try_acquire(&[busy]).unwrap_err();
+        assert!(matches!(err, AppError::TooManyRequests(_)));
+        assert_eq!(limiter.try_acquire(&[other]).unwrap().len(), 1);
+
+        // Finishing a request, successfully or not, frees its slot
+        drop(first);
+        assert_eq!(limiter.try_acquire(&[busy]).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_multi_org_request_needs_a_permit_in_every_org() {
+        let limiter = OrgConcurrencyLimiter::new(1);
+        let busy = Uuid::new_v4();
+        let idle = Uuid::new_v4();
+
+        let held = limiter.try_acquire(&[busy]).unwrap();
+        let err = limiter.try_acquire(&[idle, busy]).unwrap_err();
+        assert!(matches!(err, AppError::TooManyRequests(_)));
+
+        // The idle organization's permit was given back on rejection
+        assert_eq!(limiter.try_acquire(&[idle]).unwrap().len(), 1);
+        drop(held);
+        assert_eq!(limiter.try_acquire(&[idle, busy]).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_unlimited_without_org_or_limit() {
+        let limiter = OrgConcurrencyLimiter::new(1);
+        assert!(limiter.try_acquire(&[]).unwrap().is_empty());
+
+        let disabled = OrgConcurrencyLimiter::new(0);
+        let org = Uuid::new_v4();
+        assert!(disabled.try_acquire(&[org]).unwrap().is_empty());
+        assert!(disabled.try_acquire(&[org]).unwrap().is_empty());
+    }
+}
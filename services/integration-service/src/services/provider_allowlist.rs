@@ -0,0 +1,100 @@
+//! Per-organization provider allowlists
+//!
+//! An organization restricts its members to the providers listed in
+//! `settings.allowed_providers`. A missing or empty list allows every
+//! provider, so organizations that never set it are unaffected.
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use llm_governance_common::Result;
+
+/// Allowed providers of one organization; empty means unrestricted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProviderAllowlist {
+    pub organization_id: Uuid,
+    pub allowed_providers: Vec<String>,
+}
+
+impl ProviderAllowlist {
+    pub fn allows(&self, provider: &str) -> bool {
+        self.allowed_providers.is_empty()
+            || self.allowed_providers.iter().any(|p| p.eq_ignore_ascii_case(provider))
+    }
+}
+
+/// First organization whose allowlist excludes `provider`
+pub fn blocking_organization(allowlists: &[ProviderAllowlist], provider: &str) -> Option<Uuid> {
+    allowlists
+        .iter()
+        .find(|list| !list.allows(provider))
+        .map(|list| list.organization_id)
+}
+
+/// Allowlists of the organizations governing a request
+///
+/// `organizations` comes from `request_scope::request_organizations`, which
+/// has already validated any team the caller claimed.
+pub async fn organization_allowlists(pool: &PgPool, organizations: &[Uuid]) -> Result<Vec<ProviderAllowlist>> {
+    if organizations.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let rows: Vec<(Uuid, serde_json::Value)> = sqlx::query_as(
+        r#"
+        SELECT o.id, COALESCE(o.settings->'allowed_providers', '[]'::jsonb)
+        FROM organizations o
+        WHERE o.id = ANY($1)
+        "#,
+    )
+    .bind(organizations)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(organization_id, allowed)| ProviderAllowlist {
+            organization_id,
+            // Settings are validated on write; anything else is treated as unset
+            allowed_providers: serde_json::from_value(allowed).unwrap_or_default(),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn allowlist(providers: &[&str]) -> ProviderAllowlist {
+        ProviderAllowlist {
+            organization_id: Uuid::new_v4(),
+            allowed_providers: providers.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_allowed_provider_passes() {
+        let restricted = allowlist(&["openai", "Anthropic"]);
+
+        assert!(restricted.allows("openai"));
+        assert!(restricted.allows("anthropic"));
+        assert_eq!(blocking_organization(&[restricted], "anthropic"), None);
+    }
+
+    #[test]
+    fn test_blocked_provider_names_the_organization() {
+        let open = allowlist(&[]);
+        let restricted = allowlist(&["azure"]);
+
+        assert!(open.allows("openai"));
+        assert_eq!(
+            blocking_organization(&[open.clone(), restricted.clone()], "openai"),
+            Some(restricted.organization_id)
+        );
+        assert_eq!(blocking_organization(&[open.clone(), restricted], "azure"), None);
+
+        // No organization, or none with a list, means allow-all
+        assert_eq!(blocking_organization(&[], "bedrock"), None);
+        assert_eq!(blocking_organization(&[open], "bedrock"), None);
+    }
+}
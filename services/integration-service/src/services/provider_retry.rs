@@ -0,0 +1,324 @@
+//! Bounded retries for outgoing provider calls
+//!
+//! Connection errors, timeouts, `429` and `5xx` responses are retried with
+//! exponential backoff and full jitter. A `Retry-After` header from the
+//! provider takes precedence over the computed delay; if it asks us to wait
+//! longer than `max_delay_ms` the response is returned instead of blocking the
+//! caller. Other `4xx` responses are never retried.
+//!
+//! Each attempt is bounded by the provider's request timeout; a call that
+//! still fails on timeout surfaces as `AppError::UpstreamUnavailable`.
+//!
+//! Error responses are mapped by status: `400`, `401`, `403`, `404` and `422`
+//! become `AppError::UpstreamRejected` carrying the provider's error code,
+//! `429` and `5xx` become `AppError::UpstreamUnavailable`.
+
+use chrono::{DateTime, Utc};
+use reqwest::{RequestBuilder, Response, StatusCode};
+use std::time::Duration;
+use uuid::Uuid;
+
+use llm_governance_common::utils::backoff_delay;
+use llm_governance_common::AppError;
+
+use crate::config::Config;
+
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total attempts including the first one; `1` disables retries
+    pub max_attempts: u32,
+    /// Backoff ceiling for the first retry in milliseconds
+    pub initial_delay_ms: u64,
+    /// Upper bound for any single wait in milliseconds
+    pub max_delay_ms: u64,
+}
+
+impl RetryPolicy {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            max_attempts: config.provider_max_attempts.max(1),
+            initial_delay_ms: config.provider_retry_initial_delay_ms,
+            max_delay_ms: config.provider_retry_max_delay_ms,
+        }
+    }
+
+    /// Backoff window before jitter: `initial_delay_ms * 2^retry`, capped
+    fn backoff_ceiling(&self, retry: u32) -> Duration {
+        Duration::from_millis(backoff_delay(self.initial_delay_ms, 2.0, retry, self.max_delay_ms))
+    }
+
+    /// Full jitter: uniformly random in `[0, backoff_ceiling(retry)]`
+    pub fn backoff_delay(&self, retry: u32) -> Duration {
+        self.backoff_ceiling(retry).mul_f64(jitter_fraction())
+    }
+}
+
+/// Uniform value in `[0, 1)` from the random bits of a v4 UUID
+fn jitter_fraction() -> f64 {
+    let bits = (Uuid::new_v4().as_u128() & ((1u128 << 53) - 1)) as u64;
+    bits as f64 / (1u64 << 53) as f64
+}
+
+fn is_retryable_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// Wait requested by a `Retry-After` header, in delta-seconds or HTTP-date form
+fn retry_after(response: &Response) -> Option<Duration> {
+    let value = response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let at = DateTime::parse_from_rfc2822(value).ok()?.with_timezone(&Utc);
+    Some((at - Utc::now()).to_std().unwrap_or(Duration::ZERO))
+}
+
+/// Map a transport error from a provider call, treating timeouts as transient
+pub fn provider_error(provider: &str, e: reqwest::Error) -> AppError {
+    if e.is_timeout() {
+        AppError::UpstreamUnavailable(format!("{} request timed out", provider))
+    } else {
+        AppError::Internal(format!("{} API error: {}", provider, e))
+    }
+}
+
+/// Provider error code and message from an error body
+///
+/// Understands the `{"error": {"code"|"type", "message"}}` shape used by
+/// OpenAI and Anthropic; anything else is passed through as the message.
+fn parse_error_body(body: &str) -> (Option<String>, String) {
+    let error = serde_json::from_str::<serde_json::Value>(body)
+        .ok()
+        .and_then(|v| v.get("error").cloned());
+
+    let Some(error) = error else {
+        return (None, body.trim().to_string());
+    };
+
+    let code = ["code", "type"]
+        .iter()
+        .find_map(|key| error.get(*key).and_then(|c| c.as_str()))
+        .map(str::to_string);
+    let message = error
+        .get("message")
+        .and_then(|m| m.as_str())
+        .map(str::to_string)
+        .unwrap_or_else(|| error.to_string());
+
+    (code, message)
+}
+
+/// Map a provider's non-success response to an error
+pub fn provider_status_error(provider: &str, status: StatusCode, body: &str) -> AppError {
+    let (code, message) = parse_error_body(body);
+
+    match status.as_u16() {
+        400 | 401 | 403 | 404 | 422 => AppError::UpstreamRejected {
+            status: status.as_u16(),
+            code: code.unwrap_or_else(|| "unknown".to_string()),
+            message: format!("{}: {}", provider, message),
+        },
+        s if is_retryable_status(status) => AppError::UpstreamUnavailable(format!(
+            "{} returned {}: {}",
+            provider,
+            s,
+            code.unwrap_or(message)
+        )),
+        s => AppError::Internal(format!("{} API error ({}): {}", provider, s, message)),
+    }
+}
+
+/// Consume a non-success response and map it with `provider_status_error`
+pub async fn response_error(provider: &str, response: Response) -> AppError {
+    let status = response.status();
+    let body = response.text().await.unwrap_or_default();
+    provider_status_error(provider, status, &body)
+}
+
+/// Send the request built by `build`, retrying transient failures
+///
+/// The final response is returned as-is, including retryable statuses once
+/// attempts are exhausted, so callers keep their own error mapping.
+pub async fn send_with_retry<F>(
+    policy: &RetryPolicy,
+    provider: &str,
+    build: F,
+) -> reqwest::Result<Response>
+where
+    F: Fn() -> RequestBuilder,
+{
+    let mut attempt = 1;
+
+    loop {
+        let outcome = build().send().await;
+
+        let delay = match &outcome {
+            Ok(response) if is_retryable_status(response.status()) => match retry_after(response) {
+                Some(wait) if wait > Duration::from_millis(policy.max_delay_ms) => None,
+                Some(wait) => Some(wait),
+                None => Some(policy.backoff_delay(attempt - 1)),
+            },
+            Err(e) if e.is_connect() || e.is_timeout() => Some(policy.backoff_delay(attempt - 1)),
+            _ => None,
+        };
+
+        match delay {
+            Some(delay) if attempt < policy.max_attempts => {
+                tracing::warn!(
+                    "{} attempt {}/{} failed, retrying in {:?}",
+                    provider,
+                    attempt,
+                    policy.max_attempts,
+                    delay
+                );
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            _ => return outcome,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use llm_governance_common::testing::{MockResponse, MockServer};
+
+    fn provider_reply(status: u16) -> MockResponse {
+        MockResponse::new(status).with_body("{}")
+    }
+
+    fn fast_policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_delay_ms: 1,
+            max_delay_ms: 1000,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retries_503_then_succeeds() {
+        let server = MockServer::start(vec![provider_reply(503), provider_reply(200)]).await;
+        let client = reqwest::Client::new();
+
+        let response = send_with_retry(&fast_policy(), "mock", || client.post(server.url()))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(server.hits(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_client_errors_and_long_retry_after_are_not_retried() {
+        let server = MockServer::start(vec![provider_reply(400)]).await;
+        let client = reqwest::Client::new();
+        let response = send_with_retry(&fast_policy(), "mock", || client.post(server.url()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(server.hits(), 1);
+
+        let server = MockServer::start(vec![
+            provider_reply(429).with_header("retry-after", "3600"),
+            provider_reply(200),
+        ])
+        .await;
+        let response = send_with_retry(&fast_policy(), "mock", || client.post(server.url()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(server.hits(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_attempts_are_bounded() {
+        let server = MockServer::start(vec![provider_reply(502).with_header("retry-after", "0")]).await;
+        let client = reqwest::Client::new();
+
+        let response = send_with_retry(&fast_policy(), "mock", || client.post(server.url()))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_GATEWAY);
+        assert_eq!(server.hits(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_slow_provider_times_out_as_upstream_unavailable() {
+        let server = MockServer::start_with_delay(vec![provider_reply(200)], Duration::from_millis(500)).await;
+        let client = reqwest::Client::new();
+        let policy = RetryPolicy {
+            max_attempts: 2,
+            ..fast_policy()
+        };
+
+        let err = send_with_retry(&policy, "mock", || {
+            client.post(server.url()).timeout(Duration::from_millis(50))
+        })
+        .await
+        .map_err(|e| provider_error("Mock", e))
+        .unwrap_err();
+
+        assert!(matches!(err, AppError::UpstreamUnavailable(_)), "got {:?}", err);
+        assert_eq!(server.hits(), 2, "timeouts are retried as transient");
+    }
+
+    #[test]
+    fn test_provider_400_and_429_map_to_distinct_variants() {
+        let body = r#"{"error": {"message": "Invalid 'messages': empty array", "type": "invalid_request_error", "code": "invalid_messages"}}"#;
+        match provider_status_error("OpenAI", StatusCode::BAD_REQUEST, body) {
+            AppError::UpstreamRejected { status, code, message } => {
+                assert_eq!(status, 400);
+                assert_eq!(code, "invalid_messages");
+                assert!(message.contains("empty array"));
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+
+        let body = r#"{"type": "error", "error": {"type": "rate_limit_error", "message": "slow down"}}"#;
+        let err = provider_status_error("Anthropic", StatusCode::TOO_MANY_REQUESTS, body);
+        assert!(matches!(err, AppError::UpstreamUnavailable(ref msg) if msg.contains("rate_limit_error")));
+    }
+
+    #[test]
+    fn test_rejections_keep_client_status_except_auth() {
+        use actix_web::ResponseError;
+
+        let err = provider_status_error("Anthropic", StatusCode::UNPROCESSABLE_ENTITY, "not json");
+        assert_eq!(err.status_code().as_u16(), 422);
+        assert!(err.to_string().contains("unknown"));
+
+        let err = provider_status_error("OpenAI", StatusCode::UNAUTHORIZED, "{}");
+        assert!(matches!(err, AppError::UpstreamRejected { status: 401, .. }));
+        assert_eq!(err.status_code().as_u16(), 502);
+
+        let err = provider_status_error("OpenAI", StatusCode::BAD_GATEWAY, "");
+        assert_eq!(err.status_code().as_u16(), 503);
+    }
+
+    #[test]
+    fn test_backoff_delay_is_jittered_within_ceiling() {
+        let policy = RetryPolicy {
+            max_attempts: 5,
+            initial_delay_ms: 100,
+            max_delay_ms: 1000,
+        };
+
+        assert_eq!(policy.backoff_ceiling(0), Duration::from_millis(100));
+        assert_eq!(policy.backoff_ceiling(2), Duration::from_millis(400));
+        assert_eq!(policy.backoff_ceiling(10), Duration::from_millis(1000));
+        assert_eq!(policy.backoff_ceiling(80), Duration::from_millis(1000));
+
+        for retry in 0..5 {
+            assert!(policy.backoff_delay(retry) <= policy.backoff_ceiling(retry));
+        }
+    }
+}
@@ -0,0 +1,82 @@
+//! Organizations a proxied request acts for
+//!
+//! Provider allowlists, system prompts and concurrency limits all apply per
+//! organization. `X-Team-Id` is client-supplied, so it only selects the
+//! team's organization when the caller is a member of that team; any other
+//! claim is audited and rejected rather than falling back to no scope.
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use llm_governance_common::{AppError, Result};
+
+/// Organizations governing a request
+///
+/// The team's organization when a team is given, otherwise every organization
+/// the caller belongs to (ordered by id), so a caller in a restricted
+/// organization cannot sidestep it by omitting the team. Anonymous requests
+/// without a team have no organization.
+pub async fn request_organizations(
+    pool: &PgPool,
+    user_id: Option<Uuid>,
+    team_id: Option<Uuid>,
+) -> Result<Vec<Uuid>> {
+    match (user_id, team_id) {
+        (_, Some(team_id)) => team_organization(pool, user_id, team_id).await.map(|org| vec![org]),
+        (Some(user_id), None) => {
+            let orgs: Vec<(Uuid,)> = sqlx::query_as(
+                "SELECT organization_id FROM organization_members WHERE user_id = $1 ORDER BY organization_id",
+            )
+            .bind(user_id)
+            .fetch_all(pool)
+            .await?;
+            Ok(orgs.into_iter().map(|(org,)| org).collect())
+        }
+        (None, None) => Ok(Vec::new()),
+    }
+}
+
+/// Organization of a team the caller belongs to
+async fn team_organization(pool: &PgPool, user_id: Option<Uuid>, team_id: Uuid) -> Result<Uuid> {
+    let organization_id: Option<Uuid> = match user_id {
+        Some(user_id) => sqlx::query_scalar(
+            r#"
+            SELECT t.organization_id
+            FROM teams t
+            JOIN team_members tm ON tm.team_id = t.id
+            WHERE t.id = $1 AND tm.user_id = $2 AND t.organization_id IS NOT NULL
+            "#,
+        )
+        .bind(team_id)
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await?,
+        None => None,
+    };
+
+    match organization_id {
+        Some(organization_id) => Ok(organization_id),
+        None => {
+            record_denied_team(pool, user_id, team_id).await?;
+            Err(AppError::Forbidden)
+        }
+    }
+}
+
+async fn record_denied_team(pool: &PgPool, user_id: Option<Uuid>, team_id: Uuid) -> Result<()> {
+    tracing::warn!("Rejected claim on team {} by non-member {:?}", team_id, user_id);
+
+    sqlx::query(
+        r#"
+        INSERT INTO audit_logs (user_id, action, resource_type, resource_id, details, checksum)
+        VALUES ($1, 'team_scope_denied', 'team', $2, $3, '')
+        "#,
+    )
+    .bind(user_id)
+    .bind(team_id.to_string())
+    .bind(serde_json::json!({ "team_id": team_id }))
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,176 @@
+//! Cache of provider responses to deterministic proxy requests
+//!
+//! A request sent with `temperature` 0 (or none) gets the same completion each
+//! time, so its response can be replayed instead of paying the provider again.
+//! Entries are keyed by a SHA-256 of the caller's organizations, provider,
+//! model, messages and `max_tokens`, so one tenant never receives another's
+//! completion, and expire after the configured TTL. The cache is best
+//! effort: a store that cannot be reached is logged and the request goes to
+//! the provider as usual.
+
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::warn;
+use uuid::Uuid;
+
+use llm_governance_common::{AppError, Result};
+
+use crate::config::Config;
+
+const KEY_PREFIX: &str = "proxy_response:";
+
+/// Key-value store holding serialized responses
+#[async_trait]
+pub trait CacheStore: Send + Sync {
+    async fn get(&self, key: &str) -> Result<Option<String>>;
+    async fn set(&self, key: &str, value: &str, ttl: Duration) -> Result<()>;
+}
+
+pub struct RedisCacheStore {
+    client: redis::Client,
+}
+
+impl RedisCacheStore {
+    pub fn new(client: redis::Client) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl CacheStore for RedisCacheStore {
+    async fn get(&self, key: &str) -> Result<Option<String>> {
+        let mut conn = self
+            .client
+            .get_multiplexed_tokio_connection()
+            .await
+            .map_err(AppError::Redis)?;
+
+        redis::cmd("GET")
+            .arg(key)
+            .query_async(&mut conn)
+            .await
+            .map_err(AppError::Redis)
+    }
+
+    async fn set(&self, key: &str, value: &str, ttl: Duration) -> Result<()> {
+        let mut conn = self
+            .client
+            .get_multiplexed_tokio_connection()
+            .await
+            .map_err(AppError::Redis)?;
+
+        redis::cmd("SET")
+            .arg(key)
+            .arg(value)
+            .arg("EX")
+            .arg(ttl.as_secs().max(1))
+            .query_async::<_, ()>(&mut conn)
+            .await
+            .map_err(AppError::Redis)
+    }
+}
+
+pub struct ResponseCache {
+    store: Option<Arc<dyn CacheStore>>,
+    ttl: Duration,
+}
+
+impl ResponseCache {
+    /// `store` of `None` disables the cache
+    pub fn new(store: Option<Arc<dyn CacheStore>>, ttl: Duration) -> Self {
+        Self { store, ttl }
+    }
+
+    pub fn from_config(config: &Config, client: redis::Client) -> Self {
+        let store = config
+            .response_cache_enabled
+            .then(|| Arc::new(RedisCacheStore::new(client)) as Arc<dyn CacheStore>);
+        Self::new(store, Duration::from_secs(config.response_cache_ttl_secs))
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.store.is_some()
+    }
+
+    /// Cached value under `key`, or `None` on a miss or a store error
+    pub async fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let store = self.store.as_ref()?;
+        match store.get(key).await {
+            Ok(value) => value.and_then(|v| serde_json::from_str(&v).ok()),
+            Err(e) => {
+                warn!(error = %e, "Response cache lookup failed");
+                None
+            }
+        }
+    }
+
+    /// Store `value` under `key` for the configured TTL
+    pub async fn put<T: Serialize>(&self, key: &str, value: &T) {
+        let Some(store) = self.store.as_ref() else {
+            return;
+        };
+        let Ok(value) = serde_json::to_string(value) else {
+            return;
+        };
+        if let Err(e) = store.set(key, &value, self.ttl).await {
+            warn!(error = %e, "Response cache write failed");
+        }
+    }
+}
+
+/// Whether a request with this `temperature` always gets the same completion
+pub fn is_deterministic(temperature: Option<f32>) -> bool {
+    temperature.is_none_or(|t| t == 0.0)
+}
+
+/// Cache key of a request: SHA-256 of its organizations, provider, model,
+/// messages and `max_tokens`
+pub fn cache_key<M: Serialize>(
+    organizations: &[Uuid],
+    provider: &str,
+    model: &str,
+    messages: &M,
+    max_tokens: Option<i32>,
+) -> String {
+    // Hashing a JSON array keeps field boundaries unambiguous
+    let material = serde_json::json!([organizations, provider, model, messages, max_tokens]);
+    format!("{}{}", KEY_PREFIX, hex::encode(Sha256::digest(material.to_string())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_zero_or_absent_temperature_is_deterministic() {
+        assert!(is_deterministic(None));
+        assert!(is_deterministic(Some(0.0)));
+        assert!(!is_deterministic(Some(0.7)));
+        assert!(!is_deterministic(Some(1.0)));
+    }
+
+    #[test]
+    fn test_cache_key_covers_every_request_field() {
+        let orgs = [Uuid::new_v4()];
+        let messages = vec![("user", "hello")];
+        let key = cache_key(&orgs, "openai", "gpt-4", &messages, Some(100));
+
+        assert!(key.starts_with(KEY_PREFIX));
+        assert_eq!(key, cache_key(&orgs, "openai", "gpt-4", &messages, Some(100)));
+        assert_ne!(key, cache_key(&orgs, "anthropic", "gpt-4", &messages, Some(100)));
+        assert_ne!(key, cache_key(&orgs, "openai", "gpt-4o", &messages, Some(100)));
+        assert_ne!(key, cache_key(&orgs, "openai", "gpt-4", &vec![("user", "hello!")], Some(100)));
+        assert_ne!(key, cache_key(&orgs, "openai", "gpt-4", &messages, None));
+        assert_ne!(key, cache_key(&[Uuid::new_v4()], "openai", "gpt-4", &messages, Some(100)));
+        assert_ne!(key, cache_key(&[], "openai", "gpt-4", &messages, Some(100)));
+        // Concatenated fields must not collide across boundaries
+        assert_ne!(
+            cache_key(&orgs, "open", "aigpt-4", &messages, Some(100)),
+            cache_key(&orgs, "openai", "gpt-4", &messages, Some(100))
+        );
+    }
+}
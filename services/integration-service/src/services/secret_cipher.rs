@@ -0,0 +1,106 @@
+//! Encryption at rest for secrets the service must read back, such as
+//! webhook signing secrets
+//!
+//! Values are sealed with AES-256-GCM under a key from configuration and
+//! stored as `v1:<base64(nonce || ciphertext)>`.
+
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+
+use llm_governance_common::{AppError, Result};
+
+const PREFIX: &str = "v1:";
+const NONCE_LEN: usize = 12;
+
+#[derive(Clone)]
+pub struct SecretCipher {
+    cipher: Aes256Gcm,
+}
+
+impl SecretCipher {
+    /// Key given as base64 of exactly 32 bytes
+    pub fn from_base64(key: &str) -> Result<Self> {
+        let bytes = STANDARD
+            .decode(key.trim())
+            .map_err(|e| AppError::Validation(format!("secret key is not valid base64: {}", e)))?;
+        if bytes.len() != 32 {
+            return Err(AppError::Validation(format!(
+                "secret key must be 32 bytes, got {}",
+                bytes.len()
+            )));
+        }
+
+        Ok(Self {
+            cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&bytes)),
+        })
+    }
+
+    pub fn encrypt(&self, plaintext: &str) -> Result<String> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, plaintext.as_bytes())
+            .map_err(|_| AppError::Internal("Failed to encrypt secret".to_string()))?;
+
+        let mut sealed = nonce.to_vec();
+        sealed.extend_from_slice(&ciphertext);
+        Ok(format!("{}{}", PREFIX, STANDARD.encode(sealed)))
+    }
+
+    pub fn decrypt(&self, stored: &str) -> Result<String> {
+        let unreadable = || AppError::Internal("Stored secret could not be decrypted".to_string());
+
+        let sealed = stored
+            .strip_prefix(PREFIX)
+            .and_then(|encoded| STANDARD.decode(encoded).ok())
+            .filter(|sealed| sealed.len() > NONCE_LEN)
+            .ok_or_else(unreadable)?;
+        let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+        let plaintext = self
+            .cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| unreadable())?;
+
+        String::from_utf8(plaintext).map_err(|_| unreadable())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "MDEyMzQ1Njc4OWFiY2RlZjAxMjM0NTY3ODlhYmNkZWY=";
+
+    #[test]
+    fn test_round_trip_uses_fresh_nonces() {
+        let cipher = SecretCipher::from_base64(KEY).unwrap();
+
+        let first = cipher.encrypt("whsec_test").unwrap();
+        let second = cipher.encrypt("whsec_test").unwrap();
+        assert!(first.starts_with("v1:"));
+        assert_ne!(first, second);
+        assert!(!first.contains("whsec_test"));
+
+        assert_eq!(cipher.decrypt(&first).unwrap(), "whsec_test");
+        assert_eq!(cipher.decrypt(&second).unwrap(), "whsec_test");
+    }
+
+    #[test]
+    fn test_plaintext_and_foreign_ciphertexts_are_rejected() {
+        let cipher = SecretCipher::from_base64(KEY).unwrap();
+        let other = SecretCipher::from_base64("ZmVkY2JhOTg3NjU0MzIxMGZlZGNiYTk4NzY1NDMyMTA=").unwrap();
+
+        assert!(cipher.decrypt("whsec_plaintext").is_err());
+        assert!(cipher.decrypt("v1:AAAA").is_err());
+        assert!(cipher.decrypt(&other.encrypt("whsec_test").unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_key_must_be_32_bytes() {
+        assert!(SecretCipher::from_base64("").is_err());
+        assert!(SecretCipher::from_base64("c2hvcnQ=").is_err());
+        assert!(SecretCipher::from_base64("not base64!").is_err());
+    }
+}
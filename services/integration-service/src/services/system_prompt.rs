@@ -0,0 +1,63 @@
+//! Organization-mandated system prompts
+//!
+//! An organization can set `settings.system_prompt`, e.g. safety instructions,
+//! to be sent with every request its members proxy. The proxy injects it as
+//! the leading system message unless the caller supplied one of their own.
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use llm_governance_common::Result;
+
+/// System prompt configured by one organization
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrgSystemPrompt {
+    pub organization_id: Uuid,
+    pub prompt: String,
+}
+
+/// System prompts of the organizations governing a request
+///
+/// Takes the organizations resolved by `request_scope::request_organizations`,
+/// like provider allowlists, ordered by id so the combined prompt is stable.
+pub async fn organization_system_prompts(pool: &PgPool, organizations: &[Uuid]) -> Result<Vec<OrgSystemPrompt>> {
+    if organizations.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let rows: Vec<(Uuid, String)> = sqlx::query_as(
+        r#"
+        SELECT o.id, o.settings->>'system_prompt'
+        FROM organizations o
+        WHERE o.id = ANY($1)
+        AND COALESCE(TRIM(o.settings->>'system_prompt'), '') <> ''
+        ORDER BY o.id
+        "#,
+    )
+    .bind(organizations)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(organization_id, prompt)| OrgSystemPrompt { organization_id, prompt })
+        .collect())
+}
+
+/// Single system message combining every applicable prompt
+pub fn combined_prompt(prompts: &[OrgSystemPrompt]) -> Option<String> {
+    if prompts.is_empty() {
+        return None;
+    }
+    let parts: Vec<&str> = prompts.iter().map(|p| p.prompt.trim()).collect();
+    Some(parts.join("\n\n"))
+}
+
+/// Rough prompt-token estimate (about four characters per token)
+///
+/// Billing uses the provider's reported usage, which already includes the
+/// injected prompt; this is only for recording the guardrail's share of it.
+pub fn estimate_tokens(text: &str) -> i32 {
+    let chars = text.chars().count();
+    chars.div_ceil(4) as i32
+}
@@ -0,0 +1,586 @@
+//! Outgoing webhook deliveries with retries and dead letters
+//!
+//! Every event sent to a subscription is stored in `webhook_deliveries` before
+//! the first attempt, and the retry worker owns every attempt after that. A
+//! failed POST is rescheduled with exponential backoff; once `max_attempts` is
+//! reached the delivery is marked `dead_lettered` and kept for inspection.
+//!
+//! Each attempt is signed with HMAC-SHA256 over `"{timestamp}.{body}"` using
+//! the subscription secret. The timestamp is taken per attempt, so receivers
+//! can reject replays of an old signature without rejecting our retries.
+//! Secrets are stored encrypted and only decrypted by the worker.
+//!
+//! Subscription URLs must be `https` and resolve to public addresses only;
+//! deliveries do not follow redirects.
+
+use chrono::{DateTime, Duration, Utc};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use sqlx::PgPool;
+use std::net::IpAddr;
+use uuid::Uuid;
+
+use llm_governance_common::scheduler::Scheduler;
+use llm_governance_common::utils::backoff_delay;
+use llm_governance_common::{AppError, Result};
+
+use crate::config::Config;
+use crate::services::secret_cipher::SecretCipher;
+
+pub const SIGNATURE_HEADER: &str = "X-Webhook-Signature";
+pub const TIMESTAMP_HEADER: &str = "X-Webhook-Timestamp";
+
+/// Sent once per budget period when a hard-limit budget is spent
+pub const BUDGET_HARD_LIMIT_REACHED: &str = "budget.hard_limit_reached";
+
+/// Event types a subscription may list; an empty list receives all of them
+pub const EVENT_TYPES: &[&str] = &[BUDGET_HARD_LIMIT_REACHED];
+
+/// Longest error text stored on a delivery row
+const MAX_ERROR_LEN: usize = 500;
+
+#[derive(Debug, Clone)]
+pub struct WebhookRetryPolicy {
+    /// Total attempts including the first before a delivery is dead-lettered
+    pub max_attempts: i32,
+    /// Wait before the first retry in seconds
+    pub initial_delay_secs: i64,
+    /// Upper bound for any single wait in seconds
+    pub max_delay_secs: i64,
+    /// Request timeout for each attempt
+    pub timeout: std::time::Duration,
+}
+
+impl WebhookRetryPolicy {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            max_attempts: config.webhook_max_attempts.max(1),
+            initial_delay_secs: config.webhook_retry_initial_delay_secs,
+            max_delay_secs: config.webhook_retry_max_delay_secs,
+            timeout: std::time::Duration::from_secs(config.webhook_timeout_secs.max(1)),
+        }
+    }
+
+    /// Wait after the `attempts`-th failure: `initial_delay_secs * 2^(attempts - 1)`, capped
+    pub fn retry_delay(&self, attempts: i32) -> Duration {
+        let secs = backoff_delay(
+            self.initial_delay_secs.max(0) as u64,
+            2.0,
+            (attempts - 1).max(0) as u32,
+            self.max_delay_secs.max(0) as u64,
+        );
+        Duration::seconds(secs as i64)
+    }
+
+    /// Next state of a delivery after its `attempts`-th attempt ended in `outcome`
+    pub fn transition(&self, attempts: i32, outcome: &AttemptOutcome, now: DateTime<Utc>) -> DeliveryTransition {
+        match outcome {
+            AttemptOutcome::Delivered { .. } => DeliveryTransition::Delivered,
+            AttemptOutcome::Failed { .. } if attempts >= self.max_attempts => DeliveryTransition::DeadLettered,
+            AttemptOutcome::Failed { .. } => DeliveryTransition::Retry {
+                next_retry_at: now + self.retry_delay(attempts),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AttemptOutcome {
+    Delivered { status_code: u16 },
+    /// Non-2xx response, or no response at all when `status_code` is `None`
+    Failed { status_code: Option<u16>, error: String },
+}
+
+impl AttemptOutcome {
+    fn status_code(&self) -> Option<u16> {
+        match self {
+            AttemptOutcome::Delivered { status_code } => Some(*status_code),
+            AttemptOutcome::Failed { status_code, .. } => *status_code,
+        }
+    }
+
+    fn error(&self) -> Option<&str> {
+        match self {
+            AttemptOutcome::Delivered { .. } => None,
+            AttemptOutcome::Failed { error, .. } => Some(error),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeliveryTransition {
+    Delivered,
+    Retry { next_retry_at: DateTime<Utc> },
+    DeadLettered,
+}
+
+impl DeliveryTransition {
+    pub fn status(&self) -> &'static str {
+        match self {
+            DeliveryTransition::Delivered => "delivered",
+            DeliveryTransition::Retry { .. } => "pending",
+            DeliveryTransition::DeadLettered => "dead_lettered",
+        }
+    }
+}
+
+/// A due delivery together with where and how to send it
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct PendingDelivery {
+    pub id: Uuid,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    /// Attempts already made
+    pub attempts: i32,
+    pub url: String,
+    /// Loaded encrypted; the worker decrypts it before the attempt
+    pub secret: String,
+}
+
+/// Hex HMAC-SHA256 of `"{timestamp}.{body}"`, prefixed with `sha256=`
+pub fn sign_payload(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// POST one attempt of `delivery`, signed at the time of sending
+pub async fn attempt_delivery(
+    client: &reqwest::Client,
+    policy: &WebhookRetryPolicy,
+    delivery: &PendingDelivery,
+) -> AttemptOutcome {
+    let body = delivery.payload.to_string();
+    let timestamp = Utc::now().timestamp();
+
+    let result = client
+        .post(&delivery.url)
+        .timeout(policy.timeout)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header("X-Webhook-Id", delivery.id.to_string())
+        .header("X-Webhook-Event", &delivery.event_type)
+        .header("X-Webhook-Attempt", (delivery.attempts + 1).to_string())
+        .header(TIMESTAMP_HEADER, timestamp.to_string())
+        .header(SIGNATURE_HEADER, sign_payload(&delivery.secret, timestamp, body.as_bytes()))
+        .body(body)
+        .send()
+        .await;
+
+    match result {
+        Ok(response) if response.status().is_success() => AttemptOutcome::Delivered {
+            status_code: response.status().as_u16(),
+        },
+        Ok(response) => AttemptOutcome::Failed {
+            status_code: Some(response.status().as_u16()),
+            error: format!("endpoint responded with {}", response.status()),
+        },
+        Err(e) => AttemptOutcome::Failed {
+            status_code: None,
+            error: e.to_string(),
+        },
+    }
+}
+
+/// Check that `url` is an `https` URL whose host resolves only to public
+/// addresses, so subscriptions cannot aim deliveries at internal services
+pub async fn validate_webhook_url(url: &str) -> Result<reqwest::Url> {
+    let parsed = reqwest::Url::parse(url)
+        .map_err(|e| AppError::Validation(format!("url is not a valid URL: {}", e)))?;
+    if parsed.scheme() != "https" {
+        return Err(AppError::Validation("url must use https".to_string()));
+    }
+    let host = parsed
+        .host_str()
+        .ok_or_else(|| AppError::Validation("url must include a host".to_string()))?;
+    let port = parsed.port_or_known_default().unwrap_or(443);
+
+    let addrs: Vec<IpAddr> = tokio::net::lookup_host((host.trim_matches(['[', ']']), port))
+        .await
+        .map_err(|_| AppError::Validation(format!("url host {} could not be resolved", host)))?
+        .map(|addr| addr.ip())
+        .collect();
+    if addrs.is_empty() || !addrs.iter().all(|ip| is_public_ip(*ip)) {
+        return Err(AppError::Validation(format!(
+            "url host {} resolves to a private or reserved address",
+            host
+        )));
+    }
+
+    Ok(parsed)
+}
+
+/// Whether `ip` is routable on the public internet
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => {
+            let [a, b, ..] = v4.octets();
+            !(v4.is_private()
+                || v4.is_loopback()
+                || v4.is_link_local()
+                || v4.is_unspecified()
+                || v4.is_broadcast()
+                || v4.is_documentation()
+                || v4.is_multicast()
+                || a == 0
+                // Carrier-grade NAT, 100.64.0.0/10
+                || (a == 100 && (64..128).contains(&b)))
+        }
+        IpAddr::V6(v6) => {
+            if let Some(v4) = v6.to_ipv4_mapped() {
+                return is_public_ip(IpAddr::V4(v4));
+            }
+            let first = v6.segments()[0];
+            !(v6.is_loopback()
+                || v6.is_unspecified()
+                || v6.is_multicast()
+                // Unique local fc00::/7 and link-local fe80::/10
+                || (first & 0xfe00) == 0xfc00
+                || (first & 0xffc0) == 0xfe80)
+        }
+    }
+}
+
+/// Queue `payload` for every active subscription of `organization_id` that
+/// receives `event_type`; returns how many deliveries were queued
+///
+/// A subscription is sent an identical payload only once, so callers on hot
+/// paths may publish the same event repeatedly.
+pub async fn publish_event(
+    pool: &PgPool,
+    organization_id: Uuid,
+    event_type: &str,
+    payload: &serde_json::Value,
+) -> Result<usize> {
+    let subscriptions: Vec<(Uuid,)> = sqlx::query_as(
+        r#"
+        SELECT s.id
+        FROM webhook_subscriptions s
+        WHERE s.organization_id = $1
+          AND s.is_active = true
+          AND (cardinality(s.event_types) = 0 OR $2 = ANY(s.event_types))
+          AND NOT EXISTS (
+              SELECT 1 FROM webhook_deliveries d
+              WHERE d.subscription_id = s.id AND d.event_type = $2 AND d.payload = $3
+          )
+        "#,
+    )
+    .bind(organization_id)
+    .bind(event_type)
+    .bind(payload)
+    .fetch_all(pool)
+    .await?;
+
+    for (subscription_id,) in &subscriptions {
+        enqueue_delivery(pool, *subscription_id, event_type, payload).await?;
+    }
+
+    Ok(subscriptions.len())
+}
+
+/// Record a new delivery; the retry worker makes the first attempt
+pub async fn enqueue_delivery(
+    pool: &PgPool,
+    subscription_id: Uuid,
+    event_type: &str,
+    payload: &serde_json::Value,
+) -> Result<Uuid> {
+    let (id,): (Uuid,) = sqlx::query_as(
+        r#"
+        INSERT INTO webhook_deliveries (subscription_id, event_type, payload, next_retry_at)
+        VALUES ($1, $2, $3, NOW())
+        RETURNING id
+        "#,
+    )
+    .bind(subscription_id)
+    .bind(event_type)
+    .bind(payload)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(id)
+}
+
+/// Attempt every due delivery once and persist the result; returns how many
+/// deliveries were attempted
+///
+/// Due rows are claimed by pushing `next_retry_at` past the attempt timeout,
+/// so concurrent workers skip them and a crashed worker's claims expire.
+pub async fn process_due_deliveries(
+    pool: &PgPool,
+    client: &reqwest::Client,
+    cipher: &SecretCipher,
+    policy: &WebhookRetryPolicy,
+    batch_size: i64,
+) -> Result<usize> {
+    let due: Vec<PendingDelivery> = sqlx::query_as(
+        r#"
+        WITH due AS (
+            SELECT d.id
+            FROM webhook_deliveries d
+            JOIN webhook_subscriptions s ON s.id = d.subscription_id
+            WHERE d.status = 'pending' AND d.next_retry_at <= NOW() AND s.is_active = true
+            ORDER BY d.next_retry_at
+            LIMIT $1
+            FOR UPDATE OF d SKIP LOCKED
+        ), claimed AS (
+            UPDATE webhook_deliveries d
+            SET next_retry_at = NOW() + make_interval(secs => $2), updated_at = NOW()
+            FROM due
+            WHERE d.id = due.id
+            RETURNING d.id, d.subscription_id, d.event_type, d.payload, d.attempts
+        )
+        SELECT c.id, c.event_type, c.payload, c.attempts, s.url, s.secret_encrypted AS secret
+        FROM claimed c
+        JOIN webhook_subscriptions s ON s.id = c.subscription_id
+        "#,
+    )
+    .bind(batch_size)
+    .bind(policy.timeout.as_secs_f64() * 2.0)
+    .fetch_all(pool)
+    .await?;
+
+    let attempted = due.len();
+    for mut delivery in due {
+        // An unreadable secret fails the attempt like an unreachable endpoint
+        let outcome = match cipher.decrypt(&delivery.secret) {
+            Ok(secret) => {
+                delivery.secret = secret;
+                attempt_delivery(client, policy, &delivery).await
+            }
+            Err(e) => AttemptOutcome::Failed {
+                status_code: None,
+                error: e.to_string(),
+            },
+        };
+        let attempts = delivery.attempts + 1;
+        let transition = policy.transition(attempts, &outcome, Utc::now());
+
+        match transition {
+            DeliveryTransition::DeadLettered => tracing::warn!(
+                "Webhook delivery {} dead-lettered after {} attempts: {}",
+                delivery.id,
+                attempts,
+                outcome.error().unwrap_or_default()
+            ),
+            DeliveryTransition::Retry { next_retry_at } => tracing::info!(
+                "Webhook delivery {} attempt {} failed, retrying at {}",
+                delivery.id,
+                attempts,
+                next_retry_at
+            ),
+            DeliveryTransition::Delivered => {}
+        }
+
+        record_attempt(pool, delivery.id, attempts, &outcome, transition).await?;
+    }
+
+    Ok(attempted)
+}
+
+async fn record_attempt(
+    pool: &PgPool,
+    delivery_id: Uuid,
+    attempts: i32,
+    outcome: &AttemptOutcome,
+    transition: DeliveryTransition,
+) -> Result<()> {
+    let next_retry_at = match transition {
+        DeliveryTransition::Retry { next_retry_at } => Some(next_retry_at),
+        _ => None,
+    };
+    let error = outcome.error().map(|e| e.chars().take(MAX_ERROR_LEN).collect::<String>());
+
+    sqlx::query(
+        r#"
+        UPDATE webhook_deliveries
+        SET status = $2,
+            attempts = $3,
+            next_retry_at = $4,
+            last_status_code = $5,
+            last_error = $6,
+            delivered_at = CASE WHEN $2 = 'delivered' THEN NOW() ELSE delivered_at END,
+            updated_at = NOW()
+        WHERE id = $1
+        "#,
+    )
+    .bind(delivery_id)
+    .bind(transition.status())
+    .bind(attempts)
+    .bind(next_retry_at)
+    .bind(outcome.status_code().map(i32::from))
+    .bind(error)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Schedule `process_due_deliveries` every `interval`
+pub fn schedule_retry_worker(
+    scheduler: &mut Scheduler,
+    pool: PgPool,
+    client: reqwest::Client,
+    cipher: SecretCipher,
+    policy: WebhookRetryPolicy,
+    interval: std::time::Duration,
+    batch_size: i64,
+) {
+    scheduler.every(interval, "webhook_retries", move || {
+        let (pool, client, cipher, policy) = (pool.clone(), client.clone(), cipher.clone(), policy.clone());
+        async move {
+            process_due_deliveries(&pool, &client, &cipher, &policy, batch_size)
+                .await
+                .map(|_| ())
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use llm_governance_common::testing::{MockResponse, MockServer};
+
+    fn header<'a>(head: &'a str, name: &str) -> Option<&'a str> {
+        head.lines()
+            .filter_map(|line| line.split_once(':'))
+            .find(|(k, _)| k.trim().eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.trim())
+    }
+
+    fn fast_policy(max_attempts: i32) -> WebhookRetryPolicy {
+        WebhookRetryPolicy {
+            max_attempts,
+            initial_delay_secs: 30,
+            max_delay_secs: 3600,
+            timeout: std::time::Duration::from_secs(5),
+        }
+    }
+
+    fn delivery(url: String) -> PendingDelivery {
+        PendingDelivery {
+            id: Uuid::new_v4(),
+            event_type: "budget.alert".to_string(),
+            payload: serde_json::json!({ "budget": "ml-team", "percent": 92.5 }),
+            attempts: 0,
+            url,
+            secret: "whsec_test".to_string(),
+        }
+    }
+
+    /// Drive a delivery the way the worker does until it leaves `pending`
+    async fn run_until_settled(policy: &WebhookRetryPolicy, delivery: &mut PendingDelivery) -> DeliveryTransition {
+        let client = reqwest::Client::new();
+        loop {
+            let outcome = attempt_delivery(&client, policy, delivery).await;
+            delivery.attempts += 1;
+            match policy.transition(delivery.attempts, &outcome, Utc::now()) {
+                DeliveryTransition::Retry { .. } => continue,
+                settled => return settled,
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_delivery_succeeds_after_retry() {
+        let server = MockServer::start(vec![MockResponse::new(503), MockResponse::new(200)]).await;
+        let policy = fast_policy(5);
+        let mut delivery = delivery(server.url().to_string());
+
+        assert_eq!(run_until_settled(&policy, &mut delivery).await, DeliveryTransition::Delivered);
+        assert_eq!(delivery.attempts, 2);
+
+        // Every attempt carries a signature valid for its own timestamp
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        for (i, raw) in requests.iter().enumerate() {
+            let (head, body) = raw.split_once("\r\n\r\n").unwrap();
+            let timestamp: i64 = header(head, TIMESTAMP_HEADER).unwrap().parse().unwrap();
+            assert_eq!(
+                header(head, SIGNATURE_HEADER).unwrap(),
+                sign_payload(&delivery.secret, timestamp, body.as_bytes())
+            );
+            assert_eq!(header(head, "X-Webhook-Attempt"), Some((i + 1).to_string().as_str()));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_delivery_is_dead_lettered_after_max_attempts() {
+        let server = MockServer::start(vec![MockResponse::new(500)]).await;
+        let policy = fast_policy(3);
+        let mut delivery = delivery(server.url().to_string());
+
+        assert_eq!(run_until_settled(&policy, &mut delivery).await, DeliveryTransition::DeadLettered);
+        assert_eq!(delivery.attempts, 3);
+        assert_eq!(server.hits(), 3);
+    }
+
+    #[test]
+    fn test_private_and_reserved_addresses_are_not_public() {
+        for ip in [
+            "10.0.0.1", "172.16.5.4", "192.168.1.1", "127.0.0.1", "169.254.169.254",
+            "0.0.0.0", "100.64.0.1", "255.255.255.255", "::1", "::", "fd00::1",
+            "fe80::1", "::ffff:10.0.0.1",
+        ] {
+            assert!(!is_public_ip(ip.parse().unwrap()), "{} should be rejected", ip);
+        }
+        for ip in ["93.184.216.34", "1.1.1.1", "2606:4700:4700::1111"] {
+            assert!(is_public_ip(ip.parse().unwrap()), "{} should be allowed", ip);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_webhook_urls_must_be_https_to_public_hosts() {
+        assert!(validate_webhook_url("https://93.184.216.34/hooks").await.is_ok());
+
+        for url in [
+            "http://93.184.216.34/hooks",
+            "ftp://93.184.216.34/hooks",
+            "https://127.0.0.1/hooks",
+            "https://169.254.169.254/latest/meta-data",
+            "https://[::1]:8443/hooks",
+            "https://localhost/hooks",
+            "not a url",
+        ] {
+            assert!(
+                matches!(validate_webhook_url(url).await, Err(AppError::Validation(_))),
+                "{} should be rejected",
+                url
+            );
+        }
+    }
+
+    #[test]
+    fn test_retry_delay_backs_off_exponentially() {
+        let policy = fast_policy(10);
+        let now = Utc::now();
+        let failed = AttemptOutcome::Failed {
+            status_code: Some(502),
+            error: "bad gateway".to_string(),
+        };
+
+        assert_eq!(policy.retry_delay(1), Duration::seconds(30));
+        assert_eq!(policy.retry_delay(3), Duration::seconds(120));
+        assert_eq!(policy.retry_delay(20), Duration::seconds(3600));
+        assert_eq!(policy.retry_delay(100), Duration::seconds(3600));
+        assert_eq!(
+            policy.transition(2, &failed, now),
+            DeliveryTransition::Retry {
+                next_retry_at: now + Duration::seconds(60)
+            }
+        );
+        assert_eq!(policy.transition(10, &failed, now), DeliveryTransition::DeadLettered);
+    }
+
+    #[test]
+    fn test_signature_depends_on_timestamp() {
+        let body = br#"{"event":"finding"}"#;
+        let first = sign_payload("secret", 1_700_000_000, body);
+
+        assert!(first.starts_with("sha256="));
+        assert_eq!(first, sign_payload("secret", 1_700_000_000, body));
+        assert_ne!(first, sign_payload("secret", 1_700_000_060, body));
+        assert_ne!(first, sign_payload("other", 1_700_000_000, body));
+    }
+}
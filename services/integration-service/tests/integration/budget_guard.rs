@@ -0,0 +1,44 @@
+use actix_web::ResponseError;
+use chrono::{Duration, Utc};
+
+use integration_service::services::budget_guard::*;
+use llm_governance_common::testing::{database, seed_member, seed_organization, seed_user};
+use llm_governance_common::AppError;
+
+#[tokio::test]
+#[ignore] // Requires Postgres at DATABASE_URL
+async fn test_spent_hard_limit_budget_rejects_with_429() {
+    let pool = database().await;
+    let user_id = seed_user(&pool, "budget").await;
+    let org_id = seed_organization(&pool, "budget").await;
+    seed_member(&pool, org_id, user_id, "member").await;
+    let now = Utc::now();
+    sqlx::query(
+        "INSERT INTO budgets (organization_id, name, amount, period, alert_threshold_percentage, \
+         hard_limit, current_spend, period_start, period_end, is_active) \
+         VALUES ($1, 'org cap', 1.00, 'daily', 80, true, 0, $2, $3, true)",
+    )
+    .bind(org_id)
+    .bind(now - Duration::hours(1))
+    .bind(now + Duration::hours(23))
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let cache = BudgetUtilizationCache::new(std::time::Duration::ZERO);
+    assert!(enforce_hard_limits(&pool, &cache, Some(user_id), None).await.is_ok());
+
+    // Spend as the proxy records it
+    sqlx::query(
+        "INSERT INTO llm_metrics (time, provider, model, user_id, cost, status) \
+         VALUES (NOW(), 'openai', 'gpt-4', $1, 1.50, 'success')",
+    )
+    .bind(user_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let err = enforce_hard_limits(&pool, &cache, Some(user_id), None).await.unwrap_err();
+    assert!(matches!(err, AppError::TooManyRequests(ref msg) if msg.contains("org cap")), "{:?}", err);
+    assert_eq!(err.status_code().as_u16(), 429);
+}
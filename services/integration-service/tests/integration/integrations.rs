@@ -0,0 +1,107 @@
+use chrono::{TimeZone, Utc};
+use uuid::Uuid;
+
+use integration_service::handlers::integrations::*;
+use llm_governance_common::testing::{database, seed_member, seed_organization, seed_user};
+
+#[actix_web::test]
+#[ignore] // Requires Postgres at DATABASE_URL
+async fn test_summary_groups_by_provider_and_model_within_caller_orgs() {
+    let pool = database().await;
+    let caller = seed_user(&pool, "caller").await;
+    let outsider = seed_user(&pool, "outsider").await;
+    let org_id = seed_organization(&pool, "metrics").await;
+    seed_member(&pool, org_id, caller, "member").await;
+
+    // A provider name unique to this run keeps other rows out of the assertions
+    let provider = format!("test-{}", Uuid::new_v4());
+    let mut rows: Vec<(Uuid, &str, i32, Option<f64>)> =
+        (1..=20).map(|i| (caller, "gpt-4", i * 100, Some(50.0))).collect();
+    rows.extend([
+        (caller, "claude-3-haiku", 300, Some(120.0)),
+        (caller, "claude-3-haiku", 100, None),
+        (caller, "claude-3-haiku", 200, Some(80.0)),
+        (outsider, "claude-3-haiku", 9000, Some(1.0)),
+    ]);
+    for (user_id, model, latency_ms, tps) in rows {
+        sqlx::query(
+            "INSERT INTO llm_metrics (time, provider, model, user_id, latency_ms, tokens_per_second, status) \
+             VALUES (NOW(), $1, $2, $3, $4, $5, 'success')",
+        )
+        .bind(&provider)
+        .bind(model)
+        .bind(user_id)
+        .bind(latency_ms)
+        .bind(tps)
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+
+    let summary: Vec<ProviderMetricsSummary> = metrics_summary(&pool, caller, 1)
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|s| s.provider == provider)
+        .collect();
+    assert_eq!(summary.len(), 2);
+
+    // The outsider's request is not counted
+    let haiku = &summary[0];
+    assert_eq!(haiku.model, "claude-3-haiku");
+    assert_eq!(haiku.requests, 3);
+    assert_eq!(haiku.p50_latency_ms, 200);
+    assert_eq!(haiku.p95_latency_ms, 290);
+    assert_eq!(haiku.avg_tokens_per_second, Some(100.0));
+
+    let gpt4 = &summary[1];
+    assert_eq!(gpt4.requests, 20);
+    assert_eq!(gpt4.p50_latency_ms, 1050);
+    assert_eq!(gpt4.p95_latency_ms, 1905);
+    assert_eq!(gpt4.avg_tokens_per_second, Some(50.0));
+}
+
+#[actix_web::test]
+#[ignore] // Requires Postgres at DATABASE_URL
+async fn test_error_rate_is_scoped_and_zero_filled() {
+    let pool = database().await;
+    let caller = seed_user(&pool, "caller").await;
+    let outsider = seed_user(&pool, "outsider").await;
+    let org_id = seed_organization(&pool, "errors").await;
+    seed_member(&pool, org_id, caller, "member").await;
+
+    let start = Utc.with_ymd_and_hms(2025, 6, 1, 10, 0, 0).unwrap();
+    let hour = |h: i64| start + chrono::Duration::hours(h);
+    let provider = format!("test-{}", Uuid::new_v4());
+    // Nothing in the second hour; the outsider's failure is not the caller's to see
+    let rows = [
+        (caller, 0, "success"),
+        (caller, 0, "error"),
+        (caller, 2, "timeout"),
+        (outsider, 1, "error"),
+    ];
+    for (user_id, offset, status) in rows {
+        sqlx::query(
+            "INSERT INTO llm_metrics (time, provider, model, user_id, latency_ms, status) \
+             VALUES ($1, $2, 'gpt-4', $3, 100, $4)",
+        )
+        .bind(hour(offset).naive_utc())
+        .bind(&provider)
+        .bind(user_id)
+        .bind(status)
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+
+    let window = ErrorRateWindow { start, end: hour(3), window_secs: 3600 };
+    let counts = error_rate_counts(&pool, &[org_id], None, Some(&provider), &window)
+        .await
+        .unwrap();
+    assert_eq!(counts, vec![(hour(0), 2, 1), (hour(1), 0, 0), (hour(2), 1, 1)]);
+
+    let other_org = error_rate_counts(&pool, &[Uuid::new_v4()], None, Some(&provider), &window)
+        .await
+        .unwrap();
+    assert!(other_org.iter().all(|(_, requests, _)| *requests == 0));
+}
@@ -0,0 +1,7 @@
+//! Integration service tests against the migrated database at `DATABASE_URL`
+//!
+//! Run with `cargo test -p integration-service --test integration -- --ignored`.
+
+mod budget_guard;
+mod integrations;
+mod request_scope;
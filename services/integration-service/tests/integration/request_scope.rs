@@ -0,0 +1,41 @@
+use actix_web::ResponseError;
+use uuid::Uuid;
+
+use integration_service::services::request_scope::request_organizations;
+use llm_governance_common::testing::{database, seed_organization, seed_team, seed_user};
+
+#[tokio::test]
+#[ignore] // Requires Postgres at DATABASE_URL
+async fn test_team_claim_requires_membership_and_is_audited() {
+    let pool = database().await;
+    let user_id = seed_user(&pool, "scope").await;
+    let org_id = seed_organization(&pool, "scope").await;
+    let team_id = seed_team(&pool, org_id, "scope").await;
+
+    // Not a member, no caller, unknown team: all fail closed
+    for (user, team) in [(Some(user_id), team_id), (None, team_id), (Some(user_id), Uuid::new_v4())] {
+        let err = request_organizations(&pool, user, Some(team)).await.unwrap_err();
+        assert_eq!(err.status_code(), actix_web::http::StatusCode::FORBIDDEN);
+    }
+    let (denied,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM audit_logs WHERE action = 'team_scope_denied' AND resource_id = $1",
+    )
+    .bind(team_id.to_string())
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(denied, 2);
+
+    sqlx::query("INSERT INTO team_members (team_id, user_id) VALUES ($1, $2)")
+        .bind(team_id)
+        .bind(user_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let orgs = request_organizations(&pool, Some(user_id), Some(team_id)).await.unwrap();
+    assert_eq!(orgs, vec![org_id]);
+
+    // Without a team the caller's memberships apply
+    assert!(request_organizations(&pool, Some(user_id), None).await.unwrap().is_empty());
+    assert!(request_organizations(&pool, None, None).await.unwrap().is_empty());
+}
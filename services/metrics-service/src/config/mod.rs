@@ -1,3 +1,4 @@
+use llm_governance_common::utils::DEFAULT_WINDOW_DAYS;
 use serde::Deserialize;
 
 #[derive(Debug, Clone, Deserialize)]
@@ -6,6 +7,13 @@ pub struct Config {
     pub port: u16,
     pub database_url: String,
     pub redis_url: String,
+    /// Span of the analysis window when a request omits its bounds
+    #[serde(default = "default_window_days")]
+    pub default_window_days: i64,
+}
+
+fn default_window_days() -> i64 {
+    DEFAULT_WINDOW_DAYS
 }
 
 impl Config {
@@ -21,6 +29,7 @@ impl Default for Config {
             port: 8085,
             database_url: String::new(),
             redis_url: "redis://127.0.0.1:6379".to_string(),
+            default_window_days: default_window_days(),
         }
     }
 }
@@ -4,6 +4,9 @@ use sqlx::PgPool;
 use uuid::Uuid;
 use llm_governance_common::{AppError, Result, ApiResponse};
 use chrono::{DateTime, Utc};
+use llm_governance_common::utils::resolve_window;
+
+use crate::config::Config;
 
 #[derive(Debug, Deserialize)]
 pub struct IngestMetricRequest {
@@ -236,15 +239,15 @@ pub async fn get_hourly_aggregates(
 #[get("/metrics/aggregate/daily")]
 pub async fn get_daily_aggregates(
     pool: web::Data<PgPool>,
+    config: web::Data<Config>,
     query: web::Query<AggregateQuery>,
 ) -> Result<impl Responder> {
-    let start_time = query.start_time.clone().unwrap_or_else(|| {
-        chrono::Utc::now().checked_sub_signed(chrono::Duration::days(30)).unwrap().to_rfc3339()
-    });
-
-    let end_time = query.end_time.clone().unwrap_or_else(|| {
-        chrono::Utc::now().to_rfc3339()
-    });
+    let window = resolve_window(
+        query.start_time.as_deref(),
+        query.end_time.as_deref(),
+        config.default_window_days,
+    );
+    let (start_time, end_time) = (window.start, window.end);
 
     let aggregates = sqlx::query_as::<_, AggregatedMetrics>(
         r#"
@@ -268,6 +271,7 @@ pub async fn get_daily_aggregates(
 #[get("/metrics/stats/usage")]
 pub async fn get_usage_stats(
     pool: web::Data<PgPool>,
+    config: web::Data<Config>,
     query: web::Query<StatsQuery>,
 ) -> Result<impl Responder> {
     let team_id = query.team_id.ok_or_else(|| AppError::Validation("team_id required".to_string()))?;
@@ -283,10 +287,11 @@ pub async fn get_usage_stats(
             SUM(cost) as total_cost
         FROM llm_metrics
         WHERE team_id = $1
-        AND time >= NOW() - INTERVAL '30 days'
+        AND time >= NOW() - make_interval(days => $2)
         "#,
     )
     .bind(team_id)
+    .bind(config.default_window_days as i32)
     .fetch_one(pool.get_ref())
     .await?;
 
@@ -296,6 +301,7 @@ pub async fn get_usage_stats(
 #[get("/metrics/stats/by-provider")]
 pub async fn get_stats_by_provider(
     pool: web::Data<PgPool>,
+    config: web::Data<Config>,
     query: web::Query<StatsQuery>,
 ) -> Result<impl Responder> {
     let team_id = query.team_id.ok_or_else(|| AppError::Validation("team_id required".to_string()))?;
@@ -310,12 +316,13 @@ pub async fn get_stats_by_provider(
             AVG(latency_ms) as avg_latency_ms
         FROM llm_metrics
         WHERE team_id = $1
-        AND time >= NOW() - INTERVAL '30 days'
+        AND time >= NOW() - make_interval(days => $2)
         GROUP BY provider
         ORDER BY total_cost DESC
         "#,
     )
     .bind(team_id)
+    .bind(config.default_window_days as i32)
     .fetch_all(pool.get_ref())
     .await?;
 
@@ -325,6 +332,7 @@ pub async fn get_stats_by_provider(
 #[get("/metrics/stats/by-model")]
 pub async fn get_stats_by_model(
     pool: web::Data<PgPool>,
+    config: web::Data<Config>,
     query: web::Query<StatsQuery>,
 ) -> Result<impl Responder> {
     let team_id = query.team_id.ok_or_else(|| AppError::Validation("team_id required".to_string()))?;
@@ -340,12 +348,13 @@ pub async fn get_stats_by_model(
             AVG(latency_ms) as avg_latency_ms
         FROM llm_metrics
         WHERE team_id = $1
-        AND time >= NOW() - INTERVAL '30 days'
+        AND time >= NOW() - make_interval(days => $2)
         GROUP BY provider, model
         ORDER BY total_cost DESC
         "#,
     )
     .bind(team_id)
+    .bind(config.default_window_days as i32)
     .fetch_all(pool.get_ref())
     .await?;
 
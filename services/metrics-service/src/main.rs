@@ -1,6 +1,5 @@
 use actix_web::{web, App, HttpServer};
-use tracing::{info, Level};
-use tracing_subscriber;
+use tracing::info;
 
 mod config;
 mod handlers;
@@ -9,14 +8,16 @@ mod models;
 mod services;
 
 use config::Config;
+use llm_governance_common::metrics::RequestMetrics;
+use llm_governance_common::request_context::RequestContext;
+use llm_governance_common::telemetry;
+use llm_governance_common::versioning::ApiVersioning;
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
-    tracing_subscriber::fmt()
-        .with_max_level(Level::INFO)
-        .init();
-
     dotenv::dotenv().ok();
+    telemetry::init("metrics-service").expect("Failed to initialize telemetry");
+
     let config = Config::from_env().expect("Failed to load configuration");
 
     info!("Starting metrics-service on {}:{}", config.host, config.port);
@@ -25,14 +26,20 @@ async fn main() -> std::io::Result<()> {
         .await
         .expect("Failed to create database pool");
 
-    HttpServer::new(move || {
+    let result = HttpServer::new(move || {
         App::new()
             .app_data(web::Data::new(db_pool.clone()))
             .app_data(web::Data::new(config.clone()))
+            .wrap(RequestContext)
             .wrap(tracing_actix_web::TracingLogger::default())
+            .wrap(RequestMetrics::new("metrics-service"))
+            .wrap(ApiVersioning::new())
             .configure(handlers::configure)
     })
     .bind((config.host.as_str(), config.port))?
     .run()
-    .await
+    .await;
+
+    telemetry::shutdown();
+    result
 }
@@ -1,3 +1,5 @@
+use llm_governance_common::idempotency::DEFAULT_IDEMPOTENCY_TTL_SECONDS;
+use llm_governance_common::utils::DEFAULT_WINDOW_DAYS;
 use serde::Deserialize;
 
 #[derive(Debug, Clone, Deserialize)]
@@ -6,6 +8,20 @@ pub struct Config {
     pub port: u16,
     pub database_url: String,
     pub redis_url: String,
+    /// How long an `Idempotency-Key` replays its original response
+    #[serde(default = "default_idempotency_ttl_seconds")]
+    pub idempotency_ttl_seconds: i64,
+    /// Span of recent spend loaded when enriching an evaluation context
+    #[serde(default = "default_window_days")]
+    pub default_window_days: i64,
+}
+
+fn default_idempotency_ttl_seconds() -> i64 {
+    DEFAULT_IDEMPOTENCY_TTL_SECONDS
+}
+
+fn default_window_days() -> i64 {
+    DEFAULT_WINDOW_DAYS
 }
 
 impl Config {
@@ -21,6 +37,8 @@ impl Default for Config {
             port: 8083,
             database_url: String::new(),
             redis_url: "redis://127.0.0.1:6379".to_string(),
+            idempotency_ttl_seconds: default_idempotency_ttl_seconds(),
+            default_window_days: default_window_days(),
         }
     }
 }
@@ -0,0 +1,358 @@
+//! Effective policies for a user
+//!
+//! A user is bound by the policies assigned to them directly, to any team they
+//! belong to and to any organization they are a member of. The resolver
+//! collects those assignments, keeps each active policy once and records every
+//! scope it was inherited from.
+
+use actix_web::{get, web, HttpRequest, HttpResponse, Responder};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use uuid::Uuid;
+use llm_governance_common::query::DynamicQuery;
+use llm_governance_common::{AppError, ApiResponse, Result};
+
+use super::policies::{extract_user_id, visible_to, PolicyResponse, POLICY_SELECT};
+
+#[derive(Debug, Deserialize)]
+pub struct EffectivePolicyQuery {
+    /// Defaults to the caller
+    pub user_id: Option<Uuid>,
+}
+
+/// Where an assignment applies, most specific first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyScope {
+    User,
+    Team,
+    Organization,
+}
+
+impl PolicyScope {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "user" => Some(PolicyScope::User),
+            "team" => Some(PolicyScope::Team),
+            "organization" => Some(PolicyScope::Organization),
+            _ => None,
+        }
+    }
+}
+
+/// One assignment through which a policy reaches the user
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub struct PolicySource {
+    pub scope: PolicyScope,
+    /// The user, team or organization the policy is assigned to
+    pub scope_id: Uuid,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EffectivePolicy {
+    #[serde(flatten)]
+    pub policy: PolicyResponse,
+    pub sources: Vec<PolicySource>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct AssignmentRow {
+    policy_id: Uuid,
+    scope: String,
+    scope_id: Uuid,
+    /// Organization of the assigned team or organization; `None` for users
+    organization_id: Option<Uuid>,
+}
+
+/// A policy source and the organization it was assigned in, if any
+type ScopedSource = (Uuid, Option<Uuid>, PolicySource);
+
+#[get("/policies/effective")]
+pub async fn get_effective_policies(
+    pool: web::Data<PgPool>,
+    query: web::Query<EffectivePolicyQuery>,
+    http_req: HttpRequest,
+) -> Result<impl Responder> {
+    let current_user_id = extract_user_id(&http_req)?;
+    let user_id = query.user_id.unwrap_or(current_user_id);
+
+    let organizations = if user_id != current_user_id {
+        Some(verify_can_view_user(pool.get_ref(), current_user_id, user_id).await?)
+    } else {
+        None
+    };
+
+    let policies = load_effective_policies(pool.get_ref(), user_id, organizations.as_deref()).await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
+        "user_id": user_id,
+        "total": policies.len(),
+        "policies": policies,
+    }))))
+}
+
+/// Active policies that apply to `user_id`, each with the scopes it came from
+///
+/// With `organizations`, only what those organizations assigned or own is
+/// returned, for a caller who shares no other organization with the user.
+pub async fn load_effective_policies(
+    pool: &PgPool,
+    user_id: Uuid,
+    organizations: Option<&[Uuid]>,
+) -> Result<Vec<EffectivePolicy>> {
+    let rows = sqlx::query_as::<_, AssignmentRow>(
+        r#"
+        SELECT policy_id, 'user' AS scope, user_id AS scope_id, NULL::uuid AS organization_id
+        FROM policy_assignments
+        WHERE user_id = $1
+        UNION ALL
+        SELECT pa.policy_id, 'team', pa.team_id, t.organization_id
+        FROM policy_assignments pa
+        JOIN team_members tm ON tm.team_id = pa.team_id
+        JOIN teams t ON t.id = pa.team_id
+        WHERE tm.user_id = $1
+        UNION ALL
+        SELECT pa.policy_id, 'organization', pa.organization_id, pa.organization_id
+        FROM policy_assignments pa
+        JOIN organization_members om ON om.organization_id = pa.organization_id
+        WHERE om.user_id = $1
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    let sources: Vec<ScopedSource> = rows
+        .into_iter()
+        .filter_map(|row| {
+            let scope = PolicyScope::from_name(&row.scope)?;
+            Some((row.policy_id, row.organization_id, PolicySource { scope, scope_id: row.scope_id }))
+        })
+        .collect();
+
+    if sources.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let policy_ids: Vec<Uuid> = sources.iter().map(|(policy_id, _, _)| *policy_id).collect();
+    let mut query = DynamicQuery::new(POLICY_SELECT);
+    query.and_where().push("id = ANY(").push_bind(policy_ids).push(")");
+    query.filter("status", "active");
+    visible_to(&mut query, user_id);
+    let policies = query.build_query_as::<PolicyResponse>().fetch_all(pool).await?;
+
+    let (policies, sources) = match organizations {
+        Some(organizations) => restrict_to_organizations(policies, sources, organizations),
+        None => (policies, sources.into_iter().map(|(policy_id, _, source)| (policy_id, source)).collect()),
+    };
+
+    Ok(resolve_effective(policies, sources))
+}
+
+/// Drop the policies and sources that fall outside `organizations`
+///
+/// A policy is kept when it is shared or owned by one of them. A team or
+/// organization source is kept when it was assigned in one of them, and a
+/// direct user assignment when its policy is owned by one of them.
+fn restrict_to_organizations(
+    policies: Vec<PolicyResponse>,
+    sources: Vec<ScopedSource>,
+    organizations: &[Uuid],
+) -> (Vec<PolicyResponse>, Vec<(Uuid, PolicySource)>) {
+    let policies: Vec<PolicyResponse> = policies
+        .into_iter()
+        .filter(|policy| policy.organization_id.is_none_or(|id| organizations.contains(&id)))
+        .collect();
+    let policy_organizations: HashMap<Uuid, Option<Uuid>> =
+        policies.iter().map(|policy| (policy.id, policy.organization_id)).collect();
+
+    let sources = sources
+        .into_iter()
+        .filter(|(policy_id, organization_id, _)| {
+            organization_id
+                .or_else(|| policy_organizations.get(policy_id).copied().flatten())
+                .map_or(false, |id| organizations.contains(&id))
+        })
+        .map(|(policy_id, _, source)| (policy_id, source))
+        .collect();
+
+    (policies, sources)
+}
+
+/// Attach each policy's sources, dropping duplicates and policies without any
+///
+/// Policies are ordered by their most specific scope, then by name.
+fn resolve_effective(
+    policies: Vec<PolicyResponse>,
+    sources: Vec<(Uuid, PolicySource)>,
+) -> Vec<EffectivePolicy> {
+    let mut by_policy: HashMap<Uuid, Vec<PolicySource>> = HashMap::new();
+    for (policy_id, source) in sources {
+        by_policy.entry(policy_id).or_default().push(source);
+    }
+
+    let mut effective: Vec<EffectivePolicy> = policies
+        .into_iter()
+        .filter_map(|policy| {
+            let mut sources = by_policy.remove(&policy.id)?;
+            sources.sort();
+            sources.dedup();
+            Some(EffectivePolicy { policy, sources })
+        })
+        .collect();
+
+    effective.sort_by(|a, b| {
+        a.sources[0]
+            .scope
+            .cmp(&b.sources[0].scope)
+            .then_with(|| a.policy.name.cmp(&b.policy.name))
+            .then_with(|| a.policy.id.cmp(&b.policy.id))
+    });
+    effective
+}
+
+/// Another user's effective policies are visible to owners and admins of an
+/// organization that user belongs to, within those organizations only
+async fn verify_can_view_user(pool: &PgPool, current_user_id: Uuid, user_id: Uuid) -> Result<Vec<Uuid>> {
+    let rows: Vec<(Uuid,)> = sqlx::query_as(
+        r#"
+        SELECT admin.organization_id
+        FROM organization_members admin
+        JOIN organization_members member ON member.organization_id = admin.organization_id
+        WHERE admin.user_id = $1 AND admin.role IN ('owner', 'admin')
+        AND member.user_id = $2
+        "#,
+    )
+    .bind(current_user_id)
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    if rows.is_empty() {
+        return Err(AppError::Forbidden);
+    }
+
+    Ok(rows.into_iter().map(|(organization_id,)| organization_id).collect())
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_effective_policies);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn policy(id: Uuid, name: &str) -> PolicyResponse {
+        PolicyResponse {
+            id,
+            name: name.to_string(),
+            description: None,
+            policy_type: "usage".to_string(),
+            rules: serde_json::json!({}),
+            enforcement_level: "warning".to_string(),
+            status: "active".to_string(),
+            version: 1,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            created_by: None,
+            organization_id: None,
+        }
+    }
+
+    fn source(scope: PolicyScope, scope_id: Uuid) -> PolicySource {
+        PolicySource { scope, scope_id }
+    }
+
+    #[test]
+    fn test_user_inherits_from_two_teams_and_the_organization() {
+        let (user, team_a, team_b, org) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let (direct, team_only, shared, org_only) =
+            (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+
+        let policies = vec![
+            policy(org_only, "a-org"),
+            policy(shared, "b-shared"),
+            policy(team_only, "c-team"),
+            policy(direct, "d-direct"),
+        ];
+        let sources = vec![
+            (direct, source(PolicyScope::User, user)),
+            (team_only, source(PolicyScope::Team, team_a)),
+            (shared, source(PolicyScope::Organization, org)),
+            (shared, source(PolicyScope::Team, team_b)),
+            (shared, source(PolicyScope::Team, team_a)),
+            (org_only, source(PolicyScope::Organization, org)),
+        ];
+
+        let effective = resolve_effective(policies, sources);
+        let names: Vec<&str> = effective.iter().map(|e| e.policy.name.as_str()).collect();
+        assert_eq!(names, vec!["d-direct", "b-shared", "c-team", "a-org"]);
+
+        // The policy assigned to both teams and the organization appears once
+        let shared_sources = &effective[1].sources;
+        assert_eq!(shared_sources.len(), 3);
+        assert_eq!(shared_sources[2], source(PolicyScope::Organization, org));
+        assert!(shared_sources.contains(&source(PolicyScope::Team, team_a)));
+        assert!(shared_sources.contains(&source(PolicyScope::Team, team_b)));
+    }
+
+    #[test]
+    fn test_duplicate_sources_and_unassigned_policies_are_dropped() {
+        let (team, assigned) = (Uuid::new_v4(), Uuid::new_v4());
+        let policies = vec![policy(assigned, "assigned"), policy(Uuid::new_v4(), "unassigned")];
+        let sources = vec![
+            (assigned, source(PolicyScope::Team, team)),
+            (assigned, source(PolicyScope::Team, team)),
+        ];
+
+        let effective = resolve_effective(policies, sources);
+        assert_eq!(effective.len(), 1);
+        assert_eq!(effective[0].sources, vec![source(PolicyScope::Team, team)]);
+    }
+
+    #[test]
+    fn test_other_users_policies_are_limited_to_shared_organizations() {
+        let (user, shared_org, other_org) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let (shared_owned, other_owned, global) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let (shared_team, other_team) = (Uuid::new_v4(), Uuid::new_v4());
+
+        let owned = |id: Uuid, name: &str, organization_id: Uuid| {
+            let mut policy = policy(id, name);
+            policy.organization_id = Some(organization_id);
+            policy
+        };
+        let policies = vec![
+            owned(shared_owned, "shared-owned", shared_org),
+            owned(other_owned, "other-owned", other_org),
+            policy(global, "global"),
+        ];
+        let sources = vec![
+            (shared_owned, None, source(PolicyScope::User, user)),
+            (other_owned, None, source(PolicyScope::User, user)),
+            (other_owned, Some(other_org), source(PolicyScope::Organization, other_org)),
+            (global, Some(other_org), source(PolicyScope::Team, other_team)),
+            (global, Some(shared_org), source(PolicyScope::Team, shared_team)),
+            (global, None, source(PolicyScope::User, user)),
+        ];
+
+        let (policies, sources) = restrict_to_organizations(policies, sources, &[shared_org]);
+        let effective = resolve_effective(policies, sources);
+
+        let names: Vec<&str> = effective.iter().map(|e| e.policy.name.as_str()).collect();
+        assert_eq!(names, vec!["shared-owned", "global"]);
+        assert_eq!(effective[1].sources, vec![source(PolicyScope::Team, shared_team)]);
+    }
+
+    #[test]
+    fn test_sources_serialize_with_scope_names() {
+        let (id, team) = (Uuid::new_v4(), Uuid::new_v4());
+        let effective = resolve_effective(vec![policy(id, "p")], vec![(id, source(PolicyScope::Team, team))]);
+        let json = serde_json::to_value(&effective[0]).unwrap();
+        assert_eq!(json["name"], "p");
+        assert_eq!(json["sources"][0]["scope"], "team");
+        assert_eq!(json["sources"][0]["scope_id"], team.to_string());
+    }
+}
@@ -1,13 +1,23 @@
-use actix_web::{delete, get, post, put, web, HttpResponse, Responder, HttpRequest};
+use actix_web::{delete, get, http::StatusCode, post, put, web, HttpResponse, Responder, HttpRequest};
 use serde::{Deserialize, Serialize};
 use validator::Validate;
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 use uuid::Uuid;
 use llm_governance_common::{AppError, Result, ApiResponse};
+use llm_governance_common::idempotency::{extract_idempotency_key, with_idempotency};
+use llm_governance_common::db::{paginate, Page};
+use llm_governance_common::query::DynamicQuery;
 use chrono::{DateTime, Utc};
+use llm_governance_common::utils::hash_inputs;
 
-#[derive(Debug, Deserialize, Validate)]
+use crate::config::Config;
+use super::rule_schema::validate_rules;
+use super::simulation::verify_org_admin;
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
 pub struct CreatePolicyRequest {
+    /// Organization that owns the policy; only its members can see it
+    pub organization_id: Uuid,
     #[validate(length(min = 3, max = 255))]
     pub name: String,
     pub description: Option<String>,
@@ -38,6 +48,8 @@ pub struct PolicyResponse {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub created_by: Option<Uuid>,
+    /// `None` for policies shared by every organization
+    pub organization_id: Option<Uuid>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -45,11 +57,62 @@ pub struct EvaluateRequest {
     pub context: serde_json::Value,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct EvaluateQuery {
+    /// Include a per-rule trace in the result
+    #[serde(default)]
+    pub explain: bool,
+    /// Merge the caller's org, teams, recent spend and request rate into the context
+    #[serde(default)]
+    pub enrich: bool,
+}
+
+/// Context key reserved for server-loaded subject attributes; any value
+/// the caller supplies under it is discarded
+pub const SUBJECT_CONTEXT_KEY: &str = "_subject";
+
+/// Attributes of the calling user merged into an enriched context
+#[derive(Debug, Clone, Serialize)]
+pub struct SubjectAttributes {
+    pub user_id: Uuid,
+    pub organization_ids: Vec<Uuid>,
+    pub team_ids: Vec<Uuid>,
+    /// Total LLM spend over the last `spend_window_days`
+    pub recent_spend: f64,
+    pub spend_window_days: i64,
+    /// Requests recorded in `llm_metrics` over the last minute
+    pub requests_last_minute: i64,
+}
+
 #[derive(Debug, Serialize)]
 pub struct EvaluationResult {
     pub passed: bool,
     pub violations: Vec<PolicyViolation>,
     pub warnings: Vec<String>,
+    /// Per-rule trace, present only when `?explain=true`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explain: Option<Vec<RuleTrace>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleOutcome {
+    Passed,
+    Failed,
+    /// The rule is configured but the context lacked the field it checks
+    NotEvaluated,
+}
+
+/// One configured rule as seen by the evaluator
+#[derive(Debug, Serialize)]
+pub struct RuleTrace {
+    pub rule: String,
+    pub context_field: String,
+    /// Value read from the context, if present
+    pub input: Option<serde_json::Value>,
+    pub threshold: serde_json::Value,
+    pub outcome: RuleOutcome,
+    pub reason: Option<String>,
 }
 
 #[derive(Debug, Serialize, sqlx::FromRow)]
@@ -61,66 +124,159 @@ pub struct PolicyViolation {
     pub message: String,
 }
 
+/// One recorded evaluation of a policy
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct PolicyEvaluationRecord {
+    pub id: Uuid,
+    pub policy_id: Uuid,
+    pub policy_version: i32,
+    /// SHA-256 of the evaluated context; equal hashes mean identical inputs
+    pub context_hash: String,
+    pub passed: bool,
+    pub violation_count: i32,
+    pub violations: serde_json::Value,
+    /// User the policy was evaluated for
+    pub subject_id: Option<Uuid>,
+    pub evaluated_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct AssignPolicyRequest {
     pub team_id: Option<Uuid>,
     pub user_id: Option<Uuid>,
+    /// Assign to every member of the organization
+    pub organization_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BulkAssignmentItem {
+    pub policy_id: Uuid,
+    pub team_id: Option<Uuid>,
+    pub user_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BulkAssignPolicyRequest {
+    pub assignments: Vec<BulkAssignmentItem>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BulkAssignmentResult {
+    pub policy_id: Uuid,
+    pub team_id: Option<Uuid>,
+    pub user_id: Option<Uuid>,
+    /// "created", or "skipped" when the assignment already existed
+    pub status: String,
+}
+
+/// Upper bound on assignments accepted in one bulk request
+const MAX_BULK_ASSIGNMENTS: usize = 500;
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct PolicyAssignmentResponse {
+    pub id: Uuid,
+    pub policy_id: Uuid,
+    pub team_id: Option<Uuid>,
+    pub team_name: Option<String>,
+    pub user_id: Option<Uuid>,
+    pub user_email: Option<String>,
+    pub organization_id: Option<Uuid>,
+    pub assigned_at: DateTime<Utc>,
+    pub assigned_by: Option<Uuid>,
+}
+
+/// Team, user or organization a policy is assigned to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AssignmentTarget {
+    Team(Uuid),
+    User(Uuid),
+    Organization(Uuid),
 }
 
 #[get("/policies")]
 pub async fn list_policies(
     pool: web::Data<PgPool>,
     query: web::Query<PolicyQuery>,
+    http_req: HttpRequest,
 ) -> Result<impl Responder> {
-    let limit = query.limit.unwrap_or(20).min(100);
-    let offset = query.offset.unwrap_or(0);
+    let current_user_id = extract_user_id(&http_req)?;
+    let page = Page::new(query.limit, query.offset);
 
-    // Use parameterized queries to prevent SQL injection
-    let policies = sqlx::query_as::<_, PolicyResponse>(
-        r#"
-        SELECT id, name, description, policy_type, rules, enforcement_level, status, version, created_at, updated_at, created_by
-        FROM policies
-        WHERE ($1::text IS NULL OR policy_type = $1)
-          AND ($2::text IS NULL OR status = $2)
-        ORDER BY created_at DESC
-        LIMIT $3 OFFSET $4
-        "#
+    let (policies, total): (Vec<PolicyResponse>, i64) = paginate(
+        pool.get_ref(),
+        ordered_policies(&query, current_user_id),
+        filtered_policies("SELECT COUNT(*) FROM policies", &query, current_user_id),
+        page,
     )
-    .bind(query.policy_type.as_ref())
-    .bind(query.status.as_ref())
-    .bind(limit as i64)
-    .bind(offset as i64)
-    .fetch_all(pool.get_ref())
     .await?;
 
-    let total: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM policies")
-        .fetch_one(pool.get_ref())
-        .await?;
-
     Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
         "policies": policies,
-        "total": total.0,
-        "limit": limit,
-        "offset": offset
+        "total": total,
+        "limit": page.limit,
+        "offset": page.offset
     }))))
 }
 
+pub(crate) const POLICY_SELECT: &str = "SELECT id, name, description, policy_type, rules, enforcement_level, status, version, created_at, updated_at, created_by, organization_id FROM policies";
+
+/// Restrict `query` to policies `user_id` can see: shared policies and those
+/// of the organizations the user belongs to
+pub(crate) fn visible_to(query: &mut DynamicQuery<'static>, user_id: Uuid) {
+    query
+        .and_where()
+        .push("(organization_id IS NULL OR organization_id IN (SELECT organization_id FROM organization_members WHERE user_id = ")
+        .push_bind(user_id)
+        .push("))");
+}
+
+/// `head` restricted by the list filters, shared by the page and its total
+pub fn filtered_policies(head: &str, query: &PolicyQuery, user_id: Uuid) -> DynamicQuery<'static> {
+    let mut policies = DynamicQuery::new(head);
+    visible_to(&mut policies, user_id);
+    policies
+        .filter_opt("organization_id", query.organization_id)
+        .filter_opt("policy_type", query.policy_type.clone())
+        .filter_opt("status", query.status.clone());
+    policies
+}
+
+/// A single policy as seen by `user_id`
+///
+/// Another organization's policy is reported as missing, not forbidden, and so
+/// is a deleted (inactive) one unless `include_inactive` is set.
+pub fn policy_lookup_query(policy_id: Uuid, user_id: Uuid, include_inactive: bool) -> DynamicQuery<'static> {
+    let mut query = DynamicQuery::new(POLICY_SELECT);
+    query.filter("id", policy_id);
+    visible_to(&mut query, user_id);
+    if !include_inactive {
+        query.filter_op("status", "<>", "inactive");
+    }
+    query
+}
+
+/// Filtered policies, newest first; `id` breaks ties between policies
+/// created in the same transaction so pages never overlap or skip
+pub fn ordered_policies(query: &PolicyQuery, user_id: Uuid) -> DynamicQuery<'static> {
+    let mut policies = filtered_policies(POLICY_SELECT, query, user_id);
+    policies.push(" ORDER BY created_at DESC, id DESC");
+    policies
+}
+
 #[get("/policies/{id}")]
 pub async fn get_policy(
     pool: web::Data<PgPool>,
     policy_id: web::Path<Uuid>,
+    query: web::Query<GetPolicyQuery>,
+    http_req: HttpRequest,
 ) -> Result<impl Responder> {
-    let policy = sqlx::query_as::<_, PolicyResponse>(
-        r#"
-        SELECT id, name, description, policy_type, rules, enforcement_level, status, version, created_at, updated_at, created_by
-        FROM policies
-        WHERE id = $1
-        "#,
-    )
-    .bind(policy_id.as_ref())
-    .fetch_optional(pool.get_ref())
-    .await?
-    .ok_or_else(|| AppError::NotFound("Policy not found".to_string()))?;
+    let current_user_id = extract_user_id(&http_req)?;
+
+    let policy = policy_lookup_query(*policy_id, current_user_id, query.include_inactive)
+        .build_query_as::<PolicyResponse>()
+        .fetch_optional(pool.get_ref())
+        .await?
+        .ok_or_else(|| AppError::NotFound("Policy not found".to_string()))?;
 
     Ok(HttpResponse::Ok().json(ApiResponse::success(policy)))
 }
@@ -128,47 +284,63 @@ pub async fn get_policy(
 #[post("/policies")]
 pub async fn create_policy(
     pool: web::Data<PgPool>,
+    config: web::Data<Config>,
     req: web::Json<CreatePolicyRequest>,
     http_req: HttpRequest,
 ) -> Result<impl Responder> {
-    req.validate()
-        .map_err(|e| AppError::Validation(format!("{}", e)))?;
+    req.validate()?;
 
     let current_user_id = extract_user_id(&http_req)?;
+    let idempotency_key = extract_idempotency_key(&http_req, &*req)?;
+    verify_org_admin(pool.get_ref(), current_user_id, req.organization_id).await?;
 
     // Validate policy type
     if !is_valid_policy_type(&req.policy_type) {
         return Err(AppError::Validation("Invalid policy type".to_string()));
     }
+    validate_rules(&req.policy_type, &req.rules)?;
 
     // Validate enforcement level
     if !is_valid_enforcement_level(&req.enforcement_level) {
         return Err(AppError::Validation("Invalid enforcement level".to_string()));
     }
 
-    let policy = sqlx::query_as::<_, PolicyResponse>(
-        r#"
-        INSERT INTO policies (name, description, policy_type, rules, enforcement_level, status, created_by)
-        VALUES ($1, $2, $3, $4, $5, 'active', $6)
-        RETURNING id, name, description, policy_type, rules, enforcement_level, status, version, created_at, updated_at, created_by
-        "#,
+    // A retried request with the same Idempotency-Key replays the original policy
+    with_idempotency(
+        pool.get_ref(),
+        "policies.create_policy",
+        current_user_id,
+        idempotency_key,
+        config.idempotency_ttl_seconds,
+        StatusCode::CREATED,
+        || async {
+            let policy = sqlx::query_as::<_, PolicyResponse>(
+                r#"
+                INSERT INTO policies (name, description, policy_type, rules, enforcement_level, status, created_by, organization_id)
+                VALUES ($1, $2, $3, $4, $5, 'active', $6, $7)
+                RETURNING id, name, description, policy_type, rules, enforcement_level, status, version, created_at, updated_at, created_by, organization_id
+                "#,
+            )
+            .bind(&req.name)
+            .bind(&req.description)
+            .bind(&req.policy_type)
+            .bind(&req.rules)
+            .bind(&req.enforcement_level)
+            .bind(current_user_id)
+            .bind(req.organization_id)
+            .fetch_one(pool.get_ref())
+            .await
+            .map_err(|e| match e {
+                sqlx::Error::Database(db_err) if db_err.constraint() == Some("policies_org_name_key") => {
+                    AppError::BadRequest("Policy name already exists".to_string())
+                }
+                _ => AppError::Database(e),
+            })?;
+
+            Ok((policy.id, policy))
+        },
     )
-    .bind(&req.name)
-    .bind(&req.description)
-    .bind(&req.policy_type)
-    .bind(&req.rules)
-    .bind(&req.enforcement_level)
-    .bind(current_user_id)
-    .fetch_one(pool.get_ref())
     .await
-    .map_err(|e| match e {
-        sqlx::Error::Database(db_err) if db_err.constraint() == Some("policies_name_key") => {
-            AppError::BadRequest("Policy name already exists".to_string())
-        }
-        _ => AppError::Database(e),
-    })?;
-
-    Ok(HttpResponse::Created().json(ApiResponse::success(policy)))
 }
 
 #[put("/policies/{id}")]
@@ -181,72 +353,47 @@ pub async fn update_policy(
     req.validate()
         .map_err(|e| AppError::Validation(format!("{}", e)))?;
 
-    let _current_user_id = extract_user_id(&http_req)?;
-
-    // Get current policy version
-    let current: (i32,) = sqlx::query_as(
-        "SELECT version FROM policies WHERE id = $1"
-    )
-    .bind(policy_id.as_ref())
-    .fetch_optional(pool.get_ref())
-    .await?
-    .ok_or_else(|| AppError::NotFound("Policy not found".to_string()))?;
-
-    // Build update query dynamically
-    let mut updates = Vec::new();
-    let mut values: Vec<String> = Vec::new();
+    let current_user_id = extract_user_id(&http_req)?;
+    let policy = verify_policy_editor(pool.get_ref(), current_user_id, *policy_id).await?;
 
+    let mut update = DynamicQuery::new("UPDATE policies");
     if let Some(name) = &req.name {
-        updates.push("name = $");
-        values.push(name.clone());
+        update.set("name", name.clone());
     }
     if let Some(description) = &req.description {
-        updates.push("description = $");
-        values.push(description.clone());
+        update.set("description", description.clone());
     }
     if let Some(rules) = &req.rules {
-        updates.push("rules = $");
-        values.push(serde_json::to_string(rules).unwrap());
+        // Rules are checked against the schema of the stored policy's type
+        validate_rules(&policy.policy_type, rules)?;
+        update.set("rules", rules.clone());
     }
     if let Some(enforcement_level) = &req.enforcement_level {
-        updates.push("enforcement_level = $");
-        values.push(enforcement_level.clone());
+        update.set("enforcement_level", enforcement_level.clone());
     }
     if let Some(status) = &req.status {
-        updates.push("status = $");
-        values.push(status.clone());
+        update.set("status", status.clone());
     }
 
-    // Increment version
-    updates.push("version = version + 1");
-
-    if !values.is_empty() || !updates.is_empty() {
-        // Simplified update - in production, you'd build this dynamically
-        if let Some(rules) = &req.rules {
-            sqlx::query(
-                r#"
-                UPDATE policies
-                SET rules = $1, version = version + 1
-                WHERE id = $2
-                "#,
-            )
-            .bind(rules)
-            .bind(policy_id.as_ref())
-            .execute(pool.get_ref())
-            .await?;
-        }
+    if !update.has_assignments() {
+        return Err(AppError::Validation("No fields to update".to_string()));
     }
 
-    let policy = sqlx::query_as::<_, PolicyResponse>(
-        r#"
-        SELECT id, name, description, policy_type, rules, enforcement_level, status, version, created_at, updated_at, created_by
-        FROM policies
-        WHERE id = $1
-        "#,
-    )
-    .bind(policy_id.as_ref())
-    .fetch_one(pool.get_ref())
-    .await?;
+    update.set_raw("version = version + 1").filter("id", *policy_id);
+    visible_to(&mut update, current_user_id);
+    update.push(" RETURNING id, name, description, policy_type, rules, enforcement_level, status, version, created_at, updated_at, created_by, organization_id");
+
+    let policy = update
+        .build_query_as::<PolicyResponse>()
+        .fetch_optional(pool.get_ref())
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db_err) if db_err.constraint() == Some("policies_org_name_key") => {
+                AppError::BadRequest("Policy name already exists".to_string())
+            }
+            _ => AppError::Database(e),
+        })?
+        .ok_or_else(|| AppError::NotFound("Policy not found".to_string()))?;
 
     Ok(HttpResponse::Ok().json(ApiResponse::success(policy)))
 }
@@ -257,13 +404,14 @@ pub async fn delete_policy(
     policy_id: web::Path<Uuid>,
     http_req: HttpRequest,
 ) -> Result<impl Responder> {
-    let _current_user_id = extract_user_id(&http_req)?;
+    let current_user_id = extract_user_id(&http_req)?;
+    verify_policy_editor(pool.get_ref(), current_user_id, *policy_id).await?;
 
     // Soft delete (set status to inactive)
-    let result = sqlx::query("UPDATE policies SET status = 'inactive' WHERE id = $1")
-        .bind(policy_id.as_ref())
-        .execute(pool.get_ref())
-        .await?;
+    let mut delete = DynamicQuery::new("UPDATE policies");
+    delete.set_raw("status = 'inactive'").filter("id", *policy_id);
+    visible_to(&mut delete, current_user_id);
+    let result = delete.build().execute(pool.get_ref()).await?;
 
     if result.rows_affected() == 0 {
         return Err(AppError::NotFound("Policy not found".to_string()));
@@ -277,24 +425,119 @@ pub async fn delete_policy(
 #[post("/policies/{id}/evaluate")]
 pub async fn evaluate_policy(
     pool: web::Data<PgPool>,
+    config: web::Data<Config>,
     policy_id: web::Path<Uuid>,
+    query: web::Query<EvaluateQuery>,
     req: web::Json<EvaluateRequest>,
+    http_req: HttpRequest,
 ) -> Result<impl Responder> {
-    let policy = sqlx::query_as::<_, PolicyResponse>(
+    let current_user_id = extract_user_id(&http_req)?;
+
+    let mut policy_query = DynamicQuery::new(POLICY_SELECT);
+    policy_query.filter("id", *policy_id).filter("status", "active");
+    visible_to(&mut policy_query, current_user_id);
+
+    let policy = policy_query
+        .build_query_as::<PolicyResponse>()
+        .fetch_optional(pool.get_ref())
+        .await?
+        .ok_or_else(|| AppError::NotFound("Policy not found or inactive".to_string()))?;
+
+    let subject = if query.enrich {
+        Some(load_subject_attributes(pool.get_ref(), current_user_id, config.default_window_days).await?)
+    } else {
+        None
+    };
+    let context = prepare_context(&req.context, subject.as_ref());
+
+    let result = evaluate_policy_rules(&policy, &context, query.explain)?;
+    record_evaluation(pool.get_ref(), &policy, &context, &result, current_user_id).await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(result)))
+}
+
+#[get("/policies/{id}/evaluations")]
+pub async fn list_policy_evaluations(
+    pool: web::Data<PgPool>,
+    policy_id: web::Path<Uuid>,
+    query: web::Query<EvaluationHistoryQuery>,
+    http_req: HttpRequest,
+) -> Result<impl Responder> {
+    let current_user_id = extract_user_id(&http_req)?;
+    let organization_id =
+        verify_policy_admin(pool.get_ref(), current_user_id, *policy_id, query.organization_id).await?;
+
+    let page = Page::new(query.limit, query.offset);
+    let (evaluations, total): (Vec<PolicyEvaluationRecord>, i64) = paginate(
+        pool.get_ref(),
+        ordered_evaluations(*policy_id, organization_id, query.passed),
+        filtered_evaluations("SELECT COUNT(*) FROM policy_evaluations", *policy_id, organization_id, query.passed),
+        page,
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
+        "evaluations": evaluations,
+        "total": total,
+        "limit": page.limit,
+        "offset": page.offset
+    }))))
+}
+
+const EVALUATION_SELECT: &str = "SELECT id, policy_id, policy_version, context_hash, passed, violation_count, violations, subject_id, evaluated_at FROM policy_evaluations";
+
+/// `head` restricted to one policy's evaluations of members of
+/// `organization_id`, shared by the page and its total
+pub fn filtered_evaluations(
+    head: &str,
+    policy_id: Uuid,
+    organization_id: Uuid,
+    passed: Option<bool>,
+) -> DynamicQuery<'static> {
+    let mut evaluations = DynamicQuery::new(head);
+    evaluations.filter("policy_id", policy_id);
+    evaluations
+        .and_where()
+        .push("subject_id IN (SELECT user_id FROM organization_members WHERE organization_id = ")
+        .push_bind(organization_id)
+        .push(")");
+    evaluations.filter_opt("passed", passed);
+    evaluations
+}
+
+/// Newest evaluations first, with `id` breaking ties
+pub fn ordered_evaluations(policy_id: Uuid, organization_id: Uuid, passed: Option<bool>) -> DynamicQuery<'static> {
+    let mut evaluations = filtered_evaluations(EVALUATION_SELECT, policy_id, organization_id, passed);
+    evaluations.push(" ORDER BY evaluated_at DESC, id DESC");
+    evaluations
+}
+
+/// Append the outcome of an evaluation to the policy's history
+pub async fn record_evaluation(
+    pool: &PgPool,
+    policy: &PolicyResponse,
+    context: &serde_json::Value,
+    result: &EvaluationResult,
+    subject_id: Uuid,
+) -> Result<()> {
+    sqlx::query(
         r#"
-        SELECT id, name, description, policy_type, rules, enforcement_level, status, version, created_at, updated_at, created_by
-        FROM policies
-        WHERE id = $1 AND status = 'active'
+        INSERT INTO policy_evaluations
+            (policy_id, policy_version, context_hash, passed, violation_count, violations, subject_id)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
         "#,
     )
-    .bind(policy_id.as_ref())
-    .fetch_optional(pool.get_ref())
-    .await?
-    .ok_or_else(|| AppError::NotFound("Policy not found or inactive".to_string()))?;
-
-    let result = evaluate_policy_rules(&policy, &req.context)?;
+    .bind(policy.id)
+    .bind(policy.version)
+    .bind(hash_inputs(context))
+    .bind(result.passed)
+    .bind(result.violations.len() as i32)
+    .bind(sqlx::types::Json(&result.violations))
+    .bind(subject_id)
+    .execute(pool)
+    .await?;
 
-    Ok(HttpResponse::Ok().json(ApiResponse::success(result)))
+    Ok(())
 }
 
 #[post("/policies/{id}/assign")]
@@ -305,22 +548,20 @@ pub async fn assign_policy(
     http_req: HttpRequest,
 ) -> Result<impl Responder> {
     let current_user_id = extract_user_id(&http_req)?;
-
-    // Validate that either team_id or user_id is provided, but not both
-    if (req.team_id.is_some() && req.user_id.is_some()) ||
-       (req.team_id.is_none() && req.user_id.is_none()) {
-        return Err(AppError::Validation("Provide either team_id or user_id, not both".to_string()));
-    }
+    let target = assignment_target(req.team_id, req.user_id, req.organization_id)?;
+    verify_can_assign(&mut *pool.acquire().await?, current_user_id, *policy_id, target).await?;
 
     sqlx::query(
         r#"
-        INSERT INTO policy_assignments (policy_id, team_id, user_id, assigned_by)
-        VALUES ($1, $2, $3, $4)
+        INSERT INTO policy_assignments (policy_id, team_id, user_id, organization_id, assigned_by)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT DO NOTHING
         "#,
     )
     .bind(policy_id.as_ref())
     .bind(req.team_id)
     .bind(req.user_id)
+    .bind(req.organization_id)
     .bind(current_user_id)
     .execute(pool.get_ref())
     .await?;
@@ -330,14 +571,159 @@ pub async fn assign_policy(
     )))
 }
 
+#[post("/policies/assignments/bulk")]
+pub async fn bulk_assign_policies(
+    pool: web::Data<PgPool>,
+    req: web::Json<BulkAssignPolicyRequest>,
+    http_req: HttpRequest,
+) -> Result<impl Responder> {
+    let current_user_id = extract_user_id(&http_req)?;
+
+    // Reject the whole batch if any item is invalid
+    validate_bulk_assignments(&req.assignments).map_err(AppError::Validation)?;
+
+    let mut tx = pool.begin().await?;
+    let mut results = Vec::with_capacity(req.assignments.len());
+
+    // An item the caller may not assign fails the batch and rolls back the
+    // assignments made before it
+    for (index, item) in req.assignments.iter().enumerate() {
+        let target = assignment_target(item.team_id, item.user_id, None)?;
+        verify_can_assign(&mut tx, current_user_id, item.policy_id, target)
+            .await
+            .map_err(|e| match e {
+                AppError::NotFound(_) => AppError::Validation(format!("assignments[{}]: policy not found", index)),
+                e => e,
+            })?;
+
+        let inserted = sqlx::query(
+            r#"
+            INSERT INTO policy_assignments (policy_id, team_id, user_id, assigned_by)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(item.policy_id)
+        .bind(item.team_id)
+        .bind(item.user_id)
+        .bind(current_user_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db_err) if db_err.is_foreign_key_violation() => {
+                AppError::Validation(format!(
+                    "assignments[{}]: policy, team or user does not exist",
+                    index
+                ))
+            }
+            _ => AppError::Database(e),
+        })?;
+
+        results.push(BulkAssignmentResult {
+            policy_id: item.policy_id,
+            team_id: item.team_id,
+            user_id: item.user_id,
+            status: if inserted.rows_affected() == 1 { "created" } else { "skipped" }.to_string(),
+        });
+    }
+
+    tx.commit().await?;
+
+    let created = results.iter().filter(|r| r.status == "created").count();
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
+        "created": created,
+        "skipped": results.len() - created,
+        "results": results
+    }))))
+}
+
+#[get("/policies/{id}/assignments")]
+pub async fn list_policy_assignments(
+    pool: web::Data<PgPool>,
+    policy_id: web::Path<Uuid>,
+    query: web::Query<AssignmentListQuery>,
+    http_req: HttpRequest,
+) -> Result<impl Responder> {
+    let current_user_id = extract_user_id(&http_req)?;
+    let organization_id =
+        verify_policy_admin(pool.get_ref(), current_user_id, *policy_id, query.organization_id).await?;
+
+    // Only assignments to the organization, its teams or its members
+    let assignments = sqlx::query_as::<_, PolicyAssignmentResponse>(
+        r#"
+        SELECT pa.id, pa.policy_id, pa.team_id, t.name AS team_name,
+               pa.user_id, u.email AS user_email, pa.organization_id,
+               pa.assigned_at AT TIME ZONE 'UTC' AS assigned_at, pa.assigned_by
+        FROM policy_assignments pa
+        LEFT JOIN teams t ON t.id = pa.team_id
+        LEFT JOIN users u ON u.id = pa.user_id
+        WHERE pa.policy_id = $1
+        AND (pa.organization_id = $2
+             OR t.organization_id = $2
+             OR pa.user_id IN (SELECT user_id FROM organization_members WHERE organization_id = $2))
+        ORDER BY pa.assigned_at DESC, pa.id
+        "#,
+    )
+    .bind(policy_id.as_ref())
+    .bind(organization_id)
+    .fetch_all(pool.get_ref())
+    .await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(assignments)))
+}
+
+#[delete("/policies/{id}/assignments")]
+pub async fn unassign_policy(
+    pool: web::Data<PgPool>,
+    policy_id: web::Path<Uuid>,
+    query: web::Query<AssignPolicyRequest>,
+    http_req: HttpRequest,
+) -> Result<impl Responder> {
+    let current_user_id = extract_user_id(&http_req)?;
+    let target = assignment_target(query.team_id, query.user_id, query.organization_id)?;
+    verify_can_assign(&mut *pool.acquire().await?, current_user_id, *policy_id, target).await?;
+
+    let result = match target {
+        AssignmentTarget::Team(team_id) => {
+            sqlx::query("DELETE FROM policy_assignments WHERE policy_id = $1 AND team_id = $2")
+                .bind(policy_id.as_ref())
+                .bind(team_id)
+                .execute(pool.get_ref())
+                .await?
+        }
+        AssignmentTarget::User(user_id) => {
+            sqlx::query("DELETE FROM policy_assignments WHERE policy_id = $1 AND user_id = $2")
+                .bind(policy_id.as_ref())
+                .bind(user_id)
+                .execute(pool.get_ref())
+                .await?
+        }
+        AssignmentTarget::Organization(organization_id) => {
+            sqlx::query("DELETE FROM policy_assignments WHERE policy_id = $1 AND organization_id = $2")
+                .bind(policy_id.as_ref())
+                .bind(organization_id)
+                .execute(pool.get_ref())
+                .await?
+        }
+    };
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Policy assignment not found".to_string()));
+    }
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(
+        serde_json::json!({"message": "Policy unassigned successfully"})
+    )))
+}
+
 #[get("/policies/{id}/violations")]
 pub async fn get_policy_violations(
     pool: web::Data<PgPool>,
     policy_id: web::Path<Uuid>,
     query: web::Query<ViolationQuery>,
 ) -> Result<impl Responder> {
-    let limit = query.limit.unwrap_or(20).min(100);
-    let offset = query.offset.unwrap_or(0);
+    let Page { limit, offset } = Page::new(query.limit, query.offset);
 
     #[derive(Debug, Serialize, sqlx::FromRow)]
     struct ViolationRecord {
@@ -375,16 +761,193 @@ pub async fn get_policy_violations(
 pub struct PolicyQuery {
     pub limit: Option<u32>,
     pub offset: Option<u32>,
+    /// Narrow to one organization; shared policies are otherwise included
+    pub organization_id: Option<Uuid>,
     pub policy_type: Option<String>,
     pub status: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct GetPolicyQuery {
+    /// Return the policy even if it has been deleted
+    #[serde(default)]
+    pub include_inactive: bool,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ViolationQuery {
     pub limit: Option<u32>,
     pub offset: Option<u32>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct AssignmentListQuery {
+    /// Organization to list a shared policy's assignments for
+    pub organization_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EvaluationHistoryQuery {
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+    /// Organization to list a shared policy's evaluations for
+    pub organization_id: Option<Uuid>,
+    /// Only evaluations that passed (`true`) or failed (`false`)
+    pub passed: Option<bool>,
+}
+
+/// Check every item targets exactly one of team or user, reporting all
+/// invalid items at once
+fn validate_bulk_assignments(items: &[BulkAssignmentItem]) -> std::result::Result<(), String> {
+    if items.is_empty() {
+        return Err("assignments must not be empty".to_string());
+    }
+    if items.len() > MAX_BULK_ASSIGNMENTS {
+        return Err(format!(
+            "at most {} assignments may be submitted at once",
+            MAX_BULK_ASSIGNMENTS
+        ));
+    }
+
+    let errors: Vec<String> = items
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| item.team_id.is_some() == item.user_id.is_some())
+        .map(|(index, _)| {
+            format!("assignments[{}]: provide either team_id or user_id, not both", index)
+        })
+        .collect();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("; "))
+    }
+}
+
+/// Resolve the team, user and organization ids into exactly one assignment target
+fn assignment_target(
+    team_id: Option<Uuid>,
+    user_id: Option<Uuid>,
+    organization_id: Option<Uuid>,
+) -> Result<AssignmentTarget> {
+    match (team_id, user_id, organization_id) {
+        (Some(team_id), None, None) => Ok(AssignmentTarget::Team(team_id)),
+        (None, Some(user_id), None) => Ok(AssignmentTarget::User(user_id)),
+        (None, None, Some(organization_id)) => Ok(AssignmentTarget::Organization(organization_id)),
+        _ => Err(AppError::Validation(
+            "Provide exactly one of team_id, user_id or organization_id".to_string(),
+        )),
+    }
+}
+
+/// Check `user_id` is an owner or admin of the organization whose view of
+/// `policy_id` is requested, returning that organization
+///
+/// An organization's policy is managed by its own admins. A shared policy
+/// spans organizations, so the caller names one with `requested` and sees
+/// only that organization's part of it.
+pub async fn verify_policy_admin(
+    pool: &PgPool,
+    user_id: Uuid,
+    policy_id: Uuid,
+    requested: Option<Uuid>,
+) -> Result<Uuid> {
+    let policy = policy_lookup_query(policy_id, user_id, true)
+        .build_query_as::<PolicyResponse>()
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Policy not found".to_string()))?;
+
+    let organization_id = policy.organization_id.or(requested).ok_or_else(|| {
+        AppError::Validation("organization_id is required for shared policies".to_string())
+    })?;
+    verify_org_admin(pool, user_id, organization_id).await?;
+
+    Ok(organization_id)
+}
+
+/// Check `user_id` is an owner or admin of the organization owning `policy_id`,
+/// returning the policy
+///
+/// Shared policies belong to no organization and are read-only.
+pub async fn verify_policy_editor(pool: &PgPool, user_id: Uuid, policy_id: Uuid) -> Result<PolicyResponse> {
+    let policy = policy_lookup_query(policy_id, user_id, true)
+        .build_query_as::<PolicyResponse>()
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Policy not found".to_string()))?;
+
+    let organization_id = policy.organization_id.ok_or(AppError::Forbidden)?;
+    verify_org_admin(pool, user_id, organization_id).await?;
+
+    Ok(policy)
+}
+
+/// Check `user_id` may assign `policy_id` to `target`
+///
+/// The policy must be active and visible to the caller, and the caller must be
+/// an owner or admin of an organization the target belongs to; that must be
+/// the policy's own organization unless the policy is shared.
+async fn verify_can_assign(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    policy_id: Uuid,
+    target: AssignmentTarget,
+) -> Result<()> {
+    let policy = policy_lookup_query(policy_id, user_id, false)
+        .build_query_as::<PolicyResponse>()
+        .fetch_optional(&mut *conn)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Policy not found".to_string()))?;
+
+    let (allowed,): (bool,) = assign_permission_query(user_id, policy.organization_id, target)
+        .build_query_as()
+        .fetch_one(&mut *conn)
+        .await?;
+
+    if allowed {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden)
+    }
+}
+
+/// Whether `user_id` administers an organization `target` belongs to,
+/// restricted to `policy_organization` when the policy has one
+fn assign_permission_query(
+    user_id: Uuid,
+    policy_organization: Option<Uuid>,
+    target: AssignmentTarget,
+) -> DynamicQuery<'static> {
+    let mut query = DynamicQuery::new(
+        "SELECT EXISTS (SELECT 1 FROM organization_members WHERE role IN ('owner', 'admin')",
+    );
+    query.push(" AND user_id = ").push_bind(user_id);
+    if let Some(organization_id) = policy_organization {
+        query.push(" AND organization_id = ").push_bind(organization_id);
+    }
+    match target {
+        AssignmentTarget::Team(team_id) => {
+            query
+                .push(" AND organization_id = (SELECT organization_id FROM teams WHERE id = ")
+                .push_bind(team_id)
+                .push(")");
+        }
+        AssignmentTarget::User(target_user_id) => {
+            query
+                .push(" AND organization_id IN (SELECT organization_id FROM organization_members WHERE user_id = ")
+                .push_bind(target_user_id)
+                .push(")");
+        }
+        AssignmentTarget::Organization(organization_id) => {
+            query.push(" AND organization_id = ").push_bind(organization_id);
+        }
+    }
+    query.push(")");
+    query
+}
+
 fn is_valid_policy_type(policy_type: &str) -> bool {
     matches!(
         policy_type,
@@ -396,19 +959,95 @@ fn is_valid_enforcement_level(level: &str) -> bool {
     matches!(level, "strict" | "warning" | "monitor")
 }
 
-fn evaluate_policy_rules(
+/// Load the org memberships, team memberships, recent spend and request
+/// rate of a user
+async fn load_subject_attributes(pool: &PgPool, user_id: Uuid, window_days: i64) -> Result<SubjectAttributes> {
+    let organization_ids: Vec<Uuid> = sqlx::query_scalar(
+        "SELECT organization_id FROM organization_members WHERE user_id = $1",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    let team_ids: Vec<Uuid> = sqlx::query_scalar("SELECT team_id FROM team_members WHERE user_id = $1")
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+    let (recent_spend,): (f64,) = sqlx::query_as(
+        r#"
+        SELECT COALESCE(SUM(total_cost), 0)::FLOAT8
+        FROM llm_requests
+        WHERE user_id = $1
+        AND timestamp >= NOW() - make_interval(days => $2)
+        "#,
+    )
+    .bind(user_id)
+    .bind(window_days as i32)
+    .fetch_one(pool)
+    .await?;
+
+    let requests_last_minute: i64 = sqlx::query_scalar(
+        r#"
+        SELECT COUNT(*)
+        FROM llm_metrics
+        WHERE user_id = $1
+        AND time >= NOW() - INTERVAL '1 minute'
+        "#,
+    )
+    .bind(user_id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(SubjectAttributes {
+        user_id,
+        organization_ids,
+        team_ids,
+        recent_spend,
+        spend_window_days: window_days,
+        requests_last_minute,
+    })
+}
+
+/// Strip any caller-supplied reserved namespace, then merge the loaded
+/// subject attributes under it
+pub fn prepare_context(context: &serde_json::Value, subject: Option<&SubjectAttributes>) -> serde_json::Value {
+    let mut map = match context {
+        serde_json::Value::Object(map) => map.clone(),
+        _ => serde_json::Map::new(),
+    };
+    map.remove(SUBJECT_CONTEXT_KEY);
+
+    if let Some(subject) = subject {
+        map.insert(
+            SUBJECT_CONTEXT_KEY.to_string(),
+            serde_json::to_value(subject).unwrap_or(serde_json::Value::Null),
+        );
+    }
+
+    serde_json::Value::Object(map)
+}
+
+/// Look up a dotted path such as `_subject.recent_spend` in the context
+fn context_value<'a>(context: &'a serde_json::Value, path: &str) -> Option<&'a serde_json::Value> {
+    path.split('.').try_fold(context, |value, key| value.get(key))
+}
+
+pub fn evaluate_policy_rules(
     policy: &PolicyResponse,
     context: &serde_json::Value,
+    explain: bool,
 ) -> Result<EvaluationResult> {
     let mut violations = Vec::new();
     let mut warnings = Vec::new();
+    let mut trace = Vec::new();
 
     // Policy evaluation logic based on policy type
     match policy.policy_type.as_str() {
-        "cost" => evaluate_cost_policy(&policy.rules, context, &mut violations, &mut warnings)?,
-        "rate_limit" => evaluate_rate_limit_policy(&policy.rules, context, &mut violations, &mut warnings)?,
-        "usage" => evaluate_usage_policy(&policy.rules, context, &mut violations, &mut warnings)?,
-        "content_filter" => evaluate_content_filter_policy(&policy.rules, context, &mut violations, &mut warnings)?,
+        "cost" => evaluate_cost_policy(&policy.rules, context, &mut violations, &mut warnings, &mut trace)?,
+        "rate_limit" => evaluate_rate_limit_policy(&policy.rules, context, &mut violations, &mut warnings, &mut trace)?,
+        "usage" => evaluate_usage_policy(&policy.rules, context, &mut violations, &mut warnings, &mut trace)?,
+        "content_filter" => evaluate_content_filter_policy(&policy.rules, context, &mut violations, &mut warnings, &mut trace)?,
         _ => {}
     }
 
@@ -416,26 +1055,83 @@ fn evaluate_policy_rules(
         passed: violations.is_empty(),
         violations,
         warnings,
+        explain: explain.then_some(trace),
     })
 }
 
+/// Trace entry for a configured rule whose context field was missing
+fn not_evaluated(rule: &str, field: &str, threshold: serde_json::Value) -> RuleTrace {
+    RuleTrace {
+        rule: rule.to_string(),
+        context_field: field.to_string(),
+        input: None,
+        threshold,
+        outcome: RuleOutcome::NotEvaluated,
+        reason: Some(format!("context field '{}' is missing or has the wrong type", field)),
+    }
+}
+
+/// Trace entry for a rule that was checked against the context
+fn evaluated(
+    rule: &str,
+    field: &str,
+    input: serde_json::Value,
+    threshold: serde_json::Value,
+    passed: bool,
+) -> RuleTrace {
+    RuleTrace {
+        rule: rule.to_string(),
+        context_field: field.to_string(),
+        input: Some(input),
+        threshold,
+        outcome: if passed { RuleOutcome::Passed } else { RuleOutcome::Failed },
+        reason: None,
+    }
+}
+
 fn evaluate_cost_policy(
     rules: &serde_json::Value,
     context: &serde_json::Value,
     violations: &mut Vec<PolicyViolation>,
     _warnings: &mut Vec<String>,
+    trace: &mut Vec<RuleTrace>,
 ) -> Result<()> {
     if let Some(max_cost) = rules.get("max_cost_per_request").and_then(|v| v.as_f64()) {
-        if let Some(actual_cost) = context.get("cost").and_then(|v| v.as_f64()) {
-            if actual_cost > max_cost {
-                violations.push(PolicyViolation {
-                    policy_id: Uuid::nil(),
-                    policy_name: "Cost Policy".to_string(),
-                    rule_violated: "max_cost_per_request".to_string(),
-                    severity: "high".to_string(),
-                    message: format!("Cost ${:.4} exceeds maximum ${:.4}", actual_cost, max_cost),
-                });
+        match context.get("cost").and_then(|v| v.as_f64()) {
+            Some(actual_cost) => {
+                let passed = actual_cost <= max_cost;
+                trace.push(evaluated("max_cost_per_request", "cost", actual_cost.into(), max_cost.into(), passed));
+                if !passed {
+                    violations.push(PolicyViolation {
+                        policy_id: Uuid::nil(),
+                        policy_name: "Cost Policy".to_string(),
+                        rule_violated: "max_cost_per_request".to_string(),
+                        severity: "high".to_string(),
+                        message: format!("Cost ${:.4} exceeds maximum ${:.4}", actual_cost, max_cost),
+                    });
+                }
+            }
+            None => trace.push(not_evaluated("max_cost_per_request", "cost", max_cost.into())),
+        }
+    }
+
+    if let Some(max_spend) = rules.get("max_recent_spend").and_then(|v| v.as_f64()) {
+        let field = "_subject.recent_spend";
+        match context_value(context, field).and_then(|v| v.as_f64()) {
+            Some(recent_spend) => {
+                let passed = recent_spend <= max_spend;
+                trace.push(evaluated("max_recent_spend", field, recent_spend.into(), max_spend.into(), passed));
+                if !passed {
+                    violations.push(PolicyViolation {
+                        policy_id: Uuid::nil(),
+                        policy_name: "Cost Policy".to_string(),
+                        rule_violated: "max_recent_spend".to_string(),
+                        severity: "high".to_string(),
+                        message: format!("Recent spend ${:.2} exceeds maximum ${:.2}", recent_spend, max_spend),
+                    });
+                }
             }
+            None => trace.push(not_evaluated("max_recent_spend", field, max_spend.into())),
         }
     }
 
@@ -447,18 +1143,36 @@ fn evaluate_rate_limit_policy(
     context: &serde_json::Value,
     violations: &mut Vec<PolicyViolation>,
     _warnings: &mut Vec<String>,
+    trace: &mut Vec<RuleTrace>,
 ) -> Result<()> {
     if let Some(max_requests) = rules.get("max_requests_per_minute").and_then(|v| v.as_i64()) {
-        if let Some(current_requests) = context.get("requests_per_minute").and_then(|v| v.as_i64()) {
-            if current_requests > max_requests {
-                violations.push(PolicyViolation {
-                    policy_id: Uuid::nil(),
-                    policy_name: "Rate Limit Policy".to_string(),
-                    rule_violated: "max_requests_per_minute".to_string(),
-                    severity: "high".to_string(),
-                    message: format!("Request count {} exceeds limit {}", current_requests, max_requests),
-                });
+        // A server-loaded count is authoritative; the caller-supplied value is
+        // only used by stateless callers that did not request enrichment
+        let observed = ["_subject.requests_last_minute", "requests_per_minute"]
+            .into_iter()
+            .find_map(|field| context_value(context, field).and_then(|v| v.as_i64()).map(|n| (field, n)));
+
+        match observed {
+            Some((field, current_requests)) => {
+                let passed = current_requests <= max_requests;
+                trace.push(evaluated(
+                    "max_requests_per_minute",
+                    field,
+                    current_requests.into(),
+                    max_requests.into(),
+                    passed,
+                ));
+                if !passed {
+                    violations.push(PolicyViolation {
+                        policy_id: Uuid::nil(),
+                        policy_name: "Rate Limit Policy".to_string(),
+                        rule_violated: "max_requests_per_minute".to_string(),
+                        severity: "high".to_string(),
+                        message: format!("Request count {} exceeds limit {}", current_requests, max_requests),
+                    });
+                }
             }
+            None => trace.push(not_evaluated("max_requests_per_minute", "requests_per_minute", max_requests.into())),
         }
     }
 
@@ -470,18 +1184,24 @@ fn evaluate_usage_policy(
     context: &serde_json::Value,
     violations: &mut Vec<PolicyViolation>,
     _warnings: &mut Vec<String>,
+    trace: &mut Vec<RuleTrace>,
 ) -> Result<()> {
     if let Some(max_tokens) = rules.get("max_tokens_per_request").and_then(|v| v.as_i64()) {
-        if let Some(actual_tokens) = context.get("tokens").and_then(|v| v.as_i64()) {
-            if actual_tokens > max_tokens {
-                violations.push(PolicyViolation {
-                    policy_id: Uuid::nil(),
-                    policy_name: "Usage Policy".to_string(),
-                    rule_violated: "max_tokens_per_request".to_string(),
-                    severity: "medium".to_string(),
-                    message: format!("Token count {} exceeds limit {}", actual_tokens, max_tokens),
-                });
+        match context.get("tokens").and_then(|v| v.as_i64()) {
+            Some(actual_tokens) => {
+                let passed = actual_tokens <= max_tokens;
+                trace.push(evaluated("max_tokens_per_request", "tokens", actual_tokens.into(), max_tokens.into(), passed));
+                if !passed {
+                    violations.push(PolicyViolation {
+                        policy_id: Uuid::nil(),
+                        policy_name: "Usage Policy".to_string(),
+                        rule_violated: "max_tokens_per_request".to_string(),
+                        severity: "medium".to_string(),
+                        message: format!("Token count {} exceeds limit {}", actual_tokens, max_tokens),
+                    });
+                }
             }
+            None => trace.push(not_evaluated("max_tokens_per_request", "tokens", max_tokens.into())),
         }
     }
 
@@ -493,29 +1213,37 @@ fn evaluate_content_filter_policy(
     context: &serde_json::Value,
     violations: &mut Vec<PolicyViolation>,
     _warnings: &mut Vec<String>,
+    trace: &mut Vec<RuleTrace>,
 ) -> Result<()> {
     if let Some(blocked_patterns) = rules.get("blocked_patterns").and_then(|v| v.as_array()) {
-        if let Some(content) = context.get("content").and_then(|v| v.as_str()) {
-            for pattern in blocked_patterns {
-                if let Some(pattern_str) = pattern.as_str() {
-                    if content.contains(pattern_str) {
-                        violations.push(PolicyViolation {
-                            policy_id: Uuid::nil(),
-                            policy_name: "Content Filter Policy".to_string(),
-                            rule_violated: "blocked_patterns".to_string(),
-                            severity: "high".to_string(),
-                            message: format!("Content contains blocked pattern: {}", pattern_str),
-                        });
+        let threshold = serde_json::Value::Array(blocked_patterns.clone());
+        match context.get("content").and_then(|v| v.as_str()) {
+            Some(content) => {
+                let violations_before = violations.len();
+                for pattern in blocked_patterns {
+                    if let Some(pattern_str) = pattern.as_str() {
+                        if content.contains(pattern_str) {
+                            violations.push(PolicyViolation {
+                                policy_id: Uuid::nil(),
+                                policy_name: "Content Filter Policy".to_string(),
+                                rule_violated: "blocked_patterns".to_string(),
+                                severity: "high".to_string(),
+                                message: format!("Content contains blocked pattern: {}", pattern_str),
+                            });
+                        }
                     }
                 }
+                let passed = violations.len() == violations_before;
+                trace.push(evaluated("blocked_patterns", "content", content.into(), threshold, passed));
             }
+            None => trace.push(not_evaluated("blocked_patterns", "content", threshold)),
         }
     }
 
     Ok(())
 }
 
-fn extract_user_id(req: &HttpRequest) -> Result<Uuid> {
+pub(crate) fn extract_user_id(req: &HttpRequest) -> Result<Uuid> {
     req.headers()
         .get("X-User-Id")
         .and_then(|h| h.to_str().ok())
@@ -531,5 +1259,266 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
         .service(delete_policy)
         .service(evaluate_policy)
         .service(assign_policy)
+        .service(bulk_assign_policies)
+        .service(list_policy_assignments)
+        .service(list_policy_evaluations)
+        .service(unassign_policy)
         .service(get_policy_violations);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The query `list_policies` pages through [`paginate`]
+    fn policy_page_query(query: &PolicyQuery, user_id: Uuid, limit: u32, offset: u32) -> DynamicQuery<'static> {
+        let mut page = ordered_policies(query, user_id);
+        Page::new(Some(limit), Some(offset)).apply(&mut page);
+        page
+    }
+
+    fn policy_query(policy_type: Option<&str>, status: Option<&str>) -> PolicyQuery {
+        PolicyQuery {
+            limit: None,
+            offset: None,
+            organization_id: None,
+            policy_type: policy_type.map(String::from),
+            status: status.map(String::from),
+        }
+    }
+
+    const VISIBLE: &str = "(organization_id IS NULL OR organization_id IN \
+        (SELECT organization_id FROM organization_members WHERE user_id = $1))";
+
+    #[test]
+    fn test_total_applies_the_page_filters() {
+        let user_id = Uuid::new_v4();
+        for query in [
+            policy_query(None, None),
+            policy_query(Some("cost"), None),
+            policy_query(None, Some("active")),
+            policy_query(Some("cost"), Some("active")),
+        ] {
+            let page = policy_page_query(&query, user_id, 20, 0);
+            let total = filtered_policies("SELECT COUNT(*) FROM policies", &query, user_id);
+
+            let page_where = page.sql().split(" FROM policies").nth(1).unwrap();
+            let total_where = total.sql().split(" FROM policies").nth(1).unwrap();
+            assert!(page_where.starts_with(total_where), "{} vs {}", page_where, total_where);
+        }
+
+        let total = filtered_policies("SELECT COUNT(*) FROM policies", &policy_query(Some("cost"), Some("active")), user_id);
+        assert_eq!(
+            total.sql(),
+            format!("SELECT COUNT(*) FROM policies WHERE {} AND policy_type = $2 AND status = $3", VISIBLE)
+        );
+    }
+
+    #[test]
+    fn test_listing_is_limited_to_callers_organizations() {
+        let mut query = policy_query(None, None);
+        query.organization_id = Some(Uuid::new_v4());
+
+        let page = policy_page_query(&query, Uuid::new_v4(), 20, 0);
+        assert!(page.sql().contains(&format!("WHERE {} AND organization_id = $2 ORDER BY", VISIBLE)));
+    }
+
+    #[test]
+    fn test_page_order_has_unique_tiebreaker() {
+        let page = policy_page_query(&policy_query(Some("cost"), None), Uuid::new_v4(), 10, 30);
+        assert!(page
+            .sql()
+            .ends_with("AND policy_type = $2 ORDER BY created_at DESC, id DESC LIMIT $3 OFFSET $4"));
+    }
+
+    #[test]
+    fn test_lookup_hides_inactive_unless_requested() {
+        let (policy_id, user_id) = (Uuid::new_v4(), Uuid::new_v4());
+
+        let default = policy_lookup_query(policy_id, user_id, false);
+        assert!(default.sql().ends_with(&format!("WHERE id = $1 AND {} AND status <> $3", VISIBLE.replace("$1", "$2"))));
+
+        let included = policy_lookup_query(policy_id, user_id, true);
+        assert!(included.sql().ends_with(&format!("WHERE id = $1 AND {}", VISIBLE.replace("$1", "$2"))));
+    }
+
+    fn item(team: bool, user: bool) -> BulkAssignmentItem {
+        BulkAssignmentItem {
+            policy_id: Uuid::new_v4(),
+            team_id: team.then(Uuid::new_v4),
+            user_id: user.then(Uuid::new_v4),
+        }
+    }
+
+    #[test]
+    fn test_valid_bulk_batch() {
+        let items = vec![item(true, false), item(false, true)];
+        assert!(validate_bulk_assignments(&items).is_ok());
+    }
+
+    #[test]
+    fn test_mixed_bulk_batch_is_rejected_as_a_whole() {
+        let items = vec![
+            item(true, false),
+            item(true, true),
+            item(false, true),
+            item(false, false),
+        ];
+
+        let err = validate_bulk_assignments(&items).unwrap_err();
+        assert!(err.contains("assignments[1]"));
+        assert!(err.contains("assignments[3]"));
+        assert!(!err.contains("assignments[0]"));
+        assert!(!err.contains("assignments[2]"));
+    }
+
+    #[test]
+    fn test_assignment_target_requires_exactly_one() {
+        let id = Uuid::new_v4();
+        assert_eq!(assignment_target(Some(id), None, None).unwrap(), AssignmentTarget::Team(id));
+        assert_eq!(assignment_target(None, Some(id), None).unwrap(), AssignmentTarget::User(id));
+        assert_eq!(
+            assignment_target(None, None, Some(id)).unwrap(),
+            AssignmentTarget::Organization(id)
+        );
+        assert!(assignment_target(Some(id), Some(id), None).is_err());
+        assert!(assignment_target(None, Some(id), Some(id)).is_err());
+        assert!(assignment_target(None, None, None).is_err());
+    }
+
+    #[test]
+    fn test_shared_policy_assignment_requires_admin_of_the_target_org() {
+        let (user_id, team_id) = (Uuid::new_v4(), Uuid::new_v4());
+
+        let shared = assign_permission_query(user_id, None, AssignmentTarget::Team(team_id));
+        assert!(shared.sql().contains("role IN ('owner', 'admin') AND user_id = $1"));
+        assert!(shared.sql().contains("organization_id = (SELECT organization_id FROM teams WHERE id = $2)"));
+
+        let owned = assign_permission_query(user_id, Some(Uuid::new_v4()), AssignmentTarget::User(team_id));
+        assert!(owned.sql().contains("AND organization_id = $2 AND organization_id IN \
+            (SELECT organization_id FROM organization_members WHERE user_id = $3)"));
+    }
+
+    #[test]
+    fn test_empty_and_oversized_batches_are_rejected() {
+        assert!(validate_bulk_assignments(&[]).is_err());
+
+        let items: Vec<_> = (0..=MAX_BULK_ASSIGNMENTS).map(|_| item(true, false)).collect();
+        assert!(validate_bulk_assignments(&items).is_err());
+    }
+
+    fn policy(policy_type: &str, rules: serde_json::Value) -> PolicyResponse {
+        PolicyResponse {
+            id: Uuid::new_v4(),
+            name: "test".to_string(),
+            description: None,
+            policy_type: policy_type.to_string(),
+            rules,
+            enforcement_level: "strict".to_string(),
+            status: "active".to_string(),
+            version: 1,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            created_by: None,
+            organization_id: None,
+        }
+    }
+
+    #[test]
+    fn test_explain_lists_missing_context_field_as_not_evaluated() {
+        let policy = policy("cost", serde_json::json!({"max_cost_per_request": 0.5}));
+
+        let result = evaluate_policy_rules(&policy, &serde_json::json!({"tokens": 100}), true).unwrap();
+        assert!(result.passed);
+
+        let trace = result.explain.unwrap();
+        assert_eq!(trace.len(), 1);
+        assert_eq!(trace[0].rule, "max_cost_per_request");
+        assert_eq!(trace[0].outcome, RuleOutcome::NotEvaluated);
+        assert!(trace[0].input.is_none());
+
+        let result = evaluate_policy_rules(&policy, &serde_json::json!({"cost": 0.75}), true).unwrap();
+        assert!(!result.passed);
+        assert_eq!(result.explain.unwrap()[0].outcome, RuleOutcome::Failed);
+
+        let result = evaluate_policy_rules(&policy, &serde_json::json!({"cost": 0.75}), false).unwrap();
+        assert!(result.explain.is_none());
+    }
+
+    fn subject(recent_spend: f64) -> SubjectAttributes {
+        SubjectAttributes {
+            user_id: Uuid::new_v4(),
+            organization_ids: vec![Uuid::new_v4()],
+            team_ids: vec![],
+            recent_spend,
+            spend_window_days: 30,
+            requests_last_minute: 0,
+        }
+    }
+
+    #[test]
+    fn test_enriched_spend_is_available_to_rules() {
+        let policy = policy("cost", serde_json::json!({"max_recent_spend": 100.0}));
+        let raw = serde_json::json!({"cost": 0.1});
+
+        let result = evaluate_policy_rules(&policy, &prepare_context(&raw, None), true).unwrap();
+        assert!(result.passed);
+        assert_eq!(result.explain.unwrap()[0].outcome, RuleOutcome::NotEvaluated);
+
+        let context = prepare_context(&raw, Some(&subject(250.0)));
+        assert_eq!(context["cost"], 0.1);
+        let result = evaluate_policy_rules(&policy, &context, true).unwrap();
+        assert!(!result.passed);
+        assert_eq!(result.violations[0].rule_violated, "max_recent_spend");
+        assert_eq!(result.explain.unwrap()[0].input, Some(serde_json::json!(250.0)));
+    }
+
+    #[test]
+    fn test_recorded_request_count_exceeds_rate_limit() {
+        let policy = policy("rate_limit", serde_json::json!({"max_requests_per_minute": 60}));
+        let subject = SubjectAttributes { requests_last_minute: 75, ..subject(0.0) };
+
+        // Stateless fast path: the caller's own count is used when nothing was loaded
+        let raw = serde_json::json!({"requests_per_minute": 5});
+        assert!(evaluate_policy_rules(&policy, &prepare_context(&raw, None), false).unwrap().passed);
+
+        // Under-reporting is ignored once the recorded count is available
+        let result = evaluate_policy_rules(&policy, &prepare_context(&raw, Some(&subject)), true).unwrap();
+        assert!(!result.passed);
+        assert_eq!(result.violations[0].rule_violated, "max_requests_per_minute");
+        let trace = result.explain.unwrap();
+        assert_eq!(trace[0].context_field, "_subject.requests_last_minute");
+        assert_eq!(trace[0].input, Some(serde_json::json!(75)));
+
+        // Recorded count alone is enough when the caller supplies nothing
+        let context = prepare_context(&serde_json::json!({}), Some(&subject));
+        assert!(!evaluate_policy_rules(&policy, &context, false).unwrap().passed);
+    }
+
+    #[test]
+    fn test_caller_cannot_supply_reserved_namespace() {
+        let policy = policy("cost", serde_json::json!({"max_recent_spend": 100.0}));
+        let spoofed = serde_json::json!({"_subject": {"recent_spend": 0.0}});
+
+        let context = prepare_context(&spoofed, None);
+        assert!(context.get(SUBJECT_CONTEXT_KEY).is_none());
+
+        let context = prepare_context(&spoofed, Some(&subject(500.0)));
+        assert!(!evaluate_policy_rules(&policy, &context, false).unwrap().passed);
+    }
+
+    #[test]
+    fn test_evaluation_history_is_scoped_to_the_policy() {
+        let policy_id = Uuid::new_v4();
+        let page = ordered_evaluations(policy_id, Uuid::new_v4(), Some(false));
+        assert_eq!(
+            page.sql(),
+            format!(
+                "{} WHERE policy_id = $1 AND subject_id IN \
+                 (SELECT user_id FROM organization_members WHERE organization_id = $2) \
+                 AND passed = $3 ORDER BY evaluated_at DESC, id DESC",
+                EVALUATION_SELECT
+            )
+        );
+    }
+}
@@ -0,0 +1,140 @@
+//! JSON schemas for policy `rules`
+//!
+//! Each evaluated policy type reads a fixed set of rule keys, so `rules` are
+//! checked against the schema of their `policy_type` when a policy is created
+//! or updated. Unknown keys are rejected: the evaluator would ignore a
+//! misspelled key and leave the policy a silent no-op.
+
+use jsonschema::error::ValidationErrorKind;
+use jsonschema::JSONSchema;
+use llm_governance_common::error::FieldErrors;
+use llm_governance_common::{AppError, Result};
+use serde_json::{json, Value};
+
+/// Schema of the `rules` of `policy_type`, matching what its evaluator reads
+fn rules_schema(policy_type: &str) -> Value {
+    let properties = match policy_type {
+        "cost" => json!({
+            "max_cost_per_request": { "type": "number", "minimum": 0 },
+            "max_recent_spend": { "type": "number", "minimum": 0 },
+        }),
+        "rate_limit" => json!({
+            "max_requests_per_minute": { "type": "integer", "minimum": 1 },
+        }),
+        "usage" => json!({
+            "max_tokens_per_request": { "type": "integer", "minimum": 1 },
+        }),
+        "content_filter" => json!({
+            "blocked_patterns": {
+                "type": "array",
+                "items": { "type": "string", "minLength": 1 },
+            },
+        }),
+        // Security and compliance rules are not evaluated, so any object is kept as is
+        _ => return json!({ "type": "object" }),
+    };
+
+    json!({
+        "type": "object",
+        "properties": properties,
+        "additionalProperties": false,
+    })
+}
+
+/// Check `rules` against the schema of `policy_type`, reporting every offending key
+pub(crate) fn validate_rules(policy_type: &str, rules: &Value) -> Result<()> {
+    let schema = rules_schema(policy_type);
+    let compiled = JSONSchema::compile(&schema)
+        .map_err(|e| AppError::Internal(format!("Invalid {} rules schema: {}", policy_type, e)))?;
+
+    let Err(errors) = compiled.validate(rules) else {
+        return Ok(());
+    };
+
+    let mut fields = FieldErrors::new();
+    for error in errors {
+        let path = field_path(&error.instance_path.to_string());
+        match &error.kind {
+            ValidationErrorKind::AdditionalProperties { unexpected } => {
+                for key in unexpected {
+                    fields.entry(format!("{}.{}", path, key)).or_default().push(format!(
+                        "unknown {} rule, expected one of: {}",
+                        policy_type,
+                        known_rules(&schema)
+                    ));
+                }
+            }
+            _ => fields.entry(path).or_default().push(error.to_string()),
+        }
+    }
+
+    Err(AppError::FieldValidation(fields))
+}
+
+/// Request field of a JSON pointer into `rules`, e.g. `/blocked_patterns/0`
+/// becomes `rules.blocked_patterns[0]`
+fn field_path(pointer: &str) -> String {
+    pointer
+        .split('/')
+        .skip(1)
+        .fold("rules".to_string(), |path, segment| match segment.parse::<usize>() {
+            Ok(index) => format!("{}[{}]", path, index),
+            Err(_) => format!("{}.{}", path, segment),
+        })
+}
+
+fn known_rules(schema: &Value) -> String {
+    schema["properties"]
+        .as_object()
+        .map(|properties| properties.keys().map(String::as_str).collect::<Vec<_>>().join(", "))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field_errors(policy_type: &str, rules: Value) -> FieldErrors {
+        match validate_rules(policy_type, &rules) {
+            Err(AppError::FieldValidation(fields)) => fields,
+            other => panic!("expected field errors, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_valid_cost_rule_is_accepted() {
+        let rules = json!({"max_cost_per_request": 0.5, "max_recent_spend": 100});
+        assert!(validate_rules("cost", &rules).is_ok());
+        assert!(validate_rules("cost", &json!({})).is_ok());
+    }
+
+    #[test]
+    fn test_misspelled_rule_key_is_rejected() {
+        let fields = field_errors("cost", json!({"max_cost_per_requst": 0.5}));
+
+        let messages = &fields["rules.max_cost_per_requst"];
+        assert!(messages[0].contains("unknown cost rule"));
+        assert!(messages[0].contains("max_cost_per_request"));
+        assert_eq!(fields.len(), 1);
+    }
+
+    #[test]
+    fn test_errors_point_at_the_offending_value() {
+        let fields = field_errors("content_filter", json!({"blocked_patterns": ["ssn", 42]}));
+        assert!(fields.contains_key("rules.blocked_patterns[1]"));
+
+        let fields = field_errors("rate_limit", json!({"max_requests_per_minute": "60"}));
+        assert!(fields.contains_key("rules.max_requests_per_minute"));
+
+        let fields = field_errors("usage", json!([1, 2]));
+        assert!(fields.contains_key("rules"));
+    }
+
+    #[test]
+    fn test_unevaluated_policy_types_accept_any_object() {
+        let rules = json!({"require_mfa": true, "session_timeout_minutes": 480});
+        assert!(validate_rules("security", &rules).is_ok());
+        assert!(validate_rules("compliance", &rules).is_ok());
+        assert!(validate_rules("security", &json!("mfa")).is_err());
+    }
+}
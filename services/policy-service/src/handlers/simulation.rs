@@ -0,0 +1,387 @@
+use actix_web::{post, web, HttpRequest, HttpResponse, Responder};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+use llm_governance_common::{AppError, Result, ApiResponse};
+use llm_governance_common::utils::parse_range;
+
+use super::policies::{evaluate_policy_rules, extract_user_id, PolicyResponse};
+
+/// Upper bound on historical requests replayed in one simulation
+const MAX_SIMULATED_REQUESTS: i64 = 100_000;
+
+/// Number of users reported in `top_offenders`
+const TOP_OFFENDERS: usize = 10;
+
+#[derive(Debug, Deserialize)]
+pub struct SimulateAssignedRequest {
+    pub organization_id: Uuid,
+    pub from: String,
+    pub to: String,
+}
+
+/// A historical LLM request replayed through the policy evaluator
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct HistoricalRequest {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub team_id: Option<Uuid>,
+    pub model: String,
+    pub total_tokens: i64,
+    pub total_cost: f64,
+}
+
+impl HistoricalRequest {
+    /// Evaluation context using the same field names as live evaluation
+    fn context(&self) -> serde_json::Value {
+        serde_json::json!({
+            "cost": self.total_cost,
+            "tokens": self.total_tokens,
+            "model": self.model,
+            "user_id": self.user_id,
+            "team_id": self.team_id,
+        })
+    }
+}
+
+/// An active policy together with the teams and users it is assigned to
+#[derive(Debug)]
+pub struct AssignedPolicy {
+    pub policy: PolicyResponse,
+    pub team_ids: HashSet<Uuid>,
+    pub user_ids: HashSet<Uuid>,
+}
+
+impl AssignedPolicy {
+    fn applies_to(&self, request: &HistoricalRequest) -> bool {
+        self.user_ids.contains(&request.user_id)
+            || request.team_id.is_some_and(|team_id| self.team_ids.contains(&team_id))
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct PolicySimulation {
+    pub policy_id: Uuid,
+    pub policy_name: String,
+    pub enforcement_level: String,
+    /// Requests the policy was assigned to
+    pub evaluated_requests: usize,
+    /// Requests with at least one violation
+    pub violating_requests: usize,
+    /// Violating requests that strict enforcement would have rejected
+    pub blocked_requests: usize,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct Offender {
+    pub user_id: Uuid,
+    pub blocked_requests: usize,
+    pub violating_requests: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AssignedSimulationReport {
+    pub total_requests: usize,
+    /// Requests blocked by at least one strict policy
+    pub blocked_requests: usize,
+    /// Requests violating at least one policy, whatever its enforcement level
+    pub violating_requests: usize,
+    pub policies: Vec<PolicySimulation>,
+    pub top_offenders: Vec<Offender>,
+    /// The window held more than `max_requests` requests and only the
+    /// earliest were replayed
+    pub truncated: bool,
+    pub max_requests: i64,
+}
+
+/// Replay the currently assigned policy set over a historical window
+///
+/// POST /api/v1/policies/simulate-assigned
+#[post("/policies/simulate-assigned")]
+pub async fn simulate_assigned_policies(
+    pool: web::Data<PgPool>,
+    req: web::Json<SimulateAssignedRequest>,
+    http_req: HttpRequest,
+) -> Result<impl Responder> {
+    let user_id = extract_user_id(&http_req)?;
+    verify_org_admin(pool.get_ref(), user_id, req.organization_id).await?;
+
+    let range = parse_range("from", &req.from, "to", &req.to)?;
+    let (from, to): (DateTime<Utc>, DateTime<Utc>) = (range.start.into(), range.end.into());
+    let policies = load_assigned_policies(pool.get_ref(), req.organization_id).await?;
+
+    // One row past the cap tells whether the window was cut short
+    let mut requests = sqlx::query_as::<_, HistoricalRequest>(
+        r#"
+        SELECT r.id, r.user_id, r.team_id, m.model_name as model,
+               r.total_tokens::BIGINT as total_tokens, r.total_cost::FLOAT8 as total_cost
+        FROM llm_requests r
+        JOIN llm_models m ON r.model_id = m.id
+        WHERE r.organization_id = $1
+        AND r.timestamp >= $2 AND r.timestamp <= $3
+        ORDER BY r.timestamp
+        LIMIT $4
+        "#,
+    )
+    .bind(req.organization_id)
+    .bind(from)
+    .bind(to)
+    .bind(MAX_SIMULATED_REQUESTS + 1)
+    .fetch_all(pool.get_ref())
+    .await?;
+    let truncated = requests.len() as i64 > MAX_SIMULATED_REQUESTS;
+    requests.truncate(MAX_SIMULATED_REQUESTS as usize);
+
+    let mut report = simulate_assigned(&policies, &requests)?;
+    report.truncated = truncated;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(report)))
+}
+
+/// Replay `requests` through one policy, returning its summary and the
+/// indices of the requests it found in violation
+pub fn simulate_policy(
+    assigned: &AssignedPolicy,
+    requests: &[HistoricalRequest],
+) -> Result<(PolicySimulation, Vec<usize>)> {
+    let strict = assigned.policy.enforcement_level == "strict";
+    let mut evaluated_requests = 0;
+    let mut violating = Vec::new();
+
+    for (index, request) in requests.iter().enumerate() {
+        if !assigned.applies_to(request) {
+            continue;
+        }
+        evaluated_requests += 1;
+
+        let result = evaluate_policy_rules(&assigned.policy, &request.context(), false)?;
+        if !result.passed {
+            violating.push(index);
+        }
+    }
+
+    let summary = PolicySimulation {
+        policy_id: assigned.policy.id,
+        policy_name: assigned.policy.name.clone(),
+        enforcement_level: assigned.policy.enforcement_level.clone(),
+        evaluated_requests,
+        violating_requests: violating.len(),
+        blocked_requests: if strict { violating.len() } else { 0 },
+    };
+
+    Ok((summary, violating))
+}
+
+/// Replay `requests` through every assigned policy and aggregate the results
+pub fn simulate_assigned(
+    policies: &[AssignedPolicy],
+    requests: &[HistoricalRequest],
+) -> Result<AssignedSimulationReport> {
+    let mut summaries = Vec::with_capacity(policies.len());
+    let mut violating: HashSet<usize> = HashSet::new();
+    let mut blocked: HashSet<usize> = HashSet::new();
+
+    for assigned in policies {
+        let (summary, indices) = simulate_policy(assigned, requests)?;
+        if summary.blocked_requests > 0 {
+            blocked.extend(indices.iter().copied());
+        }
+        violating.extend(indices);
+        summaries.push(summary);
+    }
+
+    let mut per_user: HashMap<Uuid, (usize, usize)> = HashMap::new();
+    for &index in &violating {
+        let entry = per_user.entry(requests[index].user_id).or_default();
+        entry.1 += 1;
+        if blocked.contains(&index) {
+            entry.0 += 1;
+        }
+    }
+
+    let mut top_offenders: Vec<Offender> = per_user
+        .into_iter()
+        .map(|(user_id, (blocked_requests, violating_requests))| Offender {
+            user_id,
+            blocked_requests,
+            violating_requests,
+        })
+        .collect();
+    top_offenders.sort_by(|a, b| {
+        b.blocked_requests
+            .cmp(&a.blocked_requests)
+            .then(b.violating_requests.cmp(&a.violating_requests))
+            .then(a.user_id.cmp(&b.user_id))
+    });
+    top_offenders.truncate(TOP_OFFENDERS);
+
+    Ok(AssignedSimulationReport {
+        total_requests: requests.len(),
+        blocked_requests: blocked.len(),
+        violating_requests: violating.len(),
+        policies: summaries,
+        top_offenders,
+        truncated: false,
+        max_requests: MAX_SIMULATED_REQUESTS,
+    })
+}
+
+/// Load active policies assigned to teams or members of the organization
+async fn load_assigned_policies(pool: &PgPool, organization_id: Uuid) -> Result<Vec<AssignedPolicy>> {
+    let rows: Vec<(Uuid, Option<Uuid>, Option<Uuid>)> = sqlx::query_as(
+        r#"
+        SELECT pa.policy_id, pa.team_id, pa.user_id
+        FROM policy_assignments pa
+        JOIN policies p ON pa.policy_id = p.id
+        LEFT JOIN teams t ON pa.team_id = t.id
+        LEFT JOIN organization_members om
+            ON pa.user_id = om.user_id AND om.organization_id = $1
+        WHERE p.status = 'active'
+        AND (p.organization_id IS NULL OR p.organization_id = $1)
+        AND (t.organization_id = $1 OR om.organization_id IS NOT NULL)
+        "#,
+    )
+    .bind(organization_id)
+    .fetch_all(pool)
+    .await?;
+
+    let policy_ids: Vec<Uuid> = rows
+        .iter()
+        .map(|(policy_id, _, _)| *policy_id)
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+
+    let policies = sqlx::query_as::<_, PolicyResponse>(
+        r#"
+        SELECT id, name, description, policy_type, rules, enforcement_level, status, version, created_at, updated_at, created_by, organization_id
+        FROM policies
+        WHERE id = ANY($1)
+        ORDER BY name
+        "#,
+    )
+    .bind(&policy_ids)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(policies
+        .into_iter()
+        .map(|policy| {
+            let targets = rows.iter().filter(|(policy_id, _, _)| *policy_id == policy.id);
+            AssignedPolicy {
+                team_ids: targets.clone().filter_map(|(_, team_id, _)| *team_id).collect(),
+                user_ids: targets.filter_map(|(_, _, user_id)| *user_id).collect(),
+                policy,
+            }
+        })
+        .collect())
+}
+
+pub(crate) async fn verify_org_admin(pool: &PgPool, user_id: Uuid, organization_id: Uuid) -> Result<()> {
+    let (is_admin,): (bool,) = sqlx::query_as(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM organization_members
+            WHERE organization_id = $1 AND user_id = $2 AND role IN ('owner', 'admin')
+        )
+        "#,
+    )
+    .bind(organization_id)
+    .bind(user_id)
+    .fetch_one(pool)
+    .await?;
+
+    if is_admin {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden)
+    }
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(simulate_assigned_policies);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assigned(
+        policy_type: &str,
+        enforcement_level: &str,
+        rules: serde_json::Value,
+        team_ids: &[Uuid],
+        user_ids: &[Uuid],
+    ) -> AssignedPolicy {
+        AssignedPolicy {
+            policy: PolicyResponse {
+                id: Uuid::new_v4(),
+                name: format!("{} policy", policy_type),
+                description: None,
+                policy_type: policy_type.to_string(),
+                rules,
+                enforcement_level: enforcement_level.to_string(),
+                status: "active".to_string(),
+                version: 1,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                created_by: None,
+                organization_id: None,
+            },
+            team_ids: team_ids.iter().copied().collect(),
+            user_ids: user_ids.iter().copied().collect(),
+        }
+    }
+
+    fn request(user_id: Uuid, team_id: Option<Uuid>, total_tokens: i64, total_cost: f64) -> HistoricalRequest {
+        HistoricalRequest {
+            id: Uuid::new_v4(),
+            user_id,
+            team_id,
+            model: "gpt-4".to_string(),
+            total_tokens,
+            total_cost,
+        }
+    }
+
+    #[test]
+    fn test_simulate_two_assigned_policies() {
+        let team = Uuid::new_v4();
+        let (alice, bob, carol) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+
+        let policies = vec![
+            assigned("cost", "strict", serde_json::json!({"max_cost_per_request": 1.0}), &[team], &[]),
+            assigned("usage", "warning", serde_json::json!({"max_tokens_per_request": 1000}), &[], &[bob]),
+        ];
+
+        let requests = vec![
+            request(alice, Some(team), 100, 2.5),  // cost: blocked
+            request(alice, Some(team), 100, 3.0),  // cost: blocked
+            request(bob, Some(team), 5000, 0.5),   // usage: flagged only
+            request(bob, Some(team), 5000, 1.5),   // both: blocked
+            request(carol, None, 9000, 9.0),       // no assigned policy applies
+        ];
+
+        let report = simulate_assigned(&policies, &requests).unwrap();
+
+        assert_eq!(report.total_requests, 5);
+        assert_eq!(report.blocked_requests, 3);
+        assert_eq!(report.violating_requests, 4);
+        assert!(!report.truncated);
+
+        let cost = &report.policies[0];
+        assert_eq!((cost.evaluated_requests, cost.violating_requests, cost.blocked_requests), (4, 3, 3));
+        let usage = &report.policies[1];
+        assert_eq!((usage.evaluated_requests, usage.violating_requests, usage.blocked_requests), (2, 2, 0));
+
+        assert_eq!(
+            report.top_offenders,
+            vec![
+                Offender { user_id: alice, blocked_requests: 2, violating_requests: 2 },
+                Offender { user_id: bob, blocked_requests: 1, violating_requests: 2 },
+            ]
+        );
+    }
+}
@@ -0,0 +1,7 @@
+//! Policy management, assignment and evaluation, served by `main.rs`
+
+pub mod config;
+pub mod handlers;
+pub mod middleware;
+pub mod models;
+pub mod services;
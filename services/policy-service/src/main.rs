@@ -1,22 +1,20 @@
 use actix_web::{web, App, HttpServer};
-use tracing::{info, Level};
-use tracing_subscriber;
+use tracing::info;
 
-mod config;
-mod handlers;
-mod middleware;
-mod models;
-mod services;
-
-use config::Config;
+use policy_service::config::Config;
+use policy_service::handlers;
+use llm_governance_common::metrics::RequestMetrics;
+use llm_governance_common::idempotency;
+use llm_governance_common::request_context::RequestContext;
+use llm_governance_common::scheduler::Scheduler;
+use llm_governance_common::telemetry;
+use llm_governance_common::versioning::ApiVersioning;
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
-    tracing_subscriber::fmt()
-        .with_max_level(Level::INFO)
-        .init();
-
     dotenv::dotenv().ok();
+    telemetry::init("policy-service").expect("Failed to initialize telemetry");
+
     let config = Config::from_env().expect("Failed to load configuration");
 
     info!("Starting policy-service on {}:{}", config.host, config.port);
@@ -28,15 +26,30 @@ async fn main() -> std::io::Result<()> {
     let redis_client = redis::Client::open(config.redis_url.clone())
         .expect("Failed to create Redis client");
 
-    HttpServer::new(move || {
+    let mut scheduler = Scheduler::new();
+    let purge_pool = db_pool.clone();
+    scheduler.every(idempotency::PURGE_INTERVAL, "purge_idempotency_keys", move || {
+        let pool = purge_pool.clone();
+        async move { idempotency::purge_expired_keys(&pool).await.map(|_| ()) }
+    });
+    let scheduler = scheduler.start();
+
+    let result = HttpServer::new(move || {
         App::new()
             .app_data(web::Data::new(db_pool.clone()))
             .app_data(web::Data::new(redis_client.clone()))
             .app_data(web::Data::new(config.clone()))
+            .wrap(RequestContext)
             .wrap(tracing_actix_web::TracingLogger::default())
+            .wrap(RequestMetrics::new("policy-service"))
+            .wrap(ApiVersioning::new())
             .configure(handlers::configure)
     })
     .bind((config.host.as_str(), config.port))?
     .run()
-    .await
+    .await;
+
+    scheduler.shutdown().await;
+    telemetry::shutdown();
+    result
 }
@@ -0,0 +1,47 @@
+use uuid::Uuid;
+
+use llm_governance_common::testing::{database, seed_member, seed_organization, seed_team, seed_user};
+use policy_service::handlers::effective::load_effective_policies;
+
+#[actix_web::test]
+#[ignore] // Requires Postgres at DATABASE_URL
+async fn test_effective_policies_span_teams_and_organization() {
+    let pool = database().await;
+    let user_id = seed_user(&pool, "member").await;
+    let organization_id = seed_organization(&pool, "effective").await;
+    seed_member(&pool, organization_id, user_id, "member").await;
+
+    let mut teams = Vec::new();
+    for name in ["team-a", "team-b"] {
+        let team_id = seed_team(&pool, organization_id, name).await;
+        sqlx::query("INSERT INTO team_members (team_id, user_id) VALUES ($1, $2)")
+            .bind(team_id)
+            .bind(user_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        teams.push(team_id);
+    }
+
+    let (policy_id,): (Uuid,) = sqlx::query_as(
+        "INSERT INTO policies (name, policy_type, organization_id) VALUES ('inherited', 'usage', $1) RETURNING id",
+    )
+    .bind(organization_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    for (team_id, org_id) in [(Some(teams[0]), None), (Some(teams[1]), None), (None, Some(organization_id))] {
+        sqlx::query("INSERT INTO policy_assignments (policy_id, team_id, organization_id) VALUES ($1, $2, $3)")
+            .bind(policy_id)
+            .bind(team_id)
+            .bind(org_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+
+    let effective = load_effective_policies(&pool, user_id, None).await.unwrap();
+    assert_eq!(effective.len(), 1);
+    assert_eq!(effective[0].policy.id, policy_id);
+    assert_eq!(effective[0].sources.len(), 3);
+}
@@ -0,0 +1,6 @@
+//! Policy service tests against the migrated database at `DATABASE_URL`
+//!
+//! Run with `cargo test -p policy-service --test integration -- --ignored`.
+
+mod effective;
+mod policies;
@@ -0,0 +1,399 @@
+use actix_web::http::StatusCode;
+use actix_web::web;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use llm_governance_common::db::{paginate, Page};
+use llm_governance_common::query::DynamicQuery;
+use llm_governance_common::testing::{database, seed_member, seed_organization, seed_team, seed_user};
+use llm_governance_common::utils::hash_inputs;
+use llm_governance_common::AppError;
+use policy_service::handlers::policies::*;
+
+/// The query `list_policies` pages through [`paginate`]
+fn policy_page_query(query: &PolicyQuery, user_id: Uuid, limit: u32, offset: u32) -> DynamicQuery<'static> {
+    let mut page = ordered_policies(query, user_id);
+    Page::new(Some(limit), Some(offset)).apply(&mut page);
+    page
+}
+
+fn policy_query(policy_type: Option<&str>, status: Option<&str>) -> PolicyQuery {
+    PolicyQuery {
+        limit: None,
+        offset: None,
+        organization_id: None,
+        policy_type: policy_type.map(String::from),
+        status: status.map(String::from),
+    }
+}
+
+/// A user who is a member of a fresh organization owning one policy
+async fn seed_tenant(pool: &PgPool) -> (Uuid, Uuid) {
+    let user_id = seed_user(pool, "tenant").await;
+    let organization_id = seed_organization(pool, "tenant").await;
+    seed_member(pool, organization_id, user_id, "owner").await;
+    let (policy_id,): (Uuid,) = sqlx::query_as(
+        "INSERT INTO policies (name, policy_type, organization_id) VALUES ('tenant-policy', 'usage', $1) RETURNING id",
+    )
+    .bind(organization_id)
+    .fetch_one(pool)
+    .await
+    .unwrap();
+
+    (user_id, policy_id)
+}
+
+#[actix_web::test]
+#[ignore] // Requires Postgres at DATABASE_URL
+async fn test_pages_are_stable_for_policies_created_together() {
+    let pool = database().await;
+
+    // One transaction gives every row the same created_at
+    let mut tx = pool.begin().await.unwrap();
+    for _ in 0..5 {
+        sqlx::query("INSERT INTO policies (name, policy_type, status) VALUES ($1, 'rate_limit', 'draft')")
+            .bind(format!("page-test-{}", Uuid::new_v4()))
+            .execute(&mut *tx)
+            .await
+            .unwrap();
+    }
+    tx.commit().await.unwrap();
+
+    // Shared policies are visible to any caller
+    let user_id = Uuid::new_v4();
+    let query = policy_query(Some("rate_limit"), Some("draft"));
+    let all = policy_page_query(&query, user_id, 100, 0)
+        .build_query_as::<PolicyResponse>()
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+    let (total,): (i64,) = filtered_policies("SELECT COUNT(*) FROM policies", &query, user_id)
+        .build_query_as()
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(total as usize, all.len());
+
+    let mut paged = Vec::new();
+    for offset in 0..all.len() as u32 {
+        let page = policy_page_query(&query, user_id, 1, offset)
+            .build_query_as::<PolicyResponse>()
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        paged.extend(page.into_iter().map(|p| p.id));
+    }
+    assert_eq!(paged, all.iter().map(|p| p.id).collect::<Vec<_>>());
+}
+
+#[actix_web::test]
+#[ignore] // Requires Postgres at DATABASE_URL
+async fn test_status_filter_total_counts_only_matching_policies() {
+    let pool = database().await;
+
+    for status in ["active", "inactive"] {
+        sqlx::query("INSERT INTO policies (name, policy_type, status) VALUES ($1, 'usage', $2)")
+            .bind(format!("total-test-{}", Uuid::new_v4()))
+            .bind(status)
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+
+    // A caller outside every organization sees only shared policies
+    let (total,): (i64,) =
+        filtered_policies("SELECT COUNT(*) FROM policies", &policy_query(None, Some("active")), Uuid::new_v4())
+            .build_query_as()
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    let (active,): (i64,) =
+        sqlx::query_as("SELECT COUNT(*) FROM policies WHERE status = 'active' AND organization_id IS NULL")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    let (all,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM policies WHERE organization_id IS NULL")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+    assert_eq!(total, active);
+    assert!(total < all);
+}
+
+#[actix_web::test]
+#[ignore] // Requires Postgres at DATABASE_URL
+async fn test_deleted_policy_is_only_returned_on_request() {
+    let pool = database().await;
+    let (user_id, policy_id) = seed_tenant(&pool).await;
+
+    sqlx::query("UPDATE policies SET status = 'inactive' WHERE id = $1")
+        .bind(policy_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let hidden = policy_lookup_query(policy_id, user_id, false)
+        .build_query_as::<PolicyResponse>()
+        .fetch_optional(&pool)
+        .await
+        .unwrap();
+    assert!(hidden.is_none());
+
+    let included = policy_lookup_query(policy_id, user_id, true)
+        .build_query_as::<PolicyResponse>()
+        .fetch_optional(&pool)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(included.status, "inactive");
+}
+
+#[actix_web::test]
+#[ignore] // Requires Postgres at DATABASE_URL
+async fn test_policies_are_isolated_between_organizations() {
+    let pool = database().await;
+
+    // Both tenants can use the same policy name
+    let (alice, alice_policy) = seed_tenant(&pool).await;
+    let (bob, bob_policy) = seed_tenant(&pool).await;
+
+    let listed: Vec<Uuid> = policy_page_query(&policy_query(None, None), alice, 100, 0)
+        .build_query_as::<PolicyResponse>()
+        .fetch_all(&pool)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|p| p.id)
+        .collect();
+    assert!(listed.contains(&alice_policy));
+    assert!(!listed.contains(&bob_policy));
+
+    assert!(verify_policy_admin(&pool, alice, alice_policy, None).await.is_ok());
+    assert!(matches!(
+        verify_policy_admin(&pool, alice, bob_policy, None).await,
+        Err(AppError::NotFound(_))
+    ));
+    assert!(verify_policy_admin(&pool, bob, bob_policy, None).await.is_ok());
+}
+
+#[actix_web::test]
+#[ignore] // Requires Postgres at DATABASE_URL
+async fn test_policy_admin_must_administer_the_policy_organization() {
+    let pool = database().await;
+    let (owner, policy_id) = seed_tenant(&pool).await;
+    let (other_admin, _) = seed_tenant(&pool).await;
+
+    // An admin of another organization who is only a member of this one
+    let (organization_id,): (Uuid,) = sqlx::query_as("SELECT organization_id FROM policies WHERE id = $1")
+        .bind(policy_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    seed_member(&pool, organization_id, other_admin, "member").await;
+
+    assert_eq!(
+        verify_policy_admin(&pool, owner, policy_id, None).await.unwrap(),
+        organization_id
+    );
+    assert!(matches!(
+        verify_policy_admin(&pool, other_admin, policy_id, None).await,
+        Err(AppError::Forbidden)
+    ));
+}
+
+#[actix_web::test]
+#[ignore] // Requires Postgres at DATABASE_URL
+async fn test_shared_policy_admin_names_an_organization() {
+    let pool = database().await;
+    let (owner, owned_policy) = seed_tenant(&pool).await;
+    let (_, other_policy) = seed_tenant(&pool).await;
+    let (shared_policy,): (Uuid,) = sqlx::query_as(
+        "INSERT INTO policies (name, policy_type) VALUES ($1, 'usage') RETURNING id",
+    )
+    .bind(format!("shared-{}", Uuid::new_v4()))
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    let organization_of = |policy_id: Uuid| {
+        let pool = pool.clone();
+        async move {
+            let (organization_id,): (Uuid,) =
+                sqlx::query_as("SELECT organization_id FROM policies WHERE id = $1")
+                    .bind(policy_id)
+                    .fetch_one(&pool)
+                    .await
+                    .unwrap();
+            organization_id
+        }
+    };
+    let own_org = organization_of(owned_policy).await;
+    let other_org = organization_of(other_policy).await;
+
+    assert!(matches!(
+        verify_policy_admin(&pool, owner, shared_policy, None).await,
+        Err(AppError::Validation(_))
+    ));
+    assert_eq!(
+        verify_policy_admin(&pool, owner, shared_policy, Some(own_org)).await.unwrap(),
+        own_org
+    );
+    assert!(matches!(
+        verify_policy_admin(&pool, owner, shared_policy, Some(other_org)).await,
+        Err(AppError::Forbidden)
+    ));
+}
+
+#[actix_web::test]
+#[ignore] // Requires Postgres at DATABASE_URL
+async fn test_only_owning_org_admins_edit_policies() {
+    let pool = database().await;
+    let (owner, policy_id) = seed_tenant(&pool).await;
+    let (viewer, _) = seed_tenant(&pool).await;
+
+    let (organization_id,): (Uuid,) = sqlx::query_as("SELECT organization_id FROM policies WHERE id = $1")
+        .bind(policy_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    seed_member(&pool, organization_id, viewer, "viewer").await;
+    let (shared_policy,): (Uuid,) = sqlx::query_as(
+        "INSERT INTO policies (name, policy_type) VALUES ($1, 'usage') RETURNING id",
+    )
+    .bind(format!("shared-{}", Uuid::new_v4()))
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+
+    assert_eq!(verify_policy_editor(&pool, owner, policy_id).await.unwrap().id, policy_id);
+    assert!(matches!(
+        verify_policy_editor(&pool, viewer, policy_id).await,
+        Err(AppError::Forbidden)
+    ));
+    assert!(matches!(
+        verify_policy_editor(&pool, owner, shared_policy).await,
+        Err(AppError::Forbidden)
+    ));
+}
+
+#[actix_web::test]
+#[ignore] // Requires Postgres at DATABASE_URL
+async fn test_team_and_user_assignments_require_an_org_admin() {
+    let pool = database().await;
+    let (owner, policy_id) = seed_tenant(&pool).await;
+    let (member, _) = seed_tenant(&pool).await;
+
+    let (organization_id,): (Uuid,) = sqlx::query_as("SELECT organization_id FROM policies WHERE id = $1")
+        .bind(policy_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    seed_member(&pool, organization_id, member, "member").await;
+    let team_id = seed_team(&pool, organization_id, "assignees").await;
+
+    let app = actix_web::test::init_service(
+        actix_web::App::new()
+            .app_data(web::Data::new(pool.clone()))
+            .service(assign_policy),
+    )
+    .await;
+    let cases = [
+        (member, serde_json::json!({ "team_id": team_id }), StatusCode::FORBIDDEN),
+        (member, serde_json::json!({ "user_id": member }), StatusCode::FORBIDDEN),
+        (owner, serde_json::json!({ "team_id": team_id }), StatusCode::OK),
+        (owner, serde_json::json!({ "user_id": member }), StatusCode::OK),
+    ];
+    for (caller, body, expected) in cases {
+        let req = actix_web::test::TestRequest::post()
+            .uri(&format!("/policies/{}/assign", policy_id))
+            .insert_header(("X-User-Id", caller.to_string()))
+            .set_json(&body)
+            .to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_eq!(resp.status(), expected, "{} assigning {}", caller, body);
+    }
+}
+
+#[actix_web::test]
+#[ignore] // Requires Postgres at DATABASE_URL
+async fn test_failing_bulk_item_rolls_back_the_batch() {
+    let pool = database().await;
+    let (owner, policy_id) = seed_tenant(&pool).await;
+    let (_, other_policy) = seed_tenant(&pool).await;
+
+    // The owner may assign their own policy to themselves, but cannot see
+    // the other organization's policy
+    let app = actix_web::test::init_service(
+        actix_web::App::new()
+            .app_data(web::Data::new(pool.clone()))
+            .service(bulk_assign_policies),
+    )
+    .await;
+    let req = actix_web::test::TestRequest::post()
+        .uri("/policies/assignments/bulk")
+        .insert_header(("X-User-Id", owner.to_string()))
+        .set_json(serde_json::json!({
+            "assignments": [
+                {"policy_id": policy_id, "user_id": owner},
+                {"policy_id": other_policy, "user_id": owner}
+            ]
+        }))
+        .to_request();
+    let resp = actix_web::test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let (assigned,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM policy_assignments WHERE policy_id = $1")
+        .bind(policy_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(assigned, 0, "the first item must be rolled back");
+}
+
+#[actix_web::test]
+#[ignore] // Requires Postgres at DATABASE_URL
+async fn test_evaluation_is_recorded_and_listed() {
+    let pool = database().await;
+    let (user_id, policy_id) = seed_tenant(&pool).await;
+
+    let mut stored = policy_lookup_query(policy_id, user_id, false)
+        .build_query_as::<PolicyResponse>()
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let organization_id = stored.organization_id.unwrap();
+    stored.policy_type = "cost".to_string();
+    stored.rules = serde_json::json!({"max_cost_per_request": 0.5});
+
+    let context = prepare_context(&serde_json::json!({"cost": 0.9}), None);
+    let result = evaluate_policy_rules(&stored, &context, false).unwrap();
+    record_evaluation(&pool, &stored, &context, &result, user_id).await.unwrap();
+
+    let (evaluations, total): (Vec<PolicyEvaluationRecord>, i64) = paginate(
+        &pool,
+        ordered_evaluations(policy_id, organization_id, None),
+        filtered_evaluations("SELECT COUNT(*) FROM policy_evaluations", policy_id, organization_id, None),
+        Page::new(None, None),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(total, 1);
+    let evaluation = &evaluations[0];
+    assert_eq!(evaluation.policy_version, stored.version);
+    assert_eq!(evaluation.context_hash, hash_inputs(&context));
+    assert!(!evaluation.passed);
+    assert_eq!(evaluation.violation_count, 1);
+    assert_eq!(evaluation.violations[0]["rule_violated"], result.violations[0].rule_violated);
+    assert_eq!(evaluation.subject_id, Some(user_id));
+
+    let passed: (Vec<PolicyEvaluationRecord>, i64) = paginate(
+        &pool,
+        ordered_evaluations(policy_id, organization_id, Some(true)),
+        filtered_evaluations("SELECT COUNT(*) FROM policy_evaluations", policy_id, organization_id, Some(true)),
+        Page::new(None, None),
+    )
+    .await
+    .unwrap();
+    assert_eq!(passed.1, 0);
+}
@@ -6,6 +6,9 @@ pub struct Config {
     pub port: u16,
     pub database_url: String,
     pub redis_url: String,
+    /// Reject organization settings keys that `OrganizationSettings` does not define
+    #[serde(default)]
+    pub strict_settings: bool,
 }
 
 impl Config {
@@ -21,6 +24,7 @@ impl Default for Config {
             port: 8082,
             database_url: String::new(),
             redis_url: "redis://127.0.0.1:6379".to_string(),
+            strict_settings: false,
         }
     }
 }
@@ -0,0 +1,357 @@
+//! Organization data export for data-subject requests
+//!
+//! The bundle is a single JSON object written section by section while rows
+//! are read from the database, so large organizations are never held in
+//! memory. Every section is limited to rows attributed to the organization;
+//! data members hold in other organizations is not exported.
+//!
+//! Credential material (API key hashes, provider keys) is only exported when
+//! it belongs to the requesting owner and is redacted otherwise. Password
+//! hashes and webhook subscriptions, whose secrets cannot be attributed to a
+//! single user, are never selected.
+
+use actix_web::{get, web, web::Bytes, HttpRequest, HttpResponse, Responder};
+use chrono::Utc;
+use futures::channel::mpsc;
+use futures::stream::BoxStream;
+use futures::{SinkExt, StreamExt};
+use serde_json::Value;
+use sqlx::PgPool;
+use uuid::Uuid;
+use llm_governance_common::{AppError, Result};
+
+use crate::handlers::organizations::verify_organization_role;
+
+/// Replacement for secrets that belong to someone other than the requester
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Bundle layout version, bumped when sections change shape
+const EXPORT_FORMAT_VERSION: u32 = 1;
+
+/// Fields holding credential material
+const SECRET_FIELDS: &[&str] = &["key_hash", "api_key_encrypted", "secret"];
+
+/// Fields naming the user a row belongs to
+const OWNER_FIELDS: &[&str] = &["user_id", "created_by"];
+
+/// Array sections in bundle order; each query yields one JSON document per
+/// row, with `$1` bound to the organization id
+pub const SECTIONS: &[(&str, &str)] = &[
+    (
+        "members",
+        r#"
+        SELECT to_jsonb(m) || jsonb_build_object(
+            'email', u.email, 'name', u.name, 'status', u.status, 'mfa_enabled', u.mfa_enabled
+        )
+        FROM organization_members m
+        JOIN users u ON u.id = m.user_id
+        WHERE m.organization_id = $1
+        ORDER BY m.joined_at
+        "#,
+    ),
+    (
+        "teams",
+        r#"
+        SELECT to_jsonb(t) || jsonb_build_object('members', COALESCE(
+            (SELECT jsonb_agg(jsonb_build_object('user_id', tm.user_id, 'role', tm.role, 'joined_at', tm.joined_at))
+             FROM team_members tm WHERE tm.team_id = t.id),
+            '[]'::jsonb
+        ))
+        FROM teams t
+        WHERE t.organization_id = $1
+        ORDER BY t.created_at
+        "#,
+    ),
+    (
+        "policies",
+        r#"
+        SELECT to_jsonb(p) || jsonb_build_object('assignments', (
+            SELECT jsonb_agg(jsonb_build_object('team_id', pa.team_id, 'user_id', pa.user_id, 'assigned_at', pa.assigned_at))
+            FROM policy_assignments pa
+            WHERE pa.policy_id = p.id
+            AND (
+                pa.team_id IN (SELECT id FROM teams WHERE organization_id = $1)
+                OR pa.user_id IN (SELECT user_id FROM organization_members WHERE organization_id = $1)
+            )
+        ))
+        FROM policies p
+        WHERE EXISTS (
+            SELECT 1 FROM policy_assignments pa
+            WHERE pa.policy_id = p.id
+            AND (
+                pa.team_id IN (SELECT id FROM teams WHERE organization_id = $1)
+                OR pa.user_id IN (SELECT user_id FROM organization_members WHERE organization_id = $1)
+            )
+        )
+        ORDER BY p.created_at
+        "#,
+    ),
+    (
+        "budgets",
+        "SELECT to_jsonb(b) FROM budgets b WHERE b.organization_id = $1 ORDER BY b.created_at",
+    ),
+    (
+        "providers",
+        "SELECT to_jsonb(p) FROM llm_providers p WHERE p.organization_id = $1 ORDER BY p.created_at",
+    ),
+    (
+        "api_keys",
+        r#"
+        SELECT to_jsonb(k)
+        FROM api_keys k
+        WHERE k.organization_id = $1
+        ORDER BY k.created_at
+        "#,
+    ),
+    (
+        "events",
+        r#"
+        SELECT jsonb_build_object(
+            'id', a.id, 'timestamp', a.timestamp, 'action', a.action,
+            'resource_type', a.resource_type, 'resource_id', a.resource_id, 'user_id', a.user_id
+        )
+        FROM audit_logs a
+        WHERE a.details->>'organization_id' = $1::text
+        ORDER BY a.timestamp
+        "#,
+    ),
+];
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+#[get("/organizations/{id}/export")]
+pub async fn export_organization(
+    pool: web::Data<PgPool>,
+    organization_id: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<impl Responder> {
+    let user_id = extract_user_id(&req)?;
+    let organization_id = *organization_id;
+    verify_organization_role(pool.get_ref(), organization_id, user_id, &["owner"]).await?;
+
+    let organization: Value = sqlx::query_scalar("SELECT to_jsonb(o) FROM organizations o WHERE o.id = $1")
+        .bind(organization_id)
+        .fetch_optional(pool.get_ref())
+        .await?
+        .ok_or_else(|| AppError::NotFound("Organization not found".to_string()))?;
+
+    tracing::info!("User {} exporting organization {}", user_id, organization_id);
+
+    let (tx, rx) = mpsc::channel(16);
+    let pool = pool.get_ref().clone();
+    actix_web::rt::spawn(async move {
+        let mut writer = BundleWriter::new(tx);
+        let sections = SECTIONS
+            .iter()
+            .map(|(name, sql)| {
+                let rows = sqlx::query_scalar::<_, Value>(sql)
+                    .bind(organization_id)
+                    .fetch(&pool)
+                    .map(|row| row.map_err(AppError::from))
+                    .boxed();
+                (*name, rows)
+            })
+            .collect();
+
+        if let Err(e) = write_bundle(&mut writer, organization, sections, user_id).await {
+            tracing::error!("Export of organization {} failed: {}", organization_id, e);
+            writer.abort(e).await;
+        }
+    });
+
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .insert_header((
+            "Content-Disposition",
+            format!("attachment; filename=organization-{}.json", organization_id),
+        ))
+        .streaming(rx))
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn extract_user_id(req: &HttpRequest) -> Result<Uuid> {
+    req.headers()
+        .get("X-User-Id")
+        .and_then(|h| h.to_str().ok())
+        .and_then(|s| Uuid::parse_str(s).ok())
+        .ok_or_else(|| AppError::Unauthorized)
+}
+
+/// Replace secret fields of a row not owned by `requester`
+fn redact_secrets(row: &mut Value, requester: Uuid) {
+    let Some(fields) = row.as_object_mut() else {
+        return;
+    };
+
+    let requester = requester.to_string();
+    let owned = OWNER_FIELDS
+        .iter()
+        .any(|f| fields.get(*f).and_then(Value::as_str) == Some(requester.as_str()));
+    if owned {
+        return;
+    }
+
+    for field in SECRET_FIELDS {
+        if let Some(value) = fields.get_mut(*field) {
+            if !value.is_null() {
+                *value = Value::String(REDACTED.to_string());
+            }
+        }
+    }
+}
+
+/// Writes one JSON object to the response channel a field at a time
+struct BundleWriter {
+    tx: mpsc::Sender<Result<Bytes>>,
+    fields_written: usize,
+}
+
+impl BundleWriter {
+    fn new(tx: mpsc::Sender<Result<Bytes>>) -> Self {
+        Self { tx, fields_written: 0 }
+    }
+
+    async fn send(&mut self, chunk: String) -> Result<()> {
+        self.tx
+            .send(Ok(Bytes::from(chunk)))
+            .await
+            .map_err(|_| AppError::Internal("export client disconnected".to_string()))
+    }
+
+    /// `{` before the first field, `,` between fields, then the quoted name
+    async fn key(&mut self, name: &str) -> Result<()> {
+        let separator = if self.fields_written == 0 { "{" } else { "," };
+        self.fields_written += 1;
+        self.send(format!("{}{}:", separator, Value::from(name))).await
+    }
+
+    async fn field(&mut self, name: &str, value: &Value) -> Result<()> {
+        self.key(name).await?;
+        self.send(value.to_string()).await
+    }
+
+    async fn array(
+        &mut self,
+        name: &str,
+        mut rows: BoxStream<'_, Result<Value>>,
+        requester: Uuid,
+    ) -> Result<usize> {
+        self.key(name).await?;
+        self.send("[".to_string()).await?;
+
+        let mut count = 0;
+        while let Some(row) = rows.next().await {
+            let mut row = row?;
+            redact_secrets(&mut row, requester);
+            let separator = if count == 0 { "" } else { "," };
+            self.send(format!("{}{}", separator, row)).await?;
+            count += 1;
+        }
+
+        self.send("]".to_string()).await?;
+        Ok(count)
+    }
+
+    async fn finish(&mut self) -> Result<()> {
+        self.send("}".to_string()).await
+    }
+
+    /// End the response with an error so the client sees a failed transfer
+    /// rather than truncated JSON
+    async fn abort(&mut self, error: AppError) {
+        let _ = self.tx.send(Err(error)).await;
+    }
+}
+
+async fn write_bundle(
+    writer: &mut BundleWriter,
+    organization: Value,
+    sections: Vec<(&str, BoxStream<'_, Result<Value>>)>,
+    requester: Uuid,
+) -> Result<()> {
+    writer.field("format_version", &Value::from(EXPORT_FORMAT_VERSION)).await?;
+    writer.field("exported_at", &Value::from(Utc::now().to_rfc3339())).await?;
+    writer.field("organization", &organization).await?;
+
+    for (name, rows) in sections {
+        writer.array(name, rows, requester).await?;
+    }
+
+    writer.finish().await
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(export_organization);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::stream;
+    use serde_json::json;
+
+    fn rows(values: Vec<Value>) -> BoxStream<'static, Result<Value>> {
+        stream::iter(values.into_iter().map(Ok)).boxed()
+    }
+
+    #[actix_web::test]
+    async fn test_bundle_contains_every_section_for_seeded_org() {
+        let org_id = Uuid::new_v4();
+        let owner = Uuid::new_v4();
+        let other = Uuid::new_v4();
+
+        let seeded: Vec<(&str, Vec<Value>)> = vec![
+            ("members", vec![json!({ "user_id": owner, "role": "owner" }), json!({ "user_id": other, "role": "member" })]),
+            ("teams", vec![json!({ "id": Uuid::new_v4(), "name": "research", "members": [] })]),
+            ("policies", vec![json!({ "id": Uuid::new_v4(), "name": "pii-filter" })]),
+            ("budgets", vec![json!({ "id": Uuid::new_v4(), "amount": "100.00" })]),
+            ("providers", vec![json!({ "provider_name": "openai", "api_key_encrypted": "encrypted_sk-live" })]),
+            ("api_keys", vec![
+                json!({ "user_id": owner, "name": "ci", "key_hash": "owner-hash" }),
+                json!({ "user_id": other, "name": "laptop", "key_hash": "other-hash" }),
+            ]),
+            ("events", vec![json!({ "id": Uuid::new_v4(), "action": "policy.create" })]),
+        ];
+        assert_eq!(
+            seeded.iter().map(|(name, _)| *name).collect::<Vec<_>>(),
+            SECTIONS.iter().map(|(name, _)| *name).collect::<Vec<_>>(),
+        );
+
+        let (tx, rx) = mpsc::channel(4);
+        let writer_task = async move {
+            let mut writer = BundleWriter::new(tx);
+            let sections = seeded.into_iter().map(|(name, values)| (name, rows(values))).collect();
+            write_bundle(&mut writer, json!({ "id": org_id, "name": "Acme" }), sections, owner).await
+        };
+        let (written, chunks) = futures::join!(writer_task, rx.collect::<Vec<_>>());
+        written.unwrap();
+
+        let body: Vec<u8> = chunks.into_iter().flat_map(|c| c.unwrap().to_vec()).collect();
+        let bundle: Value = serde_json::from_slice(&body).unwrap();
+
+        for section in ["format_version", "exported_at", "organization", "members", "teams", "policies", "budgets", "providers", "api_keys", "events"] {
+            assert!(bundle.get(section).is_some(), "missing section {}", section);
+        }
+        assert_eq!(bundle["organization"]["id"], org_id.to_string());
+        assert_eq!(bundle["members"].as_array().unwrap().len(), 2);
+
+        // Only the requester's own secrets survive
+        assert_eq!(bundle["api_keys"][0]["key_hash"], "owner-hash");
+        assert_eq!(bundle["api_keys"][1]["key_hash"], REDACTED);
+        assert_eq!(bundle["providers"][0]["api_key_encrypted"], REDACTED);
+    }
+
+    #[test]
+    fn test_redaction_leaves_null_and_non_secret_fields() {
+        let mut row = json!({ "user_id": Uuid::new_v4(), "name": "laptop", "secret": null });
+        redact_secrets(&mut row, Uuid::new_v4());
+
+        assert_eq!(row["name"], "laptop");
+        assert!(row["secret"].is_null());
+    }
+}
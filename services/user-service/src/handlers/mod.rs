@@ -1,13 +1,17 @@
 use actix_web::web;
 
+pub mod export;
 pub mod health;
 pub mod users;
 pub mod organizations;
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(llm_governance_common::metrics::metrics_endpoint);
+    cfg.service(llm_governance_common::versioning::version_endpoint);
     cfg.service(web::scope("/api/v1")
         .configure(health::configure)
         .configure(users::configure)
         .configure(organizations::configure)
+        .configure(export::configure)
     );
 }
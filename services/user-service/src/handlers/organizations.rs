@@ -4,8 +4,11 @@ use validator::Validate;
 use sqlx::PgPool;
 use uuid::Uuid;
 use llm_governance_common::{AppError, Result, ApiResponse};
+use llm_governance_common::query::DynamicQuery;
 use chrono::{DateTime, Utc};
 
+use crate::config::Config;
+
 // ============================================================================
 // Request/Response Types
 // ============================================================================
@@ -40,6 +43,34 @@ pub struct OrganizationResponse {
     pub updated_at: DateTime<Utc>,
 }
 
+impl OrganizationResponse {
+    /// Stored settings as a typed value
+    pub fn get_settings(&self) -> Result<OrganizationSettings> {
+        serde_json::from_value(self.settings.clone())
+            .map_err(|e| AppError::Internal(format!("Invalid stored organization settings: {}", e)))
+    }
+}
+
+/// Known keys of `organizations.settings`
+///
+/// Keys not listed here are kept in `extra` and persisted as-is unless
+/// `strict_settings` is enabled, in which case they are rejected.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OrganizationSettings {
+    pub default_provider: Option<String>,
+    pub default_model: Option<String>,
+    pub data_retention_days: Option<u32>,
+    /// Providers members may use; empty means no restriction
+    pub allowed_providers: Vec<String>,
+    /// Members without MFA cannot sign in or store provider API keys
+    pub require_mfa: bool,
+    /// Injected as the leading system message of proxied requests that lack one
+    pub system_prompt: Option<String>,
+    #[serde(flatten, skip_serializing_if = "serde_json::Map::is_empty")]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
 #[derive(Debug, Deserialize, Validate)]
 pub struct AddMemberRequest {
     pub user_id: Uuid,
@@ -130,11 +161,15 @@ pub async fn get_organization(
 #[post("/organizations")]
 pub async fn create_organization(
     pool: web::Data<PgPool>,
+    config: web::Data<Config>,
     req_body: web::Json<CreateOrganizationRequest>,
     req: HttpRequest,
 ) -> Result<impl Responder> {
-    req_body.validate()
-        .map_err(|e| AppError::Validation(format!("{}", e)))?;
+    req_body.validate()?;
+
+    if let Some(ref settings) = req_body.settings {
+        validate_settings(settings, config.strict_settings)?;
+    }
 
     let user_id = extract_user_id(&req)?;
 
@@ -176,63 +211,45 @@ pub async fn create_organization(
 #[put("/organizations/{id}")]
 pub async fn update_organization(
     pool: web::Data<PgPool>,
+    config: web::Data<Config>,
     organization_id: web::Path<Uuid>,
     req_body: web::Json<UpdateOrganizationRequest>,
     req: HttpRequest,
 ) -> Result<impl Responder> {
     let user_id = extract_user_id(&req)?;
 
+    if let Some(ref settings) = req_body.settings {
+        validate_settings(settings, config.strict_settings)?;
+    }
+
     // Verify user has admin role
     verify_organization_role(pool.get_ref(), *organization_id, user_id, &["owner", "admin"]).await?;
 
-    // Build dynamic update query
-    let mut updates = vec![];
-    let mut query_params: Vec<String> = vec![];
-    let mut param_index = 1;
-
+    let mut update = DynamicQuery::new("UPDATE organizations");
     if let Some(ref name) = req_body.name {
-        updates.push(format!("name = ${}", param_index));
-        query_params.push(name.clone());
-        param_index += 1;
+        update.set("name", name.clone());
     }
-
     if let Some(ref description) = req_body.description {
-        updates.push(format!("description = ${}", param_index));
-        query_params.push(description.clone());
-        param_index += 1;
+        update.set("description", description.clone());
     }
-
     if let Some(ref settings) = req_body.settings {
-        updates.push(format!("settings = ${}::jsonb", param_index));
-        query_params.push(serde_json::to_string(settings).unwrap());
-        param_index += 1;
+        update.set("settings", settings.clone());
     }
-
     if let Some(is_active) = req_body.is_active {
-        updates.push(format!("is_active = ${}", param_index));
-        query_params.push(is_active.to_string());
-        param_index += 1;
+        update.set("is_active", is_active);
     }
 
-    if updates.is_empty() {
+    if !update.has_assignments() {
         return Err(AppError::Validation("No fields to update".to_string()));
     }
 
-    updates.push("updated_at = NOW()".to_string());
+    update
+        .set_raw("updated_at = NOW()")
+        .filter("id", *organization_id)
+        .push(" RETURNING id, name, slug, description, settings, is_active, created_at, updated_at");
 
-    let query = format!(
-        "UPDATE organizations SET {} WHERE id = ${} RETURNING id, name, slug, description, settings, is_active, created_at, updated_at",
-        updates.join(", "),
-        param_index
-    );
-
-    let mut query_builder = sqlx::query_as::<_, OrganizationResponse>(&query);
-    for param in query_params {
-        query_builder = query_builder.bind(param);
-    }
-    query_builder = query_builder.bind(*organization_id);
-
-    let organization = query_builder
+    let organization = update
+        .build_query_as::<OrganizationResponse>()
         .fetch_one(pool.get_ref())
         .await?;
 
@@ -441,6 +458,54 @@ fn extract_user_id(req: &HttpRequest) -> Result<Uuid> {
         .ok_or_else(|| AppError::Unauthorized)
 }
 
+/// Longest organization system prompt accepted
+const MAX_SYSTEM_PROMPT_CHARS: usize = 8000;
+
+/// Check incoming settings against `OrganizationSettings`
+fn validate_settings(settings: &serde_json::Value, strict: bool) -> Result<OrganizationSettings> {
+    if !settings.is_object() {
+        return Err(AppError::Validation("settings must be a JSON object".to_string()));
+    }
+
+    let parsed: OrganizationSettings = serde_json::from_value(settings.clone())
+        .map_err(|e| AppError::Validation(format!("Invalid settings: {}", e)))?;
+
+    if strict && !parsed.extra.is_empty() {
+        let mut unknown: Vec<&str> = parsed.extra.keys().map(String::as_str).collect();
+        unknown.sort_unstable();
+        return Err(AppError::Validation(format!(
+            "Unknown settings keys: {}",
+            unknown.join(", ")
+        )));
+    }
+
+    if parsed.data_retention_days == Some(0) {
+        return Err(AppError::Validation(
+            "data_retention_days must be at least 1".to_string(),
+        ));
+    }
+
+    if let Some(ref prompt) = parsed.system_prompt {
+        if prompt.trim().is_empty() || prompt.chars().count() > MAX_SYSTEM_PROMPT_CHARS {
+            return Err(AppError::Validation(format!(
+                "system_prompt must be 1-{} characters",
+                MAX_SYSTEM_PROMPT_CHARS
+            )));
+        }
+    }
+
+    if let Some(ref provider) = parsed.default_provider {
+        if !parsed.allowed_providers.is_empty() && !parsed.allowed_providers.contains(provider) {
+            return Err(AppError::Validation(format!(
+                "default_provider '{}' is not in allowed_providers",
+                provider
+            )));
+        }
+    }
+
+    Ok(parsed)
+}
+
 async fn verify_organization_member(
     pool: &PgPool,
     organization_id: Uuid,
@@ -461,7 +526,7 @@ async fn verify_organization_member(
     Ok(())
 }
 
-async fn verify_organization_role(
+pub(crate) async fn verify_organization_role(
     pool: &PgPool,
     organization_id: Uuid,
     user_id: Uuid,
@@ -495,3 +560,79 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
         .service(create_team)
         .service(delete_team);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_request_reports_each_invalid_field() {
+        let req = CreateOrganizationRequest {
+            name: String::new(),
+            slug: String::new(),
+            description: None,
+            settings: None,
+        };
+
+        let err = AppError::from(req.validate().unwrap_err());
+        let AppError::FieldValidation(fields) = err else {
+            panic!("expected per-field errors, got {:?}", err);
+        };
+        assert_eq!(fields["name"], vec!["length must be between 1 and 255".to_string()]);
+        assert!(fields["slug"].contains(&"length must be between 1 and 100".to_string()));
+    }
+
+    #[test]
+    fn test_valid_settings_are_typed() {
+        let settings = serde_json::json!({
+            "default_provider": "openai",
+            "default_model": "gpt-4",
+            "data_retention_days": 90,
+            "allowed_providers": ["openai", "anthropic"],
+            "require_mfa": true
+        });
+
+        let parsed = validate_settings(&settings, true).unwrap();
+        assert_eq!(parsed.default_model.as_deref(), Some("gpt-4"));
+        assert_eq!(parsed.data_retention_days, Some(90));
+        assert!(parsed.require_mfa);
+        assert!(parsed.extra.is_empty());
+
+        let org = OrganizationResponse {
+            id: Uuid::new_v4(),
+            name: "Acme".to_string(),
+            slug: "acme".to_string(),
+            description: None,
+            settings,
+            is_active: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        assert_eq!(org.get_settings().unwrap(), parsed);
+    }
+
+    #[test]
+    fn test_unknown_key_rejected_in_strict_mode() {
+        let settings = serde_json::json!({"defaultModal": "gpt-4"});
+
+        let err = validate_settings(&settings, true).unwrap_err();
+        assert!(matches!(err, AppError::Validation(ref msg) if msg.contains("defaultModal")));
+
+        // Lenient mode keeps the key for backwards compatibility
+        let parsed = validate_settings(&settings, false).unwrap();
+        assert!(parsed.extra.contains_key("defaultModal"));
+    }
+
+    #[test]
+    fn test_mistyped_settings_rejected() {
+        assert!(validate_settings(&serde_json::json!({"data_retention_days": "forever"}), false).is_err());
+        assert!(validate_settings(&serde_json::json!({"data_retention_days": 0}), false).is_err());
+        assert!(validate_settings(&serde_json::json!([]), false).is_err());
+        assert!(validate_settings(&serde_json::json!({"system_prompt": "  "}), false).is_err());
+        assert!(validate_settings(
+            &serde_json::json!({"default_provider": "cohere", "allowed_providers": ["openai"]}),
+            false
+        )
+        .is_err());
+    }
+}
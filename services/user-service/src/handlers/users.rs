@@ -4,6 +4,7 @@ use validator::Validate;
 use sqlx::PgPool;
 use uuid::Uuid;
 use llm_governance_common::{AppError, Result, ApiResponse};
+use llm_governance_common::db::Page;
 
 #[derive(Debug, Deserialize, Validate)]
 pub struct CreateUserRequest {
@@ -50,25 +51,39 @@ pub struct RoleInfo {
 #[get("/users")]
 pub async fn list_users(
     pool: web::Data<PgPool>,
-    query: web::Query<PaginationQuery>,
+    query: web::Query<UserListQuery>,
+    http_req: HttpRequest,
 ) -> Result<impl Responder> {
-    let limit = query.limit.unwrap_or(20).min(100);
-    let offset = query.offset.unwrap_or(0);
+    let current_user_id = extract_user_id(&http_req)?;
+    let admin_orgs = admin_organizations(pool.get_ref(), current_user_id).await?;
+    let organizations = listable_organizations(&admin_orgs, query.organization_id)?;
+
+    let Page { limit, offset } = Page::new(query.limit, query.offset);
+    let pattern = search_pattern(query.q.as_deref());
 
-    let users = sqlx::query_as::<_, UserResponse>(
+    let users = sqlx::query_as::<_, UserResponse>(&format!(
         r#"
-        SELECT id, email, name, status, mfa_enabled, created_at, updated_at
-        FROM users
-        ORDER BY created_at DESC
-        LIMIT $1 OFFSET $2
+        SELECT u.id, u.email, u.name, u.status, u.mfa_enabled, u.created_at, u.updated_at
+        FROM users u
+        WHERE {}
+        ORDER BY u.created_at DESC
+        LIMIT $4 OFFSET $5
         "#,
-    )
+        USER_LIST_FILTER
+    ))
+    .bind(pattern.as_deref())
+    .bind(&organizations)
+    .bind(query.is_active)
     .bind(limit as i64)
     .bind(offset as i64)
     .fetch_all(pool.get_ref())
     .await?;
 
-    let total: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM users")
+    // Same filters as the page so total reflects the search, not the whole table
+    let total: (i64,) = sqlx::query_as(&format!("SELECT COUNT(*) FROM users u WHERE {}", USER_LIST_FILTER))
+        .bind(pattern.as_deref())
+        .bind(&organizations)
+        .bind(query.is_active)
         .fetch_one(pool.get_ref())
         .await?;
 
@@ -268,6 +283,192 @@ pub async fn delete_user(
     )))
 }
 
+#[post("/users/{id}/deactivate")]
+pub async fn deactivate_user(
+    pool: web::Data<PgPool>,
+    user_id: web::Path<Uuid>,
+    http_req: HttpRequest,
+) -> Result<impl Responder> {
+    let current_user_id = extract_user_id(&http_req)?;
+    check_permission(pool.get_ref(), current_user_id, "users:update").await?;
+
+    if current_user_id == *user_id {
+        return Err(AppError::BadRequest("Cannot deactivate your own account".to_string()));
+    }
+
+    let mut tx = pool.begin().await?;
+
+    // Bumping token_version invalidates every access token already issued
+    let user = sqlx::query_as::<_, UserResponse>(
+        r#"
+        UPDATE users
+        SET status = 'inactive', token_version = token_version + 1
+        WHERE id = $1
+        RETURNING id, email, name, status, mfa_enabled, created_at, updated_at
+        "#,
+    )
+    .bind(user_id.as_ref())
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+    // Refresh tokens are stored as sessions
+    let revoked = sqlx::query("DELETE FROM sessions WHERE user_id = $1")
+        .bind(user_id.as_ref())
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+    tx.commit().await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
+        "user": user,
+        "sessions_revoked": revoked
+    }))))
+}
+
+/// Anonymize a user for a right-to-be-forgotten request
+///
+/// Email and name are replaced with tombstones derived from the user id,
+/// credentials are removed and every outstanding token is revoked. OAuth
+/// sign-in resolves accounts by email, so the tombstone also detaches any
+/// external identity. Audit rows keep referencing the user by id, but the
+/// email typed at failed logins and client IPs are pseudonymized in place,
+/// with checksums recomputed so integrity verification still passes.
+#[post("/users/{id}/erase")]
+pub async fn erase_user(
+    pool: web::Data<PgPool>,
+    user_id: web::Path<Uuid>,
+    http_req: HttpRequest,
+) -> Result<impl Responder> {
+    let current_user_id = extract_user_id(&http_req)?;
+    check_permission(pool.get_ref(), current_user_id, "users:delete").await?;
+
+    if current_user_id == *user_id {
+        return Err(AppError::BadRequest("Cannot erase your own account".to_string()));
+    }
+
+    let identity = ErasedIdentity::for_user(*user_id);
+    let erasure = erase_personal_data(pool.get_ref(), *user_id, current_user_id, &identity).await?;
+
+    tracing::info!("User {} erased by {}", user_id, current_user_id);
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
+        "user_id": *user_id,
+        "pseudonym": identity.pseudonym,
+        "removed": erasure.removed,
+        "audit_logs_pseudonymized": erasure.audit_logs_pseudonymized
+    }))))
+}
+
+/// What an erasure changed
+#[derive(Debug)]
+pub struct Erasure {
+    /// Rows deleted per `ERASURE_CLEANUP` table
+    pub removed: serde_json::Map<String, serde_json::Value>,
+    pub audit_logs_pseudonymized: u64,
+}
+
+/// Tombstone the user, delete their credentials and pseudonymize their audit
+/// rows in one transaction, recording the erasure as performed by `actor_id`
+pub async fn erase_personal_data(
+    pool: &PgPool,
+    user_id: Uuid,
+    actor_id: Uuid,
+    identity: &ErasedIdentity,
+) -> Result<Erasure> {
+    let mut tx = pool.begin().await?;
+
+    let (email,): (String,) = sqlx::query_as("SELECT email FROM users WHERE id = $1 FOR UPDATE")
+        .bind(user_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+    sqlx::query(
+        r#"
+        UPDATE users
+        SET email = $2,
+            name = $3,
+            password_hash = '!',
+            status = 'inactive',
+            mfa_enabled = false,
+            token_version = token_version + 1
+        WHERE id = $1
+        "#,
+    )
+    .bind(user_id)
+    .bind(&identity.email)
+    .bind(&identity.name)
+    .execute(&mut *tx)
+    .await?;
+
+    let mut removed = serde_json::Map::new();
+    for (table, statement) in ERASURE_CLEANUP {
+        let rows = sqlx::query(statement)
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        removed.insert(table.to_string(), rows.into());
+    }
+
+    // Audit rows can only be rewritten through this function, and only once
+    // the user is tombstoned; see migration 0027
+    let (pseudonymized,): (i64,) = sqlx::query_as("SELECT pseudonymize_audit_logs($1, $2, $3)")
+        .bind(user_id)
+        .bind(&email)
+        .bind(&identity.email)
+        .fetch_one(&mut *tx)
+        .await?;
+    let audit_logs_pseudonymized = pseudonymized as u64;
+
+    sqlx::query(
+        r#"
+        INSERT INTO audit_logs (user_id, action, resource_type, resource_id, details, checksum)
+        VALUES ($1, 'user.erase', 'user', $2, $3, '')
+        "#,
+    )
+    .bind(actor_id)
+    .bind(user_id.to_string())
+    .bind(identity.audit_details(&removed, audit_logs_pseudonymized))
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Erasure {
+        removed,
+        audit_logs_pseudonymized,
+    })
+}
+
+#[post("/users/{id}/reactivate")]
+pub async fn reactivate_user(
+    pool: web::Data<PgPool>,
+    user_id: web::Path<Uuid>,
+    http_req: HttpRequest,
+) -> Result<impl Responder> {
+    let current_user_id = extract_user_id(&http_req)?;
+    check_permission(pool.get_ref(), current_user_id, "users:update").await?;
+
+    // token_version is left as-is so tokens revoked on deactivation stay revoked
+    let user = sqlx::query_as::<_, UserResponse>(
+        r#"
+        UPDATE users
+        SET status = 'active'
+        WHERE id = $1 AND status = 'inactive'
+        RETURNING id, email, name, status, mfa_enabled, created_at, updated_at
+        "#,
+    )
+    .bind(user_id.as_ref())
+    .fetch_optional(pool.get_ref())
+    .await?
+    .ok_or_else(|| AppError::NotFound("Inactive user not found".to_string()))?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(user)))
+}
+
 #[get("/users/{id}/permissions")]
 pub async fn get_user_permissions(
     pool: web::Data<PgPool>,
@@ -331,12 +532,107 @@ pub async fn revoke_role(
 
 // Helper functions
 
+/// Credentials and tokens deleted on erasure, by table; `$1` is the user id.
+/// `audit_logs` is deliberately absent: audit rows are pseudonymized by
+/// `pseudonymize_audit_logs`, never deleted.
+const ERASURE_CLEANUP: &[(&str, &str)] = &[
+    ("sessions", "DELETE FROM sessions WHERE user_id = $1"),
+    ("api_keys", "DELETE FROM api_keys WHERE user_id = $1"),
+    ("mfa_secrets", "DELETE FROM mfa_secrets WHERE user_id = $1"),
+];
+
+/// Tombstone values replacing an erased user's personal data
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErasedIdentity {
+    /// Stable handle for the erased user, derived from the user id only
+    pub pseudonym: String,
+    pub email: String,
+    pub name: String,
+}
+
+impl ErasedIdentity {
+    pub fn for_user(user_id: Uuid) -> Self {
+        let pseudonym = format!("erased-{}", user_id.simple());
+        Self {
+            // Unique per user so the email constraint holds; `.invalid` never resolves
+            email: format!("{}@erased.invalid", pseudonym),
+            name: "Erased user".to_string(),
+            pseudonym,
+        }
+    }
+
+    /// Details recorded on the erasure audit entry; no personal data
+    fn audit_details(
+        &self,
+        removed: &serde_json::Map<String, serde_json::Value>,
+        audit_logs_pseudonymized: u64,
+    ) -> serde_json::Value {
+        serde_json::json!({
+            "pseudonym": self.pseudonym,
+            "removed": removed,
+            "audit_logs_pseudonymized": audit_logs_pseudonymized,
+        })
+    }
+}
+
 #[derive(Debug, Deserialize)]
-pub struct PaginationQuery {
+pub struct UserListQuery {
+    /// Case-insensitive substring of email or name
+    pub q: Option<String>,
+    pub organization_id: Option<Uuid>,
+    pub is_active: Option<bool>,
     pub limit: Option<u32>,
     pub offset: Option<u32>,
 }
 
+/// Filters for the user listing; $1 = search pattern, $2 = organization,
+/// $3 = active flag. Each is skipped when bound as NULL.
+const USER_LIST_FILTER: &str = r#"
+    ($1::text IS NULL OR u.email ILIKE $1 ESCAPE '\' OR u.name ILIKE $1 ESCAPE '\')
+    AND EXISTS (
+        SELECT 1 FROM organization_members om
+        WHERE om.user_id = u.id AND om.organization_id = ANY($2)
+    )
+    AND ($3::boolean IS NULL OR (u.status = 'active') = $3)
+"#;
+
+/// Organizations the user is an owner or admin of
+async fn admin_organizations(pool: &PgPool, user_id: Uuid) -> Result<Vec<Uuid>> {
+    let rows: Vec<(Uuid,)> = sqlx::query_as(
+        "SELECT organization_id FROM organization_members WHERE user_id = $1 AND role IN ('owner', 'admin')"
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().map(|(id,)| id).collect())
+}
+
+/// Organizations whose members an admin may list, narrowed to `requested`
+/// when given; `Forbidden` when the caller administers none of them
+fn listable_organizations(admin_orgs: &[Uuid], requested: Option<Uuid>) -> Result<Vec<Uuid>> {
+    let organizations: Vec<Uuid> = match requested {
+        Some(org) => admin_orgs.iter().copied().filter(|id| *id == org).collect(),
+        None => admin_orgs.to_vec(),
+    };
+
+    if organizations.is_empty() {
+        return Err(AppError::Forbidden);
+    }
+
+    Ok(organizations)
+}
+
+/// ILIKE pattern matching `q` anywhere, with wildcards in `q` taken literally
+fn search_pattern(q: Option<&str>) -> Option<String> {
+    let q = q.map(str::trim).filter(|q| !q.is_empty())?;
+    let escaped = q
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    Some(format!("%{}%", escaped))
+}
+
 async fn aggregate_permissions(pool: &PgPool, user_id: &Uuid) -> Result<serde_json::Value> {
     // Get all roles for user (including inherited roles)
     let role_permissions: Vec<(serde_json::Value,)> = sqlx::query_as(
@@ -426,7 +722,74 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
         .service(create_user)
         .service(update_user)
         .service(delete_user)
+        .service(deactivate_user)
+        .service(reactivate_user)
+        .service(erase_user)
         .service(get_user_permissions)
         .service(assign_role)
         .service(revoke_role);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_pattern_matches_substring_literally() {
+        assert_eq!(search_pattern(Some("alice")).as_deref(), Some("%alice%"));
+        assert_eq!(search_pattern(Some("  ")), None);
+        assert_eq!(search_pattern(None), None);
+
+        // Wildcards typed by the caller must not widen the match
+        assert_eq!(search_pattern(Some("50%_off")).as_deref(), Some("%50\\%\\_off%"));
+    }
+
+    #[test]
+    fn test_user_listing_limited_to_administered_organizations() {
+        let admin_of = Uuid::new_v4();
+        let other = Uuid::new_v4();
+
+        assert_eq!(listable_organizations(&[admin_of], None).unwrap(), vec![admin_of]);
+        assert_eq!(listable_organizations(&[admin_of], Some(admin_of)).unwrap(), vec![admin_of]);
+
+        // Members without an admin role, and orgs the caller doesn't administer, are refused
+        assert!(matches!(listable_organizations(&[], None), Err(AppError::Forbidden)));
+        assert!(matches!(listable_organizations(&[admin_of], Some(other)), Err(AppError::Forbidden)));
+    }
+
+    #[test]
+    fn test_user_listing_omits_sensitive_fields() {
+        let user = UserResponse {
+            id: Uuid::new_v4(),
+            email: "alice@example.com".to_string(),
+            name: "Alice".to_string(),
+            status: "active".to_string(),
+            mfa_enabled: true,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        };
+
+        let json = serde_json::to_value(&user).unwrap();
+        let fields = json.as_object().unwrap();
+        assert!(fields.contains_key("email"));
+        assert!(!fields.contains_key("password_hash"));
+        assert!(!fields.contains_key("mfa_secret"));
+    }
+
+    #[test]
+    fn test_erasure_scrubs_pii() {
+        let user_id = Uuid::new_v4();
+        let identity = ErasedIdentity::for_user(user_id);
+
+        assert_eq!(identity, ErasedIdentity::for_user(user_id), "pseudonym must be stable");
+        assert_ne!(identity.email, ErasedIdentity::for_user(Uuid::new_v4()).email);
+        assert!(identity.email.ends_with("@erased.invalid"));
+        assert_eq!(identity.name, "Erased user");
+
+        let mut removed = serde_json::Map::new();
+        removed.insert("sessions".to_string(), 2.into());
+        let details = identity.audit_details(&removed, 3).to_string();
+        assert!(!details.contains("alice"));
+        assert!(!details.contains('@'));
+    }
+}
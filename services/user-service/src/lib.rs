@@ -0,0 +1,7 @@
+//! User, organization and team management, served by `main.rs`
+
+pub mod config;
+pub mod handlers;
+pub mod middleware;
+pub mod models;
+pub mod services;
@@ -0,0 +1,55 @@
+use serde_json::Value;
+
+use llm_governance_common::testing::{database, seed_member, seed_organization, seed_user};
+use user_service::handlers::export::SECTIONS;
+
+#[actix_web::test]
+#[ignore] // Requires Postgres at DATABASE_URL
+async fn test_events_and_api_keys_limited_to_exported_organization() {
+    let pool = database().await;
+    let user_id = seed_user(&pool, "export").await;
+
+    // The same member belongs to two organizations
+    let mut orgs = Vec::new();
+    for label in ["exported", "other"] {
+        let org_id = seed_organization(&pool, label).await;
+        seed_member(&pool, org_id, user_id, "member").await;
+        sqlx::query("INSERT INTO api_keys (user_id, organization_id, key_hash, name) VALUES ($1, $2, $3, $4)")
+            .bind(user_id)
+            .bind(org_id)
+            .bind(format!("hash-{}", org_id))
+            .bind(label)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO audit_logs (user_id, action, resource_type, resource_id, details, checksum) \
+             VALUES ($1, $2, 'organization', $3, $4, '')",
+        )
+        .bind(user_id)
+        .bind(format!("{}.viewed", label))
+        .bind(org_id.to_string())
+        .bind(serde_json::json!({ "organization_id": org_id }))
+        .execute(&pool)
+        .await
+        .unwrap();
+        orgs.push(org_id);
+    }
+
+    let section = |name: &str| SECTIONS.iter().find(|(n, _)| *n == name).unwrap().1;
+    let keys: Vec<Value> = sqlx::query_scalar(section("api_keys"))
+        .bind(orgs[0])
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+    assert_eq!(keys.len(), 1);
+    assert_eq!(keys[0]["name"], "exported");
+
+    let events: Vec<Value> = sqlx::query_scalar(section("events"))
+        .bind(orgs[0])
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["action"], "exported.viewed");
+}
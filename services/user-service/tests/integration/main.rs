@@ -0,0 +1,6 @@
+//! User service tests against the migrated database at `DATABASE_URL`
+//!
+//! Run with `cargo test -p user-service --test integration -- --ignored`.
+
+mod export;
+mod users;
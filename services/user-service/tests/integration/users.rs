@@ -0,0 +1,92 @@
+use uuid::Uuid;
+
+use llm_governance_common::testing::database;
+use user_service::handlers::users::{erase_personal_data, ErasedIdentity};
+
+#[actix_web::test]
+#[ignore] // Requires Postgres at DATABASE_URL
+async fn test_erasure_pseudonymizes_audit_rows_and_keeps_checksums_valid() {
+    let pool = database().await;
+    let suffix = Uuid::new_v4();
+
+    let insert_user = |email: String| {
+        sqlx::query_as::<_, (Uuid,)>(
+            "INSERT INTO users (email, name, password_hash) VALUES ($1, 'Alice', 'x') RETURNING id",
+        )
+        .bind(email)
+        .fetch_one(&pool)
+    };
+    let email = format!("Alice-{}@example.com", suffix);
+    let (user_id,) = insert_user(email.clone()).await.unwrap();
+    let (admin_id,) = insert_user(format!("admin-{}@example.com", suffix)).await.unwrap();
+
+    let insert_audit = |user_id: Option<Uuid>, details: serde_json::Value| {
+        sqlx::query_as::<_, (Uuid,)>(
+            "INSERT INTO audit_logs (user_id, action, resource_type, resource_id, ip_address, details, checksum) \
+             VALUES ($1, 'login', 'auth', 'unknown', '203.0.113.7'::inet, $2, '') RETURNING id",
+        )
+        .bind(user_id)
+        .bind(details)
+        .fetch_one(&pool)
+    };
+    // A failed login attributed by typed email only, differently cased
+    let typed = serde_json::json!({ "email": email.to_lowercase(), "reason": "bad password" });
+    let (failed_login,) = insert_audit(None, typed).await.unwrap();
+    let (own_action,) = insert_audit(Some(user_id), serde_json::json!({ "mfa": false })).await.unwrap();
+    let (unrelated,) = insert_audit(None, serde_json::json!({ "email": format!("bob-{}@example.com", suffix) }))
+        .await
+        .unwrap();
+
+    let identity = ErasedIdentity::for_user(user_id);
+    let erasure = erase_personal_data(&pool, user_id, admin_id, &identity).await.unwrap();
+    assert_eq!(erasure.audit_logs_pseudonymized, 2);
+
+    let row = |id: Uuid| {
+        sqlx::query_as::<_, (Option<String>, serde_json::Value, bool)>(
+            "SELECT host(ip_address), details, \
+             checksum = generate_audit_checksum(timestamp, user_id, action, resource_type, resource_id, details) \
+             FROM audit_logs WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_one(&pool)
+    };
+
+    let (ip, details, checksum_valid) = row(failed_login).await.unwrap();
+    assert_eq!(ip, None);
+    assert_eq!(details["email"], identity.email);
+    assert_eq!(details["reason"], "bad password");
+    assert!(checksum_valid);
+
+    let (ip, _, checksum_valid) = row(own_action).await.unwrap();
+    assert_eq!(ip, None);
+    assert!(checksum_valid);
+
+    let (ip, _, checksum_valid) = row(unrelated).await.unwrap();
+    assert_eq!(ip.as_deref(), Some("203.0.113.7"));
+    assert!(checksum_valid);
+
+    // Outside the erasure function audit rows stay immutable, whatever the
+    // session sets
+    let mut conn = pool.acquire().await.unwrap();
+    sqlx::query("SET audit.erasure = 'on'").execute(&mut *conn).await.unwrap();
+    let err = sqlx::query("UPDATE audit_logs SET details = '{}' WHERE id = $1")
+        .bind(unrelated)
+        .execute(&mut *conn)
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("immutable") || err.to_string().contains("permission denied"),
+        "{}",
+        err
+    );
+
+    // The function refuses users that have not been tombstoned
+    let err = sqlx::query("SELECT pseudonymize_audit_logs($1, $2, $3)")
+        .bind(admin_id)
+        .bind(format!("admin-{}@example.com", suffix))
+        .bind(&identity.email)
+        .execute(&pool)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("has not been erased"), "{}", err);
+}